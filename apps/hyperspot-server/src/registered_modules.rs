@@ -25,17 +25,41 @@ use single_tenant_tr_plugin as _;
 #[cfg(feature = "static-tenants")]
 use static_tr_plugin as _;
 
+#[cfg(feature = "db-tenants")]
+use db_tr_plugin as _;
+
+#[cfg(feature = "introspection-authn")]
+use introspection_authn_plugin as _;
+#[cfg(feature = "jwt-authn")]
+use jwt_authn_plugin as _;
 #[cfg(feature = "static-authn")]
 use static_authn_plugin as _;
 
+#[cfg(feature = "policy-authz")]
+use policy_authz_plugin as _;
 #[cfg(feature = "static-authz")]
 use static_authz_plugin as _;
 
+#[cfg(any(feature = "static-license", feature = "signed-license"))]
+use license_resolver as _;
+#[cfg(feature = "signed-license")]
+use signed_license_plugin as _;
+#[cfg(feature = "static-license")]
+use static_license_plugin as _;
+
+#[cfg(feature = "db-credstore")]
+use db_credstore_plugin as _;
 #[cfg(feature = "static-credstore")]
 use static_credstore_plugin as _;
 
 // === Optional Modules ===
 
+#[cfg(feature = "events-broker")]
+use events_broker as _;
+
+#[cfg(feature = "audit")]
+use audit as _;
+
 #[cfg(feature = "mini-chat")]
 use mini_chat as _;
 
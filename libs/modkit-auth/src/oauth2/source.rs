@@ -29,6 +29,7 @@ pub struct OAuthTokenSource {
     scopes: Option<String>,
     auth_method: ClientAuthMethod,
     extra_headers: Vec<(String, String)>,
+    extra_params: Vec<(String, String)>,
     default_ttl: Duration,
     refresh_offset: Duration,
     min_refresh_period: Duration,
@@ -74,6 +75,7 @@ impl OAuthTokenSource {
             scopes,
             auth_method: config.auth_method,
             extra_headers: config.extra_headers.clone(),
+            extra_params: config.extra_params.clone(),
             default_ttl: config.default_ttl,
             refresh_offset: config.refresh_offset,
             min_refresh_period: config.min_refresh_period,
@@ -102,6 +104,10 @@ impl AsyncTokenSource for OAuthTokenSource {
             fields.push(("client_secret", &secret_expose));
         }
 
+        for (name, value) in &self.extra_params {
+            fields.push((name, value));
+        }
+
         // -- build request -------------------------------------------------------
         let mut builder = self.client.post(self.token_endpoint.as_str());
 
@@ -457,6 +463,26 @@ mod tests {
         mock.assert();
     }
 
+    #[tokio::test]
+    async fn extra_params_are_sent_in_form_body() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/token")
+                .body_includes("audience=https%3A%2F%2Fapi.example.com");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"access_token":"tok"}"#);
+        });
+
+        let mut cfg = test_config(&server);
+        cfg.extra_params = vec![("audience".into(), "https://api.example.com".into())];
+        let mut source = OAuthTokenSource::new(&cfg).unwrap();
+        source.request_token().await.unwrap();
+        mock.assert();
+    }
+
     #[tokio::test]
     async fn http_error_mapped_via_format_http_error() {
         let server = MockServer::start();
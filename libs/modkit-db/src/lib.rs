@@ -79,6 +79,7 @@ pub mod manager;
 pub mod migration_runner;
 pub mod odata;
 pub mod options;
+pub mod outbox;
 
 pub mod secure;
 
@@ -204,6 +205,10 @@ pub enum DbError {
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 
+    /// An outbox row was acknowledged by a worker that no longer holds its lease.
+    #[error("Outbox message {0} is no longer leased to this worker")]
+    OutboxLeaseLost(uuid::Uuid),
+
     /// Attempted to create a non-transactional connection inside an active transaction.
     ///
     /// This error occurs when `Db::conn()` is called from within a transaction closure.
@@ -0,0 +1,125 @@
+//! Migration creating `modkit_outbox_events` (idempotent).
+
+use sea_orm::{ConnectionTrait, DatabaseBackend};
+use sea_orm_migration::prelude::{
+    Alias, ColumnDef, DbErr, Index, MigrationName, MigrationTrait, SchemaManager, Table,
+};
+
+use super::TABLE;
+
+const CLAIM_INDEX: &str = "idx_modkit_outbox_events_claim";
+const LEASE_INDEX: &str = "idx_modkit_outbox_events_lease";
+const DEDUPE_INDEX: &str = "uq_modkit_outbox_events_dedupe";
+
+pub(super) struct CreateOutboxTable;
+
+impl MigrationName for CreateOutboxTable {
+    fn name(&self) -> &'static str {
+        "m000_create_modkit_outbox_events"
+    }
+}
+
+fn col(name: &'static str) -> ColumnDef {
+    ColumnDef::new(Alias::new(name))
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateOutboxTable {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new(TABLE))
+                    .if_not_exists()
+                    .col(col("id").uuid().not_null().primary_key())
+                    .col(col("namespace").string_len(128).not_null())
+                    .col(col("topic").string_len(128).not_null())
+                    .col(col("tenant_id").uuid().null())
+                    .col(col("dedupe_key").string_len(255).null())
+                    .col(col("payload").json().not_null())
+                    .col(col("status").string_len(16).not_null())
+                    .col(col("attempts").integer().not_null().default(0))
+                    .col(col("next_attempt_at").timestamp_with_time_zone().not_null())
+                    .col(col("locked_by").uuid().null())
+                    .col(col("locked_until").timestamp_with_time_zone().null())
+                    .col(col("last_error").text().null())
+                    .col(col("created_at").timestamp_with_time_zone().not_null())
+                    .col(col("updated_at").timestamp_with_time_zone().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Migrations are tracked per module, so several modules may run this
+        // one against the same database.
+        if !manager.has_index(TABLE, CLAIM_INDEX).await? {
+            manager
+                .create_index(
+                    Index::create()
+                        .name(CLAIM_INDEX)
+                        .table(Alias::new(TABLE))
+                        .col(Alias::new("status"))
+                        .col(Alias::new("next_attempt_at"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        if !manager.has_index(TABLE, LEASE_INDEX).await? {
+            manager
+                .create_index(
+                    Index::create()
+                        .name(LEASE_INDEX)
+                        .table(Alias::new(TABLE))
+                        .col(Alias::new("locked_until"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        if !manager.has_index(TABLE, DEDUPE_INDEX).await? {
+            create_dedupe_index(manager).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(Alias::new(TABLE))
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Unique `(namespace, topic, dedupe_key)` for rows that carry a dedupe key.
+///
+/// `MySQL` has no partial indexes, but treats NULLs as distinct in a unique
+/// index, which gives the same behaviour.
+async fn create_dedupe_index(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    match manager.get_database_backend() {
+        DatabaseBackend::MySql => {
+            manager
+                .create_index(
+                    Index::create()
+                        .name(DEDUPE_INDEX)
+                        .table(Alias::new(TABLE))
+                        .col(Alias::new("namespace"))
+                        .col(Alias::new("topic"))
+                        .col(Alias::new("dedupe_key"))
+                        .unique()
+                        .to_owned(),
+                )
+                .await
+        }
+        DatabaseBackend::Postgres | DatabaseBackend::Sqlite => manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                "CREATE UNIQUE INDEX IF NOT EXISTS {DEDUPE_INDEX} \
+                     ON {TABLE} (namespace, topic, dedupe_key) \
+                     WHERE dedupe_key IS NOT NULL"
+            ))
+            .await
+            .map(|_| ()),
+    }
+}
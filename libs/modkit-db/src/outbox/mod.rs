@@ -0,0 +1,290 @@
+//! Transactional outbox.
+//!
+//! Producers call [`enqueue`] with the same runner (usually a transaction)
+//! that writes the side effects the event describes, so the event is persisted
+//! if and only if those side effects commit. A dispatcher then claims rows
+//! through an [`OutboxStore`], publishes them and acknowledges the outcome:
+//!
+//! - rows are claimed with `FOR UPDATE SKIP LOCKED` and a lease
+//!   (`locked_by`, `locked_until`), so several workers never claim the same
+//!   row and rows claimed by a crashed worker are reclaimed once the lease
+//!   expires;
+//! - failures are retried with exponential backoff and equal jitter
+//!   ([`RetryPolicy`]); rows that reach `max_attempts` move to `dead`;
+//! - delivery is at-least-once, so consumers must be idempotent (typically on
+//!   `dedupe_key`);
+//! - delivered rows, and optionally dead ones, are deleted once they are
+//!   older than a [`RetentionCfg`].
+//!
+//! All events live in the shared `modkit_outbox_events` table; modules add
+//! [`migration()`] to their migrations and separate their events by
+//! `namespace`.
+//!
+//! ```ignore
+//! db.transaction(|tx| Box::pin(async move {
+//!     repo.settle(tx, &turn).await?;
+//!     outbox::enqueue(tx, OutboxMessage {
+//!         namespace: "mini-chat",
+//!         topic: "usage.settled",
+//!         tenant_id: Some(turn.tenant_id),
+//!         dedupe_key: Some(format!("{}/{}/{}", turn.tenant_id, turn.id, turn.request_id)),
+//!         payload: serde_json::to_value(&event)?,
+//!     })
+//!     .await?;
+//!     Ok(())
+//! }))
+//! .await?;
+//! ```
+
+mod entity;
+mod migration;
+mod store;
+
+use std::time::Duration;
+
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QuerySelect, Set};
+use sea_orm_migration::MigrationTrait;
+use serde_json::Value;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::DbError;
+use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
+
+pub use store::{OutboxStats, OutboxStore};
+
+/// Name of the shared outbox table.
+pub const TABLE: &str = "modkit_outbox_events";
+
+/// Row status values.
+pub mod status {
+    /// Waiting for its next delivery attempt.
+    pub const PENDING: &str = "pending";
+    /// Claimed by a worker holding a lease.
+    pub const PROCESSING: &str = "processing";
+    /// Published successfully.
+    pub const DELIVERED: &str = "delivered";
+    /// Out of attempts; never retried automatically.
+    pub const DEAD: &str = "dead";
+}
+
+/// Migration creating the outbox table and its indexes (idempotent).
+#[must_use]
+pub fn migration() -> Box<dyn MigrationTrait> {
+    Box::new(migration::CreateOutboxTable)
+}
+
+/// Event to persist with [`enqueue`].
+#[derive(Debug, Clone)]
+pub struct OutboxMessage {
+    /// Producer namespace, usually the module name.
+    pub namespace: &'static str,
+    /// Event type within the namespace.
+    pub topic: &'static str,
+    /// Tenant the event belongs to; `None` for system events.
+    pub tenant_id: Option<Uuid>,
+    /// Idempotency key: at most one row exists per `(namespace, topic, dedupe_key)`.
+    pub dedupe_key: Option<String>,
+    /// Event body, opaque to the outbox.
+    pub payload: Value,
+}
+
+/// Persist `msg` through `runner`.
+///
+/// Run this in the transaction that applies the side effects the event
+/// describes. If a row with the same `(namespace, topic, dedupe_key)` already
+/// exists the event counts as already enqueued: nothing is inserted and the
+/// existing row's id is returned.
+///
+/// # Errors
+///
+/// Returns `DbError` if the insert fails.
+pub async fn enqueue(runner: &impl DBRunner, msg: OutboxMessage) -> Result<Uuid, DbError> {
+    match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => insert(db, msg).await,
+        SeaOrmRunner::Tx(tx) => insert(tx, msg).await,
+    }
+}
+
+async fn insert<C: ConnectionTrait>(conn: &C, msg: OutboxMessage) -> Result<Uuid, DbError> {
+    use entity::Column;
+
+    let id = Uuid::new_v4();
+    let now = OffsetDateTime::now_utc();
+    let row = entity::ActiveModel {
+        id: Set(id),
+        namespace: Set(msg.namespace.to_owned()),
+        topic: Set(msg.topic.to_owned()),
+        tenant_id: Set(msg.tenant_id),
+        dedupe_key: Set(msg.dedupe_key.clone()),
+        payload: Set(msg.payload),
+        status: Set(status::PENDING.to_owned()),
+        attempts: Set(0),
+        next_attempt_at: Set(now),
+        locked_by: Set(None),
+        locked_until: Set(None),
+        last_error: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    let inserted = entity::Entity::insert(row)
+        .on_conflict(
+            OnConflict::columns([Column::Namespace, Column::Topic, Column::DedupeKey])
+                .target_and_where(Expr::col(Column::DedupeKey).is_not_null())
+                .do_nothing_on([Column::Id])
+                .to_owned(),
+        )
+        .exec_without_returning(conn)
+        .await?;
+    if inserted > 0 {
+        return Ok(id);
+    }
+
+    let Some(dedupe_key) = msg.dedupe_key else {
+        return Err(DbError::Other(anyhow::anyhow!(
+            "outbox insert into {TABLE} affected no rows"
+        )));
+    };
+    let existing: Option<Uuid> = entity::Entity::find()
+        .select_only()
+        .column(Column::Id)
+        .filter(Column::Namespace.eq(msg.namespace))
+        .filter(Column::Topic.eq(msg.topic))
+        .filter(Column::DedupeKey.eq(dedupe_key))
+        .into_tuple()
+        .one(conn)
+        .await?;
+    existing.ok_or_else(|| {
+        DbError::Other(anyhow::anyhow!(
+            "outbox dedupe conflict in {TABLE} but no matching row"
+        ))
+    })
+}
+
+/// Claim parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct ClaimCfg {
+    /// Maximum number of rows to claim.
+    pub batch_size: u32,
+    /// How long claimed rows stay leased to this worker.
+    pub lease_duration: Duration,
+    /// Maximum total delivery attempts (including the first). Rows at the
+    /// limit are not claimed but moved to `dead`.
+    pub max_attempts: u32,
+}
+
+/// How long finished rows are kept, see [`OutboxStore::purge`].
+#[derive(Debug, Clone, Copy)]
+pub struct RetentionCfg {
+    /// Delivered rows are deleted this long after delivery.
+    pub delivered: Duration,
+    /// Dead rows are deleted this long after they died; `None` keeps them
+    /// for manual inspection.
+    pub dead: Option<Duration>,
+}
+
+/// Retry schedule applied by [`OutboxStore::nack`].
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Maximum total delivery attempts; same value as [`ClaimCfg::max_attempts`].
+    pub max_attempts: u32,
+    /// Delay after the first failed attempt.
+    pub base_delay: Duration,
+    /// Upper bound of the delay before jitter.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 10,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(300),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// `min(base_delay * 2^(attempts - 1), max_delay)`, before jitter.
+    #[must_use]
+    pub fn delay(&self, attempts: u32) -> Duration {
+        let exp = attempts.saturating_sub(1).min(31);
+        self.base_delay.saturating_mul(1 << exp).min(self.max_delay)
+    }
+
+    /// [`Self::delay`] with equal jitter: uniform in `[delay / 2, delay]`.
+    #[must_use]
+    pub fn jittered_delay(&self, attempts: u32) -> Duration {
+        let delay = self.delay(attempts);
+        let half = delay / 2;
+        let spread = u64::try_from(delay.saturating_sub(half).as_nanos()).unwrap_or(u64::MAX);
+        half + Duration::from_nanos(rand::random_range(0..=spread))
+    }
+}
+
+/// A row leased to the calling worker.
+#[derive(Debug, Clone)]
+pub struct ClaimedMessage {
+    pub id: Uuid,
+    pub namespace: String,
+    pub topic: String,
+    pub tenant_id: Option<Uuid>,
+    pub dedupe_key: Option<String>,
+    pub payload: Value,
+    /// Delivery attempts including the current one.
+    pub attempts: i32,
+}
+
+impl From<entity::Model> for ClaimedMessage {
+    fn from(row: entity::Model) -> Self {
+        Self {
+            id: row.id,
+            namespace: row.namespace,
+            topic: row.topic,
+            tenant_id: row.tenant_id,
+            dedupe_key: row.dedupe_key,
+            payload: row.payload,
+            attempts: row.attempts,
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+
+    #[test]
+    fn delay_doubles_and_is_capped() {
+        let p = policy();
+        let delays: Vec<_> = (1..=6).map(|a| p.delay(a).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 10, 10]);
+        assert_eq!(p.delay(0), Duration::from_secs(1));
+        assert_eq!(p.delay(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn jitter_stays_within_half_and_full_delay() {
+        let p = policy();
+        for attempts in 1..=6 {
+            let delay = p.delay(attempts);
+            for _ in 0..50 {
+                let j = p.jittered_delay(attempts);
+                assert!(
+                    j >= delay / 2 && j <= delay,
+                    "{j:?} outside [{delay:?}/2, {delay:?}]"
+                );
+            }
+        }
+    }
+}
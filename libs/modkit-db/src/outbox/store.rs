@@ -0,0 +1,467 @@
+//! Claim / ack / nack operations used by outbox dispatchers.
+
+use sea_orm::sea_query::{Expr, LockBehavior, LockType};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use time::OffsetDateTime;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::entity::{self, Column};
+use super::{ClaimCfg, ClaimedMessage, RetentionCfg, RetryPolicy, status};
+use crate::secure::{DBRunnerInternal, SeaOrmRunner};
+use crate::{DBProvider, DbError};
+
+/// Row counts of one outbox namespace, see [`OutboxStore::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutboxStats {
+    /// Rows waiting for a delivery attempt.
+    pub pending: u64,
+    /// Rows currently leased by a worker (including expired leases).
+    pub processing: u64,
+    /// Rows that ran out of attempts.
+    pub dead: u64,
+    /// Creation time of the oldest row not yet delivered or dead.
+    pub oldest_undelivered_at: Option<OffsetDateTime>,
+}
+
+impl OutboxStats {
+    /// Age of the oldest undelivered row (zero when there is none).
+    #[must_use]
+    pub fn lag(&self) -> std::time::Duration {
+        self.oldest_undelivered_at
+            .map(|at| OffsetDateTime::now_utc() - at)
+            .and_then(|age| std::time::Duration::try_from(age).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Outbox access for one worker and namespace.
+///
+/// `claim_batch` leases rows to `worker_id`; `ack`, `nack` and `release` only
+/// succeed for rows that are still leased to it and fail with
+/// [`DbError::OutboxLeaseLost`] otherwise (the lease expired and another
+/// worker reclaimed the row). Such a failure is not a publish failure: the row
+/// now belongs to the other worker.
+pub struct OutboxStore<E> {
+    db: DBProvider<E>,
+    worker_id: Uuid,
+    namespace: String,
+    topics: Option<Vec<String>>,
+    retry: RetryPolicy,
+}
+
+impl<E> Clone for OutboxStore<E> {
+    fn clone(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+            worker_id: self.worker_id,
+            namespace: self.namespace.clone(),
+            topics: self.topics.clone(),
+            retry: self.retry,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Scope {
+    namespace: String,
+    topics: Option<Vec<String>>,
+}
+
+impl Scope {
+    fn condition(&self) -> Condition {
+        let mut cond = Condition::all().add(Column::Namespace.eq(self.namespace.as_str()));
+        if let Some(topics) = &self.topics {
+            cond = cond.add(Column::Topic.is_in(topics.iter().map(String::as_str)));
+        }
+        cond
+    }
+}
+
+impl<E> OutboxStore<E>
+where
+    E: From<DbError> + Send + 'static,
+{
+    /// Store for `namespace`, claiming rows on behalf of `worker_id`.
+    #[must_use]
+    pub fn new(db: DBProvider<E>, worker_id: Uuid, namespace: impl Into<String>) -> Self {
+        Self {
+            db,
+            worker_id,
+            namespace: namespace.into(),
+            topics: None,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Only claim rows with one of these topics (default: every topic).
+    #[must_use]
+    pub fn with_topics<I, S>(mut self, topics: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.topics = Some(topics.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Retry schedule used by [`Self::nack`].
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    #[must_use]
+    pub fn worker_id(&self) -> Uuid {
+        self.worker_id
+    }
+
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    fn scope(&self) -> Scope {
+        Scope {
+            namespace: self.namespace.clone(),
+            topics: self.topics.clone(),
+        }
+    }
+
+    /// Lease up to `cfg.batch_size` eligible rows, oldest first.
+    ///
+    /// Eligible rows are pending rows whose `next_attempt_at` has passed and
+    /// processing rows whose lease expired, with fewer than
+    /// `cfg.max_attempts` attempts. Rows of the same scope that are out of
+    /// attempts are moved to `dead` in the same transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns `E` if the transaction fails.
+    pub async fn claim_batch(&self, cfg: ClaimCfg) -> Result<Vec<ClaimedMessage>, E> {
+        let scope = self.scope();
+        let worker_id = self.worker_id;
+        self.db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let claimed = match DBRunnerInternal::as_seaorm(tx) {
+                        SeaOrmRunner::Conn(c) => claim(c, &scope, cfg, worker_id).await,
+                        SeaOrmRunner::Tx(t) => claim(t, &scope, cfg, worker_id).await,
+                    };
+                    claimed.map_err(|e| E::from(e.into()))
+                })
+            })
+            .await
+    }
+
+    /// Mark a leased row as delivered.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DbError::OutboxLeaseLost`] if the row is no longer leased to
+    /// this worker, or another `E` if the update fails.
+    pub async fn ack(&self, id: Uuid) -> Result<(), E> {
+        let now = OffsetDateTime::now_utc();
+        let update = entity::Entity::update_many()
+            .col_expr(Column::Status, Expr::value(status::DELIVERED))
+            .col_expr(Column::LockedBy, Expr::value(Option::<Uuid>::None))
+            .col_expr(
+                Column::LockedUntil,
+                Expr::value(Option::<OffsetDateTime>::None),
+            )
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.eq(status::PROCESSING))
+            .filter(Column::LockedBy.eq(self.worker_id));
+
+        let conn = self.db.conn()?;
+        let result = match DBRunnerInternal::as_seaorm(&conn) {
+            SeaOrmRunner::Conn(c) => update.exec(c).await,
+            SeaOrmRunner::Tx(t) => update.exec(t).await,
+        }
+        .map_err(|e| E::from(e.into()))?;
+        if result.rows_affected == 0 {
+            return Err(E::from(DbError::OutboxLeaseLost(id)));
+        }
+        Ok(())
+    }
+
+    /// Hand a leased row back without a delivery attempt, e.g. because its
+    /// lease ran out before its handler could be called.
+    ///
+    /// The row is due again right away and the attempt its claim counted is
+    /// taken back.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DbError::OutboxLeaseLost`] if the row is no longer leased to
+    /// this worker, or another `E` if the update fails.
+    pub async fn release(&self, id: Uuid) -> Result<(), E> {
+        let now = OffsetDateTime::now_utc();
+        let update = entity::Entity::update_many()
+            .col_expr(Column::Status, Expr::value(status::PENDING))
+            .col_expr(Column::Attempts, Expr::col(Column::Attempts).sub(1))
+            .col_expr(Column::LockedBy, Expr::value(Option::<Uuid>::None))
+            .col_expr(
+                Column::LockedUntil,
+                Expr::value(Option::<OffsetDateTime>::None),
+            )
+            .col_expr(Column::NextAttemptAt, Expr::value(now))
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.eq(status::PROCESSING))
+            .filter(Column::LockedBy.eq(self.worker_id));
+
+        let conn = self.db.conn()?;
+        let result = match DBRunnerInternal::as_seaorm(&conn) {
+            SeaOrmRunner::Conn(c) => update.exec(c).await,
+            SeaOrmRunner::Tx(t) => update.exec(t).await,
+        }
+        .map_err(|e| E::from(e.into()))?;
+        if result.rows_affected == 0 {
+            return Err(E::from(DbError::OutboxLeaseLost(id)));
+        }
+        Ok(())
+    }
+
+    /// Record a failed delivery of a leased row.
+    ///
+    /// The lease is released and `last_error` recorded. The row is scheduled
+    /// for another attempt after the [`RetryPolicy`] backoff, or moved to
+    /// `dead` once it used up `max_attempts`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DbError::OutboxLeaseLost`] if the row is no longer leased to
+    /// this worker, or another `E` if the transaction fails.
+    pub async fn nack(&self, id: Uuid, err: &str) -> Result<(), E> {
+        let worker_id = self.worker_id;
+        let retry = self.retry;
+        let err = err.to_owned();
+        self.db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let res = match DBRunnerInternal::as_seaorm(tx) {
+                        SeaOrmRunner::Conn(c) => reschedule(c, id, worker_id, retry, err).await,
+                        SeaOrmRunner::Tx(t) => reschedule(t, id, worker_id, retry, err).await,
+                    };
+                    res.map_err(E::from)
+                })
+            })
+            .await
+    }
+
+    /// Delete rows of this store's namespace (and topics, if restricted)
+    /// that finished longer ago than `cfg` allows; returns how many were
+    /// deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `E` if the delete fails.
+    pub async fn purge(&self, cfg: RetentionCfg) -> Result<u64, E> {
+        let now = OffsetDateTime::now_utc();
+        let finished = |state: &str, retention: std::time::Duration| {
+            Condition::all()
+                .add(Column::Status.eq(state))
+                .add(Column::UpdatedAt.lt(now.saturating_sub(to_time(retention))))
+        };
+        let mut expired = Condition::any().add(finished(status::DELIVERED, cfg.delivered));
+        if let Some(dead) = cfg.dead {
+            expired = expired.add(finished(status::DEAD, dead));
+        }
+        let delete = entity::Entity::delete_many()
+            .filter(self.scope().condition())
+            .filter(expired);
+
+        let conn = self.db.conn()?;
+        let result = match DBRunnerInternal::as_seaorm(&conn) {
+            SeaOrmRunner::Conn(c) => delete.exec(c).await,
+            SeaOrmRunner::Tx(t) => delete.exec(t).await,
+        }
+        .map_err(|e| E::from(e.into()))?;
+        Ok(result.rows_affected)
+    }
+
+    /// Row counts of this store's namespace (and topics, if restricted).
+    ///
+    /// # Errors
+    ///
+    /// Returns `E` if the queries fail.
+    pub async fn stats(&self) -> Result<OutboxStats, E> {
+        let scope = self.scope();
+        let conn = self.db.conn()?;
+        match DBRunnerInternal::as_seaorm(&conn) {
+            SeaOrmRunner::Conn(c) => stats(c, &scope).await,
+            SeaOrmRunner::Tx(t) => stats(t, &scope).await,
+        }
+        .map_err(|e| E::from(e.into()))
+    }
+}
+
+/// `(pending AND next_attempt_at <= now) OR (processing AND locked_until < now)`
+fn due(now: OffsetDateTime) -> Condition {
+    Condition::any()
+        .add(
+            Condition::all()
+                .add(Column::Status.eq(status::PENDING))
+                .add(Column::NextAttemptAt.lte(now)),
+        )
+        .add(
+            Condition::all()
+                .add(Column::Status.eq(status::PROCESSING))
+                .add(Column::LockedUntil.lt(now)),
+        )
+}
+
+fn to_time(d: std::time::Duration) -> time::Duration {
+    time::Duration::try_from(d).unwrap_or(time::Duration::MAX)
+}
+
+async fn claim<C: ConnectionTrait>(
+    conn: &C,
+    scope: &Scope,
+    cfg: ClaimCfg,
+    worker_id: Uuid,
+) -> Result<Vec<ClaimedMessage>, sea_orm::DbErr> {
+    let now = OffsetDateTime::now_utc();
+    let max_attempts = i32::try_from(cfg.max_attempts).unwrap_or(i32::MAX);
+
+    let buried = entity::Entity::update_many()
+        .col_expr(Column::Status, Expr::value(status::DEAD))
+        .col_expr(Column::LockedBy, Expr::value(Option::<Uuid>::None))
+        .col_expr(
+            Column::LockedUntil,
+            Expr::value(Option::<OffsetDateTime>::None),
+        )
+        .col_expr(Column::UpdatedAt, Expr::value(now))
+        .filter(scope.condition())
+        .filter(Column::Attempts.gte(max_attempts))
+        .filter(due(now))
+        .exec(conn)
+        .await?
+        .rows_affected;
+    if buried > 0 {
+        warn!(
+            namespace = %scope.namespace,
+            count = buried,
+            "outbox rows out of attempts moved to dead"
+        );
+    }
+
+    let rows = entity::Entity::find()
+        .filter(scope.condition())
+        .filter(Column::Attempts.lt(max_attempts))
+        .filter(due(now))
+        .order_by_asc(Column::CreatedAt)
+        .order_by_asc(Column::Id)
+        .limit(u64::from(cfg.batch_size))
+        .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+        .all(conn)
+        .await?;
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let locked_until = now.saturating_add(to_time(cfg.lease_duration));
+    entity::Entity::update_many()
+        .col_expr(Column::Status, Expr::value(status::PROCESSING))
+        .col_expr(Column::Attempts, Expr::col(Column::Attempts).add(1))
+        .col_expr(Column::LockedBy, Expr::value(worker_id))
+        .col_expr(Column::LockedUntil, Expr::value(locked_until))
+        .col_expr(Column::UpdatedAt, Expr::value(now))
+        .filter(Column::Id.is_in(rows.iter().map(|r| r.id)))
+        .exec(conn)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|mut row| {
+            row.attempts += 1;
+            ClaimedMessage::from(row)
+        })
+        .collect())
+}
+
+async fn reschedule<C: ConnectionTrait>(
+    conn: &C,
+    id: Uuid,
+    worker_id: Uuid,
+    retry: RetryPolicy,
+    err: String,
+) -> Result<(), DbError> {
+    let row = entity::Entity::find_by_id(id)
+        .filter(Column::Status.eq(status::PROCESSING))
+        .filter(Column::LockedBy.eq(worker_id))
+        .lock(LockType::Update)
+        .one(conn)
+        .await?
+        .ok_or(DbError::OutboxLeaseLost(id))?;
+
+    let now = OffsetDateTime::now_utc();
+    let attempts = u32::try_from(row.attempts).unwrap_or(0);
+    let mut update = entity::Entity::update_many()
+        .col_expr(Column::LockedBy, Expr::value(Option::<Uuid>::None))
+        .col_expr(
+            Column::LockedUntil,
+            Expr::value(Option::<OffsetDateTime>::None),
+        )
+        .col_expr(Column::LastError, Expr::value(err))
+        .col_expr(Column::UpdatedAt, Expr::value(now))
+        .filter(Column::Id.eq(id));
+    if attempts >= retry.max_attempts {
+        warn!(
+            %id,
+            namespace = %row.namespace,
+            topic = %row.topic,
+            attempts,
+            "outbox row out of attempts moved to dead"
+        );
+        update = update.col_expr(Column::Status, Expr::value(status::DEAD));
+    } else {
+        let next = now.saturating_add(to_time(retry.jittered_delay(attempts)));
+        update = update
+            .col_expr(Column::Status, Expr::value(status::PENDING))
+            .col_expr(Column::NextAttemptAt, Expr::value(next));
+    }
+    update.exec(conn).await?;
+    Ok(())
+}
+
+async fn stats<C: ConnectionTrait>(conn: &C, scope: &Scope) -> Result<OutboxStats, sea_orm::DbErr> {
+    let counts: Vec<(String, i64)> = entity::Entity::find()
+        .select_only()
+        .column(Column::Status)
+        .column_as(Expr::col(Column::Id).count(), "count")
+        .filter(scope.condition())
+        .filter(Column::Status.ne(status::DELIVERED))
+        .group_by(Column::Status)
+        .into_tuple()
+        .all(conn)
+        .await?;
+    let oldest: Option<Option<OffsetDateTime>> = entity::Entity::find()
+        .select_only()
+        .column_as(Expr::col(Column::CreatedAt).min(), "oldest")
+        .filter(scope.condition())
+        .filter(Column::Status.is_in([status::PENDING, status::PROCESSING]))
+        .into_tuple()
+        .one(conn)
+        .await?;
+
+    let mut stats = OutboxStats {
+        oldest_undelivered_at: oldest.flatten(),
+        ..OutboxStats::default()
+    };
+    for (status, count) in counts {
+        let count = u64::try_from(count).unwrap_or(0);
+        match status.as_str() {
+            status::PENDING => stats.pending = count,
+            status::PROCESSING => stats.processing = count,
+            status::DEAD => stats.dead = count,
+            _ => {}
+        }
+    }
+    Ok(stats)
+}
@@ -1,5 +1,6 @@
 use sea_orm::{ColumnTrait, Condition, EntityTrait, sea_query::Expr};
 
+use crate::secure::projections;
 use crate::secure::{AccessScope, ScopableEntity};
 use modkit_security::access_scope::{ScopeConstraint, ScopeFilter, ScopeValue};
 
@@ -39,6 +40,8 @@ fn deny_all() -> Condition {
 /// - Filters within a constraint are AND-ed (all must match)
 /// - Unknown `pep_properties` fail that constraint (fail-closed)
 /// - If all constraints fail resolution, deny-all
+/// - Hierarchy filters become `IN (subquery)` against the
+///   [projection tables](super::projections)
 ///
 /// # Policy Rules
 ///
@@ -102,6 +105,18 @@ where
                 let sea_values = scope_values_to_sea_values(inf.values());
                 and_cond = and_cond.add(Expr::col(col).is_in(sea_values));
             }
+            ScopeFilter::InTenantSubtree(f) => {
+                let subquery = projections::tenant_subtree_query(f);
+                and_cond = and_cond.add(Expr::col(col).in_subquery(subquery));
+            }
+            ScopeFilter::InGroup(f) => {
+                let subquery = projections::group_members_query(f);
+                and_cond = and_cond.add(Expr::col(col).in_subquery(subquery));
+            }
+            ScopeFilter::InGroupSubtree(f) => {
+                let subquery = projections::group_subtree_members_query(f);
+                and_cond = and_cond.add(Expr::col(col).in_subquery(subquery));
+            }
         }
     }
     Some(and_cond)
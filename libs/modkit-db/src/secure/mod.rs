@@ -99,6 +99,7 @@
 //! | Tenants only | Filter by tenant column |
 //! | Resources only | Filter by ID column |
 //! | Both | AND them together |
+//! | Tenant subtree / group filters | `IN (subquery)` on the [projection tables](projections) |
 //!
 //! See the [docs module](docs) for comprehensive examples and usage patterns.
 
@@ -110,6 +111,7 @@ pub mod docs;
 #[allow(clippy::module_inception)]
 mod entity_traits;
 mod error;
+pub mod projections;
 pub mod provider;
 mod runner;
 mod secure_conn;
@@ -126,8 +128,8 @@ pub use error::ScopeError;
 
 // Security types from modkit-security
 pub use modkit_security::{
-    AccessScope, EqScopeFilter, InScopeFilter, ScopeConstraint, ScopeFilter, ScopeValue,
-    pep_properties,
+    AccessScope, EqScopeFilter, InGroupScopeFilter, InGroupSubtreeScopeFilter, InScopeFilter,
+    InTenantSubtreeScopeFilter, ScopeConstraint, ScopeFilter, ScopeValue, pep_properties,
 };
 
 // Ergonomic secure connection API (no raw SeaORM types leaked)
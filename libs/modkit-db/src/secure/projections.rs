@@ -0,0 +1,220 @@
+//! Local projection tables used by hierarchy scope filters.
+//!
+//! [`ScopeFilter::InTenantSubtree`], [`ScopeFilter::InGroup`] and
+//! [`ScopeFilter::InGroupSubtree`] compile to subqueries against these tables,
+//! which must live in the same database as the scoped entity. Modules that
+//! advertise the matching `AuthZ` capabilities add [`migration()`] to their
+//! migrations and keep the tables in sync with the tenant and resource group
+//! sources of truth.
+//!
+//! | Table | Columns |
+//! |-------|---------|
+//! | `tenant_closure` | `ancestor_id`, `descendant_id`, `barrier` (0 = no barrier on the path), `descendant_status` |
+//! | `resource_group_closure` | `ancestor_id`, `descendant_id` |
+//! | `resource_group_membership` | `resource_id`, `group_id` |
+//!
+//! Both closure tables contain a self-row (`ancestor_id = descendant_id`) for
+//! every node.
+//!
+//! [`ScopeFilter::InTenantSubtree`]: modkit_security::ScopeFilter::InTenantSubtree
+//! [`ScopeFilter::InGroup`]: modkit_security::ScopeFilter::InGroup
+//! [`ScopeFilter::InGroupSubtree`]: modkit_security::ScopeFilter::InGroupSubtree
+
+use modkit_security::{InGroupScopeFilter, InGroupSubtreeScopeFilter, InTenantSubtreeScopeFilter};
+use sea_orm::sea_query::{Alias, Expr, Query, SelectStatement, SimpleExpr};
+use sea_orm_migration::prelude::{
+    ColumnDef, DbErr, Index, MigrationName, MigrationTrait, SchemaManager, Table,
+};
+
+/// Tenant hierarchy closure table.
+pub const TENANT_CLOSURE: &str = "tenant_closure";
+/// Resource group hierarchy closure table.
+pub const RESOURCE_GROUP_CLOSURE: &str = "resource_group_closure";
+/// Resource-to-group membership table.
+pub const RESOURCE_GROUP_MEMBERSHIP: &str = "resource_group_membership";
+
+const ANCESTOR_ID: &str = "ancestor_id";
+const DESCENDANT_ID: &str = "descendant_id";
+const BARRIER: &str = "barrier";
+const DESCENDANT_STATUS: &str = "descendant_status";
+const RESOURCE_ID: &str = "resource_id";
+const GROUP_ID: &str = "group_id";
+
+fn col(name: &'static str) -> Expr {
+    Expr::col(Alias::new(name))
+}
+
+/// `SELECT descendant_id FROM tenant_closure WHERE ancestor_id = :root [AND barrier = 0] [AND descendant_status IN (..)]`
+pub(crate) fn tenant_subtree_query(filter: &InTenantSubtreeScopeFilter) -> SelectStatement {
+    let mut query = Query::select();
+    query
+        .column(Alias::new(DESCENDANT_ID))
+        .from(Alias::new(TENANT_CLOSURE))
+        .and_where(col(ANCESTOR_ID).eq(filter.root_tenant_id()));
+    if filter.respect_barriers() {
+        query.and_where(col(BARRIER).eq(0));
+    }
+    if !filter.tenant_statuses().is_empty() {
+        query.and_where(col(DESCENDANT_STATUS).is_in(filter.tenant_statuses().iter().cloned()));
+    }
+    query
+}
+
+fn group_members_where(groups: SimpleExpr) -> SelectStatement {
+    Query::select()
+        .column(Alias::new(RESOURCE_ID))
+        .from(Alias::new(RESOURCE_GROUP_MEMBERSHIP))
+        .and_where(groups)
+        .to_owned()
+}
+
+/// `SELECT resource_id FROM resource_group_membership WHERE group_id IN (..)`
+pub(crate) fn group_members_query(filter: &InGroupScopeFilter) -> SelectStatement {
+    group_members_where(col(GROUP_ID).is_in(filter.group_ids().iter().copied()))
+}
+
+/// Members of `root_group_id` and every group below it.
+pub(crate) fn group_subtree_members_query(filter: &InGroupSubtreeScopeFilter) -> SelectStatement {
+    let descendants = Query::select()
+        .column(Alias::new(DESCENDANT_ID))
+        .from(Alias::new(RESOURCE_GROUP_CLOSURE))
+        .and_where(col(ANCESTOR_ID).eq(filter.root_group_id()))
+        .to_owned();
+    group_members_where(col(GROUP_ID).in_subquery(descendants))
+}
+
+/// Migration creating the projection tables (idempotent).
+#[must_use]
+pub fn migration() -> Box<dyn MigrationTrait> {
+    Box::new(CreateProjectionTables)
+}
+
+struct CreateProjectionTables;
+
+impl MigrationName for CreateProjectionTables {
+    fn name(&self) -> &'static str {
+        "m000_create_authz_projection_tables"
+    }
+}
+
+fn uuid_col(name: &'static str) -> ColumnDef {
+    ColumnDef::new(Alias::new(name))
+        .uuid()
+        .not_null()
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateProjectionTables {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new(TENANT_CLOSURE))
+                    .if_not_exists()
+                    .col(uuid_col(ANCESTOR_ID))
+                    .col(uuid_col(DESCENDANT_ID))
+                    .col(
+                        ColumnDef::new(Alias::new(BARRIER))
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(Alias::new(DESCENDANT_STATUS))
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(Alias::new(ANCESTOR_ID))
+                            .col(Alias::new(DESCENDANT_ID)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new(RESOURCE_GROUP_CLOSURE))
+                    .if_not_exists()
+                    .col(uuid_col(ANCESTOR_ID))
+                    .col(uuid_col(DESCENDANT_ID))
+                    .primary_key(
+                        Index::create()
+                            .col(Alias::new(ANCESTOR_ID))
+                            .col(Alias::new(DESCENDANT_ID)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(Alias::new(RESOURCE_GROUP_MEMBERSHIP))
+                    .if_not_exists()
+                    .col(uuid_col(RESOURCE_ID))
+                    .col(uuid_col(GROUP_ID))
+                    .primary_key(
+                        Index::create()
+                            .col(Alias::new(GROUP_ID))
+                            .col(Alias::new(RESOURCE_ID)),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            RESOURCE_GROUP_MEMBERSHIP,
+            RESOURCE_GROUP_CLOSURE,
+            TENANT_CLOSURE,
+        ] {
+            manager
+                .drop_table(
+                    Table::drop()
+                        .table(Alias::new(table))
+                        .if_exists()
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use modkit_security::pep_properties;
+    use sea_orm::sea_query::SqliteQueryBuilder;
+    use uuid::Uuid;
+
+    #[test]
+    fn tenant_subtree_query_applies_barrier_and_status() {
+        let root = Uuid::nil();
+        let filter = InTenantSubtreeScopeFilter::new(pep_properties::OWNER_TENANT_ID, root)
+            .with_tenant_statuses(vec!["active".to_owned()]);
+        let sql = tenant_subtree_query(&filter).to_string(SqliteQueryBuilder);
+        assert!(sql.contains(r#""barrier" = 0"#), "{sql}");
+        assert!(
+            sql.contains(r#""descendant_status" IN ('active')"#),
+            "{sql}"
+        );
+
+        let sql = tenant_subtree_query(&filter.ignore_barriers()).to_string(SqliteQueryBuilder);
+        assert!(!sql.contains("barrier"), "{sql}");
+    }
+
+    #[test]
+    fn group_subtree_query_nests_closure_lookup() {
+        let filter = InGroupSubtreeScopeFilter::new(pep_properties::RESOURCE_ID, Uuid::nil());
+        let sql = group_subtree_members_query(&filter).to_string(SqliteQueryBuilder);
+        assert!(
+            sql.contains(r#""group_id" IN (SELECT "descendant_id" FROM "resource_group_closure""#),
+            "{sql}"
+        );
+    }
+}
@@ -49,9 +49,12 @@ impl TenantFilterProvider for SimpleTenantFilter {
     {
         let tenant_ids = scope.all_uuid_values_for(pep_properties::OWNER_TENANT_ID);
 
-        // No tenant IDs in scope → no tenant filter
+        // No tenant IDs in scope → no tenant filter, unless tenants are only
+        // constrained by hierarchy filters this provider cannot express.
         if tenant_ids.is_empty() {
-            return None;
+            return scope
+                .has_property(pep_properties::OWNER_TENANT_ID)
+                .then(|| Condition::all().add(Expr::value(false)));
         }
 
         // Entity has no tenant column but tenant IDs requested → deny all
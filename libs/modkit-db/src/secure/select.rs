@@ -298,6 +298,14 @@ where
         self
     }
 
+    /// Lock the selected rows until the transaction ends
+    /// (`SELECT ... FOR UPDATE`). `SQLite` locks the whole database on write
+    /// instead, so there the clause is omitted.
+    pub fn lock_exclusive(mut self) -> Self {
+        self.inner = QuerySelect::lock_exclusive(self.inner);
+        self
+    }
+
     /// Apply scoping for a joined entity.
     ///
     /// This delegates to `build_scope_condition::<J>()` which handles all
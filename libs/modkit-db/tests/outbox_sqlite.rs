@@ -0,0 +1,317 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+#![cfg(feature = "sqlite")]
+
+//! `SQLite` integration tests for the transactional outbox.
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::outbox::{
+    self, ClaimCfg, OutboxMessage, OutboxStats, OutboxStore, RetentionCfg, RetryPolicy,
+};
+use modkit_db::{ConnectOpts, DBProvider, DbError, connect_db};
+use serde_json::json;
+use uuid::Uuid;
+
+const NS: &str = "test";
+
+type Provider = DBProvider<DbError>;
+
+async fn setup() -> Provider {
+    let db = connect_db("sqlite::memory:", ConnectOpts::default())
+        .await
+        .expect("db connect");
+    run_migrations_for_testing(&db, vec![outbox::migration()])
+        .await
+        .map_err(|e| anyhow!(e.to_string()))
+        .expect("migrate");
+    DBProvider::new(db)
+}
+
+fn message(topic: &'static str, dedupe_key: Option<&str>) -> OutboxMessage {
+    OutboxMessage {
+        namespace: NS,
+        topic,
+        tenant_id: Some(Uuid::from_u128(1)),
+        dedupe_key: dedupe_key.map(str::to_owned),
+        payload: json!({ "topic": topic }),
+    }
+}
+
+async fn enqueue(db: &Provider, msg: OutboxMessage) -> Uuid {
+    let conn = db.conn().unwrap();
+    outbox::enqueue(&conn, msg).await.unwrap()
+}
+
+fn claim_cfg(lease: Duration, max_attempts: u32) -> ClaimCfg {
+    ClaimCfg {
+        batch_size: 10,
+        lease_duration: lease,
+        max_attempts,
+    }
+}
+
+fn store(db: &Provider, retry: RetryPolicy) -> OutboxStore<DbError> {
+    OutboxStore::new(db.clone(), Uuid::new_v4(), NS).with_retry_policy(retry)
+}
+
+fn retry(max_attempts: u32, base_delay: Duration) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        base_delay,
+        max_delay: base_delay,
+    }
+}
+
+const LONG: Duration = Duration::from_secs(3600);
+
+#[tokio::test]
+async fn enqueue_is_idempotent_on_dedupe_key() {
+    let db = setup().await;
+
+    let first = enqueue(&db, message("a", Some("k1"))).await;
+    let again = enqueue(&db, message("a", Some("k1"))).await;
+    assert_eq!(first, again);
+
+    // The key is scoped to (namespace, topic); rows without a key never conflict.
+    let other_topic = enqueue(&db, message("b", Some("k1"))).await;
+    let no_key_1 = enqueue(&db, message("a", None)).await;
+    let no_key_2 = enqueue(&db, message("a", None)).await;
+    assert_ne!(other_topic, first);
+    assert_ne!(no_key_1, no_key_2);
+
+    let stats = store(&db, RetryPolicy::default()).stats().await.unwrap();
+    assert_eq!(stats.pending, 4);
+    assert!(stats.oldest_undelivered_at.is_some());
+}
+
+#[tokio::test]
+async fn enqueue_rolls_back_with_transaction() {
+    let db = setup().await;
+
+    let res: Result<(), DbError> = db
+        .transaction(|tx| {
+            Box::pin(async move {
+                outbox::enqueue(tx, message("a", Some("k1"))).await?;
+                Err(DbError::Other(anyhow!("side effect failed")))
+            })
+        })
+        .await;
+    assert!(res.is_err());
+
+    let stats = store(&db, RetryPolicy::default()).stats().await.unwrap();
+    assert_eq!(stats, OutboxStats::default());
+}
+
+#[tokio::test]
+async fn claim_leases_rows_in_order_and_ack_requires_lease() {
+    let db = setup().await;
+    let mut ids = Vec::new();
+    for key in ["k1", "k2", "k3"] {
+        ids.push(enqueue(&db, message("a", Some(key))).await);
+        tokio::time::sleep(Duration::from_millis(2)).await;
+    }
+
+    let worker_a = store(&db, RetryPolicy::default());
+    let worker_b = store(&db, RetryPolicy::default());
+
+    let mut cfg = claim_cfg(LONG, 5);
+    cfg.batch_size = 2;
+    let claimed_a = worker_a.claim_batch(cfg).await.unwrap();
+    let claimed_ids: Vec<_> = claimed_a.iter().map(|m| m.id).collect();
+    assert_eq!(claimed_ids, ids[..2]);
+    assert!(claimed_a.iter().all(|m| m.attempts == 1));
+    assert_eq!(claimed_a[0].payload, json!({ "topic": "a" }));
+
+    // Leased rows are skipped by other workers.
+    let claimed_b = worker_b.claim_batch(cfg).await.unwrap();
+    assert_eq!(claimed_b.iter().map(|m| m.id).collect::<Vec<_>>(), ids[2..]);
+
+    let err = worker_b.ack(ids[0]).await.unwrap_err();
+    assert!(matches!(err, DbError::OutboxLeaseLost(id) if id == ids[0]));
+
+    worker_a.ack(ids[0]).await.unwrap();
+    let err = worker_a.ack(ids[0]).await.unwrap_err();
+    assert!(matches!(err, DbError::OutboxLeaseLost(_)));
+
+    let stats = worker_a.stats().await.unwrap();
+    assert_eq!((stats.pending, stats.processing, stats.dead), (0, 2, 0));
+}
+
+#[tokio::test]
+async fn claim_is_restricted_to_namespace_and_topics() {
+    let db = setup().await;
+    let a = enqueue(&db, message("a", None)).await;
+    enqueue(&db, message("b", None)).await;
+    enqueue(
+        &db,
+        OutboxMessage {
+            namespace: "other",
+            ..message("a", None)
+        },
+    )
+    .await;
+
+    let worker = store(&db, RetryPolicy::default()).with_topics(["a"]);
+    let claimed = worker.claim_batch(claim_cfg(LONG, 5)).await.unwrap();
+    assert_eq!(claimed.iter().map(|m| m.id).collect::<Vec<_>>(), vec![a]);
+}
+
+#[tokio::test]
+async fn nack_reschedules_with_backoff_then_dead_letters() {
+    let db = setup().await;
+    let id = enqueue(&db, message("a", None)).await;
+
+    // Long backoff: the row is not due again.
+    let worker = store(&db, retry(2, LONG));
+    let cfg = claim_cfg(LONG, 2);
+    assert_eq!(worker.claim_batch(cfg).await.unwrap().len(), 1);
+    worker.nack(id, "boom").await.unwrap();
+    assert!(worker.claim_batch(cfg).await.unwrap().is_empty());
+    let stats = worker.stats().await.unwrap();
+    assert_eq!((stats.pending, stats.processing), (1, 0));
+
+    // Without backoff the row is retried right away, then dead-lettered.
+    let db = setup().await;
+    let id = enqueue(&db, message("a", None)).await;
+    let worker = store(&db, retry(2, Duration::ZERO));
+    let first = worker.claim_batch(cfg).await.unwrap();
+    assert_eq!(first[0].attempts, 1);
+    worker.nack(id, "boom").await.unwrap();
+
+    let second = worker.claim_batch(cfg).await.unwrap();
+    assert_eq!(second[0].attempts, 2);
+    worker.nack(id, "boom again").await.unwrap();
+
+    assert!(worker.claim_batch(cfg).await.unwrap().is_empty());
+    let stats = worker.stats().await.unwrap();
+    assert_eq!((stats.pending, stats.dead), (0, 1));
+    assert_eq!(stats.oldest_undelivered_at, None);
+
+    let err = worker.nack(id, "late").await.unwrap_err();
+    assert!(matches!(err, DbError::OutboxLeaseLost(_)));
+}
+
+#[tokio::test]
+async fn release_returns_row_without_counting_the_attempt() {
+    let db = setup().await;
+    let id = enqueue(&db, message("a", None)).await;
+
+    let worker = store(&db, RetryPolicy::default());
+    let other = store(&db, RetryPolicy::default());
+    let cfg = claim_cfg(LONG, 5);
+    assert_eq!(worker.claim_batch(cfg).await.unwrap()[0].attempts, 1);
+
+    assert!(matches!(
+        other.release(id).await.unwrap_err(),
+        DbError::OutboxLeaseLost(_)
+    ));
+    worker.release(id).await.unwrap();
+    let stats = worker.stats().await.unwrap();
+    assert_eq!((stats.pending, stats.processing), (1, 0));
+
+    // Due again right away, and still on its first attempt.
+    let reclaimed = other.claim_batch(cfg).await.unwrap();
+    assert_eq!(reclaimed.len(), 1);
+    assert_eq!(reclaimed[0].attempts, 1);
+    assert!(matches!(
+        worker.release(id).await.unwrap_err(),
+        DbError::OutboxLeaseLost(_)
+    ));
+}
+
+#[tokio::test]
+async fn expired_lease_is_reclaimed_by_another_worker() {
+    let db = setup().await;
+    let id = enqueue(&db, message("a", None)).await;
+
+    let crashed = store(&db, RetryPolicy::default());
+    let cfg = claim_cfg(Duration::ZERO, 5);
+    assert_eq!(crashed.claim_batch(cfg).await.unwrap().len(), 1);
+    tokio::time::sleep(Duration::from_millis(5)).await;
+
+    let worker = store(&db, RetryPolicy::default());
+    let reclaimed = worker.claim_batch(claim_cfg(LONG, 5)).await.unwrap();
+    assert_eq!(reclaimed.len(), 1);
+    assert_eq!(reclaimed[0].attempts, 2);
+
+    assert!(matches!(
+        crashed.ack(id).await.unwrap_err(),
+        DbError::OutboxLeaseLost(_)
+    ));
+    worker.ack(id).await.unwrap();
+}
+
+#[tokio::test]
+async fn rows_out_of_attempts_are_moved_to_dead_on_claim() {
+    let db = setup().await;
+    enqueue(&db, message("a", None)).await;
+
+    let crashed = store(&db, RetryPolicy::default());
+    assert_eq!(
+        crashed
+            .claim_batch(claim_cfg(Duration::ZERO, 1))
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    tokio::time::sleep(Duration::from_millis(5)).await;
+
+    let worker = store(&db, RetryPolicy::default());
+    assert!(
+        worker
+            .claim_batch(claim_cfg(LONG, 1))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let stats = worker.stats().await.unwrap();
+    assert_eq!((stats.processing, stats.dead), (0, 1));
+}
+
+#[tokio::test]
+async fn purge_deletes_finished_rows_past_retention() {
+    let db = setup().await;
+    let delivered = enqueue(&db, message("a", Some("delivered"))).await;
+    enqueue(&db, message("a", Some("dead"))).await;
+    let worker = store(&db, retry(1, Duration::ZERO));
+    for msg in worker.claim_batch(claim_cfg(LONG, 1)).await.unwrap() {
+        if msg.id == delivered {
+            worker.ack(msg.id).await.unwrap();
+        } else {
+            worker.nack(msg.id, "boom").await.unwrap();
+        }
+    }
+    let pending = enqueue(&db, message("a", Some("pending"))).await;
+    tokio::time::sleep(Duration::from_millis(5)).await;
+
+    // Nothing is old enough yet.
+    let keep = RetentionCfg {
+        delivered: LONG,
+        dead: Some(LONG),
+    };
+    assert_eq!(worker.purge(keep).await.unwrap(), 0);
+
+    // Dead rows are kept unless a dead retention is set.
+    let delivered_only = RetentionCfg {
+        delivered: Duration::ZERO,
+        dead: None,
+    };
+    assert_eq!(worker.purge(delivered_only).await.unwrap(), 1);
+    assert_ne!(
+        enqueue(&db, message("a", Some("delivered"))).await,
+        delivered
+    );
+    assert_eq!(worker.stats().await.unwrap().dead, 1);
+
+    let all = RetentionCfg {
+        delivered: LONG,
+        dead: Some(Duration::ZERO),
+    };
+    assert_eq!(worker.purge(all).await.unwrap(), 1);
+    let stats = worker.stats().await.unwrap();
+    assert_eq!((stats.pending, stats.dead), (2, 0));
+    assert_eq!(enqueue(&db, message("a", Some("pending"))).await, pending);
+}
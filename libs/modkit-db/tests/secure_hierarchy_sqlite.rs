@@ -0,0 +1,361 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+#![cfg(feature = "sqlite")]
+
+//! `SQLite` integration tests for hierarchy scope filters.
+//!
+//! Verifies that tenant subtree and group predicates compile to working
+//! subqueries against the projection tables created by
+//! `modkit_db::secure::projections::migration()`.
+
+use anyhow::anyhow;
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::secure::{
+    Db, InTenantSubtreeScopeFilter, ScopableEntity, ScopeConstraint, ScopeFilter, SecureEntityExt,
+    projections, secure_insert,
+};
+use modkit_db::{ConnectOpts, connect_db};
+use modkit_security::{AccessScope, pep_properties};
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm_migration::prelude as mig;
+use uuid::Uuid;
+
+mod doc {
+    use sea_orm::entity::prelude::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "hierarchy_doc")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        pub tenant_id: Uuid,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl ScopableEntity for doc::Entity {
+    fn tenant_col() -> Option<doc::Column> {
+        Some(doc::Column::TenantId)
+    }
+    fn resource_col() -> Option<doc::Column> {
+        Some(doc::Column::Id)
+    }
+    fn owner_col() -> Option<doc::Column> {
+        None
+    }
+    fn type_col() -> Option<doc::Column> {
+        None
+    }
+    fn resolve_property(property: &str) -> Option<doc::Column> {
+        match property {
+            p if p == pep_properties::OWNER_TENANT_ID => Self::tenant_col(),
+            p if p == pep_properties::RESOURCE_ID => Self::resource_col(),
+            _ => None,
+        }
+    }
+}
+
+mod tenant_closure {
+    use sea_orm::entity::prelude::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "tenant_closure")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub ancestor_id: Uuid,
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub descendant_id: Uuid,
+        pub barrier: i32,
+        pub descendant_status: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+mod group_closure {
+    use sea_orm::entity::prelude::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "resource_group_closure")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub ancestor_id: Uuid,
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub descendant_id: Uuid,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+mod group_membership {
+    use sea_orm::entity::prelude::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "resource_group_membership")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub group_id: Uuid,
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub resource_id: Uuid,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+macro_rules! unrestricted {
+    ($($m:ident),*) => {$(
+        impl ScopableEntity for $m::Entity {
+            const IS_UNRESTRICTED: bool = true;
+            fn tenant_col() -> Option<$m::Column> {
+                None
+            }
+            fn resource_col() -> Option<$m::Column> {
+                None
+            }
+            fn owner_col() -> Option<$m::Column> {
+                None
+            }
+            fn type_col() -> Option<$m::Column> {
+                None
+            }
+            fn resolve_property(_property: &str) -> Option<$m::Column> {
+                None
+            }
+        }
+    )*};
+}
+
+unrestricted!(tenant_closure, group_closure, group_membership);
+
+struct CreateDocs;
+
+impl mig::MigrationName for CreateDocs {
+    fn name(&self) -> &'static str {
+        "m001_create_hierarchy_doc"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateDocs {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("hierarchy_doc"))
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("id"))
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tenant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("hierarchy_doc"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+fn id(n: u128) -> Uuid {
+    Uuid::from_u128(n)
+}
+
+// Tenants: root(1) -> child(2) -> grandchild(3); root(1) -> self-managed(4).
+const ROOT: u128 = 1;
+const CHILD: u128 = 2;
+const GRANDCHILD: u128 = 3;
+const SELF_MANAGED: u128 = 4;
+
+// Groups: g10 -> g11. Docs are numbered 100 + owning tenant.
+const G_PARENT: u128 = 10;
+const G_CHILD: u128 = 11;
+
+async fn setup() -> Db {
+    let db = connect_db("sqlite::memory:", ConnectOpts::default())
+        .await
+        .expect("db connect");
+    run_migrations_for_testing(&db, vec![projections::migration(), Box::new(CreateDocs)])
+        .await
+        .map_err(|e| anyhow!(e.to_string()))
+        .expect("migrate");
+
+    let conn = db.conn().expect("conn");
+    let all = AccessScope::allow_all();
+
+    let closure = [
+        (ROOT, ROOT, 0, "active"),
+        (ROOT, CHILD, 0, "active"),
+        (ROOT, GRANDCHILD, 0, "suspended"),
+        (ROOT, SELF_MANAGED, 1, "active"),
+        (CHILD, CHILD, 0, "active"),
+        (CHILD, GRANDCHILD, 0, "suspended"),
+        (GRANDCHILD, GRANDCHILD, 0, "suspended"),
+        (SELF_MANAGED, SELF_MANAGED, 0, "active"),
+    ];
+    for (ancestor, descendant, barrier, status) in closure {
+        let am = tenant_closure::ActiveModel {
+            ancestor_id: Set(id(ancestor)),
+            descendant_id: Set(id(descendant)),
+            barrier: Set(barrier),
+            descendant_status: Set(status.to_owned()),
+        };
+        secure_insert::<tenant_closure::Entity>(am, &all, &conn)
+            .await
+            .expect("closure insert");
+    }
+
+    for (ancestor, descendant) in [
+        (G_PARENT, G_PARENT),
+        (G_PARENT, G_CHILD),
+        (G_CHILD, G_CHILD),
+    ] {
+        let am = group_closure::ActiveModel {
+            ancestor_id: Set(id(ancestor)),
+            descendant_id: Set(id(descendant)),
+        };
+        secure_insert::<group_closure::Entity>(am, &all, &conn)
+            .await
+            .expect("group closure insert");
+    }
+
+    for tenant in [ROOT, CHILD, GRANDCHILD, SELF_MANAGED] {
+        let am = doc::ActiveModel {
+            id: Set(id(100 + tenant)),
+            tenant_id: Set(id(tenant)),
+        };
+        secure_insert::<doc::Entity>(am, &all, &conn)
+            .await
+            .expect("doc insert");
+    }
+
+    for (group, doc) in [(G_PARENT, 100 + ROOT), (G_CHILD, 100 + GRANDCHILD)] {
+        let am = group_membership::ActiveModel {
+            group_id: Set(id(group)),
+            resource_id: Set(id(doc)),
+        };
+        secure_insert::<group_membership::Entity>(am, &all, &conn)
+            .await
+            .expect("membership insert");
+    }
+
+    db
+}
+
+async fn visible_docs(db: &Db, filters: Vec<ScopeFilter>) -> Vec<u128> {
+    let conn = db.conn().expect("conn");
+    let scope = AccessScope::single(ScopeConstraint::new(filters));
+    let mut ids: Vec<u128> = doc::Entity::find()
+        .secure()
+        .scope_with(&scope)
+        .all(&conn)
+        .await
+        .expect("select")
+        .into_iter()
+        .map(|m| m.id.as_u128())
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
+#[tokio::test]
+async fn tenant_subtree_respects_barriers_and_status() {
+    let db = setup().await;
+    let subtree = InTenantSubtreeScopeFilter::new(pep_properties::OWNER_TENANT_ID, id(ROOT));
+
+    let docs = visible_docs(&db, vec![ScopeFilter::InTenantSubtree(subtree.clone())]).await;
+    assert_eq!(docs, [101, 102, 103]);
+
+    let docs = visible_docs(
+        &db,
+        vec![ScopeFilter::InTenantSubtree(
+            subtree.clone().ignore_barriers(),
+        )],
+    )
+    .await;
+    assert_eq!(docs, [101, 102, 103, 104]);
+
+    let docs = visible_docs(
+        &db,
+        vec![ScopeFilter::InTenantSubtree(
+            subtree.with_tenant_statuses(vec!["active".to_owned()]),
+        )],
+    )
+    .await;
+    assert_eq!(docs, [101, 102]);
+
+    let docs = visible_docs(
+        &db,
+        vec![ScopeFilter::in_tenant_subtree(
+            pep_properties::OWNER_TENANT_ID,
+            id(CHILD),
+        )],
+    )
+    .await;
+    assert_eq!(docs, [102, 103]);
+}
+
+#[tokio::test]
+async fn group_filters_match_members_and_nested_groups() {
+    let db = setup().await;
+
+    let direct = visible_docs(
+        &db,
+        vec![ScopeFilter::in_group(
+            pep_properties::RESOURCE_ID,
+            vec![id(G_PARENT)],
+        )],
+    )
+    .await;
+    assert_eq!(direct, [101]);
+
+    let nested = visible_docs(
+        &db,
+        vec![ScopeFilter::in_group_subtree(
+            pep_properties::RESOURCE_ID,
+            id(G_PARENT),
+        )],
+    )
+    .await;
+    assert_eq!(nested, [101, 103]);
+
+    // Group predicates are combined with a tenant predicate (defense in depth).
+    let in_child_tenant = visible_docs(
+        &db,
+        vec![
+            ScopeFilter::in_tenant_subtree(pep_properties::OWNER_TENANT_ID, id(CHILD)),
+            ScopeFilter::in_group_subtree(pep_properties::RESOURCE_ID, id(G_PARENT)),
+        ],
+    )
+    .await;
+    assert_eq!(in_child_tenant, [103]);
+}
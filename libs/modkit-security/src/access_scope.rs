@@ -110,18 +110,27 @@ pub mod pep_properties {
 /// Variants mirror the predicate types from the PDP response:
 /// - [`ScopeFilter::Eq`] — equality (`property = value`)
 /// - [`ScopeFilter::In`] — set membership (`property IN (values)`)
+/// - [`ScopeFilter::InTenantSubtree`] — tenant subtree via the `tenant_closure` table
+/// - [`ScopeFilter::InGroup`] — group membership via `resource_group_membership`
+/// - [`ScopeFilter::InGroupSubtree`] — group subtree via `resource_group_closure`
 ///
-/// ## Future extensions
-///
-/// Additional filter types (`in_tenant_subtree`, `in_group`,
-/// `in_group_subtree`) are planned. See the authorization design document
-/// (`docs/arch/authorization/DESIGN.md`) for the full predicate taxonomy.
+/// The hierarchy variants are resolved by the database against local
+/// projection tables, so they have no enumerable values: value-based checks
+/// such as [`AccessScope::contains_value`] never match them (fail-closed).
+/// See the authorization design document (`docs/arch/authorization/DESIGN.md`)
+/// for the full predicate taxonomy.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ScopeFilter {
     /// Equality: `property = value`.
     Eq(EqScopeFilter),
     /// Set membership: `property IN (values)`.
     In(InScopeFilter),
+    /// Tenant subtree: `property IN (descendants of root_tenant_id)`.
+    InTenantSubtree(InTenantSubtreeScopeFilter),
+    /// Group membership: `property IN (members of group_ids)`.
+    InGroup(InGroupScopeFilter),
+    /// Group subtree: `property IN (members of root_group_id or its descendants)`.
+    InGroupSubtree(InGroupSubtreeScopeFilter),
 }
 
 /// Equality scope filter: `property = value`.
@@ -142,6 +151,39 @@ pub struct InScopeFilter {
     values: Vec<ScopeValue>,
 }
 
+/// Tenant subtree scope filter: `property IN (descendants of root_tenant_id)`.
+///
+/// The subtree includes the root tenant itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InTenantSubtreeScopeFilter {
+    /// Authorization property holding a tenant ID (e.g., `pep_properties::OWNER_TENANT_ID`).
+    property: String,
+    /// Root of the tenant subtree.
+    root_tenant_id: Uuid,
+    /// Stop at self-managed tenants (barriers) below the root.
+    respect_barriers: bool,
+    /// Allowed descendant statuses; empty means any status.
+    tenant_statuses: Vec<String>,
+}
+
+/// Group membership scope filter: `property IN (members of group_ids)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InGroupScopeFilter {
+    /// Authorization property joined with group membership (typically `pep_properties::RESOURCE_ID`).
+    property: String,
+    /// Groups whose members match.
+    group_ids: Vec<Uuid>,
+}
+
+/// Group subtree scope filter: `property IN (members of root_group_id or its descendants)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InGroupSubtreeScopeFilter {
+    /// Authorization property joined with group membership (typically `pep_properties::RESOURCE_ID`).
+    property: String,
+    /// Root of the group subtree.
+    root_group_id: Uuid,
+}
+
 impl EqScopeFilter {
     /// Create an equality scope filter.
     #[must_use]
@@ -204,6 +246,111 @@ impl InScopeFilter {
     }
 }
 
+impl InTenantSubtreeScopeFilter {
+    /// Create a tenant subtree filter that respects barriers and accepts any status.
+    #[must_use]
+    pub fn new(property: impl Into<String>, root_tenant_id: Uuid) -> Self {
+        Self {
+            property: property.into(),
+            root_tenant_id,
+            respect_barriers: true,
+            tenant_statuses: Vec::new(),
+        }
+    }
+
+    /// Traverse through self-managed tenants (barriers).
+    #[must_use]
+    pub fn ignore_barriers(mut self) -> Self {
+        self.respect_barriers = false;
+        self
+    }
+
+    /// Only match descendants whose status is one of `statuses`.
+    #[must_use]
+    pub fn with_tenant_statuses(mut self, statuses: Vec<String>) -> Self {
+        self.tenant_statuses = statuses;
+        self
+    }
+
+    /// The authorization property name.
+    #[inline]
+    #[must_use]
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// Root of the tenant subtree.
+    #[inline]
+    #[must_use]
+    pub fn root_tenant_id(&self) -> Uuid {
+        self.root_tenant_id
+    }
+
+    /// Whether traversal stops at barriers.
+    #[inline]
+    #[must_use]
+    pub fn respect_barriers(&self) -> bool {
+        self.respect_barriers
+    }
+
+    /// Allowed descendant statuses (empty: any).
+    #[inline]
+    #[must_use]
+    pub fn tenant_statuses(&self) -> &[String] {
+        &self.tenant_statuses
+    }
+}
+
+impl InGroupScopeFilter {
+    /// Create a group membership filter.
+    #[must_use]
+    pub fn new(property: impl Into<String>, group_ids: Vec<Uuid>) -> Self {
+        Self {
+            property: property.into(),
+            group_ids,
+        }
+    }
+
+    /// The authorization property name.
+    #[inline]
+    #[must_use]
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// Groups whose members match.
+    #[inline]
+    #[must_use]
+    pub fn group_ids(&self) -> &[Uuid] {
+        &self.group_ids
+    }
+}
+
+impl InGroupSubtreeScopeFilter {
+    /// Create a group subtree filter.
+    #[must_use]
+    pub fn new(property: impl Into<String>, root_group_id: Uuid) -> Self {
+        Self {
+            property: property.into(),
+            root_group_id,
+        }
+    }
+
+    /// The authorization property name.
+    #[inline]
+    #[must_use]
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// Root of the group subtree.
+    #[inline]
+    #[must_use]
+    pub fn root_group_id(&self) -> Uuid {
+        self.root_group_id
+    }
+}
+
 impl ScopeFilter {
     /// Create an equality filter (`property = value`).
     #[must_use]
@@ -226,23 +373,55 @@ impl ScopeFilter {
         ))
     }
 
+    /// Create a tenant subtree filter (respects barriers, any status).
+    #[must_use]
+    pub fn in_tenant_subtree(property: impl Into<String>, root_tenant_id: Uuid) -> Self {
+        Self::InTenantSubtree(InTenantSubtreeScopeFilter::new(property, root_tenant_id))
+    }
+
+    /// Create a group membership filter.
+    #[must_use]
+    pub fn in_group(property: impl Into<String>, group_ids: Vec<Uuid>) -> Self {
+        Self::InGroup(InGroupScopeFilter::new(property, group_ids))
+    }
+
+    /// Create a group subtree filter.
+    #[must_use]
+    pub fn in_group_subtree(property: impl Into<String>, root_group_id: Uuid) -> Self {
+        Self::InGroupSubtree(InGroupSubtreeScopeFilter::new(property, root_group_id))
+    }
+
     /// The authorization property name.
     #[must_use]
     pub fn property(&self) -> &str {
         match self {
             Self::Eq(f) => f.property(),
             Self::In(f) => f.property(),
+            Self::InTenantSubtree(f) => f.property(),
+            Self::InGroup(f) => f.property(),
+            Self::InGroupSubtree(f) => f.property(),
         }
     }
 
+    /// `true` for filters resolved through projection tables rather than
+    /// literal values.
+    #[must_use]
+    pub fn is_hierarchical(&self) -> bool {
+        !matches!(self, Self::Eq(_) | Self::In(_))
+    }
+
     /// Collect all values as a slice-like view for iteration.
     ///
     /// For `Eq`, returns a single-element slice; for `In`, returns the values slice.
+    /// Hierarchy filters have no literal values and return an empty slice.
     #[must_use]
     pub fn values(&self) -> ScopeFilterValues<'_> {
         match self {
             Self::Eq(f) => ScopeFilterValues::Single(&f.value),
             Self::In(f) => ScopeFilterValues::Multiple(&f.values),
+            Self::InTenantSubtree(_) | Self::InGroup(_) | Self::InGroupSubtree(_) => {
+                ScopeFilterValues::Multiple(&[])
+            }
         }
     }
 
@@ -600,4 +779,54 @@ mod tests {
         assert!(scope.contains_uuid(pep_properties::OWNER_TENANT_ID, uid(T1)));
         assert!(!scope.contains_uuid(pep_properties::OWNER_TENANT_ID, uid(T2)));
     }
+
+    // --- Hierarchy filters ---
+
+    #[test]
+    fn tenant_subtree_filter_defaults_and_builders() {
+        let f = InTenantSubtreeScopeFilter::new(pep_properties::OWNER_TENANT_ID, uid(T1));
+        assert!(f.respect_barriers());
+        assert!(f.tenant_statuses().is_empty());
+
+        let f = f
+            .ignore_barriers()
+            .with_tenant_statuses(vec!["active".to_owned()]);
+        assert!(!f.respect_barriers());
+        assert_eq!(f.tenant_statuses(), ["active"]);
+        assert_eq!(f.root_tenant_id(), uid(T1));
+    }
+
+    #[test]
+    fn hierarchy_filters_have_no_values() {
+        let scope = AccessScope::from_constraints(vec![
+            ScopeConstraint::new(vec![ScopeFilter::in_tenant_subtree(
+                pep_properties::OWNER_TENANT_ID,
+                uid(T1),
+            )]),
+            ScopeConstraint::new(vec![ScopeFilter::in_group(
+                pep_properties::RESOURCE_ID,
+                vec![uid(T2)],
+            )]),
+            ScopeConstraint::new(vec![ScopeFilter::in_group_subtree(
+                pep_properties::RESOURCE_ID,
+                uid(T2),
+            )]),
+        ]);
+
+        assert!(scope.has_property(pep_properties::OWNER_TENANT_ID));
+        assert!(scope.has_property(pep_properties::RESOURCE_ID));
+        assert!(
+            scope
+                .constraints()
+                .iter()
+                .all(|c| c.filters()[0].is_hierarchical())
+        );
+        // Subtree membership is decided by the database, never in memory.
+        assert!(!scope.contains_uuid(pep_properties::OWNER_TENANT_ID, uid(T1)));
+        assert!(
+            scope
+                .all_values_for(pep_properties::OWNER_TENANT_ID)
+                .is_empty()
+        );
+    }
 }
@@ -0,0 +1,348 @@
+//! Audit hook for REST operations
+//!
+//! `OperationBuilder::audited` wraps a mutating operation (POST, PUT, PATCH,
+//! DELETE) so that, once the handler has produced a response, an
+//! [`OperationAuditor`] is told who called which operation and how it ended.
+//! Modkit only defines the hook; the audit module provides the auditor that
+//! persists the records.
+//!
+//! Records are handed to the auditor on a background task through a bounded
+//! queue, so the response does not wait for the audit write. When the queue
+//! is full the response waits up to [`AuditQueueConfig::send_timeout`] for
+//! room; after that the record is dropped, logged and counted in
+//! `modkit_audit_dropped_total`.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use http::{Method, StatusCode};
+use modkit_security::SecurityContext;
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::{SendTimeoutError, TrySendError};
+
+/// A completed call of an audited operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditedOperation {
+    pub operation_id: Option<String>,
+    pub method: Method,
+    /// Route template, e.g. `/oagw/v1/upstreams/{id}`.
+    pub route: String,
+    /// Request path, e.g. `/oagw/v1/upstreams/42`.
+    pub path: String,
+    pub status: StatusCode,
+}
+
+impl AuditedOperation {
+    /// `true` unless the response is a client or server error.
+    #[must_use]
+    pub fn succeeded(&self) -> bool {
+        !(self.status.is_client_error() || self.status.is_server_error())
+    }
+
+    /// Path parameters, resolved by matching `path` against `route`.
+    ///
+    /// A trailing wildcard (`{*rest}`) captures the remainder of the path.
+    #[must_use]
+    pub fn path_params(&self) -> BTreeMap<String, String> {
+        let mut params = BTreeMap::new();
+        let mut values = self.path.trim_matches('/').split('/');
+        for segment in self.route.trim_matches('/').split('/') {
+            let Some(name) = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) else {
+                values.next();
+                continue;
+            };
+            if let Some(name) = name.strip_prefix('*') {
+                let rest: Vec<&str> = values.by_ref().collect();
+                params.insert(name.to_owned(), rest.join("/"));
+                break;
+            }
+            if let Some(value) = values.next() {
+                params.insert(name.to_owned(), value.to_owned());
+            }
+        }
+        params
+    }
+}
+
+/// Queue between an audited route and its auditor, meant to be embedded in
+/// a module configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuditQueueConfig {
+    /// Operations of one audited route waiting for the auditor.
+    pub capacity: usize,
+
+    /// How long a response waits for room in a full queue before its
+    /// operation is dropped.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub send_timeout: Duration,
+}
+
+impl Default for AuditQueueConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            send_timeout: Duration::from_millis(50),
+        }
+    }
+}
+
+/// Receives the audited operations of a module.
+#[async_trait]
+pub trait OperationAuditor: Send + Sync {
+    /// Queue settings of the routes this auditor is attached to.
+    fn queue_config(&self) -> AuditQueueConfig {
+        AuditQueueConfig::default()
+    }
+
+    /// Record `operation`, performed by the caller described by `ctx`.
+    ///
+    /// Called off the request path, after the response has been sent; an
+    /// error is logged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record could not be stored.
+    async fn record(
+        &self,
+        ctx: &SecurityContext,
+        operation: AuditedOperation,
+    ) -> anyhow::Result<()>;
+}
+
+/// `true` for the methods `OperationBuilder::audited` applies to.
+#[must_use]
+pub fn is_mutating(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    )
+}
+
+type QueuedOperation = (SecurityContext, AuditedOperation);
+
+/// Feeds an auditor from a background task.
+///
+/// The task is spawned on first use, so routes can be built outside a
+/// runtime, and ends once the route (and with it the sender) is dropped.
+pub(crate) struct AuditQueue {
+    auditor: Arc<dyn OperationAuditor>,
+    config: AuditQueueConfig,
+    tx: OnceLock<mpsc::Sender<QueuedOperation>>,
+    dropped: AtomicU64,
+    metrics: metrics::AuditMetrics,
+}
+
+impl AuditQueue {
+    pub(crate) fn new(auditor: Arc<dyn OperationAuditor>, route: &str) -> Self {
+        Self {
+            config: auditor.queue_config(),
+            auditor,
+            tx: OnceLock::new(),
+            dropped: AtomicU64::new(0),
+            metrics: metrics::AuditMetrics::new(route),
+        }
+    }
+
+    async fn push(&self, route: &str, ctx: SecurityContext, operation: AuditedOperation) {
+        let tx = self.tx.get_or_init(|| {
+            let (tx, rx) = mpsc::channel(self.config.capacity.max(1));
+            tokio::spawn(drain(self.auditor.clone(), rx));
+            tx
+        });
+        let reason = match tx.try_send((ctx, operation)) {
+            Ok(()) => return,
+            Err(TrySendError::Full(queued)) => {
+                match tx.send_timeout(queued, self.config.send_timeout).await {
+                    Ok(()) => return,
+                    Err(SendTimeoutError::Timeout(_)) => "queue_full",
+                    Err(SendTimeoutError::Closed(_)) => "stopped",
+                }
+            }
+            Err(TrySendError::Closed(_)) => "stopped",
+        };
+        let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+        self.metrics.dropped(reason);
+        tracing::warn!(route, reason, dropped, "operation not audited");
+    }
+
+    /// Operations dropped so far.
+    #[cfg(test)]
+    fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+async fn drain(auditor: Arc<dyn OperationAuditor>, mut rx: mpsc::Receiver<QueuedOperation>) {
+    while let Some((ctx, operation)) = rx.recv().await {
+        let route = operation.route.clone();
+        if let Err(e) = auditor.record(&ctx, operation).await {
+            tracing::warn!(%route, error = %e, "failed to record audit event");
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct AuditLayerState {
+    pub(crate) queue: Arc<AuditQueue>,
+    pub(crate) operation_id: Option<String>,
+    pub(crate) route: String,
+}
+
+/// Route middleware installed by `OperationBuilder::audited`.
+///
+/// Requests without a `SecurityContext` (public routes, or auth disabled)
+/// have no actor and are not recorded.
+pub(crate) async fn audit_middleware(
+    State(state): State<AuditLayerState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let ctx = req.extensions().get::<SecurityContext>().cloned();
+    let method = req.method().clone();
+    let path = req.uri().path().to_owned();
+
+    let response = next.run(req).await;
+
+    let Some(ctx) = ctx else {
+        tracing::debug!(route = %state.route, "no security context; operation not audited");
+        return response;
+    };
+    let operation = AuditedOperation {
+        operation_id: state.operation_id.clone(),
+        method,
+        route: state.route.clone(),
+        path,
+        status: response.status(),
+    };
+    state.queue.push(&state.route, ctx, operation).await;
+    response
+}
+
+#[cfg(feature = "otel")]
+mod metrics {
+    use opentelemetry::KeyValue;
+    use opentelemetry::global;
+    use opentelemetry::metrics::Counter;
+
+    /// Instruments from the global meter provider; no-ops without one.
+    pub(super) struct AuditMetrics {
+        route: KeyValue,
+        /// `modkit_audit_dropped_total{route, reason}`
+        dropped: Counter<u64>,
+    }
+
+    impl AuditMetrics {
+        pub(super) fn new(route: &str) -> Self {
+            let meter = global::meter("modkit");
+            Self {
+                route: KeyValue::new("route", route.to_owned()),
+                dropped: meter
+                    .u64_counter("modkit_audit_dropped_total")
+                    .with_description("Audited operations dropped before the auditor, by reason")
+                    .build(),
+            }
+        }
+
+        pub(super) fn dropped(&self, reason: &'static str) {
+            self.dropped
+                .add(1, &[self.route.clone(), KeyValue::new("reason", reason)]);
+        }
+    }
+}
+
+#[cfg(not(feature = "otel"))]
+mod metrics {
+    pub(super) struct AuditMetrics;
+
+    impl AuditMetrics {
+        pub(super) fn new(_route: &str) -> Self {
+            Self
+        }
+
+        pub(super) fn dropped(&self, _reason: &'static str) {}
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn operation(route: &str, path: &str) -> AuditedOperation {
+        AuditedOperation {
+            operation_id: None,
+            method: Method::PATCH,
+            route: route.to_owned(),
+            path: path.to_owned(),
+            status: StatusCode::OK,
+        }
+    }
+
+    #[test]
+    fn path_params_follow_the_route_template() {
+        let params = operation(
+            "/oagw/v1/upstreams/{upstream_id}/routes/{id}",
+            "/oagw/v1/upstreams/u1/routes/r2",
+        )
+        .path_params();
+        assert_eq!(params.get("upstream_id").map(String::as_str), Some("u1"));
+        assert_eq!(params.get("id").map(String::as_str), Some("r2"));
+
+        let params = operation("/oagw/v1/proxy/{*path}", "/oagw/v1/proxy/a/b/c").path_params();
+        assert_eq!(params.get("path").map(String::as_str), Some("a/b/c"));
+
+        assert!(operation("/settings", "/settings").path_params().is_empty());
+    }
+
+    /// Never finishes recording; its queue holds a single operation.
+    struct StalledAuditor;
+
+    #[async_trait]
+    impl OperationAuditor for StalledAuditor {
+        fn queue_config(&self) -> AuditQueueConfig {
+            AuditQueueConfig {
+                capacity: 1,
+                send_timeout: Duration::from_millis(20),
+            }
+        }
+
+        async fn record(
+            &self,
+            _ctx: &SecurityContext,
+            _operation: AuditedOperation,
+        ) -> anyhow::Result<()> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn full_queue_waits_then_drops() {
+        let queue = AuditQueue::new(Arc::new(StalledAuditor), "/x");
+        let ctx = SecurityContext::anonymous();
+
+        // The first operation is taken by the stalled auditor, the second
+        // fills the queue, the third times out.
+        for _ in 0..3 {
+            queue.push("/x", ctx.clone(), operation("/x", "/x")).await;
+        }
+        assert_eq!(queue.dropped(), 1);
+    }
+
+    #[test]
+    fn error_statuses_are_failures() {
+        let mut op = operation("/x", "/x");
+        assert!(op.succeeded());
+        op.status = StatusCode::NOT_FOUND;
+        assert!(!op.succeeded());
+        op.status = StatusCode::INTERNAL_SERVER_ERROR;
+        assert!(!op.succeeded());
+    }
+}
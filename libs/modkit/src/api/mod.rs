@@ -5,6 +5,7 @@
 //! response are specified.
 
 pub mod api_dto;
+pub mod audit;
 pub mod error_layer;
 pub mod odata;
 pub mod openapi_registry;
@@ -18,6 +19,7 @@ pub mod trace_layer;
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod odata_policy_tests;
 
+pub use audit::{AuditQueueConfig, AuditedOperation, OperationAuditor};
 pub use error_layer::{
     IntoProblem, error_mapping_middleware, extract_trace_id, map_error_to_problem,
 };
@@ -11,12 +11,16 @@
 //!   then use plain function handlers (no per-route closures that capture/clones).
 //! - Optional `method_router(...)` for advanced use (layers/middleware on route level).
 
+use crate::api::audit::{
+    AuditLayerState, AuditQueue, OperationAuditor, audit_middleware, is_mutating,
+};
 use crate::api::{api_dto, problem};
 use axum::{Router, handler::Handler, routing::MethodRouter};
 use http::Method;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 /// Convert OpenAPI-style path placeholders to Axum 0.8+ style path parameters.
 ///
@@ -1027,6 +1031,48 @@ where
     }
 }
 
+// -------------------------------------------------------------------------------------------------
+// Auditing — wraps the handler, so only available once it is set
+// -------------------------------------------------------------------------------------------------
+impl<R, S, A, L> OperationBuilder<Present, R, S, A, L>
+where
+    S: Clone + Send + Sync + 'static,
+    A: AuthState,
+    L: LicenseState,
+{
+    /// Report every call of this operation to `auditor` once the response is
+    /// ready, without holding the response until the record is stored. Only
+    /// a full queue holds it, for at most the auditor's
+    /// [`AuditQueueConfig::send_timeout`](crate::api::AuditQueueConfig).
+    ///
+    /// Only mutating operations (POST, PUT, PATCH, DELETE) are audited; for
+    /// other methods, and when `auditor` is `None`, this is a no-op, so a
+    /// module can pass an auditor that is only present when the audit module
+    /// is deployed.
+    pub fn audited(self, auditor: impl Into<Option<Arc<dyn OperationAuditor>>>) -> Self {
+        let Some(auditor) = auditor.into() else {
+            return self;
+        };
+        if !is_mutating(&self.spec.method) {
+            return self;
+        }
+        let state = AuditLayerState {
+            queue: Arc::new(AuditQueue::new(auditor, &self.spec.path)),
+            operation_id: self.spec.operation_id.clone(),
+            route: self.spec.path.clone(),
+        };
+        Self {
+            method_router: self
+                .method_router
+                .layer(axum::middleware::from_fn_with_state(
+                    state,
+                    audit_middleware,
+                )),
+            ..self
+        }
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 // Response setting — transitions Missing -> Present for response (first response)
 // -------------------------------------------------------------------------------------------------
@@ -2135,4 +2181,134 @@ mod tests {
             );
         }
     }
+
+    /// Forwards audited operations to a channel, which closes once every
+    /// route holding the auditor is dropped and its queue is drained.
+    struct RecordingAuditor {
+        seen: tokio::sync::mpsc::UnboundedSender<(uuid::Uuid, crate::api::AuditedOperation)>,
+    }
+
+    #[async_trait::async_trait]
+    impl OperationAuditor for RecordingAuditor {
+        async fn record(
+            &self,
+            ctx: &modkit_security::SecurityContext,
+            operation: crate::api::AuditedOperation,
+        ) -> anyhow::Result<()> {
+            self.seen.send((ctx.subject_id(), operation))?;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn audited_records_mutating_calls_with_security_context() {
+        use tower::ServiceExt;
+
+        let registry = MockRegistry::new();
+        let (seen, mut records) = tokio::sync::mpsc::unbounded_channel();
+        let hook: Arc<dyn OperationAuditor> = Arc::new(RecordingAuditor { seen });
+
+        let mut router = OperationBuilder::<Missing, Missing, ()>::delete("/tests/v1/items/{id}")
+            .operation_id("tests.delete_item")
+            .public()
+            .handler(test_handler)
+            .audited(hook.clone())
+            .json_response(http::StatusCode::OK, "Deleted")
+            .register(Router::new(), &registry);
+        router = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/items/{id}")
+            .public()
+            .handler(test_handler)
+            .audited(hook)
+            .json_response(http::StatusCode::OK, "Item")
+            .register(router, &registry);
+
+        let subject = uuid::Uuid::new_v4();
+        let ctx = modkit_security::SecurityContext::builder()
+            .subject_id(subject)
+            .subject_tenant_id(uuid::Uuid::new_v4())
+            .build()
+            .unwrap();
+        let router = router.layer(axum::Extension(ctx));
+
+        for method in [Method::GET, Method::DELETE] {
+            let response = router
+                .clone()
+                .oneshot(
+                    http::Request::builder()
+                        .method(method)
+                        .uri("/tests/v1/items/42")
+                        .body(axum::body::Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), http::StatusCode::OK);
+        }
+
+        // Records are stored in the background; dropping the routes lets the
+        // queue drain and then closes the channel.
+        drop(router);
+        let mut seen = Vec::new();
+        while let Some(record) = records.recv().await {
+            seen.push(record);
+        }
+        assert_eq!(seen.len(), 1, "only the DELETE is audited");
+        let (actor, op) = &seen[0];
+        assert_eq!(*actor, subject);
+        assert_eq!(op.operation_id.as_deref(), Some("tests.delete_item"));
+        assert_eq!(op.method, Method::DELETE);
+        assert_eq!(op.route, "/tests/v1/items/{id}");
+        assert_eq!(op.path, "/tests/v1/items/42");
+        assert_eq!(op.status, http::StatusCode::OK);
+    }
+
+    /// Never finishes recording.
+    struct StalledAuditor;
+
+    #[async_trait::async_trait]
+    impl OperationAuditor for StalledAuditor {
+        async fn record(
+            &self,
+            _ctx: &modkit_security::SecurityContext,
+            _operation: crate::api::AuditedOperation,
+        ) -> anyhow::Result<()> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn audited_responses_do_not_wait_for_the_auditor() {
+        use tower::ServiceExt;
+
+        let registry = MockRegistry::new();
+        let router = OperationBuilder::<Missing, Missing, ()>::delete("/tests/v1/items/{id}")
+            .public()
+            .handler(test_handler)
+            .audited(Arc::new(StalledAuditor) as Arc<dyn OperationAuditor>)
+            .json_response(http::StatusCode::OK, "Deleted")
+            .register(Router::new(), &registry);
+        let ctx = modkit_security::SecurityContext::builder()
+            .subject_id(uuid::Uuid::new_v4())
+            .subject_tenant_id(uuid::Uuid::new_v4())
+            .build()
+            .unwrap();
+        let router = router.layer(axum::Extension(ctx));
+
+        for _ in 0..3 {
+            let response = tokio::time::timeout(
+                std::time::Duration::from_secs(5),
+                router.clone().oneshot(
+                    http::Request::builder()
+                        .method(Method::DELETE)
+                        .uri("/tests/v1/items/42")
+                        .body(axum::body::Body::empty())
+                        .unwrap(),
+                ),
+            )
+            .await
+            .expect("response not held by the auditor")
+            .unwrap();
+            assert_eq!(response.status(), http::StatusCode::OK);
+        }
+    }
 }
@@ -0,0 +1,735 @@
+//! Bounded in-memory TTL cache with single-flight loading.
+//!
+//! [`Cache`] memoizes the results of remote calls (plugin clients, gRPC
+//! proxies) for a short time:
+//!
+//! - every entry expires after the configured TTL, or sooner when the value
+//!   itself expires earlier ([`Cache::get_or_try_insert_with_ttl`]);
+//! - when the cache is full, expired entries are dropped first, then the entry
+//!   closest to expiry; entries are indexed by expiry, so this costs
+//!   `O(log n)` per dropped entry;
+//! - concurrent misses for the same key share a single load
+//!   ([`Cache::get_or_try_insert_with`]); failed loads are never cached;
+//! - invalidation is explicit ([`Cache::invalidate`], [`Cache::invalidate_if`],
+//!   [`Cache::clear`]), and a load that overlaps an invalidation does not
+//!   store its possibly stale result.
+//!
+//! Request outcomes and evictions are counted per cache ([`Cache::stats`]) and,
+//! with the `otel` feature, exported as `modkit_cache_requests_total{cache,
+//! outcome}` and `modkit_cache_evictions_total{cache, reason}`.
+//!
+//! ```rust,ignore
+//! let cache = Cache::from_config("tenant-resolver.get_tenant", &cfg.cache);
+//! let tenant = match &cache {
+//!     Some(cache) => cache.get_or_try_insert_with(key, || plugin.get_tenant(ctx, id)).await?,
+//!     None => plugin.get_tenant(ctx, id).await?,
+//! };
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use modkit_security::SecurityContext;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Cache settings, meant to be embedded in a module configuration.
+///
+/// Caching is opt-in: the default is disabled.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    /// Enable the cache.
+    pub enabled: bool,
+
+    /// How long an entry is served before it is loaded again.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub ttl: Duration,
+
+    /// Maximum number of entries.
+    pub max_entries: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl: Duration::from_secs(30),
+            max_entries: 10_000,
+        }
+    }
+}
+
+/// Counters of a [`Cache`], see [`Cache::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups answered from the cache.
+    pub hits: u64,
+    /// Lookups that had to load (or, for [`Cache::get`], found nothing).
+    pub misses: u64,
+    /// Lookups answered by a load another caller was already running.
+    pub coalesced: u64,
+    /// Entries dropped to make room for new ones.
+    pub evictions: u64,
+    /// Entries removed by invalidation.
+    pub invalidations: u64,
+}
+
+/// Identity part of a cache key derived from a [`SecurityContext`].
+///
+/// Results computed on behalf of a caller must only be served to the same
+/// caller; embedding this key keeps entries of different subjects, tenants
+/// or token scopes apart. The bearer token itself is not part of the key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SubjectKey {
+    subject_id: Uuid,
+    subject_type: Option<String>,
+    subject_tenant_id: Uuid,
+    token_scopes: Vec<String>,
+}
+
+impl From<&SecurityContext> for SubjectKey {
+    fn from(ctx: &SecurityContext) -> Self {
+        Self {
+            subject_id: ctx.subject_id(),
+            subject_type: ctx.subject_type().map(ToOwned::to_owned),
+            subject_tenant_id: ctx.subject_tenant_id(),
+            token_scopes: ctx.token_scopes().to_vec(),
+        }
+    }
+}
+
+struct Entry<V> {
+    value: V,
+    expires_at: Instant,
+    /// Insertion sequence number; tells apart entries expiring together.
+    seq: u64,
+}
+
+impl<V> Entry<V> {
+    fn expiry_key(&self) -> (Instant, u64) {
+        (self.expires_at, self.seq)
+    }
+}
+
+struct State<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    /// Keys of `entries` by expiry, soonest first.
+    expiry: BTreeMap<(Instant, u64), K>,
+    next_seq: u64,
+    /// Per-key lock held by the caller currently loading that key.
+    in_flight: HashMap<K, Arc<tokio::sync::Mutex<()>>>,
+    /// Bumped by every invalidation; loads started under an older
+    /// generation do not store their result.
+    generation: u64,
+}
+
+impl<K: Hash + Eq, V: Clone> State<K, V> {
+    fn fresh(&mut self, key: &K, now: Instant) -> Option<V> {
+        match self.entries.get(key) {
+            Some(entry) if entry.expires_at > now => Some(entry.value.clone()),
+            Some(_) => {
+                self.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        match self.entries.remove(key) {
+            Some(entry) => {
+                self.expiry.remove(&entry.expiry_key());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove the entry closest to expiry if it expires by `deadline`, or
+    /// regardless of its expiry without one.
+    fn pop_soonest(&mut self, deadline: Option<Instant>) -> bool {
+        match self.expiry.first_entry() {
+            Some(first) if deadline.is_none_or(|deadline| first.key().0 <= deadline) => {
+                let key = first.remove();
+                self.entries.remove(&key);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Bounded TTL cache with single-flight loading. See the [module docs](self).
+pub struct Cache<K, V> {
+    name: String,
+    ttl: Duration,
+    max_entries: usize,
+    state: Mutex<State<K, V>>,
+    counters: Counters,
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Build the cache described by `cfg`, or `None` if it is disabled.
+    #[must_use]
+    pub fn from_config(name: impl Into<String>, cfg: &CacheConfig) -> Option<Self> {
+        (cfg.enabled && cfg.max_entries > 0 && !cfg.ttl.is_zero())
+            .then(|| Self::new(name, cfg.ttl, cfg.max_entries))
+    }
+
+    /// Create a cache holding at most `max_entries` entries for `ttl` each.
+    ///
+    /// `name` identifies the cache in metrics.
+    #[must_use]
+    pub fn new(name: impl Into<String>, ttl: Duration, max_entries: usize) -> Self {
+        let name = name.into();
+        Self {
+            counters: Counters::new(&name),
+            name,
+            ttl,
+            max_entries,
+            state: Mutex::new(State {
+                entries: HashMap::new(),
+                expiry: BTreeMap::new(),
+                next_seq: 0,
+                in_flight: HashMap::new(),
+                generation: 0,
+            }),
+        }
+    }
+
+    /// Name of the cache, as reported in metrics.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The cached value for `key`, if still fresh.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let value = self.state.lock().fresh(key, Instant::now());
+        self.counters.record(if value.is_some() {
+            Outcome::Hit
+        } else {
+            Outcome::Miss
+        });
+        value
+    }
+
+    /// Cache `value` for `key`, replacing any previous entry.
+    pub fn insert(&self, key: K, value: V) {
+        self.insert_with_ttl(key, value, self.ttl);
+    }
+
+    /// Cache `value` for `key` for at most `ttl`, capped by the cache TTL.
+    /// A zero `ttl` only removes any previous entry.
+    pub fn insert_with_ttl(&self, key: K, value: V, ttl: Duration) {
+        let mut state = self.state.lock();
+        self.store(&mut state, key, value, ttl);
+    }
+
+    /// The cached value for `key`, or the result of `load`, which is cached
+    /// on success.
+    ///
+    /// Concurrent callers missing the same key wait for a single `load`; if
+    /// it fails, each of them retries in turn.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `load`; errors are not cached.
+    pub async fn get_or_try_insert_with<F, Fut, E>(&self, key: K, load: F) -> Result<V, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        let ttl = self.ttl;
+        self.get_or_try_insert_with_ttl(
+            key,
+            || async move { load().await.map(|value| (value, ttl)) },
+        )
+        .await
+    }
+
+    /// Like [`get_or_try_insert_with`](Self::get_or_try_insert_with), for
+    /// values that expire on their own: `load` also returns how long the
+    /// value stays valid. The entry lives for that long at most, capped by
+    /// the cache TTL; a zero lifetime is returned but not cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `load`; errors are not cached.
+    pub async fn get_or_try_insert_with_ttl<F, Fut, E>(&self, key: K, load: F) -> Result<V, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<(V, Duration), E>>,
+    {
+        let lock = {
+            let mut state = self.state.lock();
+            if let Some(value) = state.fresh(&key, Instant::now()) {
+                self.counters.record(Outcome::Hit);
+                return Ok(value);
+            }
+            Arc::clone(state.in_flight.entry(key.clone()).or_default())
+        };
+
+        let _loading = lock.lock().await;
+        let generation = {
+            let mut state = self.state.lock();
+            if let Some(value) = state.fresh(&key, Instant::now()) {
+                self.counters.record(Outcome::Coalesced);
+                return Ok(value);
+            }
+            state.generation
+        };
+        self.counters.record(Outcome::Miss);
+
+        let result = load().await;
+
+        let mut state = self.state.lock();
+        if state
+            .in_flight
+            .get(&key)
+            .is_some_and(|current| Arc::ptr_eq(current, &lock))
+        {
+            state.in_flight.remove(&key);
+        }
+        match result {
+            Ok((value, ttl)) => {
+                if state.generation == generation {
+                    self.store(&mut state, key, value.clone(), ttl);
+                }
+                Ok(value)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Remove the entry for `key`. Returns whether there was one.
+    pub fn invalidate(&self, key: &K) -> bool {
+        let mut state = self.state.lock();
+        state.generation += 1;
+        let removed = state.remove(key);
+        if removed {
+            self.counters.invalidated(1);
+        }
+        removed
+    }
+
+    /// Remove every entry matching `predicate`. Returns how many were removed.
+    pub fn invalidate_if(&self, mut predicate: impl FnMut(&K, &V) -> bool) -> usize {
+        let mut state = self.state.lock();
+        state.generation += 1;
+        let before = state.entries.len();
+        let State {
+            entries, expiry, ..
+        } = &mut *state;
+        entries.retain(|key, entry| {
+            let remove = predicate(key, &entry.value);
+            if remove {
+                expiry.remove(&entry.expiry_key());
+            }
+            !remove
+        });
+        let removed = before - state.entries.len();
+        self.counters.invalidated(removed);
+        removed
+    }
+
+    /// Remove every entry.
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+        state.generation += 1;
+        let removed = state.entries.len();
+        state.entries.clear();
+        state.expiry.clear();
+        self.counters.invalidated(removed);
+    }
+
+    /// Number of entries, including expired ones not yet dropped.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    /// Whether the cache holds no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Counters accumulated since the cache was created.
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        self.counters.snapshot()
+    }
+
+    fn store(&self, state: &mut State<K, V>, key: K, value: V, ttl: Duration) {
+        let now = Instant::now();
+        state.remove(&key);
+        let ttl = ttl.min(self.ttl);
+        if ttl.is_zero() {
+            return;
+        }
+        if state.entries.len() >= self.max_entries {
+            let mut expired = 0;
+            while state.pop_soonest(Some(now)) {
+                expired += 1;
+            }
+            self.counters.evicted("expired", expired);
+
+            if state.entries.len() >= self.max_entries && state.pop_soonest(None) {
+                self.counters.evicted("capacity", 1);
+            }
+        }
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        let entry = Entry {
+            value,
+            expires_at: now + ttl,
+            seq,
+        };
+        state.expiry.insert(entry.expiry_key(), key.clone());
+        state.entries.insert(key, entry);
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Outcome {
+    Hit,
+    Miss,
+    Coalesced,
+}
+
+impl Outcome {
+    #[cfg(feature = "otel")]
+    fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Hit => "hit",
+            Outcome::Miss => "miss",
+            Outcome::Coalesced => "coalesced",
+        }
+    }
+}
+
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    coalesced: AtomicU64,
+    evictions: AtomicU64,
+    invalidations: AtomicU64,
+    #[cfg(feature = "otel")]
+    metrics: otel::CacheMetrics,
+}
+
+impl Counters {
+    #[cfg_attr(not(feature = "otel"), allow(unused_variables))]
+    fn new(name: &str) -> Self {
+        Self {
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            coalesced: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            invalidations: AtomicU64::new(0),
+            #[cfg(feature = "otel")]
+            metrics: otel::CacheMetrics::new(name),
+        }
+    }
+
+    fn record(&self, outcome: Outcome) {
+        let counter = match outcome {
+            Outcome::Hit => &self.hits,
+            Outcome::Miss => &self.misses,
+            Outcome::Coalesced => &self.coalesced,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "otel")]
+        self.metrics.request(outcome.as_str());
+    }
+
+    #[cfg_attr(not(feature = "otel"), allow(unused_variables))]
+    fn evicted(&self, reason: &'static str, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.evictions.fetch_add(count as u64, Ordering::Relaxed);
+        #[cfg(feature = "otel")]
+        self.metrics.evicted(reason, count as u64);
+    }
+
+    fn invalidated(&self, count: usize) {
+        if count > 0 {
+            self.invalidations
+                .fetch_add(count as u64, Ordering::Relaxed);
+            #[cfg(feature = "otel")]
+            self.metrics.evicted("invalidated", count as u64);
+        }
+    }
+
+    fn snapshot(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(feature = "otel")]
+mod otel {
+    use opentelemetry::KeyValue;
+    use opentelemetry::global;
+    use opentelemetry::metrics::Counter;
+
+    /// Instruments from the global meter provider; no-ops without one.
+    pub(super) struct CacheMetrics {
+        name: KeyValue,
+        /// `modkit_cache_requests_total{cache, outcome}`
+        requests: Counter<u64>,
+        /// `modkit_cache_evictions_total{cache, reason}`
+        evictions: Counter<u64>,
+    }
+
+    impl CacheMetrics {
+        pub(super) fn new(name: &str) -> Self {
+            let meter = global::meter("modkit");
+            Self {
+                name: KeyValue::new("cache", name.to_owned()),
+                requests: meter
+                    .u64_counter("modkit_cache_requests_total")
+                    .with_description("Cache lookups, by outcome")
+                    .build(),
+                evictions: meter
+                    .u64_counter("modkit_cache_evictions_total")
+                    .with_description("Cache entries removed before expiry, by reason")
+                    .build(),
+            }
+        }
+
+        pub(super) fn request(&self, outcome: &'static str) {
+            self.requests
+                .add(1, &[self.name.clone(), KeyValue::new("outcome", outcome)]);
+        }
+
+        pub(super) fn evicted(&self, reason: &'static str, count: u64) {
+            self.evictions
+                .add(count, &[self.name.clone(), KeyValue::new("reason", reason)]);
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+
+    fn cache(ttl: Duration, max_entries: usize) -> Cache<&'static str, u32> {
+        Cache::new("test", ttl, max_entries)
+    }
+
+    #[test]
+    fn disabled_config_builds_no_cache() {
+        assert!(Cache::<u32, u32>::from_config("c", &CacheConfig::default()).is_none());
+        let cfg = CacheConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(Cache::<u32, u32>::from_config("c", &cfg).is_some());
+    }
+
+    #[test]
+    fn config_parses_humantime_ttl() {
+        let cfg: CacheConfig =
+            serde_json::from_value(serde_json::json!({"enabled": true, "ttl": "5s"})).unwrap();
+        assert!(cfg.enabled);
+        assert_eq!(cfg.ttl, Duration::from_secs(5));
+        assert_eq!(cfg.max_entries, 10_000);
+    }
+
+    #[test]
+    fn entries_expire_after_ttl() {
+        let cache = cache(Duration::from_millis(20), 10);
+        cache.insert("a", 1);
+        assert_eq!(cache.get(&"a"), Some(1));
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert_eq!(cache.get(&"a"), None);
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 1,
+                misses: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn full_cache_evicts_the_entry_closest_to_expiry() {
+        let cache = cache(Duration::from_secs(60), 2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.insert("b", 3);
+        assert_eq!(cache.stats().evictions, 0);
+
+        cache.insert("c", 4);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&"a"), None);
+        assert_eq!(cache.get(&"b"), Some(3));
+        assert_eq!(cache.get(&"c"), Some(4));
+        assert_eq!(cache.stats().evictions, 1);
+    }
+
+    #[test]
+    fn expired_entries_are_evicted_before_live_ones() {
+        let cache = cache(Duration::from_secs(60), 3);
+        cache.insert_with_ttl("short", 1, Duration::from_millis(10));
+        cache.insert("a", 2);
+        cache.insert("b", 3);
+        std::thread::sleep(Duration::from_millis(20));
+
+        cache.insert("c", 4);
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.get(&"a"), Some(2));
+        assert_eq!(cache.get(&"b"), Some(3));
+        assert_eq!(cache.get(&"c"), Some(4));
+        assert_eq!(cache.stats().evictions, 1);
+
+        // Invalidated entries leave the expiry index too.
+        cache.invalidate(&"a");
+        cache.invalidate_if(|key, _| *key == "b");
+        assert_eq!(cache.state.lock().expiry.len(), 1);
+    }
+
+    #[test]
+    fn per_entry_ttl_is_capped_by_the_cache_ttl() {
+        let cache = cache(Duration::from_millis(20), 10);
+        cache.insert_with_ttl("long", 1, Duration::from_secs(60));
+        cache.insert_with_ttl("zero", 2, Duration::ZERO);
+        assert_eq!(cache.get(&"long"), Some(1));
+        assert_eq!(cache.get(&"zero"), None);
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert_eq!(cache.get(&"long"), None);
+    }
+
+    #[tokio::test]
+    async fn loads_with_zero_lifetime_are_not_cached() {
+        let cache = cache(Duration::from_secs(60), 10);
+        let value = cache
+            .get_or_try_insert_with_ttl("k", || async { Ok::<_, ()>((1, Duration::ZERO)) })
+            .await;
+        assert_eq!(value, Ok(1));
+        assert!(cache.is_empty());
+
+        let value = cache
+            .get_or_try_insert_with_ttl("k", || async {
+                Ok::<_, ()>((2, Duration::from_millis(10)))
+            })
+            .await;
+        assert_eq!(value, Ok(2));
+        assert_eq!(cache.get(&"k"), Some(2));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(cache.get(&"k"), None);
+    }
+
+    #[test]
+    fn invalidation_removes_entries() {
+        let cache = cache(Duration::from_secs(60), 10);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.insert("c", 3);
+
+        assert!(cache.invalidate(&"a"));
+        assert!(!cache.invalidate(&"a"));
+        assert_eq!(cache.invalidate_if(|_, v| *v == 2), 1);
+        assert_eq!(cache.get(&"c"), Some(3));
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats().invalidations, 3);
+    }
+
+    #[tokio::test]
+    async fn concurrent_misses_share_one_load() {
+        let cache = Arc::new(cache(Duration::from_secs(60), 10));
+        let loads = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let cache = Arc::clone(&cache);
+                let loads = Arc::clone(&loads);
+                tokio::spawn(async move {
+                    cache
+                        .get_or_try_insert_with("k", || async move {
+                            loads.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(20)).await;
+                            Ok::<_, ()>(7)
+                        })
+                        .await
+                })
+            })
+            .collect();
+        for task in tasks {
+            assert_eq!(task.await.unwrap(), Ok(7));
+        }
+
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        let stats = cache.stats();
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.hits + stats.coalesced, 7);
+    }
+
+    #[tokio::test]
+    async fn failed_loads_are_not_cached() {
+        let cache = cache(Duration::from_secs(60), 10);
+        let err = cache
+            .get_or_try_insert_with("k", || async { Err::<u32, _>("boom") })
+            .await;
+        assert_eq!(err, Err("boom"));
+        assert!(cache.is_empty());
+
+        let ok = cache
+            .get_or_try_insert_with("k", || async { Ok::<_, &str>(1) })
+            .await;
+        assert_eq!(ok, Ok(1));
+        assert_eq!(cache.get(&"k"), Some(1));
+    }
+
+    #[tokio::test]
+    async fn load_overlapping_invalidation_is_not_stored() {
+        let cache = cache(Duration::from_secs(60), 10);
+        let value = cache
+            .get_or_try_insert_with("k", || async {
+                cache.clear();
+                Ok::<_, ()>(1)
+            })
+            .await;
+        assert_eq!(value, Ok(1));
+        assert_eq!(cache.get(&"k"), None);
+    }
+
+    #[test]
+    fn subject_key_separates_callers() {
+        let ctx = |subject: u128, tenant: u128| {
+            SecurityContext::builder()
+                .subject_id(Uuid::from_u128(subject))
+                .subject_tenant_id(Uuid::from_u128(tenant))
+                .build()
+                .unwrap()
+        };
+        assert_eq!(SubjectKey::from(&ctx(1, 1)), SubjectKey::from(&ctx(1, 1)));
+        assert_ne!(SubjectKey::from(&ctx(1, 1)), SubjectKey::from(&ctx(2, 1)));
+        assert_ne!(SubjectKey::from(&ctx(1, 1)), SubjectKey::from(&ctx(1, 2)));
+    }
+}
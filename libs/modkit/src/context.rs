@@ -208,6 +208,35 @@ impl ModuleCtx {
         })
     }
 
+    /// Build an outbox dispatcher on this module's database.
+    ///
+    /// The dispatcher stops with the module (it watches the module's
+    /// cancellation token). Register handlers, then call `spawn()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database is not configured for this module.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let _dispatcher = ctx
+    ///     .outbox_dispatcher(cfg.outbox)?
+    ///     .handler("mini-chat", "usage.settled", publisher)
+    ///     .spawn();
+    /// ```
+    #[cfg(feature = "db")]
+    pub fn outbox_dispatcher(
+        &self,
+        cfg: crate::outbox::OutboxDispatcherConfig,
+    ) -> anyhow::Result<crate::outbox::OutboxDispatcher> {
+        Ok(crate::outbox::OutboxDispatcher::new(
+            self.db_required()?,
+            cfg,
+            self.cancellation_token.clone(),
+        ))
+    }
+
     /// Deserialize the module's config section into T, or use defaults if missing.
     ///
     /// This method uses lenient configuration loading: if the module is not present in config,
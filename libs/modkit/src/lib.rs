@@ -117,7 +117,10 @@ pub use http::sse::SseBroadcaster;
 pub mod telemetry;
 
 pub mod backends;
+pub mod cache;
 pub mod lifecycle;
+#[cfg(feature = "db")]
+pub mod outbox;
 pub mod plugins;
 pub mod runtime;
 
@@ -0,0 +1,734 @@
+//! Background dispatcher for the transactional outbox.
+//!
+//! Producers write events with [`enqueue`] inside their transactions (see
+//! [`modkit_db::outbox`]). A module that consumes them builds an
+//! [`OutboxDispatcher`] from its context, registers one [`OutboxHandler`] per
+//! `(namespace, topic)` and spawns it:
+//!
+//! ```rust,ignore
+//! async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+//!     let cfg: Config = ctx.config()?;
+//!     // Dropping the handle detaches the task; it stops with the module.
+//!     let _dispatcher = ctx
+//!         .outbox_dispatcher(cfg.outbox)?
+//!         .handler("mini-chat", "usage.settled", UsagePublisher::new(billing))
+//!         .spawn();
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The dispatcher polls every `poll_interval` until the module is stopped.
+//! The messages of a claimed batch are passed to their handlers one after
+//! another, each bounded by what is left of the batch's lease: `Ok`
+//! acknowledges it, `Err` or a timeout schedules a retry with backoff, and a
+//! message that used up `max_attempts` is moved to `dead`. Messages whose
+//! lease ran out before their turn are released for the next poll without
+//! counting an attempt. Delivery is at-least-once, so handlers must be
+//! idempotent.
+//!
+//! Every `retention_interval` the dispatcher deletes delivered messages older
+//! than `delivered_retention` and, if `dead_retention` is set, dead messages
+//! older than that.
+//!
+//! With the `otel` feature the dispatcher exports
+//! `modkit_outbox_messages_total{namespace, topic, outcome}` and, every
+//! `metrics_interval`, the `modkit_outbox_lag_seconds`,
+//! `modkit_outbox_pending_rows` and `modkit_outbox_dead_rows` gauges per
+//! namespace.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use modkit_db::outbox::{ClaimCfg, OutboxStore, RetentionCfg, RetryPolicy};
+use modkit_db::{DBProvider, DbError};
+use serde::Deserialize;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+pub use modkit_db::outbox::{ClaimedMessage, OutboxMessage, OutboxStats, enqueue};
+
+/// Publishes outbox messages of one `(namespace, topic)`.
+#[async_trait]
+pub trait OutboxHandler: Send + Sync {
+    /// Deliver `msg`. Returning an error schedules a retry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message could not be delivered.
+    async fn handle(&self, msg: &ClaimedMessage) -> anyhow::Result<()>;
+}
+
+/// Dispatcher settings, meant to be embedded in a module configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutboxDispatcherConfig {
+    /// Delay between polls when the outbox is drained. Keep it well below
+    /// `lease_duration`.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub poll_interval: Duration,
+
+    /// Maximum number of messages claimed per poll and namespace.
+    pub batch_size: u32,
+
+    /// How long a claimed batch stays leased. Its handlers run within the
+    /// lease, so it bounds the total handling time of a batch.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub lease_duration: Duration,
+
+    /// Maximum total delivery attempts before a message is dead-lettered.
+    pub max_attempts: u32,
+
+    /// Retry delay after the first failure; doubles on every further failure.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub base_delay: Duration,
+
+    /// Upper bound of the retry delay.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub max_delay: Duration,
+
+    /// How often the lag and row-count gauges are refreshed.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub metrics_interval: Duration,
+
+    /// How long delivered messages are kept before they are deleted.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub delivered_retention: Duration,
+
+    /// How long dead messages are kept before they are deleted; unset keeps
+    /// them until removed by hand.
+    #[serde(with = "modkit_utils::humantime_serde::option")]
+    pub dead_retention: Option<Duration>,
+
+    /// How often finished messages past their retention are deleted.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub retention_interval: Duration,
+}
+
+impl Default for OutboxDispatcherConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(1),
+            batch_size: 100,
+            lease_duration: Duration::from_secs(30),
+            max_attempts: 10,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(300),
+            metrics_interval: Duration::from_secs(30),
+            delivered_retention: Duration::from_secs(24 * 60 * 60),
+            dead_retention: Some(Duration::from_secs(30 * 24 * 60 * 60)),
+            retention_interval: Duration::from_secs(10 * 60),
+        }
+    }
+}
+
+impl OutboxDispatcherConfig {
+    fn claim_cfg(&self) -> ClaimCfg {
+        ClaimCfg {
+            batch_size: self.batch_size,
+            lease_duration: self.lease_duration,
+            max_attempts: self.max_attempts,
+        }
+    }
+
+    fn retention_cfg(&self) -> RetentionCfg {
+        RetentionCfg {
+            delivered: self.delivered_retention,
+            dead: self.dead_retention,
+        }
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.max_attempts,
+            base_delay: self.base_delay,
+            max_delay: self.max_delay,
+        }
+    }
+}
+
+type Handlers = BTreeMap<String, Arc<dyn OutboxHandler>>;
+
+/// Polls the outbox and hands claimed messages to registered handlers.
+#[must_use]
+pub struct OutboxDispatcher {
+    db: DBProvider<DbError>,
+    worker_id: Uuid,
+    cfg: OutboxDispatcherConfig,
+    cancel: CancellationToken,
+    /// namespace -> topic -> handler
+    handlers: BTreeMap<String, Handlers>,
+}
+
+impl OutboxDispatcher {
+    /// Dispatcher running until `cancel` fires, with a fresh worker id.
+    pub fn new(
+        db: DBProvider<DbError>,
+        cfg: OutboxDispatcherConfig,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self {
+            db,
+            worker_id: Uuid::now_v7(),
+            cfg,
+            cancel,
+            handlers: BTreeMap::new(),
+        }
+    }
+
+    /// Deliver messages of `(namespace, topic)` to `handler`, replacing any
+    /// handler registered for the same pair.
+    pub fn handler(
+        mut self,
+        namespace: impl Into<String>,
+        topic: impl Into<String>,
+        handler: impl OutboxHandler + 'static,
+    ) -> Self {
+        self.handlers
+            .entry(namespace.into())
+            .or_default()
+            .insert(topic.into(), Arc::new(handler));
+        self
+    }
+
+    /// Lease owner recorded on claimed rows.
+    #[must_use]
+    pub fn worker_id(&self) -> Uuid {
+        self.worker_id
+    }
+
+    /// Run [`Self::run`] on a background task.
+    #[must_use]
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    /// Poll until cancelled.
+    pub async fn run(self) {
+        let claim = self.cfg.claim_cfg();
+        let retry = self.cfg.retry_policy();
+        let retention = self.cfg.retention_cfg();
+        let lanes: Vec<Lane> = self
+            .handlers
+            .into_iter()
+            .map(|(namespace, handlers)| Lane {
+                store: OutboxStore::new(self.db.clone(), self.worker_id, namespace.clone())
+                    .with_topics(handlers.keys().cloned())
+                    .with_retry_policy(retry),
+                metrics: metrics::OutboxMetrics::new(&namespace),
+                handlers,
+            })
+            .collect();
+        if lanes.is_empty() {
+            debug!("outbox dispatcher has no handlers; not polling");
+            return;
+        }
+
+        let mut ticker = tokio::time::interval(self.cfg.poll_interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut metrics_due = Instant::now();
+        let mut purge_due = Instant::now();
+        loop {
+            tokio::select! {
+                () = self.cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+            for lane in &lanes {
+                // Keep claiming while batches come back full.
+                loop {
+                    let claimed = lane.dispatch_batch(claim).await;
+                    if claimed == 0
+                        || claimed < claim.batch_size as usize
+                        || self.cancel.is_cancelled()
+                    {
+                        break;
+                    }
+                }
+            }
+            if Instant::now() >= metrics_due {
+                for lane in &lanes {
+                    lane.record_stats().await;
+                }
+                metrics_due = Instant::now() + self.cfg.metrics_interval;
+            }
+            if Instant::now() >= purge_due {
+                for lane in &lanes {
+                    lane.purge(retention).await;
+                }
+                purge_due = Instant::now() + self.cfg.retention_interval;
+            }
+        }
+        debug!(worker_id = %self.worker_id, "outbox dispatcher stopped");
+    }
+}
+
+/// Handlers and store of one namespace.
+struct Lane {
+    store: OutboxStore<DbError>,
+    handlers: Handlers,
+    metrics: metrics::OutboxMetrics,
+}
+
+impl Lane {
+    /// Claim and deliver one batch; returns the number of claimed messages.
+    async fn dispatch_batch(&self, claim: ClaimCfg) -> usize {
+        // Taken before the claim, so it never ends after the stored lease.
+        let lease_end = Instant::now() + claim.lease_duration;
+        let messages = match self.store.claim_batch(claim).await {
+            Ok(messages) => messages,
+            Err(e) => {
+                warn!(namespace = self.store.namespace(), error = %e, "outbox claim failed");
+                return 0;
+            }
+        };
+        for msg in &messages {
+            self.deliver(msg, lease_end).await;
+        }
+        messages.len()
+    }
+
+    async fn deliver(&self, msg: &ClaimedMessage, lease_end: Instant) {
+        let Some(handler) = self.handlers.get(&msg.topic) else {
+            // Claims are restricted to registered topics.
+            return;
+        };
+        // Past the lease another worker may claim the message, so the
+        // handler only gets the time left.
+        let timeout = lease_end.saturating_duration_since(Instant::now());
+        if timeout.is_zero() {
+            let outcome = match self.store.release(msg.id).await {
+                Ok(()) => "released",
+                Err(e) => {
+                    warn!(id = %msg.id, topic = %msg.topic, error = %e, "outbox release failed");
+                    "lease_lost"
+                }
+            };
+            self.metrics.message(&msg.topic, outcome);
+            return;
+        }
+        let outcome = match tokio::time::timeout(timeout, handler.handle(msg)).await {
+            Ok(Ok(())) => match self.store.ack(msg.id).await {
+                Ok(()) => "delivered",
+                Err(e) => {
+                    // Another worker owns the row now; it is not a publish failure.
+                    warn!(id = %msg.id, topic = %msg.topic, error = %e, "outbox ack failed");
+                    "lease_lost"
+                }
+            },
+            Ok(Err(e)) => self.fail(msg, &format!("{e:#}")).await,
+            Err(_) => {
+                self.fail(msg, &format!("handler timed out after {timeout:?}"))
+                    .await
+            }
+        };
+        self.metrics.message(&msg.topic, outcome);
+    }
+
+    async fn fail(&self, msg: &ClaimedMessage, error: &str) -> &'static str {
+        warn!(
+            id = %msg.id,
+            namespace = %msg.namespace,
+            topic = %msg.topic,
+            attempts = msg.attempts,
+            error,
+            "outbox delivery failed"
+        );
+        if let Err(e) = self.store.nack(msg.id, error).await {
+            warn!(id = %msg.id, error = %e, "outbox nack failed");
+        }
+        "failed"
+    }
+
+    async fn purge(&self, retention: RetentionCfg) {
+        match self.store.purge(retention).await {
+            Ok(0) => {}
+            Ok(count) => {
+                debug!(
+                    namespace = self.store.namespace(),
+                    count, "outbox rows purged"
+                );
+            }
+            Err(e) => {
+                warn!(namespace = self.store.namespace(), error = %e, "outbox purge failed");
+            }
+        }
+    }
+
+    async fn record_stats(&self) {
+        match self.store.stats().await {
+            Ok(stats) => self.metrics.stats(&stats),
+            Err(e) => {
+                warn!(namespace = self.store.namespace(), error = %e, "outbox stats failed");
+            }
+        }
+    }
+}
+
+#[cfg(feature = "otel")]
+mod metrics {
+    use opentelemetry::KeyValue;
+    use opentelemetry::global;
+    use opentelemetry::metrics::{Counter, Gauge};
+
+    use super::OutboxStats;
+
+    /// Instruments from the global meter provider; no-ops without one.
+    pub(super) struct OutboxMetrics {
+        namespace: KeyValue,
+        /// `modkit_outbox_messages_total{namespace, topic, outcome}`
+        messages: Counter<u64>,
+        /// `modkit_outbox_lag_seconds{namespace}`
+        lag: Gauge<f64>,
+        /// `modkit_outbox_pending_rows{namespace}`
+        pending: Gauge<u64>,
+        /// `modkit_outbox_dead_rows{namespace}`
+        dead: Gauge<u64>,
+    }
+
+    impl OutboxMetrics {
+        pub(super) fn new(namespace: &str) -> Self {
+            let meter = global::meter("modkit");
+            Self {
+                namespace: KeyValue::new("namespace", namespace.to_owned()),
+                messages: meter
+                    .u64_counter("modkit_outbox_messages_total")
+                    .with_description("Outbox delivery attempts, by outcome")
+                    .build(),
+                lag: meter
+                    .f64_gauge("modkit_outbox_lag_seconds")
+                    .with_description("Age of the oldest undelivered outbox message")
+                    .build(),
+                pending: meter
+                    .u64_gauge("modkit_outbox_pending_rows")
+                    .with_description("Outbox messages waiting for delivery")
+                    .build(),
+                dead: meter
+                    .u64_gauge("modkit_outbox_dead_rows")
+                    .with_description("Outbox messages that ran out of attempts")
+                    .build(),
+            }
+        }
+
+        pub(super) fn message(&self, topic: &str, outcome: &'static str) {
+            self.messages.add(
+                1,
+                &[
+                    self.namespace.clone(),
+                    KeyValue::new("topic", topic.to_owned()),
+                    KeyValue::new("outcome", outcome),
+                ],
+            );
+        }
+
+        pub(super) fn stats(&self, stats: &OutboxStats) {
+            let attrs = [self.namespace.clone()];
+            self.lag.record(stats.lag().as_secs_f64(), &attrs);
+            self.pending
+                .record(stats.pending + stats.processing, &attrs);
+            self.dead.record(stats.dead, &attrs);
+        }
+    }
+}
+
+#[cfg(not(feature = "otel"))]
+mod metrics {
+    use super::OutboxStats;
+
+    pub(super) struct OutboxMetrics;
+
+    impl OutboxMetrics {
+        pub(super) fn new(_namespace: &str) -> Self {
+            Self
+        }
+
+        pub(super) fn message(&self, _topic: &str, _outcome: &'static str) {}
+
+        pub(super) fn stats(&self, _stats: &OutboxStats) {}
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::sync::Mutex;
+
+    use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::{ConnectOpts, connect_db};
+    use serde_json::json;
+
+    use super::*;
+
+    /// Fails the first `failures` deliveries, then records the payloads.
+    struct Recorder {
+        failures: Mutex<u32>,
+        seen: Arc<Mutex<Vec<serde_json::Value>>>,
+    }
+
+    #[async_trait]
+    impl OutboxHandler for Recorder {
+        async fn handle(&self, msg: &ClaimedMessage) -> anyhow::Result<()> {
+            {
+                let mut failures = self.failures.lock().unwrap();
+                if *failures > 0 {
+                    *failures -= 1;
+                    anyhow::bail!("downstream unavailable");
+                }
+            }
+            self.seen.lock().unwrap().push(msg.payload.clone());
+            Ok(())
+        }
+    }
+
+    async fn setup() -> DBProvider<DbError> {
+        let db = connect_db("sqlite::memory:", ConnectOpts::default())
+            .await
+            .unwrap();
+        run_migrations_for_testing(&db, vec![modkit_db::outbox::migration()])
+            .await
+            .unwrap();
+        DBProvider::new(db)
+    }
+
+    fn fast_config() -> OutboxDispatcherConfig {
+        OutboxDispatcherConfig {
+            poll_interval: Duration::from_millis(10),
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            max_attempts: 3,
+            ..OutboxDispatcherConfig::default()
+        }
+    }
+
+    async fn put(db: &DBProvider<DbError>, topic: &'static str, n: u32) {
+        let conn = db.conn().unwrap();
+        enqueue(
+            &conn,
+            OutboxMessage {
+                namespace: "test",
+                topic,
+                tenant_id: None,
+                dedupe_key: None,
+                payload: json!({ "n": n }),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn wait_for(mut done: impl FnMut() -> bool) {
+        for _ in 0..200 {
+            if done() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("condition not reached");
+    }
+
+    #[tokio::test]
+    async fn delivers_with_retries_and_stops_on_cancel() {
+        let db = setup().await;
+        put(&db, "a", 1).await;
+        put(&db, "unhandled", 2).await;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let cancel = CancellationToken::new();
+        let task = OutboxDispatcher::new(db.clone(), fast_config(), cancel.clone())
+            .handler(
+                "test",
+                "a",
+                Recorder {
+                    failures: Mutex::new(2),
+                    seen: seen.clone(),
+                },
+            )
+            .spawn();
+
+        wait_for(|| !seen.lock().unwrap().is_empty()).await;
+        assert_eq!(*seen.lock().unwrap(), vec![json!({ "n": 1 })]);
+
+        cancel.cancel();
+        tokio::time::timeout(Duration::from_secs(5), task)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // The unhandled topic is left alone.
+        let stats = OutboxStore::new(db, Uuid::nil(), "test")
+            .stats()
+            .await
+            .unwrap();
+        assert_eq!((stats.pending, stats.processing, stats.dead), (1, 0, 0));
+    }
+
+    #[tokio::test]
+    async fn dead_letters_after_max_attempts() {
+        let db = setup().await;
+        put(&db, "a", 1).await;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let cancel = CancellationToken::new();
+        let task = OutboxDispatcher::new(db.clone(), fast_config(), cancel.clone())
+            .handler(
+                "test",
+                "a",
+                Recorder {
+                    failures: Mutex::new(u32::MAX),
+                    seen: seen.clone(),
+                },
+            )
+            .spawn();
+
+        let store = OutboxStore::new(db, Uuid::nil(), "test");
+        for _ in 0..200 {
+            if store.stats().await.unwrap().dead == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        cancel.cancel();
+        task.await.unwrap();
+
+        let stats = store.stats().await.unwrap();
+        assert_eq!((stats.pending, stats.dead), (0, 1));
+        assert!(seen.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn purges_delivered_messages_past_retention() {
+        let db = setup().await;
+        let conn = db.conn().unwrap();
+        let msg = OutboxMessage {
+            namespace: "test",
+            topic: "a",
+            tenant_id: None,
+            dedupe_key: Some("k".to_owned()),
+            payload: json!({ "n": 1 }),
+        };
+        let first = enqueue(&conn, msg.clone()).await.unwrap();
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let cancel = CancellationToken::new();
+        let cfg = OutboxDispatcherConfig {
+            delivered_retention: Duration::ZERO,
+            retention_interval: Duration::from_millis(10),
+            ..fast_config()
+        };
+        let task = OutboxDispatcher::new(db.clone(), cfg, cancel.clone())
+            .handler(
+                "test",
+                "a",
+                Recorder {
+                    failures: Mutex::new(0),
+                    seen: seen.clone(),
+                },
+            )
+            .spawn();
+
+        wait_for(|| !seen.lock().unwrap().is_empty()).await;
+        // The dedupe key is free again once the delivered row is deleted.
+        let mut purged = false;
+        for _ in 0..200 {
+            if enqueue(&conn, msg.clone()).await.unwrap() != first {
+                purged = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        cancel.cancel();
+        task.await.unwrap();
+        assert!(purged);
+    }
+
+    /// Takes `delay` per message, then records its payload.
+    struct Slow {
+        delay: Duration,
+        seen: Arc<Mutex<Vec<serde_json::Value>>>,
+    }
+
+    #[async_trait]
+    impl OutboxHandler for Slow {
+        async fn handle(&self, msg: &ClaimedMessage) -> anyhow::Result<()> {
+            tokio::time::sleep(self.delay).await;
+            self.seen.lock().unwrap().push(msg.payload.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn slow_batch_is_handled_within_its_lease() {
+        let db = setup().await;
+        for n in 1..=3 {
+            put(&db, "a", n).await;
+        }
+
+        // One message fits in a lease; a second worker takes over whatever
+        // the first one's lease did not cover.
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let cancel = CancellationToken::new();
+        let cfg = OutboxDispatcherConfig {
+            lease_duration: Duration::from_millis(300),
+            max_attempts: 10,
+            ..fast_config()
+        };
+        let workers: Vec<_> = (0..2)
+            .map(|_| {
+                OutboxDispatcher::new(db.clone(), cfg.clone(), cancel.clone())
+                    .handler(
+                        "test",
+                        "a",
+                        Slow {
+                            delay: Duration::from_millis(200),
+                            seen: seen.clone(),
+                        },
+                    )
+                    .spawn()
+            })
+            .collect();
+
+        let store = OutboxStore::new(db, Uuid::nil(), "test");
+        let mut drained = false;
+        for _ in 0..500 {
+            let stats = store.stats().await.unwrap();
+            if (stats.pending, stats.processing, stats.dead) == (0, 0, 0) {
+                drained = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        cancel.cancel();
+        for worker in workers {
+            worker.await.unwrap();
+        }
+        assert!(drained, "all messages should be delivered");
+
+        // No handler ran past its lease, so nothing was delivered twice.
+        let mut seen = seen.lock().unwrap().clone();
+        seen.sort_by_key(|v| v["n"].as_u64());
+        assert_eq!(
+            seen,
+            vec![json!({ "n": 1 }), json!({ "n": 2 }), json!({ "n": 3 })]
+        );
+    }
+
+    #[test]
+    fn config_parses_humantime() {
+        let cfg: OutboxDispatcherConfig = serde_json::from_value(json!({
+            "poll_interval": "250ms",
+            "lease_duration": "1m",
+            "max_attempts": 5,
+        }))
+        .unwrap();
+        assert_eq!(cfg.poll_interval, Duration::from_millis(250));
+        assert_eq!(cfg.lease_duration, Duration::from_secs(60));
+        assert_eq!(cfg.max_attempts, 5);
+        assert_eq!(cfg.batch_size, 100);
+        assert_eq!(
+            cfg.dead_retention,
+            Some(Duration::from_secs(30 * 24 * 60 * 60))
+        );
+    }
+}
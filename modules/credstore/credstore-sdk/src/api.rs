@@ -1,8 +1,13 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
 use modkit_security::SecurityContext;
 
 use crate::error::CredStoreError;
-use crate::models::{GetSecretResponse, SecretRef};
+use crate::models::{
+    GetSecretResponse, RotateSecretResponse, SecretInfo, SecretRef, SecretValue, SecretVersion,
+    SharingMode,
+};
 
 /// Consumer-facing API trait for credential storage operations.
 ///
@@ -10,6 +15,11 @@ use crate::models::{GetSecretResponse, SecretRef};
 /// accept a `SecurityContext` from which the gateway derives tenant and
 /// owner identity. Access denial is expressed as `Ok(None)` from `get`,
 /// not as an error.
+///
+/// A key can exist twice within a tenant: once as the caller's `private`
+/// secret and once as a `tenant`/`shared` secret. Operations that address
+/// an existing secret (`get`, `get_version`, `rotate`, `delete`) resolve the
+/// caller's private secret first and fall back to the tenant-wide one.
 #[async_trait]
 pub trait CredStoreClientV1: Send + Sync {
     /// Retrieves a secret by reference.
@@ -25,4 +35,54 @@ pub trait CredStoreClientV1: Send + Sync {
         ctx: &SecurityContext,
         key: &SecretRef,
     ) -> Result<Option<GetSecretResponse>, CredStoreError>;
+
+    /// Retrieves a specific version of a secret.
+    ///
+    /// The current version is always readable; a version replaced by
+    /// `rotate` stays readable until its grace period ends. Returns
+    /// `Ok(None)` for unknown, expired or inaccessible versions.
+    async fn get_version(
+        &self,
+        ctx: &SecurityContext,
+        key: &SecretRef,
+        version: SecretVersion,
+    ) -> Result<Option<GetSecretResponse>, CredStoreError>;
+
+    /// Creates or replaces a secret owned by the caller and returns the new
+    /// version.
+    ///
+    /// `private` secrets are stored per owner, `tenant` and `shared` secrets
+    /// once per tenant. Replacing a value with `put` retires the previous
+    /// version immediately; use [`rotate`](Self::rotate) to keep it readable.
+    async fn put(
+        &self,
+        ctx: &SecurityContext,
+        key: &SecretRef,
+        value: SecretValue,
+        sharing: SharingMode,
+    ) -> Result<SecretVersion, CredStoreError>;
+
+    /// Replaces the value of an existing secret, keeping the previous version
+    /// readable for `grace_period`. The sharing mode is unchanged.
+    ///
+    /// Returns `CredStoreError::NotFound` if the secret does not exist or is
+    /// not accessible to the caller.
+    async fn rotate(
+        &self,
+        ctx: &SecurityContext,
+        key: &SecretRef,
+        value: SecretValue,
+        grace_period: Duration,
+    ) -> Result<RotateSecretResponse, CredStoreError>;
+
+    /// Deletes a secret together with all of its versions.
+    ///
+    /// Returns `CredStoreError::NotFound` if the secret does not exist or is
+    /// not accessible to the caller.
+    async fn delete(&self, ctx: &SecurityContext, key: &SecretRef) -> Result<(), CredStoreError>;
+
+    /// Lists metadata of the secrets visible to the caller: every `tenant`
+    /// and `shared` secret of the caller's tenant plus the caller's own
+    /// `private` secrets. Values are never returned.
+    async fn list(&self, ctx: &SecurityContext) -> Result<Vec<SecretInfo>, CredStoreError>;
 }
@@ -9,6 +9,15 @@ pub enum CredStoreError {
     #[error("secret not found")]
     NotFound,
 
+    #[error("secret already exists")]
+    AlreadyExists,
+
+    #[error("access denied")]
+    AccessDenied,
+
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+
     #[error("no plugin available")]
     NoPluginAvailable,
 
@@ -27,6 +36,11 @@ impl CredStoreError {
         }
     }
 
+    #[must_use]
+    pub fn invalid_request(msg: impl Into<String>) -> Self {
+        Self::InvalidRequest(msg.into())
+    }
+
     #[must_use]
     pub fn service_unavailable(msg: impl Into<String>) -> Self {
         Self::ServiceUnavailable(msg.into())
@@ -49,6 +63,15 @@ mod tests {
         assert_eq!(e.to_string(), "invalid secret reference: must not be empty");
     }
 
+    #[test]
+    fn invalid_request_constructor_sets_message() {
+        let e = CredStoreError::invalid_request("value must not be empty");
+        assert!(
+            matches!(e, CredStoreError::InvalidRequest(ref m) if m == "value must not be empty")
+        );
+        assert_eq!(e.to_string(), "invalid request: value must not be empty");
+    }
+
     #[test]
     fn service_unavailable_constructor_sets_message() {
         let e = CredStoreError::service_unavailable("backend down");
@@ -4,7 +4,8 @@
 //!
 //! - [`CredStoreClientV1`] — Consumer API trait for storing/retrieving secrets
 //! - [`CredStorePluginClientV1`] — Plugin API trait for backend storage adapters
-//! - [`SecretRef`], [`SecretValue`], [`SharingMode`], [`GetSecretResponse`], [`SecretMetadata`] — Domain models
+//! - [`SecretRef`], [`SecretValue`], [`SharingMode`], [`GetSecretResponse`], [`SecretMetadata`],
+//!   [`SecretInfo`], [`RotateSecretResponse`] — Domain models
 //! - [`CredStoreError`] — Error types
 //! - [`CredStorePluginSpecV1`] — GTS schema for plugin discovery
 //!
@@ -39,6 +40,7 @@ pub use api::CredStoreClientV1;
 pub use error::CredStoreError;
 pub use gts::CredStorePluginSpecV1;
 pub use models::{
-    GetSecretResponse, OwnerId, SecretMetadata, SecretRef, SecretValue, SharingMode, TenantId,
+    GetSecretResponse, OwnerId, RetiredVersion, RotateSecretResponse, SecretInfo, SecretMetadata,
+    SecretRef, SecretValue, SecretVersion, SharingMode, TenantId,
 };
 pub use plugin_api::CredStorePluginClientV1;
@@ -2,6 +2,7 @@ use std::fmt;
 
 use serde::de::Deserializer;
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::error::CredStoreError;
@@ -12,6 +13,10 @@ pub type TenantId = Uuid;
 /// Owner identifier, representing `SecurityContext.subject_id()`.
 pub type OwnerId = Uuid;
 
+/// Version number of a secret value. The first `put` creates version 1 and
+/// every subsequent write (`put` or `rotate`) increments it.
+pub type SecretVersion = u32;
+
 /// A validated secret reference key.
 ///
 /// Format: `[a-zA-Z0-9_-]+`, max 255 characters.
@@ -152,6 +157,8 @@ pub struct GetSecretResponse {
     /// `true` if the secret was retrieved from an ancestor tenant via
     /// hierarchical resolution, `false` if owned by the requesting tenant.
     pub is_inherited: bool,
+    /// Version of the returned value.
+    pub version: SecretVersion,
 }
 
 /// Metadata returned by plugins alongside the secret value.
@@ -161,6 +168,38 @@ pub struct SecretMetadata {
     pub owner_id: OwnerId,
     pub sharing: SharingMode,
     pub owner_tenant_id: TenantId,
+    pub version: SecretVersion,
+}
+
+/// A superseded secret version that stays readable until `expires_at`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetiredVersion {
+    pub version: SecretVersion,
+    pub expires_at: OffsetDateTime,
+}
+
+/// Secret metadata without the value, returned by `list`.
+#[derive(Debug, Clone)]
+pub struct SecretInfo {
+    pub key: SecretRef,
+    pub owner_id: OwnerId,
+    pub owner_tenant_id: TenantId,
+    pub sharing: SharingMode,
+    /// Current version.
+    pub version: SecretVersion,
+    /// Previous versions still inside their rotation grace period.
+    pub retired_versions: Vec<RetiredVersion>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+/// Response returned by [`CredStoreClientV1::rotate`](crate::CredStoreClientV1::rotate).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RotateSecretResponse {
+    /// Version holding the new value.
+    pub version: SecretVersion,
+    /// The replaced version, readable via `get_version` until it expires.
+    pub previous: RetiredVersion,
 }
 
 #[cfg(test)]
@@ -236,6 +275,7 @@ mod tests {
             owner_tenant_id: Uuid::nil(),
             sharing: SharingMode::Shared,
             is_inherited: true,
+            version: 1,
         };
         let debug = format!("{resp:?}");
         assert!(debug.contains("[REDACTED]"));
@@ -250,6 +290,7 @@ mod tests {
             owner_id: Uuid::nil(),
             sharing: SharingMode::Tenant,
             owner_tenant_id: Uuid::nil(),
+            version: 1,
         };
         let debug = format!("{meta:?}");
         assert!(debug.contains("[REDACTED]"));
@@ -1,19 +1,89 @@
 use async_trait::async_trait;
 use modkit_security::SecurityContext;
+use time::OffsetDateTime;
 
 use crate::error::CredStoreError;
-use crate::models::{SecretMetadata, SecretRef};
+use crate::models::{
+    OwnerId, SecretInfo, SecretMetadata, SecretRef, SecretValue, SecretVersion, SharingMode,
+    TenantId,
+};
 
 /// Backend storage adapter trait implemented by credential store plugins.
 ///
 /// Plugins operate at the single-tenant level with explicit parameters
 /// decomposed by the gateway. Authorization is the gateway's responsibility.
+///
+/// Secrets are addressed by `(tenant_id, key, owner_id)`: `Some(owner_id)`
+/// selects the owner's `private` secret, `None` the tenant-wide
+/// (`tenant`/`shared`) secret.
 #[async_trait]
 pub trait CredStorePluginClientV1: Send + Sync {
-    /// Retrieves a secret with full metadata from the backend.
+    /// Retrieves the current version of a secret with full metadata.
     async fn get(
         &self,
         ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        key: &SecretRef,
+        owner_id: Option<&OwnerId>,
+    ) -> Result<Option<SecretMetadata>, CredStoreError>;
+
+    /// Retrieves a specific version of a secret. Retired versions past their
+    /// expiry must not be returned.
+    async fn get_version(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: &TenantId,
         key: &SecretRef,
+        owner_id: Option<&OwnerId>,
+        version: SecretVersion,
     ) -> Result<Option<SecretMetadata>, CredStoreError>;
+
+    /// Creates or replaces a secret and returns the new version.
+    ///
+    /// `sharing == Private` stores the secret under `owner_id`; other modes
+    /// store it tenant-wide and record `owner_id` as its creator. Replacing
+    /// a value drops all previous versions.
+    async fn put(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        key: &SecretRef,
+        value: SecretValue,
+        sharing: SharingMode,
+        owner_id: &OwnerId,
+    ) -> Result<SecretVersion, CredStoreError>;
+
+    /// Stores a new version of an existing secret. The replaced version
+    /// stays readable until `previous_expires_at`.
+    ///
+    /// Returns `CredStoreError::NotFound` if the secret does not exist.
+    async fn rotate(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        key: &SecretRef,
+        owner_id: Option<&OwnerId>,
+        value: SecretValue,
+        previous_expires_at: OffsetDateTime,
+    ) -> Result<SecretVersion, CredStoreError>;
+
+    /// Deletes a secret with all of its versions.
+    ///
+    /// Returns `CredStoreError::NotFound` if the secret does not exist.
+    async fn delete(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        key: &SecretRef,
+        owner_id: Option<&OwnerId>,
+    ) -> Result<(), CredStoreError>;
+
+    /// Lists the tenant-wide secrets of `tenant_id` and the `private`
+    /// secrets of `owner_id` in that tenant.
+    async fn list(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        owner_id: &OwnerId,
+    ) -> Result<Vec<SecretInfo>, CredStoreError>;
 }
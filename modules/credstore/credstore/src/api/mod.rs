@@ -0,0 +1,3 @@
+//! API layer for the credstore module.
+
+pub mod rest;
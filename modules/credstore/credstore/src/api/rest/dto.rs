@@ -0,0 +1,211 @@
+//! REST DTOs for the credstore module.
+
+use credstore_sdk::{
+    GetSecretResponse, RetiredVersion, RotateSecretResponse, SecretInfo, SecretVersion, SharingMode,
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Visibility scope of a secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[modkit_macros::api_dto(request, response)]
+pub enum SharingModeDto {
+    /// Only the owner can access the secret.
+    Private,
+    /// All users within the owner's tenant can access the secret.
+    #[default]
+    Tenant,
+    /// The secret is accessible across tenant boundaries.
+    Shared,
+}
+
+impl From<SharingModeDto> for SharingMode {
+    fn from(mode: SharingModeDto) -> Self {
+        match mode {
+            SharingModeDto::Private => Self::Private,
+            SharingModeDto::Tenant => Self::Tenant,
+            SharingModeDto::Shared => Self::Shared,
+        }
+    }
+}
+
+impl From<SharingMode> for SharingModeDto {
+    fn from(mode: SharingMode) -> Self {
+        match mode {
+            SharingMode::Private => Self::Private,
+            SharingMode::Tenant => Self::Tenant,
+            SharingMode::Shared => Self::Shared,
+        }
+    }
+}
+
+/// Request to create a new secret.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request)]
+pub struct CreateSecretRequest {
+    /// Secret key; `[a-zA-Z0-9_-]`, at most 255 characters.
+    pub reference: String,
+    /// Secret value.
+    pub value: String,
+    /// Visibility scope; defaults to `tenant`.
+    #[serde(default)]
+    pub sharing: SharingModeDto,
+}
+
+/// Request to create or replace a secret.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request)]
+pub struct PutSecretRequest {
+    /// Secret value.
+    pub value: String,
+    /// Visibility scope; defaults to `tenant`.
+    #[serde(default)]
+    pub sharing: SharingModeDto,
+}
+
+/// Request to rotate an existing secret.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request)]
+pub struct RotateSecretRequest {
+    /// New secret value.
+    pub value: String,
+    /// How long the previous version stays readable, in seconds.
+    /// Defaults to the module's configured grace period.
+    #[serde(default)]
+    pub grace_period_secs: Option<u64>,
+}
+
+/// Query parameters for reading a secret.
+#[derive(Debug, Clone, Default)]
+#[modkit_macros::api_dto(request)]
+pub struct GetSecretQuery {
+    /// Specific version to read; defaults to the current one.
+    #[serde(default)]
+    pub version: Option<SecretVersion>,
+}
+
+/// Version written by a create or put.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct SecretVersionResponse {
+    pub reference: String,
+    pub version: SecretVersion,
+}
+
+/// Result of a rotation.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct RotateSecretResponseDto {
+    pub reference: String,
+    /// Version holding the new value.
+    pub version: SecretVersion,
+    /// Version that was replaced.
+    pub previous_version: SecretVersion,
+    /// When the replaced version stops being readable.
+    #[serde(with = "time::serde::rfc3339")]
+    pub previous_version_expires_at: OffsetDateTime,
+}
+
+impl RotateSecretResponseDto {
+    #[must_use]
+    pub fn new(reference: String, resp: RotateSecretResponse) -> Self {
+        Self {
+            reference,
+            version: resp.version,
+            previous_version: resp.previous.version,
+            previous_version_expires_at: resp.previous.expires_at,
+        }
+    }
+}
+
+/// Access metadata returned alongside a secret value.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct SecretMetadataDto {
+    /// Tenant that owns the secret.
+    pub owner_tenant_id: Uuid,
+    pub sharing: SharingModeDto,
+    /// `true` if the secret was resolved from an ancestor tenant.
+    pub is_inherited: bool,
+    /// Version of the returned value.
+    pub version: SecretVersion,
+}
+
+/// Secret value with access metadata.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct SecretResponse {
+    pub value: String,
+    pub metadata: SecretMetadataDto,
+}
+
+impl SecretResponse {
+    #[must_use]
+    pub fn new(value: String, resp: &GetSecretResponse) -> Self {
+        Self {
+            value,
+            metadata: SecretMetadataDto {
+                owner_tenant_id: resp.owner_tenant_id,
+                sharing: resp.sharing.into(),
+                is_inherited: resp.is_inherited,
+                version: resp.version,
+            },
+        }
+    }
+}
+
+/// Superseded version still inside its grace period.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct RetiredVersionDto {
+    pub version: SecretVersion,
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+}
+
+impl From<RetiredVersion> for RetiredVersionDto {
+    fn from(v: RetiredVersion) -> Self {
+        Self {
+            version: v.version,
+            expires_at: v.expires_at,
+        }
+    }
+}
+
+/// Secret metadata; never includes the value.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct SecretInfoDto {
+    pub reference: String,
+    pub owner_id: Uuid,
+    pub owner_tenant_id: Uuid,
+    pub sharing: SharingModeDto,
+    pub version: SecretVersion,
+    pub retired_versions: Vec<RetiredVersionDto>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<SecretInfo> for SecretInfoDto {
+    fn from(info: SecretInfo) -> Self {
+        Self {
+            reference: info.key.as_ref().to_owned(),
+            owner_id: info.owner_id,
+            owner_tenant_id: info.owner_tenant_id,
+            sharing: info.sharing.into(),
+            version: info.version,
+            retired_versions: info.retired_versions.into_iter().map(Into::into).collect(),
+            created_at: info.created_at,
+            updated_at: info.updated_at,
+        }
+    }
+}
+
+/// Secrets visible to the caller.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct ListSecretsResponse {
+    pub secrets: Vec<SecretInfoDto>,
+}
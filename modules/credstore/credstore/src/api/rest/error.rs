@@ -0,0 +1,115 @@
+//! REST error mapping for the credstore module.
+
+use modkit::api::prelude::StatusCode;
+use modkit::api::problem::Problem;
+
+use crate::domain::error::DomainError;
+
+impl From<DomainError> for Problem {
+    fn from(e: DomainError) -> Self {
+        let trace_id = tracing::Span::current()
+            .id()
+            .map(|id| id.into_u64().to_string());
+
+        let (status, code, title, detail) = match &e {
+            DomainError::NotFound => (
+                StatusCode::NOT_FOUND,
+                "CREDSTORE_NOT_FOUND",
+                "Secret not found",
+                "The secret does not exist or is not accessible".to_owned(),
+            ),
+            DomainError::AlreadyExists => (
+                StatusCode::CONFLICT,
+                "CREDSTORE_ALREADY_EXISTS",
+                "Secret already exists",
+                "A secret with this reference already exists".to_owned(),
+            ),
+            DomainError::AccessDenied(reason) => (
+                StatusCode::FORBIDDEN,
+                "CREDSTORE_ACCESS_DENIED",
+                "Access denied",
+                reason.clone(),
+            ),
+            DomainError::InvalidRequest(msg) => (
+                StatusCode::BAD_REQUEST,
+                "CREDSTORE_INVALID_REQUEST",
+                "Invalid request",
+                msg.clone(),
+            ),
+            DomainError::PluginNotFound { .. } | DomainError::PluginUnavailable { .. } => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "CREDSTORE_UNAVAILABLE",
+                "Service unavailable",
+                "No credential store backend is available".to_owned(),
+            ),
+            DomainError::TypesRegistryUnavailable(_)
+            | DomainError::InvalidPluginInstance { .. }
+            | DomainError::Internal(_) => {
+                tracing::error!(error = ?e, "Internal error in credstore");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "CREDSTORE_INTERNAL",
+                    "Internal Server Error",
+                    "An internal error occurred".to_owned(),
+                )
+            }
+        };
+
+        let mut problem = Problem::new(status, title, detail)
+            .with_type(format!("https://errors.hyperspot.com/{code}"))
+            .with_code(code);
+
+        if let Some(id) = trace_id {
+            problem = problem.with_trace_id(id);
+        }
+
+        problem
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn not_found_maps_to_404() {
+        let problem: Problem = DomainError::NotFound.into();
+        assert_eq!(problem.status, StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn already_exists_maps_to_409() {
+        let problem: Problem = DomainError::AlreadyExists.into();
+        assert_eq!(problem.status, StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn access_denied_maps_to_403() {
+        let problem: Problem = DomainError::AccessDenied("no".to_owned()).into();
+        assert_eq!(problem.status, StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn invalid_request_maps_to_400() {
+        let problem: Problem = DomainError::InvalidRequest("bad".to_owned()).into();
+        assert_eq!(problem.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn plugin_unavailable_maps_to_503() {
+        let problem: Problem = DomainError::PluginUnavailable {
+            gts_id: "gts.x".to_owned(),
+            reason: "starting".to_owned(),
+        }
+        .into();
+        assert_eq!(problem.status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn internal_maps_to_500_without_leaking_detail() {
+        let problem: Problem = DomainError::Internal("db password wrong".to_owned()).into();
+        assert_eq!(problem.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(problem.detail, "An internal error occurred");
+    }
+}
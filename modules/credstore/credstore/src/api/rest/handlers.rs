@@ -0,0 +1,299 @@
+//! REST handlers for the credstore module.
+//!
+//! Secret values travel as UTF-8 strings over REST.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::{Extension, Path, Query};
+use credstore_sdk::{SecretRef, SecretValue};
+use modkit::api::prelude::*;
+use modkit_security::SecurityContext;
+
+use super::dto::{
+    CreateSecretRequest, GetSecretQuery, ListSecretsResponse, PutSecretRequest,
+    RotateSecretRequest, RotateSecretResponseDto, SecretResponse, SecretVersionResponse,
+};
+use crate::domain::error::DomainError;
+use crate::domain::service::Service;
+
+fn parse_ref(reference: &str) -> Result<SecretRef, DomainError> {
+    SecretRef::new(reference).map_err(|e| DomainError::InvalidRequest(e.to_string()))
+}
+
+/// GET /credstore/v1/secrets
+///
+/// List metadata of the secrets visible to the caller.
+///
+/// # Errors
+///
+/// Returns `Problem` if the caller is not authorized or the backend fails.
+pub async fn list_secrets(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+) -> ApiResult<Json<ListSecretsResponse>> {
+    let secrets = svc.list(&ctx).await?;
+    Ok(Json(ListSecretsResponse {
+        secrets: secrets.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// POST /credstore/v1/secrets
+///
+/// Create a secret.
+///
+/// # Errors
+///
+/// Returns `Problem` with 409 if the secret already exists, 400 for an
+/// invalid reference or value, or the authorization and backend errors.
+pub async fn create_secret(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    Json(req): Json<CreateSecretRequest>,
+) -> ApiResult<(StatusCode, Json<SecretVersionResponse>)> {
+    let key = parse_ref(&req.reference)?;
+    let version = svc
+        .create(&ctx, &key, SecretValue::from(req.value), req.sharing.into())
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(SecretVersionResponse {
+            reference: req.reference,
+            version,
+        }),
+    ))
+}
+
+/// GET /credstore/v1/secrets/{reference}
+///
+/// Read the current or a specific version of a secret.
+///
+/// # Errors
+///
+/// Returns `Problem` with 404 if the secret or version is not visible to the
+/// caller, or the authorization and backend errors.
+pub async fn get_secret(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    Path(reference): Path<String>,
+    Query(query): Query<GetSecretQuery>,
+) -> ApiResult<Json<SecretResponse>> {
+    let key = parse_ref(&reference)?;
+    let resp = match query.version {
+        Some(version) => svc.get_version(&ctx, &key, version).await?,
+        None => svc.get(&ctx, &key).await?,
+    }
+    .ok_or(DomainError::NotFound)?;
+
+    let value = String::from_utf8(resp.value.as_bytes().to_vec()).map_err(|_| {
+        DomainError::Internal("secret value is not valid UTF-8 and cannot be returned".to_owned())
+    })?;
+    Ok(Json(SecretResponse::new(value, &resp)))
+}
+
+/// PUT /credstore/v1/secrets/{reference}
+///
+/// Create or replace a secret.
+///
+/// # Errors
+///
+/// Returns `Problem` with 400 for an invalid reference or value, or the
+/// authorization and backend errors.
+pub async fn put_secret(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    Path(reference): Path<String>,
+    Json(req): Json<PutSecretRequest>,
+) -> ApiResult<Json<SecretVersionResponse>> {
+    let key = parse_ref(&reference)?;
+    let version = svc
+        .put(&ctx, &key, SecretValue::from(req.value), req.sharing.into())
+        .await?;
+    Ok(Json(SecretVersionResponse { reference, version }))
+}
+
+/// POST /credstore/v1/secrets/{reference}/rotate
+///
+/// Rotate a secret, keeping the previous version readable for a grace period.
+///
+/// # Errors
+///
+/// Returns `Problem` with 404 if the secret is not visible to the caller, 400
+/// for an invalid value or grace period, or the authorization and backend errors.
+pub async fn rotate_secret(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    Path(reference): Path<String>,
+    Json(req): Json<RotateSecretRequest>,
+) -> ApiResult<Json<RotateSecretResponseDto>> {
+    let key = parse_ref(&reference)?;
+    let grace_period = req
+        .grace_period_secs
+        .map_or_else(|| svc.default_grace_period(), Duration::from_secs);
+    let resp = svc
+        .rotate(&ctx, &key, SecretValue::from(req.value), grace_period)
+        .await?;
+    Ok(Json(RotateSecretResponseDto::new(reference, resp)))
+}
+
+/// DELETE /credstore/v1/secrets/{reference}
+///
+/// Delete a secret with all of its versions.
+///
+/// # Errors
+///
+/// Returns `Problem` with 404 if the secret is not visible to the caller, or
+/// the authorization and backend errors.
+pub async fn delete_secret(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    Path(reference): Path<String>,
+) -> ApiResult<StatusCode> {
+    let key = parse_ref(&reference)?;
+    svc.delete(&ctx, &key).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::api::rest::dto::SharingModeDto;
+    use crate::domain::test_support::{allow_all_enforcer, ctx_for, static_plugin_service};
+
+    fn ctx() -> SecurityContext {
+        ctx_for(Uuid::from_u128(0xA), Uuid::from_u128(0x1))
+    }
+
+    fn service() -> Arc<Service> {
+        Arc::new(static_plugin_service(allow_all_enforcer()))
+    }
+
+    fn create_request(reference: &str) -> CreateSecretRequest {
+        CreateSecretRequest {
+            reference: reference.to_owned(),
+            value: "sk-123".to_owned(),
+            sharing: SharingModeDto::Tenant,
+        }
+    }
+
+    #[tokio::test]
+    async fn create_returns_201_then_409() {
+        let svc = service();
+
+        let (status, Json(resp)) = create_secret(
+            Extension(ctx()),
+            Extension(svc.clone()),
+            Json(create_request("vendor-key")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(resp.version, 1);
+
+        let err = create_secret(
+            Extension(ctx()),
+            Extension(svc),
+            Json(create_request("vendor-key")),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn invalid_reference_returns_400() {
+        let err = create_secret(
+            Extension(ctx()),
+            Extension(service()),
+            Json(create_request("not a valid ref")),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn get_missing_secret_returns_404() {
+        let err = get_secret(
+            Extension(ctx()),
+            Extension(service()),
+            Path("missing".to_owned()),
+            Query(GetSecretQuery::default()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn rotate_then_read_previous_version() {
+        let svc = service();
+        let Json(stored) = put_secret(
+            Extension(ctx()),
+            Extension(svc.clone()),
+            Path("vendor-key".to_owned()),
+            Json(PutSecretRequest {
+                value: "old".to_owned(),
+                sharing: SharingModeDto::Tenant,
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(stored.version, 1);
+
+        let Json(rotated) = rotate_secret(
+            Extension(ctx()),
+            Extension(svc.clone()),
+            Path("vendor-key".to_owned()),
+            Json(RotateSecretRequest {
+                value: "new".to_owned(),
+                grace_period_secs: None,
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(rotated.version, 2);
+        assert_eq!(rotated.previous_version, 1);
+
+        let Json(previous) = get_secret(
+            Extension(ctx()),
+            Extension(svc),
+            Path("vendor-key".to_owned()),
+            Query(GetSecretQuery { version: Some(1) }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(previous.value, "old");
+        assert_eq!(previous.metadata.version, 1);
+    }
+
+    #[tokio::test]
+    async fn delete_returns_204_and_list_is_empty_afterwards() {
+        let svc = service();
+        let (_, Json(created)) = create_secret(
+            Extension(ctx()),
+            Extension(svc.clone()),
+            Json(create_request("vendor-key")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(created.version, 1);
+
+        let status = delete_secret(
+            Extension(ctx()),
+            Extension(svc.clone()),
+            Path("vendor-key".to_owned()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let Json(list) = list_secrets(Extension(ctx()), Extension(svc))
+            .await
+            .unwrap();
+        assert!(list.secrets.is_empty());
+    }
+}
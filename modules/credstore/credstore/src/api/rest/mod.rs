@@ -0,0 +1,6 @@
+//! REST API for the credstore module.
+
+pub mod dto;
+pub mod error;
+pub mod handlers;
+pub mod routes;
@@ -0,0 +1,163 @@
+//! REST route registration for the credstore module.
+
+use std::sync::Arc;
+
+use axum::{Extension, Router};
+use modkit::api::OpenApiRegistry;
+use modkit::api::operation_builder::{LicenseFeature, OperationBuilder};
+use modkit::api::prelude::StatusCode;
+
+use super::dto::{
+    CreateSecretRequest, ListSecretsResponse, PutSecretRequest, RotateSecretRequest,
+    RotateSecretResponseDto, SecretResponse, SecretVersionResponse,
+};
+use super::handlers;
+use crate::domain::service::Service;
+
+const TAG: &str = "credstore";
+
+const REFERENCE_PARAM: &str = "Secret reference ([a-zA-Z0-9_-], at most 255 characters)";
+
+struct License;
+
+impl AsRef<str> for License {
+    fn as_ref(&self) -> &'static str {
+        "gts.x.core.lic.feat.v1~x.core.global.base.v1"
+    }
+}
+
+impl LicenseFeature for License {}
+
+/// Registers all REST routes for the credstore module.
+#[allow(clippy::needless_pass_by_value)]
+pub fn register_routes(
+    mut router: Router,
+    openapi: &dyn OpenApiRegistry,
+    service: Arc<Service>,
+) -> Router {
+    // GET /credstore/v1/secrets - List secret metadata
+    router = OperationBuilder::get("/credstore/v1/secrets")
+        .operation_id("credstore.list")
+        .summary("List secrets")
+        .description(
+            "List metadata of the caller's private secrets and of all tenant and shared secrets in the caller's tenant. Values are never returned.",
+        )
+        .tag(TAG)
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::list_secrets)
+        .json_response_with_schema::<ListSecretsResponse>(
+            openapi,
+            StatusCode::OK,
+            "Secret metadata",
+        )
+        .problem_response(openapi, StatusCode::SERVICE_UNAVAILABLE, "Backend unavailable")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // POST /credstore/v1/secrets - Create a secret
+    router = OperationBuilder::post("/credstore/v1/secrets")
+        .operation_id("credstore.create")
+        .summary("Create a secret")
+        .description("Create a secret in the caller's tenant. Fails if the secret already exists.")
+        .tag(TAG)
+        .authenticated()
+        .require_license_features::<License>([])
+        .json_request::<CreateSecretRequest>(openapi, "Secret to create")
+        .handler(handlers::create_secret)
+        .json_response_with_schema::<SecretVersionResponse>(
+            openapi,
+            StatusCode::CREATED,
+            "Secret created",
+        )
+        .problem_response(
+            openapi,
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Backend unavailable",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /credstore/v1/secrets/{reference} - Read a secret
+    router = OperationBuilder::get("/credstore/v1/secrets/{reference}")
+        .operation_id("credstore.get")
+        .summary("Get a secret")
+        .description(
+            "Read the current value of a secret, or a specific version with `?version=`. The caller's private secret takes precedence over a tenant-wide one.",
+        )
+        .tag(TAG)
+        .authenticated()
+        .require_license_features::<License>([])
+        .path_param("reference", REFERENCE_PARAM)
+        .query_param("version", false, "Version to read; defaults to the current one")
+        .handler(handlers::get_secret)
+        .json_response_with_schema::<SecretResponse>(openapi, StatusCode::OK, "The secret")
+        .problem_response(openapi, StatusCode::SERVICE_UNAVAILABLE, "Backend unavailable")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // PUT /credstore/v1/secrets/{reference} - Create or replace a secret
+    router = OperationBuilder::put("/credstore/v1/secrets/{reference}")
+        .operation_id("credstore.put")
+        .summary("Create or replace a secret")
+        .description(
+            "Store a secret in the caller's tenant, replacing any existing value. Previous versions are dropped; use rotate to keep them readable.",
+        )
+        .tag(TAG)
+        .authenticated()
+        .require_license_features::<License>([])
+        .path_param("reference", REFERENCE_PARAM)
+        .json_request::<PutSecretRequest>(openapi, "Secret value and sharing mode")
+        .handler(handlers::put_secret)
+        .json_response_with_schema::<SecretVersionResponse>(
+            openapi,
+            StatusCode::OK,
+            "Secret stored",
+        )
+        .problem_response(openapi, StatusCode::SERVICE_UNAVAILABLE, "Backend unavailable")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // POST /credstore/v1/secrets/{reference}/rotate - Rotate a secret
+    router = OperationBuilder::post("/credstore/v1/secrets/{reference}/rotate")
+        .operation_id("credstore.rotate")
+        .summary("Rotate a secret")
+        .description(
+            "Store a new version of an existing secret. The previous version stays readable until its grace period ends.",
+        )
+        .tag(TAG)
+        .authenticated()
+        .require_license_features::<License>([])
+        .path_param("reference", REFERENCE_PARAM)
+        .json_request::<RotateSecretRequest>(openapi, "New value and grace period")
+        .handler(handlers::rotate_secret)
+        .json_response_with_schema::<RotateSecretResponseDto>(
+            openapi,
+            StatusCode::OK,
+            "Secret rotated",
+        )
+        .problem_response(openapi, StatusCode::SERVICE_UNAVAILABLE, "Backend unavailable")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // DELETE /credstore/v1/secrets/{reference} - Delete a secret
+    router = OperationBuilder::delete("/credstore/v1/secrets/{reference}")
+        .operation_id("credstore.delete")
+        .summary("Delete a secret")
+        .description("Delete a secret together with all of its versions.")
+        .tag(TAG)
+        .authenticated()
+        .require_license_features::<License>([])
+        .path_param("reference", REFERENCE_PARAM)
+        .handler(handlers::delete_secret)
+        .json_response(StatusCode::NO_CONTENT, "Secret deleted")
+        .problem_response(
+            openapi,
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Backend unavailable",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    router.layer(Extension(service))
+}
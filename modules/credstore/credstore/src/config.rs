@@ -1,5 +1,7 @@
 //! Configuration for the credstore module.
 
+use std::time::Duration;
+
 use serde::Deserialize;
 
 /// Module configuration.
@@ -11,12 +13,38 @@ pub struct CredStoreConfig {
     /// The module queries types-registry for plugin instances matching
     /// this vendor and selects the one with lowest priority number.
     pub vendor: String,
+
+    /// Secret rotation settings.
+    pub rotation: RotationConfig,
 }
 
 impl Default for CredStoreConfig {
     fn default() -> Self {
         Self {
             vendor: "hyperspot".to_owned(),
+            rotation: RotationConfig::default(),
+        }
+    }
+}
+
+/// Grace periods for `rotate`, during which the replaced version stays readable.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RotationConfig {
+    /// Grace period used when a REST rotate request does not specify one.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub default_grace_period: Duration,
+
+    /// Longest grace period a caller may request.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub max_grace_period: Duration,
+}
+
+impl Default for RotationConfig {
+    fn default() -> Self {
+        Self {
+            default_grace_period: Duration::from_secs(24 * 60 * 60),
+            max_grace_period: Duration::from_secs(30 * 24 * 60 * 60),
         }
     }
 }
@@ -42,6 +70,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rotation_grace_periods_parse_humantime() {
+        let json = r#"{"rotation": {"default_grace_period": "1h", "max_grace_period": "7d"}}"#;
+        let cfg: CredStoreConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(cfg.rotation.default_grace_period, Duration::from_secs(3600));
+        assert_eq!(
+            cfg.rotation.max_grace_period,
+            Duration::from_secs(7 * 86400)
+        );
+    }
+
+    #[test]
+    fn rotation_defaults_apply_when_omitted() {
+        let cfg: CredStoreConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(
+            cfg.rotation.default_grace_period,
+            Duration::from_secs(86400)
+        );
+        assert!(cfg.rotation.max_grace_period >= cfg.rotation.default_grace_period);
+    }
+
     #[test]
     fn rejects_unknown_fields() {
         let json = r#"{"vendor": "x", "unexpected": true}"#;
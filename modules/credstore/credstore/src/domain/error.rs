@@ -22,6 +22,15 @@ pub enum DomainError {
     #[error("secret not found")]
     NotFound,
 
+    #[error("secret already exists")]
+    AlreadyExists,
+
+    #[error("access denied: {0}")]
+    AccessDenied(String),
+
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+
     #[error("internal error: {0}")]
     Internal(String),
 }
@@ -57,10 +66,32 @@ impl From<modkit::plugins::ChoosePluginError> for DomainError {
     }
 }
 
+impl From<authz_resolver_sdk::EnforcerError> for DomainError {
+    fn from(e: authz_resolver_sdk::EnforcerError) -> Self {
+        use authz_resolver_sdk::EnforcerError;
+
+        match e {
+            EnforcerError::Denied { deny_reason } => Self::AccessDenied(deny_reason.map_or_else(
+                || "access denied by policy".to_owned(),
+                |r| format!("{}: {}", r.error_code, r.details.unwrap_or_default()),
+            )),
+            EnforcerError::CompileFailed(e) => {
+                Self::Internal(format!("authorization constraint compilation failed: {e}"))
+            }
+            EnforcerError::EvaluationFailed(e) => {
+                Self::Internal(format!("authorization evaluation failed: {e}"))
+            }
+        }
+    }
+}
+
 impl From<CredStoreError> for DomainError {
     fn from(e: CredStoreError) -> Self {
         match e {
             CredStoreError::NotFound => Self::NotFound,
+            CredStoreError::AlreadyExists => Self::AlreadyExists,
+            CredStoreError::AccessDenied => Self::AccessDenied("denied by plugin".to_owned()),
+            CredStoreError::InvalidRequest(msg) => Self::InvalidRequest(msg),
             // CredStoreError variants don't carry vendor/gts_id, so these
             // fields cannot be populated from the error alone.
             CredStoreError::NoPluginAvailable => Self::PluginNotFound {
@@ -87,6 +118,9 @@ impl From<DomainError> for CredStoreError {
                 Self::ServiceUnavailable(format!("plugin not available for '{gts_id}': {reason}"))
             }
             DomainError::NotFound => Self::NotFound,
+            DomainError::AlreadyExists => Self::AlreadyExists,
+            DomainError::AccessDenied(_) => Self::AccessDenied,
+            DomainError::InvalidRequest(msg) => Self::InvalidRequest(msg),
             DomainError::TypesRegistryUnavailable(reason) | DomainError::Internal(reason) => {
                 Self::Internal(reason)
             }
@@ -138,6 +172,7 @@ mod tests {
     #[test]
     fn from_choose_plugin_error_not_found_becomes_plugin_not_found() {
         let src = ChoosePluginError::PluginNotFound {
+            schema_id: "gts.x.core.modkit.plugin.v1~x.core.credstore.plugin.v1~".into(),
             vendor: "acme".into(),
         };
         let dst = DomainError::from(src);
@@ -239,6 +274,39 @@ mod tests {
         assert!(matches!(dst, CredStoreError::NotFound));
     }
 
+    #[test]
+    fn domain_access_denied_hides_reason() {
+        let dst = CredStoreError::from(DomainError::AccessDenied("policy x".into()));
+        assert!(matches!(dst, CredStoreError::AccessDenied));
+    }
+
+    #[test]
+    fn domain_invalid_request_keeps_message() {
+        let dst = CredStoreError::from(DomainError::InvalidRequest("empty".into()));
+        assert!(matches!(dst, CredStoreError::InvalidRequest(msg) if msg == "empty"));
+    }
+
+    // ── From<EnforcerError> ──────────────────────────────────────────────────
+
+    #[test]
+    fn from_enforcer_denied_becomes_access_denied() {
+        let src = authz_resolver_sdk::EnforcerError::Denied { deny_reason: None };
+        assert!(matches!(
+            DomainError::from(src),
+            DomainError::AccessDenied(_)
+        ));
+    }
+
+    #[test]
+    fn from_enforcer_evaluation_failed_becomes_internal() {
+        let src = authz_resolver_sdk::EnforcerError::EvaluationFailed(
+            authz_resolver_sdk::AuthZResolverError::Internal("pdp down".into()),
+        );
+        assert!(
+            matches!(DomainError::from(src), DomainError::Internal(msg) if msg.contains("pdp down"))
+        );
+    }
+
     #[test]
     fn domain_types_registry_unavailable_becomes_internal() {
         let src = DomainError::TypesRegistryUnavailable("gone".into());
@@ -1,9 +1,13 @@
 //! Local (in-process) client for the credstore module.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
-use credstore_sdk::{CredStoreClientV1, CredStoreError, GetSecretResponse, SecretRef};
+use credstore_sdk::{
+    CredStoreClientV1, CredStoreError, GetSecretResponse, RotateSecretResponse, SecretInfo,
+    SecretRef, SecretValue, SecretVersion, SharingMode,
+};
 use modkit_macros::domain_model;
 use modkit_security::SecurityContext;
 
@@ -27,8 +31,11 @@ impl CredStoreLocalClient {
 
 fn log_and_convert(op: &str, e: DomainError) -> CredStoreError {
     match &e {
-        DomainError::NotFound => {
-            tracing::debug!(operation = op, "credstore secret not found");
+        DomainError::NotFound
+        | DomainError::AlreadyExists
+        | DomainError::AccessDenied(_)
+        | DomainError::InvalidRequest(_) => {
+            tracing::debug!(operation = op, error = %e, "credstore request rejected");
         }
         _ => {
             tracing::error!(operation = op, error = ?e, "credstore call failed");
@@ -49,6 +56,58 @@ impl CredStoreClientV1 for CredStoreLocalClient {
             .await
             .map_err(|e| log_and_convert("get", e))
     }
+
+    async fn get_version(
+        &self,
+        ctx: &SecurityContext,
+        key: &SecretRef,
+        version: SecretVersion,
+    ) -> Result<Option<GetSecretResponse>, CredStoreError> {
+        self.svc
+            .get_version(ctx, key, version)
+            .await
+            .map_err(|e| log_and_convert("get_version", e))
+    }
+
+    async fn put(
+        &self,
+        ctx: &SecurityContext,
+        key: &SecretRef,
+        value: SecretValue,
+        sharing: SharingMode,
+    ) -> Result<SecretVersion, CredStoreError> {
+        self.svc
+            .put(ctx, key, value, sharing)
+            .await
+            .map_err(|e| log_and_convert("put", e))
+    }
+
+    async fn rotate(
+        &self,
+        ctx: &SecurityContext,
+        key: &SecretRef,
+        value: SecretValue,
+        grace_period: Duration,
+    ) -> Result<RotateSecretResponse, CredStoreError> {
+        self.svc
+            .rotate(ctx, key, value, grace_period)
+            .await
+            .map_err(|e| log_and_convert("rotate", e))
+    }
+
+    async fn delete(&self, ctx: &SecurityContext, key: &SecretRef) -> Result<(), CredStoreError> {
+        self.svc
+            .delete(ctx, key)
+            .await
+            .map_err(|e| log_and_convert("delete", e))
+    }
+
+    async fn list(&self, ctx: &SecurityContext) -> Result<Vec<SecretInfo>, CredStoreError> {
+        self.svc
+            .list(ctx)
+            .await
+            .map_err(|e| log_and_convert("list", e))
+    }
 }
 
 #[cfg(test)]
@@ -65,11 +124,11 @@ mod tests {
 
     use super::*;
     use crate::domain::Service;
-    use crate::domain::test_support::{MockPlugin, MockRegistry, test_ctx};
+    use crate::domain::test_support::{MockPlugin, MockRegistry, allow_all_enforcer, test_ctx};
 
     fn make_client() -> CredStoreLocalClient {
         let hub = Arc::new(ClientHub::default());
-        let svc = Arc::new(Service::new(hub, "hyperspot".into()));
+        let svc = Arc::new(Service::new(hub, "hyperspot".into(), allow_all_enforcer()));
         CredStoreLocalClient::new(svc)
     }
 
@@ -100,7 +159,7 @@ mod tests {
             plugin,
         );
 
-        let svc = Arc::new(Service::new(hub, "hyperspot".into()));
+        let svc = Arc::new(Service::new(hub, "hyperspot".into(), allow_all_enforcer()));
         CredStoreLocalClient::new(svc)
     }
 
@@ -135,6 +194,7 @@ mod tests {
             owner_id: Uuid::nil(),
             sharing: SharingMode::Tenant,
             owner_tenant_id: Uuid::nil(),
+            version: 1,
         };
         let client = make_wired_client(MockPlugin::returns(Some(&meta)));
         let key = SecretRef::new("key").unwrap();
@@ -6,43 +6,85 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use credstore_sdk::{CredStorePluginClientV1, CredStorePluginSpecV1, GetSecretResponse, SecretRef};
+use authz_resolver_sdk::PolicyEnforcer;
+use authz_resolver_sdk::pep::{AccessRequest, ResourceType};
+use credstore_sdk::{
+    CredStorePluginClientV1, CredStorePluginSpecV1, GetSecretResponse, OwnerId, RetiredVersion,
+    RotateSecretResponse, SecretInfo, SecretMetadata, SecretRef, SecretValue, SecretVersion,
+    SharingMode,
+};
 use modkit::client_hub::{ClientHub, ClientScope};
 use modkit::plugins::{GtsPluginSelector, choose_plugin_instance};
 use modkit::telemetry::ThrottledLog;
 use modkit_macros::domain_model;
-use modkit_security::SecurityContext;
+use modkit_security::{SecurityContext, pep_properties};
+use time::OffsetDateTime;
 use tracing::info;
 use types_registry_sdk::{ListQuery, TypesRegistryClient};
 
 use super::error::DomainError;
+use crate::config::RotationConfig;
 
 /// Throttle interval for plugin unavailable warnings.
 const UNAVAILABLE_LOG_THROTTLE: Duration = Duration::from_secs(10);
 
+/// Authorization resource type for stored secrets.
+pub(crate) const SECRET_RESOURCE: ResourceType = ResourceType {
+    name: "gts.x.core.credstore.secret.v1~",
+    supported_properties: &[pep_properties::OWNER_TENANT_ID],
+};
+
+pub(crate) mod actions {
+    pub const GET: &str = "get";
+    pub const LIST: &str = "list";
+    pub const PUT: &str = "put";
+    pub const ROTATE: &str = "rotate";
+    pub const DELETE: &str = "delete";
+}
+
 /// `CredStore` domain service.
 ///
 /// Discovers plugins via types-registry and delegates storage operations.
+/// Every operation is authorized through the PEP against the caller's
+/// tenant before the plugin is called; sharing-mode and ownership rules are
+/// applied here so plugins stay plain key-value stores.
 #[domain_model]
 pub struct Service {
     hub: Arc<ClientHub>,
     vendor: String,
     selector: GtsPluginSelector,
     unavailable_log_throttle: ThrottledLog,
+    policy_enforcer: PolicyEnforcer,
+    rotation: RotationConfig,
 }
 
 impl Service {
     /// Creates a new service with lazy plugin resolution.
     #[must_use]
-    pub fn new(hub: Arc<ClientHub>, vendor: String) -> Self {
+    pub fn new(hub: Arc<ClientHub>, vendor: String, policy_enforcer: PolicyEnforcer) -> Self {
         Self {
             hub,
             vendor,
             selector: GtsPluginSelector::new(),
             unavailable_log_throttle: ThrottledLog::new(UNAVAILABLE_LOG_THROTTLE),
+            policy_enforcer,
+            rotation: RotationConfig::default(),
         }
     }
 
+    /// Overrides the rotation grace period settings.
+    #[must_use]
+    pub fn with_rotation(mut self, rotation: RotationConfig) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Grace period applied when a caller does not request one.
+    #[must_use]
+    pub fn default_grace_period(&self) -> Duration {
+        self.rotation.default_grace_period
+    }
+
     /// Lazily resolves and returns the plugin client.
     ///
     /// # Errors
@@ -102,28 +144,292 @@ impl Service {
         Ok(gts_id)
     }
 
-    /// Retrieves a secret from the plugin.
+    /// Checks the caller may perform `action` on secrets of its own tenant.
+    async fn authorize(&self, ctx: &SecurityContext, action: &str) -> Result<(), DomainError> {
+        let tenant_id = ctx.subject_tenant_id();
+        let scope = self
+            .policy_enforcer
+            .access_scope_with(
+                ctx,
+                &SECRET_RESOURCE,
+                action,
+                None,
+                &AccessRequest::new()
+                    .require_constraints(false)
+                    .context_tenant_id(tenant_id)
+                    .resource_property(pep_properties::OWNER_TENANT_ID, tenant_id),
+            )
+            .await?;
+        if scope.is_unconstrained()
+            || scope.contains_uuid(pep_properties::OWNER_TENANT_ID, tenant_id)
+        {
+            Ok(())
+        } else {
+            Err(DomainError::AccessDenied(format!(
+                "tenant {tenant_id} is outside the granted scope"
+            )))
+        }
+    }
+
+    /// Finds the secret `key` addresses for the caller: its own private
+    /// secret first, then the tenant-wide one. Returns the storage owner
+    /// (`Some` for private) with the plugin metadata.
+    async fn locate(
+        &self,
+        plugin: &dyn CredStorePluginClientV1,
+        ctx: &SecurityContext,
+        key: &SecretRef,
+        version: Option<SecretVersion>,
+    ) -> Result<Option<(Option<OwnerId>, SecretMetadata)>, DomainError> {
+        let tenant_id = ctx.subject_tenant_id();
+        let subject_id = ctx.subject_id();
+        for owner in [Some(subject_id), None] {
+            let meta = match version {
+                Some(version) => {
+                    plugin
+                        .get_version(ctx, &tenant_id, key, owner.as_ref(), version)
+                        .await?
+                }
+                None => plugin.get(ctx, &tenant_id, key, owner.as_ref()).await?,
+            };
+            // Private secrets are only ever visible to their owner, even if a
+            // backend hands one out for a tenant-wide lookup.
+            if let Some(meta) =
+                meta.filter(|m| m.sharing != SharingMode::Private || m.owner_id == subject_id)
+            {
+                return Ok(Some((owner, meta)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Retrieves the current version of a secret.
     ///
     /// Returns `Ok(None)` if the secret is not found (anti-enumeration).
     ///
     /// # Errors
     ///
-    /// Returns a `DomainError` for plugin resolution or backend failures.
+    /// Returns a `DomainError` for authorization, plugin resolution or backend failures.
     #[tracing::instrument(skip_all, fields(key = ?key))]
     pub async fn get(
         &self,
         ctx: &SecurityContext,
         key: &SecretRef,
     ) -> Result<Option<GetSecretResponse>, DomainError> {
+        self.authorize(ctx, actions::GET).await?;
+        let plugin = self.get_plugin().await?;
+
+        let result = self.locate(plugin.as_ref(), ctx, key, None).await?;
+        Ok(result.map(|(_, meta)| to_response(meta)))
+    }
+
+    /// Retrieves a specific version of a secret.
+    ///
+    /// Returns `Ok(None)` for unknown, expired or inaccessible versions.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DomainError` for authorization, plugin resolution or backend failures.
+    #[tracing::instrument(skip_all, fields(key = ?key, version))]
+    pub async fn get_version(
+        &self,
+        ctx: &SecurityContext,
+        key: &SecretRef,
+        version: SecretVersion,
+    ) -> Result<Option<GetSecretResponse>, DomainError> {
+        self.authorize(ctx, actions::GET).await?;
+        let plugin = self.get_plugin().await?;
+
+        let result = self
+            .locate(plugin.as_ref(), ctx, key, Some(version))
+            .await?;
+        Ok(result.map(|(_, meta)| to_response(meta)))
+    }
+
+    /// Creates or replaces a secret in the caller's tenant. Returns the new version.
+    ///
+    /// Any subject authorized for `put` may replace a tenant-wide secret;
+    /// the original creator stays recorded as its owner.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::InvalidRequest` for an empty value, or a
+    /// `DomainError` for authorization, plugin resolution or backend failures.
+    #[tracing::instrument(skip_all, fields(key = ?key, sharing = ?sharing))]
+    pub async fn put(
+        &self,
+        ctx: &SecurityContext,
+        key: &SecretRef,
+        value: SecretValue,
+        sharing: SharingMode,
+    ) -> Result<SecretVersion, DomainError> {
+        validate_value(&value)?;
+        self.authorize(ctx, actions::PUT).await?;
+        let plugin = self.get_plugin().await?;
+
+        let version = plugin
+            .put(
+                ctx,
+                &ctx.subject_tenant_id(),
+                key,
+                value,
+                sharing,
+                &ctx.subject_id(),
+            )
+            .await?;
+        info!(version, "Stored credstore secret");
+        Ok(version)
+    }
+
+    /// Creates a secret, failing if one already exists in the target scope
+    /// (the caller's private secrets for `private`, the tenant otherwise).
+    ///
+    /// The existence check and the write are not atomic.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::AlreadyExists` if the secret exists, plus the
+    /// errors of [`put`](Self::put).
+    #[tracing::instrument(skip_all, fields(key = ?key, sharing = ?sharing))]
+    pub async fn create(
+        &self,
+        ctx: &SecurityContext,
+        key: &SecretRef,
+        value: SecretValue,
+        sharing: SharingMode,
+    ) -> Result<SecretVersion, DomainError> {
+        validate_value(&value)?;
+        self.authorize(ctx, actions::PUT).await?;
+        let plugin = self.get_plugin().await?;
+
+        let tenant_id = ctx.subject_tenant_id();
+        let subject_id = ctx.subject_id();
+        let owner = (sharing == SharingMode::Private).then_some(&subject_id);
+        if plugin.get(ctx, &tenant_id, key, owner).await?.is_some() {
+            return Err(DomainError::AlreadyExists);
+        }
+        let version = plugin
+            .put(ctx, &tenant_id, key, value, sharing, &subject_id)
+            .await?;
+        info!(version, "Created credstore secret");
+        Ok(version)
+    }
+
+    /// Replaces the value of an existing secret, keeping the previous
+    /// version readable for `grace_period`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::NotFound` if the caller cannot see the secret,
+    /// `DomainError::InvalidRequest` for an empty value or a grace period
+    /// above the configured maximum, or a `DomainError` for authorization,
+    /// plugin resolution or backend failures.
+    #[tracing::instrument(skip_all, fields(key = ?key, grace_period = ?grace_period))]
+    pub async fn rotate(
+        &self,
+        ctx: &SecurityContext,
+        key: &SecretRef,
+        value: SecretValue,
+        grace_period: Duration,
+    ) -> Result<RotateSecretResponse, DomainError> {
+        validate_value(&value)?;
+        if grace_period > self.rotation.max_grace_period {
+            return Err(DomainError::InvalidRequest(format!(
+                "grace period exceeds the maximum of {}s",
+                self.rotation.max_grace_period.as_secs()
+            )));
+        }
+        self.authorize(ctx, actions::ROTATE).await?;
+        let plugin = self.get_plugin().await?;
+
+        let (owner, current) = self
+            .locate(plugin.as_ref(), ctx, key, None)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        let expires_at = OffsetDateTime::now_utc() + grace_period;
+        let version = plugin
+            .rotate(
+                ctx,
+                &ctx.subject_tenant_id(),
+                key,
+                owner.as_ref(),
+                value,
+                expires_at,
+            )
+            .await?;
+        info!(
+            version,
+            previous = current.version,
+            "Rotated credstore secret"
+        );
+        Ok(RotateSecretResponse {
+            version,
+            previous: RetiredVersion {
+                version: current.version,
+                expires_at,
+            },
+        })
+    }
+
+    /// Deletes a secret with all of its versions.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::NotFound` if the caller cannot see the secret,
+    /// or a `DomainError` for authorization, plugin resolution or backend failures.
+    #[tracing::instrument(skip_all, fields(key = ?key))]
+    pub async fn delete(&self, ctx: &SecurityContext, key: &SecretRef) -> Result<(), DomainError> {
+        self.authorize(ctx, actions::DELETE).await?;
         let plugin = self.get_plugin().await?;
 
-        let result = plugin.get(ctx, key).await?;
-        Ok(result.map(|meta| GetSecretResponse {
-            value: meta.value,
-            owner_tenant_id: meta.owner_tenant_id,
-            sharing: meta.sharing,
-            is_inherited: false,
-        }))
+        let (owner, _) = self
+            .locate(plugin.as_ref(), ctx, key, None)
+            .await?
+            .ok_or(DomainError::NotFound)?;
+        plugin
+            .delete(ctx, &ctx.subject_tenant_id(), key, owner.as_ref())
+            .await?;
+        info!("Deleted credstore secret");
+        Ok(())
+    }
+
+    /// Lists metadata of the secrets visible to the caller.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DomainError` for authorization, plugin resolution or backend failures.
+    #[tracing::instrument(skip_all)]
+    pub async fn list(&self, ctx: &SecurityContext) -> Result<Vec<SecretInfo>, DomainError> {
+        self.authorize(ctx, actions::LIST).await?;
+        let plugin = self.get_plugin().await?;
+
+        let subject_id = ctx.subject_id();
+        let items = plugin
+            .list(ctx, &ctx.subject_tenant_id(), &subject_id)
+            .await?;
+        Ok(items
+            .into_iter()
+            .filter(|i| i.sharing != SharingMode::Private || i.owner_id == subject_id)
+            .collect())
+    }
+}
+
+fn validate_value(value: &SecretValue) -> Result<(), DomainError> {
+    if value.as_bytes().is_empty() {
+        return Err(DomainError::InvalidRequest(
+            "secret value must not be empty".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+fn to_response(meta: SecretMetadata) -> GetSecretResponse {
+    GetSecretResponse {
+        value: meta.value,
+        owner_tenant_id: meta.owner_tenant_id,
+        sharing: meta.sharing,
+        is_inherited: false,
+        version: meta.version,
     }
 }
 
@@ -139,7 +445,10 @@ mod tests {
     use uuid::Uuid;
 
     use super::*;
-    use crate::domain::test_support::{MockPlugin, MockRegistry, test_ctx};
+    use crate::domain::test_support::{
+        MockPlugin, MockRegistry, allow_all_enforcer, ctx_for, deny_all_enforcer,
+        static_plugin_service, test_ctx,
+    };
 
     // ── helpers ──────────────────────────────────────────────────────────────
 
@@ -204,7 +513,7 @@ mod tests {
 
     #[tokio::test]
     async fn get_returns_registry_unavailable_when_hub_empty() {
-        let svc = Service::new(empty_hub(), "hyperspot".into());
+        let svc = Service::new(empty_hub(), "hyperspot".into(), allow_all_enforcer());
         let key = SecretRef::new("my-key").unwrap();
         let err = svc.get(&test_ctx(), &key).await.unwrap_err();
         assert!(
@@ -223,7 +532,7 @@ mod tests {
             "unavailable",
         )));
         hub.register::<dyn TypesRegistryClient>(registry.clone() as Arc<dyn TypesRegistryClient>);
-        let svc = Service::new(hub, "hyperspot".into());
+        let svc = Service::new(hub, "hyperspot".into(), allow_all_enforcer());
         let key = SecretRef::new("my-key").unwrap();
         assert!(svc.get(&test_ctx(), &key).await.is_err());
         assert!(svc.get(&test_ctx(), &key).await.is_err());
@@ -238,7 +547,7 @@ mod tests {
         let registry: Arc<dyn TypesRegistryClient> = Arc::new(MockRegistry::new(vec![]));
         hub.register::<dyn TypesRegistryClient>(registry);
 
-        let svc = Service::new(hub, "hyperspot".into());
+        let svc = Service::new(hub, "hyperspot".into(), allow_all_enforcer());
         let err = svc.resolve_plugin().await.unwrap_err();
         assert!(
             matches!(err, DomainError::PluginNotFound { .. }),
@@ -261,7 +570,7 @@ mod tests {
         let registry: Arc<dyn TypesRegistryClient> = Arc::new(MockRegistry::new(vec![entity]));
         hub.register::<dyn TypesRegistryClient>(registry);
 
-        let svc = Service::new(hub, "hyperspot".into());
+        let svc = Service::new(hub, "hyperspot".into(), allow_all_enforcer());
         let err = svc.resolve_plugin().await.unwrap_err();
         assert!(
             matches!(err, DomainError::PluginNotFound { .. }),
@@ -284,7 +593,7 @@ mod tests {
         let registry: Arc<dyn TypesRegistryClient> = Arc::new(MockRegistry::new(vec![entity]));
         hub.register::<dyn TypesRegistryClient>(registry);
 
-        let svc = Service::new(hub, "hyperspot".into());
+        let svc = Service::new(hub, "hyperspot".into(), allow_all_enforcer());
         let err = svc.resolve_plugin().await.unwrap_err();
         assert!(
             matches!(err, DomainError::InvalidPluginInstance { .. }),
@@ -300,7 +609,7 @@ mod tests {
         ));
         hub.register::<dyn TypesRegistryClient>(registry);
 
-        let svc = Service::new(hub, "hyperspot".into());
+        let svc = Service::new(hub, "hyperspot".into(), allow_all_enforcer());
         let err = svc.resolve_plugin().await.unwrap_err();
         assert!(
             matches!(err, DomainError::Internal(ref msg) if msg.contains("db down")),
@@ -314,7 +623,7 @@ mod tests {
         let hub =
             hub_with_registry_and_plugin(&instance_id, "hyperspot", MockPlugin::returns(None));
 
-        let svc = Service::new(hub, "hyperspot".into());
+        let svc = Service::new(hub, "hyperspot".into(), allow_all_enforcer());
         let resolved = svc.resolve_plugin().await.unwrap();
         assert_eq!(resolved, instance_id);
     }
@@ -337,7 +646,7 @@ mod tests {
         let registry: Arc<dyn TypesRegistryClient> = Arc::new(MockRegistry::new(vec![entity]));
         hub.register::<dyn TypesRegistryClient>(registry);
 
-        let svc = Service::new(hub, "hyperspot".into());
+        let svc = Service::new(hub, "hyperspot".into(), allow_all_enforcer());
         let err = svc.get_plugin().await.err().expect("expected Err");
         assert!(
             matches!(err, DomainError::PluginUnavailable { .. }),
@@ -354,7 +663,7 @@ mod tests {
             MockPlugin::returns(None),
         );
 
-        let svc = Service::new(hub, "hyperspot".into());
+        let svc = Service::new(hub, "hyperspot".into(), allow_all_enforcer());
         let p1 = svc.get_plugin().await.unwrap();
         let p2 = svc.get_plugin().await.unwrap();
 
@@ -379,6 +688,7 @@ mod tests {
             owner_id: Uuid::nil(),
             sharing: SharingMode::Tenant,
             owner_tenant_id: Uuid::nil(),
+            version: 1,
         };
         let hub = hub_with_registry_and_plugin(
             &instance_id,
@@ -386,7 +696,7 @@ mod tests {
             MockPlugin::returns(Some(&meta)),
         );
 
-        let svc = Service::new(hub, "hyperspot".into());
+        let svc = Service::new(hub, "hyperspot".into(), allow_all_enforcer());
         let key = SecretRef::new("my-key").unwrap();
         let resp = svc.get(&test_ctx(), &key).await.unwrap();
 
@@ -403,7 +713,7 @@ mod tests {
         let hub =
             hub_with_registry_and_plugin(&instance_id, "hyperspot", MockPlugin::returns(None));
 
-        let svc = Service::new(hub, "hyperspot".into());
+        let svc = Service::new(hub, "hyperspot".into(), allow_all_enforcer());
         let key = SecretRef::new("missing-key").unwrap();
         let result = svc.get(&test_ctx(), &key).await.unwrap();
         assert!(result.is_none(), "expected None for missing secret");
@@ -418,7 +728,7 @@ mod tests {
             MockPlugin::errors_internal("backend failure"),
         );
 
-        let svc = Service::new(hub, "hyperspot".into());
+        let svc = Service::new(hub, "hyperspot".into(), allow_all_enforcer());
         let key = SecretRef::new("any-key").unwrap();
         let err = svc.get(&test_ctx(), &key).await.unwrap_err();
         assert!(
@@ -426,4 +736,239 @@ mod tests {
             "expected Internal, got: {err:?}"
         );
     }
+
+    // ── write operations (static plugin backend) ─────────────────────────────
+
+    const SUBJECT_A: Uuid = Uuid::from_u128(0xA);
+    const SUBJECT_B: Uuid = Uuid::from_u128(0xB);
+    const TENANT: Uuid = Uuid::from_u128(0x1);
+
+    #[tokio::test]
+    async fn put_then_get_round_trip() {
+        let svc = static_plugin_service(allow_all_enforcer());
+        let ctx = ctx_for(SUBJECT_A, TENANT);
+        let key = SecretRef::new("vendor-key").unwrap();
+
+        assert_eq!(
+            svc.put(&ctx, &key, "v1".into(), SharingMode::Tenant)
+                .await
+                .unwrap(),
+            1
+        );
+        let resp = svc.get(&ctx, &key).await.unwrap().unwrap();
+        assert_eq!(resp.value.as_bytes(), b"v1");
+        assert_eq!(resp.version, 1);
+        assert_eq!(resp.owner_tenant_id, TENANT);
+    }
+
+    #[tokio::test]
+    async fn put_rejects_empty_value() {
+        let svc = static_plugin_service(allow_all_enforcer());
+        let key = SecretRef::new("vendor-key").unwrap();
+        let err = svc
+            .put(
+                &ctx_for(SUBJECT_A, TENANT),
+                &key,
+                "".into(),
+                SharingMode::Tenant,
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainError::InvalidRequest(_)),
+            "got: {err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn private_secret_shadows_tenant_secret_for_owner_only() {
+        let svc = static_plugin_service(allow_all_enforcer());
+        let ctx_a = ctx_for(SUBJECT_A, TENANT);
+        let ctx_b = ctx_for(SUBJECT_B, TENANT);
+        let key = SecretRef::new("vendor-key").unwrap();
+
+        svc.put(&ctx_b, &key, "tenant".into(), SharingMode::Tenant)
+            .await
+            .unwrap();
+        svc.put(&ctx_a, &key, "mine".into(), SharingMode::Private)
+            .await
+            .unwrap();
+
+        let a = svc.get(&ctx_a, &key).await.unwrap().unwrap();
+        assert_eq!(a.value.as_bytes(), b"mine");
+        assert_eq!(a.sharing, SharingMode::Private);
+
+        let b = svc.get(&ctx_b, &key).await.unwrap().unwrap();
+        assert_eq!(b.value.as_bytes(), b"tenant");
+    }
+
+    #[tokio::test]
+    async fn create_conflicts_with_existing_secret_in_same_scope() {
+        let svc = static_plugin_service(allow_all_enforcer());
+        let ctx = ctx_for(SUBJECT_A, TENANT);
+        let key = SecretRef::new("vendor-key").unwrap();
+
+        svc.create(&ctx, &key, "v1".into(), SharingMode::Tenant)
+            .await
+            .unwrap();
+        let err = svc
+            .create(&ctx, &key, "v2".into(), SharingMode::Tenant)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::AlreadyExists), "got: {err:?}");
+
+        // A private secret lives in a separate keyspace.
+        svc.create(&ctx, &key, "p".into(), SharingMode::Private)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn rotate_keeps_previous_version_readable() {
+        let svc = static_plugin_service(allow_all_enforcer());
+        let ctx = ctx_for(SUBJECT_A, TENANT);
+        let key = SecretRef::new("vendor-key").unwrap();
+        svc.put(&ctx, &key, "old".into(), SharingMode::Tenant)
+            .await
+            .unwrap();
+
+        let resp = svc
+            .rotate(&ctx, &key, "new".into(), Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(resp.version, 2);
+        assert_eq!(resp.previous.version, 1);
+        assert!(resp.previous.expires_at > OffsetDateTime::now_utc());
+
+        let current = svc.get(&ctx, &key).await.unwrap().unwrap();
+        assert_eq!(current.value.as_bytes(), b"new");
+        let previous = svc.get_version(&ctx, &key, 1).await.unwrap().unwrap();
+        assert_eq!(previous.value.as_bytes(), b"old");
+        assert_eq!(previous.version, 1);
+    }
+
+    #[tokio::test]
+    async fn rotate_with_zero_grace_period_retires_previous_version() {
+        let svc = static_plugin_service(allow_all_enforcer());
+        let ctx = ctx_for(SUBJECT_A, TENANT);
+        let key = SecretRef::new("vendor-key").unwrap();
+        svc.put(&ctx, &key, "old".into(), SharingMode::Tenant)
+            .await
+            .unwrap();
+
+        svc.rotate(&ctx, &key, "new".into(), Duration::ZERO)
+            .await
+            .unwrap();
+        assert!(svc.get_version(&ctx, &key, 1).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn rotate_rejects_grace_period_above_maximum() {
+        let svc = static_plugin_service(allow_all_enforcer()).with_rotation(RotationConfig {
+            default_grace_period: Duration::from_secs(10),
+            max_grace_period: Duration::from_secs(60),
+        });
+        let ctx = ctx_for(SUBJECT_A, TENANT);
+        let key = SecretRef::new("vendor-key").unwrap();
+        svc.put(&ctx, &key, "old".into(), SharingMode::Tenant)
+            .await
+            .unwrap();
+
+        let err = svc
+            .rotate(&ctx, &key, "new".into(), Duration::from_secs(61))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainError::InvalidRequest(_)),
+            "got: {err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn rotate_and_delete_return_not_found_for_foreign_private_secret() {
+        let svc = static_plugin_service(allow_all_enforcer());
+        let key = SecretRef::new("vendor-key").unwrap();
+        svc.put(
+            &ctx_for(SUBJECT_A, TENANT),
+            &key,
+            "mine".into(),
+            SharingMode::Private,
+        )
+        .await
+        .unwrap();
+
+        let ctx_b = ctx_for(SUBJECT_B, TENANT);
+        let err = svc
+            .rotate(&ctx_b, &key, "x".into(), Duration::ZERO)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::NotFound), "got: {err:?}");
+        let err = svc.delete(&ctx_b, &key).await.unwrap_err();
+        assert!(matches!(err, DomainError::NotFound), "got: {err:?}");
+    }
+
+    #[tokio::test]
+    async fn delete_removes_secret() {
+        let svc = static_plugin_service(allow_all_enforcer());
+        let ctx = ctx_for(SUBJECT_A, TENANT);
+        let key = SecretRef::new("vendor-key").unwrap();
+        svc.put(&ctx, &key, "v".into(), SharingMode::Shared)
+            .await
+            .unwrap();
+
+        svc.delete(&ctx, &key).await.unwrap();
+        assert!(svc.get(&ctx, &key).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn list_hides_other_subjects_private_secrets() {
+        let svc = static_plugin_service(allow_all_enforcer());
+        let ctx_a = ctx_for(SUBJECT_A, TENANT);
+        let ctx_b = ctx_for(SUBJECT_B, TENANT);
+        svc.put(
+            &ctx_a,
+            &SecretRef::new("a-private").unwrap(),
+            "v".into(),
+            SharingMode::Private,
+        )
+        .await
+        .unwrap();
+        svc.put(
+            &ctx_b,
+            &SecretRef::new("b-tenant").unwrap(),
+            "v".into(),
+            SharingMode::Tenant,
+        )
+        .await
+        .unwrap();
+
+        let keys = |items: Vec<SecretInfo>| {
+            items
+                .into_iter()
+                .map(|i| i.key.as_ref().to_owned())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            keys(svc.list(&ctx_a).await.unwrap()),
+            ["a-private", "b-tenant"]
+        );
+        assert_eq!(keys(svc.list(&ctx_b).await.unwrap()), ["b-tenant"]);
+    }
+
+    #[tokio::test]
+    async fn operations_fail_with_access_denied_when_policy_denies() {
+        let svc = static_plugin_service(deny_all_enforcer());
+        let ctx = ctx_for(SUBJECT_A, TENANT);
+        let key = SecretRef::new("vendor-key").unwrap();
+
+        let err = svc
+            .put(&ctx, &key, "v".into(), SharingMode::Tenant)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::AccessDenied(_)), "got: {err:?}");
+        let err = svc.get(&ctx, &key).await.unwrap_err();
+        assert!(matches!(err, DomainError::AccessDenied(_)), "got: {err:?}");
+        let err = svc.list(&ctx).await.unwrap_err();
+        assert!(matches!(err, DomainError::AccessDenied(_)), "got: {err:?}");
+    }
 }
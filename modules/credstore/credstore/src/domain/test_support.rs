@@ -1,16 +1,23 @@
 //! Shared test infrastructure for domain-layer unit tests.
 //!
-//! Provides `MockRegistry` and `MockPlugin` used by both `service` and
-//! `local_client` test modules.
+//! Provides `MockRegistry`, `MockPlugin` and mock `AuthZ` resolvers used by
+//! both `service` and `local_client` test modules.
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use async_trait::async_trait;
+use authz_resolver_sdk::models::{
+    EvaluationRequest, EvaluationResponse, EvaluationResponseContext,
+};
+use authz_resolver_sdk::{AuthZResolverClient, AuthZResolverError, PolicyEnforcer};
 use credstore_sdk::{
-    CredStoreError, CredStorePluginClientV1, SecretMetadata, SecretValue, SharingMode,
+    CredStoreError, CredStorePluginClientV1, CredStorePluginSpecV1, OwnerId, SecretInfo,
+    SecretMetadata, SecretValue, SecretVersion, SharingMode, TenantId,
 };
+use modkit::client_hub::{ClientHub, ClientScope};
 use modkit_security::SecurityContext;
+use time::OffsetDateTime;
 use types_registry_sdk::{
     GtsEntity, ListQuery, RegisterResult, TypesRegistryClient, TypesRegistryError,
 };
@@ -18,6 +25,8 @@ use uuid::Uuid;
 
 use credstore_sdk::SecretRef;
 
+use super::Service;
+
 // ── SecurityContext ───────────────────────────────────────────────────────────
 
 /// Build a minimal [`SecurityContext`] suitable for unit tests.
@@ -34,6 +43,52 @@ pub fn test_ctx() -> SecurityContext {
         .unwrap()
 }
 
+/// Build a [`SecurityContext`] for a concrete subject and tenant.
+///
+/// # Panics
+///
+/// Panics if the builder fails.
+#[must_use]
+pub fn ctx_for(subject_id: Uuid, tenant_id: Uuid) -> SecurityContext {
+    SecurityContext::builder()
+        .subject_id(subject_id)
+        .subject_tenant_id(tenant_id)
+        .build()
+        .unwrap()
+}
+
+// ── AuthZ ─────────────────────────────────────────────────────────────────────
+
+/// Mock `AuthZ` resolver returning a fixed decision without constraints.
+pub struct MockAuthZResolver {
+    decision: bool,
+}
+
+#[async_trait]
+impl AuthZResolverClient for MockAuthZResolver {
+    async fn evaluate(
+        &self,
+        _request: EvaluationRequest,
+    ) -> Result<EvaluationResponse, AuthZResolverError> {
+        Ok(EvaluationResponse {
+            decision: self.decision,
+            context: EvaluationResponseContext::default(),
+        })
+    }
+}
+
+/// Policy enforcer backed by a resolver that allows everything.
+#[must_use]
+pub fn allow_all_enforcer() -> PolicyEnforcer {
+    PolicyEnforcer::new(Arc::new(MockAuthZResolver { decision: true }))
+}
+
+/// Policy enforcer backed by a resolver that denies everything.
+#[must_use]
+pub fn deny_all_enforcer() -> PolicyEnforcer {
+    PolicyEnforcer::new(Arc::new(MockAuthZResolver { decision: false }))
+}
+
 // ── MockRegistry ──────────────────────────────────────────────────────────────
 
 pub struct MockRegistry {
@@ -103,6 +158,7 @@ impl MockPlugin {
         let owner_id = meta.map_or(Uuid::nil(), |m| m.owner_id);
         let sharing = meta.map_or(SharingMode::Tenant, |m| m.sharing);
         let owner_tenant_id = meta.map_or(Uuid::nil(), |m| m.owner_tenant_id);
+        let version = meta.map_or(1, |m| m.version);
         Arc::new(Self {
             handler: Arc::new(move || {
                 Ok(bytes.as_ref().map(|b| SecretMetadata {
@@ -110,6 +166,7 @@ impl MockPlugin {
                     owner_id,
                     sharing,
                     owner_tenant_id,
+                    version,
                 }))
             }),
         })
@@ -135,8 +192,106 @@ impl CredStorePluginClientV1 for MockPlugin {
     async fn get(
         &self,
         _ctx: &SecurityContext,
+        _tenant_id: &TenantId,
+        _key: &SecretRef,
+        _owner_id: Option<&OwnerId>,
+    ) -> Result<Option<SecretMetadata>, CredStoreError> {
+        (self.handler)()
+    }
+
+    async fn get_version(
+        &self,
+        _ctx: &SecurityContext,
+        _tenant_id: &TenantId,
         _key: &SecretRef,
+        _owner_id: Option<&OwnerId>,
+        _version: SecretVersion,
     ) -> Result<Option<SecretMetadata>, CredStoreError> {
         (self.handler)()
     }
+
+    async fn put(
+        &self,
+        _ctx: &SecurityContext,
+        _tenant_id: &TenantId,
+        _key: &SecretRef,
+        _value: SecretValue,
+        _sharing: SharingMode,
+        _owner_id: &OwnerId,
+    ) -> Result<SecretVersion, CredStoreError> {
+        (self.handler)().map(|_| 1)
+    }
+
+    async fn rotate(
+        &self,
+        _ctx: &SecurityContext,
+        _tenant_id: &TenantId,
+        _key: &SecretRef,
+        _owner_id: Option<&OwnerId>,
+        _value: SecretValue,
+        _previous_expires_at: OffsetDateTime,
+    ) -> Result<SecretVersion, CredStoreError> {
+        (self.handler)()?
+            .map(|m| m.version + 1)
+            .ok_or(CredStoreError::NotFound)
+    }
+
+    async fn delete(
+        &self,
+        _ctx: &SecurityContext,
+        _tenant_id: &TenantId,
+        _key: &SecretRef,
+        _owner_id: Option<&OwnerId>,
+    ) -> Result<(), CredStoreError> {
+        (self.handler)()?
+            .map(|_| ())
+            .ok_or(CredStoreError::NotFound)
+    }
+
+    async fn list(
+        &self,
+        _ctx: &SecurityContext,
+        _tenant_id: &TenantId,
+        _owner_id: &OwnerId,
+    ) -> Result<Vec<SecretInfo>, CredStoreError> {
+        (self.handler)().map(|_| Vec::new())
+    }
+}
+
+// ── Static plugin backend ─────────────────────────────────────────────────────
+
+/// Build a [`Service`] wired to an empty in-memory static plugin under the
+/// `hyperspot` vendor, for tests that exercise real write semantics.
+///
+/// # Panics
+///
+/// Panics if the static plugin cannot be built from its default config.
+#[must_use]
+pub fn static_plugin_service(policy_enforcer: PolicyEnforcer) -> Service {
+    let instance_id = format!("{}test._.static.v1", CredStorePluginSpecV1::gts_schema_id());
+    let entity = GtsEntity {
+        id: Uuid::nil(),
+        gts_id: instance_id.clone(),
+        segments: vec![],
+        is_schema: false,
+        content: serde_json::json!({
+            "id": instance_id,
+            "vendor": "hyperspot",
+            "priority": 0,
+            "properties": {}
+        }),
+        description: None,
+    };
+    let plugin = static_credstore_plugin::domain::Service::from_config(
+        &static_credstore_plugin::config::StaticCredStorePluginConfig::default(),
+    )
+    .unwrap();
+
+    let hub = Arc::new(ClientHub::default());
+    hub.register::<dyn TypesRegistryClient>(Arc::new(MockRegistry::new(vec![entity])));
+    hub.register_scoped::<dyn CredStorePluginClientV1>(
+        ClientScope::gts_id(&instance_id),
+        Arc::new(plugin),
+    );
+    Service::new(hub, "hyperspot".into(), policy_enforcer)
 }
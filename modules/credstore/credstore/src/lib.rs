@@ -3,10 +3,12 @@
 //! Implements the `CredStore` gateway module that:
 //! 1. Registers the `CredStorePluginSpecV1` schema in types-registry
 //! 2. Discovers plugin instances via types-registry (lazy, first-use)
-//! 3. Routes `get`/`put`/`delete` calls through the selected plugin
+//! 3. Routes `get`/`put`/`rotate`/`delete`/`list` calls through the selected plugin
 //! 4. Registers `Arc<dyn CredStoreClientV1>` in `ClientHub` for consumers
+//! 5. Exposes secret management REST endpoints under `/credstore/v1/secrets`
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 
+pub mod api;
 pub mod config;
 pub mod domain;
 pub mod module;
@@ -3,9 +3,11 @@
 use std::sync::{Arc, OnceLock};
 
 use async_trait::async_trait;
+use authz_resolver_sdk::{AuthZResolverClient, PolicyEnforcer};
 use credstore_sdk::{CredStoreClientV1, CredStorePluginSpecV1};
+use modkit::api::OpenApiRegistry;
 use modkit::contracts::SystemCapability;
-use modkit::{Module, ModuleCtx};
+use modkit::{Module, ModuleCtx, RestApiCapability};
 use tracing::info;
 use types_registry_sdk::{RegisterResult, TypesRegistryClient};
 
@@ -19,10 +21,11 @@ use crate::domain::{CredStoreLocalClient, Service};
 /// 2. Discovers plugin instances via types-registry (lazy, first-use)
 /// 3. Routes secret operations through the selected plugin
 /// 4. Registers `Arc<dyn CredStoreClientV1>` in `ClientHub` for consumers
+/// 5. Exposes secret management REST endpoints, authorized through the PEP
 #[modkit::module(
     name = "credstore",
-    deps = ["types-registry"],
-    capabilities = [system]
+    deps = ["types-registry", "authz-resolver"],
+    capabilities = [system, rest]
 )]
 pub struct CredStoreModule {
     service: OnceLock<Arc<Service>>,
@@ -55,9 +58,17 @@ impl Module for CredStoreModule {
             "Registered CredStore plugin schema in types-registry"
         );
 
+        // Fetch AuthZ resolver from ClientHub
+        let authz = ctx
+            .client_hub()
+            .get::<dyn AuthZResolverClient>()
+            .map_err(|e| anyhow::anyhow!("failed to get AuthZ resolver: {e}"))?;
+        let policy_enforcer = PolicyEnforcer::new(authz);
+
         // Create domain service
         let hub = ctx.client_hub();
-        let svc = Arc::new(Service::new(hub, cfg.vendor));
+        let svc =
+            Arc::new(Service::new(hub, cfg.vendor, policy_enforcer).with_rotation(cfg.rotation));
         self.service
             .set(svc.clone())
             .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
@@ -72,3 +83,24 @@ impl Module for CredStoreModule {
 
 #[async_trait]
 impl SystemCapability for CredStoreModule {}
+
+impl RestApiCapability for CredStoreModule {
+    fn register_rest(
+        &self,
+        _ctx: &ModuleCtx,
+        router: axum::Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> anyhow::Result<axum::Router> {
+        info!("Registering credstore REST routes");
+
+        let service = self
+            .service
+            .get()
+            .ok_or_else(|| anyhow::anyhow!("Service not initialized"))?
+            .clone();
+
+        Ok(crate::api::rest::routes::register_routes(
+            router, openapi, service,
+        ))
+    }
+}
@@ -0,0 +1,124 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+/// Plugin configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DbCredStorePluginConfig {
+    /// Vendor name for GTS instance registration.
+    pub vendor: String,
+
+    /// Plugin priority (lower = higher priority).
+    pub priority: i16,
+
+    /// Master key used to wrap newly created tenant data keys.
+    pub master_key: MasterKeyConfig,
+
+    /// Retired master keys, kept so that data keys wrapped by them can still
+    /// be unwrapped and re-wrapped under `master_key`.
+    pub previous_master_keys: Vec<MasterKeyConfig>,
+
+    /// Re-wrap every data key not wrapped by `master_key` during init.
+    pub rewrap_on_start: bool,
+}
+
+impl Default for DbCredStorePluginConfig {
+    fn default() -> Self {
+        Self {
+            vendor: "hyperspot".to_owned(),
+            priority: 50,
+            master_key: MasterKeyConfig::default(),
+            previous_master_keys: Vec::new(),
+            rewrap_on_start: true,
+        }
+    }
+}
+
+/// Location of a master key.
+///
+/// The key material is a base64-encoded 256-bit key read either from a file
+/// or from an environment variable; exactly one of `file` and `env` must be
+/// set.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MasterKeyConfig {
+    /// Stable identifier stored next to every data key wrapped by this key.
+    pub id: String,
+
+    /// Path to a file holding the base64-encoded key.
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+
+    /// Name of an environment variable holding the base64-encoded key.
+    #[serde(default)]
+    pub env: Option<String>,
+}
+
+impl Default for MasterKeyConfig {
+    fn default() -> Self {
+        Self {
+            id: "primary".to_owned(),
+            file: None,
+            env: Some("HYPERSPOT_CREDSTORE_MASTER_KEY".to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_defaults_are_applied() {
+        let cfg: DbCredStorePluginConfig = serde_saphyr::from_str("{}").unwrap();
+
+        assert_eq!(cfg.vendor, "hyperspot");
+        assert_eq!(cfg.priority, 50);
+        assert_eq!(cfg.master_key.id, "primary");
+        assert_eq!(
+            cfg.master_key.env.as_deref(),
+            Some("HYPERSPOT_CREDSTORE_MASTER_KEY")
+        );
+        assert!(cfg.previous_master_keys.is_empty());
+        assert!(cfg.rewrap_on_start);
+    }
+
+    #[test]
+    fn config_parses_key_sources() {
+        let yaml = r#"
+master_key:
+  id: "2026-10"
+  file: "/etc/hyperspot/credstore.key"
+previous_master_keys:
+  - id: "2026-01"
+    env: "CREDSTORE_OLD_KEY"
+"#;
+
+        let cfg: DbCredStorePluginConfig = serde_saphyr::from_str(yaml).unwrap();
+        assert_eq!(cfg.master_key.id, "2026-10");
+        assert_eq!(
+            cfg.master_key.file,
+            Some(PathBuf::from("/etc/hyperspot/credstore.key"))
+        );
+        assert!(cfg.master_key.env.is_none());
+        assert_eq!(cfg.previous_master_keys.len(), 1);
+        assert_eq!(
+            cfg.previous_master_keys[0].env.as_deref(),
+            Some("CREDSTORE_OLD_KEY")
+        );
+    }
+
+    #[test]
+    fn config_rejects_unknown_fields() {
+        let yaml = r#"
+master_key:
+  id: "k1"
+  value: "inline keys are not supported"
+"#;
+
+        let parsed: Result<DbCredStorePluginConfig, _> = serde_saphyr::from_str(yaml);
+        assert!(parsed.is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use credstore_sdk::{
+    CredStoreError, CredStorePluginClientV1, OwnerId, SecretInfo, SecretMetadata, SecretRef,
+    SecretValue, SecretVersion, SharingMode, TenantId,
+};
+use modkit_security::SecurityContext;
+use time::OffsetDateTime;
+
+use super::service::Service;
+
+#[async_trait]
+impl CredStorePluginClientV1 for Service {
+    async fn get(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        key: &SecretRef,
+        owner_id: Option<&OwnerId>,
+    ) -> Result<Option<SecretMetadata>, CredStoreError> {
+        self.get(*tenant_id, key, owner_id.copied(), None).await
+    }
+
+    async fn get_version(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        key: &SecretRef,
+        owner_id: Option<&OwnerId>,
+        version: SecretVersion,
+    ) -> Result<Option<SecretMetadata>, CredStoreError> {
+        self.get(*tenant_id, key, owner_id.copied(), Some(version))
+            .await
+    }
+
+    async fn put(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        key: &SecretRef,
+        value: SecretValue,
+        sharing: SharingMode,
+        owner_id: &OwnerId,
+    ) -> Result<SecretVersion, CredStoreError> {
+        self.put(*tenant_id, key, value, sharing, *owner_id).await
+    }
+
+    async fn rotate(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        key: &SecretRef,
+        owner_id: Option<&OwnerId>,
+        value: SecretValue,
+        previous_expires_at: OffsetDateTime,
+    ) -> Result<SecretVersion, CredStoreError> {
+        self.rotate(
+            *tenant_id,
+            key,
+            owner_id.copied(),
+            value,
+            previous_expires_at,
+        )
+        .await?
+        .ok_or(CredStoreError::NotFound)
+    }
+
+    async fn delete(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        key: &SecretRef,
+        owner_id: Option<&OwnerId>,
+    ) -> Result<(), CredStoreError> {
+        if self.delete(*tenant_id, key, owner_id.copied()).await? {
+            Ok(())
+        } else {
+            Err(CredStoreError::NotFound)
+        }
+    }
+
+    async fn list(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        owner_id: &OwnerId,
+    ) -> Result<Vec<SecretInfo>, CredStoreError> {
+        self.list(*tenant_id, *owner_id).await
+    }
+}
@@ -0,0 +1,151 @@
+//! AES-256-GCM primitives shared by data keys and master keys.
+//!
+//! Sealed payloads are laid out as `nonce || ciphertext || tag`; the nonce
+//! is random per call, so the same key can seal any number of values.
+
+use aws_lc_rs::aead::{AES_256_GCM, Aad, NONCE_LEN, Nonce, RandomizedNonceKey};
+use zeroize::Zeroizing;
+
+/// Length of an AES-256 key in bytes.
+pub const KEY_LEN: usize = 32;
+
+/// Sealing or opening failed.
+///
+/// Deliberately carries no detail: AEAD failures must not reveal whether the
+/// key, the nonce or the associated data was wrong.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct CryptoError(&'static str);
+
+/// A 256-bit AES key whose bytes are zeroed on drop.
+pub struct DataKey(Zeroizing<[u8; KEY_LEN]>);
+
+impl DataKey {
+    /// Generate a fresh random key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the system random generator fails.
+    pub fn generate() -> Result<Self, CryptoError> {
+        let mut bytes = Zeroizing::new([0u8; KEY_LEN]);
+        aws_lc_rs::rand::fill(bytes.as_mut())
+            .map_err(|_| CryptoError("random generator failure"))?;
+        Ok(Self(bytes))
+    }
+
+    /// Build a key from raw bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not exactly [`KEY_LEN`] long.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, CryptoError> {
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        if bytes.len() != KEY_LEN {
+            return Err(CryptoError("key must be 32 bytes long"));
+        }
+        key.copy_from_slice(bytes);
+        Ok(Self(key))
+    }
+
+    /// Raw key bytes.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+
+    fn aead_key(&self) -> Result<RandomizedNonceKey, CryptoError> {
+        RandomizedNonceKey::new(&AES_256_GCM, self.as_bytes())
+            .map_err(|_| CryptoError("invalid AES-256-GCM key"))
+    }
+
+    /// Encrypt `plaintext`, authenticating `aad` alongside it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if encryption fails.
+    pub fn seal(&self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let key = self.aead_key()?;
+        // Reserve room for the tag up front so appending it never reallocates
+        // and leaves a stray plaintext copy behind in freed memory.
+        let mut in_out = Vec::with_capacity(plaintext.len() + AES_256_GCM.tag_len());
+        in_out.extend_from_slice(plaintext);
+        let nonce = key
+            .seal_in_place_append_tag(Aad::from(aad), &mut in_out)
+            .map_err(|_| CryptoError("encryption failed"))?;
+
+        let mut sealed = Vec::with_capacity(NONCE_LEN + in_out.len());
+        sealed.extend_from_slice(nonce.as_ref());
+        sealed.extend_from_slice(&in_out);
+        Ok(sealed)
+    }
+
+    /// Decrypt a payload produced by [`DataKey::seal`] with the same `aad`.
+    ///
+    /// The plaintext is returned in a buffer that is zeroed on drop.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payload is malformed, was sealed with another
+    /// key or associated data, or has been tampered with.
+    pub fn open(&self, aad: &[u8], sealed: &[u8]) -> Result<Zeroizing<Vec<u8>>, CryptoError> {
+        if sealed.len() < NONCE_LEN + AES_256_GCM.tag_len() {
+            return Err(CryptoError("sealed payload is truncated"));
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let nonce =
+            Nonce::try_assume_unique_for_key(nonce).map_err(|_| CryptoError("invalid nonce"))?;
+
+        let key = self.aead_key()?;
+        let mut buf = Zeroizing::new(ciphertext.to_vec());
+        let len = key
+            .open_in_place(nonce, Aad::from(aad), buf.as_mut_slice())
+            .map_err(|_| CryptoError("decryption failed"))?
+            .len();
+        buf.truncate(len);
+        Ok(buf)
+    }
+}
+
+impl core::fmt::Debug for DataKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("DataKey([REDACTED])")
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_then_open_round_trips() {
+        let key = DataKey::generate().unwrap();
+        let sealed = key.seal(b"aad", b"sk-test-123").unwrap();
+
+        assert_ne!(&sealed[NONCE_LEN..sealed.len() - 16], b"sk-test-123");
+        assert_eq!(
+            key.open(b"aad", &sealed).unwrap().as_slice(),
+            b"sk-test-123"
+        );
+    }
+
+    #[test]
+    fn open_rejects_wrong_aad_key_or_tampering() {
+        let key = DataKey::generate().unwrap();
+        let mut sealed = key.seal(b"aad", b"value").unwrap();
+
+        assert!(key.open(b"other", &sealed).is_err());
+        assert!(DataKey::generate().unwrap().open(b"aad", &sealed).is_err());
+        assert!(key.open(b"aad", &sealed[..10]).is_err());
+
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(key.open(b"aad", &sealed).is_err());
+    }
+
+    #[test]
+    fn from_slice_requires_32_bytes() {
+        assert!(DataKey::from_slice(&[0u8; 16]).is_err());
+        assert!(DataKey::from_slice(&[7u8; KEY_LEN]).is_ok());
+    }
+}
@@ -0,0 +1,285 @@
+//! Key-encryption keys (KEKs) protecting the per-tenant data keys.
+//!
+//! Data keys never reach the database in clear: they are wrapped by a KEK
+//! supplied through [`KeyEncryptionKeyProvider`]. The bundled
+//! [`LocalKekProvider`] reads master keys from files or environment
+//! variables; a KMS- or HSM-backed provider can be plugged in instead.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use credstore_sdk::TenantId;
+use zeroize::Zeroizing;
+
+use super::crypto::DataKey;
+use crate::config::{DbCredStorePluginConfig, MasterKeyConfig};
+
+/// A data key encrypted under a key-encryption key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrappedKey {
+    /// Identifier of the KEK that wrapped the key.
+    pub kek_id: String,
+    /// Encrypted key material.
+    pub ciphertext: Vec<u8>,
+}
+
+/// Errors raised by a [`KeyEncryptionKeyProvider`].
+#[derive(Debug, thiserror::Error)]
+pub enum KekError {
+    #[error("unknown key encryption key '{0}'")]
+    UnknownKey(String),
+
+    #[error("key encryption key '{kek_id}' failed: {reason}")]
+    Crypto { kek_id: String, reason: String },
+
+    #[error("key provider unavailable: {0}")]
+    Unavailable(String),
+}
+
+/// Wraps and unwraps tenant data keys.
+///
+/// Implementations must bind the wrapped key to `tenant_id` so that a wrapped
+/// key copied to another tenant's row fails to unwrap.
+#[async_trait]
+pub trait KeyEncryptionKeyProvider: Send + Sync {
+    /// Identifier of the KEK used by [`wrap`](Self::wrap).
+    fn current_key_id(&self) -> &str;
+
+    /// Wrap `key` under the current KEK.
+    async fn wrap(&self, tenant_id: TenantId, key: &DataKey) -> Result<WrappedKey, KekError>;
+
+    /// Unwrap a key wrapped by the current or a previous KEK.
+    async fn unwrap(&self, tenant_id: TenantId, wrapped: &WrappedKey) -> Result<DataKey, KekError>;
+}
+
+/// KEK provider backed by locally configured master keys.
+pub struct LocalKekProvider {
+    current_id: String,
+    keys: HashMap<String, DataKey>,
+}
+
+impl LocalKekProvider {
+    /// Create a provider wrapping new keys with `key`.
+    #[must_use]
+    pub fn new(id: impl Into<String>, key: DataKey) -> Self {
+        let id = id.into();
+        let mut keys = HashMap::new();
+        keys.insert(id.clone(), key);
+        Self {
+            current_id: id,
+            keys,
+        }
+    }
+
+    /// Add a retired master key, used only for unwrapping.
+    #[must_use]
+    pub fn with_previous(mut self, id: impl Into<String>, key: DataKey) -> Self {
+        self.keys.entry(id.into()).or_insert(key);
+        self
+    }
+
+    /// Load the current and previous master keys named by the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a key source is missing or ambiguous, a key cannot
+    /// be read or decoded, or two keys share an id.
+    pub fn from_config(cfg: &DbCredStorePluginConfig) -> anyhow::Result<Self> {
+        let mut provider = Self::new(cfg.master_key.id.clone(), load_key(&cfg.master_key)?);
+        for previous in &cfg.previous_master_keys {
+            if provider.keys.contains_key(&previous.id) {
+                anyhow::bail!("duplicate master key id '{}'", previous.id);
+            }
+            provider = provider.with_previous(previous.id.clone(), load_key(previous)?);
+        }
+        Ok(provider)
+    }
+
+    fn key(&self, kek_id: &str) -> Result<&DataKey, KekError> {
+        self.keys
+            .get(kek_id)
+            .ok_or_else(|| KekError::UnknownKey(kek_id.to_owned()))
+    }
+}
+
+fn wrap_aad(tenant_id: TenantId) -> String {
+    format!("credstore-data-key:{tenant_id}")
+}
+
+#[async_trait]
+impl KeyEncryptionKeyProvider for LocalKekProvider {
+    fn current_key_id(&self) -> &str {
+        &self.current_id
+    }
+
+    async fn wrap(&self, tenant_id: TenantId, key: &DataKey) -> Result<WrappedKey, KekError> {
+        let ciphertext = self
+            .key(&self.current_id)?
+            .seal(wrap_aad(tenant_id).as_bytes(), key.as_bytes())
+            .map_err(|e| KekError::Crypto {
+                kek_id: self.current_id.clone(),
+                reason: e.to_string(),
+            })?;
+        Ok(WrappedKey {
+            kek_id: self.current_id.clone(),
+            ciphertext,
+        })
+    }
+
+    async fn unwrap(&self, tenant_id: TenantId, wrapped: &WrappedKey) -> Result<DataKey, KekError> {
+        let crypto_err = |reason: String| KekError::Crypto {
+            kek_id: wrapped.kek_id.clone(),
+            reason,
+        };
+        let raw = self
+            .key(&wrapped.kek_id)?
+            .open(wrap_aad(tenant_id).as_bytes(), &wrapped.ciphertext)
+            .map_err(|e| crypto_err(e.to_string()))?;
+        DataKey::from_slice(&raw).map_err(|e| crypto_err(e.to_string()))
+    }
+}
+
+/// Read and decode the key material named by `cfg`.
+fn load_key(cfg: &MasterKeyConfig) -> anyhow::Result<DataKey> {
+    let encoded = Zeroizing::new(match (&cfg.file, &cfg.env) {
+        (Some(path), None) => std::fs::read_to_string(path).map_err(|e| {
+            anyhow::anyhow!(
+                "failed to read master key '{}' from {}: {e}",
+                cfg.id,
+                path.display()
+            )
+        })?,
+        (None, Some(var)) => std::env::var(var).map_err(|e| {
+            anyhow::anyhow!("failed to read master key '{}' from ${var}: {e}", cfg.id)
+        })?,
+        _ => anyhow::bail!(
+            "master key '{}' must set exactly one of `file` or `env`",
+            cfg.id
+        ),
+    });
+    let raw = Zeroizing::new(
+        STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| anyhow::anyhow!("master key '{}' is not valid base64: {e}", cfg.id))?,
+    );
+    DataKey::from_slice(&raw).map_err(|e| anyhow::anyhow!("master key '{}': {e}", cfg.id))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::io::Write;
+
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn tenant() -> TenantId {
+        Uuid::from_u128(0x1)
+    }
+
+    fn key_file(key: &[u8]) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "{}", STANDARD.encode(key)).unwrap();
+        file
+    }
+
+    #[tokio::test]
+    async fn wrap_then_unwrap_round_trips() {
+        let provider = LocalKekProvider::new("k1", DataKey::generate().unwrap());
+        let dek = DataKey::generate().unwrap();
+
+        let wrapped = provider.wrap(tenant(), &dek).await.unwrap();
+        assert_eq!(wrapped.kek_id, "k1");
+
+        let unwrapped = provider.unwrap(tenant(), &wrapped).await.unwrap();
+        assert_eq!(unwrapped.as_bytes(), dek.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn unwrap_is_bound_to_tenant() {
+        let provider = LocalKekProvider::new("k1", DataKey::generate().unwrap());
+        let wrapped = provider
+            .wrap(tenant(), &DataKey::generate().unwrap())
+            .await
+            .unwrap();
+
+        let err = provider
+            .unwrap(Uuid::from_u128(0x2), &wrapped)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, KekError::Crypto { .. }));
+    }
+
+    #[tokio::test]
+    async fn previous_keys_unwrap_but_do_not_wrap() {
+        let old = LocalKekProvider::new("old", DataKey::from_slice(&[1u8; 32]).unwrap());
+        let wrapped = old
+            .wrap(tenant(), &DataKey::generate().unwrap())
+            .await
+            .unwrap();
+
+        let rotated = LocalKekProvider::new("new", DataKey::generate().unwrap())
+            .with_previous("old", DataKey::from_slice(&[1u8; 32]).unwrap());
+        assert_eq!(rotated.current_key_id(), "new");
+        assert!(rotated.unwrap(tenant(), &wrapped).await.is_ok());
+
+        let fresh = LocalKekProvider::new("new", DataKey::generate().unwrap());
+        let err = fresh.unwrap(tenant(), &wrapped).await.unwrap_err();
+        assert!(matches!(err, KekError::UnknownKey(id) if id == "old"));
+    }
+
+    #[test]
+    fn from_config_loads_keys_from_files() {
+        let current = key_file(&[1u8; 32]);
+        let previous = key_file(&[2u8; 32]);
+        let cfg = DbCredStorePluginConfig {
+            master_key: MasterKeyConfig {
+                id: "new".to_owned(),
+                file: Some(current.path().to_path_buf()),
+                env: None,
+            },
+            previous_master_keys: vec![MasterKeyConfig {
+                id: "old".to_owned(),
+                file: Some(previous.path().to_path_buf()),
+                env: None,
+            }],
+            ..DbCredStorePluginConfig::default()
+        };
+
+        let provider = LocalKekProvider::from_config(&cfg).unwrap();
+        assert_eq!(provider.current_key_id(), "new");
+        assert_eq!(provider.key("old").unwrap().as_bytes(), &[2u8; 32]);
+    }
+
+    #[test]
+    fn from_config_rejects_bad_key_sources() {
+        let short = key_file(&[1u8; 16]);
+        let cases = [
+            MasterKeyConfig {
+                id: "both".to_owned(),
+                file: Some(short.path().to_path_buf()),
+                env: Some("X".to_owned()),
+            },
+            MasterKeyConfig {
+                id: "neither".to_owned(),
+                file: None,
+                env: None,
+            },
+            MasterKeyConfig {
+                id: "short".to_owned(),
+                file: Some(short.path().to_path_buf()),
+                env: None,
+            },
+        ];
+        for master_key in cases {
+            let cfg = DbCredStorePluginConfig {
+                master_key,
+                ..DbCredStorePluginConfig::default()
+            };
+            assert!(LocalKekProvider::from_config(&cfg).is_err());
+        }
+    }
+}
@@ -0,0 +1,6 @@
+mod client;
+pub mod crypto;
+pub mod kek;
+pub mod service;
+
+pub use service::Service;
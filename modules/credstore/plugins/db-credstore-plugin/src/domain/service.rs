@@ -0,0 +1,663 @@
+use std::sync::Arc;
+
+use credstore_sdk::{
+    CredStoreError, OwnerId, RetiredVersion, SecretInfo, SecretMetadata, SecretRef, SecretValue,
+    SecretVersion, SharingMode, TenantId,
+};
+use modkit_db::{DBProvider, DbError};
+use modkit_macros::domain_model;
+use time::OffsetDateTime;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::crypto::DataKey;
+use super::kek::{KekError, KeyEncryptionKeyProvider, WrappedKey};
+use crate::infra::storage::entity::{secret, secret_version};
+use crate::infra::storage::{Seal, SecretAddress, SecretRepo, is_unique_violation};
+
+/// Database-backed credstore service.
+///
+/// Each value is sealed with AES-256-GCM under its tenant's data key, with
+/// the tenant, secret row and version bound in as associated data so that a
+/// ciphertext moved to another row fails to decrypt. Data keys are created
+/// on a tenant's first write and stored wrapped by the key-encryption key of
+/// [`KeyEncryptionKeyProvider`]; unwrapped keys and decrypted values live
+/// only in buffers that are zeroed on drop.
+#[domain_model]
+pub struct Service {
+    repo: SecretRepo,
+    kek: Arc<dyn KeyEncryptionKeyProvider>,
+}
+
+impl Service {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>, kek: Arc<dyn KeyEncryptionKeyProvider>) -> Self {
+        Self {
+            repo: SecretRepo::new(db),
+            kek,
+        }
+    }
+
+    /// Look up the current version of a secret, or `version` if given.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CredStoreError::Internal` if storage or decryption fails.
+    pub async fn get(
+        &self,
+        tenant_id: TenantId,
+        key: &SecretRef,
+        owner_id: Option<OwnerId>,
+        version: Option<SecretVersion>,
+    ) -> Result<Option<SecretMetadata>, CredStoreError> {
+        let addr = address(tenant_id, key, owner_id);
+        let Some(stored) = self
+            .repo
+            .find(&addr, version.map(i64::from))
+            .await
+            .map_err(|e| db_err(&e))?
+        else {
+            return Ok(None);
+        };
+        if stored
+            .version
+            .expires_at
+            .is_some_and(|at| at <= OffsetDateTime::now_utc())
+        {
+            return Ok(None);
+        }
+
+        let Some(data_key) = self.data_key(tenant_id).await? else {
+            return Err(CredStoreError::Internal(format!(
+                "tenant {tenant_id} has secrets but no data key"
+            )));
+        };
+        let aad = secret_aad(tenant_id, stored.secret.id, stored.version.version);
+        let mut plaintext = data_key
+            .open(aad.as_bytes(), &stored.version.ciphertext)
+            .map_err(|e| CredStoreError::Internal(format!("failed to decrypt secret: {e}")))?;
+
+        Ok(Some(SecretMetadata {
+            // Moves the buffer out without copying; `SecretValue` zeroes it on drop.
+            value: SecretValue::new(std::mem::take(&mut *plaintext)),
+            owner_id: stored.secret.owner_id,
+            sharing: sharing_from_str(&stored.secret.sharing)?,
+            owner_tenant_id: stored.secret.tenant_id,
+            version: to_version(stored.version.version)?,
+        }))
+    }
+
+    /// Create or replace a secret. Returns the new version.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CredStoreError::AlreadyExists` if a concurrent write created
+    /// the secret first, or `CredStoreError::Internal` if storage or
+    /// encryption fails.
+    pub async fn put(
+        &self,
+        tenant_id: TenantId,
+        key: &SecretRef,
+        value: SecretValue,
+        sharing: SharingMode,
+        owner_id: OwnerId,
+    ) -> Result<SecretVersion, CredStoreError> {
+        let data_key = self.data_key_or_create(tenant_id).await?;
+        let scope_owner = (sharing == SharingMode::Private).then_some(owner_id);
+        let version = self
+            .repo
+            .put(
+                address(tenant_id, key, scope_owner),
+                owner_id,
+                sharing_to_str(sharing),
+                sealer(data_key, tenant_id, value),
+            )
+            .await
+            .map_err(|e| {
+                if is_unique_violation(&e) {
+                    CredStoreError::AlreadyExists
+                } else {
+                    db_err(&e)
+                }
+            })?;
+        to_version(version)
+    }
+
+    /// Store a new version of an existing secret, keeping the replaced one
+    /// readable until `previous_expires_at`. Returns `None` if the secret
+    /// does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CredStoreError::Internal` if storage or encryption fails.
+    pub async fn rotate(
+        &self,
+        tenant_id: TenantId,
+        key: &SecretRef,
+        owner_id: Option<OwnerId>,
+        value: SecretValue,
+        previous_expires_at: OffsetDateTime,
+    ) -> Result<Option<SecretVersion>, CredStoreError> {
+        let Some(data_key) = self.data_key(tenant_id).await? else {
+            // No data key means the tenant never stored a secret.
+            return Ok(None);
+        };
+        self.repo
+            .rotate(
+                address(tenant_id, key, owner_id),
+                previous_expires_at,
+                sealer(data_key, tenant_id, value),
+            )
+            .await
+            .map_err(|e| db_err(&e))?
+            .map(to_version)
+            .transpose()
+    }
+
+    /// Delete a secret with all of its versions. Returns `false` if the
+    /// secret does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CredStoreError::Internal` if storage fails.
+    pub async fn delete(
+        &self,
+        tenant_id: TenantId,
+        key: &SecretRef,
+        owner_id: Option<OwnerId>,
+    ) -> Result<bool, CredStoreError> {
+        self.repo
+            .delete(address(tenant_id, key, owner_id))
+            .await
+            .map_err(|e| db_err(&e))
+    }
+
+    /// List tenant-wide secrets of `tenant_id` and the private secrets of
+    /// `owner_id`, ordered by key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CredStoreError::Internal` if storage fails or holds invalid
+    /// rows.
+    pub async fn list(
+        &self,
+        tenant_id: TenantId,
+        owner_id: OwnerId,
+    ) -> Result<Vec<SecretInfo>, CredStoreError> {
+        let now = OffsetDateTime::now_utc();
+        let rows = self
+            .repo
+            .list(tenant_id, owner_id, now)
+            .await
+            .map_err(|e| db_err(&e))?;
+
+        let mut items = rows
+            .into_iter()
+            .map(|(s, retired)| to_info(&s, retired))
+            .collect::<Result<Vec<_>, _>>()?;
+        // Tenant-wide secret first when a private one shares its key.
+        items.sort_by(|a, b| {
+            (a.key.as_ref(), a.sharing == SharingMode::Private)
+                .cmp(&(b.key.as_ref(), b.sharing == SharingMode::Private))
+        });
+        Ok(items)
+    }
+
+    /// Re-wrap every data key not wrapped by the current key-encryption key.
+    ///
+    /// Secret ciphertexts are untouched: only the data keys are re-encrypted,
+    /// so rotating the master key costs one row update per tenant. Returns the
+    /// number of keys re-wrapped by this call.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CredStoreError::Internal` if storage fails or a data key is
+    /// wrapped by a key the provider does not know.
+    pub async fn rewrap_data_keys(&self) -> Result<usize, CredStoreError> {
+        let current = self.kek.current_key_id().to_owned();
+        let stale = self
+            .repo
+            .data_keys_not_wrapped_by(&current)
+            .await
+            .map_err(|e| db_err(&e))?;
+
+        let mut rewrapped = 0;
+        for (tenant_id, wrapped) in stale {
+            if self.rewrap_data_key(tenant_id, &wrapped).await? {
+                rewrapped += 1;
+            } else {
+                warn!(%tenant_id, "Data key changed concurrently; skipped re-wrap");
+            }
+        }
+        if rewrapped > 0 {
+            info!(count = rewrapped, kek_id = %current, "Re-wrapped tenant data keys");
+        }
+        Ok(rewrapped)
+    }
+
+    async fn rewrap_data_key(
+        &self,
+        tenant_id: TenantId,
+        wrapped: &WrappedKey,
+    ) -> Result<bool, CredStoreError> {
+        let data_key = self.kek.unwrap(tenant_id, wrapped).await.map_err(kek_err)?;
+        let fresh = self.kek.wrap(tenant_id, &data_key).await.map_err(kek_err)?;
+        self.repo
+            .rewrap_data_key(tenant_id, &wrapped.kek_id, fresh)
+            .await
+            .map_err(|e| db_err(&e))
+    }
+
+    async fn data_key(&self, tenant_id: TenantId) -> Result<Option<DataKey>, CredStoreError> {
+        let Some(wrapped) = self
+            .repo
+            .data_key(tenant_id)
+            .await
+            .map_err(|e| db_err(&e))?
+        else {
+            return Ok(None);
+        };
+        let data_key = self
+            .kek
+            .unwrap(tenant_id, &wrapped)
+            .await
+            .map_err(kek_err)?;
+        Ok(Some(data_key))
+    }
+
+    async fn data_key_or_create(&self, tenant_id: TenantId) -> Result<DataKey, CredStoreError> {
+        if let Some(data_key) = self.data_key(tenant_id).await? {
+            return Ok(data_key);
+        }
+
+        let data_key = DataKey::generate()
+            .map_err(|e| CredStoreError::Internal(format!("failed to generate data key: {e}")))?;
+        let wrapped = self.kek.wrap(tenant_id, &data_key).await.map_err(kek_err)?;
+        match self.repo.insert_data_key(tenant_id, wrapped).await {
+            Ok(()) => Ok(data_key),
+            // Another writer created the tenant's key first; use theirs.
+            Err(e) if is_unique_violation(&e) => self.data_key(tenant_id).await?.ok_or_else(|| {
+                CredStoreError::Internal(format!("data key of tenant {tenant_id} vanished"))
+            }),
+            Err(e) => Err(db_err(&e)),
+        }
+    }
+}
+
+fn address(tenant_id: TenantId, key: &SecretRef, scope_owner: Option<OwnerId>) -> SecretAddress {
+    SecretAddress {
+        tenant_id,
+        reference: key.as_ref().to_owned(),
+        scope_owner_id: scope_owner.unwrap_or_else(Uuid::nil),
+    }
+}
+
+/// Associated data binding a ciphertext to its tenant, secret row and version.
+fn secret_aad(tenant_id: TenantId, secret_id: Uuid, version: i64) -> String {
+    format!("credstore-secret:{tenant_id}:{secret_id}:{version}")
+}
+
+fn sealer(data_key: DataKey, tenant_id: TenantId, value: SecretValue) -> Seal {
+    Box::new(move |secret_id, version| {
+        let aad = secret_aad(tenant_id, secret_id, version);
+        data_key
+            .seal(aad.as_bytes(), value.as_bytes())
+            .map_err(|e| anyhow::anyhow!("failed to encrypt secret: {e}"))
+    })
+}
+
+fn to_info(
+    s: &secret::Model,
+    retired: Vec<secret_version::Model>,
+) -> Result<SecretInfo, CredStoreError> {
+    let retired_versions = retired
+        .into_iter()
+        .filter_map(|v| {
+            v.expires_at.map(|expires_at| {
+                Ok(RetiredVersion {
+                    version: to_version(v.version)?,
+                    expires_at,
+                })
+            })
+        })
+        .collect::<Result<Vec<_>, CredStoreError>>()?;
+    Ok(SecretInfo {
+        key: SecretRef::new(&s.reference)
+            .map_err(|e| CredStoreError::Internal(format!("invalid stored reference: {e}")))?,
+        owner_id: s.owner_id,
+        owner_tenant_id: s.tenant_id,
+        sharing: sharing_from_str(&s.sharing)?,
+        version: to_version(s.current_version)?,
+        retired_versions,
+        created_at: s.created_at,
+        updated_at: s.updated_at,
+    })
+}
+
+fn sharing_to_str(sharing: SharingMode) -> &'static str {
+    match sharing {
+        SharingMode::Private => "private",
+        SharingMode::Tenant => "tenant",
+        SharingMode::Shared => "shared",
+    }
+}
+
+fn sharing_from_str(s: &str) -> Result<SharingMode, CredStoreError> {
+    match s {
+        "private" => Ok(SharingMode::Private),
+        "tenant" => Ok(SharingMode::Tenant),
+        "shared" => Ok(SharingMode::Shared),
+        other => Err(CredStoreError::Internal(format!(
+            "invalid stored sharing mode '{other}'"
+        ))),
+    }
+}
+
+fn to_version(v: i64) -> Result<SecretVersion, CredStoreError> {
+    SecretVersion::try_from(v)
+        .map_err(|_| CredStoreError::Internal(format!("invalid stored version {v}")))
+}
+
+fn db_err(e: &DbError) -> CredStoreError {
+    CredStoreError::Internal(format!("database error: {e}"))
+}
+
+fn kek_err(e: KekError) -> CredStoreError {
+    match e {
+        KekError::Unavailable(reason) => CredStoreError::ServiceUnavailable(reason),
+        other => CredStoreError::Internal(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use time::Duration;
+
+    use super::*;
+    use crate::domain::kek::LocalKekProvider;
+    use crate::infra::storage::test_provider;
+
+    fn tenant_a() -> Uuid {
+        Uuid::from_u128(0xA)
+    }
+
+    fn tenant_b() -> Uuid {
+        Uuid::from_u128(0xB)
+    }
+
+    fn owner() -> Uuid {
+        Uuid::from_u128(0x1)
+    }
+
+    fn other_owner() -> Uuid {
+        Uuid::from_u128(0x2)
+    }
+
+    fn key(name: &str) -> SecretRef {
+        SecretRef::new(name).unwrap()
+    }
+
+    fn master_key(byte: u8) -> DataKey {
+        DataKey::from_slice(&[byte; 32]).unwrap()
+    }
+
+    async fn service() -> Service {
+        Service::new(
+            test_provider().await,
+            Arc::new(LocalKekProvider::new("k1", master_key(1))),
+        )
+    }
+
+    async fn read(svc: &Service, tenant: Uuid, name: &str, owner: Option<Uuid>) -> Option<Vec<u8>> {
+        svc.get(tenant, &key(name), owner, None)
+            .await
+            .unwrap()
+            .map(|m| m.value.as_bytes().to_vec())
+    }
+
+    #[tokio::test]
+    async fn put_then_get_round_trips_and_stores_only_ciphertext() {
+        let svc = service().await;
+        let version = svc
+            .put(
+                tenant_a(),
+                &key("api-key"),
+                "sk-test-123".into(),
+                SharingMode::Tenant,
+                owner(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(version, 1);
+
+        let meta = svc
+            .get(tenant_a(), &key("api-key"), None, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(meta.value.as_bytes(), b"sk-test-123");
+        assert_eq!(meta.owner_id, owner());
+        assert_eq!(meta.owner_tenant_id, tenant_a());
+        assert_eq!(meta.sharing, SharingMode::Tenant);
+        assert_eq!(meta.version, 1);
+
+        let stored = svc
+            .repo
+            .find(&address(tenant_a(), &key("api-key"), None), None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            !stored
+                .version
+                .ciphertext
+                .windows(11)
+                .any(|w| w == b"sk-test-123")
+        );
+        let wrapped = svc.repo.data_key(tenant_a()).await.unwrap().unwrap();
+        assert_eq!(wrapped.kek_id, "k1");
+    }
+
+    #[tokio::test]
+    async fn tenants_are_isolated_and_use_distinct_data_keys() {
+        let svc = service().await;
+        for tenant in [tenant_a(), tenant_b()] {
+            svc.put(tenant, &key("k"), "v".into(), SharingMode::Tenant, owner())
+                .await
+                .unwrap();
+        }
+        svc.delete(tenant_b(), &key("k"), None).await.unwrap();
+
+        assert_eq!(read(&svc, tenant_a(), "k", None).await.unwrap(), b"v");
+        assert!(read(&svc, tenant_b(), "k", None).await.is_none());
+
+        let a = svc.repo.data_key(tenant_a()).await.unwrap().unwrap();
+        let b = svc.repo.data_key(tenant_b()).await.unwrap().unwrap();
+        assert_ne!(a.ciphertext, b.ciphertext);
+    }
+
+    #[tokio::test]
+    async fn private_secrets_are_keyed_by_owner() {
+        let svc = service().await;
+        svc.put(
+            tenant_a(),
+            &key("token"),
+            "mine".into(),
+            SharingMode::Private,
+            owner(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            read(&svc, tenant_a(), "token", Some(owner()))
+                .await
+                .unwrap(),
+            b"mine"
+        );
+        assert!(
+            read(&svc, tenant_a(), "token", Some(other_owner()))
+                .await
+                .is_none()
+        );
+        assert!(read(&svc, tenant_a(), "token", None).await.is_none());
+
+        let visible = svc.list(tenant_a(), other_owner()).await.unwrap();
+        assert!(visible.is_empty());
+    }
+
+    #[tokio::test]
+    async fn put_replaces_value_and_drops_previous_versions() {
+        let svc = service().await;
+        for value in ["v1", "v2"] {
+            svc.put(
+                tenant_a(),
+                &key("k"),
+                value.into(),
+                SharingMode::Shared,
+                owner(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let meta = svc
+            .get(tenant_a(), &key("k"), None, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(meta.value.as_bytes(), b"v2");
+        assert_eq!(meta.version, 2);
+        assert!(
+            svc.get(tenant_a(), &key("k"), None, Some(1))
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn rotate_keeps_previous_version_until_expiry() {
+        let svc = service().await;
+        svc.put(
+            tenant_a(),
+            &key("k"),
+            "old".into(),
+            SharingMode::Tenant,
+            owner(),
+        )
+        .await
+        .unwrap();
+
+        let expires_at = OffsetDateTime::now_utc() + Duration::hours(1);
+        let version = svc
+            .rotate(tenant_a(), &key("k"), None, "new".into(), expires_at)
+            .await
+            .unwrap();
+        assert_eq!(version, Some(2));
+
+        assert_eq!(read(&svc, tenant_a(), "k", None).await.unwrap(), b"new");
+        let previous = svc
+            .get(tenant_a(), &key("k"), None, Some(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(previous.value.as_bytes(), b"old");
+
+        let info = svc.list(tenant_a(), owner()).await.unwrap();
+        assert_eq!(info.len(), 1);
+        assert_eq!(info[0].version, 2);
+        assert_eq!(info[0].retired_versions.len(), 1);
+        assert_eq!(info[0].retired_versions[0].version, 1);
+
+        // An already-expired retirement makes the old version unreadable.
+        svc.rotate(
+            tenant_a(),
+            &key("k"),
+            None,
+            "newer".into(),
+            OffsetDateTime::now_utc() - Duration::seconds(1),
+        )
+        .await
+        .unwrap();
+        assert!(
+            svc.get(tenant_a(), &key("k"), None, Some(2))
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn rotate_and_delete_report_missing_secrets() {
+        let svc = service().await;
+        let rotated = svc
+            .rotate(
+                tenant_a(),
+                &key("missing"),
+                None,
+                "v".into(),
+                OffsetDateTime::now_utc(),
+            )
+            .await
+            .unwrap();
+        assert!(rotated.is_none());
+        assert!(!svc.delete(tenant_a(), &key("missing"), None).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn rewrap_moves_data_keys_to_the_new_master_key() {
+        let db = test_provider().await;
+        let old = Service::new(
+            db.clone(),
+            Arc::new(LocalKekProvider::new("old", master_key(1))),
+        );
+        old.put(
+            tenant_a(),
+            &key("k"),
+            "v".into(),
+            SharingMode::Tenant,
+            owner(),
+        )
+        .await
+        .unwrap();
+
+        let rotated = Service::new(
+            db.clone(),
+            Arc::new(
+                LocalKekProvider::new("new", master_key(2)).with_previous("old", master_key(1)),
+            ),
+        );
+        assert_eq!(rotated.rewrap_data_keys().await.unwrap(), 1);
+        assert_eq!(rotated.rewrap_data_keys().await.unwrap(), 0);
+
+        let new_only = Service::new(db, Arc::new(LocalKekProvider::new("new", master_key(2))));
+        assert_eq!(read(&new_only, tenant_a(), "k", None).await.unwrap(), b"v");
+    }
+
+    #[tokio::test]
+    async fn unknown_master_key_is_an_internal_error() {
+        let db = test_provider().await;
+        let old = Service::new(
+            db.clone(),
+            Arc::new(LocalKekProvider::new("old", master_key(1))),
+        );
+        old.put(
+            tenant_a(),
+            &key("k"),
+            "v".into(),
+            SharingMode::Tenant,
+            owner(),
+        )
+        .await
+        .unwrap();
+
+        let fresh = Service::new(db, Arc::new(LocalKekProvider::new("new", master_key(2))));
+        let err = fresh
+            .get(tenant_a(), &key("k"), None, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CredStoreError::Internal(_)));
+    }
+}
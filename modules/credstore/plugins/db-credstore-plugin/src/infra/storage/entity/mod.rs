@@ -0,0 +1,3 @@
+pub mod data_key;
+pub mod secret;
+pub mod secret_version;
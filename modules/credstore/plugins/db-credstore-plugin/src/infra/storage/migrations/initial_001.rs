@@ -0,0 +1,167 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let statements = match backend {
+            sea_orm::DatabaseBackend::Postgres => POSTGRES_UP,
+            sea_orm::DatabaseBackend::MySql => MYSQL_UP,
+            sea_orm::DatabaseBackend::Sqlite => SQLITE_UP,
+        };
+
+        // One statement per call: MySQL rejects multi-statement strings.
+        for sql in statements {
+            conn.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        for sql in DOWN {
+            conn.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+}
+
+const DOWN: &[&str] = &[
+    "DROP TABLE IF EXISTS credstore_secret_version",
+    "DROP TABLE IF EXISTS credstore_secret",
+    "DROP TABLE IF EXISTS credstore_data_key",
+];
+
+const POSTGRES_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS credstore_data_key (
+    tenant_id    UUID PRIMARY KEY NOT NULL,
+    kek_id       VARCHAR(255) NOT NULL,
+    wrapped_key  BYTEA NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL,
+    updated_at   TIMESTAMPTZ NOT NULL
+)
+",
+    r"
+CREATE TABLE IF NOT EXISTS credstore_secret (
+    id               UUID PRIMARY KEY NOT NULL,
+    tenant_id        UUID NOT NULL,
+    reference        VARCHAR(255) NOT NULL,
+    scope_owner_id   UUID NOT NULL,
+    owner_id         UUID NOT NULL,
+    sharing          VARCHAR(16) NOT NULL,
+    current_version  BIGINT NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL,
+    updated_at       TIMESTAMPTZ NOT NULL
+)
+",
+    r"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_credstore_secret_scope
+    ON credstore_secret (tenant_id, reference, scope_owner_id)
+",
+    r"
+CREATE TABLE IF NOT EXISTS credstore_secret_version (
+    id          UUID PRIMARY KEY NOT NULL,
+    tenant_id   UUID NOT NULL,
+    secret_id   UUID NOT NULL REFERENCES credstore_secret(id) ON DELETE CASCADE,
+    version     BIGINT NOT NULL,
+    ciphertext  BYTEA NOT NULL,
+    expires_at  TIMESTAMPTZ,
+    created_at  TIMESTAMPTZ NOT NULL
+)
+",
+    r"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_credstore_secret_version
+    ON credstore_secret_version (secret_id, version)
+",
+];
+
+const MYSQL_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS credstore_data_key (
+    tenant_id    VARCHAR(36) PRIMARY KEY NOT NULL,
+    kek_id       VARCHAR(255) NOT NULL,
+    wrapped_key  VARBINARY(512) NOT NULL,
+    created_at   TIMESTAMP(6) NOT NULL,
+    updated_at   TIMESTAMP(6) NOT NULL
+)
+",
+    r"
+CREATE TABLE IF NOT EXISTS credstore_secret (
+    id               VARCHAR(36) PRIMARY KEY NOT NULL,
+    tenant_id        VARCHAR(36) NOT NULL,
+    reference        VARCHAR(255) NOT NULL,
+    scope_owner_id   VARCHAR(36) NOT NULL,
+    owner_id         VARCHAR(36) NOT NULL,
+    sharing          VARCHAR(16) NOT NULL,
+    current_version  BIGINT NOT NULL,
+    created_at       TIMESTAMP(6) NOT NULL,
+    updated_at       TIMESTAMP(6) NOT NULL,
+    UNIQUE KEY uq_credstore_secret_scope (tenant_id, reference, scope_owner_id)
+)
+",
+    r"
+CREATE TABLE IF NOT EXISTS credstore_secret_version (
+    id          VARCHAR(36) PRIMARY KEY NOT NULL,
+    tenant_id   VARCHAR(36) NOT NULL,
+    secret_id   VARCHAR(36) NOT NULL,
+    version     BIGINT NOT NULL,
+    ciphertext  LONGBLOB NOT NULL,
+    expires_at  TIMESTAMP(6) NULL,
+    created_at  TIMESTAMP(6) NOT NULL,
+    UNIQUE KEY uq_credstore_secret_version (secret_id, version),
+    CONSTRAINT fk_credstore_secret_version_secret FOREIGN KEY (secret_id)
+        REFERENCES credstore_secret(id) ON DELETE CASCADE
+)
+",
+];
+
+const SQLITE_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS credstore_data_key (
+    tenant_id    TEXT PRIMARY KEY NOT NULL,
+    kek_id       TEXT NOT NULL,
+    wrapped_key  BLOB NOT NULL,
+    created_at   TEXT NOT NULL,
+    updated_at   TEXT NOT NULL
+)
+",
+    r"
+CREATE TABLE IF NOT EXISTS credstore_secret (
+    id               TEXT PRIMARY KEY NOT NULL,
+    tenant_id        TEXT NOT NULL,
+    reference        TEXT NOT NULL,
+    scope_owner_id   TEXT NOT NULL,
+    owner_id         TEXT NOT NULL,
+    sharing          TEXT NOT NULL,
+    current_version  INTEGER NOT NULL,
+    created_at       TEXT NOT NULL,
+    updated_at       TEXT NOT NULL
+)
+",
+    r"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_credstore_secret_scope
+    ON credstore_secret (tenant_id, reference, scope_owner_id)
+",
+    r"
+CREATE TABLE IF NOT EXISTS credstore_secret_version (
+    id          TEXT PRIMARY KEY NOT NULL,
+    tenant_id   TEXT NOT NULL,
+    secret_id   TEXT NOT NULL REFERENCES credstore_secret(id) ON DELETE CASCADE,
+    version     INTEGER NOT NULL,
+    ciphertext  BLOB NOT NULL,
+    expires_at  TEXT,
+    created_at  TEXT NOT NULL
+)
+",
+    r"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_credstore_secret_version
+    ON credstore_secret_version (secret_id, version)
+",
+];
@@ -0,0 +1,52 @@
+//! SQL storage on top of the `modkit-db` secure ORM.
+//!
+//! Only ciphertext reaches this layer: values are sealed by the domain
+//! service before they are handed over, and every query is scoped to a
+//! single tenant through [`AccessScope`](modkit_security::AccessScope).
+
+pub mod entity;
+pub mod migrations;
+mod secret_repo;
+
+pub use secret_repo::{Seal, SecretAddress, SecretRepo, StoredSecret};
+
+use modkit_db::DbError;
+use modkit_db::secure::ScopeError;
+
+/// `true` if `e` is a unique-index violation, i.e. a concurrent writer
+/// created the same row first.
+#[must_use]
+pub fn is_unique_violation(e: &DbError) -> bool {
+    let db_err = match e {
+        DbError::Sea(db) => Some(db),
+        DbError::Other(other) => match other.downcast_ref::<ScopeError>() {
+            Some(ScopeError::Db(db)) => Some(db),
+            _ => None,
+        },
+        _ => None,
+    };
+    matches!(
+        db_err.and_then(sea_orm::DbErr::sql_err),
+        Some(sea_orm::SqlErr::UniqueConstraintViolation(_))
+    )
+}
+
+#[cfg(test)]
+pub(crate) async fn test_provider() -> modkit_db::DBProvider<DbError> {
+    use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::{ConnectOpts, connect_db};
+    use sea_orm_migration::MigratorTrait;
+
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db("sqlite::memory:", opts)
+        .await
+        .expect("connect in-memory database");
+    run_migrations_for_testing(&db, migrations::Migrator::migrations())
+        .await
+        .expect("run migrations");
+    modkit_db::DBProvider::new(db)
+}
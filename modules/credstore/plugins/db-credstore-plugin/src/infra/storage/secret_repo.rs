@@ -0,0 +1,474 @@
+use std::collections::HashMap;
+
+use modkit_db::secure::{
+    DBRunner, SecureDeleteExt, SecureEntityExt, SecureUpdateExt, secure_insert,
+};
+use modkit_db::{DBProvider, DbError};
+use modkit_security::AccessScope;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, Order, Set};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::entity::{data_key, secret, secret_version};
+use crate::domain::kek::WrappedKey;
+
+/// Unique address of a secret: `scope_owner_id` is the owner for private
+/// secrets and the nil UUID for tenant-wide ones.
+#[derive(Debug, Clone)]
+pub struct SecretAddress {
+    pub tenant_id: Uuid,
+    pub reference: String,
+    pub scope_owner_id: Uuid,
+}
+
+impl SecretAddress {
+    fn scope(&self) -> AccessScope {
+        AccessScope::for_tenant(self.tenant_id)
+    }
+
+    fn condition(&self) -> Condition {
+        Condition::all()
+            .add(secret::Column::Reference.eq(self.reference.as_str()))
+            .add(secret::Column::ScopeOwnerId.eq(self.scope_owner_id))
+    }
+}
+
+/// A secret row with one of its version rows.
+#[derive(Debug, Clone)]
+pub struct StoredSecret {
+    pub secret: secret::Model,
+    pub version: secret_version::Model,
+}
+
+/// Seals a value for the given secret id and version.
+pub type Seal = Box<dyn FnOnce(Uuid, i64) -> anyhow::Result<Vec<u8>> + Send>;
+
+/// Secret and data-key storage in the module database.
+pub struct SecretRepo {
+    db: DBProvider<DbError>,
+}
+
+impl SecretRepo {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>) -> Self {
+        Self { db }
+    }
+
+    /// Wrapped data key of `tenant_id`, if one was created.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn data_key(&self, tenant_id: Uuid) -> Result<Option<WrappedKey>, DbError> {
+        let conn = self.db.conn()?;
+        let row = data_key::Entity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .one(&conn)
+            .await?;
+        Ok(row.map(|m| WrappedKey {
+            kek_id: m.kek_id,
+            ciphertext: m.wrapped_key,
+        }))
+    }
+
+    /// Store the first data key of `tenant_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the insert fails; a unique violation means a
+    /// concurrent writer stored a key first.
+    pub async fn insert_data_key(
+        &self,
+        tenant_id: Uuid,
+        wrapped: WrappedKey,
+    ) -> Result<(), DbError> {
+        let conn = self.db.conn()?;
+        let now = OffsetDateTime::now_utc();
+        let am = data_key::ActiveModel {
+            tenant_id: Set(tenant_id),
+            kek_id: Set(wrapped.kek_id),
+            wrapped_key: Set(wrapped.ciphertext),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+        secure_insert::<data_key::Entity>(am, &AccessScope::for_tenant(tenant_id), &conn).await?;
+        Ok(())
+    }
+
+    /// Data keys of all tenants not wrapped by `kek_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn data_keys_not_wrapped_by(
+        &self,
+        kek_id: &str,
+    ) -> Result<Vec<(Uuid, WrappedKey)>, DbError> {
+        let conn = self.db.conn()?;
+        // Key rotation is a maintenance task spanning every tenant.
+        let rows = data_key::Entity::find()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .filter(Condition::all().add(data_key::Column::KekId.ne(kek_id)))
+            .order_by(data_key::Column::TenantId, Order::Asc)
+            .all(&conn)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|m| {
+                (
+                    m.tenant_id,
+                    WrappedKey {
+                        kek_id: m.kek_id,
+                        ciphertext: m.wrapped_key,
+                    },
+                )
+            })
+            .collect())
+    }
+
+    /// Replace the wrapped data key of `tenant_id`, provided it is still
+    /// wrapped by `previous_kek_id`. Returns `false` if another node
+    /// re-wrapped it first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the update fails.
+    pub async fn rewrap_data_key(
+        &self,
+        tenant_id: Uuid,
+        previous_kek_id: &str,
+        wrapped: WrappedKey,
+    ) -> Result<bool, DbError> {
+        let conn = self.db.conn()?;
+        let result = data_key::Entity::update_many()
+            .secure()
+            .col_expr(data_key::Column::KekId, Expr::value(wrapped.kek_id))
+            .col_expr(
+                data_key::Column::WrappedKey,
+                Expr::value(wrapped.ciphertext),
+            )
+            .col_expr(
+                data_key::Column::UpdatedAt,
+                Expr::value(OffsetDateTime::now_utc()),
+            )
+            .filter(Condition::all().add(data_key::Column::KekId.eq(previous_kek_id)))
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .exec(&conn)
+            .await?;
+        Ok(result.rows_affected == 1)
+    }
+
+    /// Load a secret with its current version, or with `version` if given.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if a query fails.
+    pub async fn find(
+        &self,
+        addr: &SecretAddress,
+        version: Option<i64>,
+    ) -> Result<Option<StoredSecret>, DbError> {
+        let conn = self.db.conn()?;
+        let scope = addr.scope();
+        let Some(secret) = find_secret(&conn, &scope, addr).await? else {
+            return Ok(None);
+        };
+        let version = secret_version::Entity::find()
+            .secure()
+            .scope_with(&scope)
+            .filter(
+                Condition::all()
+                    .add(secret_version::Column::SecretId.eq(secret.id))
+                    .add(
+                        secret_version::Column::Version
+                            .eq(version.unwrap_or(secret.current_version)),
+                    ),
+            )
+            .one(&conn)
+            .await?;
+        Ok(version.map(|version| StoredSecret { secret, version }))
+    }
+
+    /// Create a secret, or replace an existing one and drop all of its
+    /// versions. Returns the new version.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if a query or `seal` fails; a unique violation means
+    /// a concurrent writer created the secret first.
+    pub async fn put(
+        &self,
+        addr: SecretAddress,
+        owner_id: Uuid,
+        sharing: &'static str,
+        seal: Seal,
+    ) -> Result<i64, DbError> {
+        self.db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let scope = addr.scope();
+                    let now = OffsetDateTime::now_utc();
+                    let (secret_id, version) = if let Some(existing) =
+                        find_secret(tx, &scope, &addr).await?
+                    {
+                        let version =
+                            replace_secret(tx, &scope, &existing, owner_id, sharing, now).await?;
+                        (existing.id, version)
+                    } else {
+                        let id = create_secret(tx, &scope, &addr, owner_id, sharing, now).await?;
+                        (id, 1)
+                    };
+                    insert_version(tx, &scope, addr.tenant_id, secret_id, version, seal, now)
+                        .await?;
+                    Ok(version)
+                })
+            })
+            .await
+    }
+
+    /// Store a new version of an existing secret. The replaced version stays
+    /// readable until `previous_expires_at`; versions already past their
+    /// expiry are dropped. Returns `None` if the secret does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if a query or `seal` fails.
+    pub async fn rotate(
+        &self,
+        addr: SecretAddress,
+        previous_expires_at: OffsetDateTime,
+        seal: Seal,
+    ) -> Result<Option<i64>, DbError> {
+        self.db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let scope = addr.scope();
+                    let now = OffsetDateTime::now_utc();
+                    let Some(existing) = find_secret(tx, &scope, &addr).await? else {
+                        return Ok(None);
+                    };
+
+                    secret_version::Entity::delete_many()
+                        .secure()
+                        .scope_with(&scope)
+                        .filter(
+                            Condition::all()
+                                .add(secret_version::Column::SecretId.eq(existing.id))
+                                .add(secret_version::Column::ExpiresAt.lte(now)),
+                        )
+                        .exec(tx)
+                        .await?;
+                    secret_version::Entity::update_many()
+                        .secure()
+                        .col_expr(
+                            secret_version::Column::ExpiresAt,
+                            Expr::value(previous_expires_at),
+                        )
+                        .filter(
+                            Condition::all()
+                                .add(secret_version::Column::SecretId.eq(existing.id))
+                                .add(secret_version::Column::Version.eq(existing.current_version)),
+                        )
+                        .scope_with(&scope)
+                        .exec(tx)
+                        .await?;
+
+                    let version = existing.current_version + 1;
+                    secret::Entity::update_many()
+                        .secure()
+                        .col_expr(secret::Column::CurrentVersion, Expr::value(version))
+                        .col_expr(secret::Column::UpdatedAt, Expr::value(now))
+                        .filter(Condition::all().add(secret::Column::Id.eq(existing.id)))
+                        .scope_with(&scope)
+                        .exec(tx)
+                        .await?;
+                    insert_version(tx, &scope, addr.tenant_id, existing.id, version, seal, now)
+                        .await?;
+                    Ok(Some(version))
+                })
+            })
+            .await
+    }
+
+    /// Delete a secret with all of its versions. Returns `false` if the
+    /// secret does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if a query fails.
+    pub async fn delete(&self, addr: SecretAddress) -> Result<bool, DbError> {
+        self.db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let scope = addr.scope();
+                    let Some(existing) = find_secret(tx, &scope, &addr).await? else {
+                        return Ok(false);
+                    };
+                    // Explicit rather than relying on ON DELETE CASCADE, which
+                    // SQLite only honours with foreign keys enabled.
+                    secret_version::Entity::delete_many()
+                        .secure()
+                        .scope_with(&scope)
+                        .filter(
+                            Condition::all().add(secret_version::Column::SecretId.eq(existing.id)),
+                        )
+                        .exec(tx)
+                        .await?;
+                    let result = secret::Entity::delete_many()
+                        .secure()
+                        .scope_with(&scope)
+                        .filter(Condition::all().add(secret::Column::Id.eq(existing.id)))
+                        .exec(tx)
+                        .await?;
+                    Ok(result.rows_affected == 1)
+                })
+            })
+            .await
+    }
+
+    /// Tenant-wide secrets of `tenant_id` and the private secrets of
+    /// `owner_id`, each with its retired versions still readable at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if a query fails.
+    pub async fn list(
+        &self,
+        tenant_id: Uuid,
+        owner_id: Uuid,
+        now: OffsetDateTime,
+    ) -> Result<Vec<(secret::Model, Vec<secret_version::Model>)>, DbError> {
+        let conn = self.db.conn()?;
+        let scope = AccessScope::for_tenant(tenant_id);
+        let secrets = secret::Entity::find()
+            .secure()
+            .scope_with(&scope)
+            .filter(
+                Condition::any()
+                    .add(secret::Column::ScopeOwnerId.eq(Uuid::nil()))
+                    .add(secret::Column::ScopeOwnerId.eq(owner_id)),
+            )
+            .order_by(secret::Column::Reference, Order::Asc)
+            .all(&conn)
+            .await?;
+        if secrets.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let retired = secret_version::Entity::find()
+            .secure()
+            .scope_with(&scope)
+            .filter(
+                Condition::all()
+                    .add(secret_version::Column::SecretId.is_in(secrets.iter().map(|s| s.id)))
+                    .add(secret_version::Column::ExpiresAt.gt(now)),
+            )
+            .order_by(secret_version::Column::Version, Order::Asc)
+            .all(&conn)
+            .await?;
+        let mut by_secret: HashMap<Uuid, Vec<secret_version::Model>> = HashMap::new();
+        for version in retired {
+            by_secret
+                .entry(version.secret_id)
+                .or_default()
+                .push(version);
+        }
+
+        Ok(secrets
+            .into_iter()
+            .map(|s| {
+                let versions = by_secret.remove(&s.id).unwrap_or_default();
+                (s, versions)
+            })
+            .collect())
+    }
+}
+
+async fn find_secret(
+    runner: &impl DBRunner,
+    scope: &AccessScope,
+    addr: &SecretAddress,
+) -> Result<Option<secret::Model>, DbError> {
+    Ok(secret::Entity::find()
+        .secure()
+        .scope_with(scope)
+        .filter(addr.condition())
+        .one(runner)
+        .await?)
+}
+
+/// Drop all versions of `existing` and point it at the next version.
+async fn replace_secret(
+    runner: &impl DBRunner,
+    scope: &AccessScope,
+    existing: &secret::Model,
+    owner_id: Uuid,
+    sharing: &'static str,
+    now: OffsetDateTime,
+) -> Result<i64, DbError> {
+    secret_version::Entity::delete_many()
+        .secure()
+        .scope_with(scope)
+        .filter(Condition::all().add(secret_version::Column::SecretId.eq(existing.id)))
+        .exec(runner)
+        .await?;
+    let version = existing.current_version + 1;
+    secret::Entity::update_many()
+        .secure()
+        .col_expr(secret::Column::OwnerId, Expr::value(owner_id))
+        .col_expr(secret::Column::Sharing, Expr::value(sharing))
+        .col_expr(secret::Column::CurrentVersion, Expr::value(version))
+        .col_expr(secret::Column::UpdatedAt, Expr::value(now))
+        .filter(Condition::all().add(secret::Column::Id.eq(existing.id)))
+        .scope_with(scope)
+        .exec(runner)
+        .await?;
+    Ok(version)
+}
+
+async fn create_secret(
+    runner: &impl DBRunner,
+    scope: &AccessScope,
+    addr: &SecretAddress,
+    owner_id: Uuid,
+    sharing: &'static str,
+    now: OffsetDateTime,
+) -> Result<Uuid, DbError> {
+    let am = secret::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        tenant_id: Set(addr.tenant_id),
+        reference: Set(addr.reference.clone()),
+        scope_owner_id: Set(addr.scope_owner_id),
+        owner_id: Set(owner_id),
+        sharing: Set(sharing.to_owned()),
+        current_version: Set(1),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+    Ok(secure_insert::<secret::Entity>(am, scope, runner).await?.id)
+}
+
+async fn insert_version(
+    runner: &impl DBRunner,
+    scope: &AccessScope,
+    tenant_id: Uuid,
+    secret_id: Uuid,
+    version: i64,
+    seal: Seal,
+    now: OffsetDateTime,
+) -> Result<(), DbError> {
+    let am = secret_version::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        tenant_id: Set(tenant_id),
+        secret_id: Set(secret_id),
+        version: Set(version),
+        ciphertext: Set(seal(secret_id, version)?),
+        expires_at: Set(None),
+        created_at: Set(now),
+    };
+    secure_insert::<secret_version::Entity>(am, scope, runner).await?;
+    Ok(())
+}
@@ -0,0 +1,9 @@
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod config;
+pub mod domain;
+pub mod infra;
+pub mod module;
+
+pub use domain::kek::{KekError, KeyEncryptionKeyProvider, LocalKekProvider, WrappedKey};
+pub use module::DbCredStorePlugin;
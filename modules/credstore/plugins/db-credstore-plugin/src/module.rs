@@ -0,0 +1,94 @@
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use credstore_sdk::{CredStorePluginClientV1, CredStorePluginSpecV1};
+use modkit::Module;
+use modkit::client_hub::ClientScope;
+use modkit::context::ModuleCtx;
+use modkit::gts::BaseModkitPluginV1;
+use tracing::info;
+use types_registry_sdk::{RegisterResult, TypesRegistryClient};
+
+use crate::config::DbCredStorePluginConfig;
+use crate::domain::Service;
+use crate::domain::kek::{KeyEncryptionKeyProvider, LocalKekProvider};
+
+/// Database-backed credstore plugin module.
+///
+/// Stores secrets in the module database, envelope-encrypted with per-tenant
+/// data keys that are wrapped by a locally configured master key.
+#[modkit::module(
+    name = "db-credstore-plugin",
+    deps = ["types-registry"],
+    capabilities = [db]
+)]
+pub struct DbCredStorePlugin {
+    service: OnceLock<Arc<Service>>,
+}
+
+impl Default for DbCredStorePlugin {
+    fn default() -> Self {
+        Self {
+            service: OnceLock::new(),
+        }
+    }
+}
+
+impl modkit::contracts::DatabaseCapability for DbCredStorePlugin {
+    fn migrations(&self) -> Vec<Box<dyn sea_orm_migration::MigrationTrait>> {
+        use sea_orm_migration::MigratorTrait;
+        crate::infra::storage::migrations::Migrator::migrations()
+    }
+}
+
+#[async_trait]
+impl Module for DbCredStorePlugin {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        // Load configuration
+        let cfg: DbCredStorePluginConfig = ctx.config()?;
+
+        info!(
+            vendor = %cfg.vendor,
+            priority = cfg.priority,
+            master_key_id = %cfg.master_key.id,
+            previous_master_keys = cfg.previous_master_keys.len(),
+            "Loaded plugin configuration"
+        );
+
+        // Generate plugin instance ID
+        let instance_id = CredStorePluginSpecV1::gts_make_instance_id("x.core._.db_credstore.v1");
+
+        // Load master keys and open storage (validate early, before registration)
+        let kek: Arc<dyn KeyEncryptionKeyProvider> = Arc::new(LocalKekProvider::from_config(&cfg)?);
+        let service = Arc::new(Service::new(ctx.db_required()?, kek));
+        if cfg.rewrap_on_start {
+            service.rewrap_data_keys().await?;
+        }
+
+        // Register plugin instance in types-registry
+        let registry = ctx.client_hub().get::<dyn TypesRegistryClient>()?;
+        let instance = BaseModkitPluginV1::<CredStorePluginSpecV1> {
+            id: instance_id.clone(),
+            vendor: cfg.vendor.clone(),
+            priority: cfg.priority,
+            properties: CredStorePluginSpecV1,
+        };
+        let instance_json = serde_json::to_value(&instance)?;
+
+        let results = registry.register(vec![instance_json]).await?;
+        RegisterResult::ensure_all_ok(&results)?;
+
+        // All fallible steps done — commit service to shared state
+        self.service
+            .set(service.clone())
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
+        // Register scoped client in ClientHub
+        let api: Arc<dyn CredStorePluginClientV1> = service;
+        ctx.client_hub()
+            .register_scoped::<dyn CredStorePluginClientV1>(ClientScope::gts_id(&instance_id), api);
+
+        info!(instance_id = %instance_id);
+        Ok(())
+    }
+}
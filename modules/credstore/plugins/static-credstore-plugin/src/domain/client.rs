@@ -1,8 +1,10 @@
 use async_trait::async_trait;
 use credstore_sdk::{
-    CredStoreError, CredStorePluginClientV1, SecretMetadata, SecretRef, SecretValue,
+    CredStoreError, CredStorePluginClientV1, OwnerId, SecretInfo, SecretMetadata, SecretRef,
+    SecretValue, SecretVersion, SharingMode, TenantId,
 };
 use modkit_security::SecurityContext;
+use time::OffsetDateTime;
 
 use super::service::Service;
 
@@ -10,21 +12,77 @@ use super::service::Service;
 impl CredStorePluginClientV1 for Service {
     async fn get(
         &self,
-        ctx: &SecurityContext,
+        _ctx: &SecurityContext,
+        tenant_id: &TenantId,
         key: &SecretRef,
+        owner_id: Option<&OwnerId>,
     ) -> Result<Option<SecretMetadata>, CredStoreError> {
-        let tenant_id = ctx.subject_tenant_id();
+        Ok(self.get(*tenant_id, key, owner_id.copied()))
+    }
 
-        let Some(entry) = self.get(tenant_id, key) else {
-            return Ok(None);
-        };
+    async fn get_version(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        key: &SecretRef,
+        owner_id: Option<&OwnerId>,
+        version: SecretVersion,
+    ) -> Result<Option<SecretMetadata>, CredStoreError> {
+        Ok(self.get_version(*tenant_id, key, owner_id.copied(), version))
+    }
+
+    async fn put(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        key: &SecretRef,
+        value: SecretValue,
+        sharing: SharingMode,
+        owner_id: &OwnerId,
+    ) -> Result<SecretVersion, CredStoreError> {
+        Ok(self.put(*tenant_id, key, value, sharing, *owner_id))
+    }
+
+    async fn rotate(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        key: &SecretRef,
+        owner_id: Option<&OwnerId>,
+        value: SecretValue,
+        previous_expires_at: OffsetDateTime,
+    ) -> Result<SecretVersion, CredStoreError> {
+        self.rotate(
+            *tenant_id,
+            key,
+            owner_id.copied(),
+            value,
+            previous_expires_at,
+        )
+        .ok_or(CredStoreError::NotFound)
+    }
 
-        Ok(Some(SecretMetadata {
-            value: SecretValue::new(entry.value.as_bytes().to_vec()),
-            owner_id: entry.owner_id,
-            sharing: entry.sharing,
-            owner_tenant_id: entry.owner_tenant_id,
-        }))
+    async fn delete(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        key: &SecretRef,
+        owner_id: Option<&OwnerId>,
+    ) -> Result<(), CredStoreError> {
+        if self.delete(*tenant_id, key, owner_id.copied()) {
+            Ok(())
+        } else {
+            Err(CredStoreError::NotFound)
+        }
+    }
+
+    async fn list(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: &TenantId,
+        owner_id: &OwnerId,
+    ) -> Result<Vec<SecretInfo>, CredStoreError> {
+        Ok(self.list(*tenant_id, *owner_id))
     }
 }
 
@@ -76,7 +134,9 @@ mod tests {
         let plugin: &dyn CredStorePluginClientV1 = &service;
         let key = SecretRef::new("openai_api_key").unwrap();
 
-        let result = plugin.get(&ctx_for_tenant(tenant_a()), &key).await;
+        let result = plugin
+            .get(&ctx_for_tenant(tenant_a()), &tenant_a(), &key, None)
+            .await;
         assert!(result.is_ok());
 
         let metadata = result.unwrap();
@@ -93,7 +153,9 @@ mod tests {
         let plugin: &dyn CredStorePluginClientV1 = &service;
         let key = SecretRef::new("openai_api_key").unwrap();
 
-        let result = plugin.get(&ctx_for_tenant(tenant_b()), &key).await;
+        let result = plugin
+            .get(&ctx_for_tenant(tenant_b()), &tenant_b(), &key, None)
+            .await;
         assert!(result.is_ok());
         assert!(result.unwrap().is_none());
     }
@@ -104,7 +166,9 @@ mod tests {
         let plugin: &dyn CredStorePluginClientV1 = &service;
         let key = SecretRef::new("missing").unwrap();
 
-        let result = plugin.get(&ctx_for_tenant(tenant_a()), &key).await;
+        let result = plugin
+            .get(&ctx_for_tenant(tenant_a()), &tenant_a(), &key, None)
+            .await;
         assert!(result.is_ok());
         assert!(result.unwrap().is_none());
     }
@@ -115,8 +179,64 @@ mod tests {
         let plugin: &dyn CredStorePluginClientV1 = &service;
         let key = SecretRef::new("openai_api_key").unwrap();
 
-        let result = plugin.get(&ctx_for_tenant(tenant_a()), &key).await;
+        let result = plugin
+            .get(&ctx_for_tenant(tenant_a()), &tenant_a(), &key, None)
+            .await;
         assert!(result.is_ok());
         assert!(result.unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn rotate_and_delete_report_not_found_for_missing_secret() {
+        let service = service_with_single_secret();
+        let plugin: &dyn CredStorePluginClientV1 = &service;
+        let ctx = ctx_for_tenant(tenant_b());
+        let key = SecretRef::new("openai_api_key").unwrap();
+
+        let err = plugin
+            .rotate(
+                &ctx,
+                &tenant_b(),
+                &key,
+                None,
+                "v".into(),
+                OffsetDateTime::now_utc(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CredStoreError::NotFound));
+
+        let err = plugin
+            .delete(&ctx, &tenant_b(), &key, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CredStoreError::NotFound));
+    }
+
+    #[tokio::test]
+    async fn put_then_list_round_trip() {
+        let service = Service::from_config(&StaticCredStorePluginConfig::default()).unwrap();
+        let plugin: &dyn CredStorePluginClientV1 = &service;
+        let ctx = ctx_for_tenant(tenant_a());
+        let key = SecretRef::new("vendor-key").unwrap();
+
+        let version = plugin
+            .put(
+                &ctx,
+                &tenant_a(),
+                &key,
+                "v1".into(),
+                SharingMode::Shared,
+                &owner(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(version, 1);
+
+        let items = plugin.list(&ctx, &tenant_a(), &owner()).await.unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].key, key);
+        assert_eq!(items[0].sharing, SharingMode::Shared);
+        assert_eq!(items[0].version, 1);
+    }
 }
@@ -1,26 +1,153 @@
 use std::collections::HashMap;
 
-use credstore_sdk::{OwnerId, SecretRef, SecretValue, SharingMode, TenantId};
+use credstore_sdk::{
+    OwnerId, RetiredVersion, SecretInfo, SecretMetadata, SecretRef, SecretValue, SecretVersion,
+    SharingMode, TenantId,
+};
 use modkit_macros::domain_model;
+use parking_lot::RwLock;
+use time::OffsetDateTime;
 
 use crate::config::StaticCredStorePluginConfig;
 
-/// Pre-built secret entry for O(1) lookup.
+/// Per-tenant storage key: `Some(owner)` for private secrets, `None` for
+/// tenant-wide (`tenant`/`shared`) secrets.
+type StorageKey = (SecretRef, Option<OwnerId>);
+
+/// A single stored value of a secret.
+#[domain_model]
+struct StoredVersion {
+    version: SecretVersion,
+    value: SecretValue,
+    /// `None` for the current version; set once the version is retired by
+    /// a rotation.
+    expires_at: Option<OffsetDateTime>,
+}
+
+impl StoredVersion {
+    fn is_readable(&self, now: OffsetDateTime) -> bool {
+        self.expires_at.is_none_or(|at| at > now)
+    }
+}
+
+/// A secret with its current and still-readable retired versions.
 #[domain_model]
 pub struct SecretEntry {
-    pub value: SecretValue,
     pub owner_id: OwnerId,
     pub sharing: SharingMode,
     pub owner_tenant_id: TenantId,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+    /// Ordered by version; the last element is the current version.
+    versions: Vec<StoredVersion>,
+}
+
+impl SecretEntry {
+    fn new(
+        owner_id: OwnerId,
+        sharing: SharingMode,
+        owner_tenant_id: TenantId,
+        value: SecretValue,
+        now: OffsetDateTime,
+    ) -> Self {
+        Self {
+            owner_id,
+            sharing,
+            owner_tenant_id,
+            created_at: now,
+            updated_at: now,
+            versions: vec![StoredVersion {
+                version: 1,
+                value,
+                expires_at: None,
+            }],
+        }
+    }
+
+    fn current_version(&self) -> SecretVersion {
+        self.versions.last().map_or(0, |v| v.version)
+    }
+
+    /// Appends `value` as the next version. The previous current version is
+    /// kept until `previous_expires_at`, or dropped when that is `None`.
+    fn push_version(
+        &mut self,
+        value: SecretValue,
+        previous_expires_at: Option<OffsetDateTime>,
+        now: OffsetDateTime,
+    ) -> SecretVersion {
+        let version = self.current_version() + 1;
+        match previous_expires_at {
+            Some(expires_at) => {
+                if let Some(current) = self.versions.last_mut() {
+                    current.expires_at = Some(expires_at);
+                }
+                self.versions.retain(|v| v.is_readable(now));
+            }
+            None => self.versions.clear(),
+        }
+        self.versions.push(StoredVersion {
+            version,
+            value,
+            expires_at: None,
+        });
+        self.updated_at = now;
+        version
+    }
+
+    fn metadata(
+        &self,
+        version: Option<SecretVersion>,
+        now: OffsetDateTime,
+    ) -> Option<SecretMetadata> {
+        let stored = match version {
+            Some(version) => self.versions.iter().find(|v| v.version == version)?,
+            None => self.versions.last()?,
+        };
+        if !stored.is_readable(now) {
+            return None;
+        }
+        Some(SecretMetadata {
+            value: SecretValue::new(stored.value.as_bytes().to_vec()),
+            owner_id: self.owner_id,
+            sharing: self.sharing,
+            owner_tenant_id: self.owner_tenant_id,
+            version: stored.version,
+        })
+    }
+
+    fn info(&self, key: &SecretRef, now: OffsetDateTime) -> SecretInfo {
+        SecretInfo {
+            key: key.clone(),
+            owner_id: self.owner_id,
+            owner_tenant_id: self.owner_tenant_id,
+            sharing: self.sharing,
+            version: self.current_version(),
+            retired_versions: self
+                .versions
+                .iter()
+                .filter(|v| v.is_readable(now))
+                .filter_map(|v| {
+                    v.expires_at.map(|expires_at| RetiredVersion {
+                        version: v.version,
+                        expires_at,
+                    })
+                })
+                .collect(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
 }
 
 /// Static credstore service.
 ///
-/// Stores secrets in a two-level `HashMap<TenantId, HashMap<SecretRef, SecretEntry>>`
-/// built at init from YAML configuration.
+/// Stores secrets in a two-level `HashMap<TenantId, HashMap<StorageKey, SecretEntry>>`
+/// seeded at init from YAML configuration. Writes are kept in memory only
+/// and are lost on restart.
 #[domain_model]
 pub struct Service {
-    secrets: HashMap<TenantId, HashMap<SecretRef, SecretEntry>>,
+    secrets: RwLock<HashMap<TenantId, HashMap<StorageKey, SecretEntry>>>,
 }
 
 impl Service {
@@ -32,42 +159,160 @@ impl Service {
     ///
     /// Returns an error if any configured key fails `SecretRef` validation.
     pub fn from_config(cfg: &StaticCredStorePluginConfig) -> anyhow::Result<Self> {
-        let mut secrets: HashMap<TenantId, HashMap<SecretRef, SecretEntry>> = HashMap::new();
+        let now = OffsetDateTime::now_utc();
+        let mut secrets: HashMap<TenantId, HashMap<StorageKey, SecretEntry>> = HashMap::new();
 
         for entry in &cfg.secrets {
             let key = SecretRef::new(&entry.key)?;
-            let secret_entry = SecretEntry {
-                value: SecretValue::from(entry.value.as_str()),
-                owner_id: entry.owner_id,
-                sharing: entry.sharing,
-                owner_tenant_id: entry.tenant_id,
-            };
+            let storage_key = (key, scope_owner(entry.sharing, entry.owner_id));
+            let secret_entry = SecretEntry::new(
+                entry.owner_id,
+                entry.sharing,
+                entry.tenant_id,
+                SecretValue::from(entry.value.as_str()),
+                now,
+            );
             let tenant_map = secrets.entry(entry.tenant_id).or_default();
-            if tenant_map.contains_key(&key) {
+            if tenant_map.contains_key(&storage_key) {
                 anyhow::bail!(
                     "duplicate secret key '{}' for tenant {}",
                     entry.key,
                     entry.tenant_id
                 );
             }
-            tenant_map.insert(key, secret_entry);
+            tenant_map.insert(storage_key, secret_entry);
+        }
+
+        Ok(Self {
+            secrets: RwLock::new(secrets),
+        })
+    }
+
+    /// Look up the current version of a secret.
+    #[must_use]
+    pub fn get(
+        &self,
+        tenant_id: TenantId,
+        key: &SecretRef,
+        owner_id: Option<OwnerId>,
+    ) -> Option<SecretMetadata> {
+        self.lookup(tenant_id, key, owner_id, None)
+    }
+
+    /// Look up a specific, still-readable version of a secret.
+    #[must_use]
+    pub fn get_version(
+        &self,
+        tenant_id: TenantId,
+        key: &SecretRef,
+        owner_id: Option<OwnerId>,
+        version: SecretVersion,
+    ) -> Option<SecretMetadata> {
+        self.lookup(tenant_id, key, owner_id, Some(version))
+    }
+
+    fn lookup(
+        &self,
+        tenant_id: TenantId,
+        key: &SecretRef,
+        owner_id: Option<OwnerId>,
+        version: Option<SecretVersion>,
+    ) -> Option<SecretMetadata> {
+        let secrets = self.secrets.read();
+        secrets
+            .get(&tenant_id)?
+            .get(&(key.clone(), owner_id))?
+            .metadata(version, OffsetDateTime::now_utc())
+    }
+
+    /// Create or replace a secret. Returns the new version.
+    pub fn put(
+        &self,
+        tenant_id: TenantId,
+        key: &SecretRef,
+        value: SecretValue,
+        sharing: SharingMode,
+        owner_id: OwnerId,
+    ) -> SecretVersion {
+        let now = OffsetDateTime::now_utc();
+        let storage_key = (key.clone(), scope_owner(sharing, owner_id));
+        let mut secrets = self.secrets.write();
+        let tenant_map = secrets.entry(tenant_id).or_default();
+        if let Some(entry) = tenant_map.get_mut(&storage_key) {
+            entry.sharing = sharing;
+            entry.push_version(value, None, now)
+        } else {
+            tenant_map.insert(
+                storage_key,
+                SecretEntry::new(owner_id, sharing, tenant_id, value, now),
+            );
+            1
         }
+    }
 
-        Ok(Self { secrets })
+    /// Store a new version of an existing secret, keeping the replaced one
+    /// readable until `previous_expires_at`. Returns `None` if the secret
+    /// does not exist.
+    pub fn rotate(
+        &self,
+        tenant_id: TenantId,
+        key: &SecretRef,
+        owner_id: Option<OwnerId>,
+        value: SecretValue,
+        previous_expires_at: OffsetDateTime,
+    ) -> Option<SecretVersion> {
+        let now = OffsetDateTime::now_utc();
+        let mut secrets = self.secrets.write();
+        let entry = secrets
+            .get_mut(&tenant_id)?
+            .get_mut(&(key.clone(), owner_id))?;
+        Some(entry.push_version(value, Some(previous_expires_at), now))
     }
 
-    /// Look up a secret by tenant ID and key.
+    /// Delete a secret with all of its versions. Returns `false` if the
+    /// secret does not exist.
+    pub fn delete(&self, tenant_id: TenantId, key: &SecretRef, owner_id: Option<OwnerId>) -> bool {
+        let mut secrets = self.secrets.write();
+        secrets
+            .get_mut(&tenant_id)
+            .and_then(|tenant_map| tenant_map.remove(&(key.clone(), owner_id)))
+            .is_some()
+    }
+
+    /// List tenant-wide secrets of `tenant_id` and the private secrets of
+    /// `owner_id`, ordered by key.
     #[must_use]
-    pub fn get(&self, tenant_id: TenantId, key: &SecretRef) -> Option<&SecretEntry> {
-        self.secrets.get(&tenant_id)?.get(key)
+    pub fn list(&self, tenant_id: TenantId, owner_id: OwnerId) -> Vec<SecretInfo> {
+        let now = OffsetDateTime::now_utc();
+        let secrets = self.secrets.read();
+        let Some(tenant_map) = secrets.get(&tenant_id) else {
+            return Vec::new();
+        };
+        let mut items: Vec<SecretInfo> = tenant_map
+            .iter()
+            .filter(|((_, owner), _)| owner.is_none_or(|o| o == owner_id))
+            .map(|((key, _), entry)| entry.info(key, now))
+            .collect();
+        // Tenant-wide secret first when a private one shares its key.
+        items.sort_by(|a, b| {
+            (a.key.as_ref(), a.sharing == SharingMode::Private)
+                .cmp(&(b.key.as_ref(), b.sharing == SharingMode::Private))
+        });
+        items
     }
 }
 
+/// Private secrets are stored per owner; all other modes once per tenant.
+fn scope_owner(sharing: SharingMode, owner_id: OwnerId) -> Option<OwnerId> {
+    (sharing == SharingMode::Private).then_some(owner_id)
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
     use super::*;
     use crate::config::SecretConfig;
+    use time::Duration;
     use uuid::Uuid;
 
     fn tenant_a() -> Uuid {
@@ -82,6 +327,10 @@ mod tests {
         Uuid::parse_str("33333333-3333-3333-3333-333333333333").unwrap()
     }
 
+    fn other_owner() -> Uuid {
+        Uuid::parse_str("44444444-4444-4444-4444-444444444444").unwrap()
+    }
+
     fn cfg_with_single_secret() -> StaticCredStorePluginConfig {
         StaticCredStorePluginConfig {
             secrets: vec![SecretConfig {
@@ -117,7 +366,7 @@ mod tests {
         let service = Service::from_config(&cfg_with_single_secret()).unwrap();
         let key = SecretRef::new("openai_api_key").unwrap();
 
-        let entry = service.get(tenant_a(), &key);
+        let entry = service.get(tenant_a(), &key, None);
         assert!(entry.is_some());
 
         let entry = entry.unwrap();
@@ -125,6 +374,7 @@ mod tests {
         assert_eq!(entry.owner_id, owner());
         assert_eq!(entry.owner_tenant_id, tenant_a());
         assert_eq!(entry.sharing, SharingMode::Tenant);
+        assert_eq!(entry.version, 1);
     }
 
     #[test]
@@ -132,7 +382,7 @@ mod tests {
         let service = Service::from_config(&cfg_with_single_secret()).unwrap();
         let key = SecretRef::new("openai_api_key").unwrap();
 
-        let entry = service.get(tenant_b(), &key);
+        let entry = service.get(tenant_b(), &key, None);
         assert!(entry.is_none());
     }
 
@@ -141,7 +391,7 @@ mod tests {
         let service = Service::from_config(&cfg_with_single_secret()).unwrap();
         let key = SecretRef::new("missing").unwrap();
 
-        let entry = service.get(tenant_a(), &key);
+        let entry = service.get(tenant_a(), &key, None);
         assert!(entry.is_none());
     }
 
@@ -188,8 +438,171 @@ mod tests {
         let service = Service::from_config(&cfg).unwrap();
         let key = SecretRef::new("any-key").unwrap();
         assert!(
-            service.get(tenant_a(), &key).is_none(),
+            service.get(tenant_a(), &key, None).is_none(),
             "empty config must return None for any lookup"
         );
     }
+
+    #[test]
+    fn from_config_stores_private_secret_under_owner() {
+        let cfg = StaticCredStorePluginConfig {
+            secrets: vec![SecretConfig {
+                sharing: SharingMode::Private,
+                ..cfg_with_single_secret().secrets[0].clone()
+            }],
+            ..StaticCredStorePluginConfig::default()
+        };
+        let service = Service::from_config(&cfg).unwrap();
+        let key = SecretRef::new("openai_api_key").unwrap();
+
+        assert!(service.get(tenant_a(), &key, None).is_none());
+        assert!(service.get(tenant_a(), &key, Some(other_owner())).is_none());
+        let entry = service.get(tenant_a(), &key, Some(owner())).unwrap();
+        assert_eq!(entry.sharing, SharingMode::Private);
+    }
+
+    #[test]
+    fn put_creates_then_replaces_with_next_version() {
+        let service = Service::from_config(&StaticCredStorePluginConfig::default()).unwrap();
+        let key = SecretRef::new("vendor-key").unwrap();
+
+        let v1 = service.put(tenant_a(), &key, "one".into(), SharingMode::Tenant, owner());
+        let v2 = service.put(
+            tenant_a(),
+            &key,
+            "two".into(),
+            SharingMode::Shared,
+            other_owner(),
+        );
+        assert_eq!((v1, v2), (1, 2));
+
+        let entry = service.get(tenant_a(), &key, None).unwrap();
+        assert_eq!(entry.value.as_bytes(), b"two");
+        assert_eq!(entry.sharing, SharingMode::Shared);
+        assert_eq!(entry.owner_id, owner(), "creator must be preserved");
+        assert!(
+            service.get_version(tenant_a(), &key, None, 1).is_none(),
+            "put must not keep the replaced version"
+        );
+    }
+
+    #[test]
+    fn put_private_does_not_touch_tenant_secret() {
+        let service = Service::from_config(&cfg_with_single_secret()).unwrap();
+        let key = SecretRef::new("openai_api_key").unwrap();
+
+        let version = service.put(
+            tenant_a(),
+            &key,
+            "mine".into(),
+            SharingMode::Private,
+            other_owner(),
+        );
+        assert_eq!(version, 1);
+
+        let tenant = service.get(tenant_a(), &key, None).unwrap();
+        assert_eq!(tenant.value.as_bytes(), b"sk-test-123");
+        let private = service.get(tenant_a(), &key, Some(other_owner())).unwrap();
+        assert_eq!(private.value.as_bytes(), b"mine");
+    }
+
+    #[test]
+    fn rotate_keeps_previous_version_until_expiry() {
+        let service = Service::from_config(&cfg_with_single_secret()).unwrap();
+        let key = SecretRef::new("openai_api_key").unwrap();
+        let expires_at = OffsetDateTime::now_utc() + Duration::hours(1);
+
+        let version = service
+            .rotate(tenant_a(), &key, None, "sk-new".into(), expires_at)
+            .unwrap();
+        assert_eq!(version, 2);
+
+        let current = service.get(tenant_a(), &key, None).unwrap();
+        assert_eq!(current.value.as_bytes(), b"sk-new");
+        assert_eq!(current.version, 2);
+        let previous = service.get_version(tenant_a(), &key, None, 1).unwrap();
+        assert_eq!(previous.value.as_bytes(), b"sk-test-123");
+
+        let info = service.list(tenant_a(), owner());
+        assert_eq!(
+            info[0].retired_versions,
+            vec![RetiredVersion {
+                version: 1,
+                expires_at
+            }]
+        );
+    }
+
+    #[test]
+    fn rotate_with_elapsed_grace_hides_previous_version() {
+        let service = Service::from_config(&cfg_with_single_secret()).unwrap();
+        let key = SecretRef::new("openai_api_key").unwrap();
+        let expired = OffsetDateTime::now_utc() - Duration::seconds(1);
+
+        service
+            .rotate(tenant_a(), &key, None, "sk-new".into(), expired)
+            .unwrap();
+        assert!(service.get_version(tenant_a(), &key, None, 1).is_none());
+        assert!(
+            service.list(tenant_a(), owner())[0]
+                .retired_versions
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn rotate_returns_none_for_missing_secret() {
+        let service = Service::from_config(&cfg_with_single_secret()).unwrap();
+        let key = SecretRef::new("openai_api_key").unwrap();
+        let expires_at = OffsetDateTime::now_utc();
+
+        assert!(
+            service
+                .rotate(tenant_b(), &key, None, "x".into(), expires_at)
+                .is_none()
+        );
+        assert!(
+            service
+                .rotate(tenant_a(), &key, Some(owner()), "x".into(), expires_at)
+                .is_none(),
+            "tenant secret must not be addressable as private"
+        );
+    }
+
+    #[test]
+    fn delete_removes_secret_once() {
+        let service = Service::from_config(&cfg_with_single_secret()).unwrap();
+        let key = SecretRef::new("openai_api_key").unwrap();
+
+        assert!(service.delete(tenant_a(), &key, None));
+        assert!(service.get(tenant_a(), &key, None).is_none());
+        assert!(!service.delete(tenant_a(), &key, None));
+    }
+
+    #[test]
+    fn list_includes_only_callers_private_secrets() {
+        let service = Service::from_config(&cfg_with_single_secret()).unwrap();
+        let key = SecretRef::new("mine").unwrap();
+        service.put(tenant_a(), &key, "x".into(), SharingMode::Private, owner());
+        service.put(
+            tenant_a(),
+            &key,
+            "y".into(),
+            SharingMode::Private,
+            other_owner(),
+        );
+
+        let items = service.list(tenant_a(), owner());
+        let keys: Vec<(&str, SharingMode)> =
+            items.iter().map(|i| (i.key.as_ref(), i.sharing)).collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("mine", SharingMode::Private),
+                ("openai_api_key", SharingMode::Tenant)
+            ]
+        );
+        assert!(items.iter().all(|i| i.owner_id == owner()));
+        assert!(service.list(tenant_b(), owner()).is_empty());
+    }
 }
@@ -15,6 +15,7 @@ use crate::domain::Service;
 /// Static credstore plugin module.
 ///
 /// Serves pre-configured secrets from YAML configuration for development and testing.
+/// Writes made through the API are kept in memory and lost on restart.
 #[modkit::module(
     name = "static-credstore-plugin",
     deps = ["types-registry"]
@@ -9,3 +9,16 @@ pub enum MiniChatModelPolicyPluginError {
     #[error("internal policy plugin error: {0}")]
     Internal(String),
 }
+
+/// Errors returned by `MiniChatToolExecutorPluginClientV1` methods.
+#[derive(Debug, Error)]
+pub enum MiniChatToolExecutorPluginError {
+    #[error("unknown tool: {0}")]
+    UnknownTool(String),
+
+    #[error("invalid tool arguments: {0}")]
+    InvalidArguments(String),
+
+    #[error("internal tool executor plugin error: {0}")]
+    Internal(String),
+}
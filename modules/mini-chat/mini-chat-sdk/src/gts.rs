@@ -20,3 +20,23 @@ use modkit::gts::BaseModkitPluginV1;
     properties = ""
 )]
 pub struct MiniChatModelPolicyPluginSpecV1;
+
+/// GTS type definition for mini-chat tool executor plugin instances.
+///
+/// Tool executor plugins provide the function tools offered to the model and
+/// execute the calls the model makes during a turn. Discovery follows the
+/// same vendor/priority selection as the model-policy plugin.
+///
+/// # Instance ID Format
+///
+/// ```text
+/// gts.x.core.modkit.plugin.v1~<vendor>.<package>.mini_chat_tool_executor.plugin.v1~
+/// ```
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = BaseModkitPluginV1,
+    schema_id = "gts.x.core.modkit.plugin.v1~x.core.mini_chat_tool_executor.plugin.v1~",
+    description = "Mini-Chat Tool Executor plugin specification",
+    properties = ""
+)]
+pub struct MiniChatToolExecutorPluginSpecV1;
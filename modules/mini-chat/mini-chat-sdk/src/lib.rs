@@ -3,7 +3,10 @@ pub mod gts;
 pub mod models;
 pub mod plugin_api;
 
-pub use error::MiniChatModelPolicyPluginError;
-pub use gts::MiniChatModelPolicyPluginSpecV1;
-pub use models::{ModelCatalogEntry, ModelTier, PolicySnapshot, PolicyVersionInfo};
-pub use plugin_api::MiniChatModelPolicyPluginClientV1;
+pub use error::{MiniChatModelPolicyPluginError, MiniChatToolExecutorPluginError};
+pub use gts::{MiniChatModelPolicyPluginSpecV1, MiniChatToolExecutorPluginSpecV1};
+pub use models::{
+    ModelCatalogEntry, ModelTier, PolicySnapshot, PolicyVersionInfo, ToolCallOutput,
+    ToolCallRequest, ToolDefinition,
+};
+pub use plugin_api::{MiniChatModelPolicyPluginClientV1, MiniChatToolExecutorPluginClientV1};
@@ -26,6 +26,20 @@ pub struct ModelCatalogEntry {
     pub tier: ModelTier,
     pub global_enabled: bool,
     pub is_default: bool,
+    /// Maximum tokens of one request: input plus generated output.
+    #[serde(default = "default_context_window_tokens")]
+    pub context_window_tokens: u32,
+    /// Maximum tokens the model generates in one response.
+    #[serde(default = "default_max_output_tokens")]
+    pub max_output_tokens: u32,
+}
+
+fn default_context_window_tokens() -> u32 {
+    128_000
+}
+
+fn default_max_output_tokens() -> u32 {
+    16_384
 }
 
 /// Model pricing/capability tier.
@@ -34,3 +48,32 @@ pub enum ModelTier {
     Standard,
     Premium,
 }
+
+/// A function tool exposed to the model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolDefinition {
+    /// Function name the model uses to call the tool.
+    pub name: String,
+    pub description: String,
+    /// JSON Schema of the call arguments.
+    pub parameters: serde_json::Value,
+}
+
+/// A single function call requested by the model.
+#[derive(Debug, Clone)]
+pub struct ToolCallRequest {
+    pub tenant_id: Uuid,
+    pub user_id: Uuid,
+    pub chat_id: Uuid,
+    pub name: String,
+    /// Call arguments as produced by the model (not validated against the schema).
+    pub arguments: serde_json::Value,
+}
+
+/// Result of a function call, fed back to the model verbatim.
+#[derive(Debug, Clone)]
+pub struct ToolCallOutput {
+    pub content: String,
+    /// Whether `content` describes a failure the model should react to.
+    pub is_error: bool,
+}
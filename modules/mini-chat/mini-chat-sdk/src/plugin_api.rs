@@ -1,8 +1,10 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use crate::error::MiniChatModelPolicyPluginError;
-use crate::models::{PolicySnapshot, PolicyVersionInfo};
+use crate::error::{MiniChatModelPolicyPluginError, MiniChatToolExecutorPluginError};
+use crate::models::{
+    PolicySnapshot, PolicyVersionInfo, ToolCallOutput, ToolCallRequest, ToolDefinition,
+};
 
 /// Plugin API trait for mini-chat model policy implementations.
 ///
@@ -24,3 +26,27 @@ pub trait MiniChatModelPolicyPluginClientV1: Send + Sync {
         policy_version: u64,
     ) -> Result<PolicySnapshot, MiniChatModelPolicyPluginError>;
 }
+
+/// Plugin API trait for mini-chat tool executor implementations.
+///
+/// Plugins implement this trait to offer function tools to the model and to
+/// execute the calls it makes. The mini-chat module runs the tool-calling
+/// loop and enforces step limits and timeouts; plugins only execute.
+#[async_trait]
+pub trait MiniChatToolExecutorPluginClientV1: Send + Sync {
+    /// List the function tools available to a tenant.
+    async fn list_tools(
+        &self,
+        tenant_id: Uuid,
+    ) -> Result<Vec<ToolDefinition>, MiniChatToolExecutorPluginError>;
+
+    /// Execute a single function call.
+    ///
+    /// Failures the model should see (e.g. a lookup that found nothing) are
+    /// returned as `Ok` with `is_error` set; `Err` is for calls that could
+    /// not be executed at all.
+    async fn execute(
+        &self,
+        call: ToolCallRequest,
+    ) -> Result<ToolCallOutput, MiniChatToolExecutorPluginError>;
+}
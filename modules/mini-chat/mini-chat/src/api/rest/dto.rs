@@ -3,7 +3,9 @@
 //! All REST DTOs live here; SDK `models.rs` stays transport-agnostic.
 //! Provide `From` conversions between SDK models and DTOs in this file.
 
-use crate::domain::models::ChatDetail;
+use crate::domain::models::{
+    Attachment, AttachmentKind, AttachmentStatus, ChatDetail, TurnStatus, TurnStatusState,
+};
 use axum::response::sse::Event;
 use serde::Serialize;
 use time::OffsetDateTime;
@@ -63,6 +65,151 @@ impl From<ChatDetail> for ChatDetailDto {
     }
 }
 
+// ════════════════════════════════════════════════════════════════════════════
+// Turn DTOs
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Client-facing turn lifecycle state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[modkit_macros::api_dto(response)]
+pub enum TurnStateDto {
+    Running,
+    Done,
+    Error,
+    Cancelled,
+}
+
+impl From<TurnStatusState> for TurnStateDto {
+    fn from(s: TurnStatusState) -> Self {
+        match s {
+            TurnStatusState::Running => Self::Running,
+            TurnStatusState::Done => Self::Done,
+            TurnStatusState::Error => Self::Error,
+            TurnStatusState::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+/// Response DTO for the Turn Status API.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct TurnStatusDto {
+    pub request_id: Uuid,
+    pub state: TurnStateDto,
+    pub error_code: Option<String>,
+    pub assistant_message_id: Option<Uuid>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<TurnStatus> for TurnStatusDto {
+    fn from(t: TurnStatus) -> Self {
+        Self {
+            request_id: t.request_id,
+            state: t.state.into(),
+            error_code: t.error_code,
+            assistant_message_id: t.assistant_message_id,
+            updated_at: t.updated_at,
+        }
+    }
+}
+
+/// Request body for `PATCH /v1/chats/{id}/turns/{request_id}`.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request)]
+pub struct EditTurnRequest {
+    /// Replacement user message content (must be non-empty).
+    pub content: String,
+    /// Client-generated idempotency key for the new turn. Generated by the
+    /// server when omitted.
+    #[serde(default)]
+    pub request_id: Option<Uuid>,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Attachment DTOs
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Processing status of an attachment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[modkit_macros::api_dto(response)]
+pub enum AttachmentStatusDto {
+    Pending,
+    Ready,
+    Failed,
+}
+
+impl From<AttachmentStatus> for AttachmentStatusDto {
+    fn from(s: AttachmentStatus) -> Self {
+        match s {
+            AttachmentStatus::Pending => Self::Pending,
+            AttachmentStatus::Ready => Self::Ready,
+            AttachmentStatus::Failed => Self::Failed,
+        }
+    }
+}
+
+/// Kind of an attachment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[modkit_macros::api_dto(response)]
+pub enum AttachmentKindDto {
+    Document,
+    Image,
+}
+
+impl From<AttachmentKind> for AttachmentKindDto {
+    fn from(k: AttachmentKind) -> Self {
+        match k {
+            AttachmentKind::Document => Self::Document,
+            AttachmentKind::Image => Self::Image,
+        }
+    }
+}
+
+/// Response DTO for the Attachments API.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct AttachmentDto {
+    pub attachment_id: Uuid,
+    pub status: AttachmentStatusDto,
+    pub kind: AttachmentKindDto,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub doc_summary: Option<String>,
+    /// Stable error code when `status` is `failed`.
+    pub error_code: Option<String>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub summary_updated_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<Attachment> for AttachmentDto {
+    fn from(a: Attachment) -> Self {
+        Self {
+            attachment_id: a.id,
+            status: a.status.into(),
+            kind: a.kind.into(),
+            filename: a.filename,
+            content_type: a.content_type,
+            size_bytes: a.size_bytes,
+            doc_summary: a.doc_summary,
+            error_code: a.error_code,
+            summary_updated_at: a.summary_updated_at,
+            created_at: a.created_at,
+        }
+    }
+}
+
+/// Response DTO for `DELETE /v1/chats/{id}/turns/{request_id}`.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct DeleteTurnDto {
+    pub request_id: Uuid,
+    pub deleted: bool,
+}
+
 // ════════════════════════════════════════════════════════════════════════════
 // StreamEvent — the SSE wire type
 // ════════════════════════════════════════════════════════════════════════════
@@ -70,12 +217,14 @@ impl From<ChatDetail> for ChatDetailDto {
 /// Server-sent event envelope for the `messages:stream` endpoint.
 ///
 /// Each variant maps to a distinct `event:` name and `data:` JSON payload.
-/// Ordering grammar: `ping* delta* tool* citations? (done | error)`.
+/// Ordering grammar: `ping* (delta | tool)* citations? (done | error)`.
 #[derive(Debug, Clone, ToSchema)]
 pub enum StreamEvent {
     Ping,
     Delta(DeltaData),
     Tool(ToolData),
+    ToolCall(ToolCallData),
+    ToolResult(ToolResultData),
     Citations(CitationsData),
     Done(Box<DoneData>),
     Error(ErrorData),
@@ -96,6 +245,38 @@ pub struct ToolData {
     pub details: serde_json::Value,
 }
 
+/// A function call the server is about to execute on the model's behalf.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ToolCallData {
+    /// Server-generated call ID, repeated in the matching `tool_result`.
+    pub id: Uuid,
+    /// Tool-calling round within the turn (1-based).
+    pub step: u32,
+    pub name: String,
+    pub arguments: serde_json::Value,
+}
+
+/// Outcome of a server-executed function call. The output itself is only
+/// sent to the model.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ToolResultData {
+    pub id: Uuid,
+    pub step: u32,
+    pub name: String,
+    pub status: ToolResultStatus,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolResultStatus {
+    Ok,
+    Error,
+    Timeout,
+    /// Not executed: the per-step call limit was reached.
+    Skipped,
+}
+
 /// Citations from provider annotations.
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct CitationsData {
@@ -131,6 +312,8 @@ impl StreamEvent {
             StreamEvent::Ping => Ok(Event::default().event("ping").data("{}")),
             StreamEvent::Delta(d) => Event::default().event("delta").json_data(&d),
             StreamEvent::Tool(t) => Event::default().event("tool").json_data(&t),
+            StreamEvent::ToolCall(t) => Event::default().event("tool_call").json_data(&t),
+            StreamEvent::ToolResult(t) => Event::default().event("tool_result").json_data(&t),
             StreamEvent::Citations(c) => Event::default().event("citations").json_data(&c),
             StreamEvent::Done(d) => Event::default().event("done").json_data(&*d),
             StreamEvent::Error(e) => Event::default().event("error").json_data(&e),
@@ -143,7 +326,9 @@ impl StreamEvent {
         match self {
             StreamEvent::Ping => StreamEventKind::Ping,
             StreamEvent::Delta(_) => StreamEventKind::Delta,
-            StreamEvent::Tool(_) => StreamEventKind::Tool,
+            StreamEvent::Tool(_) | StreamEvent::ToolCall(_) | StreamEvent::ToolResult(_) => {
+                StreamEventKind::Tool
+            }
             StreamEvent::Citations(_) => StreamEventKind::Citations,
             StreamEvent::Done(_) | StreamEvent::Error(_) => StreamEventKind::Terminal,
         }
@@ -176,7 +361,7 @@ pub enum StreamEventKind {
 // StreamPhase — event ordering state machine
 // ════════════════════════════════════════════════════════════════════════════
 
-/// Enforces the SSE ordering grammar: `ping* delta* tool* citations? (done | error)`.
+/// Enforces the SSE ordering grammar: `ping* (delta | tool)* citations? (done | error)`.
 ///
 /// Only forward transitions are allowed. Out-of-order events produce an
 /// [`OrderingViolation`] error.
@@ -186,9 +371,9 @@ pub enum StreamPhase {
     Idle,
     /// After one or more pings. Accepts ping, delta, tool, citations, terminal.
     Pinging,
-    /// After first delta. Accepts delta, tool, citations, terminal.
+    /// After a delta. Accepts delta, tool, citations, terminal.
     Deltas,
-    /// After first tool event. Accepts tool, citations, terminal.
+    /// After a tool event. Accepts delta, tool, citations, terminal.
     Tools,
     /// After citations. Accepts terminal only.
     Citations,
@@ -265,9 +450,10 @@ impl StreamPhase {
                 Ok(StreamPhase::Pinging)
             }
 
-            // Delta: from Idle, Pinging, or Deltas
+            // Delta: from Idle, Pinging, Deltas, or Tools (text resumes
+            // after server-executed tool calls)
             (
-                StreamPhase::Idle | StreamPhase::Pinging | StreamPhase::Deltas,
+                StreamPhase::Idle | StreamPhase::Pinging | StreamPhase::Deltas | StreamPhase::Tools,
                 StreamEventKind::Delta,
             ) => Ok(StreamPhase::Deltas),
 
@@ -504,10 +690,20 @@ mod tests {
     }
 
     #[test]
-    fn phase_tools_rejects_delta() {
-        assert!(
+    fn phase_tools_accepts_delta() {
+        assert_eq!(
             StreamPhase::Tools
                 .try_advance(StreamEventKind::Delta)
+                .unwrap(),
+            StreamPhase::Deltas
+        );
+    }
+
+    #[test]
+    fn phase_tools_rejects_ping() {
+        assert!(
+            StreamPhase::Tools
+                .try_advance(StreamEventKind::Ping)
                 .is_err()
         );
     }
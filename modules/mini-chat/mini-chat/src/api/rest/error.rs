@@ -35,6 +35,27 @@ impl From<DomainError> for Problem {
             )
             .with_trace_id(trace_id.unwrap_or_default()),
 
+            DomainError::InvalidTurnState { message } => Problem::new(
+                StatusCode::BAD_REQUEST,
+                "invalid_turn_state",
+                message.clone(),
+            )
+            .with_trace_id(trace_id.unwrap_or_default()),
+
+            DomainError::FileTooLarge { message } => Problem::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "file_too_large",
+                message.clone(),
+            )
+            .with_trace_id(trace_id.unwrap_or_default()),
+
+            DomainError::UnsupportedFileType { message } => Problem::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "unsupported_file_type",
+                message.clone(),
+            )
+            .with_trace_id(trace_id.unwrap_or_default()),
+
             DomainError::Conflict { code, message } => {
                 Problem::new(StatusCode::CONFLICT, code.clone(), message.clone())
                     .with_trace_id(trace_id.unwrap_or_default())
@@ -1,28 +1,61 @@
 use std::sync::Arc;
 
-use axum::Extension;
-use axum::extract::Path;
+use axum::extract::{Multipart, Path};
+use axum::{Extension, Json};
+use bytes::Bytes;
 use modkit::api::prelude::*;
 use modkit_security::SecurityContext;
+use uuid::Uuid;
 
+use crate::api::rest::dto::AttachmentDto;
+use crate::domain::error::DomainError;
 use crate::module::AppServices;
 
-use super::not_implemented;
-
 /// POST /mini-chat/v1/chats/{id}/attachments
+///
+/// Expects a multipart body with a `file` field. Returns the attachment in
+/// `pending` state; indexing continues in the background.
+#[tracing::instrument(skip(svc, ctx, multipart), fields(chat_id = %chat_id))]
 pub(crate) async fn upload_attachment(
-    Extension(_ctx): Extension<SecurityContext>,
-    Extension(_svc): Extension<Arc<AppServices>>,
-    Path(_chat_id): Path<uuid::Uuid>,
-) -> ApiResult<StatusCode> {
-    Err(not_implemented())
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<AppServices>>,
+    Path(chat_id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> ApiResult<impl IntoResponse> {
+    let mut file: Option<(String, Option<String>, Bytes)> = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| DomainError::validation(format!("Multipart error: {e}")))?
+    {
+        if field.name() == Some("file") {
+            let filename = field.file_name().unwrap_or_default().to_owned();
+            let content_type = field.content_type().map(ToOwned::to_owned);
+            let bytes = field
+                .bytes()
+                .await
+                .map_err(|e| DomainError::validation(format!("Failed to read file: {e}")))?;
+            file = Some((filename, content_type, bytes));
+            break;
+        }
+    }
+    let (filename, content_type, bytes) =
+        file.ok_or_else(|| DomainError::validation("No file field found in multipart request"))?;
+
+    let (attachment, _indexing) = svc
+        .attachments
+        .upload(&ctx, chat_id, &filename, content_type.as_deref(), bytes)
+        .await?;
+    Ok((StatusCode::CREATED, Json(AttachmentDto::from(attachment))))
 }
 
 /// GET /mini-chat/v1/chats/{id}/attachments/{attachment_id}
+#[tracing::instrument(skip(svc, ctx), fields(chat_id = %chat_id, attachment_id = %attachment_id))]
 pub(crate) async fn get_attachment(
-    Extension(_ctx): Extension<SecurityContext>,
-    Extension(_svc): Extension<Arc<AppServices>>,
-    Path((_chat_id, _attachment_id)): Path<(uuid::Uuid, uuid::Uuid)>,
-) -> ApiResult<StatusCode> {
-    Err(not_implemented())
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<AppServices>>,
+    Path((chat_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> ApiResult<JsonBody<AttachmentDto>> {
+    let attachment = svc.attachments.get(&ctx, chat_id, attachment_id).await?;
+    Ok(Json(AttachmentDto::from(attachment)))
 }
@@ -17,7 +17,7 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
 use crate::api::rest::dto::{StreamEvent, StreamEventKind, StreamMessageRequest, StreamPhase};
-use crate::domain::service::StreamError;
+use crate::domain::service::{StreamError, StreamOutcome};
 use crate::module::AppServices;
 
 use super::not_implemented;
@@ -85,20 +85,44 @@ pub(crate) async fn stream_message(
             return Problem::new(StatusCode::CONFLICT, "Conflict", "Duplicate request_id")
                 .into_response();
         }
-        Err(StreamError::Conflict { message, .. }) => {
-            return Problem::new(StatusCode::CONFLICT, "Conflict", &message).into_response();
+        Err(e) => return stream_error_response(e),
+    };
+
+    sse_response(provider_handle, rx, cancel, ping_secs)
+}
+
+/// Map a pre-stream error to a JSON error response.
+pub(super) fn stream_error_response(err: StreamError) -> Response {
+    match err {
+        StreamError::Replay { turn } => Problem::new(
+            StatusCode::CONFLICT,
+            "Conflict",
+            format!("Turn {} already exists", turn.request_id),
+        )
+        .into_response(),
+        StreamError::Conflict { code, message } => {
+            Problem::new(StatusCode::CONFLICT, code, message).into_response()
         }
-        Err(StreamError::TurnCreationFailed { source }) => {
+        StreamError::TurnCreationFailed { source } => {
             warn!(error = %source, "pre-stream turn creation failed");
-            return Problem::new(
+            Problem::new(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Internal Error",
                 "Failed to initialize turn",
             )
-            .into_response();
+            .into_response()
         }
-    };
+        StreamError::Rejected { source } => Problem::from(source).into_response(),
+    }
+}
 
+/// Open the SSE response relaying events from a spawned provider task.
+pub(super) fn sse_response(
+    provider_handle: tokio::task::JoinHandle<StreamOutcome>,
+    rx: mpsc::Receiver<StreamEvent>,
+    cancel: CancellationToken,
+    ping_secs: u64,
+) -> Response {
     // Monitor provider task for panics
     tokio::spawn(async move {
         if let Err(e) = provider_handle.await {
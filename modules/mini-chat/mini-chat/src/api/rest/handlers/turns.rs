@@ -1,46 +1,117 @@
 use std::sync::Arc;
 
-use axum::Extension;
 use axum::extract::Path;
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
 use modkit::api::prelude::*;
 use modkit_security::SecurityContext;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+use uuid::Uuid;
 
+use crate::api::rest::dto::{DeleteTurnDto, EditTurnRequest, StreamEvent, TurnStatusDto};
 use crate::module::AppServices;
 
-use super::not_implemented;
+use super::messages::{sse_response, stream_error_response};
 
 /// GET /mini-chat/v1/chats/{id}/turns/{request_id}
+#[tracing::instrument(skip(svc, ctx), fields(chat_id = %chat_id, request_id = %request_id))]
 pub(crate) async fn get_turn(
-    Extension(_ctx): Extension<SecurityContext>,
-    Extension(_svc): Extension<Arc<AppServices>>,
-    Path((_chat_id, _request_id)): Path<(uuid::Uuid, uuid::Uuid)>,
-) -> ApiResult<StatusCode> {
-    Err(not_implemented())
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<AppServices>>,
+    Path((chat_id, request_id)): Path<(Uuid, Uuid)>,
+) -> ApiResult<JsonBody<TurnStatusDto>> {
+    let status = svc.turns.get_turn(&ctx, chat_id, request_id).await?;
+    Ok(Json(TurnStatusDto::from(status)))
 }
 
 /// POST /mini-chat/v1/chats/{id}/turns/{request_id}/retry
+///
+/// Mutation-rule violations return JSON errors; a turn that was already
+/// replaced yields 409 naming the replacing turn. On success, streams the
+/// new turn with the same SSE contract as `messages/stream`.
 pub(crate) async fn retry_turn(
-    Extension(_ctx): Extension<SecurityContext>,
-    Extension(_svc): Extension<Arc<AppServices>>,
-    Path((_chat_id, _request_id)): Path<(uuid::Uuid, uuid::Uuid)>,
-) -> ApiResult<StatusCode> {
-    Err(not_implemented())
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<AppServices>>,
+    Path((chat_id, request_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    let ping_secs = svc.stream.ping_interval_secs();
+    let (tx, rx) = mpsc::channel::<StreamEvent>(svc.stream.channel_capacity());
+    let cancel = CancellationToken::new();
+
+    // TODO: model should come from user preferences / quota decision
+    let model = "gpt-4o".to_owned();
+
+    info!(%chat_id, %request_id, model = %model, "retrying turn");
+
+    match svc
+        .stream
+        .retry_turn(ctx, chat_id, request_id, model, cancel.clone(), tx)
+        .await
+    {
+        Ok(handle) => sse_response(handle, rx, cancel, ping_secs),
+        Err(e) => stream_error_response(e),
+    }
 }
 
 /// PATCH /mini-chat/v1/chats/{id}/turns/{request_id}
+///
+/// Same contract as retry, with the user message replaced by `content`.
 pub(crate) async fn edit_turn(
-    Extension(_ctx): Extension<SecurityContext>,
-    Extension(_svc): Extension<Arc<AppServices>>,
-    Path((_chat_id, _request_id)): Path<(uuid::Uuid, uuid::Uuid)>,
-) -> ApiResult<StatusCode> {
-    Err(not_implemented())
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<AppServices>>,
+    Path((chat_id, request_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<EditTurnRequest>,
+) -> Response {
+    if body.content.trim().is_empty() {
+        return Problem::new(
+            StatusCode::BAD_REQUEST,
+            "Bad Request",
+            "Message content must not be empty",
+        )
+        .into_response();
+    }
+
+    let ping_secs = svc.stream.ping_interval_secs();
+    let (tx, rx) = mpsc::channel::<StreamEvent>(svc.stream.channel_capacity());
+    let cancel = CancellationToken::new();
+
+    // TODO: model should come from user preferences / quota decision
+    let model = "gpt-4o".to_owned();
+    let new_request_id = body.request_id.unwrap_or_else(Uuid::new_v4);
+
+    info!(%chat_id, %request_id, %new_request_id, model = %model, "editing turn");
+
+    match svc
+        .stream
+        .edit_turn(
+            ctx,
+            chat_id,
+            request_id,
+            new_request_id,
+            body.content,
+            model,
+            cancel.clone(),
+            tx,
+        )
+        .await
+    {
+        Ok(handle) => sse_response(handle, rx, cancel, ping_secs),
+        Err(e) => stream_error_response(e),
+    }
 }
 
 /// DELETE /mini-chat/v1/chats/{id}/turns/{request_id}
+#[tracing::instrument(skip(svc, ctx), fields(chat_id = %chat_id, request_id = %request_id))]
 pub(crate) async fn delete_turn(
-    Extension(_ctx): Extension<SecurityContext>,
-    Extension(_svc): Extension<Arc<AppServices>>,
-    Path((_chat_id, _request_id)): Path<(uuid::Uuid, uuid::Uuid)>,
-) -> ApiResult<StatusCode> {
-    Err(not_implemented())
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<AppServices>>,
+    Path((chat_id, request_id)): Path<(Uuid, Uuid)>,
+) -> ApiResult<JsonBody<DeleteTurnDto>> {
+    svc.turns.delete_turn(&ctx, chat_id, request_id).await?;
+    Ok(Json(DeleteTurnDto {
+        request_id,
+        deleted: true,
+    }))
 }
@@ -3,7 +3,7 @@ use modkit::api::OpenApiRegistry;
 use modkit::api::operation_builder::OperationBuilder;
 
 use super::AiChatLicense;
-use crate::api::rest::handlers;
+use crate::api::rest::{dto, handlers};
 
 pub(super) fn register_attachment_routes(
     mut router: Router,
@@ -18,9 +18,20 @@ pub(super) fn register_attachment_routes(
         .authenticated()
         .require_license_features([&AiChatLicense])
         .path_param("id", "Chat UUID")
+        .multipart_file_request("file", Some("Document to attach"))
         .handler(handlers::attachments::upload_attachment)
-        .json_response(http::StatusCode::CREATED, "Attachment uploaded")
+        .json_response_with_schema::<dto::AttachmentDto>(
+            openapi,
+            http::StatusCode::CREATED,
+            "Attachment created; indexing continues asynchronously",
+        )
         .standard_errors(openapi)
+        .problem_response(
+            openapi,
+            http::StatusCode::PAYLOAD_TOO_LARGE,
+            "File exceeds the upload size limit",
+        )
+        .error_415(openapi)
         .register(router, openapi);
 
     // GET {prefix}/v1/chats/{id}/attachments/{attachment_id}
@@ -35,7 +46,11 @@ pub(super) fn register_attachment_routes(
     .path_param("id", "Chat UUID")
     .path_param("attachment_id", "Attachment UUID")
     .handler(handlers::attachments::get_attachment)
-    .json_response(http::StatusCode::OK, "Attachment metadata")
+    .json_response_with_schema::<dto::AttachmentDto>(
+        openapi,
+        http::StatusCode::OK,
+        "Attachment metadata and status",
+    )
     .standard_errors(openapi)
     .register(router, openapi);
 
@@ -3,7 +3,7 @@ use modkit::api::OpenApiRegistry;
 use modkit::api::operation_builder::OperationBuilder;
 
 use super::AiChatLicense;
-use crate::api::rest::handlers;
+use crate::api::rest::{dto, handlers};
 
 pub(super) fn register_turn_routes(
     mut router: Router,
@@ -20,7 +20,11 @@ pub(super) fn register_turn_routes(
         .path_param("id", "Chat UUID")
         .path_param("request_id", "Turn request UUID")
         .handler(handlers::turns::get_turn)
-        .json_response(http::StatusCode::OK, "Turn found")
+        .json_response_with_schema::<dto::TurnStatusDto>(
+            openapi,
+            http::StatusCode::OK,
+            "Authoritative turn status",
+        )
         .standard_errors(openapi)
         .register(router, openapi);
 
@@ -33,42 +37,47 @@ pub(super) fn register_turn_routes(
         "{prefix}/v1/chats/{{id}}/turns/{{request_id}}/retry"
     ))
     .operation_id("mini_chat.retry_turn")
-    .summary("Retry a failed turn")
+    .summary("Retry the latest turn and stream a new response via SSE")
     .tag("turns")
     .authenticated()
     .require_license_features([&AiChatLicense])
     .path_param("id", "Chat UUID")
     .path_param("request_id", "Turn request UUID")
     .handler(handlers::turns::retry_turn)
-    .json_response(http::StatusCode::OK, "Turn retry initiated")
+    .sse_json::<dto::StreamEvent>(openapi, "SSE stream of the new turn's response events")
     .standard_errors(openapi)
     .register(router, openapi);
 
     // PATCH {prefix}/v1/chats/{id}/turns/{request_id}
     router = OperationBuilder::patch(format!("{prefix}/v1/chats/{{id}}/turns/{{request_id}}"))
         .operation_id("mini_chat.edit_turn")
-        .summary("Edit a turn (user message)")
+        .summary("Edit the latest turn's message and stream a new response via SSE")
         .tag("turns")
         .authenticated()
         .require_license_features([&AiChatLicense])
         .path_param("id", "Chat UUID")
         .path_param("request_id", "Turn request UUID")
+        .json_request::<dto::EditTurnRequest>(openapi, "Replacement message")
         .handler(handlers::turns::edit_turn)
-        .json_response(http::StatusCode::OK, "Turn edited")
+        .sse_json::<dto::StreamEvent>(openapi, "SSE stream of the new turn's response events")
         .standard_errors(openapi)
         .register(router, openapi);
 
     // DELETE {prefix}/v1/chats/{id}/turns/{request_id}
     router = OperationBuilder::delete(format!("{prefix}/v1/chats/{{id}}/turns/{{request_id}}"))
         .operation_id("mini_chat.delete_turn")
-        .summary("Delete a turn")
+        .summary("Delete the latest turn")
         .tag("turns")
         .authenticated()
         .require_license_features([&AiChatLicense])
         .path_param("id", "Chat UUID")
         .path_param("request_id", "Turn request UUID")
         .handler(handlers::turns::delete_turn)
-        .json_response(http::StatusCode::NO_CONTENT, "Turn deleted")
+        .json_response_with_schema::<dto::DeleteTurnDto>(
+            openapi,
+            http::StatusCode::OK,
+            "Turn deleted",
+        )
         .standard_errors(openapi)
         .register(router, openapi);
 
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::infra::embedder::EmbedderConfig;
+use crate::infra::llm::ProviderConfig;
 use crate::module::DEFAULT_URL_PREFIX;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +13,18 @@ pub struct MiniChatConfig {
     pub streaming: StreamingConfig,
     #[serde(default = "default_vendor")]
     pub vendor: String,
+    /// LLM provider adapter and the OAGW upstream it calls.
+    #[serde(default)]
+    pub provider: ProviderConfig,
+    #[serde(default)]
+    pub tools: ToolsConfig,
+    #[serde(default)]
+    pub attachments: AttachmentsConfig,
+    /// Embedder for the document index; `hashing` (local) by default.
+    #[serde(default)]
+    pub embedder: EmbedderConfig,
+    #[serde(default)]
+    pub context: ContextConfig,
 }
 
 /// SSE streaming tuning parameters.
@@ -65,12 +79,277 @@ fn default_ping_interval() -> u16 {
     15
 }
 
+/// Server-side function calling limits.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolsConfig {
+    /// Offer tools from the tool executor plugin to the model.
+    /// Disabled by default; requires a registered plugin for `vendor`.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Maximum number of tool-calling rounds per turn.
+    /// Valid range: 1–10 (default 4).
+    #[serde(default = "default_max_tool_steps")]
+    pub max_steps: u8,
+
+    /// Maximum number of calls executed in a single round; further calls
+    /// are answered with an error result.
+    /// Valid range: 1–16 (default 8).
+    #[serde(default = "default_max_calls_per_step")]
+    pub max_calls_per_step: u8,
+
+    /// Per-call execution timeout in seconds.
+    /// Valid range: 1–120 (default 30).
+    #[serde(default = "default_tool_call_timeout")]
+    pub call_timeout_seconds: u16,
+}
+
+impl Default for ToolsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_steps: default_max_tool_steps(),
+            max_calls_per_step: default_max_calls_per_step(),
+            call_timeout_seconds: default_tool_call_timeout(),
+        }
+    }
+}
+
+impl ToolsConfig {
+    /// Validate configuration values at startup. Returns an error message
+    /// describing the first invalid value found.
+    pub fn validate(self) -> Result<(), String> {
+        if !(1..=10).contains(&self.max_steps) {
+            return Err(format!("max_steps must be 1-10, got {}", self.max_steps));
+        }
+        if !(1..=16).contains(&self.max_calls_per_step) {
+            return Err(format!(
+                "max_calls_per_step must be 1-16, got {}",
+                self.max_calls_per_step
+            ));
+        }
+        if !(1..=120).contains(&self.call_timeout_seconds) {
+            return Err(format!(
+                "call_timeout_seconds must be 1-120, got {}",
+                self.call_timeout_seconds
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_max_tool_steps() -> u8 {
+    4
+}
+
+fn default_max_calls_per_step() -> u8 {
+    8
+}
+
+fn default_tool_call_timeout() -> u16 {
+    30
+}
+
+/// Document attachment limits and local retrieval (RAG) tuning.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AttachmentsConfig {
+    /// Maximum size of a single uploaded file in MiB.
+    /// Valid range: 1–100 (default 25).
+    #[serde(default = "default_max_file_size_mb")]
+    pub max_file_size_mb: u32,
+
+    /// Maximum number of documents per chat.
+    /// Valid range: 1–500 (default 50).
+    #[serde(default = "default_max_documents_per_chat")]
+    pub max_documents_per_chat: u32,
+
+    /// Maximum cumulative size of documents per chat in MiB.
+    /// Valid range: 1–1024 (default 100).
+    #[serde(default = "default_max_total_upload_mb_per_chat")]
+    pub max_total_upload_mb_per_chat: u32,
+
+    /// Maximum number of indexed chunks per chat.
+    /// Valid range: 100–100000 (default 10000).
+    #[serde(default = "default_max_chunks_per_chat")]
+    pub max_chunks_per_chat: u32,
+
+    /// Target chunk size in estimated tokens.
+    /// Valid range: 64–2048 (default 400).
+    #[serde(default = "default_chunk_size_tokens")]
+    pub chunk_size_tokens: u32,
+
+    /// Estimated tokens repeated between consecutive chunks.
+    /// Must be less than half of `chunk_size_tokens` (default 50).
+    #[serde(default = "default_chunk_overlap_tokens")]
+    pub chunk_overlap_tokens: u32,
+
+    /// Number of chunks retrieved per turn.
+    /// Valid range: 1–20 (default 5).
+    #[serde(default = "default_retrieval_k")]
+    pub retrieval_k: u8,
+
+    /// Token budget for retrieved excerpts injected into one turn.
+    /// Valid range: 256–16000 (default 2000).
+    #[serde(default = "default_max_retrieved_tokens_per_turn")]
+    pub max_retrieved_tokens_per_turn: u32,
+
+    /// Dimension of the embedding vectors.
+    /// Valid range: 32–4096 (default 256).
+    #[serde(default = "default_embedding_dimensions")]
+    pub embedding_dimensions: u16,
+}
+
+impl Default for AttachmentsConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size_mb: default_max_file_size_mb(),
+            max_documents_per_chat: default_max_documents_per_chat(),
+            max_total_upload_mb_per_chat: default_max_total_upload_mb_per_chat(),
+            max_chunks_per_chat: default_max_chunks_per_chat(),
+            chunk_size_tokens: default_chunk_size_tokens(),
+            chunk_overlap_tokens: default_chunk_overlap_tokens(),
+            retrieval_k: default_retrieval_k(),
+            max_retrieved_tokens_per_turn: default_max_retrieved_tokens_per_turn(),
+            embedding_dimensions: default_embedding_dimensions(),
+        }
+    }
+}
+
+impl AttachmentsConfig {
+    /// Validate configuration values at startup. Returns an error message
+    /// describing the first invalid value found.
+    pub fn validate(self) -> Result<(), String> {
+        if !(1..=100).contains(&self.max_file_size_mb) {
+            return Err(format!(
+                "max_file_size_mb must be 1-100, got {}",
+                self.max_file_size_mb
+            ));
+        }
+        if !(1..=500).contains(&self.max_documents_per_chat) {
+            return Err(format!(
+                "max_documents_per_chat must be 1-500, got {}",
+                self.max_documents_per_chat
+            ));
+        }
+        if !(1..=1024).contains(&self.max_total_upload_mb_per_chat) {
+            return Err(format!(
+                "max_total_upload_mb_per_chat must be 1-1024, got {}",
+                self.max_total_upload_mb_per_chat
+            ));
+        }
+        if self.max_file_size_mb > self.max_total_upload_mb_per_chat {
+            return Err(format!(
+                "max_file_size_mb ({}) must not exceed max_total_upload_mb_per_chat ({})",
+                self.max_file_size_mb, self.max_total_upload_mb_per_chat
+            ));
+        }
+        if !(100..=100_000).contains(&self.max_chunks_per_chat) {
+            return Err(format!(
+                "max_chunks_per_chat must be 100-100000, got {}",
+                self.max_chunks_per_chat
+            ));
+        }
+        if !(64..=2048).contains(&self.chunk_size_tokens) {
+            return Err(format!(
+                "chunk_size_tokens must be 64-2048, got {}",
+                self.chunk_size_tokens
+            ));
+        }
+        if self.chunk_overlap_tokens * 2 >= self.chunk_size_tokens {
+            return Err(format!(
+                "chunk_overlap_tokens must be less than half of chunk_size_tokens, got {}",
+                self.chunk_overlap_tokens
+            ));
+        }
+        if !(1..=20).contains(&self.retrieval_k) {
+            return Err(format!(
+                "retrieval_k must be 1-20, got {}",
+                self.retrieval_k
+            ));
+        }
+        if !(256..=16_000).contains(&self.max_retrieved_tokens_per_turn) {
+            return Err(format!(
+                "max_retrieved_tokens_per_turn must be 256-16000, got {}",
+                self.max_retrieved_tokens_per_turn
+            ));
+        }
+        if self.chunk_size_tokens > self.max_retrieved_tokens_per_turn {
+            return Err(format!(
+                "chunk_size_tokens ({}) must not exceed max_retrieved_tokens_per_turn ({})",
+                self.chunk_size_tokens, self.max_retrieved_tokens_per_turn
+            ));
+        }
+        if !(32..=4096).contains(&self.embedding_dimensions) {
+            return Err(format!(
+                "embedding_dimensions must be 32-4096, got {}",
+                self.embedding_dimensions
+            ));
+        }
+        Ok(())
+    }
+
+    /// Per-file size limit in bytes.
+    #[must_use]
+    pub fn max_file_size_bytes(self) -> u64 {
+        u64::from(self.max_file_size_mb) * 1024 * 1024
+    }
+
+    /// Per-chat cumulative size limit in bytes.
+    #[must_use]
+    pub fn max_total_upload_bytes_per_chat(self) -> u64 {
+        u64::from(self.max_total_upload_mb_per_chat) * 1024 * 1024
+    }
+}
+
+fn default_max_file_size_mb() -> u32 {
+    25
+}
+
+fn default_max_documents_per_chat() -> u32 {
+    50
+}
+
+fn default_max_total_upload_mb_per_chat() -> u32 {
+    100
+}
+
+fn default_max_chunks_per_chat() -> u32 {
+    10_000
+}
+
+fn default_chunk_size_tokens() -> u32 {
+    400
+}
+
+fn default_chunk_overlap_tokens() -> u32 {
+    50
+}
+
+fn default_retrieval_k() -> u8 {
+    5
+}
+
+fn default_max_retrieved_tokens_per_turn() -> u32 {
+    2000
+}
+
+fn default_embedding_dimensions() -> u16 {
+    256
+}
+
 impl Default for MiniChatConfig {
     fn default() -> Self {
         Self {
             url_prefix: default_url_prefix(),
             streaming: StreamingConfig::default(),
             vendor: default_vendor(),
+            provider: ProviderConfig::default(),
+            tools: ToolsConfig::default(),
+            attachments: AttachmentsConfig::default(),
+            embedder: EmbedderConfig::default(),
+            context: ContextConfig::default(),
         }
     }
 }
@@ -83,6 +362,143 @@ fn default_vendor() -> String {
     "hyperspot".to_owned()
 }
 
+/// How earlier messages of a chat are fit into the model's context window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextStrategy {
+    /// Leave out the oldest messages that do not fit.
+    TruncateOldest,
+    /// Fold the oldest messages into the thread summary before the turn
+    /// is sent; the turn waits for the summary.
+    Summarize,
+    /// Truncate the oldest messages for the current turn and fold them
+    /// into the thread summary in the background for later turns.
+    Hybrid,
+}
+
+impl ContextStrategy {
+    /// Name stored in turn records.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::TruncateOldest => "truncate_oldest",
+            Self::Summarize => "summarize",
+            Self::Hybrid => "hybrid",
+        }
+    }
+}
+
+/// Context-window management for chat history.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContextConfig {
+    /// What happens to history that outgrows the context window
+    /// (default `hybrid`).
+    #[serde(default = "default_context_strategy")]
+    pub strategy: ContextStrategy,
+
+    /// Context window assumed for models the catalog does not list.
+    /// Valid range: 4096–2000000 (default 128000).
+    #[serde(default = "default_context_window_tokens")]
+    pub default_context_window_tokens: u32,
+
+    /// Share of the context window kept free for token estimation error.
+    /// Valid range: 0–50 (default 10).
+    #[serde(default = "default_safety_margin_percent")]
+    pub safety_margin_percent: u8,
+
+    /// History size, as a share of its budget, at which the oldest
+    /// messages are folded into the thread summary.
+    /// Valid range: 10–100 (default 80).
+    #[serde(default = "default_summary_trigger_percent")]
+    pub summary_trigger_percent: u8,
+
+    /// History size, as a share of its budget, kept verbatim after folding.
+    /// Must be below `summary_trigger_percent` (default 40).
+    #[serde(default = "default_summary_target_percent")]
+    pub summary_target_percent: u8,
+
+    /// Maximum length of the thread summary in tokens.
+    /// Valid range: 128–8192 (default 1024).
+    #[serde(default = "default_max_summary_tokens")]
+    pub max_summary_tokens: u32,
+}
+
+impl Default for ContextConfig {
+    fn default() -> Self {
+        Self {
+            strategy: default_context_strategy(),
+            default_context_window_tokens: default_context_window_tokens(),
+            safety_margin_percent: default_safety_margin_percent(),
+            summary_trigger_percent: default_summary_trigger_percent(),
+            summary_target_percent: default_summary_target_percent(),
+            max_summary_tokens: default_max_summary_tokens(),
+        }
+    }
+}
+
+impl ContextConfig {
+    /// Validate configuration values at startup. Returns an error message
+    /// describing the first invalid value found.
+    pub fn validate(self) -> Result<(), String> {
+        if !(4096..=2_000_000).contains(&self.default_context_window_tokens) {
+            return Err(format!(
+                "default_context_window_tokens must be 4096-2000000, got {}",
+                self.default_context_window_tokens
+            ));
+        }
+        if self.safety_margin_percent > 50 {
+            return Err(format!(
+                "safety_margin_percent must be 0-50, got {}",
+                self.safety_margin_percent
+            ));
+        }
+        if !(10..=100).contains(&self.summary_trigger_percent) {
+            return Err(format!(
+                "summary_trigger_percent must be 10-100, got {}",
+                self.summary_trigger_percent
+            ));
+        }
+        if self.summary_target_percent >= self.summary_trigger_percent {
+            return Err(format!(
+                "summary_target_percent ({}) must be below summary_trigger_percent ({})",
+                self.summary_target_percent, self.summary_trigger_percent
+            ));
+        }
+        if !(128..=8192).contains(&self.max_summary_tokens) {
+            return Err(format!(
+                "max_summary_tokens must be 128-8192, got {}",
+                self.max_summary_tokens
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_context_strategy() -> ContextStrategy {
+    ContextStrategy::Hybrid
+}
+
+fn default_context_window_tokens() -> u32 {
+    128_000
+}
+
+fn default_safety_margin_percent() -> u8 {
+    10
+}
+
+fn default_summary_trigger_percent() -> u8 {
+    80
+}
+
+fn default_summary_target_percent() -> u8 {
+    40
+}
+
+fn default_max_summary_tokens() -> u32 {
+    1024
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,6 +506,138 @@ mod tests {
     #[test]
     fn default_config_is_valid() {
         StreamingConfig::default().validate().unwrap();
+        ToolsConfig::default().validate().unwrap();
+        AttachmentsConfig::default().validate().unwrap();
+        ContextConfig::default().validate().unwrap();
+    }
+
+    #[test]
+    fn attachments_config_boundaries() {
+        let valid = AttachmentsConfig::default();
+
+        assert!(
+            (AttachmentsConfig {
+                max_file_size_mb: 0,
+                ..valid
+            })
+            .validate()
+            .is_err()
+        );
+        assert!(
+            (AttachmentsConfig {
+                max_file_size_mb: 101,
+                max_total_upload_mb_per_chat: 1024,
+                ..valid
+            })
+            .validate()
+            .is_err()
+        );
+        assert!(
+            (AttachmentsConfig {
+                max_file_size_mb: 50,
+                max_total_upload_mb_per_chat: 40,
+                ..valid
+            })
+            .validate()
+            .is_err()
+        );
+        assert!(
+            (AttachmentsConfig {
+                chunk_size_tokens: 64,
+                chunk_overlap_tokens: 31,
+                ..valid
+            })
+            .validate()
+            .is_ok()
+        );
+        assert!(
+            (AttachmentsConfig {
+                chunk_size_tokens: 64,
+                chunk_overlap_tokens: 32,
+                ..valid
+            })
+            .validate()
+            .is_err()
+        );
+        assert!(
+            (AttachmentsConfig {
+                retrieval_k: 0,
+                ..valid
+            })
+            .validate()
+            .is_err()
+        );
+        assert!(
+            (AttachmentsConfig {
+                retrieval_k: 20,
+                ..valid
+            })
+            .validate()
+            .is_ok()
+        );
+        assert!(
+            (AttachmentsConfig {
+                chunk_size_tokens: 1024,
+                max_retrieved_tokens_per_turn: 512,
+                ..valid
+            })
+            .validate()
+            .is_err()
+        );
+        assert!(
+            (AttachmentsConfig {
+                embedding_dimensions: 31,
+                ..valid
+            })
+            .validate()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn tools_config_boundaries() {
+        let valid = ToolsConfig::default();
+
+        assert!(
+            (ToolsConfig {
+                max_steps: 0,
+                ..valid
+            })
+            .validate()
+            .is_err()
+        );
+        assert!(
+            (ToolsConfig {
+                max_steps: 10,
+                ..valid
+            })
+            .validate()
+            .is_ok()
+        );
+        assert!(
+            (ToolsConfig {
+                max_calls_per_step: 17,
+                ..valid
+            })
+            .validate()
+            .is_err()
+        );
+        assert!(
+            (ToolsConfig {
+                call_timeout_seconds: 0,
+                ..valid
+            })
+            .validate()
+            .is_err()
+        );
+        assert!(
+            (ToolsConfig {
+                call_timeout_seconds: 121,
+                ..valid
+            })
+            .validate()
+            .is_err()
+        );
     }
 
     #[test]
@@ -167,4 +715,40 @@ mod tests {
             .is_err()
         );
     }
+
+    #[test]
+    fn context_config_boundaries() {
+        let valid = ContextConfig::default();
+
+        for invalid in [
+            ContextConfig {
+                default_context_window_tokens: 4095,
+                ..valid
+            },
+            ContextConfig {
+                safety_margin_percent: 51,
+                ..valid
+            },
+            ContextConfig {
+                summary_trigger_percent: 101,
+                ..valid
+            },
+            ContextConfig {
+                summary_trigger_percent: 80,
+                summary_target_percent: 80,
+                ..valid
+            },
+            ContextConfig {
+                max_summary_tokens: 127,
+                ..valid
+            },
+        ] {
+            assert!(invalid.validate().is_err(), "{invalid:?}");
+        }
+
+        let config: ContextConfig =
+            serde_json::from_value(serde_json::json!({ "strategy": "truncate_oldest" })).unwrap();
+        assert_eq!(config.strategy, ContextStrategy::TruncateOldest);
+        config.validate().unwrap();
+    }
 }
@@ -30,6 +30,15 @@ pub enum DomainError {
     #[error("Access denied")]
     Forbidden,
 
+    #[error("Invalid turn state: {message}")]
+    InvalidTurnState { message: String },
+
+    #[error("File too large: {message}")]
+    FileTooLarge { message: String },
+
+    #[error("Unsupported file type: {message}")]
+    UnsupportedFileType { message: String },
+
     #[error("Internal error: {message}")]
     InternalError { message: String },
 }
@@ -73,6 +82,24 @@ impl DomainError {
         }
     }
 
+    pub fn invalid_turn_state(message: impl Into<String>) -> Self {
+        Self::InvalidTurnState {
+            message: message.into(),
+        }
+    }
+
+    pub fn file_too_large(message: impl Into<String>) -> Self {
+        Self::FileTooLarge {
+            message: message.into(),
+        }
+    }
+
+    pub fn unsupported_file_type(message: impl Into<String>) -> Self {
+        Self::UnsupportedFileType {
+            message: message.into(),
+        }
+    }
+
     pub fn internal(message: impl Into<String>) -> Self {
         Self::InternalError {
             message: message.into(),
@@ -54,3 +54,64 @@ pub struct NewChat {
 pub struct ChatPatch {
     pub title: Option<Option<String>>,
 }
+
+// ── Turn ──
+
+/// Client-facing lifecycle state of a turn.
+///
+/// Maps from `chat_turns.state`: `completed` → `Done`, `failed` → `Error`.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnStatusState {
+    Running,
+    Done,
+    Error,
+    Cancelled,
+}
+
+/// Authoritative turn status, as exposed by the Turn Status API.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnStatus {
+    pub request_id: Uuid,
+    pub state: TurnStatusState,
+    pub error_code: Option<String>,
+    pub assistant_message_id: Option<Uuid>,
+    pub updated_at: OffsetDateTime,
+}
+
+// ── Attachment ──
+
+/// Processing status of an attachment.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentStatus {
+    Pending,
+    Ready,
+    Failed,
+}
+
+/// Kind of an attachment.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentKind {
+    Document,
+    Image,
+}
+
+/// Attachment metadata, as exposed by the Attachments API.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub chat_id: Uuid,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub status: AttachmentStatus,
+    pub kind: AttachmentKind,
+    pub doc_summary: Option<String>,
+    pub error_code: Option<String>,
+    pub summary_updated_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
@@ -1,2 +1,63 @@
+use async_trait::async_trait;
+use modkit_db::secure::DBRunner;
+use modkit_macros::domain_model;
+use modkit_security::AccessScope;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::infra::db::entity::attachment::{
+    AttachmentKind, AttachmentStatus, Model as AttachmentModel,
+};
+
+/// Parameters for inserting a `pending` attachment.
+#[domain_model]
+pub struct InsertAttachmentParams {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub chat_id: Uuid,
+    pub uploaded_by_user_id: Uuid,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub kind: AttachmentKind,
+}
+
 /// Repository trait for attachment persistence operations.
-pub trait AttachmentRepository: Send + Sync {}
+#[async_trait]
+pub trait AttachmentRepository: Send + Sync {
+    /// INSERT a new attachment with `status = pending`.
+    async fn insert<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        params: InsertAttachmentParams,
+    ) -> Result<AttachmentModel, DomainError>;
+
+    /// SELECT a non-deleted attachment of `chat_id` by ID.
+    async fn get<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<AttachmentModel>, DomainError>;
+
+    /// SELECT the non-deleted, non-failed document attachments of `chat_id`.
+    async fn list_documents<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+    ) -> Result<Vec<AttachmentModel>, DomainError>;
+
+    /// CAS `pending → status`. Returns the number of rows updated (0 when
+    /// the attachment already left `pending`).
+    async fn cas_update_status<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        id: Uuid,
+        status: AttachmentStatus,
+        error_code: Option<String>,
+    ) -> Result<u64, DomainError>;
+}
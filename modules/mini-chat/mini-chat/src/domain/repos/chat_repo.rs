@@ -25,6 +25,16 @@ pub trait ChatRepository: Send + Sync {
         id: Uuid,
     ) -> Result<Option<Chat>, DomainError>;
 
+    /// Like [`get`](Self::get), but also locks the chat row (`FOR UPDATE`)
+    /// until the transaction of `conn` ends. Serializes checks of per-chat
+    /// limits with the writes they guard.
+    async fn lock<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        id: Uuid,
+    ) -> Result<Option<Chat>, DomainError>;
+
     /// List chats with cursor-based pagination (`updated_at DESC`).
     async fn list_page<C: DBRunner>(
         &self,
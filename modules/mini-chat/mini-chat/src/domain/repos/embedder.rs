@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use modkit_security::SecurityContext;
+
+use crate::domain::error::DomainError;
+
+/// Turns text into embedding vectors for the local document index.
+///
+/// Every vector returned by one implementation has the same dimension, so
+/// chunks indexed and queries embedded by it can be compared directly;
+/// retrieval skips stored vectors of a different dimension.
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// Embed `texts`, returning one vector per text in the same order.
+    ///
+    /// `ctx` is the caller on whose behalf remote embedders are invoked.
+    async fn embed(
+        &self,
+        ctx: &SecurityContext,
+        texts: &[String],
+    ) -> Result<Vec<Vec<f32>>, DomainError>;
+}
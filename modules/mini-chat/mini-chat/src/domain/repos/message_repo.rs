@@ -60,4 +60,35 @@ pub trait MessageRepository: Send + Sync {
         chat_id: Uuid,
         request_id: Uuid,
     ) -> Result<Vec<MessageModel>, DomainError>;
+
+    /// SELECT the user and assistant messages of a chat that are not
+    /// deleted and not folded into the thread summary, oldest first,
+    /// excluding the turns `exclude_request_ids`.
+    async fn list_uncompressed<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        exclude_request_ids: &[Uuid],
+    ) -> Result<Vec<MessageModel>, DomainError>;
+
+    /// Mark messages as folded into the thread summary.
+    /// Returns the number of messages updated.
+    async fn mark_compressed<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        message_ids: Vec<Uuid>,
+    ) -> Result<u64, DomainError>;
+
+    /// Soft-delete all messages of a turn by `(chat_id, request_id)`.
+    /// Returns the number of messages deleted.
+    async fn soft_delete_by_request_id<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        request_id: Uuid,
+    ) -> Result<u64, DomainError>;
 }
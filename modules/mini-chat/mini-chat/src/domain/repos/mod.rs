@@ -1,25 +1,31 @@
 mod attachment_repo;
 mod chat_repo;
+mod embedder;
 mod message_repo;
 mod model_pref_repo;
 mod model_resolver;
 mod quota_usage_repo;
 mod reaction_repo;
 mod thread_summary_repo;
+mod tool_executor;
 mod turn_repo;
 mod vector_store_repo;
 
-pub(crate) use attachment_repo::AttachmentRepository;
+pub(crate) use attachment_repo::{AttachmentRepository, InsertAttachmentParams};
 pub(crate) use chat_repo::ChatRepository;
+pub(crate) use embedder::Embedder;
 pub(crate) use message_repo::{
     InsertAssistantMessageParams, InsertUserMessageParams, MessageRepository,
 };
 pub(crate) use model_pref_repo::ModelPrefRepository;
 pub(crate) use model_resolver::ModelResolver;
-pub(crate) use quota_usage_repo::{IncrementReserveParams, QuotaUsageRepository, SettleParams};
+pub(crate) use quota_usage_repo::{
+    IncrementReserveParams, QuotaUsageRepository, RecordSpentParams, SettleParams,
+};
 pub(crate) use reaction_repo::ReactionRepository;
-pub(crate) use thread_summary_repo::ThreadSummaryRepository;
+pub(crate) use thread_summary_repo::{SaveSummaryParams, ThreadSummaryRepository};
+pub(crate) use tool_executor::ToolExecutor;
 pub(crate) use turn_repo::{
-    CasCompleteParams, CasTerminalParams, CreateTurnParams, TurnRepository,
+    CasCompleteParams, CasTerminalParams, CreateTurnParams, RecordContextParams, TurnRepository,
 };
-pub(crate) use vector_store_repo::VectorStoreRepository;
+pub(crate) use vector_store_repo::{InsertChunkParams, VectorStoreRepository};
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use mini_chat_sdk::ModelCatalogEntry;
 use uuid::Uuid;
 
 use crate::domain::error::DomainError;
@@ -10,4 +11,12 @@ use crate::domain::error::DomainError;
 #[async_trait]
 pub trait ModelResolver: Send + Sync {
     async fn resolve_model(&self, tenant_id: Uuid, model: &str) -> Result<String, DomainError>;
+
+    /// Look up `model` in the tenant's catalog, including its context
+    /// limits. Returns `None` if the catalog does not list it.
+    async fn catalog_entry(
+        &self,
+        tenant_id: Uuid,
+        model: &str,
+    ) -> Result<Option<ModelCatalogEntry>, DomainError>;
 }
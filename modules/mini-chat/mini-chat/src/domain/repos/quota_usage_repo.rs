@@ -35,6 +35,20 @@ pub struct SettleParams {
     pub output_tokens: Option<i64>,
 }
 
+/// Parameters for charging usage that was not reserved, e.g. of a system
+/// task.
+#[domain_model]
+pub struct RecordSpentParams {
+    pub tenant_id: Uuid,
+    pub user_id: Uuid,
+    pub period_type: PeriodType,
+    pub period_start: time::Date,
+    pub bucket: String,
+    pub credits_micro: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+}
+
 /// Repository trait for quota usage persistence operations.
 #[async_trait]
 #[allow(dead_code)]
@@ -55,6 +69,15 @@ pub trait QuotaUsageRepository: Send + Sync {
         params: SettleParams,
     ) -> Result<(), DomainError>;
 
+    /// Atomically add one call and its spend and tokens (UPSERT), without
+    /// a reserve.
+    async fn record_spent<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        params: RecordSpentParams,
+    ) -> Result<(), DomainError>;
+
     /// SELECT all `quota_usage` rows for a user across periods and buckets.
     async fn find_bucket_rows<C: DBRunner>(
         &self,
@@ -1,2 +1,42 @@
+use async_trait::async_trait;
+use modkit_db::secure::DBRunner;
+use modkit_macros::domain_model;
+use modkit_security::AccessScope;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::infra::db::entity::thread_summary::Model as ThreadSummaryModel;
+
+/// Parameters for saving the rolling summary of a chat.
+#[domain_model]
+pub struct SaveSummaryParams {
+    pub tenant_id: Uuid,
+    pub chat_id: Uuid,
+    pub summary_text: String,
+    /// Last message folded into the summary.
+    pub summarized_up_to: Uuid,
+    pub token_estimate: i32,
+}
+
 /// Repository trait for thread summary persistence operations.
-pub trait ThreadSummaryRepository: Send + Sync {}
+#[async_trait]
+pub trait ThreadSummaryRepository: Send + Sync {
+    /// SELECT the summary of a chat, if one was generated.
+    async fn find_by_chat_id<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+    ) -> Result<Option<ThreadSummaryModel>, DomainError>;
+
+    /// Save the summary of a chat, provided it still ends at
+    /// `expected_up_to` (`None` when the chat has no summary yet).
+    /// Returns `rows_affected` (0 = a concurrent update won).
+    async fn save<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        params: SaveSummaryParams,
+        expected_up_to: Option<Uuid>,
+    ) -> Result<u64, DomainError>;
+}
@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+use mini_chat_sdk::{ToolCallOutput, ToolCallRequest, ToolDefinition};
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+
+/// Offers function tools to the model and executes the calls it makes.
+///
+/// Implementations only execute; the stream service owns the calling loop,
+/// step limits and timeouts.
+#[async_trait]
+pub trait ToolExecutor: Send + Sync {
+    /// Function tools available to the tenant. Empty disables tool calling.
+    async fn list_tools(&self, tenant_id: Uuid) -> Result<Vec<ToolDefinition>, DomainError>;
+
+    /// Execute a single function call requested by the model.
+    async fn execute(&self, call: ToolCallRequest) -> Result<ToolCallOutput, DomainError>;
+}
@@ -43,6 +43,18 @@ pub struct CasTerminalParams {
     pub error_detail: Option<String>,
 }
 
+/// How the model input of a turn was assembled within the context window.
+#[domain_model]
+pub struct RecordContextParams {
+    pub turn_id: Uuid,
+    pub strategy: String,
+    pub tokens_estimate: i32,
+    pub history_messages_sent: i32,
+    pub history_messages_truncated: i32,
+    pub summary_up_to_message_id: Option<Uuid>,
+    pub summarized_messages: i32,
+}
+
 /// Repository trait for turn persistence operations.
 #[async_trait]
 #[allow(dead_code)]
@@ -90,6 +102,15 @@ pub trait TurnRepository: Send + Sync {
         params: CasCompleteParams,
     ) -> Result<u64, DomainError>;
 
+    /// Record the context-window metadata of a turn.
+    /// Returns `rows_affected`.
+    async fn record_context<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        params: RecordContextParams,
+    ) -> Result<u64, DomainError>;
+
     /// Soft-delete a turn, linking to a replacement `request_id`.
     async fn soft_delete<C: DBRunner>(
         &self,
@@ -99,7 +120,8 @@ pub trait TurnRepository: Send + Sync {
         replaced_by_request_id: Option<Uuid>,
     ) -> Result<(), DomainError>;
 
-    /// SELECT the most recent non-deleted turn for a chat.
+    /// SELECT the most recent non-deleted turn for a chat, ordered by
+    /// `(started_at, id)`.
     async fn find_latest_turn<C: DBRunner>(
         &self,
         runner: &C,
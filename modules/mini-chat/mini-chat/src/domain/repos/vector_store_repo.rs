@@ -1,2 +1,77 @@
-/// Repository trait for vector store persistence operations.
-pub trait VectorStoreRepository: Send + Sync {}
+use async_trait::async_trait;
+use modkit_db::secure::DBRunner;
+use modkit_macros::domain_model;
+use modkit_security::AccessScope;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::infra::db::entity::attachment_chunk::Model as ChunkModel;
+use crate::infra::db::entity::chat_vector_store::Model as VectorStoreModel;
+
+/// Parameters for inserting one indexed chunk.
+#[domain_model]
+pub struct InsertChunkParams {
+    pub tenant_id: Uuid,
+    pub chat_id: Uuid,
+    pub attachment_id: Uuid,
+    pub chunk_index: i32,
+    pub heading: Option<String>,
+    pub content: String,
+    pub token_estimate: i32,
+    pub embedding: Vec<f32>,
+}
+
+/// Repository trait for the per-chat vector index.
+#[async_trait]
+pub trait VectorStoreRepository: Send + Sync {
+    /// Get the chat's vector store row, creating it on first use.
+    /// UNIQUE(`tenant_id`, `chat_id`) keeps concurrent callers on one row.
+    async fn ensure_store<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        tenant_id: Uuid,
+        chat_id: Uuid,
+        provider: &str,
+    ) -> Result<VectorStoreModel, DomainError>;
+
+    /// SELECT the chat's vector store row, if any.
+    async fn find_store<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+    ) -> Result<Option<VectorStoreModel>, DomainError>;
+
+    /// Increment `file_count` after an attachment was indexed.
+    async fn increment_file_count<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        store_id: Uuid,
+    ) -> Result<(), DomainError>;
+
+    /// INSERT the chunks of one attachment.
+    async fn insert_chunks<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chunks: Vec<InsertChunkParams>,
+    ) -> Result<(), DomainError>;
+
+    /// COUNT indexed chunks of `chat_id`.
+    async fn count_chunks<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+    ) -> Result<u64, DomainError>;
+
+    /// SELECT all indexed chunks of `chat_id`.
+    async fn find_chunks<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+    ) -> Result<Vec<ChunkModel>, DomainError>;
+}
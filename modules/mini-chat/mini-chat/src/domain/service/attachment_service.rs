@@ -1,36 +1,514 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use authz_resolver_sdk::PolicyEnforcer;
+use bytes::Bytes;
+use file_parser::domain::error::DomainError as ParseError;
+use file_parser::domain::service::FileParserService;
 use modkit_macros::domain_model;
+use modkit_security::{AccessScope, SecurityContext};
+use tracing::{info, instrument, warn};
+use uuid::Uuid;
 
-use crate::domain::repos::{AttachmentRepository, ChatRepository, VectorStoreRepository};
+use crate::config::AttachmentsConfig;
+use crate::domain::error::DomainError;
+use crate::domain::models::{Attachment, AttachmentKind, AttachmentStatus};
+use crate::domain::repos::{
+    AttachmentRepository, ChatRepository, Embedder, InsertAttachmentParams, InsertChunkParams,
+    VectorStoreRepository,
+};
+use crate::infra::db::entity::attachment::{
+    AttachmentKind as DbAttachmentKind, AttachmentStatus as DbAttachmentStatus,
+    Model as AttachmentModel,
+};
 
-use super::DbProvider;
+use super::chunker::{Chunk, chunk_document};
+use super::turn_service::authorize_chat;
+use super::{DbProvider, actions};
+
+/// Provider recorded for the chat's vector store when documents are
+/// indexed locally.
+const LOCAL_VECTOR_STORE_PROVIDER: &str = "local";
+
+/// Maximum filename length in characters.
+const MAX_FILENAME_CHARS: usize = 255;
+
+/// Content type recorded when the client does not send one.
+const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
 
 /// Service handling file attachment operations.
+///
+/// Documents are uploaded as `pending`, then parsed, chunked, embedded and
+/// written to the chat's local vector index in a background task that
+/// moves the attachment to `ready` or `failed`.
 #[domain_model]
-pub struct AttachmentService<CR: ChatRepository> {
-    _db: Arc<DbProvider>,
-    _attachment_repo: Arc<dyn AttachmentRepository>,
-    _chat_repo: Arc<CR>,
-    _vector_store_repo: Arc<dyn VectorStoreRepository>,
-    _enforcer: PolicyEnforcer,
+pub struct AttachmentService<
+    AR: AttachmentRepository,
+    VR: VectorStoreRepository,
+    CR: ChatRepository,
+> {
+    db: Arc<DbProvider>,
+    attachment_repo: Arc<AR>,
+    chat_repo: Arc<CR>,
+    vector_store_repo: Arc<VR>,
+    enforcer: PolicyEnforcer,
+    parser: Arc<FileParserService>,
+    embedder: Arc<dyn Embedder>,
+    config: AttachmentsConfig,
 }
 
-impl<CR: ChatRepository> AttachmentService<CR> {
+impl<
+    AR: AttachmentRepository + 'static,
+    VR: VectorStoreRepository + 'static,
+    CR: ChatRepository + 'static,
+> AttachmentService<AR, VR, CR>
+{
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         db: Arc<DbProvider>,
-        attachment_repo: Arc<dyn AttachmentRepository>,
+        attachment_repo: Arc<AR>,
         chat_repo: Arc<CR>,
-        vector_store_repo: Arc<dyn VectorStoreRepository>,
+        vector_store_repo: Arc<VR>,
         enforcer: PolicyEnforcer,
+        parser: Arc<FileParserService>,
+        embedder: Arc<dyn Embedder>,
+        config: AttachmentsConfig,
     ) -> Self {
         Self {
-            _db: db,
-            _attachment_repo: attachment_repo,
-            _chat_repo: chat_repo,
-            _vector_store_repo: vector_store_repo,
-            _enforcer: enforcer,
+            db,
+            attachment_repo,
+            chat_repo,
+            vector_store_repo,
+            enforcer,
+            parser,
+            embedder,
+            config,
+        }
+    }
+
+    /// Upload a document to the chat.
+    ///
+    /// Size, type and per-chat limits are checked before the `pending` row
+    /// is inserted; the per-chat limits under a lock on the chat row, so
+    /// concurrent uploads cannot overshoot them. Indexing runs on the returned task; callers that do not
+    /// need to wait for it may drop the handle.
+    #[instrument(skip(self, ctx, bytes), fields(chat_id = %chat_id, size = bytes.len()))]
+    pub async fn upload(
+        &self,
+        ctx: &SecurityContext,
+        chat_id: Uuid,
+        filename: &str,
+        content_type: Option<&str>,
+        bytes: Bytes,
+    ) -> Result<(Attachment, tokio::task::JoinHandle<()>), DomainError> {
+        let filename = sanitize_filename(filename)?;
+        let content_type = content_type
+            .filter(|ct| !ct.trim().is_empty())
+            .unwrap_or(DEFAULT_CONTENT_TYPE)
+            .to_owned();
+
+        let size = bytes.len() as u64;
+        if size == 0 {
+            return Err(DomainError::validation("File is empty"));
+        }
+        if size > self.config.max_file_size_bytes() {
+            return Err(DomainError::file_too_large(format!(
+                "File size {size} exceeds the limit of {} MiB",
+                self.config.max_file_size_mb
+            )));
+        }
+        self.check_file_type(&filename, &content_type)?;
+
+        let scope = authorize_chat(
+            &self.db,
+            &self.enforcer,
+            self.chat_repo.as_ref(),
+            ctx,
+            chat_id,
+            actions::UPLOAD,
+        )
+        .await?;
+
+        let size_bytes = i64::try_from(size)
+            .map_err(|_| DomainError::file_too_large(format!("File size {size} is too large")))?;
+        let params = InsertAttachmentParams {
+            id: Uuid::new_v4(),
+            tenant_id: ctx.subject_tenant_id(),
+            chat_id,
+            uploaded_by_user_id: ctx.subject_id(),
+            filename,
+            content_type,
+            size_bytes,
+            kind: DbAttachmentKind::Document,
+        };
+
+        let attachment_repo = Arc::clone(&self.attachment_repo);
+        let chat_repo = Arc::clone(&self.chat_repo);
+        let config = self.config;
+        let scope_tx = scope.clone();
+        let model = self
+            .db
+            .transaction(|tx| {
+                Box::pin(async move {
+                    let db_err = |e: DomainError| modkit_db::DbError::Other(anyhow::anyhow!(e));
+
+                    if chat_repo
+                        .lock(tx, &scope_tx, chat_id)
+                        .await
+                        .map_err(db_err)?
+                        .is_none()
+                    {
+                        return Ok(Err(DomainError::chat_not_found(chat_id)));
+                    }
+                    let documents = attachment_repo
+                        .list_documents(tx, &scope_tx, chat_id)
+                        .await
+                        .map_err(db_err)?;
+                    if documents.len() >= config.max_documents_per_chat as usize {
+                        return Ok(Err(DomainError::validation(format!(
+                            "Chat already has the maximum of {} documents",
+                            config.max_documents_per_chat
+                        ))));
+                    }
+                    let total: i64 = documents.iter().map(|d| d.size_bytes).sum();
+                    if u64::try_from(total + size_bytes).unwrap_or(u64::MAX)
+                        > config.max_total_upload_bytes_per_chat()
+                    {
+                        return Ok(Err(DomainError::validation(format!(
+                            "Upload exceeds the per-chat limit of {} MiB",
+                            config.max_total_upload_mb_per_chat
+                        ))));
+                    }
+
+                    let model = attachment_repo
+                        .insert(tx, &scope_tx, params)
+                        .await
+                        .map_err(db_err)?;
+                    Ok(Ok(model))
+                })
+            })
+            .await
+            .map_err(DomainError::from)??;
+
+        info!(attachment_id = %model.id, "attachment uploaded");
+
+        let indexer = Indexer {
+            db: Arc::clone(&self.db),
+            attachment_repo: Arc::clone(&self.attachment_repo),
+            chat_repo: Arc::clone(&self.chat_repo),
+            vector_store_repo: Arc::clone(&self.vector_store_repo),
+            parser: Arc::clone(&self.parser),
+            embedder: Arc::clone(&self.embedder),
+            config: self.config,
+            scope,
+            ctx: ctx.clone(),
+        };
+        let attachment = to_attachment(model.clone());
+        let handle = tokio::spawn(async move { indexer.run(model, bytes).await });
+
+        Ok((attachment, handle))
+    }
+
+    /// Get an attachment of the chat by ID.
+    #[instrument(skip(self, ctx), fields(chat_id = %chat_id, attachment_id = %attachment_id))]
+    pub async fn get(
+        &self,
+        ctx: &SecurityContext,
+        chat_id: Uuid,
+        attachment_id: Uuid,
+    ) -> Result<Attachment, DomainError> {
+        let scope = authorize_chat(
+            &self.db,
+            &self.enforcer,
+            self.chat_repo.as_ref(),
+            ctx,
+            chat_id,
+            actions::READ_ATTACHMENT,
+        )
+        .await?;
+
+        let conn = self.db.conn().map_err(DomainError::from)?;
+        self.attachment_repo
+            .get(&conn, &scope, chat_id, attachment_id)
+            .await?
+            .map(to_attachment)
+            .ok_or_else(|| DomainError::not_found("Attachment", attachment_id))
+    }
+
+    /// Reject files no registered parser can read. The extension comes from
+    /// the filename, falling back to the content type.
+    fn check_file_type(&self, filename: &str, content_type: &str) -> Result<(), DomainError> {
+        let extension = Path::new(filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_owned)
+            .or_else(|| FileParserService::extension_from_content_type(content_type));
+        let Some(extension) = extension else {
+            return Err(DomainError::unsupported_file_type(format!(
+                "Cannot determine the type of '{filename}'"
+            )));
+        };
+
+        let supported = self
+            .parser
+            .info()
+            .supported_extensions
+            .values()
+            .flatten()
+            .any(|e| e.eq_ignore_ascii_case(&extension));
+        if supported {
+            Ok(())
+        } else {
+            Err(DomainError::unsupported_file_type(format!(
+                "Files of type '.{extension}' are not supported"
+            )))
+        }
+    }
+}
+
+/// Strip any client-side directory components and check the length.
+fn sanitize_filename(filename: &str) -> Result<String, DomainError> {
+    let name = filename
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .trim();
+    if name.is_empty() {
+        return Err(DomainError::validation("Filename must not be empty"));
+    }
+    if name.chars().count() > MAX_FILENAME_CHARS {
+        return Err(DomainError::validation(format!(
+            "Filename must be at most {MAX_FILENAME_CHARS} characters"
+        )));
+    }
+    Ok(name.to_owned())
+}
+
+fn to_attachment(model: AttachmentModel) -> Attachment {
+    Attachment {
+        id: model.id,
+        chat_id: model.chat_id,
+        filename: model.filename,
+        content_type: model.content_type,
+        size_bytes: model.size_bytes,
+        status: match model.status {
+            DbAttachmentStatus::Pending => AttachmentStatus::Pending,
+            DbAttachmentStatus::Ready => AttachmentStatus::Ready,
+            DbAttachmentStatus::Failed => AttachmentStatus::Failed,
+        },
+        kind: match model.attachment_kind {
+            DbAttachmentKind::Document => AttachmentKind::Document,
+            DbAttachmentKind::Image => AttachmentKind::Image,
+        },
+        doc_summary: model.doc_summary,
+        error_code: model.error_code,
+        summary_updated_at: model.summary_updated_at,
+        created_at: model.created_at,
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Background indexing
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Why indexing a document failed; the code is stored on the attachment.
+#[domain_model]
+enum IndexError {
+    /// Expected outcome for this document, e.g. a file without text.
+    Rejected(&'static str),
+    /// Unexpected failure of a dependency.
+    Failed(DomainError),
+}
+
+impl From<DomainError> for IndexError {
+    fn from(e: DomainError) -> Self {
+        Self::Failed(e)
+    }
+}
+
+/// Everything the indexing task needs, detached from the service.
+#[domain_model]
+struct Indexer<AR: AttachmentRepository, VR: VectorStoreRepository, CR: ChatRepository> {
+    db: Arc<DbProvider>,
+    attachment_repo: Arc<AR>,
+    chat_repo: Arc<CR>,
+    vector_store_repo: Arc<VR>,
+    parser: Arc<FileParserService>,
+    embedder: Arc<dyn Embedder>,
+    config: AttachmentsConfig,
+    scope: AccessScope,
+    /// The uploader, on whose behalf the document is embedded.
+    ctx: SecurityContext,
+}
+
+impl<
+    AR: AttachmentRepository + 'static,
+    VR: VectorStoreRepository + 'static,
+    CR: ChatRepository + 'static,
+> Indexer<AR, VR, CR>
+{
+    /// Index `attachment` and record the outcome on its status.
+    #[allow(clippy::cognitive_complexity)]
+    async fn run(self, attachment: AttachmentModel, bytes: Bytes) {
+        let attachment_id = attachment.id;
+        let error_code = match self.index(attachment, bytes).await {
+            Ok(chunks) => {
+                info!(%attachment_id, chunks, "attachment indexed");
+                return;
+            }
+            Err(IndexError::Rejected(code)) => {
+                info!(%attachment_id, code, "attachment rejected by indexing");
+                code
+            }
+            Err(IndexError::Failed(e)) => {
+                warn!(%attachment_id, error = %e, "attachment indexing failed");
+                "index_failed"
+            }
+        };
+        self.mark_failed(attachment_id, error_code).await;
+    }
+
+    /// Record an indexing failure; the attachment stays `pending` if this
+    /// write fails too.
+    async fn mark_failed(&self, attachment_id: Uuid, error_code: &str) {
+        let result = match self.db.conn() {
+            Ok(conn) => self
+                .attachment_repo
+                .cas_update_status(
+                    &conn,
+                    &self.scope,
+                    attachment_id,
+                    DbAttachmentStatus::Failed,
+                    Some(error_code.to_owned()),
+                )
+                .await
+                .map(|_| ()),
+            Err(e) => Err(DomainError::from(e)),
+        };
+        if let Err(e) = result {
+            warn!(%attachment_id, error = %e, "failed to mark attachment as failed");
         }
     }
+
+    /// Parse, chunk and embed the document, then write its chunks and mark
+    /// it `ready` in one transaction. Returns the number of chunks.
+    async fn index(&self, attachment: AttachmentModel, bytes: Bytes) -> Result<usize, IndexError> {
+        let document = self
+            .parser
+            .parse_bytes(
+                Some(&attachment.filename),
+                Some(&attachment.content_type),
+                bytes,
+            )
+            .await
+            .map_err(|e| match e {
+                ParseError::UnsupportedFileType { .. } | ParseError::NoParserAvailable { .. } => {
+                    IndexError::Rejected("unsupported_file_type")
+                }
+                _ => IndexError::Rejected("parse_failed"),
+            })?;
+
+        let chunks = chunk_document(
+            &document,
+            self.config.chunk_size_tokens,
+            self.config.chunk_overlap_tokens,
+        );
+        if chunks.is_empty() {
+            return Err(IndexError::Rejected("no_text_content"));
+        }
+
+        let texts: Vec<String> = chunks.iter().map(Chunk::embedding_text).collect();
+        let embeddings = self.embedder.embed(&self.ctx, &texts).await?;
+        if embeddings.len() != chunks.len() {
+            return Err(IndexError::Failed(DomainError::internal(format!(
+                "embedder returned {} vectors for {} chunks",
+                embeddings.len(),
+                chunks.len()
+            ))));
+        }
+
+        let count = chunks.len();
+        let rows: Vec<InsertChunkParams> = chunks
+            .into_iter()
+            .zip(embeddings)
+            .enumerate()
+            .map(|(index, (chunk, embedding))| InsertChunkParams {
+                tenant_id: attachment.tenant_id,
+                chat_id: attachment.chat_id,
+                attachment_id: attachment.id,
+                chunk_index: i32::try_from(index).unwrap_or(i32::MAX),
+                heading: chunk.heading,
+                content: chunk.content,
+                token_estimate: i32::try_from(chunk.token_estimate).unwrap_or(i32::MAX),
+                embedding,
+            })
+            .collect();
+
+        let attachment_repo = Arc::clone(&self.attachment_repo);
+        let vector_store_repo = Arc::clone(&self.vector_store_repo);
+        let chat_repo = Arc::clone(&self.chat_repo);
+        let scope = self.scope.clone();
+        let max_chunks = u64::from(self.config.max_chunks_per_chat);
+        let (tenant_id, chat_id, attachment_id) =
+            (attachment.tenant_id, attachment.chat_id, attachment.id);
+
+        let outcome = self
+            .db
+            .transaction(|tx| {
+                Box::pin(async move {
+                    let db_err = |e: DomainError| modkit_db::DbError::Other(anyhow::anyhow!(e));
+
+                    // Same lock as `upload`: one chunk-limit check per chat at a time.
+                    chat_repo.lock(tx, &scope, chat_id).await.map_err(db_err)?;
+                    let existing = vector_store_repo
+                        .count_chunks(tx, &scope, chat_id)
+                        .await
+                        .map_err(db_err)?;
+                    if existing + count as u64 > max_chunks {
+                        return Ok(Err(IndexError::Rejected("chunk_limit_exceeded")));
+                    }
+
+                    let store = vector_store_repo
+                        .ensure_store(tx, &scope, tenant_id, chat_id, LOCAL_VECTOR_STORE_PROVIDER)
+                        .await
+                        .map_err(db_err)?;
+                    vector_store_repo
+                        .insert_chunks(tx, &scope, rows)
+                        .await
+                        .map_err(db_err)?;
+                    vector_store_repo
+                        .increment_file_count(tx, &scope, store.id)
+                        .await
+                        .map_err(db_err)?;
+
+                    let updated = attachment_repo
+                        .cas_update_status(
+                            tx,
+                            &scope,
+                            attachment_id,
+                            DbAttachmentStatus::Ready,
+                            None,
+                        )
+                        .await
+                        .map_err(db_err)?;
+                    if updated == 0 {
+                        // Rolls back the chunks: the attachment left `pending`.
+                        return Err(db_err(DomainError::conflict(
+                            "attachment_not_pending",
+                            format!("Attachment {attachment_id} is no longer pending"),
+                        )));
+                    }
+                    Ok(Ok(()))
+                })
+            })
+            .await
+            .map_err(|e| IndexError::Failed(DomainError::from(e)))?;
+        outcome?;
+
+        Ok(count)
+    }
 }
+
+#[cfg(test)]
+#[path = "attachment_service_test.rs"]
+mod tests;
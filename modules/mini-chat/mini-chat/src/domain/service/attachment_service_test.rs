@@ -0,0 +1,340 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use modkit_security::{AccessScope, SecurityContext};
+use uuid::Uuid;
+
+use crate::config::AttachmentsConfig;
+use crate::domain::error::DomainError;
+use crate::domain::models::{Attachment, AttachmentStatus};
+use crate::domain::repos::VectorStoreRepository as _;
+use crate::infra::db::repo::attachment_repo::AttachmentRepository as OrmAttachmentRepository;
+use crate::infra::db::repo::chat_repo::ChatRepository as OrmChatRepository;
+use crate::infra::db::repo::vector_store_repo::VectorStoreRepository as OrmVectorStoreRepository;
+use crate::infra::llm::CitationSource;
+
+use super::AttachmentService;
+use crate::domain::service::DbProvider;
+use crate::domain::service::test_helpers::{
+    hashing_embedder, inmem_db, mock_db_provider, mock_enforcer, orm_chat_repo, retrieval_service,
+    seed_chat, test_security_ctx, text_parser,
+};
+
+// ── Test Helpers ──
+
+type Service =
+    AttachmentService<OrmAttachmentRepository, OrmVectorStoreRepository, OrmChatRepository>;
+
+fn build_service(db: Arc<DbProvider>, config: AttachmentsConfig) -> Service {
+    AttachmentService::new(
+        db,
+        Arc::new(OrmAttachmentRepository),
+        orm_chat_repo(),
+        Arc::new(OrmVectorStoreRepository),
+        mock_enforcer(),
+        text_parser(),
+        hashing_embedder(),
+        config,
+    )
+}
+
+async fn setup(config: AttachmentsConfig) -> (Arc<DbProvider>, Service, SecurityContext, Uuid) {
+    let db = mock_db_provider(inmem_db().await);
+    let ctx = test_security_ctx(Uuid::new_v4());
+    let chat_id = seed_chat(&db, &ctx).await;
+    let svc = build_service(Arc::clone(&db), config);
+    (db, svc, ctx, chat_id)
+}
+
+/// Upload `content` as `filename` and wait for indexing to finish.
+async fn upload_indexed(
+    svc: &Service,
+    ctx: &SecurityContext,
+    chat_id: Uuid,
+    filename: &str,
+    content: &str,
+) -> Attachment {
+    let (attachment, indexing) = svc
+        .upload(
+            ctx,
+            chat_id,
+            filename,
+            None,
+            Bytes::from(content.to_owned()),
+        )
+        .await
+        .expect("upload should succeed");
+    indexing.await.expect("indexing task should not panic");
+    svc.get(ctx, chat_id, attachment.id).await.unwrap()
+}
+
+const HANDBOOK: &str = "<html><body><h1>Vacation policy</h1><p>Employees accrue twenty \
+vacation days per year. Unused vacation days expire at the end of March.</p>\
+<h1>Expense policy</h1><p>Travel expenses require a receipt and manager approval within \
+thirty days.</p></body></html>";
+
+// ── Upload ──
+
+#[tokio::test]
+async fn upload_indexes_document_and_becomes_ready() {
+    let (db, svc, ctx, chat_id) = setup(AttachmentsConfig::default()).await;
+
+    let (pending, indexing) = svc
+        .upload(
+            &ctx,
+            chat_id,
+            "handbook.html",
+            Some("text/html"),
+            Bytes::from(HANDBOOK),
+        )
+        .await
+        .unwrap();
+    assert_eq!(pending.status, AttachmentStatus::Pending);
+    assert_eq!(pending.filename, "handbook.html");
+    assert_eq!(pending.size_bytes, i64::try_from(HANDBOOK.len()).unwrap());
+
+    indexing.await.unwrap();
+    let ready = svc.get(&ctx, chat_id, pending.id).await.unwrap();
+    assert_eq!(ready.status, AttachmentStatus::Ready);
+    assert_eq!(ready.error_code, None);
+
+    let conn = db.conn().unwrap();
+    let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
+    let store = OrmVectorStoreRepository
+        .find_store(&conn, &scope, chat_id)
+        .await
+        .unwrap()
+        .expect("vector store should be created");
+    assert_eq!(store.file_count, 1);
+    assert_eq!(store.provider, "local");
+    let chunks = OrmVectorStoreRepository
+        .find_chunks(&conn, &scope, chat_id)
+        .await
+        .unwrap();
+    assert!(!chunks.is_empty());
+    assert!(chunks.iter().all(|c| c.attachment_id == pending.id));
+}
+
+#[tokio::test]
+async fn upload_strips_client_directories_from_filename() {
+    let (_db, svc, ctx, chat_id) = setup(AttachmentsConfig::default()).await;
+
+    let attachment = upload_indexed(
+        &svc,
+        &ctx,
+        chat_id,
+        "C:\\Users\\me\\notes.txt",
+        "Some notes.",
+    )
+    .await;
+    assert_eq!(attachment.filename, "notes.txt");
+}
+
+#[tokio::test]
+async fn upload_rejects_unsupported_type() {
+    let (_db, svc, ctx, chat_id) = setup(AttachmentsConfig::default()).await;
+
+    let result = svc
+        .upload(
+            &ctx,
+            chat_id,
+            "photo.png",
+            Some("image/png"),
+            Bytes::from_static(b"\x89PNG"),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(DomainError::UnsupportedFileType { .. })
+    ));
+
+    let result = svc
+        .upload(&ctx, chat_id, "blob", None, Bytes::from_static(b"data"))
+        .await;
+    assert!(matches!(
+        result,
+        Err(DomainError::UnsupportedFileType { .. })
+    ));
+}
+
+#[tokio::test]
+async fn upload_rejects_file_over_size_limit() {
+    let config = AttachmentsConfig {
+        max_file_size_mb: 1,
+        ..AttachmentsConfig::default()
+    };
+    let (_db, svc, ctx, chat_id) = setup(config).await;
+
+    let result = svc
+        .upload(
+            &ctx,
+            chat_id,
+            "big.txt",
+            None,
+            Bytes::from(vec![b'a'; 1024 * 1024 + 1]),
+        )
+        .await;
+    assert!(matches!(result, Err(DomainError::FileTooLarge { .. })));
+
+    let result = svc
+        .upload(&ctx, chat_id, "empty.txt", None, Bytes::new())
+        .await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
+
+#[tokio::test]
+async fn upload_enforces_document_count_per_chat() {
+    let config = AttachmentsConfig {
+        max_documents_per_chat: 1,
+        ..AttachmentsConfig::default()
+    };
+    let (_db, svc, ctx, chat_id) = setup(config).await;
+
+    upload_indexed(&svc, &ctx, chat_id, "a.txt", "First document.").await;
+    let result = svc
+        .upload(&ctx, chat_id, "b.txt", None, Bytes::from_static(b"Second."))
+        .await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
+
+#[tokio::test]
+async fn upload_to_other_users_chat_is_not_found() {
+    let (_db, svc, ctx, chat_id) = setup(AttachmentsConfig::default()).await;
+    let other = test_security_ctx(ctx.subject_tenant_id());
+
+    let result = svc
+        .upload(&other, chat_id, "a.txt", None, Bytes::from_static(b"text"))
+        .await;
+    assert!(matches!(result, Err(DomainError::ChatNotFound { .. })));
+}
+
+#[tokio::test]
+async fn document_without_text_fails_indexing() {
+    let (_db, svc, ctx, chat_id) = setup(AttachmentsConfig::default()).await;
+
+    let attachment = upload_indexed(&svc, &ctx, chat_id, "blank.txt", " \n\n \n").await;
+    assert_eq!(attachment.status, AttachmentStatus::Failed);
+    assert_eq!(attachment.error_code.as_deref(), Some("no_text_content"));
+}
+
+#[tokio::test]
+async fn chunk_limit_fails_indexing() {
+    let config = AttachmentsConfig {
+        max_chunks_per_chat: 100,
+        chunk_size_tokens: 64,
+        chunk_overlap_tokens: 0,
+        ..AttachmentsConfig::default()
+    };
+    let (_db, svc, ctx, chat_id) = setup(config).await;
+    let long = (0..6000)
+        .map(|i| format!("word{i}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let attachment = upload_indexed(&svc, &ctx, chat_id, "long.txt", &long).await;
+    assert_eq!(attachment.status, AttachmentStatus::Failed);
+    assert_eq!(
+        attachment.error_code.as_deref(),
+        Some("chunk_limit_exceeded")
+    );
+}
+
+#[tokio::test]
+async fn get_unknown_attachment_is_not_found() {
+    let (_db, svc, ctx, chat_id) = setup(AttachmentsConfig::default()).await;
+
+    let result = svc.get(&ctx, chat_id, Uuid::new_v4()).await;
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+}
+
+// ── Retrieval ──
+
+#[tokio::test]
+async fn retrieval_ranks_matching_section_first() {
+    let (db, svc, ctx, chat_id) = setup(AttachmentsConfig::default()).await;
+    let handbook = upload_indexed(&svc, &ctx, chat_id, "handbook.html", HANDBOOK).await;
+    upload_indexed(
+        &svc,
+        &ctx,
+        chat_id,
+        "menu.txt",
+        "The cafeteria serves soup and salad on weekdays.",
+    )
+    .await;
+
+    let retrieval = retrieval_service(&db, AttachmentsConfig::default());
+    let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
+    let context = retrieval
+        .retrieve(
+            &ctx,
+            &scope,
+            chat_id,
+            "How many vacation days do employees get?",
+        )
+        .await
+        .unwrap()
+        .expect("relevant excerpts should be found");
+
+    let top = &context.citations[0];
+    assert!(matches!(top.source, CitationSource::File));
+    assert_eq!(top.title, "handbook.html");
+    assert_eq!(
+        top.attachment_id.as_deref(),
+        Some(handbook.id.to_string().as_str())
+    );
+    assert!(top.snippet.contains("twenty vacation days"));
+    assert!(
+        context
+            .instructions
+            .contains("[1] handbook.html - Vacation policy")
+    );
+}
+
+#[tokio::test]
+async fn retrieval_without_documents_returns_none() {
+    let (db, _svc, ctx, chat_id) = setup(AttachmentsConfig::default()).await;
+
+    let retrieval = retrieval_service(&db, AttachmentsConfig::default());
+    let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
+    let context = retrieval
+        .retrieve(&ctx, &scope, chat_id, "anything")
+        .await
+        .unwrap();
+    assert!(context.is_none());
+}
+
+#[tokio::test]
+async fn retrieval_respects_k_and_token_budget() {
+    let config = AttachmentsConfig {
+        chunk_size_tokens: 64,
+        chunk_overlap_tokens: 0,
+        retrieval_k: 2,
+        ..AttachmentsConfig::default()
+    };
+    let (db, svc, ctx, chat_id) = setup(config).await;
+    let text = (0..200)
+        .map(|i| format!("budget item {i}."))
+        .collect::<Vec<_>>()
+        .join(" ");
+    upload_indexed(&svc, &ctx, chat_id, "items.txt", &text).await;
+
+    let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
+    let context = retrieval_service(&db, config)
+        .retrieve(&ctx, &scope, chat_id, "budget item")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(context.citations.len(), 2);
+
+    let tight = AttachmentsConfig {
+        retrieval_k: 20,
+        max_retrieved_tokens_per_turn: 256,
+        ..config
+    };
+    let context = retrieval_service(&db, tight)
+        .retrieve(&ctx, &scope, chat_id, "budget item")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(context.citations.len() <= 4);
+}
@@ -0,0 +1,318 @@
+//! Splits a parsed document into overlapping text chunks for indexing.
+//!
+//! Blocks are flattened to plain text and grouped by the heading they
+//! appear under; each group is then cut into windows of roughly
+//! `chunk_size_tokens`, repeating about `overlap_tokens` between windows.
+//! Tokens are estimated as one per four characters, which is what the
+//! quota preflight uses as well.
+
+use file_parser::domain::ir::{Inline, ParsedBlock, ParsedDocument};
+use modkit_macros::domain_model;
+
+/// Characters per estimated token.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// One chunk of document text, ready to be embedded.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    /// Heading path the chunk appears under, e.g. `"Setup > Install"`.
+    pub heading: Option<String>,
+    pub content: String,
+    pub token_estimate: u32,
+}
+
+impl Chunk {
+    /// Text fed to the embedder: the heading path gives short chunks the
+    /// context of the section they belong to.
+    pub fn embedding_text(&self) -> String {
+        match &self.heading {
+            Some(heading) => format!("{heading}\n{}", self.content),
+            None => self.content.clone(),
+        }
+    }
+}
+
+/// Estimated token count of `text`.
+pub fn estimate_tokens(text: &str) -> u32 {
+    u32::try_from(text.chars().count().div_ceil(CHARS_PER_TOKEN)).unwrap_or(u32::MAX)
+}
+
+/// Chunk `doc` into windows of about `chunk_size_tokens`.
+pub fn chunk_document(
+    doc: &ParsedDocument,
+    chunk_size_tokens: u32,
+    overlap_tokens: u32,
+) -> Vec<Chunk> {
+    let mut sections = Vec::new();
+    let mut headings: Vec<(u8, String)> = Vec::new();
+    let mut body: Vec<String> = Vec::new();
+
+    for block in &doc.blocks {
+        if let ParsedBlock::Heading { level, inlines } = block {
+            flush_section(&mut sections, &headings, &mut body);
+            headings.retain(|(l, _)| l < level);
+            let text = inline_text(inlines);
+            if !text.trim().is_empty() {
+                headings.push((*level, text.trim().to_owned()));
+            }
+        } else {
+            let text = block_text(block);
+            if !text.trim().is_empty() {
+                body.push(text);
+            }
+        }
+    }
+    flush_section(&mut sections, &headings, &mut body);
+
+    let max_chars = to_chars(chunk_size_tokens).max(1);
+    let overlap_chars = to_chars(overlap_tokens);
+    sections
+        .into_iter()
+        .flat_map(|(heading, text)| {
+            split_windows(&text, max_chars, overlap_chars)
+                .into_iter()
+                .map(move |content| {
+                    let token_estimate = estimate_tokens(&content);
+                    Chunk {
+                        heading: heading.clone(),
+                        content,
+                        token_estimate,
+                    }
+                })
+        })
+        .collect()
+}
+
+fn to_chars(tokens: u32) -> usize {
+    usize::try_from(tokens)
+        .unwrap_or(usize::MAX)
+        .saturating_mul(CHARS_PER_TOKEN)
+}
+
+fn flush_section(
+    sections: &mut Vec<(Option<String>, String)>,
+    headings: &[(u8, String)],
+    body: &mut Vec<String>,
+) {
+    if body.is_empty() {
+        return;
+    }
+    let heading = (!headings.is_empty()).then(|| {
+        headings
+            .iter()
+            .map(|(_, h)| h.as_str())
+            .collect::<Vec<_>>()
+            .join(" > ")
+    });
+    sections.push((heading, body.join("\n\n")));
+    body.clear();
+}
+
+/// Cut `text` at whitespace into windows of at most `max_chars` characters
+/// (a single longer word becomes its own window). Each window after the
+/// first starts with up to `overlap_chars` characters of its predecessor.
+fn split_windows(text: &str, max_chars: usize, overlap_chars: usize) -> Vec<String> {
+    let pieces: Vec<&str> = text.split_inclusive(char::is_whitespace).collect();
+    let lengths: Vec<usize> = pieces.iter().map(|p| p.chars().count()).collect();
+
+    let mut windows = Vec::new();
+    let mut start = 0;
+    while start < pieces.len() {
+        let mut end = start;
+        let mut len = 0;
+        while end < pieces.len() && (end == start || len + lengths[end] <= max_chars) {
+            len += lengths[end];
+            end += 1;
+        }
+
+        let window = pieces[start..end].concat();
+        let window = window.trim();
+        if !window.is_empty() {
+            windows.push(window.to_owned());
+        }
+        if end == pieces.len() {
+            break;
+        }
+
+        let mut next = end;
+        let mut overlap = 0;
+        while next > start + 1 && overlap + lengths[next - 1] <= overlap_chars {
+            next -= 1;
+            overlap += lengths[next];
+        }
+        start = next;
+    }
+    windows
+}
+
+fn inline_text(inlines: &[Inline]) -> String {
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            Inline::Text { text, .. } | Inline::Code { text, .. } | Inline::Link { text, .. } => {
+                text.as_str()
+            }
+        })
+        .collect()
+}
+
+fn blocks_text(blocks: &[ParsedBlock], separator: &str) -> String {
+    blocks
+        .iter()
+        .map(block_text)
+        .filter(|t| !t.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn block_text(block: &ParsedBlock) -> String {
+    match block {
+        ParsedBlock::Heading { inlines, .. } | ParsedBlock::Paragraph { inlines } => {
+            inline_text(inlines)
+        }
+        ParsedBlock::ListItem { blocks, .. } => format!("- {}", blocks_text(blocks, " ")),
+        ParsedBlock::CodeBlock { code, .. } => code.clone(),
+        ParsedBlock::Table(table) => table
+            .rows
+            .iter()
+            .map(|row| {
+                row.cells
+                    .iter()
+                    .map(|cell| blocks_text(&cell.blocks, " "))
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        ParsedBlock::Quote { blocks } => blocks_text(blocks, "\n"),
+        ParsedBlock::Image { alt, .. } => alt.clone().unwrap_or_default(),
+        ParsedBlock::HorizontalRule | ParsedBlock::PageBreak => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use file_parser::domain::ir::{ParsedMetadata, ParsedSource, TableBlock, TableCell, TableRow};
+
+    fn doc(blocks: Vec<ParsedBlock>) -> ParsedDocument {
+        ParsedDocument {
+            id: None,
+            title: None,
+            language: None,
+            meta: ParsedMetadata {
+                source: ParsedSource::Uploaded {
+                    original_name: "test.md".to_owned(),
+                },
+                original_filename: None,
+                content_type: None,
+                created_at: None,
+                modified_at: None,
+                is_stub: false,
+            },
+            blocks,
+        }
+    }
+
+    fn heading(level: u8, text: &str) -> ParsedBlock {
+        ParsedBlock::Heading {
+            level,
+            inlines: vec![Inline::plain(text)],
+        }
+    }
+
+    fn paragraph(text: &str) -> ParsedBlock {
+        ParsedBlock::Paragraph {
+            inlines: vec![Inline::plain(text)],
+        }
+    }
+
+    #[test]
+    fn chunks_carry_heading_path() {
+        let chunks = chunk_document(
+            &doc(vec![
+                paragraph("Preamble."),
+                heading(1, "Guide"),
+                heading(2, "Install"),
+                paragraph("Run the installer."),
+                heading(2, "Usage"),
+                paragraph("Start the app."),
+            ]),
+            400,
+            50,
+        );
+
+        let headings: Vec<_> = chunks.iter().map(|c| c.heading.as_deref()).collect();
+        assert_eq!(
+            headings,
+            vec![None, Some("Guide > Install"), Some("Guide > Usage")]
+        );
+        assert_eq!(chunks[1].content, "Run the installer.");
+        assert_eq!(chunks[2].embedding_text(), "Guide > Usage\nStart the app.");
+    }
+
+    #[test]
+    fn long_sections_are_split_with_overlap() {
+        let words: Vec<String> = (0..200).map(|i| format!("word{i:03}")).collect();
+        let chunks = chunk_document(&doc(vec![paragraph(&words.join(" "))]), 64, 8);
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.content.chars().count() <= 64 * 4);
+            assert!(chunk.token_estimate <= 64);
+        }
+        // The last words of a chunk reappear at the start of the next one.
+        let first_tail = chunks[0].content.split(' ').next_back().unwrap();
+        assert!(
+            chunks[1]
+                .content
+                .split(' ')
+                .take(4)
+                .any(|w| w == first_tail)
+        );
+        assert!(chunks.last().unwrap().content.ends_with("word199"));
+    }
+
+    #[test]
+    fn tables_and_lists_are_flattened() {
+        let cell = |t: &str| TableCell {
+            blocks: vec![paragraph(t)],
+        };
+        let chunks = chunk_document(
+            &doc(vec![
+                ParsedBlock::Table(TableBlock {
+                    rows: vec![
+                        TableRow {
+                            is_header: true,
+                            cells: vec![cell("Region"), cell("Revenue")],
+                        },
+                        TableRow {
+                            is_header: false,
+                            cells: vec![cell("EMEA"), cell("42")],
+                        },
+                    ],
+                }),
+                ParsedBlock::ListItem {
+                    level: 0,
+                    ordered: false,
+                    blocks: vec![paragraph("item one")],
+                },
+                ParsedBlock::HorizontalRule,
+            ]),
+            400,
+            50,
+        );
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(
+            chunks[0].content,
+            "Region | Revenue\nEMEA | 42\n\n- item one"
+        );
+    }
+
+    #[test]
+    fn empty_document_has_no_chunks() {
+        assert!(chunk_document(&doc(vec![ParsedBlock::PageBreak]), 400, 50).is_empty());
+    }
+}
@@ -0,0 +1,533 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use modkit_macros::domain_model;
+use modkit_security::{AccessScope, SecurityContext};
+use tracing::{info, instrument, warn};
+use uuid::Uuid;
+
+use crate::config::{ContextConfig, ContextStrategy};
+use crate::domain::error::DomainError;
+use crate::domain::repos::{
+    MessageRepository, ModelResolver, QuotaUsageRepository, SaveSummaryParams,
+    ThreadSummaryRepository,
+};
+use crate::infra::db::entity::message::{MessageRole, Model as MessageModel};
+use crate::infra::db::entity::thread_summary::Model as ThreadSummaryModel;
+use crate::infra::llm::request::{Feature, RequestMetadata, RequestType};
+use crate::infra::llm::{LlmMessage, LlmProvider, LlmRequestBuilder};
+
+use super::token_counter::TokenCounter;
+use super::{DbProvider, QuotaService, SYSTEM_SUBJECT_ID};
+
+/// Most recent messages that are never folded into the summary, so that
+/// retrying, editing or deleting the latest turn cannot make it stale.
+const MIN_RECENT_MESSAGES: usize = 2;
+
+/// Preamble of the system instructions carrying the thread summary.
+const SUMMARY_PREAMBLE: &str = "Summary of the earlier part of this conversation:";
+
+/// System instructions of the summarization request.
+const SUMMARY_INSTRUCTIONS: &str = "You maintain a running summary of a conversation between \
+a user and an assistant. Merge the new messages into the current summary. Keep facts, \
+decisions, names, numbers, open questions and references to documents; drop small talk. \
+Write in the language of the conversation. Reply with the updated summary only.";
+
+/// What the model receives for a turn, fit into its context window.
+#[domain_model]
+#[derive(Debug)]
+pub struct ContextPlan {
+    /// Earlier messages that fit, oldest first, then the user message.
+    pub messages: Vec<LlmMessage>,
+    /// System instructions carrying the thread summary, if there is one.
+    pub summary_instructions: Option<String>,
+    pub metadata: ContextMetadata,
+}
+
+impl ContextPlan {
+    /// Plan with the user message only, used when history cannot be loaded.
+    pub fn without_history(model: &str, content: &str, strategy: ContextStrategy) -> Self {
+        Self {
+            messages: vec![LlmMessage::user(content)],
+            summary_instructions: None,
+            metadata: ContextMetadata {
+                strategy,
+                tokens_estimate: TokenCounter::for_model(model).count_message(content),
+                history_messages_sent: 0,
+                history_messages_truncated: 0,
+                summary_up_to: None,
+                summarized_messages: 0,
+            },
+        }
+    }
+}
+
+/// How a [`ContextPlan`] was assembled; recorded on the turn.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextMetadata {
+    pub strategy: ContextStrategy,
+    /// Estimated input tokens of the user message, history and summary.
+    pub tokens_estimate: u32,
+    pub history_messages_sent: u32,
+    /// Earlier messages left out because they did not fit.
+    pub history_messages_truncated: u32,
+    /// Last message covered by the summary sent with the turn.
+    pub summary_up_to: Option<Uuid>,
+    /// Messages the turn folded into the summary (`summarize`) or handed
+    /// to the background summary update (`hybrid`).
+    pub summarized_messages: u32,
+}
+
+/// A thread summary as used in context assembly.
+#[domain_model]
+#[derive(Debug, Clone)]
+struct Summary {
+    text: String,
+    up_to: Uuid,
+}
+
+impl From<ThreadSummaryModel> for Summary {
+    fn from(model: ThreadSummaryModel) -> Self {
+        Self {
+            text: model.summary_text,
+            up_to: model.summarized_up_to,
+        }
+    }
+}
+
+/// Service fitting chat history into the context window of the turn's
+/// model and maintaining the rolling thread summary.
+///
+/// History that outgrows the window is truncated oldest first; depending
+/// on the strategy, the oldest messages are also folded into the summary,
+/// which replaces them in later turns.
+#[domain_model]
+pub struct ContextService<
+    MR: MessageRepository,
+    SR: ThreadSummaryRepository,
+    QR: QuotaUsageRepository,
+> {
+    db: Arc<DbProvider>,
+    message_repo: Arc<MR>,
+    summary_repo: Arc<SR>,
+    quota: Arc<QuotaService<QR>>,
+    model_resolver: Arc<dyn ModelResolver>,
+    llm: Arc<dyn LlmProvider>,
+    config: ContextConfig,
+    /// Chats with a background summary update in flight.
+    summarizing: Arc<Mutex<HashSet<Uuid>>>,
+}
+
+impl<
+    MR: MessageRepository + 'static,
+    SR: ThreadSummaryRepository + 'static,
+    QR: QuotaUsageRepository + 'static,
+> ContextService<MR, SR, QR>
+{
+    pub(crate) fn new(
+        db: Arc<DbProvider>,
+        message_repo: Arc<MR>,
+        summary_repo: Arc<SR>,
+        quota: Arc<QuotaService<QR>>,
+        model_resolver: Arc<dyn ModelResolver>,
+        llm: Arc<dyn LlmProvider>,
+        config: ContextConfig,
+    ) -> Self {
+        Self {
+            db,
+            message_repo,
+            summary_repo,
+            quota,
+            model_resolver,
+            llm,
+            config,
+            summarizing: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    pub(crate) fn strategy(&self) -> ContextStrategy {
+        self.config.strategy
+    }
+
+    /// Assemble the model input of a new turn: the thread summary, the
+    /// most recent earlier messages that fit, and `content`. Messages of
+    /// `exclude_request_ids` (the new turn and any turn it replaces) are
+    /// not history.
+    ///
+    /// `instructions` (e.g. retrieved documents) and `max_output_tokens`
+    /// are reserved before history is added.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, fields(chat_id = %chat_id))]
+    pub(crate) async fn plan(
+        &self,
+        ctx: &SecurityContext,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        exclude_request_ids: &[Uuid],
+        model: &str,
+        content: &str,
+        instructions: Option<&str>,
+        max_output_tokens: u32,
+    ) -> Result<ContextPlan, DomainError> {
+        let counter = TokenCounter::for_model(model);
+        let input_budget = self
+            .input_budget(ctx.subject_tenant_id(), model, max_output_tokens)
+            .await;
+        let fixed_tokens = counter
+            .count_message(content)
+            .saturating_add(instructions.map_or(0, |i| counter.count(i)));
+        let history_budget = input_budget.saturating_sub(fixed_tokens);
+
+        let conn = self.db.conn().map_err(DomainError::from)?;
+        let mut summary = self
+            .summary_repo
+            .find_by_chat_id(&conn, scope, chat_id)
+            .await?
+            .map(Summary::from);
+        let mut history = self
+            .message_repo
+            .list_uncompressed(&conn, scope, chat_id, exclude_request_ids)
+            .await?;
+
+        let sizes: Vec<u32> = history
+            .iter()
+            .map(|m| counter.count_message(&m.content))
+            .collect();
+        let fold = match self.config.strategy {
+            ContextStrategy::TruncateOldest => 0,
+            ContextStrategy::Summarize | ContextStrategy::Hybrid => fold_count(
+                &sizes,
+                summary_tokens(counter, summary.as_ref()),
+                history_budget,
+                self.config,
+            ),
+        };
+
+        let mut summarized_messages = 0;
+        if fold > 0 {
+            let batch = history[..fold].to_vec();
+            if self.config.strategy == ContextStrategy::Summarize {
+                match self
+                    .summarizer()
+                    .fold(
+                        ctx.subject_tenant_id(),
+                        scope,
+                        chat_id,
+                        model,
+                        summary.clone(),
+                        batch,
+                    )
+                    .await
+                {
+                    Ok(Some(updated)) => {
+                        history.drain(..fold);
+                        summary = Some(updated);
+                        summarized_messages = fold;
+                    }
+                    Ok(None) => {}
+                    Err(e) => warn!(error = %e, %chat_id, "thread summary failed, truncating"),
+                }
+            } else if self.spawn_fold(ctx, scope, chat_id, model, summary.clone(), batch) {
+                summarized_messages = fold;
+            }
+        }
+
+        let sizes = &sizes[sizes.len() - history.len()..];
+        let summary_tokens = summary_tokens(counter, summary.as_ref());
+        let kept = fit_newest(sizes, history_budget.saturating_sub(summary_tokens));
+        let truncated = history.len() - kept;
+        let history_tokens: u32 = sizes[truncated..].iter().sum();
+
+        let mut messages: Vec<LlmMessage> = history[truncated..]
+            .iter()
+            .map(|m| match m.role {
+                MessageRole::User => LlmMessage::user(&m.content),
+                MessageRole::Assistant | MessageRole::System => LlmMessage::assistant(&m.content),
+            })
+            .collect();
+        messages.push(LlmMessage::user(content));
+
+        if truncated > 0 {
+            info!(%chat_id, truncated, kept, "history truncated to fit the context window");
+        }
+
+        Ok(ContextPlan {
+            messages,
+            summary_instructions: summary
+                .as_ref()
+                .map(|s| format!("{SUMMARY_PREAMBLE}\n{}", s.text)),
+            metadata: ContextMetadata {
+                strategy: self.config.strategy,
+                tokens_estimate: counter
+                    .count_message(content)
+                    .saturating_add(summary_tokens)
+                    .saturating_add(history_tokens),
+                history_messages_sent: u32::try_from(kept).unwrap_or(u32::MAX),
+                history_messages_truncated: u32::try_from(truncated).unwrap_or(u32::MAX),
+                summary_up_to: summary.map(|s| s.up_to),
+                summarized_messages: u32::try_from(summarized_messages).unwrap_or(u32::MAX),
+            },
+        })
+    }
+
+    /// Input tokens available for a request to `model`: its context window
+    /// less the output reserve and the safety margin. Models missing from
+    /// the catalog use the configured default window.
+    async fn input_budget(&self, tenant_id: Uuid, model: &str, max_output_tokens: u32) -> u32 {
+        let (window, output) = match self.model_resolver.catalog_entry(tenant_id, model).await {
+            Ok(Some(entry)) => (
+                entry.context_window_tokens,
+                max_output_tokens.min(entry.max_output_tokens),
+            ),
+            Ok(None) => (self.config.default_context_window_tokens, max_output_tokens),
+            Err(e) => {
+                warn!(error = %e, model, "model catalog unavailable, using default context window");
+                (self.config.default_context_window_tokens, max_output_tokens)
+            }
+        };
+        window
+            .saturating_sub(output)
+            .saturating_sub(percent_of(window, self.config.safety_margin_percent))
+    }
+
+    /// Fold `batch` into the summary in the background. Returns `false` if
+    /// an update for the chat is already running.
+    fn spawn_fold(
+        &self,
+        ctx: &SecurityContext,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        model: &str,
+        previous: Option<Summary>,
+        batch: Vec<MessageModel>,
+    ) -> bool {
+        if !self
+            .summarizing
+            .lock()
+            .is_ok_and(|mut chats| chats.insert(chat_id))
+        {
+            return false;
+        }
+
+        let summarizer = self.summarizer();
+        let summarizing = Arc::clone(&self.summarizing);
+        let (tenant_id, scope, model) = (ctx.subject_tenant_id(), scope.clone(), model.to_owned());
+        tokio::spawn(async move {
+            let count = batch.len();
+            match summarizer
+                .fold(tenant_id, &scope, chat_id, &model, previous, batch)
+                .await
+            {
+                Ok(Some(_)) => info!(%chat_id, count, "thread summary updated"),
+                Ok(None) => info!(%chat_id, "thread summary changed concurrently, update dropped"),
+                Err(e) => warn!(error = %e, %chat_id, "thread summary update failed"),
+            }
+            if let Ok(mut chats) = summarizing.lock() {
+                chats.remove(&chat_id);
+            }
+        });
+        true
+    }
+
+    fn summarizer(&self) -> Summarizer<MR, SR, QR> {
+        Summarizer {
+            db: Arc::clone(&self.db),
+            message_repo: Arc::clone(&self.message_repo),
+            summary_repo: Arc::clone(&self.summary_repo),
+            quota: Arc::clone(&self.quota),
+            llm: Arc::clone(&self.llm),
+            max_summary_tokens: self.config.max_summary_tokens,
+        }
+    }
+}
+
+/// Everything a summary update needs, detached from the service.
+///
+/// A summary update is a system task: it runs as [`SYSTEM_SUBJECT_ID`]
+/// with `requester_type=system`, and its usage is charged to the tenant
+/// operational bucket rather than to the user whose turn triggered it.
+#[domain_model]
+struct Summarizer<MR: MessageRepository, SR: ThreadSummaryRepository, QR: QuotaUsageRepository> {
+    db: Arc<DbProvider>,
+    message_repo: Arc<MR>,
+    summary_repo: Arc<SR>,
+    quota: Arc<QuotaService<QR>>,
+    llm: Arc<dyn LlmProvider>,
+    max_summary_tokens: u32,
+}
+
+impl<
+    MR: MessageRepository + 'static,
+    SR: ThreadSummaryRepository + 'static,
+    QR: QuotaUsageRepository + 'static,
+> Summarizer<MR, SR, QR>
+{
+    /// Merge `batch` into `previous` with the model and save the result,
+    /// marking the batch as compressed and charging the usage in the same
+    /// transaction.
+    ///
+    /// Returns `None` if the summary changed since `previous` was read; the
+    /// new text is then dropped and the batch stays uncompressed, but the
+    /// usage is still charged.
+    async fn fold(
+        &self,
+        tenant_id: Uuid,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        model: &str,
+        previous: Option<Summary>,
+        batch: Vec<MessageModel>,
+    ) -> Result<Option<Summary>, DomainError> {
+        let Some(up_to) = batch.last().map(|m| m.id) else {
+            return Ok(previous);
+        };
+        let ctx = SecurityContext::builder()
+            .subject_id(SYSTEM_SUBJECT_ID)
+            .subject_type("system")
+            .subject_tenant_id(tenant_id)
+            .build()
+            .map_err(|e| DomainError::internal(e.to_string()))?;
+
+        let mut transcript = vec![
+            format!(
+                "Current summary:\n{}",
+                previous.as_ref().map_or("(none)", |s| s.text.as_str())
+            ),
+            "New messages:".to_owned(),
+        ];
+        transcript.extend(batch.iter().map(|m| {
+            let speaker = match m.role {
+                MessageRole::User => "User",
+                MessageRole::Assistant | MessageRole::System => "Assistant",
+            };
+            format!("{speaker}: {}", m.content)
+        }));
+
+        let request = LlmRequestBuilder::new(model)
+            .system_instructions(SUMMARY_INSTRUCTIONS)
+            .message(LlmMessage::user(transcript.join("\n\n")))
+            .max_output_tokens(u64::from(self.max_summary_tokens))
+            .metadata(RequestMetadata {
+                tenant_id: tenant_id.to_string(),
+                // No user requested the summary.
+                user_id: String::new(),
+                chat_id: chat_id.to_string(),
+                request_type: RequestType::Summary,
+                feature: Feature::None,
+            })
+            .build_non_streaming();
+        let response = self
+            .llm
+            .complete(ctx, request)
+            .await
+            .map_err(|e| DomainError::internal(format!("summary request failed: {e}")))?;
+        let usage = response.usage;
+        let text = response.content.trim().to_owned();
+        let text_is_empty = text.is_empty();
+
+        let message_repo = Arc::clone(&self.message_repo);
+        let summary_repo = Arc::clone(&self.summary_repo);
+        let quota = Arc::clone(&self.quota);
+        let scope = scope.clone();
+        let params = SaveSummaryParams {
+            tenant_id,
+            chat_id,
+            summary_text: text.clone(),
+            summarized_up_to: up_to,
+            token_estimate: i32::try_from(TokenCounter::for_model(model).count(&text))
+                .unwrap_or(i32::MAX),
+        };
+        let expected_up_to = previous.map(|s| s.up_to);
+        let message_ids = batch.into_iter().map(|m| m.id).collect();
+
+        let saved = self
+            .db
+            .transaction(|tx| {
+                Box::pin(async move {
+                    let db_err = |e: DomainError| modkit_db::DbError::Other(anyhow::anyhow!(e));
+
+                    quota
+                        .record_system_usage(tx, tenant_id, usage)
+                        .await
+                        .map_err(db_err)?;
+                    if text_is_empty {
+                        return Ok(false);
+                    }
+                    let saved = summary_repo
+                        .save(tx, &scope, params, expected_up_to)
+                        .await
+                        .map_err(db_err)?;
+                    if saved == 0 {
+                        return Ok(false);
+                    }
+                    message_repo
+                        .mark_compressed(tx, &scope, chat_id, message_ids)
+                        .await
+                        .map_err(db_err)?;
+                    Ok(true)
+                })
+            })
+            .await
+            .map_err(DomainError::from)?;
+
+        if text_is_empty {
+            return Err(DomainError::internal("summary response was empty"));
+        }
+        Ok(saved.then_some(Summary { text, up_to }))
+    }
+}
+
+/// Tokens the summary takes in the system instructions.
+fn summary_tokens(counter: TokenCounter, summary: Option<&Summary>) -> u32 {
+    summary.map_or(0, |s| {
+        counter
+            .count(SUMMARY_PREAMBLE)
+            .saturating_add(counter.count(&s.text))
+    })
+}
+
+/// Number of oldest messages to fold into the summary.
+///
+/// None while history and summary stay under `summary_trigger_percent` of
+/// `budget`; otherwise enough to bring the verbatim history down to
+/// `summary_target_percent`. The latest [`MIN_RECENT_MESSAGES`] are kept.
+fn fold_count(sizes: &[u32], summary_tokens: u32, budget: u32, config: ContextConfig) -> usize {
+    let mut remaining = sizes.iter().fold(0u32, |acc, s| acc.saturating_add(*s));
+    if remaining.saturating_add(summary_tokens)
+        <= percent_of(budget, config.summary_trigger_percent)
+    {
+        return 0;
+    }
+
+    let target = percent_of(budget, config.summary_target_percent);
+    let foldable = sizes.len().saturating_sub(MIN_RECENT_MESSAGES);
+    let mut count = 0;
+    while count < foldable && remaining > target {
+        remaining -= sizes[count];
+        count += 1;
+    }
+    count
+}
+
+/// Number of newest messages, by token size, that fit in `budget`.
+fn fit_newest(sizes: &[u32], budget: u32) -> usize {
+    let mut used: u32 = 0;
+    sizes
+        .iter()
+        .rev()
+        .take_while(|size| {
+            used = used.saturating_add(**size);
+            used <= budget
+        })
+        .count()
+}
+
+#[allow(clippy::integer_division)]
+fn percent_of(value: u32, percent: u8) -> u32 {
+    u32::try_from(u64::from(value) * u64::from(percent) / 100).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+#[path = "context_service_test.rs"]
+mod tests;
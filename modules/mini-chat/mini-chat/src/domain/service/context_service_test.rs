@@ -0,0 +1,473 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use modkit_security::{AccessScope, SecurityContext};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::config::{ContextConfig, ContextStrategy};
+use crate::domain::repos::{InsertAssistantMessageParams, InsertUserMessageParams};
+use crate::infra::db::repo::message_repo::MessageRepository as OrmMessageRepository;
+use crate::infra::db::repo::quota_usage_repo::QuotaUsageRepository as OrmQuotaUsageRepository;
+use crate::infra::db::repo::thread_summary_repo::ThreadSummaryRepository as OrmThreadSummaryRepository;
+use crate::infra::llm::request::{ContentPart, RequestType, Role};
+use crate::infra::llm::{
+    LlmProvider, LlmProviderError, LlmRequest, NonStreaming, ProviderStream, ResponseResult,
+    Streaming, Usage,
+};
+
+use super::*;
+use crate::domain::service::DbProvider;
+use crate::domain::service::test_helpers::{
+    OrmContextService, context_service, inmem_db, mock_db_provider, seed_chat, test_security_ctx,
+};
+
+// ── Test Helpers ──
+
+/// Not in the mock catalog, so the configured default window applies.
+const MODEL: &str = "small-model";
+
+/// 400 characters: 104 tokens as a message.
+fn long_text(tag: usize) -> String {
+    format!("{tag:04}").repeat(100)
+}
+
+/// Answers summary requests with a fixed text and records their prompts
+/// and requesters.
+#[derive(Default)]
+struct SummaryProvider {
+    requests: Mutex<Vec<LlmRequest<NonStreaming>>>,
+    requesters: Mutex<Vec<SecurityContext>>,
+}
+
+impl SummaryProvider {
+    fn prompts(&self) -> Vec<String> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .flat_map(|r| r.messages().iter())
+            .flat_map(|m| m.content.iter())
+            .filter_map(|p| match p {
+                ContentPart::Text { text } => Some(text.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl LlmProvider for SummaryProvider {
+    async fn stream(
+        &self,
+        _ctx: SecurityContext,
+        _request: LlmRequest<Streaming>,
+        _cancel: CancellationToken,
+    ) -> Result<ProviderStream, LlmProviderError> {
+        unimplemented!("not needed for context tests")
+    }
+
+    async fn complete(
+        &self,
+        ctx: SecurityContext,
+        request: LlmRequest<NonStreaming>,
+    ) -> Result<ResponseResult, LlmProviderError> {
+        self.requests.lock().unwrap().push(request);
+        self.requesters.lock().unwrap().push(ctx);
+        Ok(ResponseResult {
+            content: "the summary".to_owned(),
+            usage: Usage {
+                input_tokens: 10,
+                output_tokens: 3,
+            },
+            response_id: "resp-summary".to_owned(),
+            citations: vec![],
+            raw_response: serde_json::Value::Null,
+        })
+    }
+}
+
+fn config(strategy: ContextStrategy) -> ContextConfig {
+    ContextConfig {
+        strategy,
+        default_context_window_tokens: 4096,
+        ..ContextConfig::default()
+    }
+}
+
+/// Insert `count` completed exchanges of long messages; returns the
+/// message IDs, oldest first.
+async fn seed_history(
+    db: &DbProvider,
+    ctx: &SecurityContext,
+    chat_id: Uuid,
+    count: usize,
+) -> Vec<Uuid> {
+    let conn = db.conn().unwrap();
+    let tenant_id = ctx.subject_tenant_id();
+    let scope = AccessScope::for_tenant(tenant_id);
+    let mut ids = Vec::new();
+
+    for i in 0..count {
+        let request_id = Uuid::new_v4();
+        let user = OrmMessageRepository
+            .insert_user_message(
+                &conn,
+                &scope,
+                InsertUserMessageParams {
+                    id: Uuid::new_v4(),
+                    tenant_id,
+                    chat_id,
+                    request_id,
+                    content: long_text(2 * i),
+                },
+            )
+            .await
+            .unwrap();
+        let assistant = OrmMessageRepository
+            .insert_assistant_message(
+                &conn,
+                &scope,
+                InsertAssistantMessageParams {
+                    id: Uuid::new_v4(),
+                    tenant_id,
+                    chat_id,
+                    request_id,
+                    content: long_text(2 * i + 1),
+                    input_tokens: None,
+                    output_tokens: None,
+                    model: None,
+                    provider_response_id: None,
+                },
+            )
+            .await
+            .unwrap();
+        ids.extend([user.id, assistant.id]);
+    }
+    ids
+}
+
+async fn plan(
+    svc: &OrmContextService,
+    ctx: &SecurityContext,
+    chat_id: Uuid,
+    request_id: Uuid,
+) -> ContextPlan {
+    let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
+    svc.plan(
+        ctx,
+        &scope,
+        chat_id,
+        &[request_id],
+        MODEL,
+        "next",
+        None,
+        1024,
+    )
+    .await
+    .unwrap()
+}
+
+fn text_of(message: &LlmMessage) -> &str {
+    match message.content.first() {
+        Some(ContentPart::Text { text }) => text,
+        _ => "",
+    }
+}
+
+// ── Budget arithmetic ──
+
+#[test]
+fn fold_count_waits_for_trigger() {
+    let config = ContextConfig::default();
+    // 4 x 100 = 400 of a 1000 budget: below the 80% trigger.
+    assert_eq!(fold_count(&[100; 4], 0, 1000, config), 0);
+    // The summary counts toward the trigger.
+    assert_eq!(fold_count(&[100; 5], 500, 1000, config), 1);
+}
+
+#[test]
+fn fold_count_folds_down_to_target() {
+    let config = ContextConfig::default();
+    // 1000 of a 1000 budget: fold until at most 400 remain verbatim.
+    assert_eq!(fold_count(&[100; 10], 0, 1000, config), 6);
+}
+
+#[test]
+fn fold_count_keeps_recent_messages() {
+    let config = ContextConfig::default();
+    assert_eq!(fold_count(&[900, 900, 900], 0, 1000, config), 1);
+    assert_eq!(fold_count(&[900, 900], 0, 1000, config), 0);
+}
+
+#[test]
+fn fit_newest_keeps_suffix_within_budget() {
+    assert_eq!(fit_newest(&[50, 10, 20, 30], 60), 3);
+    assert_eq!(fit_newest(&[50, 10, 20, 30], 29), 0);
+    assert_eq!(fit_newest(&[], 100), 0);
+}
+
+// ── Planning ──
+
+#[tokio::test]
+async fn history_is_sent_oldest_first_without_current_turn() {
+    let db = mock_db_provider(inmem_db().await);
+    let ctx = test_security_ctx(Uuid::new_v4());
+    let chat_id = seed_chat(&db, &ctx).await;
+    let provider = Arc::new(SummaryProvider::default());
+    let svc = context_service(&db, provider.clone(), config(ContextStrategy::Hybrid));
+
+    seed_history(&db, &ctx, chat_id, 2).await;
+    let conn = db.conn().unwrap();
+    let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
+    let request_id = Uuid::new_v4();
+    OrmMessageRepository
+        .insert_user_message(
+            &conn,
+            &scope,
+            InsertUserMessageParams {
+                id: Uuid::new_v4(),
+                tenant_id: ctx.subject_tenant_id(),
+                chat_id,
+                request_id,
+                content: "next".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let plan = plan(&svc, &ctx, chat_id, request_id).await;
+
+    let roles: Vec<Role> = plan.messages.iter().map(|m| m.role).collect();
+    assert_eq!(
+        roles,
+        [
+            Role::User,
+            Role::Assistant,
+            Role::User,
+            Role::Assistant,
+            Role::User
+        ]
+    );
+    assert_eq!(text_of(&plan.messages[0]), long_text(0));
+    assert_eq!(text_of(&plan.messages[4]), "next");
+    assert!(plan.summary_instructions.is_none());
+    assert_eq!(plan.metadata.history_messages_sent, 4);
+    assert_eq!(plan.metadata.history_messages_truncated, 0);
+    assert_eq!(plan.metadata.tokens_estimate, 4 * 104 + 5);
+    assert!(provider.prompts().is_empty());
+}
+
+#[tokio::test]
+async fn truncate_oldest_drops_history_over_budget() {
+    let db = mock_db_provider(inmem_db().await);
+    let ctx = test_security_ctx(Uuid::new_v4());
+    let chat_id = seed_chat(&db, &ctx).await;
+    let provider = Arc::new(SummaryProvider::default());
+    let svc = context_service(
+        &db,
+        provider.clone(),
+        config(ContextStrategy::TruncateOldest),
+    );
+
+    // 40 messages of 104 tokens against a 2658-token history budget.
+    seed_history(&db, &ctx, chat_id, 20).await;
+    let plan = plan(&svc, &ctx, chat_id, Uuid::new_v4()).await;
+
+    assert_eq!(plan.metadata.strategy, ContextStrategy::TruncateOldest);
+    assert_eq!(plan.metadata.history_messages_sent, 25);
+    assert_eq!(plan.metadata.history_messages_truncated, 15);
+    assert_eq!(plan.metadata.summarized_messages, 0);
+    assert_eq!(plan.messages.len(), 26);
+    assert_eq!(text_of(&plan.messages[0]), long_text(15));
+    assert!(plan.summary_instructions.is_none());
+    assert!(provider.prompts().is_empty());
+}
+
+#[tokio::test]
+async fn summarize_folds_oldest_messages_before_the_turn() {
+    let db = mock_db_provider(inmem_db().await);
+    let ctx = test_security_ctx(Uuid::new_v4());
+    let chat_id = seed_chat(&db, &ctx).await;
+    let provider = Arc::new(SummaryProvider::default());
+    let svc = context_service(&db, provider.clone(), config(ContextStrategy::Summarize));
+
+    let ids = seed_history(&db, &ctx, chat_id, 20).await;
+    let first = plan(&svc, &ctx, chat_id, Uuid::new_v4()).await;
+
+    // Folded down to the 40% target: 10 messages stay verbatim.
+    assert_eq!(first.metadata.summarized_messages, 30);
+    assert_eq!(first.metadata.history_messages_sent, 10);
+    assert_eq!(first.metadata.history_messages_truncated, 0);
+    assert_eq!(first.metadata.summary_up_to, Some(ids[29]));
+    assert_eq!(
+        first.summary_instructions.as_deref(),
+        Some("Summary of the earlier part of this conversation:\nthe summary")
+    );
+    assert_eq!(text_of(&first.messages[0]), long_text(30));
+
+    let prompts = provider.prompts();
+    assert_eq!(prompts.len(), 1);
+    assert!(prompts[0].starts_with("Current summary:\n(none)"));
+    assert!(prompts[0].contains(&format!("User: {}", long_text(0))));
+    assert!(prompts[0].contains(&format!("Assistant: {}", long_text(29))));
+    assert!(!prompts[0].contains(&long_text(30)));
+    {
+        let requests = provider.requests.lock().unwrap();
+        assert_eq!(requests[0].max_output_tokens, Some(1024));
+        assert!(matches!(
+            requests[0].metadata.as_ref().map(|m| &m.request_type),
+            Some(RequestType::Summary)
+        ));
+    }
+
+    // The next turn reuses the summary without folding again.
+    let second = plan(&svc, &ctx, chat_id, Uuid::new_v4()).await;
+    assert_eq!(second.metadata.summarized_messages, 0);
+    assert_eq!(second.metadata.history_messages_sent, 10);
+    assert_eq!(second.metadata.summary_up_to, Some(ids[29]));
+    assert_eq!(second.summary_instructions, first.summary_instructions);
+    assert_eq!(provider.prompts().len(), 1);
+}
+
+#[tokio::test]
+async fn summary_runs_as_system_task_charged_to_tenant() {
+    let db = mock_db_provider(inmem_db().await);
+    let ctx = test_security_ctx(Uuid::new_v4());
+    let chat_id = seed_chat(&db, &ctx).await;
+    let provider = Arc::new(SummaryProvider::default());
+    let svc = context_service(&db, provider.clone(), config(ContextStrategy::Summarize));
+
+    seed_history(&db, &ctx, chat_id, 20).await;
+    plan(&svc, &ctx, chat_id, Uuid::new_v4()).await;
+
+    {
+        let requesters = provider.requesters.lock().unwrap();
+        assert_eq!(requesters.len(), 1);
+        assert_eq!(requesters[0].subject_id(), SYSTEM_SUBJECT_ID);
+        assert_eq!(requesters[0].subject_type(), Some("system"));
+        assert_eq!(requesters[0].subject_tenant_id(), ctx.subject_tenant_id());
+        let requests = provider.requests.lock().unwrap();
+        assert_eq!(
+            requests[0].metadata.as_ref().map(|m| m.user_id.as_str()),
+            Some("")
+        );
+    }
+
+    let conn = db.conn().unwrap();
+    let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
+    let rows = OrmQuotaUsageRepository
+        .find_bucket_rows(&conn, &scope, ctx.subject_tenant_id(), SYSTEM_SUBJECT_ID)
+        .await
+        .unwrap();
+    // One row per period (daily and monthly), all in the system bucket.
+    assert_eq!(rows.len(), 2);
+    for row in &rows {
+        assert_eq!(row.bucket, "system");
+        assert_eq!(row.calls, 1);
+        assert_eq!((row.input_tokens, row.output_tokens), (10, 3));
+        assert!(row.spent_credits_micro > 0);
+        assert_eq!(row.reserved_credits_micro, 0);
+    }
+    let user_rows = OrmQuotaUsageRepository
+        .find_bucket_rows(&conn, &scope, ctx.subject_tenant_id(), ctx.subject_id())
+        .await
+        .unwrap();
+    assert!(user_rows.is_empty());
+}
+
+#[tokio::test]
+async fn hybrid_truncates_and_summarizes_in_background() {
+    let db = mock_db_provider(inmem_db().await);
+    let ctx = test_security_ctx(Uuid::new_v4());
+    let chat_id = seed_chat(&db, &ctx).await;
+    let provider = Arc::new(SummaryProvider::default());
+    let svc = context_service(&db, provider.clone(), config(ContextStrategy::Hybrid));
+
+    let ids = seed_history(&db, &ctx, chat_id, 20).await;
+    let plan = plan(&svc, &ctx, chat_id, Uuid::new_v4()).await;
+
+    // The current turn is truncated; the fold runs after it.
+    assert_eq!(plan.metadata.summarized_messages, 30);
+    assert_eq!(plan.metadata.history_messages_sent, 25);
+    assert_eq!(plan.metadata.history_messages_truncated, 15);
+    assert!(plan.summary_instructions.is_none());
+
+    let conn = db.conn().unwrap();
+    let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
+    let mut summary = None;
+    for _ in 0..100 {
+        summary = OrmThreadSummaryRepository
+            .find_by_chat_id(&conn, &scope, chat_id)
+            .await
+            .unwrap();
+        if summary.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let summary = summary.expect("background summary should be saved");
+    assert_eq!(summary.summary_text, "the summary");
+    assert_eq!(summary.summarized_up_to, ids[29]);
+
+    let remaining = OrmMessageRepository
+        .list_uncompressed(&conn, &scope, chat_id, &[Uuid::new_v4()])
+        .await
+        .unwrap();
+    assert_eq!(remaining.len(), 10);
+}
+
+#[tokio::test]
+async fn stale_summary_update_is_dropped() {
+    let db = mock_db_provider(inmem_db().await);
+    let ctx = test_security_ctx(Uuid::new_v4());
+    let chat_id = seed_chat(&db, &ctx).await;
+    let provider = Arc::new(SummaryProvider::default());
+    let svc = context_service(&db, provider, config(ContextStrategy::Summarize));
+
+    seed_history(&db, &ctx, chat_id, 20).await;
+    plan(&svc, &ctx, chat_id, Uuid::new_v4()).await;
+
+    // A fold based on "no summary yet" loses against the saved one.
+    let conn = db.conn().unwrap();
+    let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
+    let batch = OrmMessageRepository
+        .list_uncompressed(&conn, &scope, chat_id, &[Uuid::new_v4()])
+        .await
+        .unwrap();
+    let folded = svc
+        .summarizer()
+        .fold(
+            ctx.subject_tenant_id(),
+            &scope,
+            chat_id,
+            MODEL,
+            None,
+            batch[..2].to_vec(),
+        )
+        .await;
+    assert!(folded.is_err(), "second first-summary insert must fail");
+
+    let stale = Summary {
+        text: "old".to_owned(),
+        up_to: Uuid::new_v4(),
+    };
+    let folded = svc
+        .summarizer()
+        .fold(
+            ctx.subject_tenant_id(),
+            &scope,
+            chat_id,
+            MODEL,
+            Some(stale),
+            batch[..2].to_vec(),
+        )
+        .await
+        .unwrap();
+    assert!(folded.is_none());
+    let remaining = OrmMessageRepository
+        .list_uncompressed(&conn, &scope, chat_id, &[Uuid::new_v4()])
+        .await
+        .unwrap();
+    assert_eq!(remaining.len(), 10);
+}
@@ -2,32 +2,43 @@ use std::sync::Arc;
 
 use authz_resolver_sdk::pep::ResourceType;
 use authz_resolver_sdk::{AuthZResolverClient, PolicyEnforcer};
+use file_parser::domain::service::FileParserService;
 use modkit_db::DBProvider;
 use modkit_macros::domain_model;
 
-use crate::config::StreamingConfig;
+use crate::config::{AttachmentsConfig, ContextConfig, StreamingConfig, ToolsConfig};
 use crate::domain::repos::{
-    AttachmentRepository, ChatRepository, MessageRepository, ModelPrefRepository, ModelResolver,
-    QuotaUsageRepository, ReactionRepository, ThreadSummaryRepository, TurnRepository,
-    VectorStoreRepository,
+    AttachmentRepository, ChatRepository, Embedder, MessageRepository, ModelPrefRepository,
+    ModelResolver, QuotaUsageRepository, ReactionRepository, ThreadSummaryRepository, ToolExecutor,
+    TurnRepository, VectorStoreRepository,
 };
 use crate::infra::llm::LlmProvider;
 
 mod attachment_service;
 mod chat_service;
+mod chunker;
+mod context_service;
 mod model_service;
 mod quota_service;
 mod reaction_service;
+mod retrieval_service;
 mod stream_service;
 #[cfg(test)]
 pub(crate) mod test_helpers;
+mod token_counter;
+mod turn_service;
 
 pub(crate) use attachment_service::AttachmentService;
 pub(crate) use chat_service::ChatService;
+pub(crate) use context_service::{ContextMetadata, ContextPlan, ContextService};
 pub(crate) use model_service::ModelService;
-pub(crate) use quota_service::QuotaService;
+pub(crate) use quota_service::{
+    MAX_OUTPUT_TOKENS, QuotaReservation, QuotaService, SYSTEM_SUBJECT_ID, Settlement,
+};
 pub(crate) use reaction_service::ReactionService;
-pub(crate) use stream_service::{StreamError, StreamService};
+pub(crate) use retrieval_service::{RetrievalService, RetrievedContext};
+pub(crate) use stream_service::{StreamError, StreamOutcome, StreamService};
+pub(crate) use turn_service::TurnService;
 
 pub(crate) type DbProvider = DBProvider<modkit_db::DbError>;
 
@@ -77,16 +88,19 @@ pub(crate) struct Repositories<
     MR: MessageRepository,
     QR: QuotaUsageRepository,
     CR: ChatRepository,
+    AR: AttachmentRepository,
+    VR: VectorStoreRepository,
+    SR: ThreadSummaryRepository,
 > {
     pub(crate) chat: Arc<CR>,
-    pub(crate) attachment: Arc<dyn AttachmentRepository>,
+    pub(crate) attachment: Arc<AR>,
     pub(crate) message: Arc<MR>,
     pub(crate) quota: Arc<QR>,
     pub(crate) turn: Arc<TR>,
     pub(crate) reaction: Arc<dyn ReactionRepository>,
     pub(crate) model_pref: Arc<dyn ModelPrefRepository>,
-    pub(crate) thread_summary: Arc<dyn ThreadSummaryRepository>,
-    pub(crate) vector_store: Arc<dyn VectorStoreRepository>,
+    pub(crate) thread_summary: Arc<SR>,
+    pub(crate) vector_store: Arc<VR>,
 }
 
 /// DI container — aggregates all domain services.
@@ -101,13 +115,17 @@ pub(crate) struct AppServices<
     MR: MessageRepository + 'static,
     QR: QuotaUsageRepository + 'static,
     CR: ChatRepository + 'static,
+    AR: AttachmentRepository + 'static,
+    VR: VectorStoreRepository + 'static,
+    SR: ThreadSummaryRepository + 'static,
 > {
     pub(crate) chats: ChatService<CR>,
-    pub(crate) stream: StreamService<TR, MR, CR>,
+    pub(crate) stream: StreamService<TR, MR, QR, CR, AR, VR, SR>,
+    pub(crate) turns: TurnService<TR, MR, CR>,
     pub(crate) reactions: ReactionService<CR>,
-    pub(crate) attachments: AttachmentService<CR>,
+    pub(crate) attachments: AttachmentService<AR, VR, CR>,
     pub(crate) models: ModelService,
-    pub(crate) quota: QuotaService<QR>,
+    pub(crate) quota: Arc<QuotaService<QR>>,
 }
 
 impl<
@@ -115,23 +133,53 @@ impl<
     MR: MessageRepository + 'static,
     QR: QuotaUsageRepository + 'static,
     CR: ChatRepository + 'static,
-> AppServices<TR, MR, QR, CR>
+    AR: AttachmentRepository + 'static,
+    VR: VectorStoreRepository + 'static,
+    SR: ThreadSummaryRepository + 'static,
+> AppServices<TR, MR, QR, CR, AR, VR, SR>
 {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
-        repos: &Repositories<TR, MR, QR, CR>,
+        repos: &Repositories<TR, MR, QR, CR, AR, VR, SR>,
         db: Arc<DbProvider>,
         authz: Arc<dyn AuthZResolverClient>,
         model_resolver: Arc<dyn ModelResolver>,
         llm: Arc<dyn LlmProvider>,
         streaming_config: StreamingConfig,
+        tool_executor: Option<Arc<dyn ToolExecutor>>,
+        tools_config: ToolsConfig,
+        parser: Arc<FileParserService>,
+        embedder: Arc<dyn Embedder>,
+        attachments_config: AttachmentsConfig,
+        context_config: ContextConfig,
     ) -> Self {
         let enforcer = PolicyEnforcer::new(authz);
+        let quota = Arc::new(QuotaService::new(
+            Arc::clone(&db),
+            Arc::clone(&repos.quota),
+            enforcer.clone(),
+        ));
+        let retrieval = Arc::new(RetrievalService::new(
+            Arc::clone(&db),
+            Arc::clone(&repos.attachment),
+            Arc::clone(&repos.vector_store),
+            Arc::clone(&embedder),
+            attachments_config,
+        ));
+        let context = Arc::new(ContextService::new(
+            Arc::clone(&db),
+            Arc::clone(&repos.message),
+            Arc::clone(&repos.thread_summary),
+            Arc::clone(&quota),
+            Arc::clone(&model_resolver),
+            Arc::clone(&llm),
+            context_config,
+        ));
 
         Self {
             chats: ChatService::new(
                 Arc::clone(&db),
                 Arc::clone(&repos.chat),
-                Arc::clone(&repos.thread_summary),
                 enforcer.clone(),
                 model_resolver,
             ),
@@ -140,9 +188,21 @@ impl<
                 Arc::clone(&repos.turn),
                 Arc::clone(&repos.message),
                 Arc::clone(&repos.chat),
+                Arc::clone(&quota),
                 enforcer.clone(),
                 llm,
                 streaming_config,
+                tool_executor,
+                tools_config,
+                retrieval,
+                context,
+            ),
+            turns: TurnService::new(
+                Arc::clone(&db),
+                Arc::clone(&repos.turn),
+                Arc::clone(&repos.message),
+                Arc::clone(&repos.chat),
+                enforcer.clone(),
             ),
             reactions: ReactionService::new(
                 Arc::clone(&db),
@@ -156,13 +216,12 @@ impl<
                 Arc::clone(&repos.chat),
                 Arc::clone(&repos.vector_store),
                 enforcer.clone(),
+                parser,
+                embedder,
+                attachments_config,
             ),
-            models: ModelService::new(
-                Arc::clone(&db),
-                Arc::clone(&repos.model_pref),
-                enforcer.clone(),
-            ),
-            quota: QuotaService::new(db, Arc::clone(&repos.quota), enforcer),
+            models: ModelService::new(db, Arc::clone(&repos.model_pref), enforcer),
+            quota,
         }
     }
 }
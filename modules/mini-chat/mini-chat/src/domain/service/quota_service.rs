@@ -1,17 +1,157 @@
 use std::sync::Arc;
 
 use authz_resolver_sdk::PolicyEnforcer;
+use modkit_db::secure::DBRunner;
 use modkit_macros::domain_model;
+use modkit_security::AccessScope;
+use time::{Date, OffsetDateTime};
+use uuid::Uuid;
 
-use crate::domain::repos::QuotaUsageRepository;
+use crate::domain::error::DomainError;
+use crate::domain::repos::{
+    IncrementReserveParams, QuotaUsageRepository, RecordSpentParams, SettleParams,
+};
+use crate::infra::db::entity::quota_usage::PeriodType;
+use crate::infra::llm::Usage;
 
 use super::DbProvider;
 
+// TODO P3: multipliers, `max_output_tokens` and the generation floor come
+// from the policy snapshot and ConfigMap; until then every model bills 1x.
+
+/// Credits per 1K tokens, in micro-credits, for both input and output.
+const CREDIT_MULTIPLIER_MICRO: i64 = 1_000_000;
+/// `max_output_tokens` applied to every turn.
+pub const MAX_OUTPUT_TOKENS: i32 = 4096;
+/// Output tokens charged when the provider reported no usage.
+const MINIMAL_GENERATION_FLOOR: i32 = 50;
+/// Actual credits above this percentage of the reserve are capped.
+const OVERSHOOT_TOLERANCE_PERCENT: i64 = 110;
+/// Bucket holding the overall cap across all tiers.
+const TOTAL_BUCKET: &str = "total";
+/// Bucket of the tenant's operational spend on system tasks.
+const SYSTEM_BUCKET: &str = "system";
+
+/// Subject of system tasks (`requester_type=system`). The tenant
+/// operational bucket is the `system` bucket of this subject, so system
+/// work is never charged to an end user.
+pub const SYSTEM_SUBJECT_ID: Uuid = Uuid::nil();
+
+/// Preflight reserve for one turn, computed before the provider call and
+/// persisted on `chat_turns`.
+///
+/// A turn with tools may call the provider several times, each round
+/// sending at least the same input again and generating up to
+/// `max_output_tokens`; the reserve covers every round the turn may run.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotaReservation {
+    /// Input tokens of one provider round.
+    pub estimated_input_tokens: i64,
+    /// Output cap of one provider round.
+    pub max_output_tokens: i32,
+    /// Provider rounds the turn may run: 1, or `max_steps + 1` with tools.
+    pub max_rounds: u32,
+    pub minimal_generation_floor: i32,
+    pub reserved_credits_micro: i64,
+    /// UTC day the reserve was taken; settlement targets the same periods.
+    pub period_day: Date,
+}
+
+impl QuotaReservation {
+    /// Compute the preflight reserve for a turn whose model input is
+    /// estimated at `estimated_input_tokens` (the context plan plus
+    /// surcharges) and that may call the provider up to `max_rounds` times.
+    pub fn preflight(estimated_input_tokens: u32, max_rounds: u32) -> Self {
+        let estimated_input_tokens = i64::from(estimated_input_tokens);
+        let max_rounds = max_rounds.max(1);
+        let rounds = i64::from(max_rounds);
+        Self {
+            estimated_input_tokens,
+            max_output_tokens: MAX_OUTPUT_TOKENS,
+            max_rounds,
+            minimal_generation_floor: MINIMAL_GENERATION_FLOOR,
+            reserved_credits_micro: credits_micro(
+                estimated_input_tokens.saturating_mul(rounds),
+                i64::from(MAX_OUTPUT_TOKENS).saturating_mul(rounds),
+            ),
+            period_day: OffsetDateTime::now_utc().date(),
+        }
+    }
+
+    /// `(estimated_input_tokens + max_output_tokens) * max_rounds`.
+    pub fn reserve_tokens(&self) -> i64 {
+        (self.estimated_input_tokens + i64::from(self.max_output_tokens))
+            .saturating_mul(i64::from(self.max_rounds))
+    }
+}
+
+/// How a reserved turn ended, for settlement.
+#[domain_model]
+#[derive(Debug, Clone, Copy)]
+pub enum Settlement {
+    /// Completed turn with provider-reported usage.
+    Completed(Usage),
+    /// Failed or cancelled turn; usage is `None` when the provider never
+    /// reported it.
+    Aborted(Option<Usage>),
+}
+
+/// `ceil(input * in_mult / 1000) + ceil(output * out_mult / 1000)`, rounded
+/// per component.
+#[allow(clippy::integer_division)]
+fn credits_micro(input_tokens: i64, output_tokens: i64) -> i64 {
+    let component = |tokens: i64| {
+        tokens
+            .max(0)
+            .saturating_mul(CREDIT_MULTIPLIER_MICRO)
+            .saturating_add(999)
+            / 1000
+    };
+    component(input_tokens).saturating_add(component(output_tokens))
+}
+
+/// Credits charged to quota for a settled turn.
+///
+/// Completed turns are charged their actual usage, capped at the reserve
+/// when it overshoots the tolerance. Aborted turns are charged actual usage
+/// if known, otherwise the estimated input plus the generation floor.
+#[allow(clippy::integer_division)]
+fn committed_credits_micro(reservation: &QuotaReservation, settlement: Settlement) -> i64 {
+    match settlement {
+        Settlement::Completed(usage) => {
+            let actual = credits_micro(usage.input_tokens, usage.output_tokens);
+            let limit = reservation
+                .reserved_credits_micro
+                .saturating_mul(OVERSHOOT_TOLERANCE_PERCENT)
+                / 100;
+            if actual > limit {
+                reservation.reserved_credits_micro
+            } else {
+                actual
+            }
+        }
+        Settlement::Aborted(Some(usage)) => credits_micro(usage.input_tokens, usage.output_tokens),
+        Settlement::Aborted(None) => credits_micro(
+            reservation.estimated_input_tokens,
+            i64::from(reservation.minimal_generation_floor),
+        ),
+    }
+}
+
+/// Enabled quota periods with their `period_start` for a UTC day.
+fn periods(day: Date) -> [(PeriodType, Date); 2] {
+    [
+        (PeriodType::Daily, day),
+        (PeriodType::Monthly, day.replace_day(1).unwrap_or(day)),
+    ]
+}
+
 /// Service handling quota tracking and enforcement.
 #[domain_model]
 pub struct QuotaService<QR: QuotaUsageRepository> {
     _db: Arc<DbProvider>,
-    _repo: Arc<QR>,
+    repo: Arc<QR>,
     _enforcer: PolicyEnforcer,
 }
 
@@ -19,8 +159,279 @@ impl<QR: QuotaUsageRepository> QuotaService<QR> {
     pub(crate) fn new(db: Arc<DbProvider>, repo: Arc<QR>, enforcer: PolicyEnforcer) -> Self {
         Self {
             _db: db,
-            _repo: repo,
+            repo,
             _enforcer: enforcer,
         }
     }
+
+    /// Add the reserve to the user's `total` bucket in every period.
+    /// Runs in the caller's transaction, together with the turn insert.
+    pub(crate) async fn reserve<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        reservation: &QuotaReservation,
+    ) -> Result<(), DomainError> {
+        for (period_type, period_start) in periods(reservation.period_day) {
+            self.repo
+                .increment_reserve(
+                    runner,
+                    scope,
+                    IncrementReserveParams {
+                        tenant_id,
+                        user_id,
+                        period_type,
+                        period_start,
+                        bucket: TOTAL_BUCKET.to_owned(),
+                        amount_micro: reservation.reserved_credits_micro,
+                    },
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Charge the `usage` of a system task, e.g. a thread summary update,
+    /// to the tenant operational bucket. System tasks take no reserve.
+    pub(crate) async fn record_system_usage<C: DBRunner>(
+        &self,
+        runner: &C,
+        tenant_id: Uuid,
+        usage: Usage,
+    ) -> Result<(), DomainError> {
+        let scope = AccessScope::for_tenant(tenant_id);
+        let credits_micro = credits_micro(usage.input_tokens, usage.output_tokens);
+        for (period_type, period_start) in periods(OffsetDateTime::now_utc().date()) {
+            self.repo
+                .record_spent(
+                    runner,
+                    &scope,
+                    RecordSpentParams {
+                        tenant_id,
+                        user_id: SYSTEM_SUBJECT_ID,
+                        period_type,
+                        period_start,
+                        bucket: SYSTEM_BUCKET.to_owned(),
+                        credits_micro,
+                        input_tokens: usage.input_tokens,
+                        output_tokens: usage.output_tokens,
+                    },
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Release the reserve and commit the charged credits. Must run once
+    /// per turn, by the finalizer that won the turn's terminal CAS.
+    pub(crate) async fn settle<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        reservation: &QuotaReservation,
+        settlement: Settlement,
+    ) -> Result<(), DomainError> {
+        let actual_credits_micro = committed_credits_micro(reservation, settlement);
+        let usage = match settlement {
+            Settlement::Completed(usage) | Settlement::Aborted(Some(usage)) => Some(usage),
+            Settlement::Aborted(None) => None,
+        };
+        for (period_type, period_start) in periods(reservation.period_day) {
+            self.repo
+                .settle(
+                    runner,
+                    scope,
+                    SettleParams {
+                        tenant_id,
+                        user_id,
+                        period_type,
+                        period_start,
+                        bucket: TOTAL_BUCKET.to_owned(),
+                        reserved_credits_micro: reservation.reserved_credits_micro,
+                        actual_credits_micro,
+                        input_tokens: usage.map(|u| u.input_tokens),
+                        output_tokens: usage.map(|u| u.output_tokens),
+                    },
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::service::test_helpers::{inmem_db, mock_db_provider, mock_enforcer};
+    use crate::infra::db::repo::quota_usage_repo::QuotaUsageRepository as OrmQuotaRepo;
+    use time::Month;
+
+    fn feb(day: u8) -> Date {
+        Date::from_calendar_date(2026, Month::February, day).unwrap()
+    }
+
+    fn reservation(estimated_input_tokens: i64) -> QuotaReservation {
+        QuotaReservation {
+            estimated_input_tokens,
+            max_output_tokens: MAX_OUTPUT_TOKENS,
+            max_rounds: 1,
+            minimal_generation_floor: MINIMAL_GENERATION_FLOOR,
+            reserved_credits_micro: credits_micro(
+                estimated_input_tokens,
+                i64::from(MAX_OUTPUT_TOKENS),
+            ),
+            period_day: feb(28),
+        }
+    }
+
+    #[test]
+    fn credits_round_up_per_component() {
+        assert_eq!(credits_micro(0, 0), 0);
+        assert_eq!(credits_micro(1000, 0), 1_000_000);
+        // 1 token at 1000 micro-credits per token = 1000 per component.
+        assert_eq!(credits_micro(1, 1), 2000);
+    }
+
+    #[test]
+    fn preflight_reserves_input_estimate_and_max_output() {
+        let r = QuotaReservation::preflight(1500, 1);
+        assert_eq!(r.estimated_input_tokens, 1500);
+        assert_eq!(r.reserve_tokens(), 1500 + i64::from(MAX_OUTPUT_TOKENS));
+        assert_eq!(
+            r.reserved_credits_micro,
+            credits_micro(1500, i64::from(MAX_OUTPUT_TOKENS))
+        );
+    }
+
+    #[test]
+    fn preflight_reserves_every_round() {
+        let r = QuotaReservation::preflight(1500, 3);
+        assert_eq!(r.estimated_input_tokens, 1500);
+        assert_eq!(
+            r.reserve_tokens(),
+            3 * (1500 + i64::from(MAX_OUTPUT_TOKENS))
+        );
+        assert_eq!(
+            r.reserved_credits_micro,
+            credits_micro(3 * 1500, 3 * i64::from(MAX_OUTPUT_TOKENS))
+        );
+        assert_eq!(QuotaReservation::preflight(1500, 0).max_rounds, 1);
+    }
+
+    #[test]
+    fn completed_tool_turn_charges_every_round() {
+        // One tool round, then the answer; both generate the full output.
+        let r = QuotaReservation::preflight(1000, 2);
+        let usage = Usage {
+            input_tokens: 1000 + 1200,
+            output_tokens: 2 * i64::from(MAX_OUTPUT_TOKENS),
+        };
+        let actual = credits_micro(usage.input_tokens, usage.output_tokens);
+        // Far above what a single-round reserve would let through.
+        assert!(
+            actual * 100
+                > QuotaReservation::preflight(1000, 1).reserved_credits_micro
+                    * OVERSHOOT_TOLERANCE_PERCENT
+        );
+        assert_eq!(
+            committed_credits_micro(&r, Settlement::Completed(usage)),
+            actual
+        );
+    }
+
+    #[test]
+    fn completed_within_tolerance_charges_actual() {
+        let r = reservation(100);
+        let usage = Usage {
+            input_tokens: 120,
+            output_tokens: 300,
+        };
+        assert_eq!(
+            committed_credits_micro(&r, Settlement::Completed(usage)),
+            credits_micro(120, 300)
+        );
+    }
+
+    #[test]
+    fn completed_overshoot_is_capped_at_reserve() {
+        let r = reservation(100);
+        let usage = Usage {
+            input_tokens: 10_000,
+            output_tokens: 4096,
+        };
+        assert_eq!(
+            committed_credits_micro(&r, Settlement::Completed(usage)),
+            r.reserved_credits_micro
+        );
+    }
+
+    #[test]
+    fn aborted_without_usage_charges_estimate_and_floor() {
+        let r = reservation(100);
+        assert_eq!(
+            committed_credits_micro(&r, Settlement::Aborted(None)),
+            credits_micro(100, i64::from(MINIMAL_GENERATION_FLOOR))
+        );
+    }
+
+    #[test]
+    fn monthly_period_starts_on_first_day() {
+        let [(_, daily), (_, monthly)] = periods(feb(28));
+        assert_eq!(daily, feb(28));
+        assert_eq!(monthly, feb(1));
+    }
+
+    #[tokio::test]
+    async fn reserve_then_settle_moves_credits_to_spent() {
+        let db = mock_db_provider(inmem_db().await);
+        let repo = Arc::new(OrmQuotaRepo);
+        let svc = QuotaService::new(Arc::clone(&db), Arc::clone(&repo), mock_enforcer());
+        let tenant_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let scope = AccessScope::for_tenant(tenant_id);
+        let conn = db.conn().unwrap();
+        let r = reservation(100);
+        let usage = Usage {
+            input_tokens: 100,
+            output_tokens: 200,
+        };
+
+        svc.reserve(&conn, &scope, tenant_id, user_id, &r)
+            .await
+            .unwrap();
+        let rows = repo
+            .find_bucket_rows(&conn, &scope, tenant_id, user_id)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert!(
+            rows.iter()
+                .all(|row| row.reserved_credits_micro == r.reserved_credits_micro)
+        );
+
+        svc.settle(
+            &conn,
+            &scope,
+            tenant_id,
+            user_id,
+            &r,
+            Settlement::Completed(usage),
+        )
+        .await
+        .unwrap();
+        let rows = repo
+            .find_bucket_rows(&conn, &scope, tenant_id, user_id)
+            .await
+            .unwrap();
+        for row in rows {
+            assert_eq!(row.reserved_credits_micro, 0);
+            assert_eq!(row.spent_credits_micro, credits_micro(100, 200));
+            assert_eq!(row.calls, 1);
+            assert_eq!(row.output_tokens, 200);
+        }
+    }
 }
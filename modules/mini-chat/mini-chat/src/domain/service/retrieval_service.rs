@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use modkit_macros::domain_model;
+use modkit_security::{AccessScope, SecurityContext};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::config::AttachmentsConfig;
+use crate::domain::error::DomainError;
+use crate::domain::repos::{AttachmentRepository, Embedder, VectorStoreRepository};
+use crate::infra::db::entity::attachment::AttachmentStatus;
+use crate::infra::db::repo::vector_store_repo::decode_embedding;
+use crate::infra::llm::{Citation, CitationSource};
+
+use super::DbProvider;
+
+/// Maximum length of a citation snippet in characters.
+const SNIPPET_MAX_CHARS: usize = 300;
+
+/// Preamble of the system instructions carrying retrieved excerpts.
+const RAG_INSTRUCTIONS: &str = "Excerpts from documents the user attached to this chat are \
+listed below. Use them when they are relevant to the request and cite an excerpt by its \
+number in square brackets, e.g. [1]. If the excerpts do not contain the answer, say so \
+instead of guessing.";
+
+/// Document excerpts retrieved for one turn.
+#[domain_model]
+#[derive(Debug, Clone)]
+pub struct RetrievedContext {
+    /// System instructions carrying the numbered excerpts.
+    pub instructions: String,
+    /// One citation per excerpt, in excerpt order.
+    pub citations: Vec<Citation>,
+}
+
+/// Service answering similarity queries against a chat's local document
+/// index.
+///
+/// Scoring is a brute-force cosine similarity over the chat's chunks;
+/// `max_chunks_per_chat` bounds the scan.
+#[domain_model]
+pub struct RetrievalService<AR: AttachmentRepository, VR: VectorStoreRepository> {
+    db: Arc<DbProvider>,
+    attachment_repo: Arc<AR>,
+    vector_store_repo: Arc<VR>,
+    embedder: Arc<dyn Embedder>,
+    config: AttachmentsConfig,
+}
+
+impl<AR: AttachmentRepository, VR: VectorStoreRepository> RetrievalService<AR, VR> {
+    pub(crate) fn new(
+        db: Arc<DbProvider>,
+        attachment_repo: Arc<AR>,
+        vector_store_repo: Arc<VR>,
+        embedder: Arc<dyn Embedder>,
+        config: AttachmentsConfig,
+    ) -> Self {
+        Self {
+            db,
+            attachment_repo,
+            vector_store_repo,
+            embedder,
+            config,
+        }
+    }
+
+    /// Retrieve the excerpts of the chat's ready documents most similar to
+    /// `query`, up to `retrieval_k` chunks and the per-turn token budget.
+    ///
+    /// Returns `None` when the chat has no indexed documents or nothing in
+    /// them resembles the query.
+    #[instrument(skip(self, ctx, scope, query), fields(chat_id = %chat_id))]
+    pub(crate) async fn retrieve(
+        &self,
+        ctx: &SecurityContext,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        query: &str,
+    ) -> Result<Option<RetrievedContext>, DomainError> {
+        let conn = self.db.conn().map_err(DomainError::from)?;
+        let Some(store) = self
+            .vector_store_repo
+            .find_store(&conn, scope, chat_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+        if store.file_count == 0 {
+            return Ok(None);
+        }
+
+        // Chunks of deleted or failed attachments are not retrievable.
+        let filenames: HashMap<Uuid, String> = self
+            .attachment_repo
+            .list_documents(&conn, scope, chat_id)
+            .await?
+            .into_iter()
+            .filter(|a| a.status == AttachmentStatus::Ready)
+            .map(|a| (a.id, a.filename))
+            .collect();
+        let chunks = self
+            .vector_store_repo
+            .find_chunks(&conn, scope, chat_id)
+            .await?;
+
+        let query_vector = self
+            .embedder
+            .embed(ctx, &[query.to_owned()])
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        let mut scored: Vec<_> = chunks
+            .into_iter()
+            .filter(|c| filenames.contains_key(&c.attachment_id))
+            .filter_map(|c| {
+                let embedding = decode_embedding(&c.embedding);
+                if embedding.len() != query_vector.len() {
+                    return None;
+                }
+                let similarity: f32 = embedding
+                    .iter()
+                    .zip(&query_vector)
+                    .map(|(a, b)| a * b)
+                    .sum();
+                (similarity > 0.0).then_some((similarity, c))
+            })
+            .collect();
+        scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+
+        let mut budget = self.config.max_retrieved_tokens_per_turn;
+        let mut excerpts = vec![RAG_INSTRUCTIONS.to_owned()];
+        let mut citations = Vec::new();
+        for (score, chunk) in scored
+            .into_iter()
+            .take(usize::from(self.config.retrieval_k))
+        {
+            let tokens = u32::try_from(chunk.token_estimate).unwrap_or(0);
+            if tokens > budget {
+                break;
+            }
+            budget -= tokens;
+
+            let filename = &filenames[&chunk.attachment_id];
+            let number = citations.len() + 1;
+            excerpts.push(match &chunk.heading {
+                Some(heading) => format!("[{number}] {filename} - {heading}\n{}", chunk.content),
+                None => format!("[{number}] {filename}\n{}", chunk.content),
+            });
+
+            citations.push(Citation {
+                source: CitationSource::File,
+                title: filename.clone(),
+                url: None,
+                attachment_id: Some(chunk.attachment_id.to_string()),
+                snippet: snippet(&chunk.content),
+                score: Some(f64::from(score)),
+                span: None,
+            });
+        }
+
+        if citations.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(RetrievedContext {
+            instructions: excerpts.join("\n\n"),
+            citations,
+        }))
+    }
+}
+
+/// Shorten `content` to at most [`SNIPPET_MAX_CHARS`] characters.
+fn snippet(content: &str) -> String {
+    match content.char_indices().nth(SNIPPET_MAX_CHARS) {
+        Some((end, _)) => format!("{}...", content[..end].trim_end()),
+        None => content.to_owned(),
+    }
+}
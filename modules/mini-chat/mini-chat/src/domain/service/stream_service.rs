@@ -1,7 +1,9 @@
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use authz_resolver_sdk::PolicyEnforcer;
 use futures::StreamExt;
+use mini_chat_sdk::{ToolCallOutput, ToolCallRequest};
 use modkit_macros::domain_model;
 use modkit_security::AccessScope;
 use oagw_sdk::SecurityContext;
@@ -10,20 +12,31 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-use crate::api::rest::dto::{DoneData, ErrorData, StreamEvent};
-use crate::config::StreamingConfig;
+use crate::api::rest::dto::{
+    DoneData, ErrorData, StreamEvent, ToolCallData, ToolResultData, ToolResultStatus,
+};
+use crate::config::{StreamingConfig, ToolsConfig};
 use crate::domain::error::DomainError;
 use crate::domain::repos::{
-    CasCompleteParams, CasTerminalParams, ChatRepository, CreateTurnParams,
-    InsertAssistantMessageParams, InsertUserMessageParams, MessageRepository, TurnRepository,
+    AttachmentRepository, CasCompleteParams, CasTerminalParams, ChatRepository, CreateTurnParams,
+    InsertAssistantMessageParams, InsertUserMessageParams, MessageRepository, QuotaUsageRepository,
+    RecordContextParams, ThreadSummaryRepository, ToolExecutor, TurnRepository,
+    VectorStoreRepository,
 };
 use crate::infra::db::entity::chat_turn::{Model as TurnModel, TurnState};
+use crate::infra::db::entity::message::MessageRole;
+use crate::infra::llm::request::ContentPart;
 use crate::infra::llm::{
-    ClientSseEvent, LlmMessage, LlmProvider, LlmProviderError, LlmRequestBuilder, TerminalOutcome,
-    Usage,
+    Citation, ClientSseEvent, LlmMessage, LlmProvider, LlmProviderError, LlmRequestBuilder,
+    LlmTool, TerminalOutcome, ToolCall, Usage,
 };
 
-use super::DbProvider;
+use super::token_counter::TokenCounter;
+use super::turn_service::{MutationTarget, authorize_chat, resolve_mutation_target};
+use super::{
+    ContextMetadata, ContextPlan, ContextService, DbProvider, MAX_OUTPUT_TOKENS, QuotaReservation,
+    QuotaService, RetrievalService, RetrievedContext, Settlement, actions,
+};
 
 // ════════════════════════════════════════════════════════════════════════════
 // StreamTerminal — service-level terminal classification
@@ -71,6 +84,8 @@ pub struct StreamOutcome {
     pub provider_response_id: Option<String>,
     /// Whether usage was from a partial/incomplete provider response.
     pub provider_partial_usage: bool,
+    /// Number of tool-calling rounds executed before the stream ended.
+    pub tool_steps: u32,
 }
 
 // ════════════════════════════════════════════════════════════════════════════
@@ -89,6 +104,8 @@ pub enum StreamError {
     Conflict { code: String, message: String },
     /// Turn creation or pre-stream DB operation failed.
     TurnCreationFailed { source: DomainError },
+    /// Rejected by authorization or the turn mutation rules.
+    Rejected { source: DomainError },
 }
 
 // ════════════════════════════════════════════════════════════════════════════
@@ -98,17 +115,97 @@ pub enum StreamError {
 /// Persistence context cloned into the spawned provider task for CAS
 /// finalization after stream completion. `None` in unit tests.
 #[domain_model]
-struct PersistenceCtx<TR: TurnRepository, MR: MessageRepository> {
+struct PersistenceCtx<TR: TurnRepository, MR: MessageRepository, QR: QuotaUsageRepository> {
     db: Arc<DbProvider>,
     turn_repo: Arc<TR>,
     message_repo: Arc<MR>,
+    quota: Arc<QuotaService<QR>>,
     scope: AccessScope,
     turn_id: Uuid,
     tenant_id: Uuid,
+    user_id: Uuid,
     chat_id: Uuid,
     request_id: Uuid,
     /// Pre-generated assistant message ID, also sent in `DoneData`.
     message_id: Uuid,
+    /// Preflight reserve, settled by the finalizer that wins the CAS.
+    reservation: QuotaReservation,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// TurnInput — what a new turn sends to the model
+// ════════════════════════════════════════════════════════════════════════════
+
+/// User input of a new turn. Retry and edit replace the turn `target`
+/// (a `request_id`) inside the transaction that creates the new turn.
+#[domain_model]
+enum TurnInput {
+    /// A new user message.
+    Message(String),
+    /// Re-send the user message of `target`.
+    Retry { target: Uuid },
+    /// Send `content` in place of the user message of `target`.
+    Edit { target: Uuid, content: String },
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// TurnPrompt — model input assembled before the provider task starts
+// ════════════════════════════════════════════════════════════════════════════
+
+/// What the provider task sends to the model: the history that fits the
+/// context window ending with the user message, and system instructions
+/// carrying the thread summary and retrieved documents.
+#[domain_model]
+struct TurnPrompt {
+    messages: Vec<LlmMessage>,
+    instructions: Option<String>,
+    /// Citations of the retrieved documents, reported with the response.
+    citations: Vec<Citation>,
+}
+
+impl TurnPrompt {
+    fn new(plan: ContextPlan, documents: Option<RetrievedContext>) -> Self {
+        let (documents, citations) = match documents {
+            Some(retrieved) => (Some(retrieved.instructions), retrieved.citations),
+            None => (None, Vec::new()),
+        };
+        let instructions: Vec<String> = plan
+            .summary_instructions
+            .into_iter()
+            .chain(documents)
+            .collect();
+        Self {
+            messages: plan.messages,
+            instructions: (!instructions.is_empty()).then(|| instructions.join("\n\n")),
+            citations,
+        }
+    }
+
+    /// Prompt with the user message only.
+    #[cfg(test)]
+    fn user(content: &str) -> Self {
+        Self {
+            messages: vec![LlmMessage::user(content)],
+            instructions: None,
+            citations: Vec::new(),
+        }
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// ToolSession — function tools offered for a single turn
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Function tools offered to the model for one turn, with the executor that
+/// runs the calls. `None` when tool calling is disabled or no tools exist.
+#[domain_model]
+struct ToolSession {
+    executor: Arc<dyn ToolExecutor>,
+    config: ToolsConfig,
+    tools: Vec<LlmTool>,
+    tenant_id: Uuid,
+    user_id: Uuid,
+    chat_id: Uuid,
 }
 
 // ════════════════════════════════════════════════════════════════════════════
@@ -156,36 +253,67 @@ fn normalize_error(err: &LlmProviderError) -> (String, String) {
 /// P2 adds turn persistence (pre-stream checks + CAS finalization).
 #[domain_model]
 #[allow(dead_code)]
-pub struct StreamService<TR: TurnRepository, MR: MessageRepository, CR: ChatRepository> {
+pub struct StreamService<
+    TR: TurnRepository,
+    MR: MessageRepository,
+    QR: QuotaUsageRepository,
+    CR: ChatRepository,
+    AR: AttachmentRepository,
+    VR: VectorStoreRepository,
+    SR: ThreadSummaryRepository,
+> {
     db: Arc<DbProvider>,
     turn_repo: Arc<TR>,
     message_repo: Arc<MR>,
     chat_repo: Arc<CR>,
+    quota: Arc<QuotaService<QR>>,
     enforcer: PolicyEnforcer,
     llm: Arc<dyn LlmProvider>,
     streaming_config: StreamingConfig,
+    tool_executor: Option<Arc<dyn ToolExecutor>>,
+    tools_config: ToolsConfig,
+    retrieval: Arc<RetrievalService<AR, VR>>,
+    context: Arc<ContextService<MR, SR, QR>>,
 }
 
-impl<TR: TurnRepository + 'static, MR: MessageRepository + 'static, CR: ChatRepository>
-    StreamService<TR, MR, CR>
+impl<
+    TR: TurnRepository + 'static,
+    MR: MessageRepository + 'static,
+    QR: QuotaUsageRepository + 'static,
+    CR: ChatRepository,
+    AR: AttachmentRepository,
+    VR: VectorStoreRepository,
+    SR: ThreadSummaryRepository + 'static,
+> StreamService<TR, MR, QR, CR, AR, VR, SR>
 {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         db: Arc<DbProvider>,
         turn_repo: Arc<TR>,
         message_repo: Arc<MR>,
         chat_repo: Arc<CR>,
+        quota: Arc<QuotaService<QR>>,
         enforcer: PolicyEnforcer,
         llm: Arc<dyn LlmProvider>,
         streaming_config: StreamingConfig,
+        tool_executor: Option<Arc<dyn ToolExecutor>>,
+        tools_config: ToolsConfig,
+        retrieval: Arc<RetrievalService<AR, VR>>,
+        context: Arc<ContextService<MR, SR, QR>>,
     ) -> Self {
         Self {
             db,
             turn_repo,
             message_repo,
             chat_repo,
+            quota,
             enforcer,
             llm,
             streaming_config,
+            tool_executor,
+            tools_config,
+            retrieval,
+            context,
         }
     }
 
@@ -199,6 +327,136 @@ impl<TR: TurnRepository + 'static, MR: MessageRepository + 'static, CR: ChatRepo
         u64::from(self.streaming_config.sse_ping_interval_seconds)
     }
 
+    /// Resolve the function tools offered for a turn. A failing executor
+    /// does not fail the turn; it runs without tools instead.
+    async fn tool_session(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        chat_id: Uuid,
+    ) -> Option<ToolSession> {
+        let executor = self.tool_executor.as_ref()?;
+        let definitions = match executor.list_tools(tenant_id).await {
+            Ok(definitions) => definitions,
+            Err(e) => {
+                warn!(error = %e, %chat_id, "failed to list tools, streaming without tools");
+                return None;
+            }
+        };
+        if definitions.is_empty() {
+            return None;
+        }
+
+        Some(ToolSession {
+            executor: Arc::clone(executor),
+            config: self.tools_config,
+            tools: definitions
+                .into_iter()
+                .map(|d| LlmTool::Function {
+                    name: d.name,
+                    description: d.description,
+                    parameters: d.parameters,
+                })
+                .collect(),
+            tenant_id,
+            user_id,
+            chat_id,
+        })
+    }
+
+    /// Retrieve excerpts of the chat's documents relevant to `content`.
+    /// A failing retrieval does not fail the turn; it runs without them.
+    async fn document_context(
+        &self,
+        ctx: &SecurityContext,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        content: &str,
+    ) -> Option<RetrievedContext> {
+        match self.retrieval.retrieve(ctx, scope, chat_id, content).await {
+            Ok(retrieved) => retrieved,
+            Err(e) => {
+                warn!(error = %e, %chat_id, "document retrieval failed, streaming without it");
+                None
+            }
+        }
+    }
+
+    /// Fit the chat history, less the turns `exclude_request_ids`, into
+    /// the model's context window. A failing plan does not fail the turn;
+    /// it runs with the user message only.
+    #[allow(clippy::too_many_arguments)]
+    async fn context_plan(
+        &self,
+        ctx: &SecurityContext,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        exclude_request_ids: &[Uuid],
+        model: &str,
+        content: &str,
+        documents: Option<&RetrievedContext>,
+    ) -> ContextPlan {
+        match self
+            .context
+            .plan(
+                ctx,
+                scope,
+                chat_id,
+                exclude_request_ids,
+                model,
+                content,
+                documents.map(|d| d.instructions.as_str()),
+                u32::try_from(MAX_OUTPUT_TOKENS).unwrap_or(0),
+            )
+            .await
+        {
+            Ok(plan) => plan,
+            Err(e) => {
+                warn!(error = %e, %chat_id, "context assembly failed, streaming without history");
+                ContextPlan::without_history(model, content, self.context.strategy())
+            }
+        }
+    }
+
+    /// Record on the turn how its context was assembled.
+    async fn record_context(&self, scope: &AccessScope, turn_id: Uuid, metadata: &ContextMetadata) {
+        let to_i32 = |n: u32| i32::try_from(n).unwrap_or(i32::MAX);
+        let params = RecordContextParams {
+            turn_id,
+            strategy: metadata.strategy.as_str().to_owned(),
+            tokens_estimate: to_i32(metadata.tokens_estimate),
+            history_messages_sent: to_i32(metadata.history_messages_sent),
+            history_messages_truncated: to_i32(metadata.history_messages_truncated),
+            summary_up_to_message_id: metadata.summary_up_to,
+            summarized_messages: to_i32(metadata.summarized_messages),
+        };
+        let recorded = match self.db.conn() {
+            Ok(conn) => self.turn_repo.record_context(&conn, scope, params).await,
+            Err(e) => Err(DomainError::from(e)),
+        };
+        if let Err(e) = recorded {
+            warn!(error = %e, %turn_id, "failed to record turn context metadata");
+        }
+    }
+
+    /// User message of turn `target`, which a retry sends again. `None` if
+    /// it cannot be read; creating the turn then rejects the retry.
+    async fn retry_content(
+        &self,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        target: Uuid,
+    ) -> Option<String> {
+        let conn = self.db.conn().ok()?;
+        self.message_repo
+            .find_by_chat_and_request_id(&conn, scope, chat_id, target)
+            .await
+            .ok()?
+            .into_iter()
+            .find(|m| m.role == MessageRole::User)
+            .map(|m| m.content)
+    }
+
     /// Perform pre-stream checks (idempotency, parallel guard, message/turn
     /// creation) then spawn the provider task.
     ///
@@ -215,9 +473,7 @@ impl<TR: TurnRepository + 'static, MR: MessageRepository + 'static, CR: ChatRepo
         cancel: CancellationToken,
         tx: mpsc::Sender<StreamEvent>,
     ) -> Result<tokio::task::JoinHandle<StreamOutcome>, StreamError> {
-        let tenant_id = ctx.subject_tenant_id();
-        let user_id = ctx.subject_id();
-        let scope = AccessScope::for_tenant(tenant_id);
+        let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
 
         // Non-transactional connection for pre-stream checks (D6)
         let conn = self
@@ -252,20 +508,273 @@ impl<TR: TurnRepository + 'static, MR: MessageRepository + 'static, CR: ChatRepo
             });
         }
 
-        // ── Insert user message + create turn (atomic) ──
+        self.start_turn(
+            ctx,
+            scope,
+            chat_id,
+            request_id,
+            TurnInput::Message(content),
+            model,
+            cancel,
+            tx,
+        )
+        .await
+    }
+
+    /// Regenerate the reply of the latest turn `request_id` as a new
+    /// streamed turn with a server-generated `request_id`.
+    ///
+    /// The old turn is soft-deleted and linked to the new one. Retrying a
+    /// turn that was already replaced returns `StreamError::Replay` with the
+    /// replacing turn.
+    pub(crate) async fn retry_turn(
+        &self,
+        ctx: SecurityContext,
+        chat_id: Uuid,
+        request_id: Uuid,
+        model: String,
+        cancel: CancellationToken,
+        tx: mpsc::Sender<StreamEvent>,
+    ) -> Result<tokio::task::JoinHandle<StreamOutcome>, StreamError> {
+        let scope = self
+            .authorize_mutation(&ctx, chat_id, actions::RETRY_TURN)
+            .await?;
+
+        self.start_turn(
+            ctx,
+            scope,
+            chat_id,
+            Uuid::new_v4(),
+            TurnInput::Retry { target: request_id },
+            model,
+            cancel,
+            tx,
+        )
+        .await
+    }
+
+    /// Replace the user message of the latest turn `request_id` with
+    /// `content` and stream a new reply as the new turn `new_request_id`.
+    ///
+    /// Same rules as [`Self::retry_turn`]; in addition an existing turn
+    /// with `new_request_id` is reported as `StreamError::Replay`.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn edit_turn(
+        &self,
+        ctx: SecurityContext,
+        chat_id: Uuid,
+        request_id: Uuid,
+        new_request_id: Uuid,
+        content: String,
+        model: String,
+        cancel: CancellationToken,
+        tx: mpsc::Sender<StreamEvent>,
+    ) -> Result<tokio::task::JoinHandle<StreamOutcome>, StreamError> {
+        let scope = self
+            .authorize_mutation(&ctx, chat_id, actions::EDIT_TURN)
+            .await?;
+
+        let conn = self
+            .db
+            .conn()
+            .map_err(|e| StreamError::TurnCreationFailed {
+                source: DomainError::from(e),
+            })?;
+        if let Some(existing_turn) = self
+            .turn_repo
+            .find_by_chat_and_request_id(&conn, &scope, chat_id, new_request_id)
+            .await
+            .map_err(|e| StreamError::TurnCreationFailed { source: e })?
+        {
+            return Err(StreamError::Replay {
+                turn: Box::new(existing_turn),
+            });
+        }
+
+        self.start_turn(
+            ctx,
+            scope,
+            chat_id,
+            new_request_id,
+            TurnInput::Edit {
+                target: request_id,
+                content,
+            },
+            model,
+            cancel,
+            tx,
+        )
+        .await
+    }
+
+    async fn authorize_mutation(
+        &self,
+        ctx: &SecurityContext,
+        chat_id: Uuid,
+        action: &str,
+    ) -> Result<AccessScope, StreamError> {
+        authorize_chat(
+            &self.db,
+            &self.enforcer,
+            self.chat_repo.as_ref(),
+            ctx,
+            chat_id,
+            action,
+        )
+        .await
+        .map_err(|source| match source {
+            DomainError::Database { .. } | DomainError::InternalError { .. } => {
+                StreamError::TurnCreationFailed { source }
+            }
+            source => StreamError::Rejected { source },
+        })
+    }
+
+    /// Create the new turn in one transaction, then spawn the provider task.
+    ///
+    /// The model input is planned first, so the quota reserve covers the
+    /// history, summary, documents and tools sent with the turn. The
+    /// transaction then inserts the user message, reserves quota and
+    /// inserts the `running` turn. For retry and edit it first resolves the
+    /// target under the turn mutation rules and soft-deletes it together
+    /// with its messages, so two concurrent mutations cannot both succeed.
+    #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
+    async fn start_turn(
+        &self,
+        ctx: SecurityContext,
+        scope: AccessScope,
+        chat_id: Uuid,
+        request_id: Uuid,
+        input: TurnInput,
+        model: String,
+        cancel: CancellationToken,
+        tx: mpsc::Sender<StreamEvent>,
+    ) -> Result<tokio::task::JoinHandle<StreamOutcome>, StreamError> {
+        let tenant_id = ctx.subject_tenant_id();
+        let user_id = ctx.subject_id();
         let user_msg_id = Uuid::new_v4();
         let turn_id = Uuid::new_v4();
         let requester_type = ctx.subject_type().unwrap_or("user").to_owned();
         let effective_model = model.clone();
 
+        let (target, new_content, audit_event) = match input {
+            TurnInput::Message(content) => (None, Some(content), None),
+            TurnInput::Retry { target } => (Some(target), None, Some("turn_retry")),
+            TurnInput::Edit { target, content } => (Some(target), Some(content), Some("turn_edit")),
+        };
+
+        // The replaced turn is not history of its replacement.
+        let exclude_request_ids: Vec<Uuid> = std::iter::once(request_id).chain(target).collect();
+        let draft = match (&new_content, target) {
+            (Some(content), _) => Some(content.clone()),
+            (None, Some(target)) => self.retry_content(&scope, chat_id, target).await,
+            (None, None) => None,
+        };
+        let (documents, plan) = match &draft {
+            Some(content) => {
+                let documents = self.document_context(&ctx, &scope, chat_id, content).await;
+                let plan = self
+                    .context_plan(
+                        &ctx,
+                        &scope,
+                        chat_id,
+                        &exclude_request_ids,
+                        &model,
+                        content,
+                        documents.as_ref(),
+                    )
+                    .await;
+                (documents, Some(plan))
+            }
+            None => (None, None),
+        };
+        let tools = self.tool_session(tenant_id, user_id, chat_id).await;
+        let counter = TokenCounter::for_model(&model);
+        let estimated_input_tokens = plan
+            .as_ref()
+            .map(|plan| estimated_input_tokens(counter, plan, documents.as_ref(), tools.as_ref()));
+        let max_rounds = max_rounds(tools.as_ref());
+
         let message_repo = Arc::clone(&self.message_repo);
         let turn_repo = Arc::clone(&self.turn_repo);
-        let content_clone = content.clone();
+        let quota = Arc::clone(&self.quota);
         let scope_tx = scope.clone();
 
-        self.db
+        let prepared = self
+            .db
             .transaction(|tx| {
                 Box::pin(async move {
+                    let db_err = |e: DomainError| modkit_db::DbError::Other(anyhow::anyhow!(e));
+
+                    let content = if let Some(target) = target {
+                        let turn = match resolve_mutation_target(
+                            turn_repo.as_ref(),
+                            tx,
+                            &scope_tx,
+                            chat_id,
+                            target,
+                            user_id,
+                        )
+                        .await
+                        {
+                            Ok(MutationTarget::Mutable(turn)) => turn,
+                            Ok(MutationTarget::Replaced(by)) => {
+                                let replacement = turn_repo
+                                    .find_by_chat_and_request_id(tx, &scope_tx, chat_id, by)
+                                    .await
+                                    .map_err(db_err)?;
+                                return Ok(Err(match replacement {
+                                    Some(turn) => StreamError::Replay {
+                                        turn: Box::new(turn),
+                                    },
+                                    None => StreamError::Rejected {
+                                        source: DomainError::not_found("Turn", by),
+                                    },
+                                }));
+                            }
+                            Ok(MutationTarget::Deleted) => {
+                                return Ok(Err(StreamError::Rejected {
+                                    source: DomainError::conflict(
+                                        "not_latest_turn",
+                                        format!("Turn {target} was deleted"),
+                                    ),
+                                }));
+                            }
+                            Err(source) => return Ok(Err(StreamError::Rejected { source })),
+                        };
+
+                        let content = if let Some(content) = new_content {
+                            content
+                        } else {
+                            let messages = message_repo
+                                .find_by_chat_and_request_id(tx, &scope_tx, chat_id, target)
+                                .await
+                                .map_err(db_err)?;
+                            let Some(user_msg) =
+                                messages.into_iter().find(|m| m.role == MessageRole::User)
+                            else {
+                                return Ok(Err(StreamError::Rejected {
+                                    source: DomainError::invalid_turn_state(format!(
+                                        "Turn {target} has no user message to retry"
+                                    )),
+                                }));
+                            };
+                            user_msg.content
+                        };
+
+                        turn_repo
+                            .soft_delete(tx, &scope_tx, turn.id, Some(request_id))
+                            .await
+                            .map_err(db_err)?;
+                        message_repo
+                            .soft_delete_by_request_id(tx, &scope_tx, chat_id, target)
+                            .await
+                            .map_err(db_err)?;
+                        content
+                    } else {
+                        new_content.unwrap_or_default()
+                    };
+
                     message_repo
                         .insert_user_message(
                             tx,
@@ -275,11 +784,20 @@ impl<TR: TurnRepository + 'static, MR: MessageRepository + 'static, CR: ChatRepo
                                 tenant_id,
                                 chat_id,
                                 request_id,
-                                content: content_clone,
+                                content: content.clone(),
                             },
                         )
                         .await
-                        .map_err(|e| modkit_db::DbError::Other(anyhow::anyhow!(e)))?;
+                        .map_err(db_err)?;
+
+                    let reservation = QuotaReservation::preflight(
+                        estimated_input_tokens.unwrap_or_else(|| counter.count_message(&content)),
+                        max_rounds,
+                    );
+                    quota
+                        .reserve(tx, &scope_tx, tenant_id, user_id, &reservation)
+                        .await
+                        .map_err(db_err)?;
 
                     turn_repo
                         .create_turn(
@@ -292,55 +810,175 @@ impl<TR: TurnRepository + 'static, MR: MessageRepository + 'static, CR: ChatRepo
                                 request_id,
                                 requester_type,
                                 requester_user_id: Some(user_id),
-                                reserve_tokens: None,
-                                max_output_tokens_applied: None,
-                                reserved_credits_micro: None,
+                                reserve_tokens: Some(reservation.reserve_tokens()),
+                                max_output_tokens_applied: Some(reservation.max_output_tokens),
+                                reserved_credits_micro: Some(reservation.reserved_credits_micro),
                                 policy_version_applied: None,
                                 effective_model: Some(effective_model),
-                                minimal_generation_floor_applied: None,
+                                minimal_generation_floor_applied: Some(
+                                    reservation.minimal_generation_floor,
+                                ),
                             },
                         )
                         .await
-                        .map_err(|e| modkit_db::DbError::Other(anyhow::anyhow!(e)))?;
+                        .map_err(db_err)?;
 
-                    Ok(())
+                    Ok(Ok((content, reservation)))
                 })
             })
             .await
-            .map_err(|e| StreamError::TurnCreationFailed {
-                source: DomainError::from(e),
+            .map_err(|e| {
+                if target.is_some() && is_unique_violation(&e) {
+                    StreamError::Conflict {
+                        code: "generation_in_progress".to_owned(),
+                        message: format!("Chat {chat_id} already has a running turn"),
+                    }
+                } else {
+                    StreamError::TurnCreationFailed {
+                        source: DomainError::from(e),
+                    }
+                }
             })?;
+        let (content, reservation) = prepared?;
+
+        if let (Some(event), Some(target)) = (audit_event, target) {
+            info!(
+                event,
+                actor_user_id = %user_id,
+                %chat_id,
+                original_request_id = %target,
+                new_request_id = %request_id,
+                "turn replaced"
+            );
+        }
 
         // Pre-generate assistant message ID (sent in DoneData and used in CAS)
         let message_id = Uuid::new_v4();
 
+        let plan = plan.unwrap_or_else(|| {
+            ContextPlan::without_history(&model, &content, self.context.strategy())
+        });
+        self.record_context(&scope, turn_id, &plan.metadata).await;
+
         let persist = PersistenceCtx {
             db: Arc::clone(&self.db),
             turn_repo: Arc::clone(&self.turn_repo),
             message_repo: Arc::clone(&self.message_repo),
+            quota: Arc::clone(&self.quota),
             scope,
             turn_id,
             tenant_id,
+            user_id,
             chat_id,
             request_id,
             message_id,
+            reservation,
         };
 
         Ok(spawn_provider_task(
             Arc::clone(&self.llm),
             ctx,
-            content,
+            TurnPrompt::new(plan, documents),
             model,
             cancel,
             tx,
+            tools,
             Some(persist),
         ))
     }
 }
 
+/// `estimated_input_tokens` of the preflight reserve: the context plan and
+/// retrieved documents, plus the definitions of the tools offered.
+fn estimated_input_tokens(
+    counter: TokenCounter,
+    plan: &ContextPlan,
+    documents: Option<&RetrievedContext>,
+    tools: Option<&ToolSession>,
+) -> u32 {
+    let documents = documents.map_or(0, |d| counter.count(&d.instructions));
+    let tools: u32 = tools.map_or(0, |session| {
+        session
+            .tools
+            .iter()
+            .map(|tool| match tool {
+                LlmTool::Function {
+                    name,
+                    description,
+                    parameters,
+                } => counter
+                    .count(name)
+                    .saturating_add(counter.count(description))
+                    .saturating_add(counter.count(&parameters.to_string())),
+                LlmTool::FileSearch { .. } | LlmTool::WebSearch => 0,
+            })
+            .fold(0, u32::saturating_add)
+    });
+    plan.metadata
+        .tokens_estimate
+        .saturating_add(documents)
+        .saturating_add(tools)
+}
+
+/// Provider rounds a turn may run: the answer, plus one round per tool step.
+fn max_rounds(tools: Option<&ToolSession>) -> u32 {
+    tools.map_or(1, |session| u32::from(session.config.max_steps) + 1)
+}
+
+/// Whether a failed turn-creation transaction hit a unique index, i.e. a
+/// concurrent request created a turn first.
+fn is_unique_violation(e: &modkit_db::DbError) -> bool {
+    let modkit_db::DbError::Other(err) = e else {
+        return false;
+    };
+    matches!(
+        err.downcast_ref::<DomainError>(),
+        Some(DomainError::Conflict { code, .. }) if code == "unique_violation"
+    )
+}
+/// How the provider loop ended, before finalization.
+#[domain_model]
+enum TurnEnd {
+    Completed {
+        usage: Usage,
+        response_id: String,
+    },
+    Incomplete {
+        usage: Usage,
+        reason: String,
+    },
+    Failed {
+        code: String,
+        message: String,
+        usage: Option<Usage>,
+        partial_usage: bool,
+    },
+    Cancelled {
+        usage: Option<Usage>,
+    },
+}
+
+/// Add a round's usage to the usage of earlier tool-calling rounds.
+fn add_usage(prior: Option<Usage>, usage: Usage) -> Usage {
+    match prior {
+        Some(p) => Usage {
+            input_tokens: p.input_tokens + usage.input_tokens,
+            output_tokens: p.output_tokens + usage.output_tokens,
+        },
+        None => usage,
+    }
+}
+
 /// Core provider task: reads from the LLM, translates events, and returns
 /// a [`StreamOutcome`]. After the stream ends, CAS-finalizes the turn if
 /// a persistence context is provided.
+///
+/// With a [`ToolSession`], function calls returned by the provider are
+/// executed and fed back in a new request until the model answers or
+/// `max_steps` rounds are exhausted. Usage is summed across rounds.
+///
+/// The [`TurnPrompt`] carries the history, summary and retrieved documents;
+/// document citations are reported with the completed response.
 #[allow(
     clippy::too_many_arguments,
     clippy::too_many_lines,
@@ -348,171 +986,189 @@ impl<TR: TurnRepository + 'static, MR: MessageRepository + 'static, CR: ChatRepo
     clippy::let_underscore_must_use,
     clippy::cast_possible_truncation
 )]
-fn spawn_provider_task<TR: TurnRepository + 'static, MR: MessageRepository + 'static>(
+fn spawn_provider_task<
+    TR: TurnRepository + 'static,
+    MR: MessageRepository + 'static,
+    QR: QuotaUsageRepository + 'static,
+>(
     llm: Arc<dyn LlmProvider>,
     ctx: SecurityContext,
-    content: String,
+    prompt: TurnPrompt,
     model: String,
     cancel: CancellationToken,
     tx: mpsc::Sender<StreamEvent>,
-    persist: Option<PersistenceCtx<TR, MR>>,
+    tools: Option<ToolSession>,
+    persist: Option<PersistenceCtx<TR, MR, QR>>,
 ) -> tokio::task::JoinHandle<StreamOutcome> {
     tokio::spawn(async move {
         let stream_start = std::time::Instant::now();
         let mut first_token_time: Option<std::time::Duration> = None;
         let msg_id_str = persist.as_ref().map(|p| p.message_id.to_string());
 
-        // Build the LLM request
-        let request = LlmRequestBuilder::new(&model)
-            .message(LlmMessage::user(&content))
-            .build_streaming();
+        let TurnPrompt {
+            mut messages,
+            instructions,
+            mut citations,
+        } = prompt;
+        let mut accumulated_text = String::new();
+        // Usage of finished tool-calling rounds, accounted with the last round.
+        let mut prior_usage: Option<Usage> = None;
+        let mut tool_steps: u32 = 0;
 
-        // Call the provider to start streaming
-        let stream_result = llm.stream(ctx, request, cancel.clone()).await;
+        let end = 'turn: loop {
+            let step_start = accumulated_text.len();
 
-        let mut provider_stream = match stream_result {
-            Ok(s) => s,
-            Err(e) => {
-                // Provider failed before any events — send error and return.
-                let (code, message) = normalize_error(&e);
-                let _ = tx
-                    .send(StreamEvent::Error(ErrorData {
-                        code: code.clone(),
+            // Build the LLM request
+            let mut builder = LlmRequestBuilder::new(&model).messages(messages.clone());
+            if let Some(ref instructions) = instructions {
+                builder = builder.system_instructions(instructions.clone());
+            }
+            if let Some(ref session) = tools {
+                builder = builder.tools(session.tools.clone());
+            }
+            let request = builder.build_streaming();
+
+            // Call the provider to start streaming
+            let mut provider_stream = match llm.stream(ctx.clone(), request, cancel.clone()).await {
+                Ok(s) => s,
+                Err(e) => {
+                    // Provider failed before any events of this round.
+                    let (code, message) = normalize_error(&e);
+                    break 'turn TurnEnd::Failed {
+                        code,
                         message,
-                    }))
-                    .await;
-
-                // CAS finalize: mark turn as failed
-                if let Some(ref p) = persist {
-                    cas_finalize_terminal(p, TurnState::Failed, Some(code.clone()), None).await;
+                        usage: prior_usage,
+                        partial_usage: false,
+                    };
                 }
+            };
 
-                return StreamOutcome {
-                    terminal: StreamTerminal::Failed,
-                    accumulated_text: String::new(),
-                    usage: None,
-                    effective_model: model,
-                    error_code: Some(code),
-                    provider_response_id: None,
-                    provider_partial_usage: false,
-                };
-            }
-        };
-
-        // Read events from provider, translate and forward through channel
-        let mut accumulated_text = String::new();
-        let mut cancelled = false;
+            // Read events from provider, translate and forward through channel
+            let mut cancelled = false;
 
-        loop {
-            tokio::select! {
-                biased;
+            loop {
+                tokio::select! {
+                    biased;
 
-                () = cancel.cancelled() => {
-                    debug!("stream cancelled, aborting provider");
-                    provider_stream.cancel();
-                    cancelled = true;
-                    break;
-                }
+                    () = cancel.cancelled() => {
+                        debug!("stream cancelled, aborting provider");
+                        provider_stream.cancel();
+                        cancelled = true;
+                        break;
+                    }
 
-                event = provider_stream.next() => {
-                    match event {
-                        Some(Ok(client_event)) => {
-                            if let ClientSseEvent::Delta { ref content, .. } = client_event {
-                                if first_token_time.is_none() {
-                                    let ttft = stream_start.elapsed();
-                                    first_token_time = Some(ttft);
-                                    debug!(
-                                        time_to_first_token_ms = ttft.as_millis() as u64,
-                                        model = %model,
-                                        "first token received"
-                                    );
+                    event = provider_stream.next() => {
+                        match event {
+                            Some(Ok(client_event)) => {
+                                // Calls executed by the server are reported
+                                // as tool_call/tool_result events instead.
+                                if tools.is_some()
+                                    && matches!(
+                                        client_event,
+                                        ClientSseEvent::Tool { name: "function_call", .. }
+                                    )
+                                {
+                                    continue;
+                                }
+                                if let ClientSseEvent::Delta { ref content, .. } = client_event {
+                                    if first_token_time.is_none() {
+                                        let ttft = stream_start.elapsed();
+                                        first_token_time = Some(ttft);
+                                        debug!(
+                                            time_to_first_token_ms = ttft.as_millis() as u64,
+                                            model = %model,
+                                            "first token received"
+                                        );
+                                    }
+                                    accumulated_text.push_str(content);
+                                }
+                                let stream_event = StreamEvent::from(client_event);
+                                if tx.send(stream_event).await.is_err() {
+                                    // Receiver dropped (client disconnect handled by relay)
+                                    debug!("channel closed, exiting provider task");
+                                    break;
                                 }
-                                accumulated_text.push_str(content);
-                            }
-                            let stream_event = StreamEvent::from(client_event);
-                            if tx.send(stream_event).await.is_err() {
-                                // Receiver dropped (client disconnect handled by relay)
-                                debug!("channel closed, exiting provider task");
-                                break;
                             }
-                        }
-                        Some(Err(e)) => {
-                            warn!(error = %e, "provider stream error");
-                            let (code, message) =
-                                normalize_error(&LlmProviderError::StreamError(e));
-                            let _ = tx
-                                .send(StreamEvent::Error(ErrorData {
-                                    code: code.clone(),
+                            Some(Err(e)) => {
+                                warn!(error = %e, "provider stream error");
+                                let (code, message) =
+                                    normalize_error(&LlmProviderError::StreamError(e));
+                                break 'turn TurnEnd::Failed {
+                                    code,
                                     message,
-                                }))
-                                .await;
-
-                            // CAS finalize: mark turn as failed
-                            if let Some(ref p) = persist {
-                                cas_finalize_terminal(
-                                    p,
-                                    TurnState::Failed,
-                                    Some(code.clone()),
-                                    None,
-                                ).await;
+                                    usage: prior_usage,
+                                    partial_usage: !accumulated_text.is_empty(),
+                                };
+                            }
+                            None => {
+                                // Stream ended — terminal captured by ProviderStream
+                                break;
                             }
-
-                            let has_partial = !accumulated_text.is_empty();
-                            return StreamOutcome {
-                                terminal: StreamTerminal::Failed,
-                                accumulated_text,
-                                usage: None,
-                                effective_model: model,
-                                error_code: Some(code),
-                                provider_response_id: None,
-                                provider_partial_usage: has_partial,
-                            };
-                        }
-                        None => {
-                            // Stream ended — terminal captured by ProviderStream
-                            break;
                         }
                     }
                 }
             }
-        }
-
-        if cancelled {
-            let elapsed = stream_start.elapsed();
-            info!(
-                terminal = "cancelled",
-                model = %model,
-                duration_ms = elapsed.as_millis() as u64,
-                "stream cancelled"
-            );
 
-            // CAS finalize: mark turn as cancelled
-            if let Some(ref p) = persist {
-                cas_finalize_terminal(p, TurnState::Cancelled, None, None).await;
+            if cancelled {
+                break 'turn TurnEnd::Cancelled { usage: prior_usage };
             }
 
-            return StreamOutcome {
-                terminal: StreamTerminal::Cancelled,
-                accumulated_text,
-                usage: None,
-                effective_model: model,
-                error_code: None,
-                provider_response_id: None,
-                provider_partial_usage: false,
-            };
-        }
+            // Extract the terminal outcome from the provider stream
+            match provider_stream.into_outcome().await {
+                TerminalOutcome::Completed {
+                    usage,
+                    citations: round_citations,
+                    tool_calls,
+                    response_id,
+                    ..
+                } => {
+                    citations.extend(round_citations);
+                    let usage = add_usage(prior_usage, usage);
+                    let Some(ref session) = tools else {
+                        break 'turn TurnEnd::Completed { usage, response_id };
+                    };
+                    if tool_calls.is_empty() {
+                        break 'turn TurnEnd::Completed { usage, response_id };
+                    }
+                    if tool_steps >= u32::from(session.config.max_steps) {
+                        break 'turn TurnEnd::Incomplete {
+                            usage,
+                            reason: "tool_step_limit".to_owned(),
+                        };
+                    }
 
-        // Extract the terminal outcome from the provider stream
-        let terminal = provider_stream.into_outcome().await;
+                    tool_steps += 1;
+                    prior_usage = Some(usage);
+                    messages.push(LlmMessage::assistant_tool_calls(
+                        &accumulated_text[step_start..],
+                        &tool_calls,
+                    ));
+                    match execute_tool_calls(session, &tool_calls, tool_steps, &tx, &cancel).await {
+                        Some(results) => messages.push(LlmMessage::tool_results(results)),
+                        None => break 'turn TurnEnd::Cancelled { usage: prior_usage },
+                    }
+                }
+                TerminalOutcome::Incomplete { usage, reason, .. } => {
+                    break 'turn TurnEnd::Incomplete {
+                        usage: add_usage(prior_usage, usage),
+                        reason,
+                    };
+                }
+                TerminalOutcome::Failed { error, usage, .. } => {
+                    let (code, message) = normalize_error(&error);
+                    break 'turn TurnEnd::Failed {
+                        code,
+                        message,
+                        usage: usage.map(|u| add_usage(prior_usage, u)).or(prior_usage),
+                        partial_usage: usage.is_some(),
+                    };
+                }
+            }
+        };
 
-        match terminal {
-            TerminalOutcome::Completed {
-                usage,
-                content: _,
-                citations,
-                response_id,
-                ..
-            } => {
+        let elapsed = stream_start.elapsed();
+        match end {
+            TurnEnd::Completed { usage, response_id } => {
                 // Send citations if present
                 if !citations.is_empty() {
                     let _ = tx
@@ -533,12 +1189,12 @@ fn spawn_provider_task<TR: TurnRepository + 'static, MR: MessageRepository + 'st
                         downgrade_reason: None,         // P3 provides
                     })))
                     .await;
-                let elapsed = stream_start.elapsed();
                 info!(
                     terminal = "completed",
                     model = %model,
                     input_tokens = usage.input_tokens,
                     output_tokens = usage.output_tokens,
+                    tool_steps,
                     duration_ms = elapsed.as_millis() as u64,
                     "stream completed"
                 );
@@ -563,9 +1219,10 @@ fn spawn_provider_task<TR: TurnRepository + 'static, MR: MessageRepository + 'st
                     error_code: None,
                     provider_response_id: Some(response_id),
                     provider_partial_usage: false,
+                    tool_steps,
                 }
             }
-            TerminalOutcome::Incomplete { usage, reason, .. } => {
+            TurnEnd::Incomplete { usage, reason } => {
                 let _ = tx
                     .send(StreamEvent::Done(Box::new(DoneData {
                         message_id: msg_id_str,
@@ -577,11 +1234,11 @@ fn spawn_provider_task<TR: TurnRepository + 'static, MR: MessageRepository + 'st
                         downgrade_reason: None,
                     })))
                     .await;
-                let elapsed = stream_start.elapsed();
                 warn!(
                     terminal = "incomplete",
                     model = %model,
                     reason = %reason,
+                    tool_steps,
                     duration_ms = elapsed.as_millis() as u64,
                     "stream incomplete"
                 );
@@ -607,28 +1264,34 @@ fn spawn_provider_task<TR: TurnRepository + 'static, MR: MessageRepository + 'st
                     error_code: Some(format!("incomplete:{reason}")),
                     provider_response_id: None,
                     provider_partial_usage: false,
+                    tool_steps,
                 }
             }
-            TerminalOutcome::Failed { error, usage, .. } => {
-                let (code, message) = normalize_error(&error);
+            TurnEnd::Failed {
+                code,
+                message,
+                usage,
+                partial_usage,
+            } => {
                 let _ = tx
                     .send(StreamEvent::Error(ErrorData {
                         code: code.clone(),
                         message,
                     }))
                     .await;
-                let elapsed = stream_start.elapsed();
                 warn!(
                     terminal = "failed",
                     model = %model,
                     error_code = %code,
+                    tool_steps,
                     duration_ms = elapsed.as_millis() as u64,
                     "stream failed"
                 );
 
                 // CAS finalize: mark turn as failed
                 if let Some(ref p) = persist {
-                    cas_finalize_terminal(p, TurnState::Failed, Some(code.clone()), None).await;
+                    cas_finalize_terminal(p, TurnState::Failed, Some(code.clone()), None, usage)
+                        .await;
                 }
 
                 StreamOutcome {
@@ -638,13 +1301,165 @@ fn spawn_provider_task<TR: TurnRepository + 'static, MR: MessageRepository + 'st
                     effective_model: model,
                     error_code: Some(code),
                     provider_response_id: None,
-                    provider_partial_usage: usage.is_some(),
+                    provider_partial_usage: partial_usage,
+                    tool_steps,
+                }
+            }
+            TurnEnd::Cancelled { usage } => {
+                info!(
+                    terminal = "cancelled",
+                    model = %model,
+                    tool_steps,
+                    duration_ms = elapsed.as_millis() as u64,
+                    "stream cancelled"
+                );
+
+                // CAS finalize: mark turn as cancelled
+                if let Some(ref p) = persist {
+                    cas_finalize_terminal(p, TurnState::Cancelled, None, None, usage).await;
+                }
+
+                StreamOutcome {
+                    terminal: StreamTerminal::Cancelled,
+                    accumulated_text,
+                    usage,
+                    effective_model: model,
+                    error_code: None,
+                    provider_response_id: None,
+                    provider_partial_usage: false,
+                    tool_steps,
                 }
             }
         }
     })
 }
 
+// ════════════════════════════════════════════════════════════════════════════
+// Tool execution
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Execute one round of function calls, streaming a `tool_call` event per
+/// call before execution and a `tool_result` event per call after.
+///
+/// Calls run concurrently, each bounded by `call_timeout_seconds`; calls
+/// beyond `max_calls_per_step` are answered with an error without running.
+/// Every call gets a result so the provider sees a complete round. Returns
+/// `None` if the turn is cancelled while calls are running.
+#[allow(clippy::let_underscore_must_use, clippy::cast_possible_truncation)]
+async fn execute_tool_calls(
+    session: &ToolSession,
+    calls: &[ToolCall],
+    step: u32,
+    tx: &mpsc::Sender<StreamEvent>,
+    cancel: &CancellationToken,
+) -> Option<Vec<ContentPart>> {
+    let limit = usize::from(session.config.max_calls_per_step);
+    let timeout = Duration::from_secs(u64::from(session.config.call_timeout_seconds));
+    let ids: Vec<Uuid> = calls.iter().map(|_| Uuid::new_v4()).collect();
+    let arguments: Vec<Option<serde_json::Value>> = calls
+        .iter()
+        .map(|call| serde_json::from_str(&call.arguments).ok())
+        .collect();
+
+    for ((call, id), args) in calls.iter().zip(&ids).zip(&arguments) {
+        let _ = tx
+            .send(StreamEvent::ToolCall(ToolCallData {
+                id: *id,
+                step,
+                name: call.name.clone(),
+                arguments: args
+                    .clone()
+                    .unwrap_or_else(|| serde_json::Value::String(call.arguments.clone())),
+            }))
+            .await;
+    }
+
+    let runs = calls
+        .iter()
+        .zip(arguments)
+        .enumerate()
+        .map(|(index, (call, args))| async move {
+            if index >= limit {
+                let output = ToolCallOutput {
+                    content: format!("not executed: at most {limit} tool calls per step"),
+                    is_error: true,
+                };
+                return (ToolResultStatus::Skipped, output, Duration::ZERO);
+            }
+            let Some(arguments) = args else {
+                let output = ToolCallOutput {
+                    content: "invalid tool arguments: not valid JSON".to_owned(),
+                    is_error: true,
+                };
+                return (ToolResultStatus::Error, output, Duration::ZERO);
+            };
+
+            let started = Instant::now();
+            let request = ToolCallRequest {
+                tenant_id: session.tenant_id,
+                user_id: session.user_id,
+                chat_id: session.chat_id,
+                name: call.name.clone(),
+                arguments,
+            };
+            let (status, output) =
+                match tokio::time::timeout(timeout, session.executor.execute(request)).await {
+                    Ok(Ok(output)) if output.is_error => (ToolResultStatus::Error, output),
+                    Ok(Ok(output)) => (ToolResultStatus::Ok, output),
+                    Ok(Err(e)) => {
+                        warn!(error = %e, tool = %call.name, "tool execution failed");
+                        let output = ToolCallOutput {
+                            content: "tool execution failed".to_owned(),
+                            is_error: true,
+                        };
+                        (ToolResultStatus::Error, output)
+                    }
+                    Err(_) => {
+                        warn!(tool = %call.name, "tool execution timed out");
+                        let output = ToolCallOutput {
+                            content: format!(
+                                "tool execution timed out after {}s",
+                                timeout.as_secs()
+                            ),
+                            is_error: true,
+                        };
+                        (ToolResultStatus::Timeout, output)
+                    }
+                };
+            (status, output, started.elapsed())
+        });
+
+    let results = tokio::select! {
+        biased;
+
+        () = cancel.cancelled() => {
+            debug!("stream cancelled during tool execution");
+            return None;
+        }
+
+        results = futures::future::join_all(runs) => results,
+    };
+
+    let mut parts = Vec::with_capacity(calls.len());
+    for ((call, id), (status, output, elapsed)) in calls.iter().zip(ids).zip(results) {
+        let _ = tx
+            .send(StreamEvent::ToolResult(ToolResultData {
+                id,
+                step,
+                name: call.name.clone(),
+                status,
+                duration_ms: elapsed.as_millis() as u64,
+            }))
+            .await;
+        parts.push(ContentPart::ToolResult {
+            call_id: call.call_id.clone(),
+            content: output.content,
+            is_error: output.is_error,
+        });
+    }
+    Some(parts)
+}
+
 // ════════════════════════════════════════════════════════════════════════════
 // CAS finalization helpers
 // ════════════════════════════════════════════════════════════════════════════
@@ -652,8 +1467,12 @@ fn spawn_provider_task<TR: TurnRepository + 'static, MR: MessageRepository + 'st
 /// CAS-finalize a completed/incomplete turn: insert assistant message then
 /// update turn to `completed`.
 #[allow(clippy::cognitive_complexity)]
-async fn cas_finalize_completed<TR: TurnRepository, MR: MessageRepository>(
-    p: &PersistenceCtx<TR, MR>,
+async fn cas_finalize_completed<
+    TR: TurnRepository,
+    MR: MessageRepository,
+    QR: QuotaUsageRepository,
+>(
+    p: &PersistenceCtx<TR, MR, QR>,
     text: &str,
     usage: Option<Usage>,
     model: Option<String>,
@@ -703,7 +1522,11 @@ async fn cas_finalize_completed<TR: TurnRepository, MR: MessageRepository>(
                 .await;
             match rows {
                 Ok(0) => warn!(turn_id = %p.turn_id, "CAS completed: lost race (0 rows)"),
-                Ok(_) => debug!(turn_id = %p.turn_id, "CAS completed: turn finalized"),
+                Ok(_) => {
+                    debug!(turn_id = %p.turn_id, "CAS completed: turn finalized");
+                    let settlement = usage.map_or(Settlement::Aborted(None), Settlement::Completed);
+                    settle_quota(p, &conn, settlement).await;
+                }
                 Err(e) => warn!(error = %e, turn_id = %p.turn_id, "CAS completed: update failed"),
             }
         }
@@ -715,11 +1538,16 @@ async fn cas_finalize_completed<TR: TurnRepository, MR: MessageRepository>(
 
 /// CAS-finalize a terminal (failed/cancelled) turn.
 #[allow(clippy::cognitive_complexity)]
-async fn cas_finalize_terminal<TR: TurnRepository, MR: MessageRepository>(
-    p: &PersistenceCtx<TR, MR>,
+async fn cas_finalize_terminal<
+    TR: TurnRepository,
+    MR: MessageRepository,
+    QR: QuotaUsageRepository,
+>(
+    p: &PersistenceCtx<TR, MR, QR>,
     state: TurnState,
     error_code: Option<String>,
     error_detail: Option<String>,
+    usage: Option<Usage>,
 ) {
     let conn = match p.db.conn() {
         Ok(c) => c,
@@ -746,21 +1574,59 @@ async fn cas_finalize_terminal<TR: TurnRepository, MR: MessageRepository>(
 
     match rows {
         Ok(0) => warn!(turn_id = %p.turn_id, "CAS terminal: lost race (0 rows)"),
-        Ok(_) => debug!(turn_id = %p.turn_id, state = %state_label, "CAS terminal: turn finalized"),
+        Ok(_) => {
+            debug!(turn_id = %p.turn_id, state = %state_label, "CAS terminal: turn finalized");
+            settle_quota(p, &conn, Settlement::Aborted(usage)).await;
+        }
         Err(e) => warn!(error = %e, turn_id = %p.turn_id, "CAS terminal: update failed"),
     }
 }
 
+/// Settle the turn's quota reserve after the CAS finalizer won.
+async fn settle_quota<TR: TurnRepository, MR: MessageRepository, QR: QuotaUsageRepository>(
+    p: &PersistenceCtx<TR, MR, QR>,
+    conn: &impl modkit_db::secure::DBRunner,
+    settlement: Settlement,
+) {
+    if let Err(e) = p
+        .quota
+        .settle(
+            conn,
+            &p.scope,
+            p.tenant_id,
+            p.user_id,
+            &p.reservation,
+            settlement,
+        )
+        .await
+    {
+        warn!(error = %e, turn_id = %p.turn_id, "quota settlement failed");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{AttachmentsConfig, ContextConfig};
+    use crate::domain::service::AttachmentService;
+    use crate::domain::service::test_helpers::{
+        context_service, hashing_embedder, inmem_db, mock_db_provider, mock_enforcer,
+        orm_chat_repo, retrieval_service, seed_chat, test_security_ctx, text_parser,
+    };
+    use crate::infra::db::repo::attachment_repo::AttachmentRepository as AttachmentRepo;
     use crate::infra::db::repo::message_repo::MessageRepository as MsgRepo;
+    use crate::infra::db::repo::quota_usage_repo::QuotaUsageRepository as QuotaRepo;
+    use crate::infra::db::repo::thread_summary_repo::ThreadSummaryRepository as SummaryRepo;
     use crate::infra::db::repo::turn_repo::TurnRepository as TurnRepo;
+    use crate::infra::db::repo::vector_store_repo::VectorStoreRepository as VectorStoreRepo;
+    use crate::infra::llm::request::Role;
     use crate::infra::llm::{
         LlmRequest, NonStreaming, ProviderStream, ResponseResult, Streaming, TranslatedEvent,
     };
     use futures::stream;
+    use mini_chat_sdk::ToolDefinition;
     use oagw_sdk::error::StreamingError;
+    use std::collections::VecDeque;
 
     #[test]
     fn normalize_rate_limited() {
@@ -834,6 +1700,7 @@ mod tests {
                 response_id: "resp-test".to_owned(),
                 content: full_text,
                 citations: vec![],
+                tool_calls: vec![],
                 raw_response: serde_json::Value::Null,
             })));
 
@@ -891,14 +1758,15 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<StreamEvent>(32);
         let cancel = CancellationToken::new();
 
-        let handle = spawn_provider_task::<TurnRepo, MsgRepo>(
+        let handle = spawn_provider_task::<TurnRepo, MsgRepo, QuotaRepo>(
             provider,
             mock_ctx(),
-            "hi".into(),
+            TurnPrompt::user("hi"),
             "test-model".into(),
             cancel,
             tx,
             None,
+            None,
         );
 
         // Collect all events from the channel
@@ -934,14 +1802,15 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<StreamEvent>(32);
         let cancel = CancellationToken::new();
 
-        let handle = spawn_provider_task::<TurnRepo, MsgRepo>(
+        let handle = spawn_provider_task::<TurnRepo, MsgRepo, QuotaRepo>(
             provider,
             mock_ctx(),
-            "hi".into(),
+            TurnPrompt::user("hi"),
             "test-model".into(),
             cancel,
             tx,
             None,
+            None,
         );
 
         let mut events = Vec::new();
@@ -1008,14 +1877,15 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<StreamEvent>(32);
         let cancel = CancellationToken::new();
 
-        let handle = spawn_provider_task::<TurnRepo, MsgRepo>(
+        let handle = spawn_provider_task::<TurnRepo, MsgRepo, QuotaRepo>(
             provider,
             mock_ctx(),
-            "hi".into(),
+            TurnPrompt::user("hi"),
             "test-model".into(),
             cancel.clone(),
             tx,
             None,
+            None,
         );
 
         // Read the first delta
@@ -1030,4 +1900,736 @@ mod tests {
         assert_eq!(outcome.terminal, StreamTerminal::Cancelled);
         assert_eq!(outcome.accumulated_text, "partial");
     }
+
+    // ── Tool-calling loop ──
+
+    type Round = Vec<Result<TranslatedEvent, StreamingError>>;
+
+    /// A provider that plays one scripted round per `stream()` call and
+    /// records the messages and system instructions of every request.
+    #[allow(de0309_must_have_domain_model)]
+    struct ScriptedProvider {
+        rounds: std::sync::Mutex<VecDeque<Round>>,
+        requests: std::sync::Mutex<Vec<Vec<LlmMessage>>>,
+        instructions: std::sync::Mutex<Vec<Option<String>>>,
+    }
+
+    impl ScriptedProvider {
+        fn new(rounds: Vec<Round>) -> Self {
+            Self {
+                rounds: std::sync::Mutex::new(rounds.into()),
+                requests: std::sync::Mutex::new(Vec::new()),
+                instructions: std::sync::Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl LlmProvider for ScriptedProvider {
+        async fn stream(
+            &self,
+            _ctx: SecurityContext,
+            request: LlmRequest<Streaming>,
+            cancel: CancellationToken,
+        ) -> Result<ProviderStream, LlmProviderError> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push(request.messages().to_vec());
+            self.instructions
+                .lock()
+                .unwrap()
+                .push(request.system_instructions);
+            let round = self.rounds.lock().unwrap().pop_front().unwrap_or_default();
+            Ok(ProviderStream::new(stream::iter(round), cancel))
+        }
+
+        async fn complete(
+            &self,
+            _ctx: SecurityContext,
+            _request: LlmRequest<NonStreaming>,
+        ) -> Result<ResponseResult, LlmProviderError> {
+            unimplemented!("not needed for streaming tests")
+        }
+    }
+
+    fn tool_call_round(call_id: &str, name: &str, arguments: &str) -> Round {
+        vec![Ok(TranslatedEvent::Terminal(TerminalOutcome::Completed {
+            usage: Usage {
+                input_tokens: 10,
+                output_tokens: 5,
+            },
+            response_id: "resp-tool".to_owned(),
+            content: String::new(),
+            citations: vec![],
+            tool_calls: vec![ToolCall {
+                call_id: call_id.to_owned(),
+                name: name.to_owned(),
+                arguments: arguments.to_owned(),
+            }],
+            raw_response: serde_json::Value::Null,
+        }))]
+    }
+
+    fn answer_round(text: &str) -> Round {
+        vec![
+            Ok(TranslatedEvent::Sse(ClientSseEvent::Delta {
+                r#type: "text",
+                content: text.to_owned(),
+            })),
+            Ok(TranslatedEvent::Terminal(TerminalOutcome::Completed {
+                usage: Usage {
+                    input_tokens: 30,
+                    output_tokens: 8,
+                },
+                response_id: "resp-final".to_owned(),
+                content: text.to_owned(),
+                citations: vec![],
+                tool_calls: vec![],
+                raw_response: serde_json::Value::Null,
+            })),
+        ]
+    }
+
+    #[allow(de0309_must_have_domain_model)]
+    struct MockToolExecutor {
+        delay: Option<Duration>,
+        calls: std::sync::Mutex<Vec<ToolCallRequest>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ToolExecutor for MockToolExecutor {
+        async fn list_tools(&self, _tenant_id: Uuid) -> Result<Vec<ToolDefinition>, DomainError> {
+            Ok(vec![ToolDefinition {
+                name: "get_weather".to_owned(),
+                description: "Get weather".to_owned(),
+                parameters: serde_json::json!({"type": "object"}),
+            }])
+        }
+
+        async fn execute(&self, call: ToolCallRequest) -> Result<ToolCallOutput, DomainError> {
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
+            let content = format!("{} ok", call.name);
+            self.calls.lock().unwrap().push(call);
+            Ok(ToolCallOutput {
+                content,
+                is_error: false,
+            })
+        }
+    }
+
+    fn tool_session(executor: Arc<MockToolExecutor>, config: ToolsConfig) -> ToolSession {
+        ToolSession {
+            executor,
+            config,
+            tools: vec![LlmTool::Function {
+                name: "get_weather".to_owned(),
+                description: "Get weather".to_owned(),
+                parameters: serde_json::json!({"type": "object"}),
+            }],
+            tenant_id: Uuid::nil(),
+            user_id: Uuid::nil(),
+            chat_id: Uuid::nil(),
+        }
+    }
+
+    fn executor(delay: Option<Duration>) -> Arc<MockToolExecutor> {
+        Arc::new(MockToolExecutor {
+            delay,
+            calls: std::sync::Mutex::new(Vec::new()),
+        })
+    }
+
+    async fn collect_events(rx: &mut mpsc::Receiver<StreamEvent>) -> Vec<StreamEvent> {
+        let mut events = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            let is_term = ev.is_terminal();
+            events.push(ev);
+            if is_term {
+                break;
+            }
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn tool_call_result_is_fed_back_to_model() {
+        let provider = Arc::new(ScriptedProvider::new(vec![
+            tool_call_round("call_1", "get_weather", r#"{"city":"Oslo"}"#),
+            answer_round("Sunny"),
+        ]));
+        let exec = executor(None);
+        let (tx, mut rx) = mpsc::channel::<StreamEvent>(32);
+
+        let handle = spawn_provider_task::<TurnRepo, MsgRepo, QuotaRepo>(
+            Arc::clone(&provider) as Arc<dyn LlmProvider>,
+            mock_ctx(),
+            TurnPrompt::user("weather?"),
+            "test-model".into(),
+            CancellationToken::new(),
+            tx,
+            Some(tool_session(Arc::clone(&exec), ToolsConfig::default())),
+            None,
+        );
+
+        let events = collect_events(&mut rx).await;
+        assert_eq!(events.len(), 4);
+        let StreamEvent::ToolCall(call) = &events[0] else {
+            panic!("expected tool_call, got {:?}", events[0]);
+        };
+        assert_eq!(call.step, 1);
+        assert_eq!(call.name, "get_weather");
+        assert_eq!(call.arguments["city"], "Oslo");
+        let StreamEvent::ToolResult(result) = &events[1] else {
+            panic!("expected tool_result, got {:?}", events[1]);
+        };
+        assert_eq!(result.id, call.id);
+        assert_eq!(result.status, ToolResultStatus::Ok);
+        assert!(matches!(events[2], StreamEvent::Delta(_)));
+        let StreamEvent::Done(done) = &events[3] else {
+            panic!("expected done, got {:?}", events[3]);
+        };
+        let usage = done.usage.unwrap();
+        assert_eq!((usage.input_tokens, usage.output_tokens), (40, 13));
+
+        let outcome = handle.await.expect("task should complete");
+        assert_eq!(outcome.terminal, StreamTerminal::Completed);
+        assert_eq!(outcome.tool_steps, 1);
+        assert_eq!(outcome.accumulated_text, "Sunny");
+        assert_eq!(outcome.provider_response_id.as_deref(), Some("resp-final"));
+
+        assert_eq!(exec.calls.lock().unwrap()[0].arguments["city"], "Oslo");
+        let requests = provider.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].len(), 3);
+        assert!(matches!(
+            &requests[1][1].content[..],
+            [ContentPart::ToolCall { call_id, .. }] if call_id == "call_1"
+        ));
+        assert!(matches!(
+            &requests[1][2].content[..],
+            [ContentPart::ToolResult { call_id, content, is_error: false }]
+                if call_id == "call_1" && content == "get_weather ok"
+        ));
+    }
+
+    #[tokio::test]
+    async fn tool_step_limit_ends_turn_incomplete() {
+        let provider = Arc::new(ScriptedProvider::new(vec![
+            tool_call_round("call_1", "get_weather", "{}"),
+            tool_call_round("call_2", "get_weather", "{}"),
+        ]));
+        let exec = executor(None);
+        let config = ToolsConfig {
+            max_steps: 1,
+            ..ToolsConfig::default()
+        };
+        let (tx, mut rx) = mpsc::channel::<StreamEvent>(32);
+
+        let handle = spawn_provider_task::<TurnRepo, MsgRepo, QuotaRepo>(
+            provider,
+            mock_ctx(),
+            TurnPrompt::user("weather?"),
+            "test-model".into(),
+            CancellationToken::new(),
+            tx,
+            Some(tool_session(Arc::clone(&exec), config)),
+            None,
+        );
+
+        let events = collect_events(&mut rx).await;
+        assert!(matches!(events.last(), Some(StreamEvent::Done(_))));
+
+        let outcome = handle.await.expect("task should complete");
+        assert_eq!(outcome.terminal, StreamTerminal::Incomplete);
+        assert_eq!(
+            outcome.error_code.as_deref(),
+            Some("incomplete:tool_step_limit")
+        );
+        assert_eq!(outcome.tool_steps, 1);
+        assert_eq!(outcome.usage.unwrap().input_tokens, 20);
+        assert_eq!(exec.calls.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn slow_tool_call_times_out_with_error_result() {
+        let provider = Arc::new(ScriptedProvider::new(vec![
+            tool_call_round("call_1", "get_weather", "{}"),
+            answer_round("Unknown"),
+        ]));
+        let config = ToolsConfig {
+            call_timeout_seconds: 1,
+            ..ToolsConfig::default()
+        };
+        let (tx, mut rx) = mpsc::channel::<StreamEvent>(32);
+
+        let handle = spawn_provider_task::<TurnRepo, MsgRepo, QuotaRepo>(
+            Arc::clone(&provider) as Arc<dyn LlmProvider>,
+            mock_ctx(),
+            TurnPrompt::user("weather?"),
+            "test-model".into(),
+            CancellationToken::new(),
+            tx,
+            Some(tool_session(
+                executor(Some(Duration::from_secs(30))),
+                config,
+            )),
+            None,
+        );
+
+        let events = collect_events(&mut rx).await;
+        let StreamEvent::ToolResult(result) = &events[1] else {
+            panic!("expected tool_result, got {:?}", events[1]);
+        };
+        assert_eq!(result.status, ToolResultStatus::Timeout);
+
+        let outcome = handle.await.expect("task should complete");
+        assert_eq!(outcome.terminal, StreamTerminal::Completed);
+        let requests = provider.requests.lock().unwrap();
+        assert!(matches!(
+            &requests[1][2].content[..],
+            [ContentPart::ToolResult { is_error: true, .. }]
+        ));
+    }
+
+    #[tokio::test]
+    async fn invalid_tool_arguments_are_not_executed() {
+        let provider = Arc::new(ScriptedProvider::new(vec![
+            tool_call_round("call_1", "get_weather", "{not json"),
+            answer_round("Sorry"),
+        ]));
+        let exec = executor(None);
+        let (tx, mut rx) = mpsc::channel::<StreamEvent>(32);
+
+        let handle = spawn_provider_task::<TurnRepo, MsgRepo, QuotaRepo>(
+            provider,
+            mock_ctx(),
+            TurnPrompt::user("weather?"),
+            "test-model".into(),
+            CancellationToken::new(),
+            tx,
+            Some(tool_session(Arc::clone(&exec), ToolsConfig::default())),
+            None,
+        );
+
+        let events = collect_events(&mut rx).await;
+        let StreamEvent::ToolCall(call) = &events[0] else {
+            panic!("expected tool_call, got {:?}", events[0]);
+        };
+        assert_eq!(call.arguments, "{not json");
+        let StreamEvent::ToolResult(result) = &events[1] else {
+            panic!("expected tool_result, got {:?}", events[1]);
+        };
+        assert_eq!(result.status, ToolResultStatus::Error);
+
+        handle.await.expect("task should complete");
+        assert!(exec.calls.lock().unwrap().is_empty());
+    }
+
+    // ── Turn mutations ──
+
+    type Service = StreamService<
+        TurnRepo,
+        MsgRepo,
+        QuotaRepo,
+        crate::infra::db::repo::chat_repo::ChatRepository,
+        AttachmentRepo,
+        VectorStoreRepo,
+        SummaryRepo,
+    >;
+
+    fn mutation_service(db: &Arc<DbProvider>, provider: Arc<ScriptedProvider>) -> Service {
+        tool_service(db, provider, None)
+    }
+
+    fn tool_service(
+        db: &Arc<DbProvider>,
+        provider: Arc<ScriptedProvider>,
+        tool_executor: Option<Arc<dyn ToolExecutor>>,
+    ) -> Service {
+        let quota = Arc::new(QuotaService::new(
+            Arc::clone(db),
+            Arc::new(QuotaRepo),
+            mock_enforcer(),
+        ));
+        StreamService::new(
+            Arc::clone(db),
+            Arc::new(TurnRepo),
+            Arc::new(MsgRepo),
+            orm_chat_repo(),
+            quota,
+            mock_enforcer(),
+            Arc::clone(&provider) as Arc<dyn LlmProvider>,
+            StreamingConfig::default(),
+            tool_executor,
+            ToolsConfig::default(),
+            retrieval_service(db, AttachmentsConfig::default()),
+            context_service(db, provider, ContextConfig::default()),
+        )
+    }
+
+    /// Stream one message to completion; returns its `request_id`.
+    async fn completed_turn(svc: &Service, ctx: &SecurityContext, chat_id: Uuid) -> Uuid {
+        let request_id = Uuid::new_v4();
+        let (tx, mut rx) = mpsc::channel::<StreamEvent>(32);
+        let handle = svc
+            .run_stream(
+                ctx.clone(),
+                chat_id,
+                request_id,
+                "hello".into(),
+                "test-model".into(),
+                CancellationToken::new(),
+                tx,
+            )
+            .await
+            .expect("stream should start");
+        collect_events(&mut rx).await;
+        handle.await.expect("task should complete");
+        request_id
+    }
+
+    #[tokio::test]
+    async fn retry_replaces_latest_turn_and_settles_quota() {
+        let db = mock_db_provider(inmem_db().await);
+        let provider = Arc::new(ScriptedProvider::new(vec![
+            answer_round("first"),
+            answer_round("second"),
+        ]));
+        let svc = mutation_service(&db, Arc::clone(&provider));
+        let ctx = test_security_ctx(Uuid::new_v4());
+        let chat_id = seed_chat(&db, &ctx).await;
+        let old_request_id = completed_turn(&svc, &ctx, chat_id).await;
+
+        let (tx, mut rx) = mpsc::channel::<StreamEvent>(32);
+        let handle = svc
+            .retry_turn(
+                ctx.clone(),
+                chat_id,
+                old_request_id,
+                "test-model".into(),
+                CancellationToken::new(),
+                tx,
+            )
+            .await
+            .expect("retry should start");
+        let events = collect_events(&mut rx).await;
+        assert!(matches!(events.last(), Some(StreamEvent::Done(_))));
+        handle.await.expect("task should complete");
+
+        let conn = db.conn().unwrap();
+        let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
+        let old = TurnRepo
+            .find_by_chat_and_request_id(&conn, &scope, chat_id, old_request_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(old.deleted_at.is_some());
+        let new_request_id = old.replaced_by_request_id.expect("old turn is linked");
+        let new = TurnRepo
+            .find_latest_turn(&conn, &scope, chat_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(new.request_id, new_request_id);
+        assert_eq!(new.state, TurnState::Completed);
+        let messages = MsgRepo
+            .find_by_chat_and_request_id(&conn, &scope, chat_id, new_request_id)
+            .await
+            .unwrap();
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.role == MessageRole::User && m.content == "hello")
+        );
+        // The replaced turn is not sent as history of its replacement.
+        let requests = provider.requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].len(), 1);
+
+        // Both turns were reserved and settled.
+        let rows = QuotaRepo
+            .find_bucket_rows(&conn, &scope, ctx.subject_tenant_id(), ctx.subject_id())
+            .await
+            .unwrap();
+        assert!(!rows.is_empty());
+        for row in rows {
+            assert_eq!(row.reserved_credits_micro, 0);
+            assert_eq!(row.calls, 2);
+        }
+
+        // Retrying the replaced turn again is a replay of the new turn.
+        let (tx, _rx) = mpsc::channel::<StreamEvent>(32);
+        let result = svc
+            .retry_turn(
+                ctx.clone(),
+                chat_id,
+                old_request_id,
+                "test-model".into(),
+                CancellationToken::new(),
+                tx,
+            )
+            .await;
+        assert!(
+            matches!(result, Err(StreamError::Replay { ref turn }) if turn.request_id == new_request_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn edit_replaces_user_message() {
+        let db = mock_db_provider(inmem_db().await);
+        let provider = Arc::new(ScriptedProvider::new(vec![
+            answer_round("first"),
+            answer_round("second"),
+        ]));
+        let svc = mutation_service(&db, provider);
+        let ctx = test_security_ctx(Uuid::new_v4());
+        let chat_id = seed_chat(&db, &ctx).await;
+        let old_request_id = completed_turn(&svc, &ctx, chat_id).await;
+        let new_request_id = Uuid::new_v4();
+
+        let (tx, mut rx) = mpsc::channel::<StreamEvent>(32);
+        let handle = svc
+            .edit_turn(
+                ctx.clone(),
+                chat_id,
+                old_request_id,
+                new_request_id,
+                "hello again".into(),
+                "test-model".into(),
+                CancellationToken::new(),
+                tx,
+            )
+            .await
+            .expect("edit should start");
+        collect_events(&mut rx).await;
+        handle.await.expect("task should complete");
+
+        let conn = db.conn().unwrap();
+        let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
+        let old_messages = MsgRepo
+            .find_by_chat_and_request_id(&conn, &scope, chat_id, old_request_id)
+            .await
+            .unwrap();
+        assert!(old_messages.is_empty());
+        let messages = MsgRepo
+            .find_by_chat_and_request_id(&conn, &scope, chat_id, new_request_id)
+            .await
+            .unwrap();
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.role == MessageRole::User && m.content == "hello again")
+        );
+    }
+
+    #[tokio::test]
+    async fn edit_of_older_turn_is_rejected() {
+        let db = mock_db_provider(inmem_db().await);
+        let provider = Arc::new(ScriptedProvider::new(vec![
+            answer_round("first"),
+            answer_round("second"),
+        ]));
+        let svc = mutation_service(&db, provider);
+        let ctx = test_security_ctx(Uuid::new_v4());
+        let chat_id = seed_chat(&db, &ctx).await;
+        let first = completed_turn(&svc, &ctx, chat_id).await;
+        completed_turn(&svc, &ctx, chat_id).await;
+
+        let (tx, _rx) = mpsc::channel::<StreamEvent>(32);
+        let result = svc
+            .edit_turn(
+                ctx.clone(),
+                chat_id,
+                first,
+                Uuid::new_v4(),
+                "changed".into(),
+                "test-model".into(),
+                CancellationToken::new(),
+                tx,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(StreamError::Rejected {
+                source: DomainError::Conflict { ref code, .. }
+            }) if code == "not_latest_turn"
+        ));
+    }
+
+    // ── Document retrieval ──
+
+    #[tokio::test]
+    async fn retrieved_documents_are_sent_and_cited() {
+        let db = mock_db_provider(inmem_db().await);
+        let provider = Arc::new(ScriptedProvider::new(vec![answer_round("Twenty [1]")]));
+        let svc = mutation_service(&db, Arc::clone(&provider));
+        let ctx = test_security_ctx(Uuid::new_v4());
+        let chat_id = seed_chat(&db, &ctx).await;
+
+        let attachments = AttachmentService::new(
+            Arc::clone(&db),
+            Arc::new(AttachmentRepo),
+            orm_chat_repo(),
+            Arc::new(VectorStoreRepo),
+            mock_enforcer(),
+            text_parser(),
+            hashing_embedder(),
+            AttachmentsConfig::default(),
+        );
+        let (attachment, indexing) = attachments
+            .upload(
+                &ctx,
+                chat_id,
+                "policy.txt",
+                None,
+                bytes::Bytes::from_static(b"Employees accrue twenty vacation days per year."),
+            )
+            .await
+            .unwrap();
+        indexing.await.unwrap();
+
+        let (tx, mut rx) = mpsc::channel::<StreamEvent>(32);
+        let handle = svc
+            .run_stream(
+                ctx.clone(),
+                chat_id,
+                Uuid::new_v4(),
+                "How many vacation days do I get?".into(),
+                "test-model".into(),
+                CancellationToken::new(),
+                tx,
+            )
+            .await
+            .expect("stream should start");
+        let events = collect_events(&mut rx).await;
+        handle.await.expect("task should complete");
+
+        let instructions = provider.instructions.lock().unwrap()[0]
+            .clone()
+            .expect("retrieved excerpts are sent as system instructions");
+        assert!(instructions.contains("[1] policy.txt"));
+        assert!(instructions.contains("twenty vacation days"));
+
+        let citations = events
+            .iter()
+            .find_map(|e| match e {
+                StreamEvent::Citations(c) => Some(&c.items),
+                _ => None,
+            })
+            .expect("citations event should be sent");
+        assert_eq!(citations.len(), 1);
+        assert_eq!(
+            citations[0].attachment_id.as_deref(),
+            Some(attachment.id.to_string().as_str())
+        );
+        assert!(matches!(events.last(), Some(StreamEvent::Done(_))));
+    }
+
+    // ── Context window ──
+
+    #[tokio::test]
+    async fn earlier_turns_are_sent_as_history_and_recorded() {
+        let db = mock_db_provider(inmem_db().await);
+        let provider = Arc::new(ScriptedProvider::new(vec![
+            answer_round("first"),
+            answer_round("second"),
+        ]));
+        let svc = mutation_service(&db, Arc::clone(&provider));
+        let ctx = test_security_ctx(Uuid::new_v4());
+        let chat_id = seed_chat(&db, &ctx).await;
+        completed_turn(&svc, &ctx, chat_id).await;
+        completed_turn(&svc, &ctx, chat_id).await;
+
+        let requests = provider.requests.lock().unwrap().clone();
+        assert_eq!(requests[0].len(), 1);
+        let roles: Vec<_> = requests[1].iter().map(|m| m.role).collect();
+        assert_eq!(roles, [Role::User, Role::Assistant, Role::User]);
+        assert!(matches!(
+            &requests[1][1].content[..],
+            [ContentPart::Text { text }] if text == "first"
+        ));
+
+        let conn = db.conn().unwrap();
+        let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
+        let turn = TurnRepo
+            .find_latest_turn(&conn, &scope, chat_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(turn.context_strategy.as_deref(), Some("hybrid"));
+        assert_eq!(turn.history_messages_sent, Some(2));
+        assert_eq!(turn.history_messages_truncated, Some(0));
+        assert_eq!(turn.summarized_messages, Some(0));
+        assert_eq!(turn.summary_up_to_message_id, None);
+        assert!(turn.context_tokens_estimate.is_some_and(|t| t > 0));
+
+        // The reserve covers the history sent, not only the user message.
+        let estimate = i64::from(turn.context_tokens_estimate.unwrap());
+        assert_eq!(
+            turn.reserve_tokens,
+            Some(estimate + i64::from(MAX_OUTPUT_TOKENS))
+        );
+    }
+
+    // ── Quota ──
+
+    /// Set the usage reported by the terminal event of `round`.
+    fn with_usage(mut round: Round, input_tokens: i64, output_tokens: i64) -> Round {
+        for event in &mut round {
+            if let Ok(TranslatedEvent::Terminal(TerminalOutcome::Completed { usage, .. })) = event {
+                *usage = Usage {
+                    input_tokens,
+                    output_tokens,
+                };
+            }
+        }
+        round
+    }
+
+    #[tokio::test]
+    async fn tool_turn_reserves_and_charges_every_round() {
+        let db = mock_db_provider(inmem_db().await);
+        let out = i64::from(MAX_OUTPUT_TOKENS);
+        let provider = Arc::new(ScriptedProvider::new(vec![
+            with_usage(tool_call_round("call_1", "get_weather", "{}"), 3000, out),
+            with_usage(answer_round("Sunny"), 3500, out),
+        ]));
+        let svc = tool_service(
+            &db,
+            Arc::clone(&provider),
+            Some(executor(None) as Arc<dyn ToolExecutor>),
+        );
+        let ctx = test_security_ctx(Uuid::new_v4());
+        let chat_id = seed_chat(&db, &ctx).await;
+        completed_turn(&svc, &ctx, chat_id).await;
+        assert_eq!(provider.requests.lock().unwrap().len(), 2);
+
+        let conn = db.conn().unwrap();
+        let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
+        let turn = TurnRepo
+            .find_latest_turn(&conn, &scope, chat_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(turn.state, TurnState::Completed);
+        let rounds = i64::from(ToolsConfig::default().max_steps) + 1;
+        let reserve_tokens = turn.reserve_tokens.unwrap();
+        assert_eq!(reserve_tokens % rounds, 0);
+        assert!(reserve_tokens > rounds * out);
+
+        // Both rounds are charged in full; 1000 micro-credits per token.
+        let rows = QuotaRepo
+            .find_bucket_rows(&conn, &scope, ctx.subject_tenant_id(), ctx.subject_id())
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        for row in rows {
+            assert_eq!(row.reserved_credits_micro, 0);
+            assert_eq!((row.input_tokens, row.output_tokens), (6500, 2 * out));
+            assert_eq!(row.spent_credits_micro, (6500 + 2 * out) * 1000);
+        }
+    }
 }
@@ -6,6 +6,9 @@ use authz_resolver_sdk::{
     constraints::{Constraint, EqPredicate, Predicate},
     models::{DenyReason, EvaluationRequest, EvaluationResponse, EvaluationResponseContext},
 };
+use file_parser::domain::service::{FileParserService, ServiceConfig};
+use file_parser::infra::parsers::{HtmlParser, PlainTextParser};
+use mini_chat_sdk::{ModelCatalogEntry, ModelTier};
 use modkit_db::{
     ConnectOpts, DBProvider, Db, connect_db, migration_runner::run_migrations_for_testing,
 };
@@ -13,8 +16,19 @@ use modkit_security::{SecurityContext, pep_properties};
 use sea_orm_migration::MigratorTrait;
 use uuid::Uuid;
 
+use crate::config::{AttachmentsConfig, ContextConfig};
 use crate::domain::error::DomainError;
-use crate::domain::repos::{ModelResolver, ThreadSummaryRepository};
+use crate::domain::models::Chat;
+use crate::domain::repos::{ChatRepository as _, ModelResolver};
+use crate::domain::service::{ContextService, QuotaService, RetrievalService};
+use crate::infra::db::repo::attachment_repo::AttachmentRepository as OrmAttachmentRepository;
+use crate::infra::db::repo::chat_repo::ChatRepository as OrmChatRepository;
+use crate::infra::db::repo::message_repo::MessageRepository as OrmMessageRepository;
+use crate::infra::db::repo::quota_usage_repo::QuotaUsageRepository as OrmQuotaUsageRepository;
+use crate::infra::db::repo::thread_summary_repo::ThreadSummaryRepository as OrmThreadSummaryRepository;
+use crate::infra::db::repo::vector_store_repo::VectorStoreRepository as OrmVectorStoreRepository;
+use crate::infra::embedder::HashingEmbedder;
+use crate::infra::llm::LlmProvider;
 
 // ── Mock AuthZ Resolver ──
 
@@ -129,6 +143,24 @@ impl ModelResolver for MockModelResolver {
             Err(DomainError::invalid_model(model))
         }
     }
+
+    async fn catalog_entry(
+        &self,
+        _tenant_id: Uuid,
+        model: &str,
+    ) -> Result<Option<ModelCatalogEntry>, DomainError> {
+        Ok(["gpt-5.2", "gpt-5-mini"]
+            .contains(&model)
+            .then(|| ModelCatalogEntry {
+                model_id: model.to_owned(),
+                display_name: model.to_owned(),
+                tier: ModelTier::Standard,
+                global_enabled: true,
+                is_default: model == "gpt-5.2",
+                context_window_tokens: 400_000,
+                max_output_tokens: 128_000,
+            }))
+    }
 }
 
 // ── Test Helpers ──
@@ -175,12 +207,93 @@ pub fn mock_model_resolver() -> Arc<dyn ModelResolver> {
     Arc::new(MockModelResolver)
 }
 
-pub fn mock_thread_summary_repo() -> Arc<dyn ThreadSummaryRepository> {
-    struct MockThreadSummaryRepo;
-    impl ThreadSummaryRepository for MockThreadSummaryRepo {}
-    Arc::new(MockThreadSummaryRepo)
-}
-
 pub fn mock_db_provider(db: Db) -> Arc<DBProvider<modkit_db::DbError>> {
     Arc::new(DBProvider::new(db))
 }
+
+pub fn orm_chat_repo() -> Arc<OrmChatRepository> {
+    Arc::new(OrmChatRepository::new(modkit_db::odata::LimitCfg {
+        default: 20,
+        max: 100,
+    }))
+}
+
+/// Parser service for text documents (txt, md, html).
+pub fn text_parser() -> Arc<FileParserService> {
+    Arc::new(FileParserService::new(
+        vec![
+            Arc::new(PlainTextParser::new()),
+            Arc::new(HtmlParser::new()),
+        ],
+        ServiceConfig {
+            max_file_size_bytes: 100 * 1024 * 1024,
+            allowed_local_base_dir: std::path::PathBuf::new(),
+        },
+    ))
+}
+
+pub fn hashing_embedder() -> Arc<HashingEmbedder> {
+    Arc::new(HashingEmbedder::new(
+        AttachmentsConfig::default().embedding_dimensions,
+    ))
+}
+
+pub type OrmRetrievalService = RetrievalService<OrmAttachmentRepository, OrmVectorStoreRepository>;
+
+pub fn retrieval_service(
+    db: &Arc<DBProvider<modkit_db::DbError>>,
+    config: AttachmentsConfig,
+) -> Arc<OrmRetrievalService> {
+    Arc::new(RetrievalService::new(
+        Arc::clone(db),
+        Arc::new(OrmAttachmentRepository),
+        Arc::new(OrmVectorStoreRepository),
+        hashing_embedder(),
+        config,
+    ))
+}
+
+pub type OrmContextService =
+    ContextService<OrmMessageRepository, OrmThreadSummaryRepository, OrmQuotaUsageRepository>;
+
+pub fn context_service(
+    db: &Arc<DBProvider<modkit_db::DbError>>,
+    llm: Arc<dyn LlmProvider>,
+    config: ContextConfig,
+) -> Arc<OrmContextService> {
+    Arc::new(ContextService::new(
+        Arc::clone(db),
+        Arc::new(OrmMessageRepository),
+        Arc::new(OrmThreadSummaryRepository),
+        Arc::new(QuotaService::new(
+            Arc::clone(db),
+            Arc::new(OrmQuotaUsageRepository),
+            mock_enforcer(),
+        )),
+        mock_model_resolver(),
+        llm,
+        config,
+    ))
+}
+
+/// Insert a chat owned by the subject of `ctx` and return its ID.
+pub async fn seed_chat(db: &DBProvider<modkit_db::DbError>, ctx: &SecurityContext) -> Uuid {
+    let conn = db.conn().expect("failed to get connection");
+    let scope = modkit_security::AccessScope::for_tenant(ctx.subject_tenant_id());
+    let now = time::OffsetDateTime::now_utc();
+    let chat = Chat {
+        id: Uuid::now_v7(),
+        tenant_id: ctx.subject_tenant_id(),
+        user_id: ctx.subject_id(),
+        model: "gpt-5.2".to_owned(),
+        title: None,
+        is_temporary: false,
+        created_at: now,
+        updated_at: now,
+    };
+    orm_chat_repo()
+        .create(&conn, &scope, chat)
+        .await
+        .expect("failed to seed chat")
+        .id
+}
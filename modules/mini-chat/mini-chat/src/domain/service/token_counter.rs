@@ -0,0 +1,55 @@
+//! Per-model token estimates for context-window budgeting.
+//!
+//! No provider tokenizer is bundled; counts are character-based and err on
+//! the high side. The context budget keeps a safety margin on top.
+
+use modkit_macros::domain_model;
+
+/// Tokens added per message for role and framing.
+const MESSAGE_OVERHEAD_TOKENS: u32 = 4;
+
+/// Token estimator for one model family.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenCounter {
+    chars_per_token: usize,
+}
+
+impl TokenCounter {
+    /// Estimator for `model`. Claude tokenizers produce noticeably more
+    /// tokens per character than the `OpenAI` ones, so they get a denser
+    /// ratio.
+    pub fn for_model(model: &str) -> Self {
+        let chars_per_token = if model.starts_with("claude") { 3 } else { 4 };
+        Self { chars_per_token }
+    }
+
+    /// Estimated tokens of `text`.
+    pub fn count(self, text: &str) -> u32 {
+        u32::try_from(text.chars().count().div_ceil(self.chars_per_token)).unwrap_or(u32::MAX)
+    }
+
+    /// Estimated tokens of a conversation message with content `text`.
+    pub fn count_message(self, text: &str) -> u32 {
+        self.count(text).saturating_add(MESSAGE_OVERHEAD_TOKENS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_depend_on_model_family() {
+        let text = "a".repeat(12);
+        assert_eq!(TokenCounter::for_model("gpt-5.2").count(&text), 3);
+        assert_eq!(TokenCounter::for_model("claude-sonnet-4-5").count(&text), 4);
+        assert_eq!(TokenCounter::for_model("gpt-5.2").count(""), 0);
+    }
+
+    #[test]
+    fn message_count_includes_overhead() {
+        let counter = TokenCounter::for_model("gpt-5.2");
+        assert_eq!(counter.count_message("abcd"), 1 + MESSAGE_OVERHEAD_TOKENS);
+    }
+}
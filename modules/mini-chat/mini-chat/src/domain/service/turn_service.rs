@@ -0,0 +1,262 @@
+use std::sync::Arc;
+
+use authz_resolver_sdk::PolicyEnforcer;
+use modkit_db::secure::DBRunner;
+use modkit_macros::domain_model;
+use modkit_security::{AccessScope, SecurityContext};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::models::{TurnStatus, TurnStatusState};
+use crate::domain::repos::{ChatRepository, MessageRepository, TurnRepository};
+use crate::infra::db::entity::chat_turn::{Model as TurnModel, TurnState};
+
+use super::{DbProvider, actions, resources};
+
+// ════════════════════════════════════════════════════════════════════════════
+// Turn mutation rules — shared by delete (here) and retry/edit (streaming)
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Target of a retry, edit or delete, resolved under the turn mutation rules.
+#[domain_model]
+pub(super) enum MutationTarget {
+    /// Latest non-deleted turn of the requester, in a terminal state.
+    Mutable(Box<TurnModel>),
+    /// Already replaced by a retry or edit with this `request_id`.
+    Replaced(Uuid),
+    /// Already deleted without a replacement.
+    Deleted,
+}
+
+/// Authorize `action` on the chat and check that it exists.
+///
+/// Returns the tenant scope for turn and message rows, which carry no owner
+/// column; ownership is enforced on the chat itself.
+pub(super) async fn authorize_chat<CR: ChatRepository>(
+    db: &DbProvider,
+    enforcer: &PolicyEnforcer,
+    chat_repo: &CR,
+    ctx: &SecurityContext,
+    chat_id: Uuid,
+    action: &str,
+) -> Result<AccessScope, DomainError> {
+    let conn = db.conn().map_err(DomainError::from)?;
+    let chat_scope = enforcer
+        .access_scope(ctx, &resources::CHAT, action, Some(chat_id))
+        .await?;
+    chat_repo
+        .get(&conn, &chat_scope, chat_id)
+        .await?
+        .ok_or_else(|| DomainError::chat_not_found(chat_id))?;
+    Ok(AccessScope::for_tenant(ctx.subject_tenant_id()))
+}
+
+/// Resolve turn `request_id` of `chat_id` as a mutation target.
+///
+/// Only the requester's most recent non-deleted turn may be mutated, and
+/// only once it is terminal. Must run in the same transaction as the
+/// mutation so the checks and the writes see one snapshot.
+pub(super) async fn resolve_mutation_target<TR: TurnRepository, C: DBRunner>(
+    turn_repo: &TR,
+    runner: &C,
+    scope: &AccessScope,
+    chat_id: Uuid,
+    request_id: Uuid,
+    user_id: Uuid,
+) -> Result<MutationTarget, DomainError> {
+    let turn = turn_repo
+        .find_by_chat_and_request_id(runner, scope, chat_id, request_id)
+        .await?
+        .ok_or_else(|| DomainError::not_found("Turn", request_id))?;
+
+    if turn.requester_user_id != Some(user_id) {
+        return Err(DomainError::Forbidden);
+    }
+
+    if turn.deleted_at.is_some() {
+        return Ok(turn
+            .replaced_by_request_id
+            .map_or(MutationTarget::Deleted, MutationTarget::Replaced));
+    }
+
+    let latest = turn_repo.find_latest_turn(runner, scope, chat_id).await?;
+    if latest.is_none_or(|latest| latest.id != turn.id) {
+        return Err(DomainError::conflict(
+            "not_latest_turn",
+            format!("Turn {request_id} is not the latest turn of chat {chat_id}"),
+        ));
+    }
+
+    if !turn.state.is_terminal() {
+        return Err(DomainError::invalid_turn_state(format!(
+            "Turn {request_id} is still running"
+        )));
+    }
+
+    Ok(MutationTarget::Mutable(Box::new(turn)))
+}
+
+fn status_state(state: &TurnState) -> TurnStatusState {
+    match state {
+        TurnState::Running => TurnStatusState::Running,
+        TurnState::Completed => TurnStatusState::Done,
+        TurnState::Failed => TurnStatusState::Error,
+        TurnState::Cancelled => TurnStatusState::Cancelled,
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// TurnService
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Service handling turn status reads and turn deletion.
+///
+/// Retry and edit create a new streamed turn and live in `StreamService`.
+#[domain_model]
+pub struct TurnService<TR: TurnRepository, MR: MessageRepository, CR: ChatRepository> {
+    db: Arc<DbProvider>,
+    turn_repo: Arc<TR>,
+    message_repo: Arc<MR>,
+    chat_repo: Arc<CR>,
+    enforcer: PolicyEnforcer,
+}
+
+impl<TR: TurnRepository + 'static, MR: MessageRepository + 'static, CR: ChatRepository>
+    TurnService<TR, MR, CR>
+{
+    pub(crate) fn new(
+        db: Arc<DbProvider>,
+        turn_repo: Arc<TR>,
+        message_repo: Arc<MR>,
+        chat_repo: Arc<CR>,
+        enforcer: PolicyEnforcer,
+    ) -> Self {
+        Self {
+            db,
+            turn_repo,
+            message_repo,
+            chat_repo,
+            enforcer,
+        }
+    }
+
+    /// Get the authoritative status of a turn by its `request_id`.
+    #[instrument(skip(self, ctx), fields(chat_id = %chat_id, request_id = %request_id))]
+    pub async fn get_turn(
+        &self,
+        ctx: &SecurityContext,
+        chat_id: Uuid,
+        request_id: Uuid,
+    ) -> Result<TurnStatus, DomainError> {
+        let scope = authorize_chat(
+            &self.db,
+            &self.enforcer,
+            self.chat_repo.as_ref(),
+            ctx,
+            chat_id,
+            actions::READ_TURN,
+        )
+        .await?;
+
+        let conn = self.db.conn().map_err(DomainError::from)?;
+        let turn = self
+            .turn_repo
+            .find_by_chat_and_request_id(&conn, &scope, chat_id, request_id)
+            .await?
+            .ok_or_else(|| DomainError::not_found("Turn", request_id))?;
+
+        Ok(TurnStatus {
+            request_id: turn.request_id,
+            state: status_state(&turn.state),
+            error_code: turn.error_code,
+            assistant_message_id: turn.assistant_message_id,
+            updated_at: turn.updated_at,
+        })
+    }
+
+    /// Soft-delete the latest turn and its messages.
+    ///
+    /// Deleting an already deleted turn succeeds without changes; a turn
+    /// replaced by a retry or edit is no longer the latest turn.
+    #[instrument(skip(self, ctx), fields(chat_id = %chat_id, request_id = %request_id))]
+    pub async fn delete_turn(
+        &self,
+        ctx: &SecurityContext,
+        chat_id: Uuid,
+        request_id: Uuid,
+    ) -> Result<(), DomainError> {
+        let scope = authorize_chat(
+            &self.db,
+            &self.enforcer,
+            self.chat_repo.as_ref(),
+            ctx,
+            chat_id,
+            actions::DELETE_TURN,
+        )
+        .await?;
+
+        let user_id = ctx.subject_id();
+        let turn_repo = Arc::clone(&self.turn_repo);
+        let message_repo = Arc::clone(&self.message_repo);
+
+        let deleted = self
+            .db
+            .transaction(|tx| {
+                Box::pin(async move {
+                    let target = match resolve_mutation_target(
+                        turn_repo.as_ref(),
+                        tx,
+                        &scope,
+                        chat_id,
+                        request_id,
+                        user_id,
+                    )
+                    .await
+                    {
+                        Ok(target) => target,
+                        Err(e) => return Ok(Err(e)),
+                    };
+
+                    let turn = match target {
+                        MutationTarget::Mutable(turn) => turn,
+                        MutationTarget::Deleted => return Ok(Ok(false)),
+                        MutationTarget::Replaced(_) => {
+                            return Ok(Err(DomainError::conflict(
+                                "not_latest_turn",
+                                format!("Turn {request_id} was replaced by a newer turn"),
+                            )));
+                        }
+                    };
+
+                    turn_repo
+                        .soft_delete(tx, &scope, turn.id, None)
+                        .await
+                        .map_err(|e| modkit_db::DbError::Other(anyhow::anyhow!(e)))?;
+                    message_repo
+                        .soft_delete_by_request_id(tx, &scope, chat_id, request_id)
+                        .await
+                        .map_err(|e| modkit_db::DbError::Other(anyhow::anyhow!(e)))?;
+
+                    Ok(Ok(true))
+                })
+            })
+            .await
+            .map_err(DomainError::from)??;
+
+        if deleted {
+            info!(
+                event = "turn_delete",
+                actor_user_id = %user_id,
+                %chat_id,
+                %request_id,
+                "turn deleted"
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "turn_service_test.rs"]
+mod tests;
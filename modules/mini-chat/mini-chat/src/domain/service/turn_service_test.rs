@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use modkit_security::{AccessScope, SecurityContext};
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::models::TurnStatusState;
+use crate::domain::repos::{
+    CasTerminalParams, CreateTurnParams, InsertUserMessageParams, MessageRepository as _,
+    TurnRepository as _,
+};
+use crate::infra::db::entity::chat_turn::TurnState;
+use crate::infra::db::repo::chat_repo::ChatRepository as OrmChatRepository;
+use crate::infra::db::repo::message_repo::MessageRepository as OrmMessageRepository;
+use crate::infra::db::repo::turn_repo::TurnRepository as OrmTurnRepository;
+
+use super::TurnService;
+use crate::domain::service::DbProvider;
+use crate::domain::service::test_helpers::{
+    inmem_db, mock_db_provider, mock_enforcer, orm_chat_repo, seed_chat, test_security_ctx,
+    test_security_ctx_with_id,
+};
+
+// ── Test Helpers ──
+
+type Service = TurnService<OrmTurnRepository, OrmMessageRepository, OrmChatRepository>;
+
+fn build_service(db: Arc<DbProvider>) -> Service {
+    TurnService::new(
+        db,
+        Arc::new(OrmTurnRepository),
+        Arc::new(OrmMessageRepository),
+        orm_chat_repo(),
+        mock_enforcer(),
+    )
+}
+
+/// Insert a user message and a turn in `state`; returns the `request_id`.
+async fn seed_turn(
+    db: &DbProvider,
+    ctx: &SecurityContext,
+    chat_id: Uuid,
+    state: TurnState,
+) -> Uuid {
+    let conn = db.conn().unwrap();
+    let tenant_id = ctx.subject_tenant_id();
+    let scope = AccessScope::for_tenant(tenant_id);
+    let request_id = Uuid::new_v4();
+
+    OrmMessageRepository
+        .insert_user_message(
+            &conn,
+            &scope,
+            InsertUserMessageParams {
+                id: Uuid::new_v4(),
+                tenant_id,
+                chat_id,
+                request_id,
+                content: "hello".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+    let turn = OrmTurnRepository
+        .create_turn(
+            &conn,
+            &scope,
+            CreateTurnParams {
+                id: Uuid::new_v4(),
+                tenant_id,
+                chat_id,
+                request_id,
+                requester_type: "user".to_owned(),
+                requester_user_id: Some(ctx.subject_id()),
+                reserve_tokens: None,
+                max_output_tokens_applied: None,
+                reserved_credits_micro: None,
+                policy_version_applied: None,
+                effective_model: None,
+                minimal_generation_floor_applied: None,
+            },
+        )
+        .await
+        .unwrap();
+    if state != TurnState::Running {
+        OrmTurnRepository
+            .cas_update_state(
+                &conn,
+                &scope,
+                CasTerminalParams {
+                    turn_id: turn.id,
+                    state,
+                    error_code: Some("provider_error".to_owned()),
+                    error_detail: None,
+                },
+            )
+            .await
+            .unwrap();
+    }
+    request_id
+}
+
+// ── Tests ──
+
+#[tokio::test]
+async fn get_turn_maps_failed_to_error() {
+    let db = mock_db_provider(inmem_db().await);
+    let svc = build_service(Arc::clone(&db));
+    let ctx = test_security_ctx(Uuid::new_v4());
+    let chat_id = seed_chat(&db, &ctx).await;
+    let request_id = seed_turn(&db, &ctx, chat_id, TurnState::Failed).await;
+
+    let status = svc.get_turn(&ctx, chat_id, request_id).await.unwrap();
+
+    assert_eq!(status.request_id, request_id);
+    assert_eq!(status.state, TurnStatusState::Error);
+    assert_eq!(status.error_code.as_deref(), Some("provider_error"));
+    assert_eq!(status.assistant_message_id, None);
+}
+
+#[tokio::test]
+async fn get_turn_unknown_request_id_not_found() {
+    let db = mock_db_provider(inmem_db().await);
+    let svc = build_service(Arc::clone(&db));
+    let ctx = test_security_ctx(Uuid::new_v4());
+    let chat_id = seed_chat(&db, &ctx).await;
+
+    let result = svc.get_turn(&ctx, chat_id, Uuid::new_v4()).await;
+
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn get_turn_of_other_users_chat_not_found() {
+    let db = mock_db_provider(inmem_db().await);
+    let svc = build_service(Arc::clone(&db));
+    let tenant_id = Uuid::new_v4();
+    let owner = test_security_ctx(tenant_id);
+    let other = test_security_ctx_with_id(tenant_id, Uuid::new_v4());
+    let chat_id = seed_chat(&db, &owner).await;
+    let request_id = seed_turn(&db, &owner, chat_id, TurnState::Completed).await;
+
+    let result = svc.get_turn(&other, chat_id, request_id).await;
+
+    assert!(matches!(result, Err(DomainError::ChatNotFound { .. })));
+}
+
+#[tokio::test]
+async fn delete_latest_turn_soft_deletes_turn_and_messages() {
+    let db = mock_db_provider(inmem_db().await);
+    let svc = build_service(Arc::clone(&db));
+    let ctx = test_security_ctx(Uuid::new_v4());
+    let chat_id = seed_chat(&db, &ctx).await;
+    let request_id = seed_turn(&db, &ctx, chat_id, TurnState::Completed).await;
+
+    svc.delete_turn(&ctx, chat_id, request_id).await.unwrap();
+
+    let conn = db.conn().unwrap();
+    let scope = AccessScope::for_tenant(ctx.subject_tenant_id());
+    let turn = OrmTurnRepository
+        .find_by_chat_and_request_id(&conn, &scope, chat_id, request_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(turn.deleted_at.is_some());
+    assert_eq!(turn.replaced_by_request_id, None);
+    let messages = OrmMessageRepository
+        .find_by_chat_and_request_id(&conn, &scope, chat_id, request_id)
+        .await
+        .unwrap();
+    assert!(messages.is_empty());
+
+    // Deleting again is a no-op.
+    svc.delete_turn(&ctx, chat_id, request_id).await.unwrap();
+}
+
+#[tokio::test]
+async fn delete_older_turn_rejected_as_not_latest() {
+    let db = mock_db_provider(inmem_db().await);
+    let svc = build_service(Arc::clone(&db));
+    let ctx = test_security_ctx(Uuid::new_v4());
+    let chat_id = seed_chat(&db, &ctx).await;
+    let first = seed_turn(&db, &ctx, chat_id, TurnState::Completed).await;
+    seed_turn(&db, &ctx, chat_id, TurnState::Completed).await;
+
+    let result = svc.delete_turn(&ctx, chat_id, first).await;
+
+    assert!(
+        matches!(result, Err(DomainError::Conflict { ref code, .. }) if code == "not_latest_turn"),
+        "got {result:?}"
+    );
+}
+
+#[tokio::test]
+async fn delete_running_turn_rejected() {
+    let db = mock_db_provider(inmem_db().await);
+    let svc = build_service(Arc::clone(&db));
+    let ctx = test_security_ctx(Uuid::new_v4());
+    let chat_id = seed_chat(&db, &ctx).await;
+    let request_id = seed_turn(&db, &ctx, chat_id, TurnState::Running).await;
+
+    let result = svc.delete_turn(&ctx, chat_id, request_id).await;
+
+    assert!(matches!(result, Err(DomainError::InvalidTurnState { .. })));
+}
+
+#[tokio::test]
+async fn delete_turn_of_other_requester_forbidden() {
+    let db = mock_db_provider(inmem_db().await);
+    let svc = build_service(Arc::clone(&db));
+    let ctx = test_security_ctx(Uuid::new_v4());
+    let chat_id = seed_chat(&db, &ctx).await;
+    // Same chat, but the turn was requested by someone else.
+    let requester = test_security_ctx_with_id(ctx.subject_tenant_id(), Uuid::new_v4());
+    let request_id = seed_turn(&db, &requester, chat_id, TurnState::Completed).await;
+
+    let result = svc.delete_turn(&ctx, chat_id, request_id).await;
+
+    assert!(matches!(result, Err(DomainError::Forbidden)));
+}
@@ -0,0 +1,64 @@
+use modkit_db::secure::Scopable;
+use sea_orm::entity::prelude::*;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Scopable)]
+#[sea_orm(table_name = "attachments")]
+#[secure(tenant_col = "tenant_id", resource_col = "id", no_owner, no_type)]
+#[allow(clippy::struct_field_names)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub chat_id: Uuid,
+    pub uploaded_by_user_id: Uuid,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub storage_backend: String,
+    pub provider_file_id: Option<String>,
+    pub status: AttachmentStatus,
+    pub attachment_kind: AttachmentKind,
+    #[sea_orm(column_type = "Text")]
+    pub doc_summary: Option<String>,
+    pub img_thumbnail: Option<Vec<u8>>,
+    pub img_thumbnail_width: Option<i32>,
+    pub img_thumbnail_height: Option<i32>,
+    pub summary_model: Option<String>,
+    pub summary_updated_at: Option<OffsetDateTime>,
+    pub cleanup_status: Option<String>,
+    pub cleanup_attempts: i32,
+    #[sea_orm(column_type = "Text")]
+    pub last_cleanup_error: Option<String>,
+    pub cleanup_updated_at: Option<OffsetDateTime>,
+    pub error_code: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub deleted_at: Option<OffsetDateTime>,
+}
+
+/// Processing status. Allowed transitions: Pending → Ready | Failed.
+#[derive(Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum AttachmentStatus {
+    #[sea_orm(string_value = "pending")]
+    Pending,
+    #[sea_orm(string_value = "ready")]
+    Ready,
+    #[sea_orm(string_value = "failed")]
+    Failed,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum AttachmentKind {
+    #[sea_orm(string_value = "document")]
+    Document,
+    #[sea_orm(string_value = "image")]
+    Image,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
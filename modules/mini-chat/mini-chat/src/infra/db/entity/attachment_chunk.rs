@@ -0,0 +1,30 @@
+use modkit_db::secure::Scopable;
+use sea_orm::entity::prelude::*;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// One indexed chunk of a document attachment in the chat's local vector
+/// index. `embedding` holds little-endian `f32` components.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Scopable)]
+#[sea_orm(table_name = "attachment_chunks")]
+#[secure(tenant_col = "tenant_id", resource_col = "id", no_owner, no_type)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub chat_id: Uuid,
+    pub attachment_id: Uuid,
+    pub chunk_index: i32,
+    #[sea_orm(column_type = "Text")]
+    pub heading: Option<String>,
+    #[sea_orm(column_type = "Text")]
+    pub content: String,
+    pub token_estimate: i32,
+    pub embedding: Vec<u8>,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -28,6 +28,16 @@ pub struct Model {
     pub policy_version_applied: Option<i64>,
     pub effective_model: Option<String>,
     pub minimal_generation_floor_applied: Option<i32>,
+    /// Context-window strategy applied to the history of this turn.
+    pub context_strategy: Option<String>,
+    pub context_tokens_estimate: Option<i32>,
+    pub history_messages_sent: Option<i32>,
+    /// Earlier messages left out because they did not fit.
+    pub history_messages_truncated: Option<i32>,
+    /// Last message covered by the thread summary sent with this turn.
+    pub summary_up_to_message_id: Option<Uuid>,
+    /// Messages represented by the thread summary instead of verbatim.
+    pub summarized_messages: Option<i32>,
     pub deleted_at: Option<OffsetDateTime>,
     pub replaced_by_request_id: Option<Uuid>,
     pub started_at: OffsetDateTime,
@@ -1,4 +1,8 @@
+pub mod attachment;
+pub mod attachment_chunk;
 pub mod chat;
 pub mod chat_turn;
+pub mod chat_vector_store;
 pub mod message;
 pub mod quota_usage;
+pub mod thread_summary;
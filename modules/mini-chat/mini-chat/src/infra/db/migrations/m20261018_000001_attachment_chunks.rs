@@ -0,0 +1,77 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+/// Local document index: parsed and embedded attachment chunks, plus the
+/// `error_code` reported for failed attachments.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let sql = match backend {
+            sea_orm::DatabaseBackend::Postgres => POSTGRES_UP,
+            sea_orm::DatabaseBackend::Sqlite => SQLITE_UP,
+            sea_orm::DatabaseBackend::MySql => {
+                return Err(DbErr::Migration("MySQL not supported for mini-chat".into()));
+            }
+        };
+
+        conn.execute_unprepared(sql).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        conn.execute_unprepared(DOWN).await?;
+        Ok(())
+    }
+}
+
+const DOWN: &str = r"
+DROP TABLE IF EXISTS attachment_chunks;
+ALTER TABLE attachments DROP COLUMN error_code;
+";
+
+const POSTGRES_UP: &str = r"
+ALTER TABLE attachments ADD COLUMN IF NOT EXISTS error_code VARCHAR(32);
+
+CREATE TABLE IF NOT EXISTS attachment_chunks (
+    id              UUID PRIMARY KEY NOT NULL,
+    tenant_id       UUID NOT NULL,
+    chat_id         UUID NOT NULL REFERENCES chats(id) ON DELETE CASCADE,
+    attachment_id   UUID NOT NULL REFERENCES attachments(id) ON DELETE CASCADE,
+    chunk_index     INT NOT NULL CHECK (chunk_index >= 0),
+    heading         TEXT,
+    content         TEXT NOT NULL,
+    token_estimate  INT NOT NULL DEFAULT 0 CHECK (token_estimate >= 0),
+    embedding       BYTEA NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL,
+    UNIQUE (attachment_id, chunk_index)
+);
+CREATE INDEX IF NOT EXISTS idx_attachment_chunks_tenant_chat
+    ON attachment_chunks (tenant_id, chat_id);
+";
+
+const SQLITE_UP: &str = r"
+ALTER TABLE attachments ADD COLUMN error_code TEXT;
+
+CREATE TABLE IF NOT EXISTS attachment_chunks (
+    id              TEXT PRIMARY KEY NOT NULL,
+    tenant_id       TEXT NOT NULL,
+    chat_id         TEXT NOT NULL REFERENCES chats(id) ON DELETE CASCADE,
+    attachment_id   TEXT NOT NULL REFERENCES attachments(id) ON DELETE CASCADE,
+    chunk_index     INTEGER NOT NULL CHECK (chunk_index >= 0),
+    heading         TEXT,
+    content         TEXT NOT NULL,
+    token_estimate  INTEGER NOT NULL DEFAULT 0 CHECK (token_estimate >= 0),
+    embedding       BLOB NOT NULL,
+    created_at      TEXT NOT NULL,
+    UNIQUE (attachment_id, chunk_index)
+);
+CREATE INDEX IF NOT EXISTS idx_attachment_chunks_tenant_chat
+    ON attachment_chunks (tenant_id, chat_id);
+";
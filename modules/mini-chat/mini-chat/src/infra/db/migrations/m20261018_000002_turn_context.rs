@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+/// Context-window metadata on turns: how the history sent to the model was
+/// assembled, summarized and truncated.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let sql = match backend {
+            sea_orm::DatabaseBackend::Postgres => POSTGRES_UP,
+            sea_orm::DatabaseBackend::Sqlite => SQLITE_UP,
+            sea_orm::DatabaseBackend::MySql => {
+                return Err(DbErr::Migration("MySQL not supported for mini-chat".into()));
+            }
+        };
+
+        conn.execute_unprepared(sql).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        conn.execute_unprepared(DOWN).await?;
+        Ok(())
+    }
+}
+
+const DOWN: &str = r"
+ALTER TABLE chat_turns DROP COLUMN context_strategy;
+ALTER TABLE chat_turns DROP COLUMN context_tokens_estimate;
+ALTER TABLE chat_turns DROP COLUMN history_messages_sent;
+ALTER TABLE chat_turns DROP COLUMN history_messages_truncated;
+ALTER TABLE chat_turns DROP COLUMN summary_up_to_message_id;
+ALTER TABLE chat_turns DROP COLUMN summarized_messages;
+";
+
+const POSTGRES_UP: &str = r"
+ALTER TABLE chat_turns ADD COLUMN IF NOT EXISTS context_strategy VARCHAR(16);
+ALTER TABLE chat_turns ADD COLUMN IF NOT EXISTS context_tokens_estimate INT;
+ALTER TABLE chat_turns ADD COLUMN IF NOT EXISTS history_messages_sent INT;
+ALTER TABLE chat_turns ADD COLUMN IF NOT EXISTS history_messages_truncated INT;
+ALTER TABLE chat_turns ADD COLUMN IF NOT EXISTS summary_up_to_message_id UUID;
+ALTER TABLE chat_turns ADD COLUMN IF NOT EXISTS summarized_messages INT;
+";
+
+const SQLITE_UP: &str = r"
+ALTER TABLE chat_turns ADD COLUMN context_strategy TEXT;
+ALTER TABLE chat_turns ADD COLUMN context_tokens_estimate INTEGER;
+ALTER TABLE chat_turns ADD COLUMN history_messages_sent INTEGER;
+ALTER TABLE chat_turns ADD COLUMN history_messages_truncated INTEGER;
+ALTER TABLE chat_turns ADD COLUMN summary_up_to_message_id TEXT;
+ALTER TABLE chat_turns ADD COLUMN summarized_messages INTEGER;
+";
@@ -1,12 +1,18 @@
 use sea_orm_migration::prelude::*;
 
 mod m20260302_000001_initial;
+mod m20261018_000001_attachment_chunks;
+mod m20261018_000002_turn_context;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20260302_000001_initial::Migration)]
+        vec![
+            Box::new(m20260302_000001_initial::Migration),
+            Box::new(m20261018_000001_attachment_chunks::Migration),
+            Box::new(m20261018_000002_turn_context::Migration),
+        ]
     }
 }
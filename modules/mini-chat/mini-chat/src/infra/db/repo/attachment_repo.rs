@@ -1,4 +1,125 @@
+use async_trait::async_trait;
+use modkit_db::secure::{DBRunner, SecureEntityExt, SecureUpdateExt, secure_insert};
+use modkit_security::AccessScope;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveEnum, ColumnTrait, Condition, EntityTrait, Order, QueryFilter, QueryOrder, Set,
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::repos::InsertAttachmentParams;
+use crate::infra::db::entity::attachment::{
+    ActiveModel, AttachmentKind, AttachmentStatus, Column, Entity as AttachmentEntity,
+    Model as AttachmentModel,
+};
+
+/// Storage backend recorded for attachments indexed in the local vector store.
+const LOCAL_STORAGE_BACKEND: &str = "local";
+
 /// Repository for attachment persistence operations.
 pub struct AttachmentRepository;
 
-impl crate::domain::repos::AttachmentRepository for AttachmentRepository {}
+#[async_trait]
+impl crate::domain::repos::AttachmentRepository for AttachmentRepository {
+    async fn insert<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        params: InsertAttachmentParams,
+    ) -> Result<AttachmentModel, DomainError> {
+        let now = OffsetDateTime::now_utc();
+        let am = ActiveModel {
+            id: Set(params.id),
+            tenant_id: Set(params.tenant_id),
+            chat_id: Set(params.chat_id),
+            uploaded_by_user_id: Set(params.uploaded_by_user_id),
+            filename: Set(params.filename),
+            content_type: Set(params.content_type),
+            size_bytes: Set(params.size_bytes),
+            storage_backend: Set(LOCAL_STORAGE_BACKEND.to_owned()),
+            provider_file_id: Set(None),
+            status: Set(AttachmentStatus::Pending),
+            attachment_kind: Set(params.kind),
+            doc_summary: Set(None),
+            img_thumbnail: Set(None),
+            img_thumbnail_width: Set(None),
+            img_thumbnail_height: Set(None),
+            summary_model: Set(None),
+            summary_updated_at: Set(None),
+            cleanup_status: Set(None),
+            cleanup_attempts: Set(0),
+            last_cleanup_error: Set(None),
+            cleanup_updated_at: Set(None),
+            error_code: Set(None),
+            created_at: Set(now),
+            deleted_at: Set(None),
+        };
+        Ok(secure_insert::<AttachmentEntity>(am, scope, runner).await?)
+    }
+
+    async fn get<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<AttachmentModel>, DomainError> {
+        Ok(AttachmentEntity::find()
+            .filter(
+                Condition::all()
+                    .add(Column::Id.eq(id))
+                    .add(Column::ChatId.eq(chat_id))
+                    .add(Column::DeletedAt.is_null()),
+            )
+            .secure()
+            .scope_with(scope)
+            .one(runner)
+            .await?)
+    }
+
+    async fn list_documents<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+    ) -> Result<Vec<AttachmentModel>, DomainError> {
+        Ok(AttachmentEntity::find()
+            .filter(
+                Condition::all()
+                    .add(Column::ChatId.eq(chat_id))
+                    .add(Column::AttachmentKind.eq(AttachmentKind::Document))
+                    .add(Column::Status.ne(AttachmentStatus::Failed))
+                    .add(Column::DeletedAt.is_null()),
+            )
+            .order_by(Column::CreatedAt, Order::Asc)
+            .secure()
+            .scope_with(scope)
+            .all(runner)
+            .await?)
+    }
+
+    async fn cas_update_status<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        id: Uuid,
+        status: AttachmentStatus,
+        error_code: Option<String>,
+    ) -> Result<u64, DomainError> {
+        let result = AttachmentEntity::update_many()
+            .col_expr(Column::Status, Expr::value(status.into_value()))
+            .col_expr(Column::ErrorCode, Expr::value(error_code))
+            .filter(
+                Condition::all()
+                    .add(Column::Id.eq(id))
+                    .add(Column::Status.eq(AttachmentStatus::Pending)),
+            )
+            .secure()
+            .scope_with(scope)
+            .exec(runner)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
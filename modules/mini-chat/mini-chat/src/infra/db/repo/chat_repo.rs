@@ -58,6 +58,27 @@ impl crate::domain::repos::ChatRepository for ChatRepository {
         Ok(found.map(Into::into))
     }
 
+    async fn lock<C: DBRunner>(
+        &self,
+        conn: &C,
+        scope: &AccessScope,
+        id: Uuid,
+    ) -> Result<Option<Chat>, DomainError> {
+        let found = Entity::find()
+            .filter(
+                sea_orm::Condition::all()
+                    .add(Expr::col(Column::Id).eq(id))
+                    .add(Expr::col(Column::DeletedAt).is_null()),
+            )
+            .secure()
+            .scope_with(scope)
+            .lock_exclusive()
+            .one(conn)
+            .await
+            .map_err(db_err)?;
+        Ok(found.map(Into::into))
+    }
+
     async fn list_page<C: DBRunner>(
         &self,
         conn: &C,
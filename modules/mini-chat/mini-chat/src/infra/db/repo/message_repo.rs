@@ -1,6 +1,7 @@
 use async_trait::async_trait;
-use modkit_db::secure::{DBRunner, SecureEntityExt, secure_insert};
+use modkit_db::secure::{DBRunner, SecureEntityExt, SecureUpdateExt, secure_insert};
 use modkit_security::AccessScope;
+use sea_orm::sea_query::Expr;
 use sea_orm::{ColumnTrait, Condition, EntityTrait, Order, QueryFilter, Set};
 use time::OffsetDateTime;
 use uuid::Uuid;
@@ -93,4 +94,75 @@ impl crate::domain::repos::MessageRepository for MessageRepository {
             .all(runner)
             .await?)
     }
+
+    async fn list_uncompressed<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        exclude_request_ids: &[Uuid],
+    ) -> Result<Vec<MessageModel>, DomainError> {
+        Ok(MessageEntity::find()
+            .filter(
+                Condition::all()
+                    .add(Column::ChatId.eq(chat_id))
+                    .add(Column::DeletedAt.is_null())
+                    .add(Column::IsCompressed.eq(false))
+                    .add(Column::Role.is_in([MessageRole::User, MessageRole::Assistant]))
+                    .add(
+                        Condition::any()
+                            .add(Column::RequestId.is_null())
+                            .add(Column::RequestId.is_not_in(exclude_request_ids.iter().copied())),
+                    ),
+            )
+            .secure()
+            .scope_with(scope)
+            .order_by(Column::CreatedAt, Order::Asc)
+            .all(runner)
+            .await?)
+    }
+
+    async fn mark_compressed<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        message_ids: Vec<Uuid>,
+    ) -> Result<u64, DomainError> {
+        let result = MessageEntity::update_many()
+            .col_expr(Column::IsCompressed, Expr::value(true))
+            .filter(
+                Condition::all()
+                    .add(Column::ChatId.eq(chat_id))
+                    .add(Column::Id.is_in(message_ids)),
+            )
+            .secure()
+            .scope_with(scope)
+            .exec(runner)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    async fn soft_delete_by_request_id<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+        request_id: Uuid,
+    ) -> Result<u64, DomainError> {
+        let now = OffsetDateTime::now_utc();
+        let result = MessageEntity::update_many()
+            .col_expr(Column::DeletedAt, Expr::value(Some(now)))
+            .filter(
+                Condition::all()
+                    .add(Column::ChatId.eq(chat_id))
+                    .add(Column::RequestId.eq(request_id))
+                    .add(Column::DeletedAt.is_null()),
+            )
+            .secure()
+            .scope_with(scope)
+            .exec(runner)
+            .await?;
+        Ok(result.rows_affected)
+    }
 }
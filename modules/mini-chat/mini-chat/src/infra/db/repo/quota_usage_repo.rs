@@ -9,7 +9,7 @@ use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::domain::error::DomainError;
-use crate::domain::repos::{IncrementReserveParams, SettleParams};
+use crate::domain::repos::{IncrementReserveParams, RecordSpentParams, SettleParams};
 use crate::infra::db::entity::quota_usage::{
     ActiveModel, Column, Entity as QuotaUsageEntity, Model as QuotaUsageModel,
 };
@@ -129,6 +129,70 @@ impl crate::domain::repos::QuotaUsageRepository for QuotaUsageRepository {
         Ok(())
     }
 
+    async fn record_spent<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        params: RecordSpentParams,
+    ) -> Result<(), DomainError> {
+        let now = OffsetDateTime::now_utc();
+
+        let am = ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(params.tenant_id),
+            user_id: Set(params.user_id),
+            period_type: Set(params.period_type),
+            period_start: Set(params.period_start),
+            bucket: Set(params.bucket),
+            spent_credits_micro: Set(params.credits_micro),
+            reserved_credits_micro: Set(0),
+            calls: Set(1),
+            input_tokens: Set(params.input_tokens),
+            output_tokens: Set(params.output_tokens),
+            file_search_calls: Set(0),
+            web_search_calls: Set(0),
+            rag_retrieval_calls: Set(0),
+            image_inputs: Set(0),
+            image_upload_bytes: Set(0),
+            updated_at: Set(now),
+        };
+
+        // ON CONFLICT: add to spend, calls and tokens, refresh updated_at.
+        let on_conflict = SecureOnConflict::<QuotaUsageEntity>::columns([
+            Column::TenantId,
+            Column::UserId,
+            Column::PeriodType,
+            Column::PeriodStart,
+            Column::Bucket,
+        ])
+        .value(
+            Column::SpentCreditsMicro,
+            Expr::col(Column::SpentCreditsMicro).add(Expr::value(params.credits_micro)),
+        )?
+        .value(
+            Column::Calls,
+            Expr::col(Column::Calls).add(Expr::value(1i32)),
+        )?
+        .value(
+            Column::InputTokens,
+            Expr::col(Column::InputTokens).add(Expr::value(params.input_tokens)),
+        )?
+        .value(
+            Column::OutputTokens,
+            Expr::col(Column::OutputTokens).add(Expr::value(params.output_tokens)),
+        )?
+        .value(Column::UpdatedAt, Expr::value(now))?;
+
+        QuotaUsageEntity::insert(am)
+            .secure()
+            .scope_unchecked(scope)?
+            .on_conflict(on_conflict)
+            .exec(runner)
+            .await?;
+
+        Ok(())
+    }
+
     async fn find_bucket_rows<C: DBRunner>(
         &self,
         runner: &C,
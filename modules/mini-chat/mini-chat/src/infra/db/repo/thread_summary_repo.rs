@@ -1,4 +1,74 @@
+use async_trait::async_trait;
+use modkit_db::secure::{DBRunner, SecureEntityExt, SecureUpdateExt, secure_insert};
+use modkit_security::AccessScope;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, Set};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::repos::SaveSummaryParams;
+use crate::infra::db::entity::thread_summary::{
+    ActiveModel, Column, Entity as ThreadSummaryEntity, Model as ThreadSummaryModel,
+};
+
 /// Repository for thread summary persistence operations.
 pub struct ThreadSummaryRepository;
 
-impl crate::domain::repos::ThreadSummaryRepository for ThreadSummaryRepository {}
+#[async_trait]
+impl crate::domain::repos::ThreadSummaryRepository for ThreadSummaryRepository {
+    async fn find_by_chat_id<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+    ) -> Result<Option<ThreadSummaryModel>, DomainError> {
+        Ok(ThreadSummaryEntity::find()
+            .filter(Column::ChatId.eq(chat_id))
+            .secure()
+            .scope_with(scope)
+            .one(runner)
+            .await?)
+    }
+
+    async fn save<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        params: SaveSummaryParams,
+        expected_up_to: Option<Uuid>,
+    ) -> Result<u64, DomainError> {
+        let now = OffsetDateTime::now_utc();
+        let Some(expected_up_to) = expected_up_to else {
+            // A concurrent first summary fails on the unique chat_id.
+            let am = ActiveModel {
+                id: Set(Uuid::new_v4()),
+                tenant_id: Set(params.tenant_id),
+                chat_id: Set(params.chat_id),
+                summary_text: Set(params.summary_text),
+                summarized_up_to: Set(params.summarized_up_to),
+                token_estimate: Set(params.token_estimate),
+                created_at: Set(now),
+                updated_at: Set(now),
+            };
+            secure_insert::<ThreadSummaryEntity>(am, scope, runner).await?;
+            return Ok(1);
+        };
+
+        let result = ThreadSummaryEntity::update_many()
+            .col_expr(Column::SummaryText, Expr::value(params.summary_text))
+            .col_expr(Column::SummarizedUpTo, Expr::value(params.summarized_up_to))
+            .col_expr(Column::TokenEstimate, Expr::value(params.token_estimate))
+            .col_expr(Column::UpdatedAt, Expr::value(now))
+            .filter(
+                Condition::all()
+                    .add(Column::ChatId.eq(params.chat_id))
+                    .add(Column::SummarizedUpTo.eq(expected_up_to)),
+            )
+            .secure()
+            .scope_with(scope)
+            .exec(runner)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
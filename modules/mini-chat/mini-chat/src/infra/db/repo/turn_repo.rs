@@ -7,7 +7,9 @@ use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::domain::error::DomainError;
-use crate::domain::repos::{CasCompleteParams, CasTerminalParams, CreateTurnParams};
+use crate::domain::repos::{
+    CasCompleteParams, CasTerminalParams, CreateTurnParams, RecordContextParams,
+};
 use crate::infra::db::entity::chat_turn::{
     ActiveModel, Column, Entity as TurnEntity, Model as TurnModel, TurnState,
 };
@@ -42,6 +44,12 @@ impl crate::domain::repos::TurnRepository for TurnRepository {
             policy_version_applied: Set(params.policy_version_applied),
             effective_model: Set(params.effective_model),
             minimal_generation_floor_applied: Set(params.minimal_generation_floor_applied),
+            context_strategy: Set(None),
+            context_tokens_estimate: Set(None),
+            history_messages_sent: Set(None),
+            history_messages_truncated: Set(None),
+            summary_up_to_message_id: Set(None),
+            summarized_messages: Set(None),
             deleted_at: Set(None),
             replaced_by_request_id: Set(None),
             started_at: Set(now),
@@ -148,6 +156,42 @@ impl crate::domain::repos::TurnRepository for TurnRepository {
         Ok(result.rows_affected)
     }
 
+    async fn record_context<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        params: RecordContextParams,
+    ) -> Result<u64, DomainError> {
+        let result = TurnEntity::update_many()
+            .col_expr(Column::ContextStrategy, Expr::value(Some(params.strategy)))
+            .col_expr(
+                Column::ContextTokensEstimate,
+                Expr::value(Some(params.tokens_estimate)),
+            )
+            .col_expr(
+                Column::HistoryMessagesSent,
+                Expr::value(Some(params.history_messages_sent)),
+            )
+            .col_expr(
+                Column::HistoryMessagesTruncated,
+                Expr::value(Some(params.history_messages_truncated)),
+            )
+            .col_expr(
+                Column::SummaryUpToMessageId,
+                Expr::value(params.summary_up_to_message_id),
+            )
+            .col_expr(
+                Column::SummarizedMessages,
+                Expr::value(Some(params.summarized_messages)),
+            )
+            .filter(Column::Id.eq(params.turn_id))
+            .secure()
+            .scope_with(scope)
+            .exec(runner)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
     async fn soft_delete<C: DBRunner>(
         &self,
         runner: &C,
@@ -186,6 +230,7 @@ impl crate::domain::repos::TurnRepository for TurnRepository {
             .secure()
             .scope_with(scope)
             .order_by(Column::StartedAt, Order::Desc)
+            .order_by(Column::Id, Order::Desc)
             .one(runner)
             .await?)
     }
@@ -1,4 +1,162 @@
+use async_trait::async_trait;
+use modkit_db::secure::{
+    DBRunner, SecureEntityExt, SecureInsertExt, SecureOnConflict, SecureUpdateExt, secure_insert,
+};
+use modkit_security::AccessScope;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, Order, QueryFilter, QueryOrder, Set};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::repos::InsertChunkParams;
+use crate::infra::db::entity::attachment_chunk::{
+    ActiveModel as ChunkActiveModel, Column as ChunkColumn, Entity as ChunkEntity,
+    Model as ChunkModel,
+};
+use crate::infra::db::entity::chat_vector_store::{
+    ActiveModel, Column, Entity as VectorStoreEntity, Model as VectorStoreModel,
+};
+
 /// Repository for vector store persistence operations.
 pub struct VectorStoreRepository;
 
-impl crate::domain::repos::VectorStoreRepository for VectorStoreRepository {}
+#[async_trait]
+impl crate::domain::repos::VectorStoreRepository for VectorStoreRepository {
+    async fn ensure_store<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        tenant_id: Uuid,
+        chat_id: Uuid,
+        provider: &str,
+    ) -> Result<VectorStoreModel, DomainError> {
+        let am = ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            chat_id: Set(chat_id),
+            vector_store_id: Set(None),
+            provider: Set(provider.to_owned()),
+            file_count: Set(0),
+            created_at: Set(OffsetDateTime::now_utc()),
+        };
+
+        // ON CONFLICT: keep the existing row untouched.
+        let on_conflict =
+            SecureOnConflict::<VectorStoreEntity>::columns([Column::TenantId, Column::ChatId])
+                .value(Column::Provider, Expr::col(Column::Provider).into())?;
+
+        VectorStoreEntity::insert(am)
+            .secure()
+            .scope_unchecked(scope)?
+            .on_conflict(on_conflict)
+            .exec(runner)
+            .await?;
+
+        self.find_store(runner, scope, chat_id)
+            .await?
+            .ok_or_else(|| DomainError::internal("vector store row missing after upsert"))
+    }
+
+    async fn find_store<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+    ) -> Result<Option<VectorStoreModel>, DomainError> {
+        Ok(VectorStoreEntity::find()
+            .filter(Condition::all().add(Column::ChatId.eq(chat_id)))
+            .secure()
+            .scope_with(scope)
+            .one(runner)
+            .await?)
+    }
+
+    async fn increment_file_count<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        store_id: Uuid,
+    ) -> Result<(), DomainError> {
+        VectorStoreEntity::update_many()
+            .col_expr(
+                Column::FileCount,
+                Expr::col(Column::FileCount).add(Expr::value(1i32)),
+            )
+            .filter(Condition::all().add(Column::Id.eq(store_id)))
+            .secure()
+            .scope_with(scope)
+            .exec(runner)
+            .await?;
+        Ok(())
+    }
+
+    async fn insert_chunks<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chunks: Vec<InsertChunkParams>,
+    ) -> Result<(), DomainError> {
+        let now = OffsetDateTime::now_utc();
+        for chunk in chunks {
+            let am = ChunkActiveModel {
+                id: Set(Uuid::new_v4()),
+                tenant_id: Set(chunk.tenant_id),
+                chat_id: Set(chunk.chat_id),
+                attachment_id: Set(chunk.attachment_id),
+                chunk_index: Set(chunk.chunk_index),
+                heading: Set(chunk.heading),
+                content: Set(chunk.content),
+                token_estimate: Set(chunk.token_estimate),
+                embedding: Set(encode_embedding(&chunk.embedding)),
+                created_at: Set(now),
+            };
+            secure_insert::<ChunkEntity>(am, scope, runner).await?;
+        }
+        Ok(())
+    }
+
+    async fn count_chunks<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+    ) -> Result<u64, DomainError> {
+        Ok(ChunkEntity::find()
+            .filter(Condition::all().add(ChunkColumn::ChatId.eq(chat_id)))
+            .secure()
+            .scope_with(scope)
+            .count(runner)
+            .await?)
+    }
+
+    async fn find_chunks<C: DBRunner>(
+        &self,
+        runner: &C,
+        scope: &AccessScope,
+        chat_id: Uuid,
+    ) -> Result<Vec<ChunkModel>, DomainError> {
+        Ok(ChunkEntity::find()
+            .filter(Condition::all().add(ChunkColumn::ChatId.eq(chat_id)))
+            .order_by(ChunkColumn::AttachmentId, Order::Asc)
+            .order_by(ChunkColumn::ChunkIndex, Order::Asc)
+            .secure()
+            .scope_with(scope)
+            .all(runner)
+            .await?)
+    }
+}
+
+/// Serialize an embedding as little-endian `f32` components.
+fn encode_embedding(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+/// Inverse of [`encode_embedding`]. Trailing bytes that do not form a full
+/// component are ignored.
+pub(crate) fn decode_embedding(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use modkit_security::SecurityContext;
+
+use crate::domain::error::DomainError;
+use crate::domain::repos::Embedder;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Deterministic local embedder based on feature hashing.
+///
+/// Each lowercase alphanumeric token is hashed (FNV-1a) into one of
+/// `dimensions` buckets with a hash-derived sign; the resulting vector is
+/// L2-normalized. It needs no model or network access, so documents can be
+/// indexed offline and tests get reproducible rankings. Lexical overlap is
+/// all it captures — deployments wanting semantic recall select an
+/// embedding model instead (see [`EmbedderKind`](super::EmbedderKind)).
+pub struct HashingEmbedder {
+    dimensions: usize,
+}
+
+impl HashingEmbedder {
+    pub(crate) fn new(dimensions: u16) -> Self {
+        Self {
+            dimensions: usize::from(dimensions.max(1)),
+        }
+    }
+
+    fn embed_one(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimensions];
+        for token in text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|t| !t.is_empty())
+        {
+            let hash = fnv1a(&token.to_lowercase());
+            #[allow(clippy::cast_possible_truncation)]
+            let bucket = (hash % self.dimensions as u64) as usize;
+            let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+            vector[bucket] += sign;
+        }
+
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for v in &mut vector {
+                *v /= norm;
+            }
+        }
+        vector
+    }
+}
+
+#[async_trait]
+impl Embedder for HashingEmbedder {
+    async fn embed(
+        &self,
+        _ctx: &SecurityContext,
+        texts: &[String],
+    ) -> Result<Vec<Vec<f32>>, DomainError> {
+        Ok(texts.iter().map(|t| self.embed_one(t)).collect())
+    }
+}
+
+fn fnv1a(token: &str) -> u64 {
+    token.bytes().fold(FNV_OFFSET, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> SecurityContext {
+        SecurityContext::anonymous()
+    }
+
+    #[tokio::test]
+    async fn embeddings_are_deterministic_and_normalized() {
+        let embedder = HashingEmbedder::new(64);
+        let texts = vec!["Quarterly revenue grew".to_owned(); 2];
+        let vectors = embedder.embed(&ctx(), &texts).await.unwrap();
+
+        assert_eq!(vectors[0], vectors[1]);
+        assert_eq!(vectors[0].len(), 64);
+        let norm: f32 = vectors[0].iter().map(|v| v * v).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-5);
+    }
+
+    #[tokio::test]
+    async fn empty_text_yields_zero_vector() {
+        let embedder = HashingEmbedder::new(32);
+        let vectors = embedder.embed(&ctx(), &[String::new()]).await.unwrap();
+        assert!(vectors[0].iter().all(|v| *v == 0.0));
+    }
+
+    #[tokio::test]
+    async fn tokens_are_case_insensitive() {
+        let embedder = HashingEmbedder::new(128);
+        let vectors = embedder
+            .embed(
+                &ctx(),
+                &["Invoice TOTAL".to_owned(), "invoice total".to_owned()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(vectors[0], vectors[1]);
+    }
+}
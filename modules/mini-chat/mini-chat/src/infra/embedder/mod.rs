@@ -0,0 +1,90 @@
+//! Embedding adapters for the local document index.
+//!
+//! [`HashingEmbedder`] runs in-process and is the default, for offline
+//! deployments and tests; [`OpenAiEmbedder`] calls an embeddings API
+//! through OAGW.
+
+mod hashing;
+mod openai;
+
+use std::sync::Arc;
+
+use oagw_sdk::ServiceGatewayClientV1;
+use serde::{Deserialize, Serialize};
+
+pub use hashing::HashingEmbedder;
+pub use openai::OpenAiEmbedder;
+
+use crate::domain::repos::Embedder;
+
+// ════════════════════════════════════════════════════════════════════════════
+// Embedder selection
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Which embedder to use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EmbedderKind {
+    /// Local feature hashing; no model or network access.
+    #[default]
+    #[serde(rename = "hashing")]
+    Hashing,
+    /// `OpenAI` Embeddings API (`/v1/embeddings`).
+    #[serde(rename = "openai_embeddings")]
+    OpenAiEmbeddings,
+}
+
+/// Configuration for the document embedder.
+///
+/// The vector dimension is `attachments.embedding_dimensions` for every
+/// kind. Switching kind or model makes previously indexed documents
+/// unretrievable until they are uploaded again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EmbedderConfig {
+    /// Which embedder to use.
+    #[serde(default)]
+    pub kind: EmbedderKind,
+    /// OAGW upstream alias; unused by `hashing`.
+    #[serde(default = "default_upstream_alias")]
+    pub upstream_alias: String,
+    /// Embedding model; unused by `hashing`.
+    #[serde(default = "default_model")]
+    pub model: String,
+}
+
+impl Default for EmbedderConfig {
+    fn default() -> Self {
+        Self {
+            kind: EmbedderKind::default(),
+            upstream_alias: default_upstream_alias(),
+            model: default_model(),
+        }
+    }
+}
+
+fn default_upstream_alias() -> String {
+    "openai".to_owned()
+}
+
+fn default_model() -> String {
+    "text-embedding-3-small".to_owned()
+}
+
+/// Create an embedder producing `dimensions`-sized vectors from
+/// configuration.
+#[must_use]
+pub fn create_embedder(
+    gateway: Arc<dyn ServiceGatewayClientV1>,
+    config: EmbedderConfig,
+    dimensions: u16,
+) -> Arc<dyn Embedder> {
+    match config.kind {
+        EmbedderKind::Hashing => Arc::new(HashingEmbedder::new(dimensions)),
+        EmbedderKind::OpenAiEmbeddings => Arc::new(OpenAiEmbedder::new(
+            gateway,
+            config.upstream_alias,
+            config.model,
+            dimensions,
+        )),
+    }
+}
@@ -0,0 +1,204 @@
+//! `OpenAI` Embeddings API adapter (`/v1/embeddings`), proxied through OAGW.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use modkit_security::SecurityContext;
+use oagw_sdk::{Body, ServiceGatewayClientV1};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::DomainError;
+use crate::domain::repos::Embedder;
+use crate::infra::llm::{LlmProviderError, sanitize_provider_message};
+
+/// Embedder backed by an `OpenAI`-compatible embeddings endpoint.
+///
+/// Requests vectors of the configured dimension, so they stay comparable
+/// with the ones already indexed.
+pub struct OpenAiEmbedder {
+    gateway: Arc<dyn ServiceGatewayClientV1>,
+    upstream_alias: String,
+    model: String,
+    dimensions: u16,
+}
+
+impl OpenAiEmbedder {
+    #[must_use]
+    pub fn new(
+        gateway: Arc<dyn ServiceGatewayClientV1>,
+        upstream_alias: String,
+        model: String,
+        dimensions: u16,
+    ) -> Self {
+        Self {
+            gateway,
+            upstream_alias,
+            model,
+            dimensions,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct EmbeddingsRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+    dimensions: u16,
+    encoding_format: &'static str,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+#[derive(Deserialize)]
+struct ErrorPayload {
+    error: ErrorDetail,
+}
+
+#[derive(Deserialize)]
+struct ErrorDetail {
+    #[serde(default)]
+    message: String,
+}
+
+fn embedding_error(detail: impl std::fmt::Display) -> DomainError {
+    DomainError::internal(format!("embedding request failed: {detail}"))
+}
+
+#[async_trait]
+impl Embedder for OpenAiEmbedder {
+    #[tracing::instrument(skip_all, fields(model = %self.model, count = texts.len()))]
+    async fn embed(
+        &self,
+        ctx: &SecurityContext,
+        texts: &[String],
+    ) -> Result<Vec<Vec<f32>>, DomainError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let body = serde_json::to_vec(&EmbeddingsRequest {
+            model: &self.model,
+            input: texts,
+            dimensions: self.dimensions,
+            encoding_format: "float",
+        })
+        .map_err(embedding_error)?;
+        let http_request = http::Request::builder()
+            .method(http::Method::POST)
+            .uri(format!("/{}/v1/embeddings", self.upstream_alias))
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .header(http::header::ACCEPT, "application/json")
+            .body(Body::Bytes(Bytes::from(body)))
+            .map_err(embedding_error)?;
+
+        let response = self
+            .gateway
+            .proxy_request(ctx.clone(), http_request)
+            .await
+            .map_err(|e| embedding_error(LlmProviderError::from(e)))?;
+        let (parts, resp_body) = response.into_parts();
+        let bytes = resp_body.into_bytes().await.map_err(embedding_error)?;
+
+        if !parts.status.is_success() {
+            let detail = serde_json::from_slice::<ErrorPayload>(&bytes).map_or_else(
+                |_| String::from_utf8_lossy(&bytes).chars().take(200).collect(),
+                |p| p.error.message,
+            );
+            return Err(embedding_error(format!(
+                "HTTP {}: {}",
+                parts.status,
+                sanitize_provider_message(&detail)
+            )));
+        }
+
+        let mut resp: EmbeddingsResponse =
+            serde_json::from_slice(&bytes).map_err(embedding_error)?;
+        resp.data.sort_by_key(|d| d.index);
+        if resp.data.len() != texts.len() || resp.data.iter().enumerate().any(|(i, d)| d.index != i)
+        {
+            return Err(embedding_error(format!(
+                "expected {} embeddings, got {}",
+                texts.len(),
+                resp.data.len()
+            )));
+        }
+        Ok(resp.data.into_iter().map(|d| d.embedding).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::infra::llm::providers::test_support::MockGateway;
+
+    fn embedder(gateway: Arc<MockGateway>) -> OpenAiEmbedder {
+        OpenAiEmbedder::new(
+            gateway,
+            "openai".to_owned(),
+            "text-embedding-3-small".to_owned(),
+            3,
+        )
+    }
+
+    #[tokio::test]
+    async fn requests_configured_dimensions_and_orders_by_index() {
+        let gateway = MockGateway::returning_json(json!({
+            "object": "list",
+            "data": [
+                { "object": "embedding", "index": 1, "embedding": [0.0, 1.0, 0.0] },
+                { "object": "embedding", "index": 0, "embedding": [1.0, 0.0, 0.0] }
+            ],
+            "usage": { "prompt_tokens": 4, "total_tokens": 4 }
+        }));
+        let vectors = embedder(Arc::clone(&gateway))
+            .embed(
+                &SecurityContext::anonymous(),
+                &["first".to_owned(), "second".to_owned()],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(vectors, vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0]]);
+        assert_eq!(
+            gateway.last_request_uri().as_deref(),
+            Some("/openai/v1/embeddings")
+        );
+        let body: serde_json::Value =
+            serde_json::from_str(&gateway.last_request_body().unwrap()).unwrap();
+        assert_eq!(body["model"], "text-embedding-3-small");
+        assert_eq!(body["dimensions"], 3);
+        assert_eq!(body["input"], json!(["first", "second"]));
+    }
+
+    #[tokio::test]
+    async fn provider_errors_and_missing_vectors_fail() {
+        let gateway = MockGateway::returning_status(
+            http::StatusCode::BAD_REQUEST,
+            json!({ "error": { "message": "bad model", "code": "invalid_model" } }),
+        );
+        let err = embedder(gateway)
+            .embed(&SecurityContext::anonymous(), &["text".to_owned()])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("bad model"), "{err}");
+
+        let gateway = MockGateway::returning_json(json!({ "data": [] }));
+        let err = embedder(gateway)
+            .embed(&SecurityContext::anonymous(), &["text".to_owned()])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("expected 1 embeddings"), "{err}");
+    }
+}
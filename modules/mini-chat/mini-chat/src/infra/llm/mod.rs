@@ -127,7 +127,7 @@ static RE_RESP_ID: LazyLock<Regex> =
 static RE_URL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"https?://[^\s,\])}"']+"#).unwrap());
 #[allow(clippy::unwrap_used)]
 static RE_CRED: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"(sk-[A-Za-z0-9]{10,}|Bearer\s+[A-Za-z0-9._\-]+)").unwrap());
+    LazyLock::new(|| Regex::new(r"(sk-[A-Za-z0-9_\-]{10,}|Bearer\s+[A-Za-z0-9._\-]+)").unwrap());
 
 /// Regex-based scrubbing of provider response IDs, URLs, and credential fragments.
 pub(crate) fn sanitize_provider_message(msg: &str) -> String {
@@ -141,12 +141,21 @@ impl From<ServiceGatewayError> for LlmProviderError {
         match err {
             ServiceGatewayError::RateLimitExceeded {
                 retry_after_secs, ..
+            }
+            | ServiceGatewayError::QueueFull {
+                retry_after_secs, ..
+            }
+            | ServiceGatewayError::QueueTimeout {
+                retry_after_secs, ..
             } => LlmProviderError::RateLimited { retry_after_secs },
 
             ServiceGatewayError::ConnectionTimeout { .. }
             | ServiceGatewayError::RequestTimeout { .. } => LlmProviderError::Timeout,
 
-            ServiceGatewayError::UpstreamDisabled { .. } => LlmProviderError::ProviderUnavailable,
+            ServiceGatewayError::UpstreamDisabled { .. }
+            | ServiceGatewayError::CircuitBreakerOpen { .. } => {
+                LlmProviderError::ProviderUnavailable
+            }
 
             other => {
                 let raw = other.to_string();
@@ -211,6 +220,19 @@ pub struct ResponseResult {
     pub raw_response: serde_json::Value,
 }
 
+/// A function call the model asked the caller to execute.
+///
+/// Only client-executed [`LlmTool::Function`] tools produce these;
+/// provider-side tools (file/web search) are resolved by the provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolCall {
+    /// Provider-issued call ID, echoed back with the result.
+    pub call_id: String,
+    pub name: String,
+    /// Raw JSON arguments as streamed by the provider.
+    pub arguments: String,
+}
+
 /// Terminal outcome when a stream ends.
 #[derive(Debug)]
 pub enum TerminalOutcome {
@@ -220,6 +242,8 @@ pub enum TerminalOutcome {
         response_id: String,
         content: String,
         citations: Vec<Citation>,
+        /// Function calls awaiting execution; empty for a final answer.
+        tool_calls: Vec<ToolCall>,
         raw_response: serde_json::Value,
     },
     /// Provider returned an error or stream failed.
@@ -494,6 +518,14 @@ mod tests {
         assert!(sanitized.contains("[credential]"));
     }
 
+    #[test]
+    fn sanitize_removes_hyphenated_keys() {
+        let msg = "invalid x-api-key sk-ant-REDACTED";
+        let sanitized = sanitize_provider_message(msg);
+        assert!(!sanitized.contains("sk-ant-api03"));
+        assert_eq!(sanitized, "invalid x-api-key [credential]");
+    }
+
     #[test]
     fn sanitize_mixed_content() {
         let msg = "resp_abc123 at https://api.openai.com with sk-test1234567890";
@@ -534,6 +566,22 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn gateway_queue_timeout_maps_to_rate_limited() {
+        let err = ServiceGatewayError::QueueTimeout {
+            detail: "queued too long".into(),
+            instance: "/test".into(),
+            retry_after_secs: Some(2),
+        };
+        let mapped: LlmProviderError = err.into();
+        assert!(matches!(
+            mapped,
+            LlmProviderError::RateLimited {
+                retry_after_secs: Some(2)
+            }
+        ));
+    }
+
     #[test]
     fn gateway_connection_timeout_maps_to_timeout() {
         let err = ServiceGatewayError::ConnectionTimeout {
@@ -564,6 +612,17 @@ mod tests {
         assert!(matches!(mapped, LlmProviderError::ProviderUnavailable));
     }
 
+    #[test]
+    fn gateway_circuit_breaker_open_maps_to_unavailable() {
+        let err = ServiceGatewayError::CircuitBreakerOpen {
+            detail: "circuit open".into(),
+            instance: "/test".into(),
+            retry_after_secs: Some(5),
+        };
+        let mapped: LlmProviderError = err.into();
+        assert!(matches!(mapped, LlmProviderError::ProviderUnavailable));
+    }
+
     #[test]
     fn gateway_downstream_error_maps_to_provider_error() {
         let err = ServiceGatewayError::DownstreamError {
@@ -0,0 +1,1568 @@
+//! Anthropic Messages API adapter (`/v1/messages`).
+//!
+//! Implements [`LlmProvider`] by converting [`LlmRequest`] to the Messages
+//! API wire format, proxying through OAGW, parsing SSE events, and
+//! translating them to the shared `TranslatedEvent` contract.
+//!
+//! The Messages API streams content as indexed blocks (`text`, `thinking`,
+//! `tool_use`, `server_tool_use`, ...). Text deltas are forwarded as-is;
+//! thinking deltas are consumed but not forwarded, since the client
+//! contract only carries answer text. Usage arrives in two parts: input
+//! tokens in `message_start`, cumulative output tokens in `message_delta`.
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::StreamExt;
+use oagw_sdk::error::StreamingError;
+use oagw_sdk::sse::{FromServerEvent, ServerEvent, ServerEventsResponse, ServerEventsStream};
+use oagw_sdk::{Body, SecurityContext, ServiceGatewayClientV1};
+use serde::Deserialize;
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+use crate::infra::llm::request::{ContentPart as MessageContentPart, LlmTool, Role};
+use crate::infra::llm::{
+    Citation, CitationSource, ClientSseEvent, LlmProviderError, LlmRequest, NonStreaming,
+    ProviderStream, RawDetail, ResponseResult, Streaming, TerminalOutcome, TextSpan, ToolCall,
+    ToolPhase, TranslatedEvent, Usage,
+};
+
+/// Value of the `anthropic-version` header sent with every request.
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// `max_tokens` is mandatory in the Messages API; used when the request
+/// does not set `max_output_tokens`.
+const DEFAULT_MAX_TOKENS: u64 = 4096;
+
+/// Server-side web search tool type.
+const WEB_SEARCH_TOOL_TYPE: &str = "web_search_20250305";
+
+// ════════════════════════════════════════════════════════════════════════════
+// Messages API SSE event types
+// ════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug)]
+enum MessagesEvent {
+    /// `message_start` — carries the message ID and input token usage.
+    MessageStart { id: String, usage: MessagesUsage },
+    /// `content_block_start` — a new indexed content block.
+    BlockStart { index: usize, block: ContentBlock },
+    /// `content_block_delta` — incremental content for a block.
+    BlockDelta { index: usize, delta: BlockDelta },
+    /// `content_block_stop` — the block at `index` is complete.
+    BlockStop { index: usize },
+    /// `message_delta` — stop reason and cumulative output usage.
+    MessageDelta {
+        stop_reason: Option<String>,
+        usage: Option<MessagesUsage>,
+    },
+    /// `message_stop` — end of the message.
+    MessageStop,
+    /// `error` — the provider failed mid-stream.
+    Error { error: ErrorPayload },
+    /// `ping` and unrecognized events (ignored).
+    Unknown,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[allow(clippy::struct_field_names)] // mirrors the wire format
+struct MessagesUsage {
+    #[serde(default)]
+    input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<i64>,
+    #[serde(default)]
+    output_tokens: Option<i64>,
+}
+
+impl MessagesUsage {
+    /// Total input tokens, including prompt-cache writes and reads.
+    fn total_input(&self) -> Option<i64> {
+        self.input_tokens.map(|n| {
+            n + self.cache_creation_input_tokens.unwrap_or(0)
+                + self.cache_read_input_tokens.unwrap_or(0)
+        })
+    }
+}
+
+/// A content block as announced by `content_block_start` (or returned in a
+/// non-streaming response).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentBlock {
+    Text {
+        #[serde(default)]
+        text: String,
+        #[serde(default)]
+        citations: Vec<MessagesCitation>,
+    },
+    Thinking,
+    RedactedThinking,
+    ToolUse {
+        id: String,
+        name: String,
+    },
+    ServerToolUse {
+        #[serde(default)]
+        name: String,
+    },
+    WebSearchToolResult {
+        #[serde(default)]
+        content: serde_json::Value,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum BlockDelta {
+    TextDelta {
+        text: String,
+    },
+    ThinkingDelta,
+    SignatureDelta,
+    InputJsonDelta {
+        partial_json: String,
+    },
+    CitationsDelta {
+        citation: MessagesCitation,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// A citation attached to a text block.
+#[derive(Debug, Clone, Deserialize)]
+struct MessagesCitation {
+    #[serde(default)]
+    r#type: String,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    document_title: Option<String>,
+    #[serde(default)]
+    cited_text: String,
+    #[serde(default)]
+    start_char_index: Option<usize>,
+    #[serde(default)]
+    end_char_index: Option<usize>,
+}
+
+impl MessagesCitation {
+    fn to_citation(&self) -> Option<Citation> {
+        let (source, title, url) = match self.r#type.as_str() {
+            "web_search_result_location" => (
+                CitationSource::Web,
+                self.title.clone().unwrap_or_default(),
+                self.url.clone(),
+            ),
+            "char_location" | "page_location" | "content_block_location" => (
+                CitationSource::File,
+                self.document_title.clone().unwrap_or_default(),
+                None,
+            ),
+            _ => return None,
+        };
+        Some(Citation {
+            source,
+            title,
+            url,
+            attachment_id: None,
+            snippet: self.cited_text.clone(),
+            score: None,
+            span: match (self.start_char_index, self.end_char_index) {
+                (Some(start), Some(end)) => Some(TextSpan { start, end }),
+                _ => None,
+            },
+        })
+    }
+}
+
+/// Provider error payload: `{"type": "error", "error": {"type": ..., "message": ...}}`.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ErrorPayload {
+    #[serde(default, rename = "type")]
+    kind: String,
+    #[serde(default)]
+    message: String,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// SSE deserialization helpers
+// ════════════════════════════════════════════════════════════════════════════
+
+#[derive(Deserialize)]
+struct MessageStartData {
+    message: MessageStartMessage,
+}
+
+#[derive(Deserialize)]
+struct MessageStartMessage {
+    id: String,
+    #[serde(default)]
+    usage: MessagesUsage,
+}
+
+#[derive(Deserialize)]
+struct BlockStartData {
+    index: usize,
+    content_block: ContentBlock,
+}
+
+#[derive(Deserialize)]
+struct BlockDeltaData {
+    index: usize,
+    delta: BlockDelta,
+}
+
+#[derive(Deserialize)]
+struct BlockStopData {
+    index: usize,
+}
+
+#[derive(Deserialize)]
+struct MessageDeltaData {
+    #[serde(default)]
+    delta: MessageDeltaBody,
+    #[serde(default)]
+    usage: Option<MessagesUsage>,
+}
+
+#[derive(Default, Deserialize)]
+struct MessageDeltaBody {
+    #[serde(default)]
+    stop_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ErrorEnvelope {
+    error: ErrorPayload,
+}
+
+fn parse_data<T: serde::de::DeserializeOwned>(
+    event: &ServerEvent,
+    what: &str,
+) -> Result<T, StreamingError> {
+    serde_json::from_str(&event.data).map_err(|e| StreamingError::ServerEventsParse {
+        detail: format!("failed to parse {what}: {e}"),
+    })
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// FromServerEvent
+// ════════════════════════════════════════════════════════════════════════════
+
+impl FromServerEvent for MessagesEvent {
+    fn from_server_event(event: ServerEvent) -> Result<Self, StreamingError> {
+        let event_name = event.event.as_deref().unwrap_or("message");
+
+        match event_name {
+            "message_start" => {
+                let data: MessageStartData = parse_data(&event, "message_start")?;
+                Ok(MessagesEvent::MessageStart {
+                    id: data.message.id,
+                    usage: data.message.usage,
+                })
+            }
+
+            "content_block_start" => {
+                let data: BlockStartData = parse_data(&event, "content_block_start")?;
+                Ok(MessagesEvent::BlockStart {
+                    index: data.index,
+                    block: data.content_block,
+                })
+            }
+
+            "content_block_delta" => {
+                let data: BlockDeltaData = parse_data(&event, "content_block_delta")?;
+                Ok(MessagesEvent::BlockDelta {
+                    index: data.index,
+                    delta: data.delta,
+                })
+            }
+
+            "content_block_stop" => {
+                let data: BlockStopData = parse_data(&event, "content_block_stop")?;
+                Ok(MessagesEvent::BlockStop { index: data.index })
+            }
+
+            "message_delta" => {
+                let data: MessageDeltaData = parse_data(&event, "message_delta")?;
+                Ok(MessagesEvent::MessageDelta {
+                    stop_reason: data.delta.stop_reason,
+                    usage: data.usage,
+                })
+            }
+
+            "message_stop" => Ok(MessagesEvent::MessageStop),
+
+            "error" => {
+                let sanitized_data = crate::infra::llm::sanitize_provider_message(&event.data);
+                tracing::warn!(data = %sanitized_data, "provider error SSE event");
+                let error = serde_json::from_str::<ErrorEnvelope>(&event.data).map_or_else(
+                    |_| ErrorPayload {
+                        kind: String::new(),
+                        message: event.data.clone(),
+                    },
+                    |e| e.error,
+                );
+                Ok(MessagesEvent::Error { error })
+            }
+
+            "ping" => Ok(MessagesEvent::Unknown),
+
+            other => {
+                debug!(event_name = other, data = %event.data, "unknown provider event, skipping");
+                Ok(MessagesEvent::Unknown)
+            }
+        }
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Scan state + translation
+// ════════════════════════════════════════════════════════════════════════════
+
+/// An open `tool_use` block whose input JSON is still streaming.
+struct PendingToolUse {
+    index: usize,
+    id: String,
+    name: String,
+    input_json: String,
+}
+
+struct MessagesState {
+    response_id: String,
+    accumulated_text: String,
+    input_tokens: i64,
+    output_tokens: i64,
+    stop_reason: Option<String>,
+    tool_uses: Vec<PendingToolUse>,
+    tool_calls: Vec<ToolCall>,
+    citations: Vec<Citation>,
+}
+
+impl MessagesState {
+    fn new() -> Self {
+        Self {
+            response_id: String::new(),
+            accumulated_text: String::new(),
+            input_tokens: 0,
+            output_tokens: 0,
+            stop_reason: None,
+            tool_uses: Vec::new(),
+            tool_calls: Vec::new(),
+            citations: Vec::new(),
+        }
+    }
+
+    fn apply_usage(&mut self, usage: &MessagesUsage) {
+        if let Some(input) = usage.total_input() {
+            self.input_tokens = input;
+        }
+        if let Some(output) = usage.output_tokens {
+            self.output_tokens = output;
+        }
+    }
+
+    fn usage(&self) -> Usage {
+        Usage {
+            input_tokens: self.input_tokens,
+            output_tokens: self.output_tokens,
+        }
+    }
+
+    fn make_terminal(&self) -> TranslatedEvent {
+        match self.stop_reason.as_deref() {
+            Some("max_tokens") => TranslatedEvent::Terminal(TerminalOutcome::Incomplete {
+                reason: "max_tokens".to_owned(),
+                usage: self.usage(),
+                partial_content: self.accumulated_text.clone(),
+            }),
+            Some(reason @ ("refusal" | "pause_turn")) => {
+                TranslatedEvent::Terminal(TerminalOutcome::Incomplete {
+                    reason: reason.to_owned(),
+                    usage: self.usage(),
+                    partial_content: self.accumulated_text.clone(),
+                })
+            }
+            _ => TranslatedEvent::Terminal(TerminalOutcome::Completed {
+                usage: self.usage(),
+                response_id: self.response_id.clone(),
+                content: self.accumulated_text.clone(),
+                citations: self.citations.clone(),
+                tool_calls: self.tool_calls.clone(),
+                raw_response: serde_json::Value::Null,
+            }),
+        }
+    }
+}
+
+/// Map a Messages API error payload to a provider error.
+///
+/// `rate_limit_error` and `overloaded_error` get their dedicated variants so
+/// that callers treat them like the equivalent OAGW failures.
+fn error_from_payload(error: &ErrorPayload) -> LlmProviderError {
+    match error.kind.as_str() {
+        "rate_limit_error" => LlmProviderError::RateLimited {
+            retry_after_secs: None,
+        },
+        "overloaded_error" => LlmProviderError::ProviderUnavailable,
+        _ => LlmProviderError::ProviderError {
+            code: error.kind.clone(),
+            message: crate::infra::llm::sanitize_provider_message(&error.message),
+            raw_detail: Some(RawDetail(error.message.clone())),
+        },
+    }
+}
+
+fn translate_messages_event(event: MessagesEvent, state: &mut MessagesState) -> TranslatedEvent {
+    match event {
+        MessagesEvent::MessageStart { id, usage } => {
+            state.response_id = id;
+            state.apply_usage(&usage);
+            TranslatedEvent::Skip
+        }
+
+        MessagesEvent::BlockStart { index, block } => match block {
+            ContentBlock::Text { text, citations } => {
+                state
+                    .citations
+                    .extend(citations.iter().filter_map(MessagesCitation::to_citation));
+                if text.is_empty() {
+                    return TranslatedEvent::Skip;
+                }
+                state.accumulated_text.push_str(&text);
+                TranslatedEvent::Sse(ClientSseEvent::Delta {
+                    r#type: "text",
+                    content: text,
+                })
+            }
+            ContentBlock::ToolUse { id, name, .. } => {
+                let start = TranslatedEvent::Sse(ClientSseEvent::Tool {
+                    phase: ToolPhase::Start,
+                    name: "function_call",
+                    details: serde_json::json!({
+                        "index": index,
+                        "call_id": id,
+                        "name": name,
+                    }),
+                });
+                state.tool_uses.push(PendingToolUse {
+                    index,
+                    id,
+                    name,
+                    input_json: String::new(),
+                });
+                start
+            }
+            ContentBlock::ServerToolUse { name } if name == "web_search" => {
+                TranslatedEvent::Sse(ClientSseEvent::Tool {
+                    phase: ToolPhase::Start,
+                    name: "web_search",
+                    details: serde_json::json!({}),
+                })
+            }
+            ContentBlock::WebSearchToolResult { content } => {
+                let results = content.as_array().map_or(0, Vec::len);
+                TranslatedEvent::Sse(ClientSseEvent::Tool {
+                    phase: ToolPhase::Done,
+                    name: "web_search",
+                    details: serde_json::json!({ "results": results }),
+                })
+            }
+            ContentBlock::ServerToolUse { .. }
+            | ContentBlock::Thinking
+            | ContentBlock::RedactedThinking
+            | ContentBlock::Other => TranslatedEvent::Skip,
+        },
+
+        MessagesEvent::BlockDelta { index, delta } => match delta {
+            BlockDelta::TextDelta { text } => {
+                state.accumulated_text.push_str(&text);
+                TranslatedEvent::Sse(ClientSseEvent::Delta {
+                    r#type: "text",
+                    content: text,
+                })
+            }
+            BlockDelta::InputJsonDelta { partial_json } => {
+                if let Some(tool) = state.tool_uses.iter_mut().find(|t| t.index == index) {
+                    tool.input_json.push_str(&partial_json);
+                }
+                TranslatedEvent::Skip
+            }
+            BlockDelta::CitationsDelta { citation } => {
+                state.citations.extend(citation.to_citation());
+                TranslatedEvent::Skip
+            }
+            // Thinking is not part of the answer; the signature only matters
+            // when replaying thinking blocks back to the provider.
+            BlockDelta::ThinkingDelta | BlockDelta::SignatureDelta | BlockDelta::Other => {
+                TranslatedEvent::Skip
+            }
+        },
+
+        MessagesEvent::BlockStop { index } => {
+            let Some(pos) = state.tool_uses.iter().position(|t| t.index == index) else {
+                return TranslatedEvent::Skip;
+            };
+            let tool = state.tool_uses.remove(pos);
+            // A tool without parameters streams no input at all.
+            let arguments = if tool.input_json.is_empty() {
+                "{}".to_owned()
+            } else {
+                tool.input_json
+            };
+            let event = TranslatedEvent::Sse(ClientSseEvent::Tool {
+                phase: ToolPhase::Done,
+                name: "function_call",
+                details: serde_json::json!({
+                    "call_id": tool.id,
+                    "name": tool.name,
+                    "arguments": arguments,
+                }),
+            });
+            state.tool_calls.push(ToolCall {
+                call_id: tool.id,
+                name: tool.name,
+                arguments,
+            });
+            event
+        }
+
+        MessagesEvent::MessageDelta { stop_reason, usage } => {
+            if stop_reason.is_some() {
+                state.stop_reason = stop_reason;
+            }
+            if let Some(usage) = usage {
+                state.apply_usage(&usage);
+            }
+            TranslatedEvent::Skip
+        }
+
+        MessagesEvent::MessageStop => state.make_terminal(),
+
+        MessagesEvent::Error { error } => TranslatedEvent::Terminal(TerminalOutcome::Failed {
+            error: error_from_payload(&error),
+            usage: Some(state.usage()),
+            partial_content: state.accumulated_text.clone(),
+        }),
+
+        MessagesEvent::Unknown => TranslatedEvent::Skip,
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// LlmRequest → Messages API conversion
+// ════════════════════════════════════════════════════════════════════════════
+
+fn build_request_body<M>(request: &LlmRequest<M>, stream: bool) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "model": &request.model,
+        "max_tokens": request.max_output_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+    });
+
+    if stream {
+        body["stream"] = serde_json::json!(true);
+    }
+
+    // The Messages API has no system role: system instructions and any
+    // system messages are joined into the top-level `system` field.
+    let system: Vec<&str> = request
+        .system_instructions
+        .iter()
+        .map(String::as_str)
+        .chain(
+            request
+                .messages
+                .iter()
+                .filter(|msg| msg.role == Role::System)
+                .flat_map(|msg| &msg.content)
+                .filter_map(|part| match part {
+                    MessageContentPart::Text { text } => Some(text.as_str()),
+                    _ => None,
+                }),
+        )
+        .collect();
+    if !system.is_empty() {
+        body["system"] = serde_json::json!(system.join("\n\n"));
+    }
+
+    let messages: Vec<serde_json::Value> = request
+        .messages
+        .iter()
+        .filter(|msg| msg.role != Role::System)
+        .map(|msg| {
+            let role = if msg.role == Role::Assistant {
+                "assistant"
+            } else {
+                "user"
+            };
+
+            // Simple text messages use string content
+            if msg.content.len() == 1
+                && let MessageContentPart::Text { text } = &msg.content[0]
+            {
+                return serde_json::json!({ "role": role, "content": text });
+            }
+
+            let content: Vec<serde_json::Value> = msg
+                .content
+                .iter()
+                .map(|part| match part {
+                    MessageContentPart::Text { text } => serde_json::json!({
+                        "type": "text",
+                        "text": text
+                    }),
+                    MessageContentPart::Image { file_id } => serde_json::json!({
+                        "type": "image",
+                        "source": { "type": "file", "file_id": file_id }
+                    }),
+                    MessageContentPart::ToolCall {
+                        call_id,
+                        name,
+                        arguments,
+                    } => serde_json::json!({
+                        "type": "tool_use",
+                        "id": call_id,
+                        "name": name,
+                        // `input` must be an object; unparseable arguments
+                        // were already reported back to the model as an error.
+                        "input": serde_json::from_str::<serde_json::Value>(arguments)
+                            .ok()
+                            .filter(serde_json::Value::is_object)
+                            .unwrap_or_else(|| serde_json::json!({}))
+                    }),
+                    MessageContentPart::ToolResult {
+                        call_id,
+                        content,
+                        is_error,
+                    } => serde_json::json!({
+                        "type": "tool_result",
+                        "tool_use_id": call_id,
+                        "content": content,
+                        "is_error": is_error
+                    }),
+                })
+                .collect();
+            serde_json::json!({ "role": role, "content": content })
+        })
+        .collect();
+    body["messages"] = serde_json::Value::Array(messages);
+
+    // metadata.user_id: "{tenant_id}:{user_id}"
+    if let Some(ref identity) = request.user_identity {
+        body["metadata"] = serde_json::json!({
+            "user_id": format!("{}:{}", identity.tenant_id, identity.user_id)
+        });
+    }
+
+    // Map tools: Function → custom tool, WebSearch → server web search,
+    // FileSearch → drop
+    let tools: Vec<serde_json::Value> = request
+        .tools
+        .iter()
+        .filter_map(|tool| match tool {
+            LlmTool::Function {
+                name,
+                description,
+                parameters,
+            } => Some(serde_json::json!({
+                "name": name,
+                "description": description,
+                "input_schema": parameters
+            })),
+            LlmTool::WebSearch => Some(serde_json::json!({
+                "type": WEB_SEARCH_TOOL_TYPE,
+                "name": "web_search"
+            })),
+            LlmTool::FileSearch { .. } => {
+                debug!("FileSearch tool not supported by Anthropic Messages, dropping");
+                None
+            }
+        })
+        .collect();
+    if !tools.is_empty() {
+        body["tools"] = serde_json::Value::Array(tools);
+    }
+
+    // Merge additional params (e.g. `thinking`, `temperature`)
+    if let Some(ref extra) = request.additional_params
+        && let (Some(body_obj), Some(extra_obj)) = (body.as_object_mut(), extra.as_object())
+    {
+        for (k, v) in extra_obj {
+            body_obj.insert(k.clone(), v.clone());
+        }
+    }
+
+    body
+}
+
+/// Serialize a request body to `Body::Bytes`.
+#[allow(clippy::expect_used)] // serde_json::Value always serializes successfully
+fn body_to_bytes(body: &serde_json::Value) -> Body {
+    let json = serde_json::to_vec(body).expect("serde_json::Value always serializes");
+    Body::Bytes(Bytes::from(json))
+}
+
+/// Parse an error response body (`{"type":"error","error":{...}}`).
+fn parse_error_response(bytes: &[u8]) -> LlmProviderError {
+    if let Ok(envelope) = serde_json::from_slice::<ErrorEnvelope>(bytes) {
+        return error_from_payload(&envelope.error);
+    }
+
+    let body_str = String::from_utf8_lossy(bytes);
+    let snippet = crate::infra::llm::sanitize_provider_message(
+        &body_str.chars().take(200).collect::<String>(),
+    );
+    LlmProviderError::InvalidResponse {
+        detail: format!("non-SSE response with unparseable body: {snippet}"),
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// AnthropicMessagesProvider
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Anthropic Messages API adapter. Routes all calls through OAGW.
+///
+/// The OAGW upstream is expected to inject the `x-api-key` credential.
+#[derive(Clone)]
+pub struct AnthropicMessagesProvider {
+    gateway: Arc<dyn ServiceGatewayClientV1>,
+    upstream_alias: String,
+}
+
+impl AnthropicMessagesProvider {
+    #[must_use]
+    pub fn new(gateway: Arc<dyn ServiceGatewayClientV1>, upstream_alias: String) -> Self {
+        Self {
+            gateway,
+            upstream_alias,
+        }
+    }
+
+    fn build_http_request(
+        &self,
+        body: &serde_json::Value,
+        accept: &str,
+    ) -> Result<http::Request<Body>, LlmProviderError> {
+        http::Request::builder()
+            .method(http::Method::POST)
+            .uri(format!("/{}/v1/messages", self.upstream_alias))
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .header(http::header::ACCEPT, accept)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .body(body_to_bytes(body))
+            .map_err(|e| LlmProviderError::InvalidResponse {
+                detail: format!("failed to build HTTP request: {e}"),
+            })
+    }
+}
+
+/// Messages API non-streaming response.
+#[derive(Deserialize)]
+struct MessagesResponse {
+    id: String,
+    #[serde(default)]
+    content: Vec<ContentBlock>,
+    #[serde(default)]
+    usage: MessagesUsage,
+}
+
+#[async_trait::async_trait]
+impl crate::infra::llm::LlmProvider for AnthropicMessagesProvider {
+    #[tracing::instrument(
+        skip(self, ctx, request, cancel),
+        fields(model = %request.model())
+    )]
+    async fn stream(
+        &self,
+        ctx: SecurityContext,
+        request: LlmRequest<Streaming>,
+        cancel: CancellationToken,
+    ) -> Result<ProviderStream, LlmProviderError> {
+        let body = build_request_body(&request, true);
+        let http_request = self.build_http_request(&body, "text/event-stream")?;
+
+        let response = self.gateway.proxy_request(ctx, http_request).await?;
+
+        match ServerEventsStream::from_response::<MessagesEvent>(response) {
+            ServerEventsResponse::Events(event_stream) => {
+                let translated = event_stream.scan(MessagesState::new(), |state, result| {
+                    let output = match result {
+                        Ok(event) => Ok(translate_messages_event(event, state)),
+                        Err(e) => {
+                            tracing::warn!(error = %e, "provider SSE stream error");
+                            Err(e)
+                        }
+                    };
+                    async move { Some(output) }
+                });
+
+                Ok(ProviderStream::new(translated, cancel))
+            }
+            ServerEventsResponse::Response(resp) => {
+                let (parts, body) = resp.into_parts();
+                tracing::warn!(status = %parts.status, "provider returned non-SSE response");
+                match body.into_bytes().await {
+                    Ok(bytes) => Err(parse_error_response(&bytes)),
+                    Err(e) => Err(LlmProviderError::InvalidResponse {
+                        detail: format!("failed to read response body: {e}"),
+                    }),
+                }
+            }
+        }
+    }
+
+    #[tracing::instrument(
+        skip(self, ctx, request),
+        fields(model = %request.model())
+    )]
+    async fn complete(
+        &self,
+        ctx: SecurityContext,
+        request: LlmRequest<NonStreaming>,
+    ) -> Result<ResponseResult, LlmProviderError> {
+        let body = build_request_body(&request, false);
+        let http_request = self.build_http_request(&body, "application/json")?;
+
+        let response = self.gateway.proxy_request(ctx, http_request).await?;
+
+        let (parts, resp_body) = response.into_parts();
+        let bytes =
+            resp_body
+                .into_bytes()
+                .await
+                .map_err(|e| LlmProviderError::InvalidResponse {
+                    detail: format!("failed to read response body: {e}"),
+                })?;
+
+        if !parts.status.is_success() {
+            return Err(parse_error_response(&bytes));
+        }
+
+        let resp: MessagesResponse =
+            serde_json::from_slice(&bytes).map_err(|_| parse_error_response(&bytes))?;
+
+        let mut content = String::new();
+        let mut citations = Vec::new();
+        for block in &resp.content {
+            if let ContentBlock::Text {
+                text,
+                citations: block_citations,
+            } = block
+            {
+                content.push_str(text);
+                citations.extend(
+                    block_citations
+                        .iter()
+                        .filter_map(MessagesCitation::to_citation),
+                );
+            }
+        }
+
+        Ok(ResponseResult {
+            content,
+            usage: Usage {
+                input_tokens: resp.usage.total_input().unwrap_or(0),
+                output_tokens: resp.usage.output_tokens.unwrap_or(0),
+            },
+            response_id: resp.id,
+            citations,
+            raw_response: serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null),
+        })
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Tests
+// ════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+#[allow(clippy::str_to_string)]
+mod tests {
+    use super::*;
+    use crate::infra::llm::providers::test_support::MockGateway;
+    use crate::infra::llm::{LlmMessage, LlmProvider, llm_request};
+
+    use oagw_sdk::error::ServiceGatewayError;
+
+    fn test_security_context() -> SecurityContext {
+        SecurityContext::anonymous()
+    }
+
+    fn sse_event(event_type: &str, data: &str) -> String {
+        format!("event: {event_type}\ndata: {data}")
+    }
+
+    fn parse(event_type: &str, data: &str) -> MessagesEvent {
+        MessagesEvent::from_server_event(ServerEvent {
+            event: Some(event_type.into()),
+            data: data.into(),
+            id: None,
+            retry: None,
+        })
+        .unwrap()
+    }
+
+    /// Feed raw SSE events through parsing and translation.
+    fn translate_all(events: &[(&str, &str)]) -> (Vec<TranslatedEvent>, MessagesState) {
+        let mut state = MessagesState::new();
+        let translated = events
+            .iter()
+            .map(|(name, data)| translate_messages_event(parse(name, data), &mut state))
+            .collect();
+        (translated, state)
+    }
+
+    const MESSAGE_START: (&str, &str) = (
+        "message_start",
+        r#"{"type":"message_start","message":{"id":"msg_01abc","usage":{"input_tokens":120,"cache_read_input_tokens":30,"output_tokens":1}}}"#,
+    );
+
+    // ── Request serialization tests ───────────────────────────────────────
+
+    #[test]
+    fn request_basic_text() {
+        let request = llm_request("claude-sonnet-4-5")
+            .message(LlmMessage::user("Hello"))
+            .system_instructions("Be helpful")
+            .max_output_tokens(1024)
+            .user_identity("abc", "def")
+            .build_streaming();
+
+        let body = build_request_body(&request, true);
+
+        assert_eq!(body["model"], "claude-sonnet-4-5");
+        assert_eq!(body["max_tokens"], 1024);
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["system"], "Be helpful");
+        assert_eq!(body["metadata"]["user_id"], "abc:def");
+
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["role"], "user");
+        assert_eq!(messages[0]["content"], "Hello");
+    }
+
+    #[test]
+    fn request_defaults_max_tokens_and_omits_stream() {
+        let request = llm_request("claude-sonnet-4-5").build_non_streaming();
+
+        let body = build_request_body(&request, false);
+
+        assert_eq!(body["max_tokens"], DEFAULT_MAX_TOKENS);
+        assert!(body.get("stream").is_none());
+        assert!(body.get("system").is_none());
+    }
+
+    #[test]
+    fn request_system_messages_join_system_field() {
+        let request = llm_request("claude-sonnet-4-5")
+            .system_instructions("Be helpful")
+            .message(LlmMessage {
+                role: Role::System,
+                content: vec![MessageContentPart::Text {
+                    text: "Thread summary".into(),
+                }],
+            })
+            .message(LlmMessage::user("Hi"))
+            .message(LlmMessage::assistant("Hello!"))
+            .build_streaming();
+
+        let body = build_request_body(&request, true);
+
+        assert_eq!(body["system"], "Be helpful\n\nThread summary");
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0]["role"], "user");
+        assert_eq!(messages[1]["role"], "assistant");
+    }
+
+    #[test]
+    fn request_image_uses_file_source() {
+        let request = llm_request("claude-sonnet-4-5")
+            .message(LlmMessage::user_with_image("Describe this", "file_011abc"))
+            .build_streaming();
+
+        let body = build_request_body(&request, true);
+
+        let content = &body["messages"][0]["content"];
+        assert_eq!(content[0]["type"], "text");
+        assert_eq!(content[1]["type"], "image");
+        assert_eq!(content[1]["source"]["type"], "file");
+        assert_eq!(content[1]["source"]["file_id"], "file_011abc");
+    }
+
+    #[test]
+    fn request_tools_mapped() {
+        let request = llm_request("claude-sonnet-4-5")
+            .tools(vec![
+                LlmTool::Function {
+                    name: "get_weather".into(),
+                    description: "Get weather".into(),
+                    parameters: serde_json::json!({"type": "object"}),
+                },
+                LlmTool::WebSearch,
+                LlmTool::FileSearch {
+                    vector_store_ids: vec!["vs-1".into()],
+                },
+            ])
+            .build_streaming();
+
+        let body = build_request_body(&request, true);
+
+        let tools = body["tools"].as_array().unwrap();
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0]["name"], "get_weather");
+        assert_eq!(tools[0]["input_schema"]["type"], "object");
+        assert_eq!(tools[1]["type"], WEB_SEARCH_TOOL_TYPE);
+        assert_eq!(tools[1]["name"], "web_search");
+    }
+
+    #[test]
+    fn request_tool_round_trip_mapped() {
+        let call = ToolCall {
+            call_id: "toolu_01".into(),
+            name: "get_weather".into(),
+            arguments: r#"{"location":"SF"}"#.into(),
+        };
+        let request = llm_request("claude-sonnet-4-5")
+            .message(LlmMessage::user("Weather in SF?"))
+            .message(LlmMessage::assistant_tool_calls("Checking.", &[call]))
+            .message(LlmMessage::tool_results(vec![
+                MessageContentPart::ToolResult {
+                    call_id: "toolu_01".into(),
+                    content: "upstream unavailable".into(),
+                    is_error: true,
+                },
+            ]))
+            .build_streaming();
+
+        let body = build_request_body(&request, true);
+
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 3);
+        let assistant = messages[1]["content"].as_array().unwrap();
+        assert_eq!(assistant[0]["type"], "text");
+        assert_eq!(assistant[1]["type"], "tool_use");
+        assert_eq!(assistant[1]["id"], "toolu_01");
+        assert_eq!(assistant[1]["input"]["location"], "SF");
+        assert_eq!(messages[2]["role"], "user");
+        assert_eq!(messages[2]["content"][0]["type"], "tool_result");
+        assert_eq!(messages[2]["content"][0]["tool_use_id"], "toolu_01");
+        assert_eq!(messages[2]["content"][0]["is_error"], true);
+    }
+
+    #[test]
+    fn request_additional_params_merged() {
+        let request = llm_request("claude-sonnet-4-5")
+            .additional_params(serde_json::json!({
+                "thinking": {"type": "enabled", "budget_tokens": 2048}
+            }))
+            .build_streaming();
+
+        let body = build_request_body(&request, true);
+
+        assert_eq!(body["thinking"]["budget_tokens"], 2048);
+    }
+
+    // ── FromServerEvent tests ─────────────────────────────────────────────
+
+    #[test]
+    fn parse_message_start() {
+        match parse(MESSAGE_START.0, MESSAGE_START.1) {
+            MessagesEvent::MessageStart { id, usage } => {
+                assert_eq!(id, "msg_01abc");
+                assert_eq!(usage.total_input(), Some(150));
+            }
+            other => panic!("expected MessageStart, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_ping_is_unknown() {
+        assert!(matches!(
+            parse("ping", r#"{"type":"ping"}"#),
+            MessagesEvent::Unknown
+        ));
+    }
+
+    #[test]
+    fn parse_unknown_block_and_delta_types() {
+        assert!(matches!(
+            parse(
+                "content_block_start",
+                r#"{"index":0,"content_block":{"type":"future_block"}}"#
+            ),
+            MessagesEvent::BlockStart {
+                block: ContentBlock::Other,
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse(
+                "content_block_delta",
+                r#"{"index":0,"delta":{"type":"future_delta","x":1}}"#
+            ),
+            MessagesEvent::BlockDelta {
+                delta: BlockDelta::Other,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn parse_malformed_json_returns_error() {
+        let result = MessagesEvent::from_server_event(ServerEvent {
+            event: Some("content_block_delta".into()),
+            data: "not json".into(),
+            id: None,
+            retry: None,
+        });
+        assert!(matches!(
+            result.unwrap_err(),
+            StreamingError::ServerEventsParse { .. }
+        ));
+    }
+
+    // ── Translation tests ─────────────────────────────────────────────────
+
+    #[test]
+    fn translate_text_and_thinking() {
+        let (events, state) = translate_all(&[
+            MESSAGE_START,
+            (
+                "content_block_start",
+                r#"{"index":0,"content_block":{"type":"thinking","thinking":""}}"#,
+            ),
+            (
+                "content_block_delta",
+                r#"{"index":0,"delta":{"type":"thinking_delta","thinking":"Let me think"}}"#,
+            ),
+            (
+                "content_block_delta",
+                r#"{"index":0,"delta":{"type":"signature_delta","signature":"abc"}}"#,
+            ),
+            ("content_block_stop", r#"{"index":0}"#),
+            (
+                "content_block_start",
+                r#"{"index":1,"content_block":{"type":"text","text":""}}"#,
+            ),
+            (
+                "content_block_delta",
+                r#"{"index":1,"delta":{"type":"text_delta","text":"Hello"}}"#,
+            ),
+            ("content_block_stop", r#"{"index":1}"#),
+        ]);
+
+        let deltas: Vec<&str> = events
+            .iter()
+            .filter_map(|e| match e {
+                TranslatedEvent::Sse(ClientSseEvent::Delta { content, .. }) => {
+                    Some(content.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(deltas, vec!["Hello"]);
+        assert_eq!(state.accumulated_text, "Hello");
+        assert_eq!(state.response_id, "msg_01abc");
+    }
+
+    #[test]
+    fn translate_tool_use_emits_start_and_done() {
+        let (events, _) = translate_all(&[
+            (
+                "content_block_start",
+                r#"{"index":1,"content_block":{"type":"tool_use","id":"toolu_01","name":"get_weather","input":{}}}"#,
+            ),
+            (
+                "content_block_delta",
+                r#"{"index":1,"delta":{"type":"input_json_delta","partial_json":"{\"location\":"}}"#,
+            ),
+            (
+                "content_block_delta",
+                r#"{"index":1,"delta":{"type":"input_json_delta","partial_json":"\"SF\"}"}}"#,
+            ),
+            ("content_block_stop", r#"{"index":1}"#),
+        ]);
+
+        match &events[0] {
+            TranslatedEvent::Sse(ClientSseEvent::Tool {
+                phase: ToolPhase::Start,
+                name,
+                details,
+            }) => {
+                assert_eq!(*name, "function_call");
+                assert_eq!(details["call_id"], "toolu_01");
+                assert_eq!(details["name"], "get_weather");
+            }
+            other => panic!("expected Tool(Start), got {other:?}"),
+        }
+        assert!(matches!(events[1], TranslatedEvent::Skip));
+        assert!(matches!(events[2], TranslatedEvent::Skip));
+        match &events[3] {
+            TranslatedEvent::Sse(ClientSseEvent::Tool {
+                phase: ToolPhase::Done,
+                details,
+                ..
+            }) => {
+                assert_eq!(details["arguments"], r#"{"location":"SF"}"#);
+            }
+            other => panic!("expected Tool(Done), got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn translate_tool_use_without_input_has_empty_object_arguments() {
+        let (events, _) = translate_all(&[
+            (
+                "content_block_start",
+                r#"{"index":0,"content_block":{"type":"tool_use","id":"toolu_02","name":"now","input":{}}}"#,
+            ),
+            ("content_block_stop", r#"{"index":0}"#),
+        ]);
+
+        match &events[1] {
+            TranslatedEvent::Sse(ClientSseEvent::Tool { details, .. }) => {
+                assert_eq!(details["arguments"], "{}");
+            }
+            other => panic!("expected Tool(Done), got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn translate_tool_use_stop_reports_calls_in_terminal() {
+        let (mut events, _) = translate_all(&[
+            MESSAGE_START,
+            (
+                "content_block_start",
+                r#"{"index":0,"content_block":{"type":"tool_use","id":"toolu_01","name":"get_weather","input":{}}}"#,
+            ),
+            (
+                "content_block_delta",
+                r#"{"index":0,"delta":{"type":"input_json_delta","partial_json":"{\"location\":\"SF\"}"}}"#,
+            ),
+            ("content_block_stop", r#"{"index":0}"#),
+            (
+                "message_delta",
+                r#"{"delta":{"stop_reason":"tool_use"},"usage":{"output_tokens":18}}"#,
+            ),
+            ("message_stop", "{}"),
+        ]);
+
+        match events.pop().unwrap() {
+            TranslatedEvent::Terminal(TerminalOutcome::Completed { tool_calls, .. }) => {
+                assert_eq!(
+                    tool_calls,
+                    vec![ToolCall {
+                        call_id: "toolu_01".into(),
+                        name: "get_weather".into(),
+                        arguments: r#"{"location":"SF"}"#.into(),
+                    }]
+                );
+            }
+            other => panic!("expected Completed terminal, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn translate_web_search_and_citations() {
+        let (events, state) = translate_all(&[
+            (
+                "content_block_start",
+                r#"{"index":0,"content_block":{"type":"server_tool_use","id":"srvtoolu_01","name":"web_search","input":{}}}"#,
+            ),
+            (
+                "content_block_start",
+                r#"{"index":1,"content_block":{"type":"web_search_tool_result","tool_use_id":"srvtoolu_01","content":[{"type":"web_search_result","url":"https://example.com","title":"Example"}]}}"#,
+            ),
+            (
+                "content_block_start",
+                r#"{"index":2,"content_block":{"type":"text","text":""}}"#,
+            ),
+            (
+                "content_block_delta",
+                r#"{"index":2,"delta":{"type":"citations_delta","citation":{"type":"web_search_result_location","url":"https://example.com","title":"Example","cited_text":"quoted"}}}"#,
+            ),
+        ]);
+
+        assert!(matches!(
+            &events[0],
+            TranslatedEvent::Sse(ClientSseEvent::Tool {
+                phase: ToolPhase::Start,
+                name: "web_search",
+                ..
+            })
+        ));
+        match &events[1] {
+            TranslatedEvent::Sse(ClientSseEvent::Tool {
+                phase: ToolPhase::Done,
+                name: "web_search",
+                details,
+            }) => assert_eq!(details["results"], 1),
+            other => panic!("expected Tool(Done), got {other:?}"),
+        }
+        assert_eq!(state.citations.len(), 1);
+        assert!(matches!(state.citations[0].source, CitationSource::Web));
+        assert_eq!(
+            state.citations[0].url.as_deref(),
+            Some("https://example.com")
+        );
+        assert_eq!(state.citations[0].snippet, "quoted");
+    }
+
+    #[test]
+    fn translate_stop_reasons() {
+        for (reason, expect_complete) in [
+            ("end_turn", true),
+            ("tool_use", true),
+            ("stop_sequence", true),
+            ("max_tokens", false),
+            ("refusal", false),
+        ] {
+            let delta = format!(
+                r#"{{"delta":{{"stop_reason":"{reason}"}},"usage":{{"output_tokens":42}}}}"#
+            );
+            let (mut events, _) = translate_all(&[
+                MESSAGE_START,
+                ("message_delta", &delta),
+                ("message_stop", "{}"),
+            ]);
+            match events.pop().unwrap() {
+                TranslatedEvent::Terminal(TerminalOutcome::Completed {
+                    usage,
+                    response_id,
+                    ..
+                }) if expect_complete => {
+                    assert_eq!(usage.input_tokens, 150);
+                    assert_eq!(usage.output_tokens, 42);
+                    assert_eq!(response_id, "msg_01abc");
+                }
+                TranslatedEvent::Terminal(TerminalOutcome::Incomplete {
+                    reason: r, usage, ..
+                }) if !expect_complete => {
+                    assert_eq!(r, reason);
+                    assert_eq!(usage.output_tokens, 42);
+                }
+                other => panic!("unexpected terminal for {reason}: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn translate_error_event_is_sanitized() {
+        let (events, _) = translate_all(&[(
+            "error",
+            r#"{"type":"error","error":{"type":"api_error","message":"failed msg_01xyz at https://api.anthropic.com/v1/messages"}}"#,
+        )]);
+
+        match &events[0] {
+            TranslatedEvent::Terminal(TerminalOutcome::Failed {
+                error:
+                    LlmProviderError::ProviderError {
+                        code,
+                        message,
+                        raw_detail,
+                    },
+                ..
+            }) => {
+                assert_eq!(code, "api_error");
+                assert!(!message.contains("msg_01xyz"));
+                assert!(!message.contains("https://api.anthropic.com"));
+                assert!(raw_detail.is_some());
+            }
+            other => panic!("expected Terminal(Failed), got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn overloaded_and_rate_limit_errors_use_dedicated_variants() {
+        let overloaded = ErrorPayload {
+            kind: "overloaded_error".into(),
+            message: "Overloaded".into(),
+        };
+        assert!(matches!(
+            error_from_payload(&overloaded),
+            LlmProviderError::ProviderUnavailable
+        ));
+        let rate_limited = ErrorPayload {
+            kind: "rate_limit_error".into(),
+            message: "slow down".into(),
+        };
+        assert!(matches!(
+            error_from_payload(&rate_limited),
+            LlmProviderError::RateLimited {
+                retry_after_secs: None
+            }
+        ));
+    }
+
+    // ── Integration tests: mock upstream ──────────────────────────────────
+
+    #[tokio::test]
+    async fn stream_yields_events_and_outcome() {
+        let events = vec![
+            sse_event(MESSAGE_START.0, MESSAGE_START.1),
+            sse_event(
+                "content_block_start",
+                r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            ),
+            sse_event("ping", r#"{"type":"ping"}"#),
+            sse_event(
+                "content_block_delta",
+                r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hello"}}"#,
+            ),
+            sse_event(
+                "content_block_delta",
+                r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":" world"}}"#,
+            ),
+            sse_event(
+                "content_block_stop",
+                r#"{"type":"content_block_stop","index":0}"#,
+            ),
+            sse_event(
+                "message_delta",
+                r#"{"type":"message_delta","delta":{"stop_reason":"end_turn","stop_sequence":null},"usage":{"output_tokens":12}}"#,
+            ),
+            sse_event("message_stop", r#"{"type":"message_stop"}"#),
+        ];
+
+        let gw = MockGateway::returning_sse(events);
+        let provider = AnthropicMessagesProvider::new(gw.clone(), "anthropic".into());
+
+        let request = llm_request("claude-sonnet-4-5")
+            .message(LlmMessage::user("Hello"))
+            .build_streaming();
+
+        let stream = provider
+            .stream(test_security_context(), request, CancellationToken::new())
+            .await
+            .unwrap();
+        let outcome = stream.into_outcome().await;
+
+        match outcome {
+            TerminalOutcome::Completed {
+                content,
+                usage,
+                response_id,
+                ..
+            } => {
+                assert_eq!(content, "Hello world");
+                assert_eq!(usage.input_tokens, 150);
+                assert_eq!(usage.output_tokens, 12);
+                assert_eq!(response_id, "msg_01abc");
+            }
+            _ => panic!("expected Completed, got {outcome:?}"),
+        }
+
+        assert_eq!(gw.last_request_uri().unwrap(), "/anthropic/v1/messages");
+        assert_eq!(
+            gw.last_request_header("anthropic-version").as_deref(),
+            Some(ANTHROPIC_VERSION)
+        );
+        let sent: serde_json::Value =
+            serde_json::from_str(&gw.last_request_body().unwrap()).unwrap();
+        assert_eq!(sent["stream"], true);
+    }
+
+    #[tokio::test]
+    async fn non_sse_error_response_is_sanitized() {
+        let gw = MockGateway::returning_status(
+            http::StatusCode::BAD_REQUEST,
+            serde_json::json!({
+                "type": "error",
+                "error": {
+                    "type": "invalid_request_error",
+                    "message": "bad key sk-ant-REDACTED"
+                }
+            }),
+        );
+        let provider = AnthropicMessagesProvider::new(gw, "anthropic".into());
+
+        let result = provider
+            .stream(
+                test_security_context(),
+                llm_request("claude-sonnet-4-5").build_streaming(),
+                CancellationToken::new(),
+            )
+            .await;
+
+        match result.unwrap_err() {
+            LlmProviderError::ProviderError { code, message, .. } => {
+                assert_eq!(code, "invalid_request_error");
+                assert!(!message.contains("sk-ant-api03"));
+                assert!(message.contains("[credential]"));
+            }
+            other => panic!("expected ProviderError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn oagw_rate_limit_error() {
+        let gw = MockGateway::returning_error(ServiceGatewayError::RateLimitExceeded {
+            detail: "too many requests".into(),
+            instance: "/test".into(),
+            retry_after_secs: Some(30),
+        });
+        let provider = AnthropicMessagesProvider::new(gw, "anthropic".into());
+
+        let result = provider
+            .stream(
+                test_security_context(),
+                llm_request("claude-sonnet-4-5").build_streaming(),
+                CancellationToken::new(),
+            )
+            .await;
+
+        assert!(matches!(
+            result.unwrap_err(),
+            LlmProviderError::RateLimited {
+                retry_after_secs: Some(30)
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn complete_response_success() {
+        let gw = MockGateway::returning_json(serde_json::json!({
+            "id": "msg_01complete",
+            "type": "message",
+            "role": "assistant",
+            "content": [
+                {"type": "thinking", "thinking": "hmm", "signature": "sig"},
+                {"type": "text", "text": "Summary of the conversation."}
+            ],
+            "stop_reason": "end_turn",
+            "usage": {"input_tokens": 500, "output_tokens": 50}
+        }));
+        let provider = AnthropicMessagesProvider::new(gw.clone(), "anthropic".into());
+
+        let request = llm_request("claude-sonnet-4-5")
+            .system_instructions("Summarize.")
+            .message(LlmMessage::user("conversation"))
+            .build_non_streaming();
+
+        let result = provider
+            .complete(test_security_context(), request)
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "Summary of the conversation.");
+        assert_eq!(result.usage.input_tokens, 500);
+        assert_eq!(result.usage.output_tokens, 50);
+        assert_eq!(result.response_id, "msg_01complete");
+        assert_eq!(
+            gw.last_request_header("accept").as_deref(),
+            Some("application/json")
+        );
+    }
+}
@@ -4,13 +4,18 @@
 //! [`LlmRequest`](super::LlmRequest) to the provider's wire format, proxying
 //! through OAGW, and translating SSE events back to `TranslatedEvent`.
 
+pub mod anthropic_messages;
 pub mod openai_chat;
 pub mod openai_responses;
+#[cfg(test)]
+pub(crate) mod test_support;
 
 use std::sync::Arc;
 
 use oagw_sdk::ServiceGatewayClientV1;
+use serde::{Deserialize, Serialize};
 
+pub use anthropic_messages::AnthropicMessagesProvider;
 pub use openai_chat::OpenAiChatProvider;
 pub use openai_responses::OpenAiResponsesProvider;
 
@@ -19,23 +24,45 @@ pub use openai_responses::OpenAiResponsesProvider;
 // ════════════════════════════════════════════════════════════════════════════
 
 /// Which provider adapter to use.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProviderKind {
     /// `OpenAI` Responses API (`/v1/responses`).
+    #[default]
+    #[serde(rename = "openai_responses")]
     OpenAiResponses,
     /// `OpenAI` Chat Completions API (`/v1/chat/completions`).
+    #[serde(rename = "openai_chat_completions")]
     OpenAiChatCompletions,
+    /// Anthropic Messages API (`/v1/messages`).
+    #[serde(rename = "anthropic_messages")]
+    AnthropicMessages,
 }
 
 /// Configuration for a provider adapter.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ProviderConfig {
     /// Which provider adapter to use.
+    #[serde(default)]
     pub kind: ProviderKind,
     /// OAGW upstream alias (e.g., "openai", "azure-openai", "anthropic").
+    #[serde(default = "default_upstream_alias")]
     pub upstream_alias: String,
 }
 
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        Self {
+            kind: ProviderKind::default(),
+            upstream_alias: default_upstream_alias(),
+        }
+    }
+}
+
+fn default_upstream_alias() -> String {
+    "openai".to_owned()
+}
+
 /// Create a provider adapter from configuration.
 #[must_use]
 pub fn create_provider(
@@ -49,5 +76,9 @@ pub fn create_provider(
         ProviderKind::OpenAiChatCompletions => {
             Arc::new(OpenAiChatProvider::new(gateway, config.upstream_alias))
         }
+        ProviderKind::AnthropicMessages => Arc::new(AnthropicMessagesProvider::new(
+            gateway,
+            config.upstream_alias,
+        )),
     }
 }
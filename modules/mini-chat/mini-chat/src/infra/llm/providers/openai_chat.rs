@@ -18,7 +18,7 @@ use tracing::debug;
 use crate::infra::llm::request::{ContentPart as MessageContentPart, LlmTool, Role};
 use crate::infra::llm::{
     ClientSseEvent, LlmProviderError, LlmRequest, NonStreaming, ProviderStream, RawDetail,
-    ResponseResult, Streaming, TerminalOutcome, ToolPhase, TranslatedEvent, Usage,
+    ResponseResult, Streaming, TerminalOutcome, ToolCall, ToolPhase, TranslatedEvent, Usage,
 };
 
 // ════════════════════════════════════════════════════════════════════════════
@@ -271,6 +271,18 @@ impl ChatCompletionsState {
                 response_id: self.response_id.clone(),
                 content: self.accumulated_text.clone(),
                 citations: vec![],
+                tool_calls: if finish_reason == "tool_calls" {
+                    self.tool_calls
+                        .iter()
+                        .map(|tc| ToolCall {
+                            call_id: tc.id.clone(),
+                            name: tc.name.clone(),
+                            arguments: tc.arguments.clone(),
+                        })
+                        .collect()
+                } else {
+                    vec![]
+                },
                 raw_response: serde_json::Value::Null,
             }),
         }
@@ -440,6 +452,54 @@ fn build_request_body<M>(request: &LlmRequest<M>, stream: bool) -> serde_json::V
             Role::System => "system",
         };
 
+        // Function call results become one `tool` message per call.
+        for part in &msg.content {
+            if let MessageContentPart::ToolResult {
+                call_id, content, ..
+            } = part
+            {
+                messages.push(serde_json::json!({
+                    "role": "tool",
+                    "tool_call_id": call_id,
+                    "content": content
+                }));
+            }
+        }
+
+        // Assistant function calls go into `tool_calls` next to the text.
+        let tool_calls: Vec<serde_json::Value> = msg
+            .content
+            .iter()
+            .filter_map(|part| match part {
+                MessageContentPart::ToolCall {
+                    call_id,
+                    name,
+                    arguments,
+                } => Some(serde_json::json!({
+                    "id": call_id,
+                    "type": "function",
+                    "function": { "name": name, "arguments": arguments }
+                })),
+                _ => None,
+            })
+            .collect();
+        if !tool_calls.is_empty() {
+            let text: String = msg
+                .content
+                .iter()
+                .filter_map(|part| match part {
+                    MessageContentPart::Text { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            messages.push(serde_json::json!({
+                "role": role,
+                "content": if text.is_empty() { serde_json::Value::Null } else { text.into() },
+                "tool_calls": tool_calls
+            }));
+            continue;
+        }
+
         // Simple text messages use string content
         if msg.content.len() == 1
             && let MessageContentPart::Text { text } = &msg.content[0]
@@ -454,17 +514,21 @@ fn build_request_body<M>(request: &LlmRequest<M>, stream: bool) -> serde_json::V
         let content: Vec<serde_json::Value> = msg
             .content
             .iter()
-            .map(|part| match part {
-                MessageContentPart::Text { text } => serde_json::json!({
+            .filter_map(|part| match part {
+                MessageContentPart::Text { text } => Some(serde_json::json!({
                     "type": "text",
                     "text": text
-                }),
-                MessageContentPart::Image { file_id } => serde_json::json!({
+                })),
+                MessageContentPart::Image { file_id } => Some(serde_json::json!({
                     "type": "image_url",
                     "image_url": { "url": file_id }
-                }),
+                })),
+                MessageContentPart::ToolCall { .. } | MessageContentPart::ToolResult { .. } => None,
             })
             .collect();
+        if content.is_empty() {
+            continue;
+        }
 
         messages.push(serde_json::json!({
             "role": role,
@@ -1163,6 +1227,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn translate_tool_calls_finish_reports_calls_in_terminal() {
+        let mut state = ChatCompletionsState::new();
+        state.tool_calls.push(AccumulatedToolCall {
+            id: "call_abc".into(),
+            name: "get_weather".into(),
+            arguments: r#"{"location":"SF"}"#.into(),
+        });
+
+        let done = ChatCompletionEvent::Done {
+            usage: ChatUsage {
+                prompt_tokens: 12,
+                completion_tokens: 7,
+            },
+            finish_reason: "tool_calls".into(),
+        };
+        let events = translate_chat_event(&done, &mut state);
+
+        match events.last() {
+            Some(TranslatedEvent::Terminal(TerminalOutcome::Completed { tool_calls, .. })) => {
+                assert_eq!(
+                    tool_calls,
+                    &vec![ToolCall {
+                        call_id: "call_abc".into(),
+                        name: "get_weather".into(),
+                        arguments: r#"{"location":"SF"}"#.into(),
+                    }]
+                );
+            }
+            other => panic!("expected Completed terminal, got {other:?}"),
+        }
+    }
+
     // ── Request serialization tests ───────────────────────────────────────
 
     #[test]
@@ -1234,6 +1331,42 @@ mod tests {
         assert_eq!(body["tools"][0]["function"]["description"], "Get weather");
     }
 
+    #[test]
+    fn request_tool_round_trip_mapped() {
+        let call = ToolCall {
+            call_id: "call_abc".into(),
+            name: "get_weather".into(),
+            arguments: r#"{"location":"SF"}"#.into(),
+        };
+        let request = llm_request("gpt-4o")
+            .message(LlmMessage::user("Weather in SF?"))
+            .message(LlmMessage::assistant_tool_calls("", &[call]))
+            .message(LlmMessage::tool_results(vec![
+                MessageContentPart::ToolResult {
+                    call_id: "call_abc".into(),
+                    content: "sunny".into(),
+                    is_error: false,
+                },
+            ]))
+            .build_streaming();
+
+        let body = build_request_body(&request, true);
+
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[1]["role"], "assistant");
+        assert!(messages[1]["content"].is_null());
+        assert_eq!(messages[1]["tool_calls"][0]["id"], "call_abc");
+        assert_eq!(messages[1]["tool_calls"][0]["type"], "function");
+        assert_eq!(
+            messages[1]["tool_calls"][0]["function"]["arguments"],
+            r#"{"location":"SF"}"#
+        );
+        assert_eq!(messages[2]["role"], "tool");
+        assert_eq!(messages[2]["tool_call_id"], "call_abc");
+        assert_eq!(messages[2]["content"], "sunny");
+    }
+
     #[test]
     fn request_file_search_dropped() {
         let request = llm_request("gpt-4o")
@@ -392,6 +392,8 @@ fn translate_provider_event(event: &ProviderEvent, accumulated_text: &str) -> Tr
                 response_id: response.id.clone(),
                 content: accumulated_text.to_owned(),
                 citations,
+                // Function tools are not sent to the Responses API.
+                tool_calls: vec![],
                 raw_response: raw,
             })
         }
@@ -494,15 +496,19 @@ fn build_request_body<M>(request: &LlmRequest<M>, stream: bool) -> serde_json::V
             let content: Vec<serde_json::Value> = msg
                 .content
                 .iter()
-                .map(|part| match part {
-                    MessageContentPart::Text { text } => serde_json::json!({
+                .filter_map(|part| match part {
+                    MessageContentPart::Text { text } => Some(serde_json::json!({
                         "type": "input_text",
                         "text": text
-                    }),
-                    MessageContentPart::Image { file_id } => serde_json::json!({
+                    })),
+                    MessageContentPart::Image { file_id } => Some(serde_json::json!({
                         "type": "input_image",
                         "file_id": file_id
-                    }),
+                    })),
+                    MessageContentPart::ToolCall { .. } | MessageContentPart::ToolResult { .. } => {
+                        debug!("Function call parts not supported by Responses API, dropping");
+                        None
+                    }
                 })
                 .collect();
             serde_json::json!({
@@ -748,209 +754,10 @@ mod tests {
     use crate::infra::llm::request::{Feature, RequestMetadata, RequestType};
     use crate::infra::llm::{LlmMessage, LlmProvider, LlmTool, llm_request};
 
-    use std::sync::Mutex;
-
     use futures::StreamExt;
     use oagw_sdk::error::ServiceGatewayError;
-    use oagw_sdk::models::*;
-
-    // ── MockGateway ───────────────────────────────────────────────────────
-
-    /// What the mock should return from `proxy_request`.
-    enum MockResponse {
-        /// Return an SSE stream from raw byte chunks.
-        Sse(Vec<String>),
-        /// Return a JSON body (non-SSE).
-        Json(serde_json::Value),
-        /// Return a `ServiceGatewayError`.
-        Error(ServiceGatewayError),
-    }
-
-    struct MockGateway {
-        response: Mutex<Option<MockResponse>>,
-        last_request: Mutex<Option<(String, String)>>, // (uri, body)
-    }
-
-    impl MockGateway {
-        fn returning_sse(events: Vec<String>) -> Arc<Self> {
-            Arc::new(MockGateway {
-                response: Mutex::new(Some(MockResponse::Sse(events))),
-                last_request: Mutex::new(None),
-            })
-        }
-
-        fn returning_json(json: serde_json::Value) -> Arc<Self> {
-            Arc::new(MockGateway {
-                response: Mutex::new(Some(MockResponse::Json(json))),
-                last_request: Mutex::new(None),
-            })
-        }
-
-        fn returning_error(err: ServiceGatewayError) -> Arc<Self> {
-            Arc::new(MockGateway {
-                response: Mutex::new(Some(MockResponse::Error(err))),
-                last_request: Mutex::new(None),
-            })
-        }
-
-        fn last_request_uri(&self) -> Option<String> {
-            self.last_request
-                .lock()
-                .unwrap()
-                .as_ref()
-                .map(|(u, _)| u.clone())
-        }
-
-        fn last_request_body(&self) -> Option<String> {
-            self.last_request
-                .lock()
-                .unwrap()
-                .as_ref()
-                .map(|(_, b)| b.clone())
-        }
-    }
-
-    #[async_trait::async_trait]
-    impl ServiceGatewayClientV1 for MockGateway {
-        async fn create_upstream(
-            &self,
-            _: SecurityContext,
-            _: CreateUpstreamRequest,
-        ) -> Result<Upstream, ServiceGatewayError> {
-            unimplemented!()
-        }
-        async fn get_upstream(
-            &self,
-            _: SecurityContext,
-            _: uuid::Uuid,
-        ) -> Result<Upstream, ServiceGatewayError> {
-            unimplemented!()
-        }
-        async fn list_upstreams(
-            &self,
-            _: SecurityContext,
-            _: &ListQuery,
-        ) -> Result<Vec<Upstream>, ServiceGatewayError> {
-            unimplemented!()
-        }
-        async fn update_upstream(
-            &self,
-            _: SecurityContext,
-            _: uuid::Uuid,
-            _: UpdateUpstreamRequest,
-        ) -> Result<Upstream, ServiceGatewayError> {
-            unimplemented!()
-        }
-        async fn delete_upstream(
-            &self,
-            _: SecurityContext,
-            _: uuid::Uuid,
-        ) -> Result<(), ServiceGatewayError> {
-            unimplemented!()
-        }
-        async fn create_route(
-            &self,
-            _: SecurityContext,
-            _: CreateRouteRequest,
-        ) -> Result<Route, ServiceGatewayError> {
-            unimplemented!()
-        }
-        async fn get_route(
-            &self,
-            _: SecurityContext,
-            _: uuid::Uuid,
-        ) -> Result<Route, ServiceGatewayError> {
-            unimplemented!()
-        }
-        async fn list_routes(
-            &self,
-            _: SecurityContext,
-            _: uuid::Uuid,
-            _: &ListQuery,
-        ) -> Result<Vec<Route>, ServiceGatewayError> {
-            unimplemented!()
-        }
-        async fn update_route(
-            &self,
-            _: SecurityContext,
-            _: uuid::Uuid,
-            _: UpdateRouteRequest,
-        ) -> Result<Route, ServiceGatewayError> {
-            unimplemented!()
-        }
-        async fn delete_route(
-            &self,
-            _: SecurityContext,
-            _: uuid::Uuid,
-        ) -> Result<(), ServiceGatewayError> {
-            unimplemented!()
-        }
-        async fn resolve_upstream(
-            &self,
-            _: SecurityContext,
-            _: &str,
-        ) -> Result<Upstream, ServiceGatewayError> {
-            unimplemented!()
-        }
-        async fn resolve_route(
-            &self,
-            _: SecurityContext,
-            _: uuid::Uuid,
-            _: &str,
-            _: &str,
-        ) -> Result<Route, ServiceGatewayError> {
-            unimplemented!()
-        }
 
-        async fn proxy_request(
-            &self,
-            _ctx: SecurityContext,
-            req: http::Request<Body>,
-        ) -> Result<http::Response<Body>, ServiceGatewayError> {
-            let uri = req.uri().to_string();
-            let (_parts, body) = req.into_parts();
-            let body_bytes = body.into_bytes().await.unwrap_or_default();
-            let body_str = String::from_utf8_lossy(&body_bytes).to_string();
-            *self.last_request.lock().unwrap() = Some((uri, body_str));
-
-            let mock_resp = self
-                .response
-                .lock()
-                .unwrap()
-                .take()
-                .expect("MockGateway response already consumed");
-
-            match mock_resp {
-                MockResponse::Sse(events) => {
-                    let mut sse_bytes = String::new();
-                    for event_str in &events {
-                        sse_bytes.push_str(event_str);
-                        sse_bytes.push_str("\n\n");
-                    }
-                    let body = Body::Stream(Box::pin(futures::stream::once(async move {
-                        Ok(Bytes::from(sse_bytes))
-                    })));
-
-                    let response = http::Response::builder()
-                        .status(200)
-                        .header("content-type", "text/event-stream")
-                        .body(body)
-                        .unwrap();
-                    Ok(response)
-                }
-                MockResponse::Json(json) => {
-                    let body = Body::Bytes(Bytes::from(serde_json::to_vec(&json).unwrap()));
-                    let response = http::Response::builder()
-                        .status(200)
-                        .header("content-type", "application/json")
-                        .body(body)
-                        .unwrap();
-                    Ok(response)
-                }
-                MockResponse::Error(err) => Err(err),
-            }
-        }
-    }
+    use crate::infra::llm::providers::test_support::MockGateway;
 
     fn test_security_context() -> SecurityContext {
         SecurityContext::anonymous()
@@ -0,0 +1,223 @@
+//! Test double for [`ServiceGatewayClientV1`] shared by the provider adapter
+//! tests: records the proxied request and replays a canned response.
+
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use oagw_sdk::error::ServiceGatewayError;
+use oagw_sdk::models::*;
+use oagw_sdk::{Body, SecurityContext, ServiceGatewayClientV1};
+
+/// What the mock should return from `proxy_request`.
+pub enum MockResponse {
+    /// Return an SSE stream from raw byte chunks.
+    Sse(Vec<String>),
+    /// Return a JSON body (non-SSE) with the given status.
+    Json(http::StatusCode, serde_json::Value),
+    /// Return a `ServiceGatewayError`.
+    Error(ServiceGatewayError),
+}
+
+pub struct MockGateway {
+    response: Mutex<Option<MockResponse>>,
+    last_request: Mutex<Option<(String, String)>>, // (uri, body)
+    last_headers: Mutex<http::HeaderMap>,
+}
+
+impl MockGateway {
+    pub fn returning_sse(events: Vec<String>) -> Arc<Self> {
+        Arc::new(MockGateway {
+            response: Mutex::new(Some(MockResponse::Sse(events))),
+            last_request: Mutex::new(None),
+            last_headers: Mutex::default(),
+        })
+    }
+
+    pub fn returning_json(json: serde_json::Value) -> Arc<Self> {
+        Self::returning_status(http::StatusCode::OK, json)
+    }
+
+    pub fn returning_status(status: http::StatusCode, json: serde_json::Value) -> Arc<Self> {
+        Arc::new(MockGateway {
+            response: Mutex::new(Some(MockResponse::Json(status, json))),
+            last_request: Mutex::new(None),
+            last_headers: Mutex::default(),
+        })
+    }
+
+    pub fn returning_error(err: ServiceGatewayError) -> Arc<Self> {
+        Arc::new(MockGateway {
+            response: Mutex::new(Some(MockResponse::Error(err))),
+            last_request: Mutex::new(None),
+            last_headers: Mutex::default(),
+        })
+    }
+
+    pub fn last_request_uri(&self) -> Option<String> {
+        self.last_request
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|(u, _)| u.clone())
+    }
+
+    pub fn last_request_body(&self) -> Option<String> {
+        self.last_request
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|(_, b)| b.clone())
+    }
+
+    pub fn last_request_header(&self, name: &str) -> Option<String> {
+        self.last_headers
+            .lock()
+            .unwrap()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_owned)
+    }
+}
+
+#[async_trait::async_trait]
+impl ServiceGatewayClientV1 for MockGateway {
+    async fn create_upstream(
+        &self,
+        _: SecurityContext,
+        _: CreateUpstreamRequest,
+    ) -> Result<Upstream, ServiceGatewayError> {
+        unimplemented!()
+    }
+    async fn get_upstream(
+        &self,
+        _: SecurityContext,
+        _: uuid::Uuid,
+    ) -> Result<Upstream, ServiceGatewayError> {
+        unimplemented!()
+    }
+    async fn list_upstreams(
+        &self,
+        _: SecurityContext,
+        _: &ListQuery,
+    ) -> Result<Vec<Upstream>, ServiceGatewayError> {
+        unimplemented!()
+    }
+    async fn update_upstream(
+        &self,
+        _: SecurityContext,
+        _: uuid::Uuid,
+        _: UpdateUpstreamRequest,
+    ) -> Result<Upstream, ServiceGatewayError> {
+        unimplemented!()
+    }
+    async fn delete_upstream(
+        &self,
+        _: SecurityContext,
+        _: uuid::Uuid,
+    ) -> Result<(), ServiceGatewayError> {
+        unimplemented!()
+    }
+    async fn create_route(
+        &self,
+        _: SecurityContext,
+        _: CreateRouteRequest,
+    ) -> Result<Route, ServiceGatewayError> {
+        unimplemented!()
+    }
+    async fn get_route(
+        &self,
+        _: SecurityContext,
+        _: uuid::Uuid,
+    ) -> Result<Route, ServiceGatewayError> {
+        unimplemented!()
+    }
+    async fn list_routes(
+        &self,
+        _: SecurityContext,
+        _: uuid::Uuid,
+        _: &ListQuery,
+    ) -> Result<Vec<Route>, ServiceGatewayError> {
+        unimplemented!()
+    }
+    async fn update_route(
+        &self,
+        _: SecurityContext,
+        _: uuid::Uuid,
+        _: UpdateRouteRequest,
+    ) -> Result<Route, ServiceGatewayError> {
+        unimplemented!()
+    }
+    async fn delete_route(
+        &self,
+        _: SecurityContext,
+        _: uuid::Uuid,
+    ) -> Result<(), ServiceGatewayError> {
+        unimplemented!()
+    }
+    async fn resolve_upstream(
+        &self,
+        _: SecurityContext,
+        _: &str,
+    ) -> Result<Upstream, ServiceGatewayError> {
+        unimplemented!()
+    }
+    async fn resolve_route(
+        &self,
+        _: SecurityContext,
+        _: uuid::Uuid,
+        _: &str,
+        _: &str,
+    ) -> Result<Route, ServiceGatewayError> {
+        unimplemented!()
+    }
+
+    async fn proxy_request(
+        &self,
+        _ctx: SecurityContext,
+        req: http::Request<Body>,
+    ) -> Result<http::Response<Body>, ServiceGatewayError> {
+        let uri = req.uri().to_string();
+        let (parts, body) = req.into_parts();
+        let body_bytes = body.into_bytes().await.unwrap_or_default();
+        let body_str = String::from_utf8_lossy(&body_bytes).to_string();
+        *self.last_request.lock().unwrap() = Some((uri, body_str));
+        *self.last_headers.lock().unwrap() = parts.headers;
+
+        let mock_resp = self
+            .response
+            .lock()
+            .unwrap()
+            .take()
+            .expect("MockGateway response already consumed");
+
+        match mock_resp {
+            MockResponse::Sse(events) => {
+                let mut sse_bytes = String::new();
+                for event_str in &events {
+                    sse_bytes.push_str(event_str);
+                    sse_bytes.push_str("\n\n");
+                }
+                let body = Body::Stream(Box::pin(futures::stream::once(async move {
+                    Ok(Bytes::from(sse_bytes))
+                })));
+
+                let response = http::Response::builder()
+                    .status(200)
+                    .header("content-type", "text/event-stream")
+                    .body(body)
+                    .unwrap();
+                Ok(response)
+            }
+            MockResponse::Json(status, json) => {
+                let body = Body::Bytes(Bytes::from(serde_json::to_vec(&json).unwrap()));
+                let response = http::Response::builder()
+                    .status(status)
+                    .header("content-type", "application/json")
+                    .body(body)
+                    .unwrap();
+                Ok(response)
+            }
+            MockResponse::Error(err) => Err(err),
+        }
+    }
+}
@@ -7,7 +7,7 @@ use std::marker::PhantomData;
 
 use serde::Serialize;
 
-use super::{NonStreaming, Streaming};
+use super::{NonStreaming, Streaming, ToolCall};
 
 // ════════════════════════════════════════════════════════════════════════════
 // Message types
@@ -30,6 +30,20 @@ pub enum ContentPart {
     Text { text: String },
     #[serde(rename = "image")]
     Image { file_id: String },
+    /// A function call made by the assistant in an earlier step.
+    #[serde(rename = "tool_call")]
+    ToolCall {
+        call_id: String,
+        name: String,
+        arguments: String,
+    },
+    /// The result of executing a function call, sent back to the model.
+    #[serde(rename = "tool_result")]
+    ToolResult {
+        call_id: String,
+        content: String,
+        is_error: bool,
+    },
 }
 
 /// A single message in the conversation.
@@ -71,6 +85,35 @@ impl LlmMessage {
             ],
         }
     }
+
+    /// Create an assistant message replaying the text and function calls of
+    /// a tool-calling step.
+    #[must_use]
+    pub fn assistant_tool_calls(text: impl Into<String>, calls: &[ToolCall]) -> Self {
+        let text = text.into();
+        let mut content = Vec::with_capacity(calls.len() + 1);
+        if !text.is_empty() {
+            content.push(ContentPart::Text { text });
+        }
+        content.extend(calls.iter().map(|call| ContentPart::ToolCall {
+            call_id: call.call_id.clone(),
+            name: call.name.clone(),
+            arguments: call.arguments.clone(),
+        }));
+        LlmMessage {
+            role: Role::Assistant,
+            content,
+        }
+    }
+
+    /// Create a user message carrying function call results.
+    #[must_use]
+    pub fn tool_results(results: Vec<ContentPart>) -> Self {
+        LlmMessage {
+            role: Role::User,
+            content: results,
+        }
+    }
 }
 
 // ════════════════════════════════════════════════════════════════════════════
@@ -1,3 +1,5 @@
 pub mod db;
+pub(crate) mod embedder;
 pub mod llm;
 pub(crate) mod model_policy;
+pub(crate) mod tool_executor;
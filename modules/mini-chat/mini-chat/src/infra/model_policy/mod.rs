@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use mini_chat_sdk::{MiniChatModelPolicyPluginClientV1, MiniChatModelPolicyPluginSpecV1};
+use mini_chat_sdk::{
+    MiniChatModelPolicyPluginClientV1, MiniChatModelPolicyPluginSpecV1, ModelCatalogEntry,
+    PolicySnapshot,
+};
 use modkit::client_hub::{ClientHub, ClientScope};
 use modkit::plugins::{GtsPluginSelector, choose_plugin_instance};
 use types_registry_sdk::{ListQuery, TypesRegistryClient};
@@ -46,6 +49,19 @@ impl ModelPolicyGateway {
             })
     }
 
+    /// Fetch the tenant's current policy snapshot from the plugin.
+    async fn policy_snapshot(&self, tenant_id: Uuid) -> Result<PolicySnapshot, DomainError> {
+        let plugin = self.get_policy_plugin().await?;
+        let version_info = plugin
+            .get_current_policy_version(tenant_id)
+            .await
+            .map_err(|e| DomainError::internal(e.to_string()))?;
+        plugin
+            .get_policy_snapshot(tenant_id, version_info.policy_version)
+            .await
+            .map_err(|e| DomainError::internal(e.to_string()))
+    }
+
     /// Resolve the policy plugin instance from types-registry.
     async fn resolve_policy_plugin(&self) -> Result<String, anyhow::Error> {
         let registry = self.hub.get::<dyn TypesRegistryClient>()?;
@@ -70,15 +86,7 @@ impl ModelPolicyGateway {
 #[async_trait]
 impl ModelResolver for ModelPolicyGateway {
     async fn resolve_model(&self, tenant_id: Uuid, model: &str) -> Result<String, DomainError> {
-        let plugin = self.get_policy_plugin().await?;
-        let version_info = plugin
-            .get_current_policy_version(tenant_id)
-            .await
-            .map_err(|e| DomainError::internal(e.to_string()))?;
-        let snapshot = plugin
-            .get_policy_snapshot(tenant_id, version_info.policy_version)
-            .await
-            .map_err(|e| DomainError::internal(e.to_string()))?;
+        let snapshot = self.policy_snapshot(tenant_id).await?;
 
         if model.is_empty() {
             // Find default model (prefer is_default + enabled, else first enabled)
@@ -106,4 +114,16 @@ impl ModelResolver for ModelPolicyGateway {
             }
         }
     }
+
+    async fn catalog_entry(
+        &self,
+        tenant_id: Uuid,
+        model: &str,
+    ) -> Result<Option<ModelCatalogEntry>, DomainError> {
+        let snapshot = self.policy_snapshot(tenant_id).await?;
+        Ok(snapshot
+            .model_catalog
+            .into_iter()
+            .find(|m| m.model_id == model))
+    }
 }
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mini_chat_sdk::{
+    MiniChatToolExecutorPluginClientV1, MiniChatToolExecutorPluginError,
+    MiniChatToolExecutorPluginSpecV1, ToolCallOutput, ToolCallRequest, ToolDefinition,
+};
+use modkit::client_hub::{ClientHub, ClientScope};
+use modkit::plugins::{GtsPluginSelector, choose_plugin_instance};
+use types_registry_sdk::{ListQuery, TypesRegistryClient};
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::repos::ToolExecutor;
+
+/// Executes function tools through the tool executor plugin discovered via GTS.
+pub struct ToolExecutorGateway {
+    hub: Arc<ClientHub>,
+    vendor: String,
+    executor_selector: GtsPluginSelector,
+}
+
+impl ToolExecutorGateway {
+    pub(crate) fn new(hub: Arc<ClientHub>, vendor: String) -> Self {
+        Self {
+            hub,
+            vendor,
+            executor_selector: GtsPluginSelector::new(),
+        }
+    }
+
+    /// Lazily resolve the tool executor plugin from `ClientHub`.
+    async fn get_executor_plugin(
+        &self,
+    ) -> Result<Arc<dyn MiniChatToolExecutorPluginClientV1>, DomainError> {
+        let instance_id = self
+            .executor_selector
+            .get_or_init(|| self.resolve_executor_plugin())
+            .await
+            .map_err(|e| DomainError::internal(e.to_string()))?;
+
+        let scope = ClientScope::gts_id(instance_id.as_ref());
+        self.hub
+            .try_get_scoped::<dyn MiniChatToolExecutorPluginClientV1>(&scope)
+            .ok_or_else(|| {
+                DomainError::internal(format!(
+                    "Tool executor plugin client not registered: {instance_id}"
+                ))
+            })
+    }
+
+    /// Resolve the tool executor plugin instance from types-registry.
+    async fn resolve_executor_plugin(&self) -> Result<String, anyhow::Error> {
+        let registry = self.hub.get::<dyn TypesRegistryClient>()?;
+        let plugin_type_id = MiniChatToolExecutorPluginSpecV1::gts_schema_id().clone();
+        let instances = registry
+            .list(
+                ListQuery::new()
+                    .with_pattern(format!("{plugin_type_id}*"))
+                    .with_is_type(false),
+            )
+            .await?;
+
+        let gts_id = choose_plugin_instance::<MiniChatToolExecutorPluginSpecV1>(
+            &self.vendor,
+            instances.iter().map(|e| (e.gts_id.as_str(), &e.content)),
+        )?;
+
+        Ok(gts_id)
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for ToolExecutorGateway {
+    async fn list_tools(&self, tenant_id: Uuid) -> Result<Vec<ToolDefinition>, DomainError> {
+        let plugin = self.get_executor_plugin().await?;
+        plugin
+            .list_tools(tenant_id)
+            .await
+            .map_err(|e| DomainError::internal(e.to_string()))
+    }
+
+    async fn execute(&self, call: ToolCallRequest) -> Result<ToolCallOutput, DomainError> {
+        let plugin = self.get_executor_plugin().await?;
+        match plugin.execute(call).await {
+            Ok(output) => Ok(output),
+            // The model picked a bad tool or arguments: let it correct itself.
+            Err(
+                e @ (MiniChatToolExecutorPluginError::UnknownTool(_)
+                | MiniChatToolExecutorPluginError::InvalidArguments(_)),
+            ) => Ok(ToolCallOutput {
+                content: e.to_string(),
+                is_error: true,
+            }),
+            Err(e) => Err(DomainError::internal(e.to_string())),
+        }
+    }
+}
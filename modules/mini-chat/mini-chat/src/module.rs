@@ -1,8 +1,13 @@
+use std::path::PathBuf;
 use std::sync::{Arc, OnceLock};
 
 use async_trait::async_trait;
 use authz_resolver_sdk::AuthZResolverClient;
-use mini_chat_sdk::MiniChatModelPolicyPluginSpecV1;
+use file_parser::domain::service::{FileParserService, ServiceConfig};
+use file_parser::infra::parsers::{
+    DocxParser, HtmlParser, PdfParser, PlainTextParser, PptxParser, XlsxParser,
+};
+use mini_chat_sdk::{MiniChatModelPolicyPluginSpecV1, MiniChatToolExecutorPluginSpecV1};
 use modkit::api::OpenApiRegistry;
 use modkit::{DatabaseCapability, Module, ModuleCtx, RestApiCapability};
 use oagw_sdk::ServiceGatewayClientV1;
@@ -11,10 +16,18 @@ use tracing::info;
 use types_registry_sdk::{RegisterResult, TypesRegistryClient};
 
 use crate::api::rest::routes;
+use crate::domain::repos::ToolExecutor;
 use crate::domain::service::{AppServices as GenericAppServices, Repositories};
 
-pub(crate) type AppServices =
-    GenericAppServices<TurnRepository, MessageRepository, QuotaUsageRepository, ChatRepository>;
+pub(crate) type AppServices = GenericAppServices<
+    TurnRepository,
+    MessageRepository,
+    QuotaUsageRepository,
+    ChatRepository,
+    AttachmentRepository,
+    VectorStoreRepository,
+    ThreadSummaryRepository,
+>;
 use crate::infra::db::repo::attachment_repo::AttachmentRepository;
 use crate::infra::db::repo::chat_repo::ChatRepository;
 use crate::infra::db::repo::message_repo::MessageRepository;
@@ -24,8 +37,10 @@ use crate::infra::db::repo::reaction_repo::ReactionRepository;
 use crate::infra::db::repo::thread_summary_repo::ThreadSummaryRepository;
 use crate::infra::db::repo::turn_repo::TurnRepository;
 use crate::infra::db::repo::vector_store_repo::VectorStoreRepository;
-use crate::infra::llm::providers::{ProviderConfig, ProviderKind, create_provider};
+use crate::infra::embedder::create_embedder;
+use crate::infra::llm::providers::create_provider;
 use crate::infra::model_policy::ModelPolicyGateway;
+use crate::infra::tool_executor::ToolExecutorGateway;
 
 /// Default URL prefix for all mini-chat REST routes.
 pub const DEFAULT_URL_PREFIX: &str = "/mini-chat";
@@ -59,6 +74,15 @@ impl Module for MiniChatModule {
         cfg.streaming
             .validate()
             .map_err(|e| anyhow::anyhow!("streaming config: {e}"))?;
+        cfg.tools
+            .validate()
+            .map_err(|e| anyhow::anyhow!("tools config: {e}"))?;
+        cfg.attachments
+            .validate()
+            .map_err(|e| anyhow::anyhow!("attachments config: {e}"))?;
+        cfg.context
+            .validate()
+            .map_err(|e| anyhow::anyhow!("context config: {e}"))?;
 
         let vendor = cfg.vendor.trim().to_owned();
         if vendor.is_empty() {
@@ -68,21 +92,28 @@ impl Module for MiniChatModule {
             ));
         }
 
-        // Register model-policy plugin schema in types-registry
+        // Register model-policy and tool executor plugin schemas in types-registry
         let registry = ctx.client_hub().get::<dyn TypesRegistryClient>()?;
-        let schema_str = MiniChatModelPolicyPluginSpecV1::gts_schema_with_refs_as_string();
-        let mut schema_json: serde_json::Value = serde_json::from_str(&schema_str)?;
-        if let Some(obj) = schema_json.as_object_mut() {
-            obj.insert(
-                "additionalProperties".to_owned(),
-                serde_json::Value::Bool(false),
-            );
+        let mut schemas = Vec::with_capacity(2);
+        for schema_str in [
+            MiniChatModelPolicyPluginSpecV1::gts_schema_with_refs_as_string(),
+            MiniChatToolExecutorPluginSpecV1::gts_schema_with_refs_as_string(),
+        ] {
+            let mut schema_json: serde_json::Value = serde_json::from_str(&schema_str)?;
+            if let Some(obj) = schema_json.as_object_mut() {
+                obj.insert(
+                    "additionalProperties".to_owned(),
+                    serde_json::Value::Bool(false),
+                );
+            }
+            schemas.push(schema_json);
         }
-        let results = registry.register(vec![schema_json]).await?;
+        let results = registry.register(schemas).await?;
         RegisterResult::ensure_all_ok(&results)?;
         info!(
-            schema_id = %MiniChatModelPolicyPluginSpecV1::gts_schema_id(),
-            "Registered model-policy plugin schema in types-registry"
+            model_policy_schema_id = %MiniChatModelPolicyPluginSpecV1::gts_schema_id(),
+            tool_executor_schema_id = %MiniChatToolExecutorPluginSpecV1::gts_schema_id(),
+            "Registered mini-chat plugin schemas in types-registry"
         );
 
         self.url_prefix
@@ -101,15 +132,12 @@ impl Module for MiniChatModule {
             .get::<dyn ServiceGatewayClientV1>()
             .map_err(|e| anyhow::anyhow!("failed to get OAGW gateway: {e}"))?;
 
-        // TODO: provider kind and upstream alias should come from config in a
-        // follow-up — hardcoded to OpenAI Responses for initial P1 wiring.
-        let llm = create_provider(
-            gateway,
-            ProviderConfig {
-                kind: ProviderKind::OpenAiResponses,
-                upstream_alias: "openai".to_owned(),
-            },
+        info!(
+            provider = ?cfg.provider.kind,
+            upstream = %cfg.provider.upstream_alias,
+            "Using LLM provider"
         );
+        let llm = create_provider(Arc::clone(&gateway), cfg.provider);
 
         let repos = Repositories {
             chat: Arc::new(ChatRepository::new(modkit_db::odata::LimitCfg {
@@ -126,6 +154,43 @@ impl Module for MiniChatModule {
             vector_store: Arc::new(VectorStoreRepository),
         };
 
+        let tool_executor: Option<Arc<dyn ToolExecutor>> = if cfg.tools.enabled {
+            info!(
+                max_steps = cfg.tools.max_steps,
+                call_timeout_seconds = cfg.tools.call_timeout_seconds,
+                "Server-side tool calling enabled"
+            );
+            Some(Arc::new(ToolExecutorGateway::new(
+                ctx.client_hub(),
+                vendor.clone(),
+            )))
+        } else {
+            None
+        };
+
+        // Documents are parsed in-process; images have no local parser and
+        // are rejected at upload.
+        let parser = Arc::new(FileParserService::new(
+            vec![
+                Arc::new(PlainTextParser::new()),
+                Arc::new(HtmlParser::new()),
+                Arc::new(PdfParser::new()),
+                Arc::new(DocxParser::new()),
+                Arc::new(XlsxParser::new()),
+                Arc::new(PptxParser::new()),
+            ],
+            ServiceConfig {
+                max_file_size_bytes: usize::try_from(cfg.attachments.max_file_size_bytes())?,
+                allowed_local_base_dir: PathBuf::new(),
+            },
+        ));
+        info!(
+            embedder = ?cfg.embedder.kind,
+            dimensions = cfg.attachments.embedding_dimensions,
+            "Using document embedder"
+        );
+        let embedder = create_embedder(gateway, cfg.embedder, cfg.attachments.embedding_dimensions);
+
         let model_policy_gw = Arc::new(ModelPolicyGateway::new(ctx.client_hub(), vendor));
         let services = Arc::new(AppServices::new(
             &repos,
@@ -134,6 +199,12 @@ impl Module for MiniChatModule {
             model_policy_gw,
             llm,
             cfg.streaming,
+            tool_executor,
+            cfg.tools,
+            parser,
+            embedder,
+            cfg.attachments,
+            cfg.context,
         ));
 
         self.service
@@ -27,6 +27,8 @@ impl Default for StaticMiniChatPolicyPluginConfig {
                     tier: ModelTier::Premium,
                     global_enabled: true,
                     is_default: true,
+                    context_window_tokens: 400_000,
+                    max_output_tokens: 128_000,
                 },
                 ModelCatalogEntry {
                     model_id: "gpt-5-mini".to_owned(),
@@ -34,6 +36,8 @@ impl Default for StaticMiniChatPolicyPluginConfig {
                     tier: ModelTier::Standard,
                     global_enabled: true,
                     is_default: false,
+                    context_window_tokens: 400_000,
+                    max_output_tokens: 128_000,
                 },
             ],
         }
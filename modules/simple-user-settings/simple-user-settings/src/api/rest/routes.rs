@@ -4,7 +4,7 @@ use crate::infra::storage::sea_orm_repo::SeaOrmSettingsRepository;
 use axum::http::StatusCode;
 use axum::{Extension, Router};
 use modkit::api::operation_builder::LicenseFeature;
-use modkit::api::{OpenApiRegistry, OperationBuilder};
+use modkit::api::{OpenApiRegistry, OperationAuditor, OperationBuilder};
 use std::sync::Arc;
 
 /// Type alias for the concrete service type.
@@ -24,6 +24,7 @@ pub fn register_routes(
     mut router: Router,
     openapi: &dyn OpenApiRegistry,
     service: Arc<ConcreteService>,
+    auditor: Option<Arc<dyn OperationAuditor>>,
 ) -> Router {
     router = OperationBuilder::get("/simple-user-settings/v1/settings")
         .operation_id("simple_user_settings.get_settings")
@@ -52,6 +53,7 @@ pub fn register_routes(
         .require_license_features::<License>([])
         .json_request::<dto::UpdateSimpleUserSettingsRequest>(openapi, "Settings update data")
         .handler(handlers::update_settings)
+        .audited(auditor.clone())
         .json_response_with_schema::<dto::SimpleUserSettingsDto>(
             openapi,
             StatusCode::OK,
@@ -73,6 +75,7 @@ pub fn register_routes(
         .require_license_features::<License>([])
         .json_request::<dto::PatchSimpleUserSettingsRequest>(openapi, "Settings patch data")
         .handler(handlers::patch_settings)
+        .audited(auditor)
         .json_response_with_schema::<dto::SimpleUserSettingsDto>(
             openapi,
             StatusCode::OK,
@@ -82,7 +82,7 @@ impl Module for SettingsModule {
 impl modkit::contracts::RestApiCapability for SettingsModule {
     fn register_rest(
         &self,
-        _ctx: &ModuleCtx,
+        ctx: &ModuleCtx,
         router: Router,
         openapi: &dyn OpenApiRegistry,
     ) -> anyhow::Result<Router> {
@@ -93,7 +93,9 @@ impl modkit::contracts::RestApiCapability for SettingsModule {
             .ok_or_else(|| anyhow::anyhow!("Service not initialized"))?
             .clone();
 
-        let router = routes::register_routes(router, openapi, service);
+        // Settings changes are audited when the audit module is deployed.
+        let auditor = audit_sdk::operation_auditor(&ctx.client_hub());
+        let router = routes::register_routes(router, openapi, service, auditor);
         info!("Settings module: REST routes registered successfully");
         Ok(router)
     }
@@ -1,4 +1,4 @@
-use axum::extract::Request;
+use axum::extract::{Request, State};
 use axum::http::StatusCode;
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
@@ -6,9 +6,9 @@ use dashmap::DashMap;
 use http::Method;
 use std::sync::Arc;
 
+use license_resolver_sdk::{BASE_FEATURE, LicenseResolverClient, LicenseResolverError};
 use modkit::api::{OperationSpec, Problem};
-
-const BASE_FEATURE: &str = "gts.x.core.lic.feat.v1~x.core.global.base.v1";
+use modkit_security::SecurityContext;
 
 type LicenseKey = (Method, String);
 
@@ -43,8 +43,21 @@ impl LicenseRequirementMap {
     }
 }
 
+#[derive(Clone)]
+pub struct LicenseState {
+    pub requirements: LicenseRequirementMap,
+    /// License Resolver client; `None` when the `license_resolver` module is
+    /// not loaded, in which case only the base feature can be required.
+    pub resolver: Option<Arc<dyn LicenseResolverClient>>,
+}
+
+/// License validation middleware.
+///
+/// Runs after authentication: the features required by the route must all
+/// be licensed to the caller's tenant, as reported by the License Resolver
+/// (which caches them per tenant).
 pub async fn license_validation_middleware(
-    map: LicenseRequirementMap,
+    State(state): State<LicenseState>,
     req: Request,
     next: Next,
 ) -> Response {
@@ -54,23 +67,67 @@ pub async fn license_validation_middleware(
         .get::<axum::extract::MatchedPath>()
         .map_or_else(|| req.uri().path().to_owned(), |p| p.as_str().to_owned());
 
-    let Some(required) = map.get(&method, &path) else {
+    let Some(required) = state.requirements.get(&method, &path) else {
+        return next.run(req).await;
+    };
+
+    let Some(resolver) = &state.resolver else {
+        if required.iter().any(|r| r != BASE_FEATURE) {
+            return Problem::new(
+                StatusCode::FORBIDDEN,
+                "Forbidden",
+                format!(
+                    "Endpoint requires unsupported license features '{required:?}'; only '{BASE_FEATURE}' is allowed without a license resolver",
+                ),
+            )
+            .into_response();
+        }
         return next.run(req).await;
     };
 
-    // TODO: this is a stub implementation
-    // We need first to implement plugin and get its client from client_hub
-    // Plugin should provide an interface to get a list of global features (features that are not scoped to particular resource)
-    if required.iter().any(|r| r != BASE_FEATURE) {
+    let Some(ctx) = req.extensions().get::<SecurityContext>().cloned() else {
         return Problem::new(
             StatusCode::FORBIDDEN,
             "Forbidden",
-            format!(
-                "Endpoint requires unsupported license features '{required:?}'; only '{BASE_FEATURE}' is allowed",
-            ),
+            "Licensed endpoint called without a security context",
         )
         .into_response();
+    };
+
+    let tenant_id = ctx.subject_tenant_id();
+    match resolver.get_global_features(&ctx, tenant_id).await {
+        Ok(features) => {
+            let missing = features.missing(&required);
+            if missing.is_empty() {
+                next.run(req).await
+            } else {
+                tracing::debug!(%tenant_id, ?missing, "license check failed");
+                Problem::new(
+                    StatusCode::FORBIDDEN,
+                    "Forbidden",
+                    format!("Tenant license does not include features '{missing:?}'"),
+                )
+                .into_response()
+            }
+        }
+        Err(err) => license_error_to_response(&err),
     }
+}
 
-    next.run(req).await
+/// Convert `LicenseResolverError` to an RFC-9457 Problem Details response.
+fn license_error_to_response(err: &LicenseResolverError) -> Response {
+    tracing::error!(error = %err, "license resolution failed");
+    let (status, title, detail) = match err {
+        LicenseResolverError::NoPluginAvailable | LicenseResolverError::ServiceUnavailable(_) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Service Unavailable",
+            "License service unavailable",
+        ),
+        LicenseResolverError::Internal(_) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Internal Server Error",
+            "Internal license error",
+        ),
+    };
+    Problem::new(status, title, detail).into_response()
 }
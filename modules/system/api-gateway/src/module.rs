@@ -26,6 +26,7 @@ use tower_http::{
 use tracing::debug;
 
 use authn_resolver_sdk::AuthNResolverClient;
+use license_resolver_sdk::LicenseResolverClient;
 
 use crate::config::ApiGatewayConfig;
 use crate::middleware::auth;
@@ -55,6 +56,8 @@ pub struct ApiGateway {
     pub(crate) final_router: Mutex<Option<axum::Router>>,
     // AuthN Resolver client (resolved during init, None when auth_disabled)
     pub(crate) authn_client: Mutex<Option<Arc<dyn AuthNResolverClient>>>,
+    // License Resolver client (resolved during init, None when the module is not loaded)
+    pub(crate) license_client: Mutex<Option<Arc<dyn LicenseResolverClient>>>,
 
     // Duplicate detection (per (method, path) and per handler id)
     pub(crate) registered_routes: DashMap<(Method, String), ()>,
@@ -70,6 +73,7 @@ impl Default for ApiGateway {
             router_cache: RouterCache::new(default_router),
             final_router: Mutex::new(None),
             authn_client: Mutex::new(None),
+            license_client: Mutex::new(None),
             registered_routes: DashMap::new(),
             registered_handlers: DashMap::new(),
         }
@@ -87,6 +91,7 @@ impl ApiGateway {
             router_cache: RouterCache::new(default_router),
             final_router: Mutex::new(None),
             authn_client: Mutex::new(None),
+            license_client: Mutex::new(None),
             registered_routes: DashMap::new(),
             registered_handlers: DashMap::new(),
         }
@@ -188,12 +193,13 @@ impl ApiGateway {
             .collect();
 
         // 11) License validation
-        let license_map = middleware::license_validation::LicenseRequirementMap::from_specs(&specs);
-        router = router.layer(from_fn(
-            move |req: axum::extract::Request, next: axum::middleware::Next| {
-                let map = license_map.clone();
-                middleware::license_validation::license_validation_middleware(map, req, next)
-            },
+        let license_state = middleware::license_validation::LicenseState {
+            requirements: middleware::license_validation::LicenseRequirementMap::from_specs(&specs),
+            resolver: self.license_client.lock().clone(),
+        };
+        router = router.layer(from_fn_with_state(
+            license_state,
+            middleware::license_validation::license_validation_middleware,
         ));
 
         // 10) Auth
@@ -555,6 +561,17 @@ impl modkit::Module for ApiGateway {
             tracing::info!("AuthN Resolver client resolved from ClientHub");
         }
 
+        // License Resolver is optional: without it only the base feature can be required
+        let license_client = ctx.client_hub().get::<dyn LicenseResolverClient>().ok();
+        if license_client.is_some() {
+            tracing::info!("License Resolver client resolved from ClientHub");
+        } else {
+            tracing::warn!(
+                "License Resolver client not available; endpoints requiring features other than the base feature will be rejected"
+            );
+        }
+        *self.license_client.lock() = license_client;
+
         Ok(())
     }
 }
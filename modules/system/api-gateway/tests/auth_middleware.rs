@@ -596,6 +596,7 @@ fn mock_accepting_token(
                         .subject_tenant_id(tenant_id)
                         .build()
                         .unwrap(),
+                    expires_at: None,
                 })
             } else {
                 Err(AuthNResolverError::Unauthorized("invalid token".to_owned()))
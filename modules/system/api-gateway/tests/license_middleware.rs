@@ -8,6 +8,9 @@ use axum::{
     http::{Request, StatusCode},
     response::IntoResponse,
 };
+use license_resolver_sdk::{
+    BASE_FEATURE, GlobalFeatures, LicenseResolverClient, LicenseResolverError,
+};
 use modkit::{
     ClientHub, Module,
     api::OperationBuilder,
@@ -16,6 +19,7 @@ use modkit::{
     context::ModuleCtx,
     contracts::{ApiGatewayCapability, OpenApiRegistry, RestApiCapability},
 };
+use modkit_security::SecurityContext;
 use serde_json::json;
 use std::sync::Arc;
 use tower::ServiceExt;
@@ -32,8 +36,10 @@ impl ConfigProvider for TestConfigProvider {
 }
 
 fn create_api_gateway_ctx(config: serde_json::Value) -> ModuleCtx {
-    let hub = Arc::new(ClientHub::new());
+    create_api_gateway_ctx_with_hub(config, Arc::new(ClientHub::new()))
+}
 
+fn create_api_gateway_ctx_with_hub(config: serde_json::Value, hub: Arc<ClientHub>) -> ModuleCtx {
     ModuleCtx::new(
         "api-gateway",
         Uuid::new_v4(),
@@ -55,6 +61,25 @@ fn create_test_module_ctx() -> ModuleCtx {
     )
 }
 
+/// License Resolver granting a fixed set of features to every tenant.
+struct FixedLicenses(Option<Vec<&'static str>>);
+
+#[async_trait]
+impl LicenseResolverClient for FixedLicenses {
+    async fn get_global_features(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<GlobalFeatures, LicenseResolverError> {
+        let Some(features) = &self.0 else {
+            return Err(LicenseResolverError::NoPluginAvailable);
+        };
+        let mut granted = GlobalFeatures::none(tenant_id);
+        granted.features = features.iter().map(|f| (*f).to_owned()).collect();
+        Ok(granted)
+    }
+}
+
 async fn ok_handler() -> impl IntoResponse {
     StatusCode::OK
 }
@@ -252,3 +277,81 @@ async fn allows_no_license_requirement() {
 
     assert_eq!(response.status(), StatusCode::OK);
 }
+
+async fn licensed_router(licenses: FixedLicenses) -> Router {
+    let config = json!({
+        "api-gateway": {
+            "config": {
+                "bind_addr": "0.0.0.0:8080",
+                "enable_docs": false,
+                "cors_enabled": false,
+                "auth_disabled": true
+            }
+        }
+    });
+
+    let hub = Arc::new(ClientHub::new());
+    hub.register::<dyn LicenseResolverClient>(Arc::new(licenses));
+    let api_ctx = create_api_gateway_ctx_with_hub(config, hub);
+    let test_ctx = create_test_module_ctx();
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&api_ctx).await.expect("Failed to init");
+
+    let router = TestLicenseModule
+        .register_rest(&test_ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+
+    api_gateway
+        .rest_finalize(&api_ctx, router)
+        .expect("Failed to finalize")
+}
+
+async fn status(router: &Router, uri: &str) -> StatusCode {
+    router
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .expect("Request failed")
+        .status()
+}
+
+#[tokio::test]
+async fn checks_required_features_against_license_resolver() {
+    let router = licensed_router(FixedLicenses(Some(vec![BASE_FEATURE]))).await;
+    assert_eq!(
+        status(&router, "/tests/v1/license/good").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        status(&router, "/tests/v1/license/bad").await,
+        StatusCode::FORBIDDEN
+    );
+
+    let router = licensed_router(FixedLicenses(Some(vec!["some_other_feature"]))).await;
+    assert_eq!(
+        status(&router, "/tests/v1/license/bad").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        status(&router, "/tests/v1/license/good").await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        status(&router, "/tests/v1/license/none").await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn license_resolver_errors_are_service_unavailable() {
+    let router = licensed_router(FixedLicenses(None)).await;
+    assert_eq!(
+        status(&router, "/tests/v1/license/good").await,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+    assert_eq!(
+        status(&router, "/tests/v1/license/none").await,
+        StatusCode::OK
+    );
+}
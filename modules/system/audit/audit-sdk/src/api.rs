@@ -0,0 +1,37 @@
+//! Public API trait of the audit module.
+
+use async_trait::async_trait;
+use modkit_odata::{ODataQuery, Page};
+use modkit_security::SecurityContext;
+
+use crate::errors::AuditError;
+use crate::models::{AuditEvent, AuditRecord, ChainVerification};
+
+/// Audit log API (v1).
+///
+/// Obtain from `ClientHub`:
+/// ```ignore
+/// let audit = hub.get::<dyn AuditClientV1>()?;
+/// audit.record(&ctx, AuditRecord::new("settings.update", "settings")).await?;
+/// ```
+#[async_trait]
+pub trait AuditClientV1: Send + Sync {
+    /// Append an event to the log of the caller's tenant, with the caller as
+    /// actor.
+    async fn record(
+        &self,
+        ctx: &SecurityContext,
+        record: AuditRecord,
+    ) -> Result<AuditEvent, AuditError>;
+
+    /// Events visible to the caller, newest first unless `$orderby` says
+    /// otherwise.
+    async fn list(
+        &self,
+        ctx: &SecurityContext,
+        query: &ODataQuery,
+    ) -> Result<Page<AuditEvent>, AuditError>;
+
+    /// Re-compute the hash chain of the caller's tenant.
+    async fn verify_chain(&self, ctx: &SecurityContext) -> Result<ChainVerification, AuditError>;
+}
@@ -0,0 +1,38 @@
+//! Error types for the audit SDK.
+
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone)]
+pub enum AuditError {
+    #[error("Validation error on field '{field}': {message}")]
+    Validation { field: String, message: String },
+
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+impl AuditError {
+    #[must_use]
+    pub fn validation(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Validation {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::Unauthorized(message.into())
+    }
+
+    #[must_use]
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::Internal(message.into())
+    }
+}
@@ -0,0 +1,43 @@
+//! Audit SDK
+//!
+//! This crate provides the public API of the audit module:
+//! - `AuditClientV1` trait for recording and querying audit events
+//! - Model types (`AuditRecord`, `AuditEvent`, `AuditOutcome`, `ChainVerification`)
+//! - Error type (`AuditError`)
+//! - `OData` filter fields of audit events
+//! - `AuditOperationRecorder`, the `OperationAuditor` behind
+//!   `OperationBuilder::audited`
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! use audit_sdk::{AuditClientV1, AuditRecord, operation_auditor};
+//!
+//! let audit = hub.get::<dyn AuditClientV1>()?;
+//! audit
+//!     .record(&ctx, AuditRecord::new("settings.update", "settings").with_resource_id(id))
+//!     .await?;
+//!
+//! // In `register_rest`: audit the module's mutating operations.
+//! let auditor = operation_auditor(&ctx.client_hub());
+//! router = OperationBuilder::patch("/my-module/v1/things/{id}")
+//!     // ...
+//!     .handler(handlers::update_thing)
+//!     .audited(auditor.clone())
+//!     // ...
+//!     .register(router, openapi);
+//! ```
+
+#![forbid(unsafe_code)]
+
+pub mod api;
+pub mod errors;
+pub mod models;
+pub mod odata;
+pub mod operation;
+
+pub use api::AuditClientV1;
+pub use errors::AuditError;
+pub use models::{AuditEvent, AuditOutcome, AuditRecord, ChainVerification};
+pub use odata::AuditEventFilterField;
+pub use operation::{AuditOperationRecorder, operation_auditor, operation_record};
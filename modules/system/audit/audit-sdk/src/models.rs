@@ -0,0 +1,153 @@
+//! Audit event types.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// How the audited action ended.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    #[default]
+    Success,
+    Failure,
+}
+
+impl AuditOutcome {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+        }
+    }
+}
+
+impl fmt::Display for AuditOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AuditOutcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "success" => Ok(Self::Success),
+            "failure" => Ok(Self::Failure),
+            other => Err(format!("unknown audit outcome '{other}'")),
+        }
+    }
+}
+
+/// Action to record.
+///
+/// The actor and tenant come from the `SecurityContext` passed to
+/// `AuditClientV1::record`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditRecord {
+    /// What happened, e.g. `oagw.update_upstream`.
+    pub action: String,
+    /// Kind of resource acted on, e.g. `oagw.upstream`.
+    pub resource_type: String,
+    pub resource_id: Option<String>,
+    pub outcome: AuditOutcome,
+    /// Free-form JSON context; `null` when there is none.
+    pub details: serde_json::Value,
+}
+
+impl AuditRecord {
+    #[must_use]
+    pub fn new(action: impl Into<String>, resource_type: impl Into<String>) -> Self {
+        Self {
+            action: action.into(),
+            resource_type: resource_type.into(),
+            resource_id: None,
+            outcome: AuditOutcome::Success,
+            details: serde_json::Value::Null,
+        }
+    }
+
+    #[must_use]
+    pub fn with_resource_id(mut self, id: impl Into<String>) -> Self {
+        self.resource_id = Some(id.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_outcome(mut self, outcome: AuditOutcome) -> Self {
+        self.outcome = outcome;
+        self
+    }
+
+    #[must_use]
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+/// Stored audit event.
+///
+/// Events of a tenant form a hash chain: `hash` covers the event's fields
+/// and `prev_hash`, the hash of the event before it (`seq - 1`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    /// Position in the tenant's chain; starts at 1 and has no gaps.
+    pub seq: u64,
+    pub actor_id: Uuid,
+    pub actor_type: Option<String>,
+    pub action: String,
+    pub resource_type: String,
+    pub resource_id: Option<String>,
+    pub outcome: AuditOutcome,
+    pub details: serde_json::Value,
+    #[serde(with = "time::serde::rfc3339")]
+    pub occurred_at: OffsetDateTime,
+    /// Hex SHA-256 of the previous event; all zeros for the first event.
+    pub prev_hash: String,
+    /// Hex SHA-256 of this event.
+    pub hash: String,
+}
+
+/// Result of re-computing a tenant's hash chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainVerification {
+    pub events_checked: u64,
+    /// Sequence number of the last event the chain should contain.
+    pub head_seq: u64,
+    /// First sequence number whose event is missing, altered or out of
+    /// chain; `None` if the chain is intact.
+    pub broken_at: Option<u64>,
+}
+
+impl ChainVerification {
+    #[must_use]
+    pub fn is_intact(&self) -> bool {
+        self.broken_at.is_none()
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcome_round_trips_through_its_string_form() {
+        for outcome in [AuditOutcome::Success, AuditOutcome::Failure] {
+            assert_eq!(outcome.as_str().parse::<AuditOutcome>(), Ok(outcome));
+            assert_eq!(
+                serde_json::to_value(outcome).unwrap(),
+                serde_json::json!(outcome.as_str())
+            );
+        }
+        assert!("ok".parse::<AuditOutcome>().is_err());
+    }
+}
@@ -0,0 +1,36 @@
+//! `OData` filter fields of audit events.
+
+use modkit_odata_macros::ODataFilterable;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Audit event fields usable in `$filter` and `$orderby`.
+#[derive(ODataFilterable)]
+pub struct AuditEventQuery {
+    #[odata(filter(kind = "Uuid"))]
+    pub id: Uuid,
+
+    #[odata(filter(kind = "I64"))]
+    pub seq: i64,
+
+    #[odata(filter(kind = "Uuid"))]
+    pub actor_id: Uuid,
+
+    #[odata(filter(kind = "String"))]
+    pub action: String,
+
+    #[odata(filter(kind = "String"))]
+    pub resource_type: String,
+
+    #[odata(filter(kind = "String"))]
+    pub resource_id: String,
+
+    #[odata(filter(kind = "String"))]
+    pub outcome: String,
+
+    #[odata(filter(kind = "DateTimeUtc"))]
+    pub occurred_at: OffsetDateTime,
+}
+
+/// Filter field enum generated by the `ODataFilterable` derive.
+pub use AuditEventQueryFilterField as AuditEventFilterField;
@@ -0,0 +1,153 @@
+//! `OperationAuditor` that records audited REST operations in the audit log.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use modkit::api::{AuditQueueConfig, AuditedOperation, OperationAuditor};
+use modkit::client_hub::ClientHub;
+use modkit_security::SecurityContext;
+use serde_json::json;
+
+use crate::api::AuditClientV1;
+use crate::models::{AuditOutcome, AuditRecord};
+
+/// Records operations wrapped with `OperationBuilder::audited`.
+///
+/// The action is the operation id (or `METHOD route` without one), the
+/// resource type is the route template and the resource id is the `id` path
+/// parameter, falling back to the last one.
+pub struct AuditOperationRecorder {
+    client: Arc<dyn AuditClientV1>,
+    queue: AuditQueueConfig,
+}
+
+impl AuditOperationRecorder {
+    #[must_use]
+    pub fn new(client: Arc<dyn AuditClientV1>) -> Self {
+        Self {
+            client,
+            queue: AuditQueueConfig::default(),
+        }
+    }
+
+    /// Use `queue` for the routes this recorder is attached to.
+    #[must_use]
+    pub fn with_queue_config(mut self, queue: AuditQueueConfig) -> Self {
+        self.queue = queue;
+        self
+    }
+}
+
+/// Build the audit record of a completed operation.
+#[must_use]
+pub fn operation_record(operation: &AuditedOperation) -> AuditRecord {
+    let action = operation
+        .operation_id
+        .clone()
+        .unwrap_or_else(|| format!("{} {}", operation.method, operation.route));
+    let mut params = operation.path_params();
+    let resource_id = params
+        .remove("id")
+        .or_else(|| params.into_values().next_back());
+    let outcome = if operation.succeeded() {
+        AuditOutcome::Success
+    } else {
+        AuditOutcome::Failure
+    };
+
+    let mut record = AuditRecord::new(action, operation.route.clone())
+        .with_outcome(outcome)
+        .with_details(json!({
+            "method": operation.method.as_str(),
+            "path": operation.path,
+            "status": operation.status.as_u16(),
+        }));
+    if let Some(id) = resource_id {
+        record = record.with_resource_id(id);
+    }
+    record
+}
+
+#[async_trait]
+impl OperationAuditor for AuditOperationRecorder {
+    fn queue_config(&self) -> AuditQueueConfig {
+        self.queue.clone()
+    }
+
+    async fn record(
+        &self,
+        ctx: &SecurityContext,
+        operation: AuditedOperation,
+    ) -> anyhow::Result<()> {
+        self.client
+            .record(ctx, operation_record(&operation))
+            .await?;
+        Ok(())
+    }
+}
+
+/// The auditor to pass to `OperationBuilder::audited`, or `None` when the
+/// audit module is not deployed.
+///
+/// Prefers the recorder the audit module registers with its configured
+/// queue; with only an `AuditClientV1`, the queue uses the defaults.
+///
+/// Call from `register_rest`, after every module has been initialized.
+#[must_use]
+pub fn operation_auditor(hub: &ClientHub) -> Option<Arc<dyn OperationAuditor>> {
+    if let Ok(auditor) = hub.get::<dyn OperationAuditor>() {
+        return Some(auditor);
+    }
+    let client = hub.get::<dyn AuditClientV1>().ok()?;
+    Some(Arc::new(AuditOperationRecorder::new(client)))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use http::{Method, StatusCode};
+
+    use super::*;
+
+    fn operation(route: &str, path: &str, status: StatusCode) -> AuditedOperation {
+        AuditedOperation {
+            operation_id: None,
+            method: Method::PATCH,
+            route: route.to_owned(),
+            path: path.to_owned(),
+            status,
+        }
+    }
+
+    #[test]
+    fn record_prefers_the_id_path_parameter() {
+        let mut op = operation(
+            "/oagw/v1/upstreams/{upstream_id}/routes/{id}",
+            "/oagw/v1/upstreams/u1/routes/r2",
+            StatusCode::OK,
+        );
+        op.operation_id = Some("oagw.update_route".to_owned());
+
+        let record = operation_record(&op);
+        assert_eq!(record.action, "oagw.update_route");
+        assert_eq!(
+            record.resource_type,
+            "/oagw/v1/upstreams/{upstream_id}/routes/{id}"
+        );
+        assert_eq!(record.resource_id.as_deref(), Some("r2"));
+        assert_eq!(record.outcome, AuditOutcome::Success);
+        assert_eq!(record.details["status"], 200);
+    }
+
+    #[test]
+    fn record_without_operation_id_or_params() {
+        let record = operation_record(&operation(
+            "/simple-user-settings/v1/settings",
+            "/simple-user-settings/v1/settings",
+            StatusCode::FORBIDDEN,
+        ));
+        assert_eq!(record.action, "PATCH /simple-user-settings/v1/settings");
+        assert_eq!(record.resource_id, None);
+        assert_eq!(record.outcome, AuditOutcome::Failure);
+    }
+}
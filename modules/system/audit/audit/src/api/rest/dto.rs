@@ -0,0 +1,94 @@
+//! REST DTOs for the audit log.
+
+use audit_sdk::{AuditEvent, AuditOutcome, ChainVerification};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// How the audited action ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[modkit_macros::api_dto(response)]
+pub enum AuditOutcomeDto {
+    Success,
+    Failure,
+}
+
+impl From<AuditOutcome> for AuditOutcomeDto {
+    fn from(outcome: AuditOutcome) -> Self {
+        match outcome {
+            AuditOutcome::Success => Self::Success,
+            AuditOutcome::Failure => Self::Failure,
+        }
+    }
+}
+
+/// An audit event.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct AuditEventDto {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    #[schema(value_type = String)]
+    pub tenant_id: Uuid,
+    /// Position in the tenant's hash chain.
+    pub seq: u64,
+    #[schema(value_type = String)]
+    pub actor_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_type: Option<String>,
+    pub action: String,
+    pub resource_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_id: Option<String>,
+    pub outcome: AuditOutcomeDto,
+    pub details: serde_json::Value,
+    #[serde(with = "time::serde::rfc3339")]
+    pub occurred_at: OffsetDateTime,
+    /// Hex SHA-256 of the previous event of the chain.
+    pub prev_hash: String,
+    /// Hex SHA-256 of this event.
+    pub hash: String,
+}
+
+impl From<AuditEvent> for AuditEventDto {
+    fn from(e: AuditEvent) -> Self {
+        Self {
+            id: e.id,
+            tenant_id: e.tenant_id,
+            seq: e.seq,
+            actor_id: e.actor_id,
+            actor_type: e.actor_type,
+            action: e.action,
+            resource_type: e.resource_type,
+            resource_id: e.resource_id,
+            outcome: e.outcome.into(),
+            details: e.details,
+            occurred_at: e.occurred_at,
+            prev_hash: e.prev_hash,
+            hash: e.hash,
+        }
+    }
+}
+
+/// Result of verifying the caller's tenant chain.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct ChainVerificationDto {
+    pub intact: bool,
+    pub events_checked: u64,
+    /// Sequence number of the last recorded event.
+    pub head_seq: u64,
+    /// First sequence number whose event is missing or altered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broken_at: Option<u64>,
+}
+
+impl From<ChainVerification> for ChainVerificationDto {
+    fn from(v: ChainVerification) -> Self {
+        Self {
+            intact: v.is_intact(),
+            events_checked: v.events_checked,
+            head_seq: v.head_seq,
+            broken_at: v.broken_at,
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! REST error mapping for the audit log.
+
+use modkit::api::prelude::StatusCode;
+use modkit::api::problem::Problem;
+
+use crate::domain::DomainError;
+
+impl From<DomainError> for Problem {
+    fn from(e: DomainError) -> Self {
+        let trace_id = tracing::Span::current()
+            .id()
+            .map(|id| id.into_u64().to_string());
+
+        let (status, code, title, detail) = match e {
+            // OData errors carry their own problem codes.
+            DomainError::InvalidQuery(odata) => return Problem::from(odata),
+            DomainError::Validation { field, message } => (
+                StatusCode::BAD_REQUEST,
+                "AUDIT_INVALID_REQUEST",
+                "Invalid request",
+                format!("{field}: {message}"),
+            ),
+            DomainError::Forbidden(message) => (
+                StatusCode::FORBIDDEN,
+                "AUDIT_ACCESS_DENIED",
+                "Access denied",
+                message,
+            ),
+            e @ (DomainError::Internal(_) | DomainError::Database(_)) => {
+                tracing::error!(error = ?e, "Internal error in audit");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "AUDIT_INTERNAL",
+                    "Internal Server Error",
+                    "An internal error occurred".to_owned(),
+                )
+            }
+        };
+
+        let mut problem = Problem::new(status, title, detail)
+            .with_type(format!("https://errors.hyperspot.com/{code}"))
+            .with_code(code);
+
+        if let Some(id) = trace_id {
+            problem = problem.with_trace_id(id);
+        }
+
+        problem
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn domain_errors_map_to_status_codes() {
+        let cases = [
+            (
+                DomainError::validation("action", "empty"),
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                DomainError::Forbidden("no".to_owned()),
+                StatusCode::FORBIDDEN,
+            ),
+            (
+                DomainError::InvalidQuery(modkit_odata::Error::InvalidCursor),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+        ];
+        for (error, status) in cases {
+            let problem: Problem = error.into();
+            assert_eq!(problem.status, status);
+        }
+    }
+
+    #[test]
+    fn internal_maps_to_500_without_leaking_detail() {
+        let problem: Problem = DomainError::Internal("db password wrong".to_owned()).into();
+        assert_eq!(problem.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(problem.detail, "An internal error occurred");
+    }
+}
@@ -0,0 +1,43 @@
+//! REST handlers for the audit log.
+
+use std::sync::Arc;
+
+use axum::extract::Extension;
+use modkit::api::odata::OData;
+use modkit::api::prelude::*;
+use modkit_security::SecurityContext;
+
+use super::dto::{AuditEventDto, ChainVerificationDto};
+use crate::domain::Service;
+
+/// GET /audit/v1/events
+///
+/// List the audit events visible to the caller.
+///
+/// # Errors
+///
+/// Returns `Problem` with 422 for an invalid `$filter`, `$orderby` or
+/// cursor, or 403 if denied.
+pub async fn list_events(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    OData(query): OData,
+) -> ApiResult<JsonPage<AuditEventDto>> {
+    let page = svc.list(&ctx, &query).await?;
+    Ok(Json(page.map_items(AuditEventDto::from)))
+}
+
+/// GET /audit/v1/chain/verify
+///
+/// Re-compute the hash chain of the caller's tenant.
+///
+/// # Errors
+///
+/// Returns `Problem` with 403 if denied.
+pub async fn verify_chain(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+) -> ApiResult<Json<ChainVerificationDto>> {
+    let verification = svc.verify_chain(&ctx).await?;
+    Ok(Json(verification.into()))
+}
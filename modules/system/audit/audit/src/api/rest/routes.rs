@@ -0,0 +1,82 @@
+//! REST route registration for the audit log.
+
+use std::sync::Arc;
+
+use audit_sdk::AuditEventFilterField;
+use axum::{Extension, Router};
+use modkit::api::OpenApiRegistry;
+use modkit::api::operation_builder::{LicenseFeature, OperationBuilder, OperationBuilderODataExt};
+use modkit::api::prelude::StatusCode;
+
+use super::dto::{AuditEventDto, ChainVerificationDto};
+use super::handlers;
+use crate::domain::Service;
+
+const TAG: &str = "Audit";
+
+struct License;
+
+impl AsRef<str> for License {
+    fn as_ref(&self) -> &'static str {
+        "gts.x.core.lic.feat.v1~x.core.global.base.v1"
+    }
+}
+
+impl LicenseFeature for License {}
+
+/// Registers the audit log routes.
+#[allow(clippy::needless_pass_by_value)]
+pub fn register_routes(
+    mut router: Router,
+    openapi: &dyn OpenApiRegistry,
+    service: Arc<Service>,
+) -> Router {
+    // GET /audit/v1/events - Query audit events
+    router = OperationBuilder::get("/audit/v1/events")
+        .operation_id("audit.list_events")
+        .summary("List audit events")
+        .description(
+            "Audit events visible to the caller, newest first. Supports `$filter` and `$orderby` on the event fields, and cursor paging.",
+        )
+        .tag(TAG)
+        .authenticated()
+        .require_license_features::<License>([])
+        .query_param_typed(
+            "limit",
+            false,
+            "Maximum number of events to return",
+            "integer",
+        )
+        .query_param("cursor", false, "Cursor for pagination")
+        .handler(handlers::list_events)
+        .json_response_with_schema::<modkit_odata::Page<AuditEventDto>>(
+            openapi,
+            StatusCode::OK,
+            "Page of audit events",
+        )
+        .with_odata_filter::<AuditEventFilterField>()
+        .with_odata_orderby::<AuditEventFilterField>()
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // GET /audit/v1/chain/verify - Verify the tenant's hash chain
+    router = OperationBuilder::get("/audit/v1/chain/verify")
+        .operation_id("audit.verify_chain")
+        .summary("Verify the audit chain")
+        .description(
+            "Re-compute the hash chain of the caller's tenant and report the first missing or altered event.",
+        )
+        .tag(TAG)
+        .authenticated()
+        .require_license_features::<License>([])
+        .handler(handlers::verify_chain)
+        .json_response_with_schema::<ChainVerificationDto>(
+            openapi,
+            StatusCode::OK,
+            "Verification result",
+        )
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    router.layer(Extension(service))
+}
@@ -0,0 +1,32 @@
+//! Configuration for the audit module.
+
+use modkit::api::AuditQueueConfig;
+use serde::Deserialize;
+
+/// Module configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuditConfig {
+    /// Page size of a query without `limit`.
+    pub default_page_size: u64,
+
+    /// Upper bound of `limit`.
+    pub max_page_size: u64,
+
+    /// Largest accepted `details`, in bytes of its JSON encoding.
+    pub max_details_bytes: usize,
+
+    /// Queue of every route audited through `operation_auditor`.
+    pub operation_queue: AuditQueueConfig,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 500,
+            max_details_bytes: 64 * 1024,
+            operation_queue: AuditQueueConfig::default(),
+        }
+    }
+}
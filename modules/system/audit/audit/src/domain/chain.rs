@@ -0,0 +1,175 @@
+//! Hash chain over the audit events of a tenant.
+//!
+//! `hash = SHA-256(prev_hash, id, tenant_id, seq, actor, action, resource,
+//! outcome, details, occurred_at)`, each field length-prefixed so that no two
+//! different events encode to the same bytes. The first event of a tenant
+//! links to [`GENESIS_HASH`].
+
+use sha2::{Digest, Sha256};
+
+use crate::infra::storage::entity::audit_event;
+
+/// `prev_hash` of the first event of a chain.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Hex SHA-256 of `event`, over every column except `hash`.
+#[must_use]
+pub fn event_hash(event: &audit_event::Model) -> String {
+    let mut h = Sha256::new();
+    field(&mut h, Some(&event.prev_hash));
+    field(&mut h, Some(&event.id.to_string()));
+    field(&mut h, Some(&event.tenant_id.to_string()));
+    field(&mut h, Some(&event.seq.to_string()));
+    field(&mut h, Some(&event.actor_id.to_string()));
+    field(&mut h, event.actor_type.as_deref());
+    field(&mut h, Some(&event.action));
+    field(&mut h, Some(&event.resource_type));
+    field(&mut h, event.resource_id.as_deref());
+    field(&mut h, Some(&event.outcome));
+    field(&mut h, Some(&event.details));
+    field(
+        &mut h,
+        Some(&event.occurred_at.unix_timestamp_nanos().to_string()),
+    );
+    hex::encode(h.finalize())
+}
+
+fn field(h: &mut Sha256, value: Option<&str>) {
+    match value {
+        Some(v) => {
+            h.update(b"s");
+            h.update((v.len() as u64).to_be_bytes());
+            h.update(v.as_bytes());
+        }
+        None => h.update(b"n"),
+    }
+}
+
+/// Walks a chain in `seq` order and reports the first broken link.
+#[derive(Debug)]
+pub struct ChainWalker {
+    expected_seq: i64,
+    prev_hash: String,
+    checked: u64,
+    broken_at: Option<i64>,
+}
+
+impl Default for ChainWalker {
+    fn default() -> Self {
+        Self {
+            expected_seq: 1,
+            prev_hash: GENESIS_HASH.to_owned(),
+            checked: 0,
+            broken_at: None,
+        }
+    }
+}
+
+impl ChainWalker {
+    /// Check the next event; returns `false` once the chain is broken.
+    pub fn check(&mut self, event: &audit_event::Model) -> bool {
+        if self.broken_at.is_some() {
+            return false;
+        }
+        // A gap means events were removed; report the first missing one.
+        if event.seq != self.expected_seq {
+            self.broken_at = Some(self.expected_seq);
+            return false;
+        }
+        if event.prev_hash != self.prev_hash || event_hash(event) != event.hash {
+            self.broken_at = Some(event.seq);
+            return false;
+        }
+        self.checked += 1;
+        self.expected_seq += 1;
+        self.prev_hash.clone_from(&event.hash);
+        true
+    }
+
+    /// Finish the walk against the chain head; events after the last one
+    /// seen but before `head_seq` are missing.
+    #[must_use]
+    pub fn finish(self, head_seq: i64) -> (u64, Option<i64>) {
+        let broken_at = self
+            .broken_at
+            .or_else(|| (self.expected_seq <= head_seq).then_some(self.expected_seq));
+        (self.checked, broken_at)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn chain(len: i64) -> Vec<audit_event::Model> {
+        let mut prev = GENESIS_HASH.to_owned();
+        (1..=len)
+            .map(|seq| {
+                let mut event = audit_event::Model {
+                    id: Uuid::from_u128(seq.unsigned_abs().into()),
+                    tenant_id: Uuid::from_u128(1),
+                    seq,
+                    actor_id: Uuid::from_u128(0xA),
+                    actor_type: None,
+                    action: "settings.update".to_owned(),
+                    resource_type: "settings".to_owned(),
+                    resource_id: None,
+                    outcome: "success".to_owned(),
+                    details: "null".to_owned(),
+                    occurred_at: OffsetDateTime::from_unix_timestamp(1_700_000_000 + seq).unwrap(),
+                    prev_hash: prev.clone(),
+                    hash: String::new(),
+                };
+                event.hash = event_hash(&event);
+                prev.clone_from(&event.hash);
+                event
+            })
+            .collect()
+    }
+
+    fn walk(events: &[audit_event::Model], head_seq: i64) -> (u64, Option<i64>) {
+        let mut walker = ChainWalker::default();
+        for event in events {
+            if !walker.check(event) {
+                break;
+            }
+        }
+        walker.finish(head_seq)
+    }
+
+    #[test]
+    fn intact_chain_verifies() {
+        assert_eq!(walk(&chain(3), 3), (3, None));
+        assert_eq!(walk(&[], 0), (0, None));
+    }
+
+    #[test]
+    fn altered_field_breaks_the_chain_at_that_event() {
+        let mut events = chain(3);
+        events[1].details = r#"{"forged":true}"#.to_owned();
+        assert_eq!(walk(&events, 3), (1, Some(2)));
+    }
+
+    #[test]
+    fn missing_events_are_detected() {
+        let mut events = chain(4);
+        events.remove(1);
+        assert_eq!(walk(&events, 4), (1, Some(2)));
+
+        // Truncated tail: the head still remembers the last sequence number.
+        let events = chain(4);
+        assert_eq!(walk(&events[..2], 4), (2, Some(3)));
+    }
+
+    #[test]
+    fn absent_and_empty_optional_fields_hash_differently() {
+        let mut event = chain(1).remove(0);
+        let absent = event_hash(&event);
+        event.resource_id = Some(String::new());
+        assert_ne!(event_hash(&event), absent);
+    }
+}
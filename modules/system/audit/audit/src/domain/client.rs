@@ -0,0 +1,34 @@
+//! In-process client of the audit module.
+//!
+//! Implements `AuditClientV1` on the domain service; this is the client the
+//! module registers in `ClientHub`.
+
+use async_trait::async_trait;
+use audit_sdk::{AuditClientV1, AuditError, AuditEvent, AuditRecord, ChainVerification};
+use modkit_odata::{ODataQuery, Page};
+use modkit_security::SecurityContext;
+
+use super::service::Service;
+
+#[async_trait]
+impl AuditClientV1 for Service {
+    async fn record(
+        &self,
+        ctx: &SecurityContext,
+        record: AuditRecord,
+    ) -> Result<AuditEvent, AuditError> {
+        Ok(Service::record(self, ctx, record).await?)
+    }
+
+    async fn list(
+        &self,
+        ctx: &SecurityContext,
+        query: &ODataQuery,
+    ) -> Result<Page<AuditEvent>, AuditError> {
+        Ok(Service::list(self, ctx, query).await?)
+    }
+
+    async fn verify_chain(&self, ctx: &SecurityContext) -> Result<ChainVerification, AuditError> {
+        Ok(Service::verify_chain(self, ctx).await?)
+    }
+}
@@ -0,0 +1,67 @@
+use audit_sdk::AuditError;
+use modkit_db::DbError;
+use modkit_macros::domain_model;
+use modkit_odata::Error as ODataError;
+
+#[domain_model]
+#[derive(Debug, thiserror::Error)]
+pub enum DomainError {
+    #[error("Validation error on field '{field}': {message}")]
+    Validation { field: String, message: String },
+
+    #[error("Invalid query: {0}")]
+    InvalidQuery(ODataError),
+
+    #[error("Access forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Internal error: {0}")]
+    Internal(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] DbError),
+}
+
+impl DomainError {
+    pub fn validation(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Validation {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl From<ODataError> for DomainError {
+    fn from(e: ODataError) -> Self {
+        match e {
+            ODataError::Db(message) => Self::Internal(message),
+            other => Self::InvalidQuery(other),
+        }
+    }
+}
+
+impl From<authz_resolver_sdk::EnforcerError> for DomainError {
+    fn from(e: authz_resolver_sdk::EnforcerError) -> Self {
+        tracing::error!(error = %e, "AuthZ scope resolution failed");
+        match e {
+            authz_resolver_sdk::EnforcerError::Denied { .. }
+            | authz_resolver_sdk::EnforcerError::CompileFailed(_) => Self::Forbidden(e.to_string()),
+            authz_resolver_sdk::EnforcerError::EvaluationFailed(_) => Self::Internal(e.to_string()),
+        }
+    }
+}
+
+impl From<DomainError> for AuditError {
+    fn from(e: DomainError) -> Self {
+        match e {
+            DomainError::Validation { field, message } => Self::Validation { field, message },
+            DomainError::InvalidQuery(odata) => Self::InvalidQuery(odata.to_string()),
+            DomainError::Forbidden(message) => Self::Unauthorized(message),
+            DomainError::Internal(message) => Self::Internal(message),
+            DomainError::Database(db) => {
+                tracing::error!(error = %db, "audit database error");
+                Self::internal("database error")
+            }
+        }
+    }
+}
@@ -0,0 +1,9 @@
+//! Domain layer for the audit module.
+
+pub mod chain;
+mod client;
+pub mod error;
+pub mod service;
+
+pub use error::DomainError;
+pub use service::Service;
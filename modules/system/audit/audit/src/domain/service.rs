@@ -0,0 +1,439 @@
+//! Domain service for the audit module.
+
+use audit_sdk::{AuditEvent, AuditRecord, ChainVerification};
+use authz_resolver_sdk::PolicyEnforcer;
+use authz_resolver_sdk::pep::{AccessRequest, ResourceType};
+use modkit_db::odata::LimitCfg;
+use modkit_db::{DBProvider, DbError};
+use modkit_macros::domain_model;
+use modkit_odata::{ODataQuery, Page};
+use modkit_security::{SecurityContext, pep_properties};
+use uuid::Uuid;
+
+use super::chain::ChainWalker;
+use super::error::DomainError;
+use crate::config::AuditConfig;
+use crate::infra::storage::entity::audit_event;
+use crate::infra::storage::{AuditRepo, NewAuditRow};
+
+/// Authorization resource type of audit events.
+pub(crate) const AUDIT_EVENT_RESOURCE: ResourceType = ResourceType {
+    name: "audit.event",
+    supported_properties: &[pep_properties::OWNER_TENANT_ID, pep_properties::RESOURCE_ID],
+};
+
+pub(crate) mod actions {
+    pub const LIST: &str = "list";
+    pub const VERIFY: &str = "verify";
+}
+
+const MAX_NAME_LEN: usize = 255;
+
+/// Events read per query while verifying a chain.
+const VERIFY_BATCH: u64 = 500;
+
+/// Audit service.
+///
+/// Appends events to the hash chain of the recording caller's tenant and
+/// serves them back under the scope the policy enforcer grants. Recording
+/// needs no grant: callers can only add events about themselves, to their
+/// own tenant.
+#[domain_model]
+pub struct Service {
+    repo: AuditRepo,
+    policy_enforcer: PolicyEnforcer,
+    cfg: AuditConfig,
+}
+
+impl Service {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>, policy_enforcer: PolicyEnforcer, cfg: AuditConfig) -> Self {
+        Self {
+            repo: AuditRepo::new(db),
+            policy_enforcer,
+            cfg,
+        }
+    }
+
+    /// Append `record` to the chain of the caller's tenant.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::Validation` for empty or oversized fields,
+    /// `DomainError::Forbidden` for a caller without a tenant, and
+    /// `DomainError::Database` if storage fails.
+    pub async fn record(
+        &self,
+        ctx: &SecurityContext,
+        record: AuditRecord,
+    ) -> Result<AuditEvent, DomainError> {
+        let tenant_id = ctx.subject_tenant_id();
+        if tenant_id.is_nil() {
+            return Err(DomainError::Forbidden(
+                "audit events need a caller with a tenant".to_owned(),
+            ));
+        }
+        validate_name("action", &record.action)?;
+        validate_name("resource_type", &record.resource_type)?;
+        if let Some(id) = &record.resource_id {
+            validate_name("resource_id", id)?;
+        }
+        let details = record.details.to_string();
+        if details.len() > self.cfg.max_details_bytes {
+            return Err(DomainError::validation(
+                "details",
+                format!("must not exceed {} bytes", self.cfg.max_details_bytes),
+            ));
+        }
+
+        let stored = self
+            .repo
+            .append(NewAuditRow {
+                id: Uuid::now_v7(),
+                tenant_id,
+                actor_id: ctx.subject_id(),
+                actor_type: ctx.subject_type().map(str::to_owned),
+                action: record.action,
+                resource_type: record.resource_type,
+                resource_id: record.resource_id,
+                outcome: record.outcome.as_str().to_owned(),
+                details,
+            })
+            .await?;
+        tracing::debug!(tenant_id = %tenant_id, seq = stored.seq, action = %stored.action, "audit event recorded");
+        to_event(stored)
+    }
+
+    /// A page of the events the caller may list.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::Forbidden` if listing is denied,
+    /// `DomainError::InvalidQuery` for an invalid filter, order or cursor.
+    pub async fn list(
+        &self,
+        ctx: &SecurityContext,
+        query: &ODataQuery,
+    ) -> Result<Page<AuditEvent>, DomainError> {
+        let scope = self
+            .policy_enforcer
+            .access_scope(ctx, &AUDIT_EVENT_RESOURCE, actions::LIST, None)
+            .await?;
+        let limit_cfg = LimitCfg {
+            default: self.cfg.default_page_size,
+            max: self.cfg.max_page_size,
+        };
+        let page = self.repo.list_page(&scope, query, limit_cfg).await?;
+        let items = page
+            .items
+            .into_iter()
+            .map(to_event)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Page::new(items, page.page_info))
+    }
+
+    /// Re-compute the hash chain of the caller's tenant.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::Forbidden` if verification is denied,
+    /// `DomainError::Database` if storage fails.
+    pub async fn verify_chain(
+        &self,
+        ctx: &SecurityContext,
+    ) -> Result<ChainVerification, DomainError> {
+        let tenant_id = ctx.subject_tenant_id();
+        self.policy_enforcer
+            .access_scope_with(
+                ctx,
+                &AUDIT_EVENT_RESOURCE,
+                actions::VERIFY,
+                None,
+                &AccessRequest::new()
+                    .resource_property(pep_properties::OWNER_TENANT_ID, tenant_id)
+                    .require_constraints(false),
+            )
+            .await?;
+
+        let head_seq = self.repo.head_seq(tenant_id).await?;
+        let mut walker = ChainWalker::default();
+        let mut after = 0;
+        'walk: loop {
+            let batch = self
+                .repo
+                .chain_after(tenant_id, after, VERIFY_BATCH)
+                .await?;
+            let Some(last) = batch.last() else {
+                break;
+            };
+            after = last.seq;
+            for event in &batch {
+                if !walker.check(event) {
+                    break 'walk;
+                }
+            }
+        }
+
+        let (events_checked, broken_at) = walker.finish(head_seq);
+        if let Some(seq) = broken_at {
+            tracing::warn!(tenant_id = %tenant_id, seq, "audit chain is broken");
+        }
+        Ok(ChainVerification {
+            events_checked,
+            head_seq: to_u64(head_seq),
+            broken_at: broken_at.map(to_u64),
+        })
+    }
+}
+
+fn validate_name(field: &str, value: &str) -> Result<(), DomainError> {
+    if value.trim().is_empty() {
+        return Err(DomainError::validation(field, "must not be empty"));
+    }
+    if value.len() > MAX_NAME_LEN {
+        return Err(DomainError::validation(
+            field,
+            format!("must not exceed {MAX_NAME_LEN} bytes"),
+        ));
+    }
+    Ok(())
+}
+
+fn to_u64(seq: i64) -> u64 {
+    u64::try_from(seq).unwrap_or_default()
+}
+
+fn to_event(m: audit_event::Model) -> Result<AuditEvent, DomainError> {
+    let details = serde_json::from_str(&m.details).map_err(|e| {
+        DomainError::Internal(format!("audit event {} has invalid details: {e}", m.id))
+    })?;
+    let outcome = m.outcome.parse().map_err(DomainError::Internal)?;
+    Ok(AuditEvent {
+        id: m.id,
+        tenant_id: m.tenant_id,
+        seq: to_u64(m.seq),
+        actor_id: m.actor_id,
+        actor_type: m.actor_type,
+        action: m.action,
+        resource_type: m.resource_type,
+        resource_id: m.resource_id,
+        outcome,
+        details,
+        occurred_at: m.occurred_at,
+        prev_hash: m.prev_hash,
+        hash: m.hash,
+    })
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use audit_sdk::AuditOutcome;
+    use authz_resolver_sdk::constraints::{Constraint, EqPredicate, Predicate};
+    use authz_resolver_sdk::models::{
+        EvaluationRequest, EvaluationResponse, EvaluationResponseContext,
+    };
+    use authz_resolver_sdk::{AuthZResolverClient, AuthZResolverError};
+    use modkit_db::secure::{SecureEntityExt, SecureUpdateExt};
+    use modkit_odata::CursorV1;
+    use modkit_odata::ast::{CompareOperator, Expr, Value};
+    use modkit_security::AccessScope;
+    use sea_orm::EntityTrait;
+    use sea_orm::sea_query::Expr as SeaExpr;
+    use serde_json::json;
+
+    use super::*;
+    use crate::domain::chain::GENESIS_HASH;
+    use crate::infra::storage::test_provider;
+
+    /// Grants every action, constrained to one tenant when `tenant` is set.
+    struct MockAuthZResolver {
+        decision: bool,
+        tenant: Option<Uuid>,
+    }
+
+    #[async_trait]
+    impl AuthZResolverClient for MockAuthZResolver {
+        async fn evaluate(
+            &self,
+            _request: EvaluationRequest,
+        ) -> Result<EvaluationResponse, AuthZResolverError> {
+            let constraints = self
+                .tenant
+                .map(|t| Constraint {
+                    predicates: vec![Predicate::Eq(EqPredicate::new(
+                        pep_properties::OWNER_TENANT_ID,
+                        t,
+                    ))],
+                })
+                .into_iter()
+                .collect();
+            Ok(EvaluationResponse {
+                decision: self.decision,
+                context: EvaluationResponseContext {
+                    constraints,
+                    deny_reason: None,
+                },
+            })
+        }
+    }
+
+    fn ctx(tenant: u128) -> SecurityContext {
+        SecurityContext::builder()
+            .subject_id(Uuid::from_u128(0xA))
+            .subject_tenant_id(Uuid::from_u128(tenant))
+            .build()
+            .unwrap()
+    }
+
+    async fn service_with(decision: bool, tenant: Option<u128>) -> (Service, DBProvider<DbError>) {
+        let db = test_provider().await;
+        let enforcer = PolicyEnforcer::new(Arc::new(MockAuthZResolver {
+            decision,
+            tenant: tenant.map(Uuid::from_u128),
+        }));
+        let svc = Service::new(db.clone(), enforcer, AuditConfig::default());
+        (svc, db)
+    }
+
+    async fn record(svc: &Service, tenant: u128, action: &str) -> AuditEvent {
+        svc.record(
+            &ctx(tenant),
+            AuditRecord::new(action, "settings").with_details(json!({ "theme": "dark" })),
+        )
+        .await
+        .unwrap()
+    }
+
+    fn action_eq(action: &str) -> Expr {
+        Expr::Compare(
+            Box::new(Expr::Identifier("action".to_owned())),
+            CompareOperator::Eq,
+            Box::new(Expr::Value(Value::String(action.to_owned()))),
+        )
+    }
+
+    #[tokio::test]
+    async fn events_of_a_tenant_form_a_hash_chain() {
+        let (svc, _) = service_with(true, None).await;
+
+        let first = record(&svc, 1, "settings.update").await;
+        let other_tenant = record(&svc, 2, "settings.update").await;
+        let second = record(&svc, 1, "settings.patch").await;
+
+        assert_eq!((first.seq, second.seq, other_tenant.seq), (1, 2, 1));
+        assert_eq!(first.prev_hash, GENESIS_HASH);
+        assert_eq!(other_tenant.prev_hash, GENESIS_HASH);
+        assert_eq!(second.prev_hash, first.hash);
+        assert_eq!(first.actor_id, Uuid::from_u128(0xA));
+        assert_eq!(first.tenant_id, Uuid::from_u128(1));
+        assert_eq!(first.outcome, AuditOutcome::Success);
+        assert_eq!(first.details, json!({ "theme": "dark" }));
+
+        let verification = svc.verify_chain(&ctx(1)).await.unwrap();
+        assert!(verification.is_intact());
+        assert_eq!((verification.events_checked, verification.head_seq), (2, 2));
+    }
+
+    #[tokio::test]
+    async fn stored_events_cannot_be_changed() {
+        let (svc, db) = service_with(true, None).await;
+        let event = record(&svc, 1, "settings.update").await;
+
+        let conn = db.conn().unwrap();
+        let all = AccessScope::allow_all();
+        let update = audit_event::Entity::update_many()
+            .secure()
+            .scope_with(&all)
+            .col_expr(audit_event::Column::Action, SeaExpr::value("forged"))
+            .exec(&conn)
+            .await;
+        assert!(update.is_err());
+
+        let stored = audit_event::Entity::find()
+            .secure()
+            .scope_with(&all)
+            .all(&conn)
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].action, event.action);
+        assert!(svc.verify_chain(&ctx(1)).await.unwrap().is_intact());
+    }
+
+    #[tokio::test]
+    async fn list_is_scoped_filtered_and_paged() {
+        let (svc, _) = service_with(true, Some(1)).await;
+        for n in 0..3 {
+            record(&svc, 1, &format!("a{n}")).await;
+        }
+        record(&svc, 1, "settings.update").await;
+        record(&svc, 2, "settings.update").await;
+
+        let filtered = svc
+            .list(
+                &ctx(1),
+                &ODataQuery::new().with_filter(action_eq("settings.update")),
+            )
+            .await
+            .unwrap();
+        assert_eq!(filtered.items.len(), 1);
+        assert_eq!(filtered.items[0].tenant_id, Uuid::from_u128(1));
+
+        let mut query = ODataQuery::new().with_limit(3);
+        let mut seqs = Vec::new();
+        loop {
+            let page = svc.list(&ctx(1), &query).await.unwrap();
+            seqs.extend(page.items.iter().map(|e| e.seq));
+            let Some(cursor) = page.page_info.next_cursor else {
+                break;
+            };
+            query = query.with_cursor(CursorV1::decode(&cursor).unwrap());
+        }
+        assert_eq!(seqs, vec![4, 3, 2, 1], "newest first, own tenant only");
+    }
+
+    #[tokio::test]
+    async fn denied_caller_cannot_list_or_verify() {
+        let (svc, _) = service_with(false, None).await;
+        record(&svc, 1, "settings.update").await;
+
+        assert!(matches!(
+            svc.list(&ctx(1), &ODataQuery::new()).await,
+            Err(DomainError::Forbidden(_))
+        ));
+        assert!(matches!(
+            svc.verify_chain(&ctx(1)).await,
+            Err(DomainError::Forbidden(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn invalid_records_are_rejected() {
+        let (svc, _) = service_with(true, None).await;
+
+        let err = svc
+            .record(&ctx(1), AuditRecord::new(" ", "settings"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Validation { ref field, .. } if field == "action"));
+
+        let err = svc
+            .record(
+                &ctx(1),
+                AuditRecord::new("a", "settings").with_details(json!("x".repeat(70_000))),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Validation { ref field, .. } if field == "details"));
+
+        let err = svc
+            .record(&SecurityContext::anonymous(), AuditRecord::new("a", "b"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Forbidden(_)));
+    }
+}
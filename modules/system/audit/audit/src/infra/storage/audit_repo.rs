@@ -0,0 +1,215 @@
+use audit_sdk::AuditEventFilterField;
+use modkit_db::odata::{LimitCfg, paginate_odata};
+use modkit_db::secure::{
+    SecureEntityExt, SecureInsertExt, SecureOnConflict, SecureUpdateExt, secure_insert,
+};
+use modkit_db::{DBProvider, DbError};
+use modkit_odata::{Error as ODataError, ODataQuery, Page, SortDir};
+use modkit_security::AccessScope;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, Order, Set};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::entity::{audit_event, chain_head};
+use super::odata_mapper::AuditEventODataMapper;
+use crate::domain::chain::{GENESIS_HASH, event_hash};
+
+/// Event to append; the chain fields are filled in by [`AuditRepo::append`].
+pub struct NewAuditRow {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub actor_id: Uuid,
+    pub actor_type: Option<String>,
+    pub action: String,
+    pub resource_type: String,
+    pub resource_id: Option<String>,
+    pub outcome: String,
+    pub details: String,
+}
+
+/// Audit events and chain heads in the module database.
+///
+/// Writes run with `AccessScope::allow_all()`: the tenant of an event is the
+/// recording caller's, set by the service. Reads for callers are scoped.
+pub struct AuditRepo {
+    db: DBProvider<DbError>,
+}
+
+impl AuditRepo {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>) -> Self {
+        Self { db }
+    }
+
+    /// Append an event to its tenant's chain.
+    ///
+    /// Bumping `last_seq` of the chain head locks it until commit, so
+    /// concurrent writers of a tenant take turns and each links to the hash
+    /// its predecessor stored in the head.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if a query fails.
+    pub async fn append(&self, row: NewAuditRow) -> Result<audit_event::Model, DbError> {
+        self.db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let all = AccessScope::allow_all();
+                    let now = OffsetDateTime::now_utc();
+                    // Postgres and MySQL keep microseconds; hash what is stored.
+                    let occurred_at = now
+                        .replace_nanosecond(now.nanosecond() - now.nanosecond() % 1_000)
+                        .map_err(|e| DbError::Other(e.into()))?;
+
+                    let bump = SecureOnConflict::<chain_head::Entity>::columns([
+                        chain_head::Column::TenantId,
+                    ])
+                    .value(
+                        chain_head::Column::LastSeq,
+                        Expr::col((chain_head::Entity, chain_head::Column::LastSeq)).add(1),
+                    )?;
+                    chain_head::Entity::insert(chain_head::ActiveModel {
+                        tenant_id: Set(row.tenant_id),
+                        last_seq: Set(1),
+                        last_hash: Set(GENESIS_HASH.to_owned()),
+                        updated_at: Set(occurred_at),
+                    })
+                    .secure()
+                    .scope_unchecked(&all)?
+                    .on_conflict(bump)
+                    .exec(tx)
+                    .await?;
+
+                    let head = chain_head::Entity::find()
+                        .secure()
+                        .scope_with(&all)
+                        .filter(
+                            Condition::all().add(chain_head::Column::TenantId.eq(row.tenant_id)),
+                        )
+                        .one(tx)
+                        .await?
+                        .ok_or_else(|| {
+                            DbError::Other(anyhow::anyhow!(
+                                "audit chain head of tenant {} vanished",
+                                row.tenant_id
+                            ))
+                        })?;
+
+                    let mut event = audit_event::Model {
+                        id: row.id,
+                        tenant_id: row.tenant_id,
+                        seq: head.last_seq,
+                        actor_id: row.actor_id,
+                        actor_type: row.actor_type,
+                        action: row.action,
+                        resource_type: row.resource_type,
+                        resource_id: row.resource_id,
+                        outcome: row.outcome,
+                        details: row.details,
+                        occurred_at,
+                        prev_hash: head.last_hash,
+                        hash: String::new(),
+                    };
+                    event.hash = event_hash(&event);
+
+                    chain_head::Entity::update_many()
+                        .secure()
+                        .scope_with(&all)
+                        .filter(
+                            Condition::all().add(chain_head::Column::TenantId.eq(event.tenant_id)),
+                        )
+                        .col_expr(
+                            chain_head::Column::LastHash,
+                            Expr::value(event.hash.clone()),
+                        )
+                        .col_expr(chain_head::Column::UpdatedAt, Expr::value(occurred_at))
+                        .exec(tx)
+                        .await?;
+
+                    let am = audit_event::ActiveModel {
+                        id: Set(event.id),
+                        tenant_id: Set(event.tenant_id),
+                        seq: Set(event.seq),
+                        actor_id: Set(event.actor_id),
+                        actor_type: Set(event.actor_type.clone()),
+                        action: Set(event.action.clone()),
+                        resource_type: Set(event.resource_type.clone()),
+                        resource_id: Set(event.resource_id.clone()),
+                        outcome: Set(event.outcome.clone()),
+                        details: Set(event.details.clone()),
+                        occurred_at: Set(event.occurred_at),
+                        prev_hash: Set(event.prev_hash.clone()),
+                        hash: Set(event.hash.clone()),
+                    };
+                    secure_insert::<audit_event::Entity>(am, &all, tx).await?;
+                    Ok(event)
+                })
+            })
+            .await
+    }
+
+    /// A page of the events visible under `scope`, newest first by default.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ODataError` for an invalid filter, order or cursor, or if
+    /// the query fails.
+    pub async fn list_page(
+        &self,
+        scope: &AccessScope,
+        query: &ODataQuery,
+        limit_cfg: LimitCfg,
+    ) -> Result<Page<audit_event::Model>, ODataError> {
+        let conn = self.db.conn().map_err(|e| ODataError::Db(e.to_string()))?;
+        let base_query = audit_event::Entity::find().secure().scope_with(scope);
+        // Ids are UUIDv7, so ordering by id is ordering by recording time.
+        paginate_odata::<AuditEventFilterField, AuditEventODataMapper, _, _, _, _>(
+            base_query,
+            &conn,
+            query,
+            ("id", SortDir::Desc),
+            limit_cfg,
+            |m| m,
+        )
+        .await
+    }
+
+    /// `last_seq` of the tenant's chain head; 0 if nothing was recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn head_seq(&self, tenant_id: Uuid) -> Result<i64, DbError> {
+        let conn = self.db.conn()?;
+        let head = chain_head::Entity::find()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .filter(Condition::all().add(chain_head::Column::TenantId.eq(tenant_id)))
+            .one(&conn)
+            .await?;
+        Ok(head.map_or(0, |h| h.last_seq))
+    }
+
+    /// Up to `limit` events of the tenant's chain after `after_seq`, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn chain_after(
+        &self,
+        tenant_id: Uuid,
+        after_seq: i64,
+        limit: u64,
+    ) -> Result<Vec<audit_event::Model>, DbError> {
+        let conn = self.db.conn()?;
+        Ok(audit_event::Entity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .filter(Condition::all().add(audit_event::Column::Seq.gt(after_seq)))
+            .order_by(audit_event::Column::Seq, Order::Asc)
+            .limit(limit)
+            .all(&conn)
+            .await?)
+    }
+}
@@ -0,0 +1,184 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let statements = match backend {
+            sea_orm::DatabaseBackend::Postgres => POSTGRES_UP,
+            sea_orm::DatabaseBackend::MySql => MYSQL_UP,
+            sea_orm::DatabaseBackend::Sqlite => SQLITE_UP,
+        };
+
+        // One statement per call: MySQL rejects multi-statement strings.
+        for sql in statements {
+            conn.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        // Dropping a table drops its triggers.
+        for table in ["audit_chain_heads", "audit_events"] {
+            conn.execute_unprepared(&format!("DROP TABLE IF EXISTS {table}"))
+                .await?;
+        }
+        if manager.get_database_backend() == sea_orm::DatabaseBackend::Postgres {
+            conn.execute_unprepared("DROP FUNCTION IF EXISTS audit_events_immutable()")
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+// Audit events are append-only: the triggers reject UPDATE and DELETE, so
+// rewriting history needs DDL privileges on top of write access, and the
+// hash chain still exposes it.
+
+const POSTGRES_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS audit_events (
+    id             UUID PRIMARY KEY NOT NULL,
+    tenant_id      UUID NOT NULL,
+    seq            BIGINT NOT NULL,
+    actor_id       UUID NOT NULL,
+    actor_type     VARCHAR(255),
+    action         VARCHAR(255) NOT NULL,
+    resource_type  VARCHAR(255) NOT NULL,
+    resource_id    VARCHAR(255),
+    outcome        VARCHAR(16) NOT NULL,
+    details        TEXT NOT NULL,
+    occurred_at    TIMESTAMPTZ NOT NULL,
+    prev_hash      CHAR(64) NOT NULL,
+    hash           CHAR(64) NOT NULL
+)
+",
+    r"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_audit_events_tenant_seq ON audit_events (tenant_id, seq)
+",
+    r"
+CREATE INDEX IF NOT EXISTS idx_audit_events_tenant_occurred
+    ON audit_events (tenant_id, occurred_at)
+",
+    r"
+CREATE TABLE IF NOT EXISTS audit_chain_heads (
+    tenant_id   UUID PRIMARY KEY NOT NULL,
+    last_seq    BIGINT NOT NULL,
+    last_hash   CHAR(64) NOT NULL,
+    updated_at  TIMESTAMPTZ NOT NULL
+)
+",
+    r"
+CREATE OR REPLACE FUNCTION audit_events_immutable() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'audit events are immutable';
+END;
+$$ LANGUAGE plpgsql
+",
+    r"
+DROP TRIGGER IF EXISTS audit_events_immutable ON audit_events
+",
+    r"
+CREATE TRIGGER audit_events_immutable
+    BEFORE UPDATE OR DELETE ON audit_events
+    FOR EACH ROW EXECUTE FUNCTION audit_events_immutable()
+",
+];
+
+const MYSQL_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS audit_events (
+    id             VARCHAR(36) PRIMARY KEY NOT NULL,
+    tenant_id      VARCHAR(36) NOT NULL,
+    seq            BIGINT NOT NULL,
+    actor_id       VARCHAR(36) NOT NULL,
+    actor_type     VARCHAR(255) NULL,
+    action         VARCHAR(255) NOT NULL,
+    resource_type  VARCHAR(255) NOT NULL,
+    resource_id    VARCHAR(255) NULL,
+    outcome        VARCHAR(16) NOT NULL,
+    details        LONGTEXT NOT NULL,
+    occurred_at    TIMESTAMP(6) NOT NULL,
+    prev_hash      CHAR(64) NOT NULL,
+    hash           CHAR(64) NOT NULL,
+    UNIQUE KEY uq_audit_events_tenant_seq (tenant_id, seq),
+    KEY idx_audit_events_tenant_occurred (tenant_id, occurred_at)
+)
+",
+    r"
+CREATE TABLE IF NOT EXISTS audit_chain_heads (
+    tenant_id   VARCHAR(36) PRIMARY KEY NOT NULL,
+    last_seq    BIGINT NOT NULL,
+    last_hash   CHAR(64) NOT NULL,
+    updated_at  TIMESTAMP(6) NOT NULL
+)
+",
+    r"
+DROP TRIGGER IF EXISTS audit_events_no_update
+",
+    r"
+CREATE TRIGGER audit_events_no_update BEFORE UPDATE ON audit_events
+    FOR EACH ROW SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = 'audit events are immutable'
+",
+    r"
+DROP TRIGGER IF EXISTS audit_events_no_delete
+",
+    r"
+CREATE TRIGGER audit_events_no_delete BEFORE DELETE ON audit_events
+    FOR EACH ROW SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = 'audit events are immutable'
+",
+];
+
+const SQLITE_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS audit_events (
+    id             TEXT PRIMARY KEY NOT NULL,
+    tenant_id      TEXT NOT NULL,
+    seq            INTEGER NOT NULL,
+    actor_id       TEXT NOT NULL,
+    actor_type     TEXT,
+    action         TEXT NOT NULL,
+    resource_type  TEXT NOT NULL,
+    resource_id    TEXT,
+    outcome        TEXT NOT NULL,
+    details        TEXT NOT NULL,
+    occurred_at    TEXT NOT NULL,
+    prev_hash      TEXT NOT NULL,
+    hash           TEXT NOT NULL
+)
+",
+    r"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_audit_events_tenant_seq ON audit_events (tenant_id, seq)
+",
+    r"
+CREATE INDEX IF NOT EXISTS idx_audit_events_tenant_occurred
+    ON audit_events (tenant_id, occurred_at)
+",
+    r"
+CREATE TABLE IF NOT EXISTS audit_chain_heads (
+    tenant_id   TEXT PRIMARY KEY NOT NULL,
+    last_seq    INTEGER NOT NULL,
+    last_hash   TEXT NOT NULL,
+    updated_at  TEXT NOT NULL
+)
+",
+    r"
+CREATE TRIGGER IF NOT EXISTS audit_events_no_update BEFORE UPDATE ON audit_events
+BEGIN
+    SELECT RAISE(ABORT, 'audit events are immutable');
+END
+",
+    r"
+CREATE TRIGGER IF NOT EXISTS audit_events_no_delete BEFORE DELETE ON audit_events
+BEGIN
+    SELECT RAISE(ABORT, 'audit events are immutable');
+END
+",
+];
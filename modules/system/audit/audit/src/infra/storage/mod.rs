@@ -0,0 +1,32 @@
+//! SQL storage of the audit log on top of the `modkit-db` secure ORM.
+//!
+//! `audit_events` holds the events, numbered per tenant by `seq`, and
+//! `audit_chain_heads` the last sequence number and hash of each tenant's
+//! chain.
+
+mod audit_repo;
+pub mod entity;
+pub mod migrations;
+mod odata_mapper;
+
+pub use audit_repo::{AuditRepo, NewAuditRow};
+
+#[cfg(test)]
+pub(crate) async fn test_provider() -> modkit_db::DBProvider<modkit_db::DbError> {
+    use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::{ConnectOpts, connect_db};
+    use sea_orm_migration::MigratorTrait;
+
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db("sqlite::memory:", opts)
+        .await
+        .expect("connect in-memory database");
+    run_migrations_for_testing(&db, migrations::Migrator::migrations())
+        .await
+        .expect("run migrations");
+    modkit_db::DBProvider::new(db)
+}
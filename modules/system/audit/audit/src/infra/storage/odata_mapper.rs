@@ -0,0 +1,52 @@
+//! Mapping of the audit event `OData` fields to `audit_events` columns.
+
+use audit_sdk::AuditEventFilterField;
+use modkit_db::odata::sea_orm_filter::{FieldToColumn, ODataFieldMapping};
+
+use super::entity::audit_event::{Column, Entity, Model};
+
+pub struct AuditEventODataMapper;
+
+impl FieldToColumn<AuditEventFilterField> for AuditEventODataMapper {
+    type Column = Column;
+
+    fn map_field(field: AuditEventFilterField) -> Column {
+        match field {
+            AuditEventFilterField::Id => Column::Id,
+            AuditEventFilterField::Seq => Column::Seq,
+            AuditEventFilterField::ActorId => Column::ActorId,
+            AuditEventFilterField::Action => Column::Action,
+            AuditEventFilterField::ResourceType => Column::ResourceType,
+            AuditEventFilterField::ResourceId => Column::ResourceId,
+            AuditEventFilterField::Outcome => Column::Outcome,
+            AuditEventFilterField::OccurredAt => Column::OccurredAt,
+        }
+    }
+}
+
+impl ODataFieldMapping<AuditEventFilterField> for AuditEventODataMapper {
+    type Entity = Entity;
+
+    fn extract_cursor_value(model: &Model, field: AuditEventFilterField) -> sea_orm::Value {
+        match field {
+            AuditEventFilterField::Id => sea_orm::Value::Uuid(Some(Box::new(model.id))),
+            AuditEventFilterField::Seq => sea_orm::Value::BigInt(Some(model.seq)),
+            AuditEventFilterField::ActorId => sea_orm::Value::Uuid(Some(Box::new(model.actor_id))),
+            AuditEventFilterField::Action => {
+                sea_orm::Value::String(Some(Box::new(model.action.clone())))
+            }
+            AuditEventFilterField::ResourceType => {
+                sea_orm::Value::String(Some(Box::new(model.resource_type.clone())))
+            }
+            AuditEventFilterField::ResourceId => {
+                sea_orm::Value::String(model.resource_id.clone().map(Box::new))
+            }
+            AuditEventFilterField::Outcome => {
+                sea_orm::Value::String(Some(Box::new(model.outcome.clone())))
+            }
+            AuditEventFilterField::OccurredAt => {
+                sea_orm::Value::TimeDateTimeWithTimeZone(Some(Box::new(model.occurred_at)))
+            }
+        }
+    }
+}
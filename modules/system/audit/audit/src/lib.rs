@@ -0,0 +1,42 @@
+//! Audit Module
+//!
+//! Immutable audit log of security- and business-relevant actions. Each
+//! event records the actor and tenant from the caller's `SecurityContext`
+//! together with the action, the resource and the outcome.
+//!
+//! Events are append-only: the storage rejects updates and deletes, and the
+//! events of a tenant form a SHA-256 hash chain, so a modified, removed or
+//! reordered event is detected by re-computing the chain.
+//!
+//! Modules record events through `AuditClientV1` from `ClientHub`, or have
+//! their mutating REST operations recorded by passing
+//! `audit_sdk::operation_auditor` to `OperationBuilder::audited`. Events are
+//! queried over REST with `OData` `$filter`/`$orderby` and cursor paging.
+//!
+//! ## Configuration
+//!
+//! ```yaml
+//! modules:
+//!   audit:
+//!     database:
+//!       server: "sqlite_users"
+//!       file: "audit.db"
+//!     config:
+//!       default_page_size: 50
+//!       max_page_size: 500
+//!       max_details_bytes: 65536
+//! ```
+
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod module;
+pub use module::AuditModule;
+
+#[doc(hidden)]
+pub mod api;
+#[doc(hidden)]
+pub mod config;
+#[doc(hidden)]
+pub mod domain;
+#[doc(hidden)]
+pub mod infra;
@@ -0,0 +1,96 @@
+//! Audit module.
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use audit_sdk::{AuditClientV1, AuditOperationRecorder};
+use authz_resolver_sdk::{AuthZResolverClient, PolicyEnforcer};
+use axum::Router;
+use modkit::Module;
+use modkit::api::{OpenApiRegistry, OperationAuditor};
+use modkit::context::ModuleCtx;
+use tracing::info;
+
+use crate::api::rest::routes;
+use crate::config::AuditConfig;
+use crate::domain::Service;
+
+/// Audit module.
+///
+/// Registers the in-process `AuditClientV1` and the `OperationAuditor` of
+/// audited routes in `ClientHub`, and exposes the audit log query API over
+/// REST.
+#[modkit::module(
+    name = "audit",
+    deps = ["authz-resolver"],
+    capabilities = [db, rest]
+)]
+pub struct AuditModule {
+    service: OnceLock<Arc<Service>>,
+}
+
+impl Default for AuditModule {
+    fn default() -> Self {
+        Self {
+            service: OnceLock::new(),
+        }
+    }
+}
+
+impl modkit::contracts::DatabaseCapability for AuditModule {
+    fn migrations(&self) -> Vec<Box<dyn sea_orm_migration::MigrationTrait>> {
+        use sea_orm_migration::MigratorTrait;
+        crate::infra::storage::migrations::Migrator::migrations()
+    }
+}
+
+#[async_trait]
+impl Module for AuditModule {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        let cfg: AuditConfig = ctx.config()?;
+        info!(
+            default_page_size = cfg.default_page_size,
+            max_page_size = cfg.max_page_size,
+            max_details_bytes = cfg.max_details_bytes,
+            operation_queue_capacity = cfg.operation_queue.capacity,
+            "Loaded audit configuration"
+        );
+        let operation_queue = cfg.operation_queue.clone();
+
+        let authz = ctx
+            .client_hub()
+            .get::<dyn AuthZResolverClient>()
+            .map_err(|e| anyhow::anyhow!("failed to get AuthZ resolver: {e}"))?;
+        let policy_enforcer = PolicyEnforcer::new(authz);
+
+        let service = Arc::new(Service::new(ctx.db_required()?, policy_enforcer, cfg));
+        self.service
+            .set(service.clone())
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
+        let api: Arc<dyn AuditClientV1> = service;
+        ctx.client_hub().register::<dyn AuditClientV1>(api.clone());
+
+        // Recorder handed out by `operation_auditor`, with the configured queue.
+        let recorder: Arc<dyn OperationAuditor> =
+            Arc::new(AuditOperationRecorder::new(api).with_queue_config(operation_queue));
+        ctx.client_hub().register::<dyn OperationAuditor>(recorder);
+        Ok(())
+    }
+}
+
+impl modkit::contracts::RestApiCapability for AuditModule {
+    fn register_rest(
+        &self,
+        _ctx: &ModuleCtx,
+        router: Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> anyhow::Result<Router> {
+        let service = self
+            .service
+            .get()
+            .ok_or_else(|| anyhow::anyhow!("Service not initialized"))?
+            .clone();
+        Ok(routes::register_routes(router, openapi, service))
+    }
+}
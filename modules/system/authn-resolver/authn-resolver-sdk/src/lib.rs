@@ -4,6 +4,7 @@
 //!
 //! - [`AuthNResolverClient`] - Public API trait for consumers
 //! - [`AuthNResolverPluginClient`] - Plugin API trait for implementations
+//! - [`AuthNRevocationListener`] - Token revocation notifications from plugins
 //! - [`AuthenticationResult`] - Authentication result model
 //! - [`AuthNResolverError`] - Error types
 //! - [`AuthNResolverPluginSpecV1`] - GTS schema for plugin discovery
@@ -34,4 +35,4 @@ pub use api::AuthNResolverClient;
 pub use error::AuthNResolverError;
 pub use gts::AuthNResolverPluginSpecV1;
 pub use models::AuthenticationResult;
-pub use plugin_api::AuthNResolverPluginClient;
+pub use plugin_api::{AuthNResolverPluginClient, AuthNRevocationListener};
@@ -1,5 +1,7 @@
 //! Domain models for the `AuthN` resolver module.
 
+use std::time::SystemTime;
+
 use modkit_security::SecurityContext;
 
 /// Result of a successful authentication.
@@ -17,4 +19,8 @@ pub struct AuthenticationResult {
     /// - `bearer_token` — Original token for PDP forwarding
     /// - `tenant_id` — Context tenant (may be set by `AuthN` or later by middleware)
     pub security_context: SecurityContext,
+
+    /// When the token stops being valid, if known. Results must not be
+    /// cached beyond it.
+    pub expires_at: Option<SystemTime>,
 }
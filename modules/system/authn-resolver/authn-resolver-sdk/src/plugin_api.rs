@@ -33,3 +33,13 @@ pub trait AuthNResolverPluginClient: Send + Sync {
         bearer_token: &str,
     ) -> Result<AuthenticationResult, AuthNResolverError>;
 }
+
+/// Token revocation notifications from plugins to the gateway.
+///
+/// The gateway registers this trait in `ClientHub`. A plugin that learns a
+/// token was revoked calls it, so the cached authentication result is not
+/// served until it expires.
+pub trait AuthNRevocationListener: Send + Sync {
+    /// One or more previously valid tokens were revoked.
+    fn tokens_revoked(&self);
+}
@@ -1,5 +1,6 @@
 //! Configuration for the `AuthN` resolver.
 
+use modkit::cache::CacheConfig;
 use serde::Deserialize;
 
 /// Configuration.
@@ -11,12 +12,20 @@ pub struct AuthNResolverConfig {
     /// The resolver queries types-registry for plugin instances matching
     /// this vendor and selects the one with lowest priority.
     pub vendor: String,
+
+    /// Caching of authentication results, keyed by token hash (disabled
+    /// by default).
+    ///
+    /// A revoked token keeps authenticating until its entry expires, so keep
+    /// the TTL short.
+    pub cache: CacheConfig,
 }
 
 impl Default for AuthNResolverConfig {
     fn default() -> Self {
         Self {
             vendor: "hyperspot".to_owned(),
+            cache: CacheConfig::default(),
         }
     }
 }
@@ -3,7 +3,9 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use authn_resolver_sdk::{AuthNResolverClient, AuthNResolverError, AuthenticationResult};
+use authn_resolver_sdk::{
+    AuthNResolverClient, AuthNResolverError, AuthNRevocationListener, AuthenticationResult,
+};
 use modkit_macros::domain_model;
 
 use super::{DomainError, Service};
@@ -40,3 +42,10 @@ impl AuthNResolverClient for AuthNResolverLocalClient {
             .map_err(|e| log_and_convert("authenticate", e))
     }
 }
+
+impl AuthNRevocationListener for AuthNResolverLocalClient {
+    fn tokens_revoked(&self) {
+        tracing::debug!("Tokens revoked; clearing cache");
+        self.svc.invalidate_cache();
+    }
+}
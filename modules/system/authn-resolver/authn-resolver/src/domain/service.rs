@@ -4,15 +4,17 @@
 //! types-registry is ready.
 
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use authn_resolver_sdk::{
     AuthNResolverPluginClient, AuthNResolverPluginSpecV1, AuthenticationResult,
 };
+use modkit::cache::{Cache, CacheConfig};
 use modkit::client_hub::{ClientHub, ClientScope};
 use modkit::plugins::{GtsPluginSelector, choose_plugin_instance};
 use modkit::telemetry::ThrottledLog;
 use modkit_macros::domain_model;
+use sha2::{Digest, Sha256};
 use tracing::info;
 use types_registry_sdk::{ListQuery, TypesRegistryClient};
 
@@ -24,23 +26,40 @@ const UNAVAILABLE_LOG_THROTTLE: Duration = Duration::from_secs(10);
 /// `AuthN` resolver service.
 ///
 /// Discovers plugins via types-registry and delegates authentication calls.
+///
+/// With `cache.enabled`, successful results are cached by the SHA-256 of the
+/// token, so raw tokens are not kept as cache keys. An entry never outlives
+/// the token's `expires_at`; results for already expired tokens are not
+/// cached.
 #[domain_model]
 pub struct Service {
     hub: Arc<ClientHub>,
     vendor: String,
     selector: GtsPluginSelector,
     unavailable_log_throttle: ThrottledLog,
+    cache: Option<Cache<[u8; 32], AuthenticationResult>>,
 }
 
 impl Service {
     /// Creates a new service with lazy plugin resolution.
     #[must_use]
-    pub fn new(hub: Arc<ClientHub>, vendor: String) -> Self {
+    pub fn new(hub: Arc<ClientHub>, vendor: String, cache: &CacheConfig) -> Self {
         Self {
             hub,
             vendor,
             selector: GtsPluginSelector::new(),
             unavailable_log_throttle: ThrottledLog::new(UNAVAILABLE_LOG_THROTTLE),
+            cache: Cache::from_config("authn-resolver.authenticate", cache),
+        }
+    }
+
+    /// Drop all cached authentication results.
+    ///
+    /// Called when a plugin reports revoked tokens; entries are keyed by a
+    /// token hash, so they are not invalidated selectively.
+    pub fn invalidate_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
         }
     }
 
@@ -109,10 +128,143 @@ impl Service {
         &self,
         bearer_token: &str,
     ) -> Result<AuthenticationResult, DomainError> {
-        let plugin = self.get_plugin().await?;
-        plugin
-            .authenticate(bearer_token)
-            .await
-            .map_err(DomainError::from)
+        let authenticate = || async {
+            let plugin = self.get_plugin().await?;
+            plugin
+                .authenticate(bearer_token)
+                .await
+                .map_err(DomainError::from)
+        };
+        match &self.cache {
+            Some(cache) => {
+                let key = Sha256::digest(bearer_token.as_bytes()).into();
+                cache
+                    .get_or_try_insert_with_ttl(key, || async {
+                        let result = authenticate().await?;
+                        let lifetime = remaining_lifetime(&result);
+                        Ok((result, lifetime))
+                    })
+                    .await
+            }
+            None => authenticate().await,
+        }
+    }
+}
+
+/// How long `result` may be cached: until the token expires, or for the
+/// cache TTL when the plugin reports no expiry.
+fn remaining_lifetime(result: &AuthenticationResult) -> Duration {
+    match result.expires_at {
+        Some(expires_at) => expires_at
+            .duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+        None => Duration::MAX,
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use authn_resolver_sdk::{AuthNResolverError, AuthNRevocationListener};
+    use modkit_security::SecurityContext;
+    use types_registry_sdk::{GtsEntity, RegisterResult, TypesRegistryError};
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::domain::AuthNResolverLocalClient;
+
+    struct MockRegistry {
+        instance: GtsEntity,
+    }
+
+    #[async_trait]
+    impl TypesRegistryClient for MockRegistry {
+        async fn list(&self, _query: ListQuery) -> Result<Vec<GtsEntity>, TypesRegistryError> {
+            Ok(vec![self.instance.clone()])
+        }
+
+        async fn get(&self, gts_id: &str) -> Result<GtsEntity, TypesRegistryError> {
+            Err(TypesRegistryError::not_found(gts_id))
+        }
+
+        async fn register(
+            &self,
+            _entities: Vec<serde_json::Value>,
+        ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+            Ok(vec![])
+        }
+    }
+
+    /// Plugin that accepts every token and counts its calls.
+    #[derive(Default)]
+    struct CountingPlugin {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl AuthNResolverPluginClient for CountingPlugin {
+        async fn authenticate(
+            &self,
+            bearer_token: &str,
+        ) -> Result<AuthenticationResult, AuthNResolverError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let security_context = SecurityContext::builder()
+                .subject_id(Uuid::from_u128(1))
+                .subject_tenant_id(Uuid::from_u128(2))
+                .bearer_token(bearer_token.to_owned())
+                .build()
+                .unwrap();
+            Ok(AuthenticationResult {
+                security_context,
+                expires_at: None,
+            })
+        }
+    }
+
+    fn cached_service(plugin: Arc<CountingPlugin>) -> Service {
+        let instance_id = AuthNResolverPluginSpecV1::gts_make_instance_id("test._.mock.v1");
+        let hub = Arc::new(ClientHub::default());
+        hub.register::<dyn TypesRegistryClient>(Arc::new(MockRegistry {
+            instance: GtsEntity {
+                id: Uuid::nil(),
+                gts_id: instance_id.to_string(),
+                segments: vec![],
+                is_schema: false,
+                content: serde_json::json!({
+                    "id": instance_id,
+                    "vendor": "hyperspot",
+                    "priority": 0,
+                    "properties": {}
+                }),
+                description: None,
+            },
+        }));
+        hub.register_scoped::<dyn AuthNResolverPluginClient>(
+            ClientScope::gts_id(&instance_id),
+            plugin,
+        );
+        let cache = CacheConfig {
+            enabled: true,
+            ..CacheConfig::default()
+        };
+        Service::new(hub, "hyperspot".to_owned(), &cache)
+    }
+
+    #[tokio::test]
+    async fn revocation_drops_cached_results() {
+        let plugin = Arc::new(CountingPlugin::default());
+        let svc = Arc::new(cached_service(plugin.clone()));
+        let listener = AuthNResolverLocalClient::new(svc.clone());
+
+        svc.authenticate("token").await.unwrap();
+        svc.authenticate("token").await.unwrap();
+        assert_eq!(plugin.calls.load(Ordering::SeqCst), 1);
+
+        listener.tokens_revoked();
+        svc.authenticate("token").await.unwrap();
+        assert_eq!(plugin.calls.load(Ordering::SeqCst), 2);
     }
 }
@@ -3,7 +3,7 @@
 use std::sync::{Arc, OnceLock};
 
 use async_trait::async_trait;
-use authn_resolver_sdk::{AuthNResolverClient, AuthNResolverPluginSpecV1};
+use authn_resolver_sdk::{AuthNResolverClient, AuthNResolverPluginSpecV1, AuthNRevocationListener};
 use modkit::Module;
 use modkit::context::ModuleCtx;
 use modkit::contracts::SystemCapability;
@@ -75,14 +75,19 @@ impl Module for AuthNResolver {
 
         // Create service
         let hub = ctx.client_hub();
-        let svc = Arc::new(Service::new(hub, cfg.vendor));
+        let svc = Arc::new(Service::new(hub, cfg.vendor, &cfg.cache));
         self.service
             .set(svc.clone())
             .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
 
-        // Register client in ClientHub
-        let api: Arc<dyn AuthNResolverClient> = Arc::new(AuthNResolverLocalClient::new(svc));
+        // Register local client in ClientHub; plugins report revoked tokens
+        // through the same client.
+        let client = Arc::new(AuthNResolverLocalClient::new(svc));
+        let api: Arc<dyn AuthNResolverClient> = client.clone();
         ctx.client_hub().register::<dyn AuthNResolverClient>(api);
+        let listener: Arc<dyn AuthNRevocationListener> = client;
+        ctx.client_hub()
+            .register::<dyn AuthNRevocationListener>(listener);
 
         Ok(())
     }
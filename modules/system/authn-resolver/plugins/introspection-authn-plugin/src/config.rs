@@ -0,0 +1,217 @@
+//! Configuration for the introspection `AuthN` resolver plugin.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Plugin configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IntrospectionAuthNPluginConfig {
+    /// Vendor name for GTS instance registration.
+    pub vendor: String,
+
+    /// Plugin priority (lower = higher priority).
+    pub priority: i16,
+
+    /// RFC 7662 introspection endpoint URL.
+    pub introspection_endpoint: String,
+
+    /// Credentials the plugin presents to the introspection endpoint.
+    pub client: ClientConfig,
+
+    /// `token_type_hint` sent with each request; omitted when `None`.
+    pub token_type_hint: Option<String>,
+
+    /// Timeout for introspection requests.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub http_timeout: Duration,
+
+    /// Introspection response fields mapped onto the `SecurityContext`.
+    pub claims: ClaimMapping,
+
+    /// Cache of introspection results.
+    pub cache: CacheConfig,
+}
+
+impl Default for IntrospectionAuthNPluginConfig {
+    fn default() -> Self {
+        Self {
+            vendor: "hyperspot".to_owned(),
+            priority: 60,
+            introspection_endpoint: String::new(),
+            client: ClientConfig::default(),
+            token_type_hint: Some("access_token".to_owned()),
+            http_timeout: Duration::from_secs(5),
+            claims: ClaimMapping::default(),
+            cache: CacheConfig::default(),
+        }
+    }
+}
+
+/// How the plugin authenticates to the introspection endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientAuth {
+    /// `Authorization: Basic` with the client id and secret.
+    #[default]
+    Basic,
+    /// `client_id` and `client_secret` form fields.
+    Form,
+    /// A bearer token obtained through the client credentials grant at
+    /// `token_endpoint`.
+    Bearer,
+}
+
+/// Introspection client credentials.
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClientConfig {
+    /// `OAuth2` client identifier.
+    pub client_id: String,
+
+    /// `OAuth2` client secret. `${VAR}` reads it from the environment.
+    pub client_secret: String,
+
+    /// How the credentials are presented.
+    pub auth: ClientAuth,
+
+    /// Token endpoint for the client credentials grant (`bearer` only).
+    pub token_endpoint: Option<String>,
+
+    /// Scopes requested with the client credentials grant (`bearer` only).
+    pub scopes: Vec<String>,
+}
+
+/// `Debug` redacts `client_secret`.
+impl std::fmt::Debug for ClientConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientConfig")
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"[REDACTED]")
+            .field("auth", &self.auth)
+            .field("token_endpoint", &self.token_endpoint)
+            .field("scopes", &self.scopes)
+            .finish()
+    }
+}
+
+/// Introspection response fields mapped onto the `SecurityContext`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClaimMapping {
+    /// Subject ID field; must hold a UUID.
+    pub subject_id: String,
+
+    /// Subject home tenant field; must hold a UUID.
+    pub subject_tenant_id: String,
+
+    /// Optional subject type field.
+    pub subject_type: Option<String>,
+
+    /// Scopes field: a space-separated string or an array of strings.
+    pub scopes: String,
+}
+
+impl Default for ClaimMapping {
+    fn default() -> Self {
+        Self {
+            subject_id: "sub".to_owned(),
+            subject_tenant_id: "tenant_id".to_owned(),
+            subject_type: None,
+            scopes: "scope".to_owned(),
+        }
+    }
+}
+
+/// Introspection result cache settings.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    /// Enable the cache.
+    pub enabled: bool,
+
+    /// How long an active token's result is reused; never beyond its `exp`.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub ttl: Duration,
+
+    /// How long an inactive token stays rejected without asking the
+    /// endpoint again.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub negative_ttl: Duration,
+
+    /// Maximum number of cached tokens.
+    pub max_entries: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl: Duration::from_secs(60),
+            negative_ttl: Duration::from_secs(10),
+            max_entries: 10_000,
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_defaults_are_applied() {
+        let cfg: IntrospectionAuthNPluginConfig = serde_saphyr::from_str("{}").unwrap();
+
+        assert_eq!(cfg.vendor, "hyperspot");
+        assert_eq!(cfg.priority, 60);
+        assert_eq!(cfg.client.auth, ClientAuth::Basic);
+        assert_eq!(cfg.token_type_hint.as_deref(), Some("access_token"));
+        assert_eq!(cfg.claims.subject_id, "sub");
+        assert_eq!(cfg.claims.scopes, "scope");
+        assert!(cfg.cache.enabled);
+        assert_eq!(cfg.cache.negative_ttl, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn config_parses_bearer_client_and_cache() {
+        let yaml = r#"
+introspection_endpoint: "https://idp.example.com/oauth2/introspect"
+client:
+  client_id: "hyperspot"
+  client_secret: "${INTROSPECTION_SECRET}"
+  auth: bearer
+  token_endpoint: "https://idp.example.com/oauth2/token"
+  scopes: ["introspect"]
+claims:
+  subject_tenant_id: "org_id"
+cache:
+  ttl: "2m"
+  negative_ttl: "5s"
+"#;
+
+        let cfg: IntrospectionAuthNPluginConfig = serde_saphyr::from_str(yaml).unwrap();
+        assert_eq!(cfg.client.auth, ClientAuth::Bearer);
+        assert_eq!(
+            cfg.client.token_endpoint.as_deref(),
+            Some("https://idp.example.com/oauth2/token")
+        );
+        assert_eq!(cfg.claims.subject_tenant_id, "org_id");
+        assert_eq!(cfg.cache.ttl, Duration::from_secs(120));
+        assert_eq!(cfg.cache.negative_ttl, Duration::from_secs(5));
+        assert!(!format!("{:?}", cfg.client).contains("INTROSPECTION_SECRET"));
+    }
+
+    #[test]
+    fn config_rejects_unknown_fields() {
+        let yaml = r#"
+client:
+  client_id: "hyperspot"
+  password: "nope"
+"#;
+
+        let parsed: Result<IntrospectionAuthNPluginConfig, _> = serde_saphyr::from_str(yaml);
+        assert!(parsed.is_err());
+    }
+}
@@ -0,0 +1,18 @@
+//! Client implementation for the introspection `AuthN` resolver plugin.
+//!
+//! Implements `AuthNResolverPluginClient` using the domain service.
+
+use async_trait::async_trait;
+use authn_resolver_sdk::{AuthNResolverError, AuthNResolverPluginClient, AuthenticationResult};
+
+use super::service::Service;
+
+#[async_trait]
+impl AuthNResolverPluginClient for Service {
+    async fn authenticate(
+        &self,
+        bearer_token: &str,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        self.authenticate(bearer_token).await
+    }
+}
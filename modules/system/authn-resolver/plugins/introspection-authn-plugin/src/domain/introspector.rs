@@ -0,0 +1,27 @@
+//! Port to the token introspection endpoint.
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+/// Errors raised while introspecting a token.
+#[derive(Debug, thiserror::Error)]
+pub enum IntrospectionError {
+    /// The endpoint could not be reached or answered with an error status.
+    #[error("introspection endpoint unavailable: {0}")]
+    Unavailable(String),
+
+    /// The endpoint answered with something that is not an RFC 7662
+    /// response.
+    #[error("invalid introspection response: {0}")]
+    InvalidResponse(String),
+}
+
+/// Asks the authorization server about a token (RFC 7662).
+#[async_trait]
+pub trait TokenIntrospector: Send + Sync {
+    /// Introspect `token` and return the response object.
+    ///
+    /// The response is returned as-is; interpreting `active` and the other
+    /// members is up to the caller.
+    async fn introspect(&self, token: &str) -> Result<Value, IntrospectionError>;
+}
@@ -0,0 +1,7 @@
+//! Domain layer for the introspection `AuthN` resolver plugin.
+
+mod client;
+pub mod introspector;
+pub mod service;
+
+pub use service::{CachedOutcome, IntrospectionCache, Service};
@@ -0,0 +1,385 @@
+//! Service implementation for the introspection `AuthN` resolver plugin.
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use authn_resolver_sdk::{AuthNResolverError, AuthenticationResult};
+use modkit::cache::Cache;
+use modkit_auth::ClaimsError;
+use modkit_auth::validation::{extract_string, parse_timestamp, parse_uuid_from_value};
+use modkit_macros::domain_model;
+use modkit_security::SecurityContext;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+
+use super::introspector::{IntrospectionError, TokenIntrospector};
+use crate::config::ClaimMapping;
+
+/// A cached introspection outcome.
+#[derive(Debug, Clone)]
+pub enum CachedOutcome {
+    /// The token was active and mapped to this result.
+    Active(AuthenticationResult),
+    /// The token was inactive or unusable.
+    Inactive,
+}
+
+/// Introspection outcomes, keyed by the SHA-256 of the token so raw tokens
+/// are not kept as cache keys.
+pub type IntrospectionCache = Cache<[u8; 32], CachedOutcome>;
+
+/// Introspection `AuthN` resolver service.
+///
+/// Asks the authorization server whether a token is active and maps the
+/// introspection response to a `SecurityContext`.
+///
+/// Active tokens are cached for the earlier of the cache TTL and their
+/// `exp`; inactive tokens for the (shorter) negative TTL so a flood of bad
+/// tokens does not reach the endpoint. Endpoint failures are never cached.
+#[domain_model]
+pub struct Service {
+    introspector: Arc<dyn TokenIntrospector>,
+    claims: ClaimMapping,
+    cache: Option<IntrospectionCache>,
+    negative_ttl: Duration,
+}
+
+impl Service {
+    /// Create a service introspecting tokens through `introspector`.
+    #[must_use]
+    pub fn new(introspector: Arc<dyn TokenIntrospector>, claims: ClaimMapping) -> Self {
+        Self {
+            introspector,
+            claims,
+            cache: None,
+            negative_ttl: Duration::ZERO,
+        }
+    }
+
+    /// Cache outcomes in `cache`, remembering inactive tokens for
+    /// `negative_ttl`.
+    #[must_use]
+    pub fn with_cache(mut self, cache: IntrospectionCache, negative_ttl: Duration) -> Self {
+        self.cache = Some(cache);
+        self.negative_ttl = negative_ttl;
+        self
+    }
+
+    /// Authenticate a bearer token.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Unauthorized` if the token is inactive, expired or lacks the
+    /// mapped identity fields, `ServiceUnavailable` if the introspection
+    /// endpoint cannot be reached, and `Internal` if it answers with an
+    /// invalid response.
+    pub async fn authenticate(
+        &self,
+        bearer_token: &str,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        if bearer_token.is_empty() {
+            return Err(unauthorized("missing bearer token"));
+        }
+
+        let outcome = match &self.cache {
+            Some(cache) => {
+                let key = Sha256::digest(bearer_token.as_bytes()).into();
+                cache
+                    .get_or_try_insert_with_ttl(key, || async {
+                        let outcome = self.introspect(bearer_token).await?;
+                        let ttl = match &outcome {
+                            CachedOutcome::Active(result) => {
+                                result.expires_at.map_or(Duration::MAX, |expires_at| {
+                                    expires_at
+                                        .duration_since(SystemTime::now())
+                                        .unwrap_or(Duration::ZERO)
+                                })
+                            }
+                            CachedOutcome::Inactive => self.negative_ttl,
+                        };
+                        Ok((outcome, ttl))
+                    })
+                    .await?
+            }
+            None => self.introspect(bearer_token).await?,
+        };
+
+        match outcome {
+            CachedOutcome::Active(result) => Ok(result),
+            CachedOutcome::Inactive => Err(unauthorized("token is not active")),
+        }
+    }
+
+    /// Ask the endpoint about `bearer_token`; only endpoint failures are
+    /// errors.
+    async fn introspect(&self, bearer_token: &str) -> Result<CachedOutcome, AuthNResolverError> {
+        let response = self
+            .introspector
+            .introspect(bearer_token)
+            .await
+            .map_err(|e| match e {
+                IntrospectionError::Unavailable(reason) => {
+                    tracing::warn!(%reason, "Token introspection failed");
+                    AuthNResolverError::ServiceUnavailable(
+                        "token introspection unavailable".to_owned(),
+                    )
+                }
+                IntrospectionError::InvalidResponse(reason) => {
+                    tracing::error!(%reason, "Invalid token introspection response");
+                    AuthNResolverError::Internal("invalid token introspection response".to_owned())
+                }
+            })?;
+
+        if response.get("active").and_then(Value::as_bool) != Some(true) {
+            return Ok(CachedOutcome::Inactive);
+        }
+
+        match self.map_active(&response, bearer_token) {
+            Ok(result) => Ok(CachedOutcome::Active(result)),
+            Err(e) => {
+                tracing::debug!(error = %e, "Active token rejected");
+                Ok(CachedOutcome::Inactive)
+            }
+        }
+    }
+
+    /// Map an active response, recording when the token expires.
+    fn map_active(
+        &self,
+        response: &Value,
+        bearer_token: &str,
+    ) -> Result<AuthenticationResult, ClaimsError> {
+        let lifetime = match response.get("exp") {
+            Some(exp) => {
+                let remaining = parse_timestamp(exp, "exp")? - OffsetDateTime::now_utc();
+                if !remaining.is_positive() {
+                    return Err(ClaimsError::Expired);
+                }
+                Some(remaining.try_into().unwrap_or(Duration::ZERO))
+            }
+            None => None,
+        };
+
+        let security_context = build_security_context(response, &self.claims, bearer_token)?;
+        Ok(AuthenticationResult {
+            security_context,
+            expires_at: lifetime.map(|lifetime| SystemTime::now() + lifetime),
+        })
+    }
+}
+
+fn unauthorized(reason: &str) -> AuthNResolverError {
+    AuthNResolverError::Unauthorized(reason.to_owned())
+}
+
+fn required<'a>(response: &'a Value, field: &str) -> Result<&'a Value, ClaimsError> {
+    response
+        .get(field)
+        .ok_or_else(|| ClaimsError::MissingClaim(field.to_owned()))
+}
+
+/// Read scopes from a space-separated string (RFC 7662 `scope`) or an array
+/// of strings.
+fn parse_scopes(value: &Value, field: &str) -> Result<Vec<String>, ClaimsError> {
+    match value {
+        Value::String(s) => Ok(s.split_whitespace().map(ToOwned::to_owned).collect()),
+        Value::Array(items) => items.iter().map(|v| extract_string(v, field)).collect(),
+        _ => Err(ClaimsError::InvalidClaimFormat {
+            field: field.to_owned(),
+            reason: "must be a string or array of strings".to_owned(),
+        }),
+    }
+}
+
+fn build_security_context(
+    response: &Value,
+    mapping: &ClaimMapping,
+    bearer_token: &str,
+) -> Result<SecurityContext, ClaimsError> {
+    let subject_id = parse_uuid_from_value(
+        required(response, &mapping.subject_id)?,
+        &mapping.subject_id,
+    )?;
+    let subject_tenant_id = parse_uuid_from_value(
+        required(response, &mapping.subject_tenant_id)?,
+        &mapping.subject_tenant_id,
+    )?;
+
+    let mut builder = SecurityContext::builder()
+        .subject_id(subject_id)
+        .subject_tenant_id(subject_tenant_id)
+        .bearer_token(bearer_token.to_owned());
+
+    if let Some(field) = &mapping.subject_type
+        && let Some(value) = response.get(field)
+    {
+        builder = builder.subject_type(&extract_string(value, field)?);
+    }
+
+    if let Some(value) = response.get(&mapping.scopes) {
+        builder = builder.token_scopes(parse_scopes(value, &mapping.scopes)?);
+    }
+
+    builder
+        .build()
+        .map_err(|e| ClaimsError::Malformed(e.to_string()))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::*;
+
+    const SUBJECT: &str = "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa";
+    const TENANT: &str = "bbbbbbbb-bbbb-bbbb-bbbb-bbbbbbbbbbbb";
+
+    /// Answers from a fixed token table and counts calls.
+    #[derive(Default)]
+    struct FakeIntrospector {
+        responses: HashMap<&'static str, Value>,
+        calls: AtomicUsize,
+        unavailable: bool,
+    }
+
+    #[async_trait]
+    impl TokenIntrospector for FakeIntrospector {
+        async fn introspect(&self, token: &str) -> Result<Value, IntrospectionError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.unavailable {
+                return Err(IntrospectionError::Unavailable("timeout".to_owned()));
+            }
+            Ok(self
+                .responses
+                .get(token)
+                .cloned()
+                .unwrap_or_else(|| json!({ "active": false })))
+        }
+    }
+
+    fn active(exp_offset: i64) -> Value {
+        json!({
+            "active": true,
+            "sub": SUBJECT,
+            "tenant_id": TENANT,
+            "scope": "read write",
+            "exp": OffsetDateTime::now_utc().unix_timestamp() + exp_offset,
+        })
+    }
+
+    fn service(introspector: Arc<FakeIntrospector>) -> Service {
+        Service::new(introspector, ClaimMapping::default()).with_cache(
+            IntrospectionCache::new("test", Duration::from_secs(60), 100),
+            Duration::from_secs(60),
+        )
+    }
+
+    fn fake(responses: impl IntoIterator<Item = (&'static str, Value)>) -> Arc<FakeIntrospector> {
+        Arc::new(FakeIntrospector {
+            responses: responses.into_iter().collect(),
+            ..FakeIntrospector::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn active_token_maps_to_security_context() {
+        let service = service(fake([("tok", active(300))]));
+
+        let ctx = service.authenticate("tok").await.unwrap().security_context;
+        assert_eq!(ctx.subject_id(), Uuid::parse_str(SUBJECT).unwrap());
+        assert_eq!(ctx.subject_tenant_id(), Uuid::parse_str(TENANT).unwrap());
+        assert_eq!(ctx.token_scopes(), &["read", "write"]);
+    }
+
+    #[tokio::test]
+    async fn inactive_expired_and_unmappable_tokens_are_unauthorized() {
+        let mut no_tenant = active(300);
+        no_tenant.as_object_mut().unwrap().remove("tenant_id");
+        let service = service(fake([("expired", active(-5)), ("no-tenant", no_tenant)]));
+
+        for token in ["inactive", "expired", "no-tenant", ""] {
+            let err = service.authenticate(token).await.unwrap_err();
+            assert!(
+                matches!(err, AuthNResolverError::Unauthorized(_)),
+                "{token}: {err:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn both_outcomes_are_cached() {
+        let introspector = fake([("tok", active(300))]);
+        let service = service(introspector.clone());
+
+        for _ in 0..3 {
+            assert!(service.authenticate("tok").await.is_ok());
+            assert!(service.authenticate("bad").await.is_err());
+        }
+        assert_eq!(introspector.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn inactive_and_short_lived_outcomes_expire() {
+        let introspector = fake([("short", active(1))]);
+        let service = Service::new(introspector.clone(), ClaimMapping::default()).with_cache(
+            IntrospectionCache::new("test", Duration::from_secs(60), 100),
+            Duration::from_millis(20),
+        );
+
+        assert!(service.authenticate("bad").await.is_err());
+        assert!(service.authenticate("short").await.is_ok());
+        assert!(service.authenticate("bad").await.is_err());
+        assert_eq!(introspector.calls.load(Ordering::SeqCst), 2);
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        assert!(service.authenticate("bad").await.is_err());
+        assert!(service.authenticate("short").await.is_err());
+        assert_eq!(introspector.calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn endpoint_failures_are_unavailable_and_not_cached() {
+        let introspector = Arc::new(FakeIntrospector {
+            unavailable: true,
+            ..FakeIntrospector::default()
+        });
+        let service = service(introspector.clone());
+
+        for _ in 0..2 {
+            let err = service.authenticate("tok").await.unwrap_err();
+            assert!(matches!(err, AuthNResolverError::ServiceUnavailable(_)));
+        }
+        assert_eq!(introspector.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn custom_mapping_reads_configured_fields() {
+        let response = json!({
+            "active": true,
+            "uid": SUBJECT,
+            "org": TENANT,
+            "kind": "service",
+            "scp": ["a", "b"],
+        });
+        let service = Service::new(
+            fake([("tok", response)]),
+            ClaimMapping {
+                subject_id: "uid".to_owned(),
+                subject_tenant_id: "org".to_owned(),
+                subject_type: Some("kind".to_owned()),
+                scopes: "scp".to_owned(),
+            },
+        );
+
+        let ctx = service.authenticate("tok").await.unwrap().security_context;
+        assert_eq!(ctx.subject_type(), Some("service"));
+        assert_eq!(ctx.token_scopes(), &["a", "b"]);
+    }
+}
@@ -0,0 +1,3 @@
+//! Infrastructure for the introspection `AuthN` resolver plugin.
+
+pub mod http_introspector;
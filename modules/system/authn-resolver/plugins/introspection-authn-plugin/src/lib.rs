@@ -0,0 +1,35 @@
+//! Introspection `AuthN` Resolver Plugin
+//!
+//! Validates opaque bearer tokens by asking the authorization server's
+//! `OAuth2` token introspection endpoint (RFC 7662).
+//!
+//! - The plugin authenticates to the endpoint with its client credentials,
+//!   either directly (`basic`/`form`) or with a bearer token obtained
+//!   through the client credentials grant (`bearer`).
+//! - `active`, subject, tenant and scope fields of the response are mapped
+//!   onto the `SecurityContext`.
+//! - Active and inactive outcomes are cached by token hash, so most
+//!   requests do not reach the endpoint.
+//!
+//! ## Configuration
+//!
+//! ```yaml
+//! modules:
+//!   introspection_authn_plugin:
+//!     config:
+//!       vendor: "hyperspot"
+//!       priority: 60
+//!       introspection_endpoint: "https://idp.example.com/oauth2/introspect"
+//!       client:
+//!         client_id: "hyperspot"
+//!         client_secret: "${INTROSPECTION_CLIENT_SECRET}"
+//!         auth: basic
+//! ```
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod config;
+pub mod domain;
+pub mod infra;
+pub mod module;
+
+pub use module::IntrospectionAuthNPlugin;
@@ -0,0 +1,172 @@
+//! Introspection `AuthN` resolver plugin module.
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use authn_resolver_sdk::{AuthNResolverPluginClient, AuthNResolverPluginSpecV1};
+use modkit::Module;
+use modkit::client_hub::ClientScope;
+use modkit::context::ModuleCtx;
+use modkit::gts::BaseModkitPluginV1;
+use modkit_auth::{ClientAuthMethod, HttpClientBuilderExt, OAuthClientConfig, SecretString, Token};
+use tracing::info;
+use types_registry_sdk::{RegisterResult, TypesRegistryClient};
+
+use crate::config::{CacheConfig, ClientAuth, ClientConfig, IntrospectionAuthNPluginConfig};
+use crate::domain::{IntrospectionCache, Service};
+use crate::infra::http_introspector::{ClientCredentials, HttpIntrospector};
+
+/// Introspection `AuthN` resolver plugin module.
+///
+/// Validates opaque bearer tokens against an RFC 7662 introspection
+/// endpoint.
+#[modkit::module(
+    name = "introspection-authn-plugin",
+    deps = ["types-registry"]
+)]
+pub struct IntrospectionAuthNPlugin {
+    service: OnceLock<Arc<Service>>,
+}
+
+impl Default for IntrospectionAuthNPlugin {
+    fn default() -> Self {
+        Self {
+            service: OnceLock::new(),
+        }
+    }
+}
+
+/// Resolve `${VAR}` from the environment; other values are used verbatim.
+fn resolve_secret(value: &str) -> anyhow::Result<SecretString> {
+    match value.strip_prefix("${").and_then(|v| v.strip_suffix('}')) {
+        Some(var) => std::env::var(var).map(SecretString::new).map_err(|_| {
+            anyhow::anyhow!("environment variable '{var}' not found for client_secret")
+        }),
+        None => Ok(SecretString::new(value)),
+    }
+}
+
+/// Build the HTTP introspector, obtaining a client credentials token first
+/// in `bearer` mode.
+async fn build_introspector(
+    cfg: &IntrospectionAuthNPluginConfig,
+) -> anyhow::Result<HttpIntrospector> {
+    let ClientConfig {
+        client_id,
+        client_secret,
+        auth,
+        token_endpoint,
+        scopes,
+    } = &cfg.client;
+    if client_id.trim().is_empty() {
+        anyhow::bail!("client.client_id must be set");
+    }
+    let client_secret = resolve_secret(client_secret)?;
+
+    let mut builder = modkit_http::HttpClient::builder().timeout(cfg.http_timeout);
+    let credentials = match auth {
+        ClientAuth::Bearer => {
+            let token_endpoint = token_endpoint.as_deref().ok_or_else(|| {
+                anyhow::anyhow!("client.token_endpoint is required for bearer auth")
+            })?;
+            let token = Token::new(OAuthClientConfig {
+                token_endpoint: Some(url::Url::parse(token_endpoint)?),
+                client_id: client_id.clone(),
+                client_secret,
+                scopes: scopes.clone(),
+                ..OAuthClientConfig::default()
+            })
+            .await?;
+            builder = builder.with_bearer_auth(token);
+            None
+        }
+        ClientAuth::Basic | ClientAuth::Form => Some(ClientCredentials {
+            client_id: client_id.clone(),
+            client_secret,
+            method: if *auth == ClientAuth::Form {
+                ClientAuthMethod::Form
+            } else {
+                ClientAuthMethod::Basic
+            },
+        }),
+    };
+
+    Ok(HttpIntrospector::new(
+        builder.build()?,
+        cfg.introspection_endpoint.clone(),
+        credentials,
+        cfg.token_type_hint.clone(),
+    ))
+}
+
+fn introspection_cache(cfg: &CacheConfig) -> Option<IntrospectionCache> {
+    IntrospectionCache::from_config(
+        "introspection-authn-plugin.authenticate",
+        &modkit::cache::CacheConfig {
+            enabled: cfg.enabled,
+            ttl: cfg.ttl,
+            max_entries: cfg.max_entries,
+        },
+    )
+}
+
+#[async_trait]
+impl Module for IntrospectionAuthNPlugin {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        // Load configuration
+        let cfg: IntrospectionAuthNPluginConfig = ctx.config()?;
+        url::Url::parse(&cfg.introspection_endpoint).map_err(|e| {
+            anyhow::anyhow!("{}: invalid introspection_endpoint: {e}", Self::MODULE_NAME)
+        })?;
+
+        info!(
+            vendor = %cfg.vendor,
+            priority = cfg.priority,
+            endpoint = %cfg.introspection_endpoint,
+            client_auth = ?cfg.client.auth,
+            cache_enabled = cfg.cache.enabled,
+            "Loaded plugin configuration"
+        );
+
+        // Generate plugin instance ID
+        let instance_id = AuthNResolverPluginSpecV1::gts_make_instance_id(
+            "hyperspot.builtin.introspection_authn_resolver.plugin.v1",
+        );
+
+        // Create service from config
+        let introspector = build_introspector(&cfg).await?;
+        let mut service = Service::new(Arc::new(introspector), cfg.claims.clone());
+        if let Some(cache) = introspection_cache(&cfg.cache) {
+            service = service.with_cache(cache, cfg.cache.negative_ttl);
+        }
+        let service = Arc::new(service);
+        self.service
+            .set(service.clone())
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
+        // Register scoped client in ClientHub
+        let api: Arc<dyn AuthNResolverPluginClient> = service;
+        ctx.client_hub()
+            .register_scoped::<dyn AuthNResolverPluginClient>(
+                ClientScope::gts_id(&instance_id),
+                api,
+            );
+
+        // Register plugin instance in types-registry last, once the client it
+        // advertises is available
+        let registry = ctx.client_hub().get::<dyn TypesRegistryClient>()?;
+        let instance = BaseModkitPluginV1::<AuthNResolverPluginSpecV1> {
+            id: instance_id.clone(),
+            vendor: cfg.vendor.clone(),
+            priority: cfg.priority,
+            properties: AuthNResolverPluginSpecV1,
+        };
+        let instance_json = serde_json::to_value(&instance)?;
+
+        let results = registry.register(vec![instance_json]).await?;
+        RegisterResult::ensure_all_ok(&results)?;
+
+        info!(instance_id = %instance_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+//! Configuration for the JWT `AuthN` resolver plugin.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Plugin configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JwtAuthNPluginConfig {
+    /// Vendor name for GTS instance registration.
+    pub vendor: String,
+
+    /// Plugin priority (lower = higher priority).
+    pub priority: i16,
+
+    /// Trusted token issuers. A token whose `iss` is not listed is rejected.
+    pub issuers: Vec<IssuerConfig>,
+
+    /// Leeway in seconds for `exp` and `nbf` checks (clock skew).
+    pub leeway_seconds: i64,
+
+    /// Reject tokens without an `exp` claim.
+    pub require_exp: bool,
+
+    /// JWKS fetching and refresh.
+    pub jwks: JwksRefreshConfig,
+
+    /// Cache of successful validations.
+    pub cache: CacheConfig,
+}
+
+impl Default for JwtAuthNPluginConfig {
+    fn default() -> Self {
+        Self {
+            vendor: "hyperspot".to_owned(),
+            priority: 50,
+            issuers: Vec::new(),
+            leeway_seconds: 60,
+            require_exp: true,
+            jwks: JwksRefreshConfig::default(),
+            cache: CacheConfig::default(),
+        }
+    }
+}
+
+/// A trusted token issuer.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IssuerConfig {
+    /// Expected `iss` claim, e.g. `https://idp.example.com/realms/main`.
+    pub issuer: String,
+
+    /// JWKS endpoint. When omitted it is read from the issuer's
+    /// `/.well-known/openid-configuration` document at startup.
+    #[serde(default)]
+    pub jwks_uri: Option<String>,
+
+    /// Accepted `aud` values; at least one must match. Empty accepts any
+    /// audience.
+    #[serde(default)]
+    pub audiences: Vec<String>,
+
+    /// Where the identity fields are read from in this issuer's tokens.
+    #[serde(default)]
+    pub claims: ClaimMapping,
+}
+
+/// Claim paths mapped onto the `SecurityContext`.
+///
+/// A path is a claim name, or a dot-separated path into nested objects
+/// (`ext.tenant.id`). A claim whose name itself contains dots (such as a
+/// namespaced `https://example.com/tenant`) is matched by its full name first.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClaimMapping {
+    /// Subject ID claim; must hold a UUID.
+    pub subject_id: String,
+
+    /// Subject home tenant claim; must hold a UUID.
+    pub subject_tenant_id: String,
+
+    /// Optional subject type claim.
+    pub subject_type: Option<String>,
+
+    /// Optional scopes claim: a space-separated string or an array of strings.
+    pub scopes: Option<String>,
+}
+
+impl Default for ClaimMapping {
+    fn default() -> Self {
+        Self {
+            subject_id: "sub".to_owned(),
+            subject_tenant_id: "tenant_id".to_owned(),
+            subject_type: None,
+            scopes: Some("scope".to_owned()),
+        }
+    }
+}
+
+/// JWKS fetching and refresh settings, shared by all issuers.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JwksRefreshConfig {
+    /// Interval between background key refreshes.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub refresh_interval: Duration,
+
+    /// Upper bound of the retry backoff after failed refreshes.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub max_backoff: Duration,
+
+    /// Minimum delay between refreshes triggered by an unknown `kid`.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub on_demand_refresh_cooldown: Duration,
+
+    /// Timeout for JWKS and discovery requests.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub http_timeout: Duration,
+}
+
+impl Default for JwksRefreshConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval: Duration::from_secs(300),
+            max_backoff: Duration::from_secs(3600),
+            on_demand_refresh_cooldown: Duration::from_secs(60),
+            http_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Validation result cache settings.
+///
+/// Only successful validations are cached, never longer than the token
+/// lifetime.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    /// Enable the cache.
+    pub enabled: bool,
+
+    /// Maximum time a validation result is reused.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub ttl: Duration,
+
+    /// Maximum number of cached tokens.
+    pub max_entries: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl: Duration::from_secs(60),
+            max_entries: 10_000,
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_defaults_are_applied() {
+        let cfg: JwtAuthNPluginConfig = serde_saphyr::from_str("{}").unwrap();
+
+        assert_eq!(cfg.vendor, "hyperspot");
+        assert_eq!(cfg.priority, 50);
+        assert!(cfg.issuers.is_empty());
+        assert_eq!(cfg.leeway_seconds, 60);
+        assert!(cfg.require_exp);
+        assert_eq!(cfg.jwks.refresh_interval, Duration::from_secs(300));
+        assert!(cfg.cache.enabled);
+        assert_eq!(cfg.cache.ttl, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn config_parses_issuers_and_claim_mapping() {
+        let yaml = r#"
+issuers:
+  - issuer: "https://idp.example.com/realms/main"
+    audiences: ["hyperspot"]
+    claims:
+      subject_tenant_id: "ext.tenant"
+      subject_type: "typ"
+      scopes: "scp"
+  - issuer: "https://login.example.org"
+    jwks_uri: "https://login.example.org/keys"
+jwks:
+  refresh_interval: "10m"
+cache:
+  ttl: "30s"
+"#;
+
+        let cfg: JwtAuthNPluginConfig = serde_saphyr::from_str(yaml).unwrap();
+        assert_eq!(cfg.issuers.len(), 2);
+
+        let first = &cfg.issuers[0];
+        assert!(first.jwks_uri.is_none());
+        assert_eq!(first.audiences, vec!["hyperspot"]);
+        assert_eq!(first.claims.subject_id, "sub");
+        assert_eq!(first.claims.subject_tenant_id, "ext.tenant");
+        assert_eq!(first.claims.subject_type.as_deref(), Some("typ"));
+        assert_eq!(first.claims.scopes.as_deref(), Some("scp"));
+
+        let second = &cfg.issuers[1];
+        assert_eq!(
+            second.jwks_uri.as_deref(),
+            Some("https://login.example.org/keys")
+        );
+        assert_eq!(second.claims.subject_tenant_id, "tenant_id");
+
+        assert_eq!(cfg.jwks.refresh_interval, Duration::from_secs(600));
+        assert_eq!(cfg.jwks.max_backoff, Duration::from_secs(3600));
+        assert_eq!(cfg.cache.ttl, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn config_rejects_unknown_fields() {
+        let yaml = r#"
+issuers:
+  - issuer: "https://idp.example.com"
+    secret: "not supported"
+"#;
+
+        let parsed: Result<JwtAuthNPluginConfig, _> = serde_saphyr::from_str(yaml);
+        assert!(parsed.is_err());
+    }
+}
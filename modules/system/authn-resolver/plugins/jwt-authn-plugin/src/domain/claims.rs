@@ -0,0 +1,168 @@
+//! Mapping of validated token claims onto a `SecurityContext`.
+
+use modkit_auth::ClaimsError;
+use modkit_auth::validation::{extract_string, parse_uuid_from_value};
+use modkit_security::SecurityContext;
+use serde_json::Value;
+
+use crate::config::ClaimMapping;
+
+/// Look up `path` in `claims`.
+///
+/// The full path is tried as a top-level claim name first, so namespaced
+/// claims such as `https://example.com/tenant` work; otherwise the path is
+/// split on `.` and followed through nested objects.
+#[must_use]
+pub fn lookup<'a>(claims: &'a Value, path: &str) -> Option<&'a Value> {
+    if let Some(value) = claims.get(path) {
+        return Some(value);
+    }
+    path.split('.')
+        .try_fold(claims, |value, segment| value.get(segment))
+}
+
+fn required<'a>(claims: &'a Value, path: &str) -> Result<&'a Value, ClaimsError> {
+    lookup(claims, path).ok_or_else(|| ClaimsError::MissingClaim(path.to_owned()))
+}
+
+/// Read scopes from a space-separated string (`scope`) or an array of
+/// strings (`scp`).
+fn parse_scopes(value: &Value, path: &str) -> Result<Vec<String>, ClaimsError> {
+    match value {
+        Value::String(s) => Ok(s.split_whitespace().map(ToOwned::to_owned).collect()),
+        Value::Array(items) => items.iter().map(|v| extract_string(v, path)).collect(),
+        _ => Err(ClaimsError::InvalidClaimFormat {
+            field: path.to_owned(),
+            reason: "must be a string or array of strings".to_owned(),
+        }),
+    }
+}
+
+/// Build the security context for `bearer_token` from its validated claims.
+///
+/// # Errors
+///
+/// Returns an error if a required claim is missing or has the wrong format.
+pub fn build_security_context(
+    claims: &Value,
+    mapping: &ClaimMapping,
+    bearer_token: &str,
+) -> Result<SecurityContext, ClaimsError> {
+    let subject_id =
+        parse_uuid_from_value(required(claims, &mapping.subject_id)?, &mapping.subject_id)?;
+    let subject_tenant_id = parse_uuid_from_value(
+        required(claims, &mapping.subject_tenant_id)?,
+        &mapping.subject_tenant_id,
+    )?;
+
+    let mut builder = SecurityContext::builder()
+        .subject_id(subject_id)
+        .subject_tenant_id(subject_tenant_id)
+        .bearer_token(bearer_token.to_owned());
+
+    if let Some(path) = &mapping.subject_type
+        && let Some(value) = lookup(claims, path)
+    {
+        builder = builder.subject_type(&extract_string(value, path)?);
+    }
+
+    if let Some(path) = &mapping.scopes
+        && let Some(value) = lookup(claims, path)
+    {
+        builder = builder.token_scopes(parse_scopes(value, path)?);
+    }
+
+    builder
+        .build()
+        .map_err(|e| ClaimsError::Malformed(e.to_string()))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use secrecy::ExposeSecret;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::*;
+
+    const SUBJECT: &str = "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa";
+    const TENANT: &str = "bbbbbbbb-bbbb-bbbb-bbbb-bbbbbbbbbbbb";
+
+    #[test]
+    fn lookup_prefers_full_name_then_nested_path() {
+        let claims = json!({
+            "https://example.com/tenant": "flat",
+            "ext": { "tenant": { "id": "nested" } },
+        });
+
+        assert_eq!(
+            lookup(&claims, "https://example.com/tenant"),
+            Some(&json!("flat"))
+        );
+        assert_eq!(lookup(&claims, "ext.tenant.id"), Some(&json!("nested")));
+        assert!(lookup(&claims, "ext.missing").is_none());
+    }
+
+    #[test]
+    fn default_mapping_reads_standard_claims() {
+        let claims = json!({
+            "sub": SUBJECT,
+            "tenant_id": TENANT,
+            "scope": "read:data  write:data",
+        });
+
+        let ctx = build_security_context(&claims, &ClaimMapping::default(), "tok").unwrap();
+        assert_eq!(ctx.subject_id(), Uuid::parse_str(SUBJECT).unwrap());
+        assert_eq!(ctx.subject_tenant_id(), Uuid::parse_str(TENANT).unwrap());
+        assert_eq!(ctx.token_scopes(), &["read:data", "write:data"]);
+        assert!(ctx.subject_type().is_none());
+        assert_eq!(
+            ctx.bearer_token().map(ExposeSecret::expose_secret),
+            Some("tok")
+        );
+    }
+
+    #[test]
+    fn custom_mapping_reads_nested_claims() {
+        let mapping = ClaimMapping {
+            subject_id: "oid".to_owned(),
+            subject_tenant_id: "ext.tenant".to_owned(),
+            subject_type: Some("ext.kind".to_owned()),
+            scopes: Some("scp".to_owned()),
+        };
+        let claims = json!({
+            "oid": SUBJECT,
+            "ext": { "tenant": TENANT, "kind": "service" },
+            "scp": ["a", "b"],
+        });
+
+        let ctx = build_security_context(&claims, &mapping, "tok").unwrap();
+        assert_eq!(ctx.subject_type(), Some("service"));
+        assert_eq!(ctx.token_scopes(), &["a", "b"]);
+    }
+
+    #[test]
+    fn missing_or_malformed_identity_claims_are_rejected() {
+        let mapping = ClaimMapping::default();
+
+        let err = build_security_context(&json!({ "sub": SUBJECT }), &mapping, "tok").unwrap_err();
+        assert!(matches!(err, ClaimsError::MissingClaim(c) if c == "tenant_id"));
+
+        let err = build_security_context(
+            &json!({ "sub": "alice", "tenant_id": TENANT }),
+            &mapping,
+            "tok",
+        )
+        .unwrap_err();
+        assert!(matches!(err, ClaimsError::InvalidClaimFormat { field, .. } if field == "sub"));
+
+        let err = build_security_context(
+            &json!({ "sub": SUBJECT, "tenant_id": TENANT, "scope": 42 }),
+            &mapping,
+            "tok",
+        )
+        .unwrap_err();
+        assert!(matches!(err, ClaimsError::InvalidClaimFormat { field, .. } if field == "scope"));
+    }
+}
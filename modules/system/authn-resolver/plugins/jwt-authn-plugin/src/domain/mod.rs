@@ -0,0 +1,7 @@
+//! Domain layer for the JWT `AuthN` resolver plugin.
+
+pub mod claims;
+mod client;
+pub mod service;
+
+pub use service::{Issuer, Service, TokenCache};
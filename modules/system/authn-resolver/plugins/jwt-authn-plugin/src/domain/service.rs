@@ -0,0 +1,426 @@
+//! Service implementation for the JWT `AuthN` resolver plugin.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use authn_resolver_sdk::{AuthNResolverError, AuthenticationResult};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use modkit::cache::Cache;
+use modkit_auth::validation::parse_timestamp;
+use modkit_auth::{ClaimsError, KeyProvider, StandardClaim, ValidationConfig, validate_claims};
+use modkit_macros::domain_model;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+
+use super::claims::build_security_context;
+use crate::config::ClaimMapping;
+
+/// A trusted issuer: where its signing keys come from and how its tokens
+/// are checked and mapped.
+#[domain_model]
+pub struct Issuer {
+    /// Expected `iss` claim.
+    pub name: String,
+    /// Verifies token signatures, typically a `JwksKeyProvider`.
+    pub keys: Arc<dyn KeyProvider>,
+    /// Accepted audiences; empty accepts any.
+    pub audiences: Vec<String>,
+    /// Claim paths for the security context.
+    pub claims: ClaimMapping,
+}
+
+struct IssuerEntry {
+    keys: Arc<dyn KeyProvider>,
+    validation: ValidationConfig,
+    claims: ClaimMapping,
+}
+
+/// Successful validations, keyed by the SHA-256 of the token so raw tokens
+/// are not kept as cache keys.
+pub type TokenCache = Cache<[u8; 32], AuthenticationResult>;
+
+/// JWT `AuthN` resolver service.
+///
+/// Routes each token to its issuer by the (unverified) `iss` claim, verifies
+/// the signature with that issuer's keys, then checks issuer, audience,
+/// `exp` and `nbf` before mapping the claims to a `SecurityContext`.
+///
+/// Successful validations are cached for the earlier of the cache TTL and
+/// the token's `exp`. Failures are never cached: a token rejected because
+/// the issuer's keys were stale must succeed once they refresh.
+#[domain_model]
+pub struct Service {
+    issuers: HashMap<String, IssuerEntry>,
+    require_exp: bool,
+    cache: Option<TokenCache>,
+}
+
+impl Service {
+    /// Create a service trusting `issuers`.
+    #[must_use]
+    pub fn new(
+        issuers: Vec<Issuer>,
+        leeway_seconds: i64,
+        require_exp: bool,
+        cache: Option<TokenCache>,
+    ) -> Self {
+        let issuers = issuers
+            .into_iter()
+            .map(|i| {
+                let validation = ValidationConfig {
+                    allowed_issuers: vec![i.name.clone()],
+                    allowed_audiences: i.audiences,
+                    leeway_seconds,
+                };
+                let entry = IssuerEntry {
+                    keys: i.keys,
+                    validation,
+                    claims: i.claims,
+                };
+                (i.name, entry)
+            })
+            .collect();
+
+        Self {
+            issuers,
+            require_exp,
+            cache,
+        }
+    }
+
+    /// Authenticate a bearer token.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Unauthorized` if the token is malformed, from an untrusted
+    /// issuer, badly signed, expired or lacks the mapped identity claims, and
+    /// `ServiceUnavailable` if the issuer's keys cannot be fetched.
+    pub async fn authenticate(
+        &self,
+        bearer_token: &str,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        if bearer_token.is_empty() {
+            return Err(AuthNResolverError::Unauthorized(
+                "missing bearer token".to_owned(),
+            ));
+        }
+
+        let Some(cache) = &self.cache else {
+            return self.validate(bearer_token).await;
+        };
+        let key = Sha256::digest(bearer_token.as_bytes()).into();
+        cache
+            .get_or_try_insert_with_ttl(key, || async {
+                let result = self.validate(bearer_token).await?;
+                let lifetime = result.expires_at.map_or(Duration::MAX, |expires_at| {
+                    expires_at
+                        .duration_since(SystemTime::now())
+                        .unwrap_or(Duration::ZERO)
+                });
+                Ok((result, lifetime))
+            })
+            .await
+    }
+
+    async fn validate(
+        &self,
+        bearer_token: &str,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        let iss = unverified_issuer(bearer_token).ok_or_else(|| {
+            AuthNResolverError::Unauthorized("token has no readable issuer".to_owned())
+        })?;
+        let issuer = self.issuers.get(&iss).ok_or_else(|| {
+            tracing::debug!(issuer = %iss, "Rejected token from untrusted issuer");
+            AuthNResolverError::Unauthorized("untrusted token issuer".to_owned())
+        })?;
+
+        let (_, claims) = issuer
+            .keys
+            .validate_and_decode(bearer_token)
+            .await
+            .map_err(claims_err)?;
+        validate_claims(&claims, &issuer.validation).map_err(claims_err)?;
+
+        let lifetime = self.remaining_lifetime(&claims)?;
+        let security_context =
+            build_security_context(&claims, &issuer.claims, bearer_token).map_err(claims_err)?;
+        Ok(AuthenticationResult {
+            security_context,
+            expires_at: lifetime.map(|lifetime| SystemTime::now() + lifetime),
+        })
+    }
+
+    /// Time until the token's `exp`, enforcing its presence if required.
+    fn remaining_lifetime(&self, claims: &Value) -> Result<Option<Duration>, AuthNResolverError> {
+        let Some(exp) = claims.get(StandardClaim::EXP) else {
+            if self.require_exp {
+                return Err(claims_err(ClaimsError::MissingClaim(
+                    StandardClaim::EXP.to_owned(),
+                )));
+            }
+            return Ok(None);
+        };
+        let exp = parse_timestamp(exp, StandardClaim::EXP).map_err(claims_err)?;
+        let remaining = exp - OffsetDateTime::now_utc();
+        Ok(Some(remaining.try_into().unwrap_or(Duration::ZERO)))
+    }
+}
+
+/// Read `iss` from the token payload without verifying it.
+///
+/// Only used to pick the issuer whose keys verify the token; the claim is
+/// checked again after the signature.
+fn unverified_issuer(token: &str) -> Option<String> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+    let claims: Value = serde_json::from_slice(&bytes).ok()?;
+    claims
+        .get(StandardClaim::ISS)?
+        .as_str()
+        .map(ToOwned::to_owned)
+}
+
+fn claims_err(e: ClaimsError) -> AuthNResolverError {
+    match e {
+        ClaimsError::JwksFetchFailed(reason) => {
+            tracing::warn!(%reason, "Issuer keys unavailable");
+            AuthNResolverError::ServiceUnavailable("token issuer keys unavailable".to_owned())
+        }
+        other => {
+            tracing::debug!(error = %other, "Token rejected");
+            AuthNResolverError::Unauthorized(other.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use async_trait::async_trait;
+    use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use uuid::Uuid;
+
+    use super::*;
+
+    const ISSUER: &str = "https://idp.example.com";
+    const SUBJECT: &str = "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa";
+    const TENANT: &str = "bbbbbbbb-bbbb-bbbb-bbbb-bbbbbbbbbbbb";
+
+    /// HMAC-backed key provider standing in for a JWKS endpoint.
+    struct StaticKeys {
+        secret: &'static [u8],
+        calls: AtomicUsize,
+        unavailable: bool,
+    }
+
+    impl StaticKeys {
+        fn new(secret: &'static [u8]) -> Arc<Self> {
+            Arc::new(Self {
+                secret,
+                calls: AtomicUsize::new(0),
+                unavailable: false,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl KeyProvider for StaticKeys {
+        fn name(&self) -> &'static str {
+            "static"
+        }
+
+        async fn validate_and_decode(
+            &self,
+            token: &str,
+        ) -> Result<(jsonwebtoken::Header, Value), ClaimsError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.unavailable {
+                return Err(ClaimsError::JwksFetchFailed(
+                    "connection refused".to_owned(),
+                ));
+            }
+            let mut validation = Validation::new(Algorithm::HS256);
+            validation.validate_exp = false;
+            validation.validate_aud = false;
+            validation.set_required_spec_claims::<&str>(&[]);
+            let data = jsonwebtoken::decode::<Value>(
+                token,
+                &DecodingKey::from_secret(self.secret),
+                &validation,
+            )
+            .map_err(|_| ClaimsError::InvalidSignature)?;
+            Ok((data.header, data.claims))
+        }
+    }
+
+    fn sign(secret: &[u8], claims: &Value) -> String {
+        jsonwebtoken::encode(
+            &Header::new(Algorithm::HS256),
+            claims,
+            &EncodingKey::from_secret(secret),
+        )
+        .unwrap()
+    }
+
+    fn claims(exp_offset: i64) -> Value {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        json!({
+            "iss": ISSUER,
+            "aud": "hyperspot",
+            "sub": SUBJECT,
+            "tenant_id": TENANT,
+            "scope": "read write",
+            "exp": now + exp_offset,
+        })
+    }
+
+    fn service(keys: Arc<StaticKeys>, cache: Option<TokenCache>) -> Service {
+        Service::new(
+            vec![Issuer {
+                name: ISSUER.to_owned(),
+                keys,
+                audiences: vec!["hyperspot".to_owned()],
+                claims: ClaimMapping::default(),
+            }],
+            30,
+            true,
+            cache,
+        )
+    }
+
+    fn assert_unauthorized(result: Result<AuthenticationResult, AuthNResolverError>) {
+        match result {
+            Err(AuthNResolverError::Unauthorized(_)) => {}
+            other => panic!("expected Unauthorized, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn valid_token_maps_to_security_context() {
+        let service = service(StaticKeys::new(b"secret"), None);
+        let token = sign(b"secret", &claims(300));
+
+        let result = service.authenticate(&token).await.unwrap();
+        let ctx = result.security_context;
+        assert_eq!(ctx.subject_id(), Uuid::parse_str(SUBJECT).unwrap());
+        assert_eq!(ctx.subject_tenant_id(), Uuid::parse_str(TENANT).unwrap());
+        assert_eq!(ctx.token_scopes(), &["read", "write"]);
+    }
+
+    #[tokio::test]
+    async fn rejects_bad_signature_untrusted_issuer_and_garbage() {
+        let service = service(StaticKeys::new(b"secret"), None);
+
+        assert_unauthorized(service.authenticate(&sign(b"other", &claims(300))).await);
+
+        let mut foreign = claims(300);
+        foreign["iss"] = json!("https://evil.example.com");
+        assert_unauthorized(service.authenticate(&sign(b"secret", &foreign)).await);
+
+        assert_unauthorized(service.authenticate("not-a-jwt").await);
+        assert_unauthorized(service.authenticate("").await);
+    }
+
+    #[tokio::test]
+    async fn checks_audience_and_expiry_with_leeway() {
+        let service = service(StaticKeys::new(b"secret"), None);
+
+        let mut wrong_aud = claims(300);
+        wrong_aud["aud"] = json!("someone-else");
+        assert_unauthorized(service.authenticate(&sign(b"secret", &wrong_aud)).await);
+
+        assert_unauthorized(service.authenticate(&sign(b"secret", &claims(-120))).await);
+        // Expired 10s ago but within the 30s leeway.
+        assert!(
+            service
+                .authenticate(&sign(b"secret", &claims(-10)))
+                .await
+                .is_ok()
+        );
+
+        let mut no_exp = claims(0);
+        no_exp.as_object_mut().unwrap().remove("exp");
+        assert_unauthorized(service.authenticate(&sign(b"secret", &no_exp)).await);
+    }
+
+    #[tokio::test]
+    async fn key_fetch_failure_is_service_unavailable() {
+        let keys = Arc::new(StaticKeys {
+            secret: b"secret",
+            calls: AtomicUsize::new(0),
+            unavailable: true,
+        });
+        let service = service(keys, None);
+
+        let err = service
+            .authenticate(&sign(b"secret", &claims(300)))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AuthNResolverError::ServiceUnavailable(_)));
+    }
+
+    #[tokio::test]
+    async fn successful_validations_are_cached() {
+        let keys = StaticKeys::new(b"secret");
+        let service = service(
+            keys.clone(),
+            Some(TokenCache::new("test", Duration::from_secs(60), 100)),
+        );
+        let token = sign(b"secret", &claims(300));
+
+        service.authenticate(&token).await.unwrap();
+        service.authenticate(&token).await.unwrap();
+        assert_eq!(keys.calls.load(Ordering::SeqCst), 1);
+
+        let bad = sign(b"other", &claims(300));
+        assert!(service.authenticate(&bad).await.is_err());
+        assert!(service.authenticate(&bad).await.is_err());
+        assert_eq!(keys.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn routes_tokens_to_their_issuer() {
+        let other_issuer = "https://login.example.org";
+        let service = Service::new(
+            vec![
+                Issuer {
+                    name: ISSUER.to_owned(),
+                    keys: StaticKeys::new(b"first"),
+                    audiences: vec![],
+                    claims: ClaimMapping::default(),
+                },
+                Issuer {
+                    name: other_issuer.to_owned(),
+                    keys: StaticKeys::new(b"second"),
+                    audiences: vec![],
+                    claims: ClaimMapping {
+                        subject_tenant_id: "org".to_owned(),
+                        ..ClaimMapping::default()
+                    },
+                },
+            ],
+            0,
+            true,
+            None,
+        );
+
+        let mut second = claims(300);
+        second["iss"] = json!(other_issuer);
+        second["org"] = json!(TENANT);
+        second.as_object_mut().unwrap().remove("tenant_id");
+
+        assert!(
+            service
+                .authenticate(&sign(b"second", &second))
+                .await
+                .is_ok()
+        );
+        // Signed with the other issuer's key.
+        assert_unauthorized(service.authenticate(&sign(b"first", &second)).await);
+    }
+}
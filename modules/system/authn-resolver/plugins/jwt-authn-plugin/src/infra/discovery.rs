@@ -0,0 +1,109 @@
+//! OIDC discovery of an issuer's JWKS endpoint.
+
+use serde::Deserialize;
+
+/// Minimal subset of the `OpenID` Connect discovery document.
+#[derive(Deserialize)]
+struct OidcDiscoveryDoc {
+    issuer: String,
+    jwks_uri: String,
+}
+
+/// Resolve the JWKS endpoint of `issuer`.
+///
+/// Fetches `{issuer}/.well-known/openid-configuration` and returns its
+/// `jwks_uri`. The document's `issuer` must equal `issuer`, as required by
+/// OIDC Discovery §4.3.
+///
+/// # Errors
+///
+/// Returns an error if the document cannot be fetched or parsed, or names a
+/// different issuer.
+pub async fn discover_jwks_uri(
+    client: &modkit_http::HttpClient,
+    issuer: &str,
+) -> anyhow::Result<String> {
+    let url = format!(
+        "{}/.well-known/openid-configuration",
+        issuer.trim_end_matches('/')
+    );
+
+    let doc: OidcDiscoveryDoc = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("OIDC discovery for {issuer} failed: {e}"))?
+        .error_for_status()
+        .map_err(|e| anyhow::anyhow!("OIDC discovery for {issuer} failed: {e}"))?
+        .json()
+        .await
+        .map_err(|e| anyhow::anyhow!("invalid OIDC discovery document for {issuer}: {e}"))?;
+
+    if doc.issuer != issuer {
+        anyhow::bail!(
+            "OIDC discovery document for {issuer} names issuer {}",
+            doc.issuer
+        );
+    }
+    Ok(doc.jwks_uri)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use httpmock::prelude::*;
+
+    use super::*;
+
+    fn client() -> modkit_http::HttpClient {
+        modkit_http::HttpClientBuilder::with_config(modkit_http::HttpClientConfig::for_testing())
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn reads_jwks_uri_from_discovery_document() {
+        let server = MockServer::start();
+        let issuer = server.base_url();
+        let jwks_uri = format!("{issuer}/protocol/openid-connect/certs");
+        server.mock(|when, then| {
+            when.method(GET).path("/.well-known/openid-configuration");
+            then.status(200).json_body(serde_json::json!({
+                "issuer": issuer,
+                "jwks_uri": jwks_uri,
+                "token_endpoint": format!("{issuer}/token"),
+            }));
+        });
+
+        let discovered = discover_jwks_uri(&client(), &issuer).await.unwrap();
+        assert_eq!(discovered, jwks_uri);
+    }
+
+    #[tokio::test]
+    async fn rejects_issuer_mismatch_and_http_errors() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/.well-known/openid-configuration");
+            then.status(200).json_body(serde_json::json!({
+                "issuer": "https://evil.example.com",
+                "jwks_uri": "https://evil.example.com/keys",
+            }));
+        });
+        assert!(
+            discover_jwks_uri(&client(), &server.base_url())
+                .await
+                .is_err()
+        );
+
+        let missing = MockServer::start();
+        missing.mock(|when, then| {
+            when.any_request();
+            then.status(404);
+        });
+        assert!(
+            discover_jwks_uri(&client(), &missing.base_url())
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,3 @@
+//! Infrastructure for the JWT `AuthN` resolver plugin.
+
+pub mod discovery;
@@ -0,0 +1,35 @@
+//! JWT `AuthN` Resolver Plugin
+//!
+//! Production `AuthN` plugin validating bearer JWTs issued by one or more
+//! OIDC identity providers.
+//!
+//! - Signing keys come from each issuer's JWKS (configured, or found through
+//!   OIDC discovery) and are refreshed in the background.
+//! - Tokens are checked for signature, issuer, audience, `exp` and `nbf`,
+//!   with configurable clock-skew leeway.
+//! - Configurable claim paths map the token onto the `SecurityContext`
+//!   subject id, subject tenant, subject type and scopes.
+//! - Successful validations are cached for a bounded time.
+//!
+//! ## Configuration
+//!
+//! ```yaml
+//! modules:
+//!   jwt_authn_plugin:
+//!     config:
+//!       vendor: "hyperspot"
+//!       priority: 50
+//!       issuers:
+//!         - issuer: "https://idp.example.com/realms/main"
+//!           audiences: ["hyperspot"]
+//!           claims:
+//!             subject_tenant_id: "tenant_id"
+//! ```
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod config;
+pub mod domain;
+pub mod infra;
+pub mod module;
+
+pub use module::JwtAuthNPlugin;
@@ -0,0 +1,170 @@
+//! JWT `AuthN` resolver plugin module.
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use authn_resolver_sdk::{AuthNResolverPluginClient, AuthNResolverPluginSpecV1};
+use modkit::Module;
+use modkit::client_hub::ClientScope;
+use modkit::context::ModuleCtx;
+use modkit::gts::BaseModkitPluginV1;
+use modkit_auth::JwksKeyProvider;
+use modkit_auth::providers::jwks::run_jwks_refresh_task;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+use types_registry_sdk::{RegisterResult, TypesRegistryClient};
+
+use crate::config::{CacheConfig, JwksRefreshConfig, JwtAuthNPluginConfig};
+use crate::domain::{Issuer, Service, TokenCache};
+use crate::infra::discovery::discover_jwks_uri;
+
+/// JWT `AuthN` resolver plugin module.
+///
+/// Validates bearer JWTs against the configured issuers' JWKS and keeps the
+/// key sets fresh in the background while the module runs.
+#[modkit::module(
+    name = "jwt-authn-plugin",
+    deps = ["types-registry"],
+    capabilities = [stateful],
+    lifecycle(entry = "serve")
+)]
+pub struct JwtAuthNPlugin {
+    service: OnceLock<Arc<Service>>,
+    key_providers: OnceLock<Vec<Arc<JwksKeyProvider>>>,
+}
+
+impl Default for JwtAuthNPlugin {
+    fn default() -> Self {
+        Self {
+            service: OnceLock::new(),
+            key_providers: OnceLock::new(),
+        }
+    }
+}
+
+impl JwtAuthNPlugin {
+    /// Refresh every issuer's JWKS until `cancel` fires.
+    pub(crate) async fn serve(self: Arc<Self>, cancel: CancellationToken) -> anyhow::Result<()> {
+        let mut tasks = JoinSet::new();
+        for provider in self.key_providers.get().into_iter().flatten() {
+            tasks.spawn(run_jwks_refresh_task(provider.clone(), cancel.clone()));
+        }
+        while tasks.join_next().await.is_some() {}
+        Ok(())
+    }
+}
+
+async fn build_issuers(
+    cfg: &JwtAuthNPluginConfig,
+) -> anyhow::Result<(Vec<Issuer>, Vec<Arc<JwksKeyProvider>>)> {
+    let discovery_client = modkit_http::HttpClient::builder()
+        .timeout(cfg.jwks.http_timeout)
+        .build()?;
+
+    let mut issuers = Vec::with_capacity(cfg.issuers.len());
+    let mut providers = Vec::with_capacity(cfg.issuers.len());
+    for issuer in &cfg.issuers {
+        let jwks_uri = match &issuer.jwks_uri {
+            Some(uri) => uri.clone(),
+            None => discover_jwks_uri(&discovery_client, &issuer.issuer).await?,
+        };
+        info!(issuer = %issuer.issuer, %jwks_uri, "Trusting token issuer");
+
+        let provider = Arc::new(jwks_provider(&jwks_uri, &cfg.jwks)?);
+        providers.push(provider.clone());
+        issuers.push(Issuer {
+            name: issuer.issuer.clone(),
+            keys: provider,
+            audiences: issuer.audiences.clone(),
+            claims: issuer.claims.clone(),
+        });
+    }
+    Ok((issuers, providers))
+}
+
+fn jwks_provider(jwks_uri: &str, cfg: &JwksRefreshConfig) -> anyhow::Result<JwksKeyProvider> {
+    Ok(
+        JwksKeyProvider::with_http_timeout(jwks_uri, cfg.http_timeout)?
+            .with_refresh_interval(cfg.refresh_interval)
+            .with_max_backoff(cfg.max_backoff)
+            .with_on_demand_refresh_cooldown(cfg.on_demand_refresh_cooldown),
+    )
+}
+
+fn token_cache(cfg: &CacheConfig) -> Option<TokenCache> {
+    TokenCache::from_config(
+        "jwt-authn-plugin.authenticate",
+        &modkit::cache::CacheConfig {
+            enabled: cfg.enabled,
+            ttl: cfg.ttl,
+            max_entries: cfg.max_entries,
+        },
+    )
+}
+
+#[async_trait]
+impl Module for JwtAuthNPlugin {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        // Load configuration
+        let cfg: JwtAuthNPluginConfig = ctx.config()?;
+        if cfg.issuers.is_empty() {
+            anyhow::bail!(
+                "{}: at least one issuer must be configured",
+                Self::MODULE_NAME
+            );
+        }
+
+        info!(
+            vendor = %cfg.vendor,
+            priority = cfg.priority,
+            issuer_count = cfg.issuers.len(),
+            cache_enabled = cfg.cache.enabled,
+            "Loaded plugin configuration"
+        );
+
+        // Generate plugin instance ID
+        let instance_id = AuthNResolverPluginSpecV1::gts_make_instance_id(
+            "hyperspot.builtin.jwt_authn_resolver.plugin.v1",
+        );
+
+        // Register plugin instance in types-registry
+        let registry = ctx.client_hub().get::<dyn TypesRegistryClient>()?;
+        let instance = BaseModkitPluginV1::<AuthNResolverPluginSpecV1> {
+            id: instance_id.clone(),
+            vendor: cfg.vendor.clone(),
+            priority: cfg.priority,
+            properties: AuthNResolverPluginSpecV1,
+        };
+        let instance_json = serde_json::to_value(&instance)?;
+
+        let results = registry.register(vec![instance_json]).await?;
+        RegisterResult::ensure_all_ok(&results)?;
+
+        // Resolve issuers and create the service
+        let (issuers, providers) = build_issuers(&cfg).await?;
+        let service = Arc::new(Service::new(
+            issuers,
+            cfg.leeway_seconds,
+            cfg.require_exp,
+            token_cache(&cfg.cache),
+        ));
+        self.key_providers
+            .set(providers)
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+        self.service
+            .set(service.clone())
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
+        // Register scoped client in ClientHub
+        let api: Arc<dyn AuthNResolverPluginClient> = service;
+        ctx.client_hub()
+            .register_scoped::<dyn AuthNResolverPluginClient>(
+                ClientScope::gts_id(&instance_id),
+                api,
+            );
+
+        info!(instance_id = %instance_id);
+        Ok(())
+    }
+}
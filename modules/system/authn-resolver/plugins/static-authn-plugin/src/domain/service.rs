@@ -68,6 +68,7 @@ fn build_result(identity: &IdentityConfig, bearer_token: &str) -> Option<Authent
 
     Some(AuthenticationResult {
         security_context: ctx,
+        expires_at: None,
     })
 }
 
@@ -5,17 +5,25 @@
 //!
 //! ## Supported predicates
 //!
-//! Only `Eq` and `In` predicates are supported in the first iteration.
+//! | `op` | Meaning | Required PEP capability |
+//! |------|---------|-------------------------|
+//! | `eq` | `property = value` | — |
+//! | `in` | `property IN (values)` | — |
+//! | `in_tenant_subtree` | `property` is `root_tenant_id` or one of its descendants | `tenant_hierarchy` |
+//! | `in_group` | `property` is a member of one of `group_ids` | `group_membership` or `group_hierarchy` |
+//! | `in_group_subtree` | `property` is a member of `root_group_id` or a nested group | `group_hierarchy` |
 //!
-//! ## Future extensions
-//!
-//! Additional predicate types (`in_tenant_subtree`, `in_group`,
-//! `in_group_subtree`) are planned. See the authorization design document
-//! (`docs/arch/authorization/DESIGN.md`) for the full predicate taxonomy.
+//! The hierarchy predicates are evaluated by the PEP's database against local
+//! closure tables, so the PDP does not have to enumerate tenant or group IDs.
+//! A PDP must only return them when the request advertised the capability.
+//! See the authorization design document (`docs/arch/authorization/DESIGN.md`)
+//! for the full predicate taxonomy.
 
+use crate::models::BarrierMode;
 use crate::pep::IntoPropertyValue;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use uuid::Uuid;
 
 /// A constraint on a specific resource property.
 ///
@@ -28,7 +36,7 @@ pub struct Constraint {
     pub predicates: Vec<Predicate>,
 }
 
-/// A predicate comparing a resource property to a value.
+/// A predicate on a resource property.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "op", rename_all = "snake_case")]
 pub enum Predicate {
@@ -36,6 +44,26 @@ pub enum Predicate {
     Eq(EqPredicate),
     /// Set membership: `resource_property IN (values)`
     In(InPredicate),
+    /// Tenant subtree: `resource_property IN (descendants of root_tenant_id)`
+    InTenantSubtree(InTenantSubtreePredicate),
+    /// Group membership: `resource_property IN (members of group_ids)`
+    InGroup(InGroupPredicate),
+    /// Group subtree: `resource_property IN (members of root_group_id and nested groups)`
+    InGroupSubtree(InGroupSubtreePredicate),
+}
+
+impl Predicate {
+    /// The resource property this predicate constrains.
+    #[must_use]
+    pub fn property(&self) -> &str {
+        match self {
+            Self::Eq(p) => &p.property,
+            Self::In(p) => &p.property,
+            Self::InTenantSubtree(p) => &p.property,
+            Self::InGroup(p) => &p.property,
+            Self::InGroupSubtree(p) => &p.property,
+        }
+    }
 }
 
 /// Equality predicate: `property = value`.
@@ -84,6 +112,77 @@ impl InPredicate {
     }
 }
 
+/// Tenant subtree predicate: the tenant in `property` is `root_tenant_id`
+/// or one of its descendants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InTenantSubtreePredicate {
+    /// Resource property holding a tenant ID (typically `pep_properties::OWNER_TENANT_ID`).
+    pub property: String,
+    /// Root of the tenant subtree.
+    pub root_tenant_id: Uuid,
+    /// Whether traversal stops at self-managed tenants (default: `respect`).
+    #[serde(default)]
+    pub barrier_mode: BarrierMode,
+    /// Allowed descendant statuses (e.g., `["active"]`); empty means any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tenant_status: Vec<String>,
+}
+
+impl InTenantSubtreePredicate {
+    /// Create a subtree predicate that respects barriers and accepts any status.
+    #[must_use]
+    pub fn new(property: impl Into<String>, root_tenant_id: Uuid) -> Self {
+        Self {
+            property: property.into(),
+            root_tenant_id,
+            barrier_mode: BarrierMode::default(),
+            tenant_status: Vec::new(),
+        }
+    }
+}
+
+/// Group membership predicate: the resource in `property` belongs to one of
+/// `group_ids`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InGroupPredicate {
+    /// Resource property joined with group membership (typically `pep_properties::RESOURCE_ID`).
+    pub property: String,
+    /// Groups whose members match.
+    pub group_ids: Vec<Uuid>,
+}
+
+impl InGroupPredicate {
+    /// Create a group membership predicate.
+    #[must_use]
+    pub fn new(property: impl Into<String>, group_ids: impl IntoIterator<Item = Uuid>) -> Self {
+        Self {
+            property: property.into(),
+            group_ids: group_ids.into_iter().collect(),
+        }
+    }
+}
+
+/// Group subtree predicate: the resource in `property` belongs to
+/// `root_group_id` or any group nested below it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InGroupSubtreePredicate {
+    /// Resource property joined with group membership (typically `pep_properties::RESOURCE_ID`).
+    pub property: String,
+    /// Root of the group subtree.
+    pub root_group_id: Uuid,
+}
+
+impl InGroupSubtreePredicate {
+    /// Create a group subtree predicate.
+    #[must_use]
+    pub fn new(property: impl Into<String>, root_group_id: Uuid) -> Self {
+        Self {
+            property: property.into(),
+            root_group_id,
+        }
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -132,4 +231,39 @@ mod tests {
         let json_str = serde_json::to_string(&in_pred).unwrap();
         assert!(json_str.contains(r#""op":"in""#));
     }
+
+    #[test]
+    fn hierarchy_predicates_deserialize_with_defaults() {
+        let predicates: Vec<Predicate> = serde_json::from_value(json!([
+            {
+                "op": "in_tenant_subtree",
+                "property": "owner_tenant_id",
+                "root_tenant_id": "11111111-1111-1111-1111-111111111111"
+            },
+            {
+                "op": "in_group",
+                "property": "id",
+                "group_ids": ["22222222-2222-2222-2222-222222222222"]
+            },
+            {
+                "op": "in_group_subtree",
+                "property": "id",
+                "root_group_id": "22222222-2222-2222-2222-222222222222"
+            }
+        ]))
+        .unwrap();
+
+        let Predicate::InTenantSubtree(subtree) = &predicates[0] else {
+            panic!("expected in_tenant_subtree, got {:?}", predicates[0]);
+        };
+        assert_eq!(subtree.barrier_mode, BarrierMode::Respect);
+        assert!(subtree.tenant_status.is_empty());
+        assert!(matches!(predicates[1], Predicate::InGroup(_)));
+        assert!(matches!(predicates[2], Predicate::InGroupSubtree(_)));
+        assert_eq!(predicates[2].property(), pep_properties::RESOURCE_ID);
+
+        let json_str = serde_json::to_string(&predicates[0]).unwrap();
+        assert!(json_str.contains(r#""op":"in_tenant_subtree""#));
+        assert!(json_str.contains(r#""barrier_mode":"respect""#));
+    }
 }
@@ -4,6 +4,7 @@
 //!
 //! - [`AuthZResolverClient`] - Public API trait for consumers
 //! - [`AuthZResolverPluginClient`] - Plugin API trait for implementations
+//! - [`AuthZPolicyListener`] - Policy change notifications from plugins
 //! - [`EvaluationRequest`], [`EvaluationResponse`] - Evaluation models
 //! - [`Constraint`], [`Predicate`] - Constraint types
 //! - [`AuthZResolverError`] - Error types
@@ -52,7 +53,10 @@ pub mod plugin_api;
 
 // Re-export main types at crate root
 pub use api::AuthZResolverClient;
-pub use constraints::{Constraint, EqPredicate, InPredicate, Predicate};
+pub use constraints::{
+    Constraint, EqPredicate, InGroupPredicate, InGroupSubtreePredicate, InPredicate,
+    InTenantSubtreePredicate, Predicate,
+};
 pub use error::AuthZResolverError;
 pub use gts::AuthZResolverPluginSpecV1;
 pub use models::{
@@ -60,4 +64,4 @@ pub use models::{
     EvaluationResponse, EvaluationResponseContext, Resource, Subject, TenantContext, TenantMode,
 };
 pub use pep::{AccessRequest, EnforcerError, IntoPropertyValue, PolicyEnforcer, ResourceType};
-pub use plugin_api::AuthZResolverPluginClient;
+pub use plugin_api::{AuthZPolicyListener, AuthZResolverPluginClient};
@@ -11,17 +11,20 @@
 //! | true              | empty       | `ConstraintsRequiredButAbsent` |
 //! | true              | present     | Compile constraints → `AccessScope` |
 //!
-//! Unknown/unsupported properties fail that constraint (fail-closed).
+//! Unknown/unsupported properties fail that constraint (fail-closed), as do
+//! hierarchy predicates whose [`Capability`] the PEP did not advertise.
 //!
 //! When `require_constraints=false`, empty constraints are treated as
 //! `allow_all()` (legitimate PDP "yes, no row-level filtering"). When
 //! `require_constraints=true`, empty constraints are an error (fail-closed).
 //! If the PDP returns constraints regardless of the flag, they are compiled.
 
-use modkit_security::{AccessScope, ScopeConstraint, ScopeFilter, ScopeValue};
+use modkit_security::{
+    AccessScope, InTenantSubtreeScopeFilter, ScopeConstraint, ScopeFilter, ScopeValue,
+};
 
 use crate::constraints::{Constraint, Predicate};
-use crate::models::EvaluationResponse;
+use crate::models::{BarrierMode, Capability, EvaluationResponse};
 
 /// Error during constraint compilation.
 #[derive(Debug, thiserror::Error)]
@@ -63,6 +66,29 @@ pub fn compile_to_access_scope(
     response: &EvaluationResponse,
     require_constraints: bool,
     supported_properties: &[&str],
+) -> Result<AccessScope, ConstraintCompileError> {
+    compile_to_access_scope_with(response, require_constraints, supported_properties, &[])
+}
+
+/// Like [`compile_to_access_scope`], additionally accepting the hierarchy
+/// predicates enabled by `capabilities`:
+///
+/// | Predicate | Required capability |
+/// |-----------|---------------------|
+/// | `in_tenant_subtree` | `TenantHierarchy` |
+/// | `in_group` | `GroupMembership` or `GroupHierarchy` |
+/// | `in_group_subtree` | `GroupHierarchy` |
+///
+/// A predicate whose capability is missing fails its constraint (fail-closed).
+///
+/// # Errors
+///
+/// Same as [`compile_to_access_scope`].
+pub fn compile_to_access_scope_with(
+    response: &EvaluationResponse,
+    require_constraints: bool,
+    supported_properties: &[&str],
+    capabilities: &[Capability],
 ) -> Result<AccessScope, ConstraintCompileError> {
     // Step 1: Handle empty constraints based on require_constraints flag.
     if response.context.constraints.is_empty() {
@@ -77,7 +103,7 @@ pub fn compile_to_access_scope(
     let mut fail_reasons: Vec<String> = Vec::new();
 
     for constraint in &response.context.constraints {
-        match compile_constraint(constraint, supported_properties) {
+        match compile_constraint(constraint, supported_properties, capabilities) {
             Ok(sc) => constraints.push(sc),
             Err(reason) => {
                 tracing::warn!(
@@ -107,18 +133,25 @@ pub fn compile_to_access_scope(
 /// Compile a single PDP constraint into a `ScopeConstraint`.
 ///
 /// Each predicate becomes a `ScopeFilter`. If any predicate's property
-/// is not in `supported_properties`, the entire constraint fails (fail-closed).
+/// is not in `supported_properties`, or it needs a capability missing from
+/// `capabilities`, the entire constraint fails (fail-closed).
 fn compile_constraint(
     constraint: &Constraint,
     supported_properties: &[&str],
+    capabilities: &[Capability],
 ) -> Result<ScopeConstraint, String> {
     let mut filters = Vec::new();
 
     for predicate in &constraint.predicates {
-        let (property, filter) = match predicate {
+        let property = predicate.property();
+        if !supported_properties.contains(&property) {
+            return Err(format!("unsupported property: {property}"));
+        }
+
+        let filter = match predicate {
             Predicate::Eq(eq) => {
                 let value = json_to_scope_value(&eq.value)?;
-                (eq.property.as_str(), ScopeFilter::eq(&eq.property, value))
+                ScopeFilter::eq(property, value)
             }
             Predicate::In(p) => {
                 let values: Vec<ScopeValue> = p
@@ -126,20 +159,60 @@ fn compile_constraint(
                     .iter()
                     .map(json_to_scope_value)
                     .collect::<Result<_, _>>()?;
-                (p.property.as_str(), ScopeFilter::r#in(&p.property, values))
+                ScopeFilter::r#in(property, values)
+            }
+            Predicate::InTenantSubtree(p) => {
+                require_capability(
+                    capabilities,
+                    &[Capability::TenantHierarchy],
+                    "in_tenant_subtree",
+                )?;
+                let mut filter = InTenantSubtreeScopeFilter::new(property, p.root_tenant_id)
+                    .with_tenant_statuses(p.tenant_status.clone());
+                if p.barrier_mode == BarrierMode::Ignore {
+                    filter = filter.ignore_barriers();
+                }
+                ScopeFilter::InTenantSubtree(filter)
+            }
+            Predicate::InGroup(p) => {
+                require_capability(
+                    capabilities,
+                    &[Capability::GroupMembership, Capability::GroupHierarchy],
+                    "in_group",
+                )?;
+                ScopeFilter::in_group(property, p.group_ids.clone())
+            }
+            Predicate::InGroupSubtree(p) => {
+                require_capability(
+                    capabilities,
+                    &[Capability::GroupHierarchy],
+                    "in_group_subtree",
+                )?;
+                ScopeFilter::in_group_subtree(property, p.root_group_id)
             }
         };
 
-        if !supported_properties.contains(&property) {
-            return Err(format!("unsupported property: {property}"));
-        }
-
         filters.push(filter);
     }
 
     Ok(ScopeConstraint::new(filters))
 }
 
+/// Fail unless the PEP advertised any of `accepted`.
+fn require_capability(
+    capabilities: &[Capability],
+    accepted: &[Capability],
+    op: &str,
+) -> Result<(), String> {
+    if accepted.iter().any(|c| capabilities.contains(c)) {
+        Ok(())
+    } else {
+        Err(format!(
+            "predicate {op} requires an unadvertised capability"
+        ))
+    }
+}
+
 /// Convert a `serde_json::Value` to a `ScopeValue`.
 ///
 /// UUID strings are detected and stored as `ScopeValue::Uuid`;
@@ -167,7 +240,10 @@ fn json_to_scope_value(v: &serde_json::Value) -> Result<ScopeValue, String> {
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
     use super::*;
-    use crate::constraints::{EqPredicate, InPredicate};
+    use crate::constraints::{
+        EqPredicate, InGroupPredicate, InGroupSubtreePredicate, InPredicate,
+        InTenantSubtreePredicate,
+    };
     use crate::models::EvaluationResponseContext;
     use modkit_security::pep_properties;
     use serde_json::json;
@@ -505,4 +581,93 @@ mod tests {
             Err(ConstraintCompileError::AllConstraintsFailed { .. })
         ));
     }
+
+    // === Hierarchy Predicate Tests ===
+
+    fn single_constraint(predicates: Vec<Predicate>) -> EvaluationResponse {
+        EvaluationResponse {
+            decision: true,
+            context: EvaluationResponseContext {
+                constraints: vec![Constraint { predicates }],
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn tenant_subtree_compiles_with_capability() {
+        let mut predicate =
+            InTenantSubtreePredicate::new(pep_properties::OWNER_TENANT_ID, uuid(T1));
+        predicate.barrier_mode = BarrierMode::Ignore;
+        predicate.tenant_status = vec!["active".to_owned()];
+        let response = single_constraint(vec![Predicate::InTenantSubtree(predicate)]);
+
+        let scope = compile_to_access_scope_with(
+            &response,
+            true,
+            DEFAULT_PROPS,
+            &[Capability::TenantHierarchy],
+        )
+        .unwrap();
+
+        let ScopeFilter::InTenantSubtree(filter) = &scope.constraints()[0].filters()[0] else {
+            panic!("expected a tenant subtree filter");
+        };
+        assert_eq!(filter.root_tenant_id(), uuid(T1));
+        assert!(!filter.respect_barriers());
+        assert_eq!(filter.tenant_statuses(), ["active"]);
+    }
+
+    #[test]
+    fn hierarchy_predicates_without_capability_fail_closed() {
+        let response = single_constraint(vec![Predicate::InTenantSubtree(
+            InTenantSubtreePredicate::new(pep_properties::OWNER_TENANT_ID, uuid(T1)),
+        )]);
+        assert!(matches!(
+            compile_to_access_scope(&response, true, DEFAULT_PROPS),
+            Err(ConstraintCompileError::AllConstraintsFailed { .. })
+        ));
+
+        let response = single_constraint(vec![Predicate::InGroupSubtree(
+            InGroupSubtreePredicate::new(pep_properties::RESOURCE_ID, uuid(T2)),
+        )]);
+        assert!(matches!(
+            compile_to_access_scope_with(
+                &response,
+                true,
+                DEFAULT_PROPS,
+                &[Capability::GroupMembership],
+            ),
+            Err(ConstraintCompileError::AllConstraintsFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn group_hierarchy_implies_group_membership() {
+        let response = single_constraint(vec![
+            Predicate::In(InPredicate::new(
+                pep_properties::OWNER_TENANT_ID,
+                [uuid(T1)],
+            )),
+            Predicate::InGroup(InGroupPredicate::new(
+                pep_properties::RESOURCE_ID,
+                [uuid(T2)],
+            )),
+        ]);
+
+        let scope = compile_to_access_scope_with(
+            &response,
+            true,
+            DEFAULT_PROPS,
+            &[Capability::GroupHierarchy],
+        )
+        .unwrap();
+
+        let filters = scope.constraints()[0].filters();
+        assert!(matches!(filters[1], ScopeFilter::InGroup(_)));
+        assert_eq!(
+            scope.all_uuid_values_for(pep_properties::OWNER_TENANT_ID),
+            &[uuid(T1)]
+        );
+    }
 }
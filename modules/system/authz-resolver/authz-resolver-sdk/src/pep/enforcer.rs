@@ -21,7 +21,7 @@ use crate::models::{
     Action, BarrierMode, Capability, EvaluationRequest, EvaluationRequestContext, Resource,
     Subject, TenantContext, TenantMode,
 };
-use crate::pep::compiler::{ConstraintCompileError, compile_to_access_scope};
+use crate::pep::compiler::{ConstraintCompileError, compile_to_access_scope_with};
 
 /// Error from the PEP enforcement flow.
 #[derive(Debug, thiserror::Error)]
@@ -351,10 +351,11 @@ impl PolicyEnforcer {
             });
         }
 
-        Ok(compile_to_access_scope(
+        Ok(compile_to_access_scope_with(
             &response,
             require,
             resource.supported_properties,
+            &self.capabilities,
         )?)
     }
 }
@@ -373,7 +374,7 @@ mod tests {
     use async_trait::async_trait;
 
     use super::*;
-    use crate::constraints::{Constraint, InPredicate, Predicate};
+    use crate::constraints::{Constraint, InPredicate, InTenantSubtreePredicate, Predicate};
     use crate::models::{EvaluationResponse, EvaluationResponseContext};
     use modkit_security::pep_properties;
 
@@ -420,6 +421,35 @@ mod tests {
         }
     }
 
+    /// Mock that grants the context tenant's subtree, regardless of the
+    /// capabilities advertised by the PEP.
+    struct SubtreeMock;
+
+    #[async_trait]
+    impl AuthZResolverClient for SubtreeMock {
+        async fn evaluate(
+            &self,
+            req: EvaluationRequest,
+        ) -> Result<EvaluationResponse, AuthZResolverError> {
+            let root_id = req
+                .context
+                .tenant_context
+                .and_then(|tc| tc.root_id)
+                .unwrap_or_default();
+            Ok(EvaluationResponse {
+                decision: true,
+                context: EvaluationResponseContext {
+                    constraints: vec![Constraint {
+                        predicates: vec![Predicate::InTenantSubtree(
+                            InTenantSubtreePredicate::new(pep_properties::OWNER_TENANT_ID, root_id),
+                        )],
+                    }],
+                    ..Default::default()
+                },
+            })
+        }
+    }
+
     /// Mock that always returns `decision=false` with an optional deny reason.
     struct DenyMock {
         deny_reason: Option<crate::models::DenyReason>,
@@ -562,6 +592,25 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn access_scope_accepts_subtree_only_with_tenant_hierarchy() {
+        let ctx = test_ctx();
+        let request = AccessRequest::new().context_tenant_id(uuid(TENANT));
+
+        let result = enforcer(SubtreeMock)
+            .access_scope_with(&ctx, &TEST_RESOURCE, "list", None, &request)
+            .await;
+        assert!(matches!(result, Err(EnforcerError::CompileFailed(_))));
+
+        let scope = enforcer(SubtreeMock)
+            .with_capabilities(vec![Capability::TenantHierarchy])
+            .access_scope_with(&ctx, &TEST_RESOURCE, "list", None, &request)
+            .await
+            .expect("should succeed");
+        assert!(scope.constraints()[0].filters()[0].is_hierarchical());
+        assert!(scope.has_property(pep_properties::OWNER_TENANT_ID));
+    }
+
     #[tokio::test]
     async fn access_scope_with_for_create() {
         let e = enforcer(AllowAllMock);
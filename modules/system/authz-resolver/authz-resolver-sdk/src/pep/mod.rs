@@ -3,6 +3,7 @@
 //! - [`PolicyEnforcer`] — PEP object (build → evaluate → compile)
 //! - [`ResourceType`] — Static descriptor for a resource type + its supported properties
 //! - [`compile_to_access_scope`] — Low-level: compile evaluation response into `AccessScope`
+//!   ([`compile_to_access_scope_with`] also accepts hierarchy predicates)
 //! - [`IntoPropertyValue`] — Convert typed values into `serde_json::Value` for PDP requests
 
 use serde_json::Value;
@@ -11,7 +12,7 @@ use uuid::Uuid;
 pub mod compiler;
 pub mod enforcer;
 
-pub use compiler::{ConstraintCompileError, compile_to_access_scope};
+pub use compiler::{ConstraintCompileError, compile_to_access_scope, compile_to_access_scope_with};
 pub use enforcer::{AccessRequest, EnforcerError, PolicyEnforcer, ResourceType};
 
 /// Trait for types that can be converted into `serde_json::Value` for PDP
@@ -21,3 +21,13 @@ pub trait AuthZResolverPluginClient: Send + Sync {
         request: EvaluationRequest,
     ) -> Result<EvaluationResponse, AuthZResolverError>;
 }
+
+/// Policy change notifications from plugins to the gateway.
+///
+/// The gateway registers this trait in `ClientHub`. A plugin whose policies
+/// change at runtime calls it once the change is in effect, so cached
+/// decisions are not served until they expire.
+pub trait AuthZPolicyListener: Send + Sync {
+    /// Policies changed; earlier decisions may no longer hold.
+    fn policies_changed(&self);
+}
@@ -1,5 +1,6 @@
 //! Configuration for the `AuthZ` resolver.
 
+use modkit::cache::CacheConfig;
 use serde::Deserialize;
 
 /// Configuration.
@@ -8,12 +9,19 @@ use serde::Deserialize;
 pub struct AuthZResolverConfig {
     /// Vendor selector used to pick a plugin implementation.
     pub vendor: String,
+
+    /// Caching of evaluation responses, keyed by the full request (disabled
+    /// by default).
+    ///
+    /// Policy changes take effect once the cached decisions expire.
+    pub cache: CacheConfig,
 }
 
 impl Default for AuthZResolverConfig {
     fn default() -> Self {
         Self {
             vendor: "hyperspot".to_owned(),
+            cache: CacheConfig::default(),
         }
     }
 }
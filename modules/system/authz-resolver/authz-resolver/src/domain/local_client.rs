@@ -4,7 +4,8 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use authz_resolver_sdk::{
-    AuthZResolverClient, AuthZResolverError, EvaluationRequest, EvaluationResponse,
+    AuthZPolicyListener, AuthZResolverClient, AuthZResolverError, EvaluationRequest,
+    EvaluationResponse,
 };
 use modkit_macros::domain_model;
 
@@ -40,3 +41,10 @@ impl AuthZResolverClient for AuthZResolverLocalClient {
             .map_err(|e| log_and_convert("evaluate", e))
     }
 }
+
+impl AuthZPolicyListener for AuthZResolverLocalClient {
+    fn policies_changed(&self) {
+        tracing::debug!("AuthZ policies changed; clearing cache");
+        self.svc.invalidate_cache();
+    }
+}
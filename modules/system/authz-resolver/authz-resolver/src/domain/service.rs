@@ -6,6 +6,7 @@ use std::time::Duration;
 use authz_resolver_sdk::{
     AuthZResolverPluginClient, AuthZResolverPluginSpecV1, EvaluationRequest, EvaluationResponse,
 };
+use modkit::cache::{Cache, CacheConfig};
 use modkit::client_hub::{ClientHub, ClientScope};
 use modkit::plugins::{GtsPluginSelector, choose_plugin_instance};
 use modkit::telemetry::ThrottledLog;
@@ -19,22 +20,38 @@ use super::error::DomainError;
 const UNAVAILABLE_LOG_THROTTLE: Duration = Duration::from_secs(10);
 
 /// `AuthZ` resolver service.
+///
+/// With `cache.enabled`, responses are cached by the whole evaluation request,
+/// which includes the subject, so a decision is only reused for the same
+/// subject, action, resource and context.
 #[domain_model]
 pub struct Service {
     hub: Arc<ClientHub>,
     vendor: String,
     selector: GtsPluginSelector,
     unavailable_log_throttle: ThrottledLog,
+    cache: Option<Cache<String, EvaluationResponse>>,
 }
 
 impl Service {
     #[must_use]
-    pub fn new(hub: Arc<ClientHub>, vendor: String) -> Self {
+    pub fn new(hub: Arc<ClientHub>, vendor: String, cache: &CacheConfig) -> Self {
         Self {
             hub,
             vendor,
             selector: GtsPluginSelector::new(),
             unavailable_log_throttle: ThrottledLog::new(UNAVAILABLE_LOG_THROTTLE),
+            cache: Cache::from_config("authz-resolver.evaluate", cache),
+        }
+    }
+
+    /// Drop all cached evaluation responses.
+    ///
+    /// Called when a plugin reports a policy change: a policy may affect any
+    /// subject or resource, so entries are not invalidated selectively.
+    pub fn invalidate_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
         }
     }
 
@@ -100,8 +117,180 @@ impl Service {
     pub async fn evaluate(
         &self,
         request: EvaluationRequest,
+    ) -> Result<EvaluationResponse, DomainError> {
+        let Some((cache, key)) = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache_key(&request).map(|key| (cache, key)))
+        else {
+            return self.evaluate_uncached(request).await;
+        };
+        cache
+            .get_or_try_insert_with(key, || self.evaluate_uncached(request))
+            .await
+    }
+
+    async fn evaluate_uncached(
+        &self,
+        request: EvaluationRequest,
     ) -> Result<EvaluationResponse, DomainError> {
         let plugin = self.get_plugin().await?;
         plugin.evaluate(request).await.map_err(DomainError::from)
     }
 }
+
+/// Cache key for `request`: its JSON form with object keys sorted, so equal
+/// requests map to the same key whatever the order of their property maps.
+///
+/// `None` (no caching) if the request cannot be serialized.
+fn cache_key(request: &EvaluationRequest) -> Option<String> {
+    let mut value = serde_json::to_value(request).ok()?;
+    value.sort_all_objects();
+    Some(value.to_string())
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use authz_resolver_sdk::models::{
+        Action, EvaluationRequestContext, EvaluationResponseContext, Resource, Subject,
+    };
+    use authz_resolver_sdk::{AuthZPolicyListener, AuthZResolverError};
+    use types_registry_sdk::{GtsEntity, RegisterResult, TypesRegistryError};
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::domain::AuthZResolverLocalClient;
+
+    struct MockRegistry {
+        instance: GtsEntity,
+    }
+
+    #[async_trait]
+    impl TypesRegistryClient for MockRegistry {
+        async fn list(&self, _query: ListQuery) -> Result<Vec<GtsEntity>, TypesRegistryError> {
+            Ok(vec![self.instance.clone()])
+        }
+
+        async fn get(&self, gts_id: &str) -> Result<GtsEntity, TypesRegistryError> {
+            Err(TypesRegistryError::not_found(gts_id))
+        }
+
+        async fn register(
+            &self,
+            _entities: Vec<serde_json::Value>,
+        ) -> Result<Vec<RegisterResult>, TypesRegistryError> {
+            Ok(vec![])
+        }
+    }
+
+    /// Plugin that allows everything and counts its evaluations.
+    #[derive(Default)]
+    struct CountingPlugin {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl AuthZResolverPluginClient for CountingPlugin {
+        async fn evaluate(
+            &self,
+            _request: EvaluationRequest,
+        ) -> Result<EvaluationResponse, AuthZResolverError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(EvaluationResponse {
+                decision: true,
+                context: EvaluationResponseContext::default(),
+            })
+        }
+    }
+
+    fn cached_service(plugin: Arc<CountingPlugin>) -> Service {
+        let instance_id = AuthZResolverPluginSpecV1::gts_make_instance_id("test._.mock.v1");
+        let hub = Arc::new(ClientHub::default());
+        hub.register::<dyn TypesRegistryClient>(Arc::new(MockRegistry {
+            instance: GtsEntity {
+                id: Uuid::nil(),
+                gts_id: instance_id.to_string(),
+                segments: vec![],
+                is_schema: false,
+                content: serde_json::json!({
+                    "id": instance_id,
+                    "vendor": "hyperspot",
+                    "priority": 0,
+                    "properties": {}
+                }),
+                description: None,
+            },
+        }));
+        hub.register_scoped::<dyn AuthZResolverPluginClient>(
+            ClientScope::gts_id(&instance_id),
+            plugin,
+        );
+        let cache = CacheConfig {
+            enabled: true,
+            ..CacheConfig::default()
+        };
+        Service::new(hub, "hyperspot".to_owned(), &cache)
+    }
+
+    fn request(subject: u128, properties: &[(&str, u32)]) -> EvaluationRequest {
+        let properties: HashMap<String, serde_json::Value> = properties
+            .iter()
+            .map(|(k, v)| ((*k).to_owned(), serde_json::json!(v)))
+            .collect();
+        EvaluationRequest {
+            subject: Subject {
+                id: Uuid::from_u128(subject),
+                subject_type: None,
+                properties: HashMap::new(),
+            },
+            action: Action {
+                name: "read".to_owned(),
+            },
+            resource: Resource {
+                resource_type: "doc".to_owned(),
+                id: None,
+                properties,
+            },
+            context: EvaluationRequestContext {
+                tenant_context: None,
+                token_scopes: Vec::new(),
+                require_constraints: true,
+                capabilities: Vec::new(),
+                supported_properties: Vec::new(),
+                bearer_token: None,
+            },
+        }
+    }
+
+    #[test]
+    fn cache_key_ignores_property_order() {
+        let a = request(1, &[("a", 1), ("b", 2), ("c", 3), ("d", 4)]);
+        let b = request(1, &[("d", 4), ("c", 3), ("b", 2), ("a", 1)]);
+        assert_eq!(cache_key(&a), cache_key(&b));
+    }
+
+    #[test]
+    fn cache_key_separates_subjects() {
+        assert_ne!(cache_key(&request(1, &[])), cache_key(&request(2, &[])));
+    }
+
+    #[tokio::test]
+    async fn policy_change_drops_cached_decisions() {
+        let plugin = Arc::new(CountingPlugin::default());
+        let svc = Arc::new(cached_service(plugin.clone()));
+        let listener = AuthZResolverLocalClient::new(svc.clone());
+
+        svc.evaluate(request(1, &[])).await.unwrap();
+        svc.evaluate(request(1, &[])).await.unwrap();
+        assert_eq!(plugin.calls.load(Ordering::SeqCst), 1);
+
+        listener.policies_changed();
+        svc.evaluate(request(1, &[])).await.unwrap();
+        assert_eq!(plugin.calls.load(Ordering::SeqCst), 2);
+    }
+}
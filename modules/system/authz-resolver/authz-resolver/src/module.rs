@@ -3,7 +3,7 @@
 use std::sync::{Arc, OnceLock};
 
 use async_trait::async_trait;
-use authz_resolver_sdk::{AuthZResolverClient, AuthZResolverPluginSpecV1};
+use authz_resolver_sdk::{AuthZPolicyListener, AuthZResolverClient, AuthZResolverPluginSpecV1};
 use modkit::Module;
 use modkit::context::ModuleCtx;
 use modkit::contracts::SystemCapability;
@@ -75,14 +75,19 @@ impl Module for AuthZResolver {
 
         // Create service
         let hub = ctx.client_hub();
-        let svc = Arc::new(Service::new(hub, cfg.vendor));
+        let svc = Arc::new(Service::new(hub, cfg.vendor, &cfg.cache));
         self.service
             .set(svc.clone())
             .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
 
-        // Register client in ClientHub
-        let api: Arc<dyn AuthZResolverClient> = Arc::new(AuthZResolverLocalClient::new(svc));
+        // Register local client in ClientHub; plugins report policy changes
+        // through the same client.
+        let client = Arc::new(AuthZResolverLocalClient::new(svc));
+        let api: Arc<dyn AuthZResolverClient> = client.clone();
         ctx.client_hub().register::<dyn AuthZResolverClient>(api);
+        let listener: Arc<dyn AuthZPolicyListener> = client;
+        ctx.client_hub()
+            .register::<dyn AuthZPolicyListener>(listener);
 
         Ok(())
     }
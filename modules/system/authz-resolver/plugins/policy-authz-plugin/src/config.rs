@@ -0,0 +1,96 @@
+//! Configuration for the policy `AuthZ` resolver plugin.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::domain::PolicyDocument;
+
+/// Plugin configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolicyAuthZPluginConfig {
+    /// Vendor name for GTS instance registration.
+    pub vendor: String,
+
+    /// Plugin priority (lower = higher priority).
+    pub priority: i16,
+
+    /// Global policy, applied to every tenant.
+    pub policy: PolicyDocument,
+
+    /// Per-tenant policies stored in the module database.
+    pub database: DatabasePolicyConfig,
+}
+
+impl Default for PolicyAuthZPluginConfig {
+    fn default() -> Self {
+        Self {
+            vendor: "hyperspot".to_owned(),
+            priority: 50,
+            policy: PolicyDocument::default(),
+            database: DatabasePolicyConfig::default(),
+        }
+    }
+}
+
+/// Database policy source.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabasePolicyConfig {
+    /// Load policy documents from the `authz_policy` table.
+    pub enabled: bool,
+
+    /// How often stored documents are reloaded.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub reload_interval: Duration,
+}
+
+impl Default for DatabasePolicyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            reload_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_and_inline_policy() {
+        let cfg: PolicyAuthZPluginConfig = serde_saphyr::from_str(
+            r#"
+priority: 40
+policy:
+  roles:
+    - name: member
+      rules:
+        - resource_types: ["*"]
+          actions: ["get"]
+  bindings:
+    - role: member
+database:
+  enabled: true
+  reload_interval: "2m"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(cfg.vendor, "hyperspot");
+        assert_eq!(cfg.priority, 40);
+        assert_eq!(cfg.policy.roles.len(), 1);
+        assert!(cfg.database.enabled);
+        assert_eq!(cfg.database.reload_interval, Duration::from_secs(120));
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let result: Result<PolicyAuthZPluginConfig, _> =
+            serde_saphyr::from_str("policy: { rolez: [] }");
+        assert!(result.is_err());
+    }
+}
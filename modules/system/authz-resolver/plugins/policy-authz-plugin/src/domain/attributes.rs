@@ -0,0 +1,161 @@
+//! Request attributes available to conditions and row filters.
+//!
+//! | Attribute | Value |
+//! |-----------|-------|
+//! | `subject.id` | Subject UUID |
+//! | `subject.type` | Subject type, if any |
+//! | `subject.tenant_id` | Subject home tenant (`subject.properties.tenant_id`) |
+//! | `subject.properties.<key>` | Subject property |
+//! | `resource.type` | Resource type |
+//! | `resource.id` | Resource UUID, if any |
+//! | `resource.properties.<key>` | Resource property |
+//! | `action` | Action name |
+//! | `context.tenant_id` | Context tenant the request operates on |
+//! | `context.token_scopes` | Token scopes (array) |
+
+use authz_resolver_sdk::EvaluationRequest;
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::policy::operand_reference;
+
+const SUBJECT_PROPERTIES: &str = "subject.properties.";
+const RESOURCE_PROPERTIES: &str = "resource.properties.";
+
+/// `true` if `path` names an attribute listed in the module docs.
+#[must_use]
+pub fn is_known_attribute(path: &str) -> bool {
+    matches!(
+        path,
+        "subject.id"
+            | "subject.type"
+            | "subject.tenant_id"
+            | "resource.type"
+            | "resource.id"
+            | "action"
+            | "context.tenant_id"
+            | "context.token_scopes"
+    ) || path
+        .strip_prefix(SUBJECT_PROPERTIES)
+        .or_else(|| path.strip_prefix(RESOURCE_PROPERTIES))
+        .is_some_and(|key| !key.is_empty())
+}
+
+/// Attribute view of one evaluation request.
+pub struct Attributes<'a> {
+    request: &'a EvaluationRequest,
+    tenant_id: Uuid,
+}
+
+impl<'a> Attributes<'a> {
+    /// `tenant_id` is the context tenant resolved for the request.
+    #[must_use]
+    pub fn new(request: &'a EvaluationRequest, tenant_id: Uuid) -> Self {
+        Self { request, tenant_id }
+    }
+
+    /// Value of the attribute at `path`, if present.
+    #[must_use]
+    pub fn get(&self, path: &str) -> Option<Value> {
+        let subject = &self.request.subject;
+        let resource = &self.request.resource;
+        match path {
+            "subject.id" => Some(Value::String(subject.id.to_string())),
+            "subject.type" => subject.subject_type.clone().map(Value::String),
+            "subject.tenant_id" => subject.properties.get("tenant_id").cloned(),
+            "resource.type" => Some(Value::String(resource.resource_type.clone())),
+            "resource.id" => resource.id.map(|id| Value::String(id.to_string())),
+            "action" => Some(Value::String(self.request.action.name.clone())),
+            "context.tenant_id" => Some(Value::String(self.tenant_id.to_string())),
+            "context.token_scopes" => Some(Value::from(self.request.context.token_scopes.clone())),
+            _ => {
+                if let Some(key) = path.strip_prefix(SUBJECT_PROPERTIES) {
+                    subject.properties.get(key).cloned()
+                } else if let Some(key) = path.strip_prefix(RESOURCE_PROPERTIES) {
+                    resource.properties.get(key).cloned()
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Resolve an operand: `$attribute` references are looked up, anything
+    /// else is a literal.
+    #[must_use]
+    pub fn resolve(&self, operand: &Value) -> Option<Value> {
+        match operand_reference(operand) {
+            Some(path) => self.get(path),
+            None => Some(operand.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::collections::HashMap;
+
+    use authz_resolver_sdk::{Action, EvaluationRequestContext, Resource, Subject};
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn resolves_request_attributes_and_references() {
+        let subject_id = Uuid::from_u128(1);
+        let tenant_id = Uuid::from_u128(2);
+        let request = EvaluationRequest {
+            subject: Subject {
+                id: subject_id,
+                subject_type: Some("user".to_owned()),
+                properties: HashMap::from([("dept".to_owned(), json!("sales"))]),
+            },
+            action: Action {
+                name: "get".to_owned(),
+            },
+            resource: Resource {
+                resource_type: "doc".to_owned(),
+                id: None,
+                properties: HashMap::from([("status".to_owned(), json!("draft"))]),
+            },
+            context: EvaluationRequestContext {
+                tenant_context: None,
+                token_scopes: vec!["read".to_owned()],
+                require_constraints: true,
+                capabilities: vec![],
+                supported_properties: vec![],
+                bearer_token: None,
+            },
+        };
+        let attributes = Attributes::new(&request, tenant_id);
+
+        assert_eq!(attributes.get("subject.type"), Some(json!("user")));
+        assert_eq!(
+            attributes.get("subject.properties.dept"),
+            Some(json!("sales"))
+        );
+        assert_eq!(
+            attributes.get("resource.properties.status"),
+            Some(json!("draft"))
+        );
+        assert_eq!(
+            attributes.get("context.token_scopes"),
+            Some(json!(["read"]))
+        );
+        assert_eq!(attributes.get("resource.id"), None);
+        assert_eq!(
+            attributes.resolve(&json!("$subject.id")),
+            Some(json!(subject_id.to_string()))
+        );
+        assert_eq!(attributes.resolve(&json!(42)), Some(json!(42)));
+    }
+
+    #[test]
+    fn known_attributes() {
+        assert!(is_known_attribute("action"));
+        assert!(is_known_attribute("resource.properties.owner_id"));
+        assert!(!is_known_attribute("resource.properties."));
+        assert!(!is_known_attribute("subject.name"));
+    }
+}
@@ -0,0 +1,673 @@
+//! Policy evaluation.
+//!
+//! 1. The context tenant is resolved as in the static plugin: the request's
+//!    `TenantContext.root_id`, falling back to the subject's `tenant_id`.
+//! 2. Roles are collected from the bindings matching the subject, in the
+//!    global policy and in the policy of the context tenant.
+//! 3. Rules of those roles apply when the resource type, action and all
+//!    conditions match. Any applicable `deny` rule denies the request.
+//! 4. Every applicable `allow` rule contributes one constraint (the
+//!    constraints are `ORed`): `owner_tenant_id IN (tenant)` unless the rule
+//!    is not tenant-scoped, `ANDed` with the rule's row filters. Subtree
+//!    rules use `in_tenant_subtree` instead when the PEP supports it, so the
+//!    descendants of the tenant are never enumerated.
+
+use std::collections::{HashMap, HashSet};
+
+use authz_resolver_sdk::{
+    Capability, Constraint, DenyReason, EqPredicate, EvaluationRequest, EvaluationResponse,
+    EvaluationResponseContext, InGroupPredicate, InGroupSubtreePredicate, InPredicate,
+    InTenantSubtreePredicate, Predicate, TenantMode,
+};
+use modkit_security::pep_properties;
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::attributes::Attributes;
+use super::policy::{
+    Condition, Effect, PolicyDocument, PolicyError, Role, RoleBinding, RowFilter, Rule,
+    pattern_matches,
+};
+
+/// Deny code for requests no rule grants.
+pub const INSUFFICIENT_PERMISSIONS: &str =
+    "gts.x.core.errors.err.v1~x.authz.errors.insufficient_permissions.v1";
+
+/// Deny code for requests that cannot be evaluated.
+pub const INVALID_REQUEST: &str = "gts.x.core.errors.err.v1~x.authz.errors.invalid_request.v1";
+
+/// Token scope granting every scope.
+const ALL_SCOPES: &str = "*";
+
+/// Validated global policy and per-tenant policies.
+#[derive(Debug, Default)]
+pub struct PolicySet {
+    global: PolicyDocument,
+    tenants: HashMap<Uuid, PolicyDocument>,
+}
+
+impl PolicySet {
+    /// Build a policy set from the global document and tenant documents.
+    ///
+    /// Documents of the same tenant are merged; documents of the nil tenant
+    /// are merged into the global policy. Tenant bindings may reference
+    /// global roles, but tenant roles may not redefine them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PolicyError` if any document is invalid.
+    pub fn new(
+        global: PolicyDocument,
+        tenant_documents: impl IntoIterator<Item = (Uuid, PolicyDocument)>,
+    ) -> Result<Self, PolicyError> {
+        let mut global = global;
+        let mut tenants: HashMap<Uuid, PolicyDocument> = HashMap::new();
+        for (tenant_id, document) in tenant_documents {
+            let target = if tenant_id.is_nil() {
+                &mut global
+            } else {
+                tenants.entry(tenant_id).or_default()
+            };
+            target.roles.extend(document.roles);
+            target.bindings.extend(document.bindings);
+        }
+
+        global.validate(&HashSet::new())?;
+        let global_roles: HashSet<String> = global.roles.iter().map(|r| r.name.clone()).collect();
+        for document in tenants.values() {
+            document.validate(&global_roles)?;
+            if let Some(role) = document
+                .roles
+                .iter()
+                .find(|r| global_roles.contains(&r.name))
+            {
+                return Err(PolicyError::DuplicateRole(role.name.clone()));
+            }
+        }
+
+        Ok(Self { global, tenants })
+    }
+
+    /// Number of tenants with their own policy.
+    #[must_use]
+    pub fn tenant_count(&self) -> usize {
+        self.tenants.len()
+    }
+
+    /// Evaluate an authorization request.
+    #[must_use]
+    pub fn evaluate(&self, request: &EvaluationRequest) -> EvaluationResponse {
+        let Some(tenant_id) = resolve_tenant(request) else {
+            return deny(INVALID_REQUEST, "no valid context tenant");
+        };
+        let attributes = Attributes::new(request, tenant_id);
+        let documents: Vec<&PolicyDocument> = std::iter::once(&self.global)
+            .chain(self.tenants.get(&tenant_id))
+            .collect();
+
+        let roles = bound_roles(&documents, request, &attributes);
+        if roles.is_empty() {
+            return deny(INSUFFICIENT_PERMISSIONS, "no role is bound to the subject");
+        }
+
+        let applicable: Vec<(&Role, &Rule)> = roles
+            .iter()
+            .flat_map(|role| role.rules.iter().map(move |rule| (*role, rule)))
+            .filter(|(_, rule)| rule_applies(rule, request, &attributes))
+            .collect();
+
+        if let Some((role, rule)) = applicable.iter().find(|(_, r)| r.effect == Effect::Deny) {
+            return deny(
+                INSUFFICIENT_PERMISSIONS,
+                &format!(
+                    "denied by rule '{}' of role '{}'",
+                    rule.id.as_deref().unwrap_or("<unnamed>"),
+                    role.name
+                ),
+            );
+        }
+
+        let supported = &request.context.supported_properties;
+        let constraints: Vec<Constraint> = applicable
+            .iter()
+            .filter(|(_, rule)| rule.effect == Effect::Allow)
+            .filter_map(|(_, rule)| build_constraint(rule, tenant_id, request, &attributes))
+            .filter(|c| {
+                supported.is_empty()
+                    || c.predicates
+                        .iter()
+                        .all(|p| supported.iter().any(|s| s == p.property()))
+            })
+            .collect();
+
+        if constraints.is_empty() {
+            return deny(
+                INSUFFICIENT_PERMISSIONS,
+                &format!(
+                    "no rule grants '{}' on '{}'",
+                    request.action.name, request.resource.resource_type
+                ),
+            );
+        }
+
+        // An unrestricted grant subsumes every other constraint.
+        let constraints = if constraints.iter().any(|c| c.predicates.is_empty()) {
+            vec![Constraint { predicates: vec![] }]
+        } else {
+            constraints
+        };
+
+        EvaluationResponse {
+            decision: true,
+            context: EvaluationResponseContext {
+                constraints,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+fn deny(error_code: &str, details: &str) -> EvaluationResponse {
+    EvaluationResponse {
+        decision: false,
+        context: EvaluationResponseContext {
+            deny_reason: Some(DenyReason {
+                error_code: error_code.to_owned(),
+                details: Some(details.to_owned()),
+            }),
+            ..Default::default()
+        },
+    }
+}
+
+fn subject_tenant(request: &EvaluationRequest) -> Option<Uuid> {
+    request
+        .subject
+        .properties
+        .get("tenant_id")
+        .and_then(Value::as_str)
+        .and_then(|s| Uuid::parse_str(s).ok())
+}
+
+fn resolve_tenant(request: &EvaluationRequest) -> Option<Uuid> {
+    request
+        .context
+        .tenant_context
+        .as_ref()
+        .and_then(|t| t.root_id)
+        .or_else(|| subject_tenant(request))
+        .filter(|id| !id.is_nil())
+}
+
+/// Roles bound to the subject, each at most once.
+fn bound_roles<'a>(
+    documents: &[&'a PolicyDocument],
+    request: &EvaluationRequest,
+    attributes: &Attributes<'_>,
+) -> Vec<&'a Role> {
+    let mut names = HashSet::new();
+    documents
+        .iter()
+        .flat_map(|d| d.bindings.iter())
+        .filter(|b| binding_matches(b, request, attributes))
+        .filter(|b| names.insert(b.role.as_str()))
+        .filter_map(|b| {
+            documents
+                .iter()
+                .flat_map(|d| d.roles.iter())
+                .find(|r| r.name == b.role)
+        })
+        .collect()
+}
+
+fn binding_matches(
+    binding: &RoleBinding,
+    request: &EvaluationRequest,
+    attributes: &Attributes<'_>,
+) -> bool {
+    let subject = &request.subject;
+    let token_scopes = &request.context.token_scopes;
+
+    (binding.subject_ids.is_empty() || binding.subject_ids.contains(&subject.id))
+        && (binding.subject_types.is_empty()
+            || subject
+                .subject_type
+                .as_ref()
+                .is_some_and(|t| binding.subject_types.contains(t)))
+        && (binding.tenant_ids.is_empty()
+            || subject_tenant(request).is_some_and(|t| binding.tenant_ids.contains(&t)))
+        && (binding.token_scopes.is_empty()
+            || token_scopes
+                .iter()
+                .any(|s| s == ALL_SCOPES || binding.token_scopes.contains(s)))
+        && binding.conditions.iter().all(|c| holds(c, attributes))
+}
+
+fn rule_applies(rule: &Rule, request: &EvaluationRequest, attributes: &Attributes<'_>) -> bool {
+    rule.resource_types
+        .iter()
+        .any(|p| pattern_matches(p, &request.resource.resource_type))
+        && rule
+            .actions
+            .iter()
+            .any(|p| pattern_matches(p, &request.action.name))
+        && rule.conditions.iter().all(|c| holds(c, attributes))
+}
+
+/// Equality that treats UUID strings case-insensitively and arrays as
+/// containing `expected`.
+fn value_matches(actual: &Value, expected: &Value) -> bool {
+    let same = |a: &Value| {
+        a == expected
+            || matches!(
+                (a.as_str().map(Uuid::parse_str), expected.as_str().map(Uuid::parse_str)),
+                (Some(Ok(x)), Some(Ok(y))) if x == y
+            )
+    };
+    match actual {
+        Value::Array(items) => items.iter().any(same),
+        _ => same(actual),
+    }
+}
+
+fn holds(condition: &Condition, attributes: &Attributes<'_>) -> bool {
+    let actual = |attribute: &str| attributes.get(attribute).filter(|v| !v.is_null());
+    match condition {
+        Condition::Eq { attribute, value } => actual(attribute)
+            .zip(attributes.resolve(value))
+            .is_some_and(|(a, e)| value_matches(&a, &e)),
+        Condition::Ne { attribute, value } => actual(attribute)
+            .zip(attributes.resolve(value))
+            .is_some_and(|(a, e)| !value_matches(&a, &e)),
+        Condition::In { attribute, values } => actual(attribute).is_some_and(|a| {
+            values
+                .iter()
+                .filter_map(|v| attributes.resolve(v))
+                .any(|e| value_matches(&a, &e))
+        }),
+        Condition::Exists { attribute } => actual(attribute).is_some(),
+    }
+}
+
+/// Resolve operands, flattening arrays; `None` if any is missing.
+fn resolve_all(values: &[Value], attributes: &Attributes<'_>) -> Option<Vec<Value>> {
+    let mut resolved = Vec::with_capacity(values.len());
+    for value in values {
+        match attributes.resolve(value)? {
+            Value::Array(items) => resolved.extend(items),
+            Value::Null => return None,
+            other => resolved.push(other),
+        }
+    }
+    Some(resolved)
+}
+
+fn as_uuid(value: &Value) -> Option<Uuid> {
+    value.as_str().and_then(|s| Uuid::parse_str(s).ok())
+}
+
+/// The tenant predicate of a tenant-scoped rule.
+fn tenant_predicate(rule: &Rule, tenant_id: Uuid, request: &EvaluationRequest) -> Predicate {
+    let context = request.context.tenant_context.clone().unwrap_or_default();
+    let subtree = rule.tenant_subtree
+        && context.mode == TenantMode::Subtree
+        && request
+            .context
+            .capabilities
+            .contains(&Capability::TenantHierarchy);
+    if subtree {
+        let mut predicate =
+            InTenantSubtreePredicate::new(pep_properties::OWNER_TENANT_ID, tenant_id);
+        predicate.barrier_mode = context.barrier_mode;
+        predicate.tenant_status = context.tenant_status.unwrap_or_default();
+        Predicate::InTenantSubtree(predicate)
+    } else {
+        Predicate::In(InPredicate::new(
+            pep_properties::OWNER_TENANT_ID,
+            [tenant_id],
+        ))
+    }
+}
+
+/// The rule's constraint, or `None` if a filter operand is missing or the
+/// PEP cannot evaluate one of its filters.
+fn build_constraint(
+    rule: &Rule,
+    tenant_id: Uuid,
+    request: &EvaluationRequest,
+    attributes: &Attributes<'_>,
+) -> Option<Constraint> {
+    let scalar = |v: &Value| {
+        attributes
+            .resolve(v)
+            .filter(|v| !v.is_null() && !v.is_array())
+    };
+    let supports = |capability: Capability| request.context.capabilities.contains(&capability);
+
+    let mut predicates = Vec::with_capacity(rule.filters.len() + 1);
+    if rule.tenant_scoped {
+        predicates.push(tenant_predicate(rule, tenant_id, request));
+    }
+    for filter in &rule.filters {
+        predicates.push(match filter {
+            RowFilter::Eq { property, value } => Predicate::Eq(EqPredicate {
+                property: property.clone(),
+                value: scalar(value)?,
+            }),
+            RowFilter::In { property, values } => Predicate::In(InPredicate {
+                property: property.clone(),
+                values: resolve_all(values, attributes)?,
+            }),
+            RowFilter::InGroup {
+                property,
+                group_ids,
+            } => {
+                if !supports(Capability::GroupMembership) && !supports(Capability::GroupHierarchy) {
+                    return None;
+                }
+                let group_ids = resolve_all(group_ids, attributes)?
+                    .iter()
+                    .map(as_uuid)
+                    .collect::<Option<Vec<_>>>()?;
+                Predicate::InGroup(InGroupPredicate::new(property.clone(), group_ids))
+            }
+            RowFilter::InGroupSubtree {
+                property,
+                root_group_id,
+            } => {
+                if !supports(Capability::GroupHierarchy) {
+                    return None;
+                }
+                let root_group_id = as_uuid(&scalar(root_group_id)?)?;
+                Predicate::InGroupSubtree(InGroupSubtreePredicate::new(
+                    property.clone(),
+                    root_group_id,
+                ))
+            }
+        });
+    }
+    Some(Constraint { predicates })
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use authz_resolver_sdk::pep::IntoPropertyValue;
+    use authz_resolver_sdk::pep::compiler::compile_to_access_scope;
+    use authz_resolver_sdk::{Action, EvaluationRequestContext, Resource, Subject, TenantContext};
+    use serde_json::json;
+
+    use super::*;
+
+    const DOC: &str = "gts.x.docs.document.v1~";
+
+    fn subject() -> Uuid {
+        Uuid::from_u128(0x11)
+    }
+
+    fn tenant() -> Uuid {
+        Uuid::from_u128(0x22)
+    }
+
+    fn request(action: &str, scopes: &[&str]) -> EvaluationRequest {
+        EvaluationRequest {
+            subject: Subject {
+                id: subject(),
+                subject_type: Some("user".to_owned()),
+                properties: HashMap::from([("tenant_id".to_owned(), json!(tenant().to_string()))]),
+            },
+            action: Action {
+                name: action.to_owned(),
+            },
+            resource: Resource {
+                resource_type: DOC.to_owned(),
+                id: None,
+                properties: HashMap::new(),
+            },
+            context: EvaluationRequestContext {
+                tenant_context: Some(TenantContext {
+                    root_id: Some(tenant()),
+                    ..TenantContext::default()
+                }),
+                token_scopes: scopes.iter().map(|s| (*s).to_owned()).collect(),
+                require_constraints: true,
+                capabilities: vec![],
+                supported_properties: vec![],
+                bearer_token: None,
+            },
+        }
+    }
+
+    /// Members read, owners write their own documents, nobody deletes
+    /// archived documents.
+    fn policy() -> PolicySet {
+        let global: PolicyDocument = serde_saphyr::from_str(
+            r#"
+roles:
+  - name: member
+    rules:
+      - resource_types: ["gts.x.docs.*"]
+        actions: ["list", "get"]
+  - name: owner
+    rules:
+      - id: owners-write
+        resource_types: ["gts.x.docs.*"]
+        actions: ["update", "delete"]
+        filters:
+          - { op: eq, property: owner_id, value: "$subject.id" }
+      - id: keep-archived
+        effect: deny
+        resource_types: ["*"]
+        actions: ["delete"]
+        conditions:
+          - { op: eq, attribute: resource.properties.status, value: archived }
+bindings:
+  - role: member
+    subject_types: ["user"]
+  - role: owner
+    token_scopes: ["docs:write"]
+"#,
+        )
+        .unwrap();
+        PolicySet::new(global, []).unwrap()
+    }
+
+    fn reason(response: &EvaluationResponse) -> &DenyReason {
+        response.context.deny_reason.as_ref().unwrap()
+    }
+
+    #[test]
+    fn members_read_within_their_tenant() {
+        let response = policy().evaluate(&request("list", &[]));
+
+        assert!(response.decision);
+        let [constraint] = response.context.constraints.as_slice() else {
+            panic!("expected one constraint");
+        };
+        match constraint.predicates.as_slice() {
+            [Predicate::In(p)] => {
+                assert_eq!(p.property, pep_properties::OWNER_TENANT_ID);
+                assert_eq!(p.values, vec![tenant().into_filter_value()]);
+            }
+            other => panic!("unexpected predicates: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn owners_write_only_their_own_rows() {
+        let policy = policy();
+
+        let denied = policy.evaluate(&request("update", &[]));
+        assert!(!denied.decision);
+        assert_eq!(reason(&denied).error_code, INSUFFICIENT_PERMISSIONS);
+
+        let granted = policy.evaluate(&request("update", &["docs:write"]));
+        assert!(granted.decision);
+        let predicates = &granted.context.constraints[0].predicates;
+        assert!(matches!(
+            &predicates[1],
+            Predicate::Eq(p) if p.property == pep_properties::OWNER_ID
+                && p.value == json!(subject().to_string())
+        ));
+
+        let scope = compile_to_access_scope(
+            &granted,
+            true,
+            &[pep_properties::OWNER_TENANT_ID, pep_properties::OWNER_ID],
+        )
+        .unwrap();
+        assert!(!scope.is_unconstrained());
+    }
+
+    #[test]
+    fn deny_rules_override_grants() {
+        let mut req = request("delete", &["*"]);
+        req.resource
+            .properties
+            .insert("status".to_owned(), json!("archived"));
+
+        let response = policy().evaluate(&req);
+        assert!(!response.decision);
+        assert!(
+            reason(&response)
+                .details
+                .as_deref()
+                .unwrap()
+                .contains("keep-archived")
+        );
+    }
+
+    #[test]
+    fn unresolvable_tenant_and_unbound_subjects_are_denied() {
+        let mut no_tenant = request("list", &[]);
+        no_tenant.context.tenant_context = None;
+        no_tenant.subject.properties.clear();
+        let response = policy().evaluate(&no_tenant);
+        assert_eq!(reason(&response).error_code, INVALID_REQUEST);
+
+        let mut service = request("list", &[]);
+        service.subject.subject_type = Some("service".to_owned());
+        let response = policy().evaluate(&service);
+        assert!(!response.decision);
+        assert_eq!(reason(&response).error_code, INSUFFICIENT_PERMISSIONS);
+    }
+
+    #[test]
+    fn constraints_on_unsupported_properties_are_dropped() {
+        let mut req = request("update", &["docs:write"]);
+        req.context.supported_properties = vec![pep_properties::OWNER_TENANT_ID.to_owned()];
+
+        let response = policy().evaluate(&req);
+        assert!(!response.decision);
+    }
+
+    #[test]
+    fn tenant_documents_apply_only_to_their_tenant() {
+        let tenant_doc: PolicyDocument = serde_saphyr::from_str(
+            r#"
+roles:
+  - name: auditor
+    rules:
+      - resource_types: ["*"]
+        actions: ["*"]
+        tenant_scoped: false
+bindings:
+  - role: auditor
+    subject_ids: ["00000000-0000-0000-0000-000000000011"]
+  - role: member
+    subject_types: ["service"]
+"#,
+        )
+        .unwrap();
+        let global = PolicyDocument {
+            roles: policy().global.roles,
+            bindings: vec![],
+        };
+        let policy = PolicySet::new(global, [(tenant(), tenant_doc)]).unwrap();
+        assert_eq!(policy.tenant_count(), 1);
+
+        let response = policy.evaluate(&request("purge", &[]));
+        assert!(response.decision);
+        assert!(response.context.constraints[0].predicates.is_empty());
+
+        let mut other_tenant = request("purge", &[]);
+        other_tenant.context.tenant_context = Some(TenantContext {
+            root_id: Some(Uuid::from_u128(0x33)),
+            ..TenantContext::default()
+        });
+        assert!(!policy.evaluate(&other_tenant).decision);
+    }
+
+    #[test]
+    fn tenant_documents_cannot_redefine_global_roles() {
+        let doc = PolicyDocument {
+            roles: vec![Role {
+                name: "member".to_owned(),
+                rules: vec![],
+            }],
+            bindings: vec![],
+        };
+        let global = PolicyDocument {
+            roles: policy().global.roles,
+            bindings: vec![],
+        };
+        assert!(matches!(
+            PolicySet::new(global, [(tenant(), doc)]),
+            Err(PolicyError::DuplicateRole(_))
+        ));
+    }
+
+    #[test]
+    fn subtree_grants_need_tenant_hierarchy_capability() {
+        let global: PolicyDocument = serde_saphyr::from_str(
+            r#"
+roles:
+  - name: admin
+    rules:
+      - resource_types: ["*"]
+        actions: ["list"]
+        tenant_subtree: true
+      - resource_types: ["*"]
+        actions: ["get"]
+        tenant_subtree: true
+        filters:
+          - { op: in_group_subtree, property: id, root_group_id: "$subject.properties.group_id" }
+bindings:
+  - role: admin
+"#,
+        )
+        .unwrap();
+        let policy = PolicySet::new(global, []).unwrap();
+
+        // Without the capability only the context tenant is granted.
+        let response = policy.evaluate(&request("list", &[]));
+        assert!(matches!(
+            response.context.constraints[0].predicates.as_slice(),
+            [Predicate::In(_)]
+        ));
+
+        let mut req = request("list", &[]);
+        req.context.capabilities = vec![Capability::TenantHierarchy];
+        let response = policy.evaluate(&req);
+        match response.context.constraints[0].predicates.as_slice() {
+            [Predicate::InTenantSubtree(p)] => assert_eq!(p.root_tenant_id, tenant()),
+            other => panic!("unexpected predicates: {other:?}"),
+        }
+
+        // Group filters cannot be dropped, so the rule needs the capability.
+        let group = Uuid::from_u128(0x44);
+        let mut req = request("get", &[]);
+        req.subject
+            .properties
+            .insert("group_id".to_owned(), json!(group.to_string()));
+        req.context.capabilities = vec![Capability::TenantHierarchy];
+        assert!(!policy.evaluate(&req).decision);
+
+        req.context.capabilities.push(Capability::GroupHierarchy);
+        let response = policy.evaluate(&req);
+        assert!(matches!(
+            &response.context.constraints[0].predicates[1],
+            Predicate::InGroupSubtree(p) if p.root_group_id == group
+        ));
+    }
+}
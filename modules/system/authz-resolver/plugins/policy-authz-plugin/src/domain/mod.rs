@@ -0,0 +1,11 @@
+//! Domain layer for the policy `AuthZ` resolver plugin.
+
+pub mod attributes;
+mod client;
+pub mod engine;
+pub mod policy;
+pub mod service;
+
+pub use engine::PolicySet;
+pub use policy::PolicyDocument;
+pub use service::Service;
@@ -0,0 +1,402 @@
+//! Declarative policy model.
+//!
+//! A [`PolicyDocument`] holds roles and the bindings that assign them to
+//! subjects. The same shape is used for the inline config policy and for the
+//! per-tenant documents stored in the database.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::attributes::is_known_attribute;
+
+/// Policy validation error.
+#[derive(Debug, thiserror::Error)]
+pub enum PolicyError {
+    #[error("duplicate role '{0}'")]
+    DuplicateRole(String),
+
+    #[error("binding references unknown role '{0}'")]
+    UnknownRole(String),
+
+    #[error("role '{role}': {reason}")]
+    InvalidRule { role: String, reason: String },
+
+    #[error("unknown attribute '{0}'")]
+    UnknownAttribute(String),
+}
+
+/// Roles and their assignments.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolicyDocument {
+    pub roles: Vec<Role>,
+    pub bindings: Vec<RoleBinding>,
+}
+
+/// A named set of rules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Role {
+    pub name: String,
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+/// Whether a matching rule grants or denies access.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Effect {
+    #[default]
+    Allow,
+    /// Denies the request, overriding every `allow` rule.
+    Deny,
+}
+
+/// Permission to perform `actions` on `resource_types`.
+///
+/// Patterns are exact names, `*`, or a prefix ending in `*`
+/// (`gts.x.core.users.*`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    /// Rule identifier reported in deny reasons.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    #[serde(default)]
+    pub effect: Effect,
+
+    pub resource_types: Vec<String>,
+
+    pub actions: Vec<String>,
+
+    /// Attribute conditions that must all hold for the rule to apply.
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+
+    /// Restrict granted rows to the context tenant (default: `true`).
+    #[serde(default = "default_true")]
+    pub tenant_scoped: bool,
+
+    /// Extend a tenant-scoped grant to the context tenant's subtree.
+    ///
+    /// Applies when the PEP advertises `tenant_hierarchy` and the request's
+    /// tenant mode is `subtree`; otherwise only the context tenant is granted.
+    #[serde(default)]
+    pub tenant_subtree: bool,
+
+    /// Row filters added to the granted constraint (`allow` rules only).
+    #[serde(default)]
+    pub filters: Vec<RowFilter>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Assigns `role` to every subject matching all non-empty selectors.
+///
+/// Within a selector any listed value matches; a binding without selectors
+/// applies to every subject.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoleBinding {
+    pub role: String,
+
+    #[serde(default)]
+    pub subject_ids: Vec<Uuid>,
+
+    #[serde(default)]
+    pub subject_types: Vec<String>,
+
+    /// Subject home tenants.
+    #[serde(default)]
+    pub tenant_ids: Vec<Uuid>,
+
+    /// Token scopes, any of which assigns the role.
+    #[serde(default)]
+    pub token_scopes: Vec<String>,
+
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+}
+
+/// Attribute condition.
+///
+/// Attributes are paths such as `subject.type` or `resource.properties.status`
+/// (see [`attributes`](super::attributes)). String operands starting with `$`
+/// name another attribute, e.g. `$subject.id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Condition {
+    /// The attribute equals `value`, or is an array containing it.
+    Eq { attribute: String, value: Value },
+    /// The attribute is present and does not equal `value`.
+    Ne { attribute: String, value: Value },
+    /// The attribute (or any element of it) is one of `values`.
+    In {
+        attribute: String,
+        values: Vec<Value>,
+    },
+    /// The attribute is present.
+    Exists { attribute: String },
+}
+
+/// Row filter emitted as a constraint predicate.
+///
+/// Operands follow the same `$attribute` convention as conditions; a rule
+/// whose operand cannot be resolved does not apply. Group filters need group
+/// UUIDs and a PEP advertising the matching capability, otherwise the rule
+/// does not apply either.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum RowFilter {
+    /// `property = value`
+    Eq { property: String, value: Value },
+    /// `property IN (values)`
+    In {
+        property: String,
+        values: Vec<Value>,
+    },
+    /// `property` is a member of one of `group_ids` (`group_membership`).
+    InGroup {
+        property: String,
+        group_ids: Vec<Value>,
+    },
+    /// `property` is a member of `root_group_id` or a nested group
+    /// (`group_hierarchy`).
+    InGroupSubtree {
+        property: String,
+        root_group_id: Value,
+    },
+}
+
+impl RowFilter {
+    /// The filtered resource property.
+    #[must_use]
+    pub fn property(&self) -> &str {
+        match self {
+            Self::Eq { property, .. }
+            | Self::In { property, .. }
+            | Self::InGroup { property, .. }
+            | Self::InGroupSubtree { property, .. } => property,
+        }
+    }
+
+    fn operands(&self) -> &[Value] {
+        match self {
+            Self::Eq { value, .. }
+            | Self::InGroupSubtree {
+                root_group_id: value,
+                ..
+            } => std::slice::from_ref(value),
+            Self::In { values, .. }
+            | Self::InGroup {
+                group_ids: values, ..
+            } => values,
+        }
+    }
+}
+
+impl Condition {
+    fn attribute(&self) -> &str {
+        match self {
+            Self::Eq { attribute, .. }
+            | Self::Ne { attribute, .. }
+            | Self::In { attribute, .. }
+            | Self::Exists { attribute } => attribute,
+        }
+    }
+
+    fn operands(&self) -> &[Value] {
+        match self {
+            Self::Eq { value, .. } | Self::Ne { value, .. } => std::slice::from_ref(value),
+            Self::In { values, .. } => values,
+            Self::Exists { .. } => &[],
+        }
+    }
+}
+
+/// Attribute named by an operand, if it is a `$` reference.
+#[must_use]
+pub fn operand_reference(operand: &Value) -> Option<&str> {
+    operand.as_str().and_then(|s| s.strip_prefix('$'))
+}
+
+fn check_attributes<'a>(
+    attributes: impl IntoIterator<Item = &'a str>,
+    operands: impl IntoIterator<Item = &'a Value>,
+) -> Result<(), PolicyError> {
+    let references = operands.into_iter().filter_map(operand_reference);
+    for attribute in attributes.into_iter().chain(references) {
+        if !is_known_attribute(attribute) {
+            return Err(PolicyError::UnknownAttribute(attribute.to_owned()));
+        }
+    }
+    Ok(())
+}
+
+fn check_conditions(conditions: &[Condition]) -> Result<(), PolicyError> {
+    check_attributes(
+        conditions.iter().map(Condition::attribute),
+        conditions.iter().flat_map(Condition::operands),
+    )
+}
+
+impl Rule {
+    fn validate(&self, role: &str) -> Result<(), PolicyError> {
+        let invalid = |reason: &str| PolicyError::InvalidRule {
+            role: role.to_owned(),
+            reason: reason.to_owned(),
+        };
+        if self.resource_types.is_empty() {
+            return Err(invalid("rule has no resource_types"));
+        }
+        if self.actions.is_empty() {
+            return Err(invalid("rule has no actions"));
+        }
+        if self.effect == Effect::Deny && !self.filters.is_empty() {
+            return Err(invalid("deny rules cannot have filters"));
+        }
+        if self.tenant_subtree && !self.tenant_scoped {
+            return Err(invalid("tenant_subtree requires tenant_scoped"));
+        }
+        if self.filters.iter().any(|f| f.property().is_empty()) {
+            return Err(invalid("filter has an empty property"));
+        }
+        check_conditions(&self.conditions)?;
+        check_attributes([], self.filters.iter().flat_map(RowFilter::operands))
+    }
+}
+
+impl PolicyDocument {
+    /// Check rules and conditions, and that bindings only reference roles
+    /// defined here or in `inherited_roles`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PolicyError` describing the first problem found.
+    pub fn validate(&self, inherited_roles: &HashSet<String>) -> Result<(), PolicyError> {
+        let mut names = HashSet::new();
+        for role in &self.roles {
+            if !names.insert(role.name.as_str()) {
+                return Err(PolicyError::DuplicateRole(role.name.clone()));
+            }
+            for rule in &role.rules {
+                rule.validate(&role.name)?;
+            }
+        }
+        for binding in &self.bindings {
+            if !names.contains(binding.role.as_str()) && !inherited_roles.contains(&binding.role) {
+                return Err(PolicyError::UnknownRole(binding.role.clone()));
+            }
+            check_conditions(&binding.conditions)?;
+        }
+        Ok(())
+    }
+}
+
+/// Match `value` against an exact, `*` or `prefix*` pattern.
+#[must_use]
+pub fn pattern_matches(pattern: &str, value: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => value.starts_with(prefix),
+        None => pattern == value,
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn parse(yaml: &str) -> PolicyDocument {
+        serde_saphyr::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn parses_roles_bindings_conditions_and_filters() {
+        let doc = parse(
+            r#"
+roles:
+  - name: owner
+    rules:
+      - id: owners-write
+        resource_types: ["gts.x.docs.*"]
+        actions: ["update", "delete"]
+        conditions:
+          - { op: eq, attribute: subject.type, value: user }
+        filters:
+          - { op: eq, property: owner_id, value: "$subject.id" }
+bindings:
+  - role: owner
+    token_scopes: ["docs:write"]
+"#,
+        );
+
+        assert!(doc.validate(&HashSet::new()).is_ok());
+        let rule = &doc.roles[0].rules[0];
+        assert_eq!(rule.effect, Effect::Allow);
+        assert!(rule.tenant_scoped);
+        assert!(
+            matches!(&rule.filters[0], RowFilter::Eq { property, .. } if property == "owner_id")
+        );
+    }
+
+    #[test]
+    fn validation_rejects_inconsistent_documents() {
+        let unknown_role = parse("bindings: [{ role: admin }]");
+        assert!(matches!(
+            unknown_role.validate(&HashSet::new()),
+            Err(PolicyError::UnknownRole(_))
+        ));
+        assert!(
+            unknown_role
+                .validate(&HashSet::from(["admin".to_owned()]))
+                .is_ok()
+        );
+
+        let bad_attribute = parse(
+            r#"
+roles:
+  - name: r
+    rules:
+      - resource_types: ["*"]
+        actions: ["*"]
+        conditions: [{ op: exists, attribute: subject.shoe_size }]
+"#,
+        );
+        assert!(matches!(
+            bad_attribute.validate(&HashSet::new()),
+            Err(PolicyError::UnknownAttribute(_))
+        ));
+
+        let deny_with_filter = parse(
+            r#"
+roles:
+  - name: r
+    rules:
+      - effect: deny
+        resource_types: ["*"]
+        actions: ["*"]
+        filters: [{ op: eq, property: owner_id, value: "$subject.id" }]
+"#,
+        );
+        assert!(matches!(
+            deny_with_filter.validate(&HashSet::new()),
+            Err(PolicyError::InvalidRule { .. })
+        ));
+    }
+
+    #[test]
+    fn patterns_match_exact_wildcard_and_prefix() {
+        assert!(pattern_matches("*", "anything"));
+        assert!(pattern_matches("gts.x.docs.*", "gts.x.docs.doc.v1~"));
+        assert!(pattern_matches("get", "get"));
+        assert!(!pattern_matches("get", "list"));
+        assert!(!pattern_matches("gts.x.docs.*", "gts.x.users.user.v1~"));
+    }
+}
@@ -0,0 +1,49 @@
+//! Service implementation for the policy `AuthZ` resolver plugin.
+
+use std::sync::{Arc, PoisonError, RwLock};
+
+use authz_resolver_sdk::{EvaluationRequest, EvaluationResponse};
+use modkit_macros::domain_model;
+
+use super::engine::PolicySet;
+
+/// Policy `AuthZ` resolver service.
+///
+/// Evaluates requests against the current [`PolicySet`], which can be
+/// replaced at runtime when policies are reloaded.
+#[domain_model]
+pub struct Service {
+    policies: RwLock<Arc<PolicySet>>,
+}
+
+impl Service {
+    #[must_use]
+    pub fn new(policies: PolicySet) -> Self {
+        Self {
+            policies: RwLock::new(Arc::new(policies)),
+        }
+    }
+
+    /// Current policy set.
+    #[must_use]
+    pub fn policies(&self) -> Arc<PolicySet> {
+        self.policies
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replace the policy set used by subsequent evaluations.
+    pub fn replace_policies(&self, policies: PolicySet) {
+        *self
+            .policies
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Arc::new(policies);
+    }
+
+    /// Evaluate an authorization request.
+    #[must_use]
+    pub fn evaluate(&self, request: &EvaluationRequest) -> EvaluationResponse {
+        self.policies().evaluate(request)
+    }
+}
@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let statements = match backend {
+            sea_orm::DatabaseBackend::Postgres => POSTGRES_UP,
+            sea_orm::DatabaseBackend::MySql => MYSQL_UP,
+            sea_orm::DatabaseBackend::Sqlite => SQLITE_UP,
+        };
+
+        // One statement per call: MySQL rejects multi-statement strings.
+        for sql in statements {
+            conn.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS authz_policy")
+            .await?;
+        Ok(())
+    }
+}
+
+const POSTGRES_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS authz_policy (
+    id          UUID PRIMARY KEY NOT NULL,
+    tenant_id   UUID NOT NULL,
+    name        VARCHAR(255) NOT NULL,
+    document    TEXT NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL,
+    updated_at  TIMESTAMPTZ NOT NULL
+)
+",
+    r"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_authz_policy_name
+    ON authz_policy (tenant_id, name)
+",
+];
+
+const MYSQL_UP: &[&str] = &[r"
+CREATE TABLE IF NOT EXISTS authz_policy (
+    id          VARCHAR(36) PRIMARY KEY NOT NULL,
+    tenant_id   VARCHAR(36) NOT NULL,
+    name        VARCHAR(255) NOT NULL,
+    document    LONGTEXT NOT NULL,
+    created_at  TIMESTAMP(6) NOT NULL,
+    updated_at  TIMESTAMP(6) NOT NULL,
+    UNIQUE KEY uq_authz_policy_name (tenant_id, name)
+)
+"];
+
+const SQLITE_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS authz_policy (
+    id          TEXT PRIMARY KEY NOT NULL,
+    tenant_id   TEXT NOT NULL,
+    name        TEXT NOT NULL,
+    document    TEXT NOT NULL,
+    created_at  TEXT NOT NULL,
+    updated_at  TEXT NOT NULL
+)
+",
+    r"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_authz_policy_name
+    ON authz_policy (tenant_id, name)
+",
+];
@@ -0,0 +1,27 @@
+//! Per-tenant policy documents stored in the module database.
+
+pub mod entity;
+pub mod migrations;
+mod policy_repo;
+
+pub use policy_repo::PolicyRepo;
+
+#[cfg(test)]
+pub(crate) async fn test_provider() -> modkit_db::DBProvider<modkit_db::DbError> {
+    use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::{ConnectOpts, connect_db};
+    use sea_orm_migration::MigratorTrait;
+
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db("sqlite::memory:", opts)
+        .await
+        .expect("connect in-memory database");
+    run_migrations_for_testing(&db, migrations::Migrator::migrations())
+        .await
+        .expect("run migrations");
+    modkit_db::DBProvider::new(db)
+}
@@ -0,0 +1,140 @@
+use modkit_db::secure::{SecureDeleteExt, SecureEntityExt, SecureUpdateExt, secure_insert};
+use modkit_db::{DBProvider, DbError};
+use modkit_security::AccessScope;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, Order, Set};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::entity;
+use crate::domain::PolicyDocument;
+
+/// Policy document storage in the module database.
+pub struct PolicyRepo {
+    db: DBProvider<DbError>,
+}
+
+impl PolicyRepo {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>) -> Self {
+        Self { db }
+    }
+
+    /// Documents of every tenant, ordered by tenant and name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn list_all(&self) -> Result<Vec<entity::Model>, DbError> {
+        let conn = self.db.conn()?;
+        // The PDP evaluates requests of every tenant.
+        let rows = entity::Entity::find()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .order_by(entity::Column::TenantId, Order::Asc)
+            .order_by(entity::Column::Name, Order::Asc)
+            .all(&conn)
+            .await?;
+        Ok(rows)
+    }
+
+    /// Create or replace the document `name` of `tenant_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the document cannot be encoded or a query fails.
+    pub async fn save(
+        &self,
+        tenant_id: Uuid,
+        name: &str,
+        document: &PolicyDocument,
+    ) -> Result<(), DbError> {
+        let conn = self.db.conn()?;
+        let scope = AccessScope::for_tenant(tenant_id);
+        let json = serde_json::to_string(document).map_err(anyhow::Error::from)?;
+        let now = OffsetDateTime::now_utc();
+
+        let updated = entity::Entity::update_many()
+            .secure()
+            .col_expr(entity::Column::Document, Expr::value(json.clone()))
+            .col_expr(entity::Column::UpdatedAt, Expr::value(now))
+            .filter(Condition::all().add(entity::Column::Name.eq(name)))
+            .scope_with(&scope)
+            .exec(&conn)
+            .await?;
+        if updated.rows_affected > 0 {
+            return Ok(());
+        }
+
+        let am = entity::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            name: Set(name.to_owned()),
+            document: Set(json),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+        secure_insert::<entity::Entity>(am, &scope, &conn).await?;
+        Ok(())
+    }
+
+    /// Delete the document `name` of `tenant_id`. Returns `false` if it did
+    /// not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn delete(&self, tenant_id: Uuid, name: &str) -> Result<bool, DbError> {
+        let conn = self.db.conn()?;
+        let result = entity::Entity::delete_many()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .filter(Condition::all().add(entity::Column::Name.eq(name)))
+            .exec(&conn)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::domain::policy::Role;
+    use crate::infra::storage::test_provider;
+
+    fn document(role: &str) -> PolicyDocument {
+        PolicyDocument {
+            roles: vec![Role {
+                name: role.to_owned(),
+                rules: vec![],
+            }],
+            bindings: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn save_replaces_and_delete_removes_documents() {
+        let repo = PolicyRepo::new(test_provider().await);
+        let tenant = Uuid::from_u128(1);
+
+        repo.save(tenant, "base", &document("viewer"))
+            .await
+            .unwrap();
+        repo.save(tenant, "base", &document("editor"))
+            .await
+            .unwrap();
+        repo.save(Uuid::from_u128(2), "base", &document("viewer"))
+            .await
+            .unwrap();
+
+        let rows = repo.list_all().await.unwrap();
+        assert_eq!(rows.len(), 2);
+        let stored: PolicyDocument = serde_json::from_str(&rows[0].document).unwrap();
+        assert_eq!(stored.roles[0].name, "editor");
+
+        assert!(repo.delete(tenant, "base").await.unwrap());
+        assert!(!repo.delete(tenant, "base").await.unwrap());
+        assert_eq!(repo.list_all().await.unwrap().len(), 1);
+    }
+}
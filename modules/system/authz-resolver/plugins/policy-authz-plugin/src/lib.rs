@@ -0,0 +1,41 @@
+//! Policy `AuthZ` Resolver Plugin
+//!
+//! Policy Decision Point driven by declarative RBAC/ABAC policies instead
+//! of code.
+//!
+//! - Roles group `allow`/`deny` rules on resource types and actions,
+//!   optionally guarded by attribute conditions.
+//! - Bindings assign roles to subjects by id, type, home tenant, token scope
+//!   or attribute conditions.
+//! - Granted rules produce row-level constraints (tenant scope plus filters
+//!   such as `owner_id = $subject.id`) that the PEP compiles into
+//!   `AccessScope`; denials carry a `DenyReason`.
+//!
+//! The global policy comes from config; per-tenant documents can be stored
+//! in the module database and are reloaded periodically.
+//!
+//! ## Configuration
+//!
+//! ```yaml
+//! modules:
+//!   policy_authz_plugin:
+//!     config:
+//!       vendor: "hyperspot"
+//!       priority: 50
+//!       policy:
+//!         roles:
+//!           - name: member
+//!             rules:
+//!               - resource_types: ["*"]
+//!                 actions: ["list", "get"]
+//!         bindings:
+//!           - role: member
+//! ```
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod config;
+pub mod domain;
+pub mod infra;
+pub mod module;
+
+pub use module::PolicyAuthZPlugin;
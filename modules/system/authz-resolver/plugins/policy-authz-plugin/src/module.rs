@@ -0,0 +1,166 @@
+//! Policy `AuthZ` resolver plugin module.
+
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use authz_resolver_sdk::{AuthZResolverPluginClient, AuthZResolverPluginSpecV1};
+use modkit::Module;
+use modkit::client_hub::ClientScope;
+use modkit::context::ModuleCtx;
+use modkit::gts::BaseModkitPluginV1;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+use types_registry_sdk::{RegisterResult, TypesRegistryClient};
+
+use crate::config::PolicyAuthZPluginConfig;
+use crate::domain::{PolicyDocument, PolicySet, Service};
+use crate::infra::storage::PolicyRepo;
+
+/// Policy `AuthZ` resolver plugin module.
+///
+/// Evaluates requests against the configured policy and, when the database
+/// source is enabled, keeps per-tenant policies fresh while the module runs.
+#[modkit::module(
+    name = "policy-authz-plugin",
+    deps = ["types-registry"],
+    capabilities = [db, stateful],
+    lifecycle(entry = "serve")
+)]
+pub struct PolicyAuthZPlugin {
+    service: OnceLock<Arc<Service>>,
+    reloader: OnceLock<Reloader>,
+}
+
+impl Default for PolicyAuthZPlugin {
+    fn default() -> Self {
+        Self {
+            service: OnceLock::new(),
+            reloader: OnceLock::new(),
+        }
+    }
+}
+
+impl modkit::contracts::DatabaseCapability for PolicyAuthZPlugin {
+    fn migrations(&self) -> Vec<Box<dyn sea_orm_migration::MigrationTrait>> {
+        use sea_orm_migration::MigratorTrait;
+        crate::infra::storage::migrations::Migrator::migrations()
+    }
+}
+
+/// Rebuilds the policy set from config and the database.
+struct Reloader {
+    global: PolicyDocument,
+    repo: PolicyRepo,
+    interval: Duration,
+}
+
+impl Reloader {
+    async fn load(&self) -> anyhow::Result<PolicySet> {
+        let mut documents = Vec::new();
+        for row in self.repo.list_all().await? {
+            let document = serde_json::from_str(&row.document).with_context(|| {
+                format!(
+                    "invalid policy document '{}' of tenant {}",
+                    row.name, row.tenant_id
+                )
+            })?;
+            documents.push((row.tenant_id, document));
+        }
+        PolicySet::new(self.global.clone(), documents).context("invalid policy")
+    }
+}
+
+impl PolicyAuthZPlugin {
+    /// Reload stored policies every `reload_interval` until `cancel` fires.
+    ///
+    /// A failed reload keeps the previous policies: dropping one invalid
+    /// document could silently lift the `deny` rules it contains.
+    pub(crate) async fn serve(self: Arc<Self>, cancel: CancellationToken) -> anyhow::Result<()> {
+        let (Some(reloader), Some(service)) = (self.reloader.get(), self.service.get()) else {
+            return Ok(());
+        };
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => return Ok(()),
+                () = tokio::time::sleep(reloader.interval) => {}
+            }
+            match reloader.load().await {
+                Ok(policies) => service.replace_policies(policies),
+                Err(e) => warn!(
+                    error = format!("{e:#}"),
+                    "Policy reload failed; keeping previous policies"
+                ),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Module for PolicyAuthZPlugin {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        // Load configuration
+        let cfg: PolicyAuthZPluginConfig = ctx.config()?;
+        info!(
+            vendor = %cfg.vendor,
+            priority = cfg.priority,
+            roles = cfg.policy.roles.len(),
+            bindings = cfg.policy.bindings.len(),
+            database = cfg.database.enabled,
+            "Loaded plugin configuration"
+        );
+
+        // Load policies (validate early, before registration)
+        let policies = if cfg.database.enabled {
+            let reloader = Reloader {
+                global: cfg.policy.clone(),
+                repo: PolicyRepo::new(ctx.db_required()?),
+                interval: cfg.database.reload_interval,
+            };
+            let policies = reloader.load().await?;
+            info!(tenants = policies.tenant_count(), "Loaded stored policies");
+            self.reloader
+                .set(reloader)
+                .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+            policies
+        } else {
+            PolicySet::new(cfg.policy.clone(), []).context("invalid policy")?
+        };
+
+        // Generate plugin instance ID
+        let instance_id = AuthZResolverPluginSpecV1::gts_make_instance_id(
+            "hyperspot.builtin.policy_authz_resolver.plugin.v1",
+        );
+
+        // Register plugin instance in types-registry
+        let registry = ctx.client_hub().get::<dyn TypesRegistryClient>()?;
+        let instance = BaseModkitPluginV1::<AuthZResolverPluginSpecV1> {
+            id: instance_id.clone(),
+            vendor: cfg.vendor.clone(),
+            priority: cfg.priority,
+            properties: AuthZResolverPluginSpecV1,
+        };
+        let instance_json = serde_json::to_value(&instance)?;
+
+        let results = registry.register(vec![instance_json]).await?;
+        RegisterResult::ensure_all_ok(&results)?;
+
+        // Create service
+        let service = Arc::new(Service::new(policies));
+        self.service
+            .set(service.clone())
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
+        // Register scoped client in ClientHub
+        let api: Arc<dyn AuthZResolverPluginClient> = service;
+        ctx.client_hub()
+            .register_scoped::<dyn AuthZResolverPluginClient>(
+                ClientScope::gts_id(&instance_id),
+                api,
+            );
+
+        info!(instance_id = %instance_id);
+        Ok(())
+    }
+}
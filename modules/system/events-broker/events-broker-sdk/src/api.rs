@@ -0,0 +1,73 @@
+//! Events broker API trait.
+
+use async_trait::async_trait;
+use modkit_security::SecurityContext;
+
+use crate::errors::EventsBrokerError;
+use crate::models::{Event, FetchRequest, NewEvent, ReplayRequest, StartPosition, TopicId};
+
+/// Events broker API (version 1).
+///
+/// Events are appended to topics and numbered by a per-topic offset.
+/// Consumer groups keep a committed position per topic in the broker
+/// database, so delivery is at-least-once: events fetched but not committed
+/// are fetched again, by any member of the group.
+///
+/// Registered in `ClientHub` by the events-broker module (in-process) or by
+/// [`wire_client`](crate::wire_client) (out-of-process):
+/// ```ignore
+/// let broker = hub.get::<dyn EventsBrokerClientV1>()?;
+/// broker.publish(&ctx, NewEvent::new(topic, payload)).await?;
+/// ```
+#[async_trait]
+pub trait EventsBrokerClientV1: Send + Sync {
+    /// Append an event to its topic.
+    ///
+    /// The event is attributed to the caller's tenant.
+    async fn publish(
+        &self,
+        ctx: &SecurityContext,
+        event: NewEvent,
+    ) -> Result<Event, EventsBrokerError>;
+
+    /// Events after the group's committed position, in offset order.
+    ///
+    /// Waits up to `req.wait` when no event is pending. A group that has
+    /// never read the topic starts at `req.start`.
+    async fn fetch(
+        &self,
+        ctx: &SecurityContext,
+        req: FetchRequest,
+    ) -> Result<Vec<Event>, EventsBrokerError>;
+
+    /// Mark the events of `topic` up to and including `offset` as processed
+    /// by `group`.
+    ///
+    /// Committing an offset at or below the current position is a no-op.
+    async fn commit(
+        &self,
+        ctx: &SecurityContext,
+        group: &str,
+        topic: &TopicId,
+        offset: u64,
+    ) -> Result<(), EventsBrokerError>;
+
+    /// Move the group's position, backwards to reprocess events or forwards
+    /// to skip them.
+    ///
+    /// Returns the offset of the next event the group will receive.
+    async fn seek(
+        &self,
+        ctx: &SecurityContext,
+        group: &str,
+        topic: &TopicId,
+        position: StartPosition,
+    ) -> Result<u64, EventsBrokerError>;
+
+    /// Read events from a position without touching any consumer group.
+    async fn replay(
+        &self,
+        ctx: &SecurityContext,
+        req: ReplayRequest,
+    ) -> Result<Vec<Event>, EventsBrokerError>;
+}
@@ -0,0 +1,165 @@
+//! gRPC client implementation of `EventsBrokerClientV1`.
+//!
+//! Internal client used by `wire_client()`. Not exported from SDK.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tonic::transport::Channel;
+
+use modkit_security::SecurityContext;
+use modkit_transport_grpc::attach_secctx;
+use modkit_transport_grpc::client::{GrpcClientConfig, connect_with_retry};
+
+use crate::api::EventsBrokerClientV1;
+use crate::errors::EventsBrokerError;
+use crate::models::{Event, FetchRequest, NewEvent, ReplayRequest, StartPosition, TopicId};
+use crate::proto;
+use crate::proto::events_broker_service_client::EventsBrokerServiceClient;
+
+/// Fetches long-poll for up to their `wait`; leave room for that on top of
+/// the usual round trip.
+const RPC_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// gRPC client implementation of `EventsBrokerClientV1`.
+pub struct EventsBrokerGrpcClient {
+    inner: EventsBrokerServiceClient<Channel>,
+}
+
+impl EventsBrokerGrpcClient {
+    /// Connect to the `EventsBrokerService` with retries.
+    pub async fn connect(uri: impl Into<String>) -> Result<Self> {
+        let cfg = GrpcClientConfig::new("events_broker").with_rpc_timeout(RPC_TIMEOUT);
+        let channel: Channel = connect_with_retry(uri, &cfg).await?;
+        Ok(Self {
+            inner: EventsBrokerServiceClient::new(channel),
+        })
+    }
+}
+
+fn request<T>(ctx: &SecurityContext, message: T) -> Result<tonic::Request<T>, EventsBrokerError> {
+    let mut request = tonic::Request::new(message);
+    attach_secctx(request.metadata_mut(), ctx)
+        .map_err(|e| EventsBrokerError::internal(e.message()))?;
+    Ok(request)
+}
+
+fn map_status(status: &tonic::Status) -> EventsBrokerError {
+    let message = status.message().to_owned();
+    match status.code() {
+        tonic::Code::Unauthenticated | tonic::Code::PermissionDenied => {
+            EventsBrokerError::Unauthorized(message)
+        }
+        tonic::Code::InvalidArgument => EventsBrokerError::validation("request", message),
+        tonic::Code::Internal => EventsBrokerError::Internal(message),
+        _ => EventsBrokerError::Transport(message),
+    }
+}
+
+fn from_proto_list(list: proto::EventList) -> Result<Vec<Event>, EventsBrokerError> {
+    list.events.into_iter().map(Event::try_from).collect()
+}
+
+#[async_trait]
+impl EventsBrokerClientV1 for EventsBrokerGrpcClient {
+    async fn publish(
+        &self,
+        ctx: &SecurityContext,
+        event: NewEvent,
+    ) -> Result<Event, EventsBrokerError> {
+        let message = proto::PublishRequest {
+            topic: event.topic.to_string(),
+            payload: event.payload.to_string(),
+            dedupe_key: event.dedupe_key,
+        };
+        let response = self
+            .inner
+            .clone()
+            .publish(request(ctx, message)?)
+            .await
+            .map_err(|s| map_status(&s))?;
+        Event::try_from(response.into_inner())
+    }
+
+    async fn fetch(
+        &self,
+        ctx: &SecurityContext,
+        req: FetchRequest,
+    ) -> Result<Vec<Event>, EventsBrokerError> {
+        let message = proto::FetchRequest {
+            topic: req.topic.to_string(),
+            group: req.group,
+            start: Some(req.start.try_into()?),
+            max_events: req.max_events,
+            wait_ms: u64::try_from(req.wait.as_millis()).unwrap_or(u64::MAX),
+        };
+        let response = self
+            .inner
+            .clone()
+            .fetch(request(ctx, message)?)
+            .await
+            .map_err(|s| map_status(&s))?;
+        from_proto_list(response.into_inner())
+    }
+
+    async fn commit(
+        &self,
+        ctx: &SecurityContext,
+        group: &str,
+        topic: &TopicId,
+        offset: u64,
+    ) -> Result<(), EventsBrokerError> {
+        let message = proto::CommitRequest {
+            topic: topic.to_string(),
+            group: group.to_owned(),
+            offset,
+        };
+        self.inner
+            .clone()
+            .commit(request(ctx, message)?)
+            .await
+            .map_err(|s| map_status(&s))?;
+        Ok(())
+    }
+
+    async fn seek(
+        &self,
+        ctx: &SecurityContext,
+        group: &str,
+        topic: &TopicId,
+        position: StartPosition,
+    ) -> Result<u64, EventsBrokerError> {
+        let message = proto::SeekRequest {
+            topic: topic.to_string(),
+            group: group.to_owned(),
+            position: Some(position.try_into()?),
+        };
+        let response = self
+            .inner
+            .clone()
+            .seek(request(ctx, message)?)
+            .await
+            .map_err(|s| map_status(&s))?;
+        Ok(response.into_inner().next_offset)
+    }
+
+    async fn replay(
+        &self,
+        ctx: &SecurityContext,
+        req: ReplayRequest,
+    ) -> Result<Vec<Event>, EventsBrokerError> {
+        let message = proto::ReplayRequest {
+            topic: req.topic.to_string(),
+            from: Some(req.from.try_into()?),
+            max_events: req.max_events,
+        };
+        let response = self
+            .inner
+            .clone()
+            .replay(request(ctx, message)?)
+            .await
+            .map_err(|s| map_status(&s))?;
+        from_proto_list(response.into_inner())
+    }
+}
@@ -0,0 +1,153 @@
+//! Conversions between SDK models and their protobuf form.
+//!
+//! Payloads travel as JSON text and timestamps as RFC 3339 strings.
+
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use uuid::Uuid;
+
+use crate::errors::EventsBrokerError;
+use crate::models::{Event, StartPosition, TopicId};
+use crate::proto;
+use crate::proto::start_position::Position;
+
+pub(crate) fn format_time(t: OffsetDateTime) -> Result<String, EventsBrokerError> {
+    t.format(&Rfc3339)
+        .map_err(|e| EventsBrokerError::internal(format!("failed to format timestamp: {e}")))
+}
+
+pub(crate) fn parse_time(field: &str, value: &str) -> Result<OffsetDateTime, EventsBrokerError> {
+    OffsetDateTime::parse(value, &Rfc3339)
+        .map_err(|e| EventsBrokerError::validation(field, format!("invalid RFC 3339 time: {e}")))
+}
+
+/// Parse a JSON payload received over the wire.
+///
+/// # Errors
+///
+/// Returns [`EventsBrokerError::Validation`] if `value` is not valid JSON.
+pub fn parse_payload(value: &str) -> Result<serde_json::Value, EventsBrokerError> {
+    serde_json::from_str(value)
+        .map_err(|e| EventsBrokerError::validation("payload", format!("invalid JSON: {e}")))
+}
+
+fn parse_uuid(field: &str, value: &str) -> Result<Uuid, EventsBrokerError> {
+    Uuid::parse_str(value)
+        .map_err(|e| EventsBrokerError::validation(field, format!("invalid UUID: {e}")))
+}
+
+impl TryFrom<&Event> for proto::Event {
+    type Error = EventsBrokerError;
+
+    fn try_from(event: &Event) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: event.id.to_string(),
+            topic: event.topic.to_string(),
+            offset: event.offset,
+            tenant_id: event.tenant_id.to_string(),
+            dedupe_key: event.dedupe_key.clone(),
+            payload: event.payload.to_string(),
+            published_at: format_time(event.published_at)?,
+        })
+    }
+}
+
+impl TryFrom<proto::Event> for Event {
+    type Error = EventsBrokerError;
+
+    fn try_from(event: proto::Event) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: parse_uuid("id", &event.id)?,
+            topic: TopicId::new(event.topic)?,
+            offset: event.offset,
+            tenant_id: parse_uuid("tenant_id", &event.tenant_id)?,
+            dedupe_key: event.dedupe_key,
+            payload: parse_payload(&event.payload)?,
+            published_at: parse_time("published_at", &event.published_at)?,
+        })
+    }
+}
+
+impl TryFrom<StartPosition> for proto::StartPosition {
+    type Error = EventsBrokerError;
+
+    fn try_from(position: StartPosition) -> Result<Self, Self::Error> {
+        let position = match position {
+            StartPosition::Earliest => Position::Earliest(true),
+            StartPosition::Latest => Position::Latest(true),
+            StartPosition::Offset(offset) => Position::Offset(offset),
+            StartPosition::Time(t) => Position::Time(format_time(t)?),
+        };
+        Ok(Self {
+            position: Some(position),
+        })
+    }
+}
+
+impl TryFrom<Option<proto::StartPosition>> for StartPosition {
+    type Error = EventsBrokerError;
+
+    /// A missing position means [`StartPosition::Latest`].
+    fn try_from(position: Option<proto::StartPosition>) -> Result<Self, Self::Error> {
+        Ok(match position.and_then(|p| p.position) {
+            None | Some(Position::Latest(_)) => Self::Latest,
+            Some(Position::Earliest(_)) => Self::Earliest,
+            Some(Position::Offset(offset)) => Self::Offset(offset),
+            Some(Position::Time(t)) => Self::Time(parse_time("time", &t)?),
+        })
+    }
+}
+
+/// Convert a list of events to their protobuf form.
+///
+/// # Errors
+///
+/// Returns [`EventsBrokerError::Internal`] if a timestamp cannot be formatted.
+pub fn to_proto_list(events: &[Event]) -> Result<proto::EventList, EventsBrokerError> {
+    Ok(proto::EventList {
+        events: events
+            .iter()
+            .map(proto::Event::try_from)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn event_round_trips_through_proto() {
+        let event = Event {
+            id: Uuid::from_u128(1),
+            topic: TopicId::new("gts.x.core.events.topic.v1~x.demo.orders.created.v1~").unwrap(),
+            offset: 7,
+            tenant_id: Uuid::from_u128(2),
+            dedupe_key: Some("k".to_owned()),
+            payload: json!({ "a": [1, 2] }),
+            published_at: OffsetDateTime::from_unix_timestamp_nanos(1_767_323_045_678_000_000)
+                .unwrap(),
+        };
+        let wire = proto::Event::try_from(&event).unwrap();
+        assert_eq!(Event::try_from(wire).unwrap(), event);
+    }
+
+    #[test]
+    fn start_position_round_trips_through_proto() {
+        for position in [
+            StartPosition::Earliest,
+            StartPosition::Latest,
+            StartPosition::Offset(42),
+            StartPosition::Time(OffsetDateTime::from_unix_timestamp(1_767_323_045).unwrap()),
+        ] {
+            let wire = proto::StartPosition::try_from(position).unwrap();
+            assert_eq!(StartPosition::try_from(Some(wire)).unwrap(), position);
+        }
+        assert_eq!(
+            StartPosition::try_from(None).unwrap(),
+            StartPosition::Latest
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! Error types for the events broker SDK.
+
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone)]
+pub enum EventsBrokerError {
+    #[error("Invalid topic '{topic}': {message}")]
+    InvalidTopic { topic: String, message: String },
+
+    #[error("Validation error on field '{field}': {message}")]
+    Validation { field: String, message: String },
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Transport error: {0}")]
+    Transport(String),
+
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+impl EventsBrokerError {
+    #[must_use]
+    pub fn invalid_topic(topic: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::InvalidTopic {
+            topic: topic.into(),
+            message: message.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn validation(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Validation {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::Unauthorized(message.into())
+    }
+
+    #[must_use]
+    pub fn transport(message: impl Into<String>) -> Self {
+        Self::Transport(message.into())
+    }
+
+    #[must_use]
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::Internal(message.into())
+    }
+}
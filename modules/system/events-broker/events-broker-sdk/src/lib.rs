@@ -0,0 +1,48 @@
+//! Events Broker SDK
+//!
+//! This crate provides the public API of the events-broker module:
+//! - `EventsBrokerClientV1` trait for publishing and consuming events
+//! - Model types (`TopicId`, `NewEvent`, `Event`, `StartPosition`, ...)
+//! - Error type (`EventsBrokerError`)
+//! - `subscribe`, a consumer-group loop driving an `EventHandler`
+//! - `wire_client` and proto stubs for out-of-process consumers
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! use events_broker_sdk::{EventsBrokerClientV1, NewEvent, SubscriptionConfig, TopicId, subscribe};
+//!
+//! let broker = hub.get::<dyn EventsBrokerClientV1>()?;
+//! let topic = TopicId::new("gts.x.core.events.topic.v1~x.tenants.lifecycle.tenant_created.v1~")?;
+//! broker.publish(&ctx, NewEvent::new(topic.clone(), payload)).await?;
+//!
+//! let task = subscribe(broker, ctx, SubscriptionConfig::new(topic, "billing"), handler, cancel);
+//! ```
+
+#![forbid(unsafe_code)]
+
+pub mod api;
+pub mod convert;
+pub mod errors;
+pub mod models;
+pub mod subscriber;
+
+mod client;
+mod wiring;
+
+pub use api::EventsBrokerClientV1;
+pub use errors::EventsBrokerError;
+pub use models::{Event, FetchRequest, NewEvent, ReplayRequest, StartPosition, TopicId};
+pub use subscriber::{EventHandler, SubscriptionConfig, subscribe};
+pub use wiring::wire_client;
+
+/// Generated protobuf types for `EventsBrokerService`.
+#[allow(clippy::all, clippy::pedantic, clippy::nursery, warnings)]
+pub mod proto {
+    tonic::include_proto!("events_broker.v1");
+}
+
+pub use proto::events_broker_service_server::{EventsBrokerService, EventsBrokerServiceServer};
+
+/// Service name of `EventsBrokerService` (used for service discovery).
+pub const SERVICE_NAME: &str = <EventsBrokerServiceServer<()> as tonic::server::NamedService>::NAME;
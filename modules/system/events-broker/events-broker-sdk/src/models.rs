@@ -0,0 +1,171 @@
+//! Event and position types of the events broker.
+
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::errors::EventsBrokerError;
+
+/// Topic identifier: a GTS type id, e.g.
+/// `gts.x.core.events.topic.v1~x.tenants.lifecycle.tenant_created.v1~`.
+///
+/// The type describes the payload carried by the topic's events.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TopicId(String);
+
+impl TopicId {
+    /// Parse and validate a topic id.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventsBrokerError::InvalidTopic`] if `id` is not a GTS type id.
+    pub fn new(id: impl Into<String>) -> Result<Self, EventsBrokerError> {
+        let id = id.into();
+        match gts::GtsID::new(&id) {
+            Ok(parsed) if parsed.is_type() => Ok(Self(id)),
+            Ok(_) => Err(EventsBrokerError::invalid_topic(
+                id,
+                "expected a GTS type id (ending with '~')",
+            )),
+            Err(e) => Err(EventsBrokerError::invalid_topic(id, e.to_string())),
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for TopicId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for TopicId {
+    type Error = EventsBrokerError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<TopicId> for String {
+    fn from(value: TopicId) -> Self {
+        value.0
+    }
+}
+
+/// Event to publish.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewEvent {
+    pub topic: TopicId,
+    pub payload: serde_json::Value,
+    /// Publishing again with the same key on the same topic returns the
+    /// event stored the first time instead of appending a new one.
+    pub dedupe_key: Option<String>,
+}
+
+impl NewEvent {
+    #[must_use]
+    pub fn new(topic: TopicId, payload: serde_json::Value) -> Self {
+        Self {
+            topic,
+            payload,
+            dedupe_key: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_dedupe_key(mut self, key: impl Into<String>) -> Self {
+        self.dedupe_key = Some(key.into());
+        self
+    }
+}
+
+/// Stored event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    pub id: Uuid,
+    pub topic: TopicId,
+    /// Position in the topic; offsets start at 1 and have no gaps.
+    pub offset: u64,
+    /// Tenant of the publisher.
+    pub tenant_id: Uuid,
+    pub dedupe_key: Option<String>,
+    pub payload: serde_json::Value,
+    #[serde(with = "time::serde::rfc3339")]
+    pub published_at: OffsetDateTime,
+}
+
+/// Where reading starts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StartPosition {
+    /// First event of the topic.
+    Earliest,
+    /// Only events published from now on.
+    #[default]
+    Latest,
+    /// The event with this offset.
+    Offset(u64),
+    /// First event published at or after this time.
+    Time(OffsetDateTime),
+}
+
+/// Fetch of the next events for a consumer group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchRequest {
+    pub topic: TopicId,
+    pub group: String,
+    /// Position of a group that has never read the topic.
+    pub start: StartPosition,
+    pub max_events: u32,
+    /// How long to wait for events when none are pending.
+    pub wait: Duration,
+}
+
+/// Read of a topic outside any consumer group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayRequest {
+    pub topic: TopicId,
+    pub from: StartPosition,
+    pub max_events: u32,
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topic_id_requires_gts_type_id() {
+        let topic = TopicId::new("gts.x.core.events.topic.v1~x.demo.orders.created.v1~").unwrap();
+        assert_eq!(
+            topic.as_str(),
+            "gts.x.core.events.topic.v1~x.demo.orders.created.v1~"
+        );
+
+        assert!(matches!(
+            TopicId::new("orders"),
+            Err(EventsBrokerError::InvalidTopic { .. })
+        ));
+        assert!(matches!(
+            TopicId::new("gts.x.core.events.topic.v1~x.demo.orders.created.v1"),
+            Err(EventsBrokerError::InvalidTopic { .. })
+        ));
+    }
+
+    #[test]
+    fn topic_id_deserialization_is_validated() {
+        let ok: Result<TopicId, _> =
+            serde_json::from_str("\"gts.x.core.events.topic.v1~x.demo.orders.created.v1~\"");
+        assert!(ok.is_ok());
+        let bad: Result<TopicId, _> = serde_json::from_str("\"orders\"");
+        assert!(bad.is_err());
+    }
+}
@@ -0,0 +1,292 @@
+//! Consumer-group subscription loop on top of [`EventsBrokerClientV1`].
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use modkit_security::SecurityContext;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::api::EventsBrokerClientV1;
+use crate::models::{Event, FetchRequest, StartPosition, TopicId};
+
+/// Processes the events of a subscription.
+#[async_trait]
+pub trait EventHandler: Send + Sync {
+    /// Handle one event.
+    ///
+    /// An error stops the batch: the events handled so far are committed and
+    /// the failed event is delivered again after `retry_delay`. Handlers must
+    /// therefore be idempotent.
+    async fn handle(&self, event: &Event) -> anyhow::Result<()>;
+}
+
+/// Settings of a subscription.
+#[derive(Debug, Clone)]
+pub struct SubscriptionConfig {
+    pub topic: TopicId,
+    pub group: String,
+    /// Position of a group that has never read the topic.
+    pub start: StartPosition,
+    pub batch_size: u32,
+    /// Long-poll duration of each fetch.
+    pub wait: Duration,
+    /// Pause after a failed fetch or handler.
+    pub retry_delay: Duration,
+}
+
+impl SubscriptionConfig {
+    #[must_use]
+    pub fn new(topic: TopicId, group: impl Into<String>) -> Self {
+        Self {
+            topic,
+            group: group.into(),
+            start: StartPosition::default(),
+            batch_size: 100,
+            wait: Duration::from_secs(10),
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+
+    #[must_use]
+    pub fn with_start(mut self, start: StartPosition) -> Self {
+        self.start = start;
+        self
+    }
+}
+
+/// Run `handler` over the events of `cfg.topic` as a member of `cfg.group`
+/// until `cancel` fires.
+///
+/// Events are handled one at a time in offset order, and the group's
+/// position is committed after each batch.
+#[must_use]
+pub fn subscribe(
+    client: Arc<dyn EventsBrokerClientV1>,
+    ctx: SecurityContext,
+    cfg: SubscriptionConfig,
+    handler: Arc<dyn EventHandler>,
+    cancel: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        tracing::info!(topic = %cfg.topic, group = %cfg.group, "subscription started");
+        while !cancel.is_cancelled() {
+            let req = FetchRequest {
+                topic: cfg.topic.clone(),
+                group: cfg.group.clone(),
+                start: cfg.start,
+                max_events: cfg.batch_size,
+                wait: cfg.wait,
+            };
+            let events = tokio::select! {
+                () = cancel.cancelled() => break,
+                res = client.fetch(&ctx, req) => match res {
+                    Ok(events) => events,
+                    Err(e) => {
+                        tracing::warn!(topic = %cfg.topic, group = %cfg.group, error = %e, "fetch failed");
+                        pause(cfg.retry_delay, &cancel).await;
+                        continue;
+                    }
+                },
+            };
+
+            let mut committable = None;
+            let mut failed = false;
+            for event in &events {
+                if cancel.is_cancelled() {
+                    break;
+                }
+                if let Err(e) = handler.handle(event).await {
+                    tracing::warn!(
+                        topic = %cfg.topic,
+                        group = %cfg.group,
+                        offset = event.offset,
+                        error = %e,
+                        "event handler failed"
+                    );
+                    failed = true;
+                    break;
+                }
+                committable = Some(event.offset);
+            }
+
+            if let Some(offset) = committable
+                && let Err(e) = client.commit(&ctx, &cfg.group, &cfg.topic, offset).await
+            {
+                // The uncommitted events are fetched again and handled twice.
+                tracing::warn!(topic = %cfg.topic, group = %cfg.group, offset, error = %e, "commit failed");
+            }
+            if failed {
+                pause(cfg.retry_delay, &cancel).await;
+            }
+        }
+        tracing::info!(topic = %cfg.topic, group = %cfg.group, "subscription stopped");
+    })
+}
+
+async fn pause(delay: Duration, cancel: &CancellationToken) {
+    tokio::select! {
+        () = cancel.cancelled() => {}
+        () = tokio::time::sleep(delay) => {}
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::sync::Mutex;
+
+    use serde_json::json;
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::errors::EventsBrokerError;
+    use crate::models::{NewEvent, ReplayRequest};
+
+    /// In-memory broker with a single consumer group.
+    #[derive(Default)]
+    struct FakeBroker {
+        events: Mutex<Vec<Event>>,
+        position: Mutex<u64>,
+        commits: Mutex<Vec<u64>>,
+    }
+
+    fn topic() -> TopicId {
+        TopicId::new("gts.x.core.events.topic.v1~x.demo.orders.created.v1~").unwrap()
+    }
+
+    #[async_trait]
+    impl EventsBrokerClientV1 for FakeBroker {
+        async fn publish(
+            &self,
+            _ctx: &SecurityContext,
+            event: NewEvent,
+        ) -> Result<Event, EventsBrokerError> {
+            let mut events = self.events.lock().unwrap();
+            let stored = Event {
+                id: Uuid::new_v4(),
+                topic: event.topic,
+                offset: events.len() as u64 + 1,
+                tenant_id: Uuid::nil(),
+                dedupe_key: event.dedupe_key,
+                payload: event.payload,
+                published_at: OffsetDateTime::now_utc(),
+            };
+            events.push(stored.clone());
+            Ok(stored)
+        }
+
+        async fn fetch(
+            &self,
+            _ctx: &SecurityContext,
+            req: FetchRequest,
+        ) -> Result<Vec<Event>, EventsBrokerError> {
+            let position = *self.position.lock().unwrap();
+            let pending: Vec<Event> = self
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| e.offset >= position)
+                .take(req.max_events as usize)
+                .cloned()
+                .collect();
+            if pending.is_empty() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            Ok(pending)
+        }
+
+        async fn commit(
+            &self,
+            _ctx: &SecurityContext,
+            _group: &str,
+            _topic: &TopicId,
+            offset: u64,
+        ) -> Result<(), EventsBrokerError> {
+            let mut position = self.position.lock().unwrap();
+            *position = (*position).max(offset + 1);
+            self.commits.lock().unwrap().push(offset);
+            Ok(())
+        }
+
+        async fn seek(
+            &self,
+            _ctx: &SecurityContext,
+            _group: &str,
+            _topic: &TopicId,
+            _position: StartPosition,
+        ) -> Result<u64, EventsBrokerError> {
+            unimplemented!()
+        }
+
+        async fn replay(
+            &self,
+            _ctx: &SecurityContext,
+            _req: ReplayRequest,
+        ) -> Result<Vec<Event>, EventsBrokerError> {
+            unimplemented!()
+        }
+    }
+
+    /// Records handled offsets and fails the first attempt at `fail_once`.
+    struct Recorder {
+        seen: Mutex<Vec<u64>>,
+        fail_once: Mutex<Option<u64>>,
+    }
+
+    #[async_trait]
+    impl EventHandler for Recorder {
+        async fn handle(&self, event: &Event) -> anyhow::Result<()> {
+            if self
+                .fail_once
+                .lock()
+                .unwrap()
+                .take_if(|o| *o == event.offset)
+                .is_some()
+            {
+                anyhow::bail!("transient failure");
+            }
+            self.seen.lock().unwrap().push(event.offset);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_event_is_redelivered_after_committing_progress() {
+        let broker = Arc::new(FakeBroker::default());
+        let ctx = SecurityContext::anonymous();
+        for n in 0..4 {
+            broker
+                .publish(&ctx, NewEvent::new(topic(), json!({ "n": n })))
+                .await
+                .unwrap();
+        }
+
+        let recorder = Arc::new(Recorder {
+            seen: Mutex::new(Vec::new()),
+            fail_once: Mutex::new(Some(3)),
+        });
+        let mut cfg = SubscriptionConfig::new(topic(), "g").with_start(StartPosition::Earliest);
+        cfg.wait = Duration::from_millis(5);
+        cfg.retry_delay = Duration::from_millis(5);
+
+        let cancel = CancellationToken::new();
+        let task = subscribe(broker.clone(), ctx, cfg, recorder.clone(), cancel.clone());
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while recorder.seen.lock().unwrap().len() < 4 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("all events handled");
+        cancel.cancel();
+        task.await.unwrap();
+
+        assert_eq!(*recorder.seen.lock().unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(*broker.commits.lock().unwrap(), vec![2, 4]);
+    }
+}
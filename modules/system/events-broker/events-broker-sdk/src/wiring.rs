@@ -0,0 +1,37 @@
+//! Wiring for the events broker SDK.
+//!
+//! Provides `wire_client` to register the gRPC client into `ClientHub`.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use cf_system_sdks::directory::DirectoryClient;
+use modkit::client_hub::ClientHub;
+
+use crate::SERVICE_NAME;
+use crate::api::EventsBrokerClientV1;
+use crate::client::EventsBrokerGrpcClient;
+
+/// Wire the events broker gRPC client into the `ClientHub`.
+///
+/// Out-of-process modules call this to reach the broker; in-process modules
+/// get the local client the events-broker module registers itself.
+///
+/// # Errors
+///
+/// Returns an error if the service cannot be resolved or connected to.
+///
+/// # Example
+/// ```ignore
+/// use events_broker_sdk::{wire_client, EventsBrokerClientV1};
+///
+/// wire_client(&hub, &directory_api).await?;
+/// let broker = hub.get::<dyn EventsBrokerClientV1>()?;
+/// ```
+pub async fn wire_client(hub: &ClientHub, resolver: &dyn DirectoryClient) -> Result<()> {
+    let endpoint = resolver.resolve_grpc_service(SERVICE_NAME).await?;
+    let client = EventsBrokerGrpcClient::connect(&endpoint.uri).await?;
+    hub.register::<dyn EventsBrokerClientV1>(Arc::new(client));
+    tracing::info!(service = SERVICE_NAME, "EventsBrokerClientV1 client wired");
+    Ok(())
+}
@@ -0,0 +1,5 @@
+//! gRPC API for the events broker.
+
+pub mod server;
+
+pub use server::EventsBrokerServiceImpl;
@@ -0,0 +1,142 @@
+//! gRPC server implementation of `EventsBrokerService`.
+//!
+//! Converts requests to SDK models and delegates to the domain service.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use events_broker_sdk::convert::{parse_payload, to_proto_list};
+use events_broker_sdk::proto::{
+    CommitRequest, Event as ProtoEvent, EventList, FetchRequest as ProtoFetch, PublishRequest,
+    ReplayRequest as ProtoReplay, SeekRequest, SeekResponse,
+};
+use events_broker_sdk::{
+    EventsBrokerError, EventsBrokerService, FetchRequest, NewEvent, ReplayRequest, StartPosition,
+    TopicId,
+};
+use modkit_transport_grpc::extract_secctx;
+use tonic::{Request, Response, Status};
+
+use crate::domain::{DomainError, Service};
+
+/// gRPC service wrapping the domain `Service`.
+#[derive(Clone)]
+pub struct EventsBrokerServiceImpl {
+    service: Arc<Service>,
+}
+
+impl EventsBrokerServiceImpl {
+    #[must_use]
+    pub fn new(service: Arc<Service>) -> Self {
+        Self { service }
+    }
+}
+
+fn status(e: &EventsBrokerError) -> Status {
+    match e {
+        EventsBrokerError::InvalidTopic { .. } | EventsBrokerError::Validation { .. } => {
+            Status::invalid_argument(e.to_string())
+        }
+        EventsBrokerError::Unauthorized(_) => Status::unauthenticated(e.to_string()),
+        EventsBrokerError::Transport(_) => Status::unavailable(e.to_string()),
+        EventsBrokerError::Internal(_) => Status::internal(e.to_string()),
+    }
+}
+
+fn domain_status(e: DomainError) -> Status {
+    status(&e.into())
+}
+
+fn topic(value: String) -> Result<TopicId, Status> {
+    TopicId::new(value).map_err(|e| status(&e))
+}
+
+fn position(
+    value: Option<events_broker_sdk::proto::StartPosition>,
+) -> Result<StartPosition, Status> {
+    StartPosition::try_from(value).map_err(|e| status(&e))
+}
+
+#[tonic::async_trait]
+impl EventsBrokerService for EventsBrokerServiceImpl {
+    async fn publish(
+        &self,
+        request: Request<PublishRequest>,
+    ) -> Result<Response<ProtoEvent>, Status> {
+        let ctx = extract_secctx(request.metadata())?;
+        let req = request.into_inner();
+        let event = NewEvent {
+            topic: topic(req.topic)?,
+            payload: parse_payload(&req.payload).map_err(|e| status(&e))?,
+            dedupe_key: req.dedupe_key,
+        };
+        let stored = self
+            .service
+            .publish(&ctx, event)
+            .await
+            .map_err(domain_status)?;
+        Ok(Response::new(
+            ProtoEvent::try_from(&stored).map_err(|e| status(&e))?,
+        ))
+    }
+
+    async fn fetch(&self, request: Request<ProtoFetch>) -> Result<Response<EventList>, Status> {
+        let ctx = extract_secctx(request.metadata())?;
+        let req = request.into_inner();
+        let req = FetchRequest {
+            topic: topic(req.topic)?,
+            group: req.group,
+            start: position(req.start)?,
+            max_events: req.max_events,
+            wait: Duration::from_millis(req.wait_ms),
+        };
+        let events = self.service.fetch(&ctx, req).await.map_err(domain_status)?;
+        Ok(Response::new(
+            to_proto_list(&events).map_err(|e| status(&e))?,
+        ))
+    }
+
+    async fn commit(&self, request: Request<CommitRequest>) -> Result<Response<()>, Status> {
+        let ctx = extract_secctx(request.metadata())?;
+        let req = request.into_inner();
+        self.service
+            .commit(&ctx, &req.group, &topic(req.topic)?, req.offset)
+            .await
+            .map_err(domain_status)?;
+        Ok(Response::new(()))
+    }
+
+    async fn seek(&self, request: Request<SeekRequest>) -> Result<Response<SeekResponse>, Status> {
+        let ctx = extract_secctx(request.metadata())?;
+        let req = request.into_inner();
+        let next_offset = self
+            .service
+            .seek(
+                &ctx,
+                &req.group,
+                &topic(req.topic)?,
+                position(req.position)?,
+            )
+            .await
+            .map_err(domain_status)?;
+        Ok(Response::new(SeekResponse { next_offset }))
+    }
+
+    async fn replay(&self, request: Request<ProtoReplay>) -> Result<Response<EventList>, Status> {
+        let ctx = extract_secctx(request.metadata())?;
+        let req = request.into_inner();
+        let req = ReplayRequest {
+            topic: topic(req.topic)?,
+            from: position(req.from)?,
+            max_events: req.max_events,
+        };
+        let events = self
+            .service
+            .replay(&ctx, req)
+            .await
+            .map_err(domain_status)?;
+        Ok(Response::new(
+            to_proto_list(&events).map_err(|e| status(&e))?,
+        ))
+    }
+}
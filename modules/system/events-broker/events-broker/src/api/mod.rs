@@ -0,0 +1,5 @@
+//! API layer for the events broker.
+//!
+//! Contains transport adapters (gRPC).
+
+pub mod grpc;
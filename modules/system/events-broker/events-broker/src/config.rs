@@ -0,0 +1,31 @@
+//! Configuration for the events broker module.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Module configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+#[allow(clippy::struct_field_names)] // every field is an upper bound
+pub struct EventsBrokerConfig {
+    /// Upper bound of `max_events` in fetch and replay requests.
+    pub max_fetch_events: u32,
+
+    /// Upper bound of the long-poll `wait` of a fetch.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub max_wait: Duration,
+
+    /// Largest accepted payload, in bytes of its JSON encoding.
+    pub max_payload_bytes: usize,
+}
+
+impl Default for EventsBrokerConfig {
+    fn default() -> Self {
+        Self {
+            max_fetch_events: 500,
+            max_wait: Duration::from_secs(20),
+            max_payload_bytes: 1024 * 1024,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! In-process client of the events broker.
+//!
+//! Implements `EventsBrokerClientV1` on the domain service; this is the
+//! client the module registers in `ClientHub`.
+
+use async_trait::async_trait;
+use events_broker_sdk::{
+    Event, EventsBrokerClientV1, EventsBrokerError, FetchRequest, NewEvent, ReplayRequest,
+    StartPosition, TopicId,
+};
+use modkit_security::SecurityContext;
+
+use super::service::Service;
+
+#[async_trait]
+impl EventsBrokerClientV1 for Service {
+    async fn publish(
+        &self,
+        ctx: &SecurityContext,
+        event: NewEvent,
+    ) -> Result<Event, EventsBrokerError> {
+        Ok(Service::publish(self, ctx, event).await?)
+    }
+
+    async fn fetch(
+        &self,
+        ctx: &SecurityContext,
+        req: FetchRequest,
+    ) -> Result<Vec<Event>, EventsBrokerError> {
+        Ok(Service::fetch(self, ctx, req).await?)
+    }
+
+    async fn commit(
+        &self,
+        ctx: &SecurityContext,
+        group: &str,
+        topic: &TopicId,
+        offset: u64,
+    ) -> Result<(), EventsBrokerError> {
+        Ok(Service::commit(self, ctx, group, topic, offset).await?)
+    }
+
+    async fn seek(
+        &self,
+        ctx: &SecurityContext,
+        group: &str,
+        topic: &TopicId,
+        position: StartPosition,
+    ) -> Result<u64, EventsBrokerError> {
+        Ok(Service::seek(self, ctx, group, topic, position).await?)
+    }
+
+    async fn replay(
+        &self,
+        ctx: &SecurityContext,
+        req: ReplayRequest,
+    ) -> Result<Vec<Event>, EventsBrokerError> {
+        Ok(Service::replay(self, ctx, req).await?)
+    }
+}
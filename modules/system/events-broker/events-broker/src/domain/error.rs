@@ -0,0 +1,69 @@
+use events_broker_sdk::EventsBrokerError;
+use modkit_db::DbError;
+use modkit_macros::domain_model;
+
+#[domain_model]
+#[derive(Debug, thiserror::Error)]
+pub enum DomainError {
+    #[error("Validation error on field '{field}': {message}")]
+    Validation { field: String, message: String },
+
+    #[error("Invalid topic '{topic}': {message}")]
+    InvalidTopic { topic: String, message: String },
+
+    #[error("Access forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Internal error: {0}")]
+    Internal(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] DbError),
+}
+
+impl DomainError {
+    pub fn validation(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Validation {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl From<EventsBrokerError> for DomainError {
+    fn from(e: EventsBrokerError) -> Self {
+        match e {
+            EventsBrokerError::InvalidTopic { topic, message } => {
+                Self::InvalidTopic { topic, message }
+            }
+            EventsBrokerError::Validation { field, message } => Self::Validation { field, message },
+            other => Self::Internal(other.to_string()),
+        }
+    }
+}
+
+impl From<authz_resolver_sdk::EnforcerError> for DomainError {
+    fn from(e: authz_resolver_sdk::EnforcerError) -> Self {
+        tracing::error!(error = %e, "AuthZ scope resolution failed");
+        match e {
+            authz_resolver_sdk::EnforcerError::Denied { .. }
+            | authz_resolver_sdk::EnforcerError::CompileFailed(_) => Self::Forbidden(e.to_string()),
+            authz_resolver_sdk::EnforcerError::EvaluationFailed(_) => Self::Internal(e.to_string()),
+        }
+    }
+}
+
+impl From<DomainError> for EventsBrokerError {
+    fn from(e: DomainError) -> Self {
+        match e {
+            DomainError::Validation { field, message } => Self::Validation { field, message },
+            DomainError::InvalidTopic { topic, message } => Self::InvalidTopic { topic, message },
+            DomainError::Forbidden(message) => Self::Unauthorized(message),
+            DomainError::Internal(message) => Self::Internal(message),
+            DomainError::Database(db) => {
+                tracing::error!(error = %db, "events broker database error");
+                Self::internal("database error")
+            }
+        }
+    }
+}
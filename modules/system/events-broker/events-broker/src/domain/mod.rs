@@ -0,0 +1,9 @@
+//! Domain layer for the events broker.
+
+mod client;
+pub mod error;
+mod notifier;
+pub mod service;
+
+pub use error::DomainError;
+pub use service::Service;
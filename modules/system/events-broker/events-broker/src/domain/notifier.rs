@@ -0,0 +1,39 @@
+//! Wake-ups for fetches waiting on a topic.
+
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+use tokio::sync::watch;
+
+/// Per-topic channel carrying the last published offset.
+///
+/// Only publishes made through this process wake waiters; fetches also
+/// re-query when their wait times out, which picks up events published by
+/// other broker instances sharing the database.
+#[derive(Default)]
+pub struct TopicNotifier {
+    topics: Mutex<HashMap<String, watch::Sender<i64>>>,
+}
+
+impl TopicNotifier {
+    /// Receiver that changes on the next publish to `topic`.
+    ///
+    /// Take it before querying for events so a publish landing between the
+    /// query and the wait is not missed.
+    pub fn subscribe(&self, topic: &str) -> watch::Receiver<i64> {
+        let mut topics = self.topics.lock();
+        if let Some(tx) = topics.get(topic) {
+            return tx.subscribe();
+        }
+        let (tx, rx) = watch::channel(0);
+        topics.insert(topic.to_owned(), tx);
+        rx
+    }
+
+    /// Wake the waiters of `topic`.
+    pub fn notify(&self, topic: &str, offset: i64) {
+        if let Some(tx) = self.topics.lock().get(topic) {
+            tx.send_replace(offset);
+        }
+    }
+}
@@ -0,0 +1,723 @@
+//! Domain service for the events broker.
+
+use authz_resolver_sdk::PolicyEnforcer;
+use authz_resolver_sdk::pep::{AccessRequest, ResourceType};
+use events_broker_sdk::{Event, FetchRequest, NewEvent, ReplayRequest, StartPosition, TopicId};
+use modkit_db::{DBProvider, DbError};
+use modkit_macros::domain_model;
+use modkit_security::{SecurityContext, pep_properties};
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use super::error::DomainError;
+use super::notifier::TopicNotifier;
+use crate::config::EventsBrokerConfig;
+use crate::infra::storage::entity::event;
+use crate::infra::storage::{EventRepo, NewEventRow, is_unique_violation};
+
+/// Resource property naming the topic an event is published to.
+const TOPIC_PROPERTY: &str = "topic";
+
+/// Authorization resource type of published events.
+pub(crate) const EVENT_RESOURCE: ResourceType = ResourceType {
+    name: "events_broker.event",
+    supported_properties: &[pep_properties::OWNER_TENANT_ID],
+};
+
+pub(crate) mod actions {
+    pub const PUBLISH: &str = "publish";
+    pub const FETCH: &str = "fetch";
+    pub const COMMIT: &str = "commit";
+    pub const SEEK: &str = "seek";
+    pub const REPLAY: &str = "replay";
+}
+
+const MAX_NAME_LEN: usize = 255;
+
+/// Events broker service.
+///
+/// Appends events to per-topic logs and tracks consumer-group positions,
+/// both in the module database. A fetch returns the events at and after
+/// the group's position; the position only moves on commit, so events that
+/// were fetched but never committed are delivered again.
+///
+/// Events are attributed to the publishing caller's tenant and read back
+/// under the scope the policy enforcer grants. Consumer groups belong to the
+/// caller's tenant.
+#[domain_model]
+pub struct Service {
+    repo: EventRepo,
+    notifier: TopicNotifier,
+    policy_enforcer: PolicyEnforcer,
+    cfg: EventsBrokerConfig,
+}
+
+impl Service {
+    #[must_use]
+    pub fn new(
+        db: DBProvider<DbError>,
+        policy_enforcer: PolicyEnforcer,
+        cfg: EventsBrokerConfig,
+    ) -> Self {
+        Self {
+            repo: EventRepo::new(db),
+            notifier: TopicNotifier::default(),
+            policy_enforcer,
+            cfg,
+        }
+    }
+
+    /// Append `new` to its topic, attributed to the caller's tenant.
+    ///
+    /// With a dedupe key the tenant already used on the topic, the stored
+    /// event is returned and nothing is appended.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::Validation` for an oversized payload or an
+    /// invalid dedupe key, `DomainError::Forbidden` if publishing to the
+    /// topic is denied, `DomainError::Database` if storage fails.
+    pub async fn publish(
+        &self,
+        ctx: &SecurityContext,
+        new: NewEvent,
+    ) -> Result<Event, DomainError> {
+        let payload = new.payload.to_string();
+        if payload.len() > self.cfg.max_payload_bytes {
+            return Err(DomainError::validation(
+                "payload",
+                format!("must not exceed {} bytes", self.cfg.max_payload_bytes),
+            ));
+        }
+        let tenant_id = ctx.subject_tenant_id();
+        self.policy_enforcer
+            .access_scope_with(
+                ctx,
+                &EVENT_RESOURCE,
+                actions::PUBLISH,
+                None,
+                &AccessRequest::new()
+                    .resource_property(pep_properties::OWNER_TENANT_ID, tenant_id)
+                    .resource_property(TOPIC_PROPERTY, new.topic.as_str())
+                    .require_constraints(false),
+            )
+            .await?;
+        if let Some(key) = &new.dedupe_key {
+            validate_name("dedupe_key", key)?;
+            if let Some(existing) = self
+                .repo
+                .find_by_dedupe_key(tenant_id, new.topic.as_str(), key)
+                .await?
+            {
+                return to_event(existing);
+            }
+        }
+
+        let row = NewEventRow {
+            id: Uuid::now_v7(),
+            topic: new.topic.to_string(),
+            tenant_id,
+            dedupe_key: new.dedupe_key.clone(),
+            payload,
+        };
+        let stored = match self.repo.append(row).await {
+            Ok(stored) => stored,
+            Err(e) => match new.dedupe_key {
+                // A concurrent publish with the same key won the race.
+                Some(key) if is_unique_violation(&e) => self
+                    .repo
+                    .find_by_dedupe_key(tenant_id, new.topic.as_str(), &key)
+                    .await?
+                    .ok_or(e)?,
+                _ => return Err(e.into()),
+            },
+        };
+        self.notifier.notify(&stored.topic, stored.seq);
+        to_event(stored)
+    }
+
+    /// Events the caller may read at and after the position of its tenant's
+    /// group, waiting up to `req.wait` for one to be published when there are
+    /// none.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::Validation` for an invalid group or batch size,
+    /// `DomainError::Forbidden` if fetching is denied, `DomainError::Database`
+    /// if storage fails.
+    pub async fn fetch(
+        &self,
+        ctx: &SecurityContext,
+        req: FetchRequest,
+    ) -> Result<Vec<Event>, DomainError> {
+        validate_name("group", &req.group)?;
+        let limit = self.batch_limit(req.max_events)?;
+        let topic = req.topic.as_str();
+        let tenant_id = self.authorize_group(ctx, actions::FETCH).await?;
+        let scope = self
+            .policy_enforcer
+            .access_scope(ctx, &EVENT_RESOURCE, actions::FETCH, None)
+            .await?;
+
+        let position = if let Some(position) = self
+            .repo
+            .group_position(tenant_id, &req.group, topic)
+            .await?
+        {
+            position
+        } else {
+            let start = self.resolve(topic, req.start).await?;
+            self.repo
+                .init_group_position(tenant_id, &req.group, topic, start)
+                .await?
+        };
+
+        let deadline = Instant::now() + req.wait.min(self.cfg.max_wait);
+        loop {
+            let mut published = self.notifier.subscribe(topic);
+            let events = self
+                .repo
+                .events_from(&scope, topic, position, limit)
+                .await?;
+            if !events.is_empty() || Instant::now() >= deadline {
+                return events.into_iter().map(to_event).collect();
+            }
+            // Woken early by a local publish, or re-query once at the deadline.
+            if tokio::time::timeout_at(deadline, published.changed())
+                .await
+                .is_err()
+            {
+                tracing::trace!(topic, "fetch wait elapsed");
+            }
+        }
+    }
+
+    /// Mark the events up to and including `offset` as processed by the
+    /// tenant's `group`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::Validation` for an invalid group or an offset
+    /// beyond the end of the topic, `DomainError::Forbidden` if committing is
+    /// denied, `DomainError::Database` if storage fails.
+    pub async fn commit(
+        &self,
+        ctx: &SecurityContext,
+        group: &str,
+        topic: &TopicId,
+        offset: u64,
+    ) -> Result<(), DomainError> {
+        validate_name("group", group)?;
+        let tenant_id = self.authorize_group(ctx, actions::COMMIT).await?;
+        let offset = to_db_offset(offset)?;
+        let last = self.repo.last_offset(topic.as_str()).await?;
+        if offset > last {
+            return Err(DomainError::validation(
+                "offset",
+                format!("{offset} is beyond the last offset {last} of the topic"),
+            ));
+        }
+        self.repo
+            .advance_group(tenant_id, group, topic.as_str(), offset + 1)
+            .await?;
+        Ok(())
+    }
+
+    /// Move the position of the tenant's group; returns the offset of the
+    /// next event the group will receive.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::Validation` for an invalid group or position,
+    /// `DomainError::Forbidden` if seeking is denied, `DomainError::Database`
+    /// if storage fails.
+    pub async fn seek(
+        &self,
+        ctx: &SecurityContext,
+        group: &str,
+        topic: &TopicId,
+        position: StartPosition,
+    ) -> Result<u64, DomainError> {
+        validate_name("group", group)?;
+        let tenant_id = self.authorize_group(ctx, actions::SEEK).await?;
+        let position = self.resolve(topic.as_str(), position).await?;
+        self.repo
+            .set_group_position(tenant_id, group, topic.as_str(), position)
+            .await?;
+        from_db_offset(position)
+    }
+
+    /// Events the caller may read from `req.from`, without any consumer
+    /// group.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::Validation` for an invalid batch size or
+    /// position, `DomainError::Forbidden` if replaying is denied,
+    /// `DomainError::Database` if storage fails.
+    pub async fn replay(
+        &self,
+        ctx: &SecurityContext,
+        req: ReplayRequest,
+    ) -> Result<Vec<Event>, DomainError> {
+        let limit = self.batch_limit(req.max_events)?;
+        let scope = self
+            .policy_enforcer
+            .access_scope(ctx, &EVENT_RESOURCE, actions::REPLAY, None)
+            .await?;
+        let topic = req.topic.as_str();
+        let from = self.resolve(topic, req.from).await?;
+        let events = self.repo.events_from(&scope, topic, from, limit).await?;
+        events.into_iter().map(to_event).collect()
+    }
+
+    /// Check that the caller may run `action` on its own tenant's consumer
+    /// groups; returns that tenant.
+    async fn authorize_group(
+        &self,
+        ctx: &SecurityContext,
+        action: &str,
+    ) -> Result<Uuid, DomainError> {
+        let tenant_id = ctx.subject_tenant_id();
+        self.policy_enforcer
+            .access_scope_with(
+                ctx,
+                &EVENT_RESOURCE,
+                action,
+                None,
+                &AccessRequest::new()
+                    .resource_property(pep_properties::OWNER_TENANT_ID, tenant_id)
+                    .require_constraints(false),
+            )
+            .await?;
+        Ok(tenant_id)
+    }
+
+    fn batch_limit(&self, max_events: u32) -> Result<u64, DomainError> {
+        if max_events == 0 {
+            return Err(DomainError::validation(
+                "max_events",
+                "must be greater than 0",
+            ));
+        }
+        Ok(u64::from(max_events.min(self.cfg.max_fetch_events)))
+    }
+
+    /// Offset of the first event at `position`; one past the end of the
+    /// topic when no event is there yet.
+    async fn resolve(&self, topic: &str, position: StartPosition) -> Result<i64, DomainError> {
+        Ok(match position {
+            StartPosition::Earliest => 1,
+            StartPosition::Offset(offset) => to_db_offset(offset)?.max(1),
+            StartPosition::Latest => self.repo.last_offset(topic).await? + 1,
+            StartPosition::Time(at) => {
+                // Read the end first: an event published in between is then
+                // found by the time query instead of being skipped.
+                let end = self.repo.last_offset(topic).await? + 1;
+                self.repo
+                    .first_offset_since(topic, at)
+                    .await?
+                    .unwrap_or(end)
+            }
+        })
+    }
+}
+
+fn validate_name(field: &str, value: &str) -> Result<(), DomainError> {
+    if value.is_empty() || value.len() > MAX_NAME_LEN {
+        return Err(DomainError::validation(
+            field,
+            format!("must be 1 to {MAX_NAME_LEN} bytes long"),
+        ));
+    }
+    if value.chars().any(char::is_control) {
+        return Err(DomainError::validation(
+            field,
+            "must not contain control characters",
+        ));
+    }
+    Ok(())
+}
+
+fn to_db_offset(offset: u64) -> Result<i64, DomainError> {
+    i64::try_from(offset).map_err(|_| DomainError::validation("offset", "out of range"))
+}
+
+fn from_db_offset(offset: i64) -> Result<u64, DomainError> {
+    u64::try_from(offset).map_err(|_| DomainError::Internal(format!("negative offset {offset}")))
+}
+
+fn to_event(model: event::Model) -> Result<Event, DomainError> {
+    let payload = serde_json::from_str(&model.payload)
+        .map_err(|e| DomainError::Internal(format!("stored payload is not JSON: {e}")))?;
+    Ok(Event {
+        id: model.id,
+        topic: TopicId::new(model.topic)?,
+        offset: from_db_offset(model.seq)?,
+        tenant_id: model.tenant_id,
+        dedupe_key: model.dedupe_key,
+        payload,
+        published_at: model.published_at,
+    })
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use authz_resolver_sdk::constraints::{Constraint, EqPredicate, Predicate};
+    use authz_resolver_sdk::models::{
+        EvaluationRequest, EvaluationResponse, EvaluationResponseContext,
+    };
+    use authz_resolver_sdk::{AuthZResolverClient, AuthZResolverError};
+    use serde_json::json;
+    use time::OffsetDateTime;
+
+    use super::*;
+    use crate::infra::storage::test_provider;
+
+    /// Subject denied every action by [`MockAuthZResolver`].
+    const DENIED_SUBJECT: Uuid = Uuid::from_u128(0xD);
+
+    /// Grants every action within the caller's own tenant, except to
+    /// [`DENIED_SUBJECT`].
+    struct MockAuthZResolver;
+
+    #[async_trait]
+    impl AuthZResolverClient for MockAuthZResolver {
+        async fn evaluate(
+            &self,
+            request: EvaluationRequest,
+        ) -> Result<EvaluationResponse, AuthZResolverError> {
+            let tenant = request
+                .subject
+                .properties
+                .get("tenant_id")
+                .and_then(|v| v.as_str())
+                .and_then(|v| Uuid::parse_str(v).ok())
+                .unwrap();
+            Ok(EvaluationResponse {
+                decision: request.subject.id != DENIED_SUBJECT,
+                context: EvaluationResponseContext {
+                    constraints: vec![Constraint {
+                        predicates: vec![Predicate::Eq(EqPredicate::new(
+                            pep_properties::OWNER_TENANT_ID,
+                            tenant,
+                        ))],
+                    }],
+                    deny_reason: None,
+                },
+            })
+        }
+    }
+
+    fn topic() -> TopicId {
+        TopicId::new("gts.x.core.events.topic.v1~x.demo.orders.created.v1~").unwrap()
+    }
+
+    fn tenant_ctx(tenant: u128) -> SecurityContext {
+        SecurityContext::builder()
+            .subject_id(Uuid::from_u128(0xA))
+            .subject_tenant_id(Uuid::from_u128(tenant))
+            .build()
+            .unwrap()
+    }
+
+    fn ctx() -> SecurityContext {
+        tenant_ctx(0x1)
+    }
+
+    fn enforcer() -> PolicyEnforcer {
+        PolicyEnforcer::new(Arc::new(MockAuthZResolver))
+    }
+
+    async fn service() -> Service {
+        Service::new(
+            test_provider().await,
+            enforcer(),
+            EventsBrokerConfig::default(),
+        )
+    }
+
+    async fn publish(svc: &Service, n: u64) -> Event {
+        svc.publish(&ctx(), NewEvent::new(topic(), json!({ "n": n })))
+            .await
+            .unwrap()
+    }
+
+    fn fetch_req(group: &str, start: StartPosition) -> FetchRequest {
+        FetchRequest {
+            topic: topic(),
+            group: group.to_owned(),
+            start,
+            max_events: 10,
+            wait: Duration::ZERO,
+        }
+    }
+
+    fn offsets(events: &[Event]) -> Vec<u64> {
+        events.iter().map(|e| e.offset).collect()
+    }
+
+    #[tokio::test]
+    async fn publish_assigns_contiguous_offsets_and_tenant() {
+        let svc = service().await;
+        let first = publish(&svc, 1).await;
+        let second = publish(&svc, 2).await;
+
+        assert_eq!((first.offset, second.offset), (1, 2));
+        assert_eq!(first.tenant_id, Uuid::from_u128(0x1));
+        assert_eq!(second.payload, json!({ "n": 2 }));
+    }
+
+    #[tokio::test]
+    async fn publish_with_dedupe_key_is_idempotent() {
+        let svc = service().await;
+        let event = NewEvent::new(topic(), json!({})).with_dedupe_key("k1");
+        let first = svc.publish(&ctx(), event.clone()).await.unwrap();
+        let again = svc.publish(&ctx(), event).await.unwrap();
+        assert_eq!(first, again);
+
+        let next = publish(&svc, 3).await;
+        assert_eq!(next.offset, 2);
+    }
+
+    #[tokio::test]
+    async fn publish_rejects_oversized_payload() {
+        let svc = Service::new(
+            test_provider().await,
+            enforcer(),
+            EventsBrokerConfig {
+                max_payload_bytes: 8,
+                ..EventsBrokerConfig::default()
+            },
+        );
+        let err = svc
+            .publish(&ctx(), NewEvent::new(topic(), json!({ "too": "large" })))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Validation { ref field, .. } if field == "payload"));
+    }
+
+    #[tokio::test]
+    async fn denied_publish_appends_nothing() {
+        let svc = service().await;
+        let denied = SecurityContext::builder()
+            .subject_id(DENIED_SUBJECT)
+            .subject_tenant_id(Uuid::from_u128(0x1))
+            .build()
+            .unwrap();
+        let err = svc
+            .publish(&denied, NewEvent::new(topic(), json!({ "n": 1 })))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Forbidden(_)), "{err:?}");
+        assert_eq!(svc.repo.last_offset(topic().as_str()).await.unwrap(), 0);
+
+        // The next permitted publish still gets the first offset.
+        assert_eq!(publish(&svc, 2).await.offset, 1);
+    }
+
+    #[tokio::test]
+    async fn group_redelivers_until_commit() {
+        let svc = service().await;
+        for n in 1..=3 {
+            publish(&svc, n).await;
+        }
+
+        let req = fetch_req("g", StartPosition::Earliest);
+        let events = svc.fetch(&ctx(), req.clone()).await.unwrap();
+        assert_eq!(offsets(&events), vec![1, 2, 3]);
+
+        // Nothing committed: the same events come back.
+        assert_eq!(
+            offsets(&svc.fetch(&ctx(), req.clone()).await.unwrap()),
+            vec![1, 2, 3]
+        );
+
+        svc.commit(&ctx(), "g", &topic(), 2).await.unwrap();
+        assert_eq!(
+            offsets(&svc.fetch(&ctx(), req.clone()).await.unwrap()),
+            vec![3]
+        );
+
+        // Commits never move a group back.
+        svc.commit(&ctx(), "g", &topic(), 1).await.unwrap();
+        assert_eq!(offsets(&svc.fetch(&ctx(), req).await.unwrap()), vec![3]);
+
+        let err = svc.commit(&ctx(), "g", &topic(), 4).await.unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }));
+    }
+
+    #[tokio::test]
+    async fn start_position_applies_to_new_groups_only() {
+        let svc = service().await;
+        publish(&svc, 1).await;
+
+        let latest = fetch_req("late", StartPosition::Latest);
+        assert!(svc.fetch(&ctx(), latest.clone()).await.unwrap().is_empty());
+        publish(&svc, 2).await;
+        assert_eq!(offsets(&svc.fetch(&ctx(), latest).await.unwrap()), vec![2]);
+
+        // The group exists now; a different start position is ignored.
+        let earliest = fetch_req("late", StartPosition::Earliest);
+        assert_eq!(
+            offsets(&svc.fetch(&ctx(), earliest).await.unwrap()),
+            vec![2]
+        );
+
+        let from_offset = fetch_req("other", StartPosition::Offset(2));
+        assert_eq!(
+            offsets(&svc.fetch(&ctx(), from_offset).await.unwrap()),
+            vec![2]
+        );
+    }
+
+    #[tokio::test]
+    async fn seek_and_replay_by_offset_and_time() {
+        let svc = service().await;
+        publish(&svc, 1).await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let cutoff = OffsetDateTime::now_utc();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        publish(&svc, 2).await;
+        publish(&svc, 3).await;
+
+        let req = fetch_req("g", StartPosition::Latest);
+        assert!(svc.fetch(&ctx(), req.clone()).await.unwrap().is_empty());
+
+        assert_eq!(
+            svc.seek(&ctx(), "g", &topic(), StartPosition::Time(cutoff))
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            offsets(&svc.fetch(&ctx(), req.clone()).await.unwrap()),
+            vec![2, 3]
+        );
+
+        assert_eq!(
+            svc.seek(&ctx(), "g", &topic(), StartPosition::Earliest)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            offsets(&svc.fetch(&ctx(), req).await.unwrap()),
+            vec![1, 2, 3]
+        );
+
+        let replay = svc
+            .replay(
+                &ctx(),
+                ReplayRequest {
+                    topic: topic(),
+                    from: StartPosition::Offset(3),
+                    max_events: 10,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(offsets(&replay), vec![3]);
+
+        let future = OffsetDateTime::now_utc() + time::Duration::hours(1);
+        let replay = svc
+            .replay(
+                &ctx(),
+                ReplayRequest {
+                    topic: topic(),
+                    from: StartPosition::Time(future),
+                    max_events: 10,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(replay.is_empty());
+    }
+
+    #[tokio::test]
+    async fn tenants_only_read_their_own_events_and_groups() {
+        let svc = service().await;
+        let (a, b) = (tenant_ctx(0xA), tenant_ctx(0xB));
+        let first = svc
+            .publish(
+                &a,
+                NewEvent::new(topic(), json!({ "n": 1 })).with_dedupe_key("k"),
+            )
+            .await
+            .unwrap();
+        svc.publish(&b, NewEvent::new(topic(), json!({ "n": 2 })))
+            .await
+            .unwrap();
+
+        // B's dedupe key lives next to A's instead of returning A's event.
+        let b_keyed = svc
+            .publish(
+                &b,
+                NewEvent::new(topic(), json!({ "n": 3 })).with_dedupe_key("k"),
+            )
+            .await
+            .unwrap();
+        assert_ne!(b_keyed.id, first.id);
+
+        let req = fetch_req("g", StartPosition::Earliest);
+        assert_eq!(offsets(&svc.fetch(&a, req.clone()).await.unwrap()), vec![1]);
+        assert_eq!(
+            offsets(&svc.fetch(&b, req.clone()).await.unwrap()),
+            vec![2, 3]
+        );
+
+        let replay = ReplayRequest {
+            topic: topic(),
+            from: StartPosition::Earliest,
+            max_events: 10,
+        };
+        let events = svc.replay(&b, replay).await.unwrap();
+        assert!(events.iter().all(|e| e.tenant_id == Uuid::from_u128(0xB)));
+        assert_eq!(offsets(&events), vec![2, 3]);
+
+        // The same group name is a different group in each tenant.
+        svc.commit(&b, "g", &topic(), 3).await.unwrap();
+        assert!(svc.fetch(&b, req.clone()).await.unwrap().is_empty());
+        assert_eq!(offsets(&svc.fetch(&a, req).await.unwrap()), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn fetch_waits_for_a_publish() {
+        let svc = Arc::new(service().await);
+        let mut req = fetch_req("g", StartPosition::Latest);
+        req.wait = Duration::from_secs(10);
+
+        let waiting = tokio::spawn({
+            let svc = svc.clone();
+            async move { svc.fetch(&ctx(), req).await.unwrap() }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        publish(&svc, 1).await;
+
+        let events = tokio::time::timeout(Duration::from_secs(5), waiting)
+            .await
+            .expect("fetch woke up")
+            .unwrap();
+        assert_eq!(offsets(&events), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn fetch_validates_group_and_batch_size() {
+        let svc = service().await;
+        let err = svc
+            .fetch(&ctx(), fetch_req("", StartPosition::Earliest))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Validation { ref field, .. } if field == "group"));
+
+        let mut req = fetch_req("g", StartPosition::Earliest);
+        req.max_events = 0;
+        let err = svc.fetch(&ctx(), req).await.unwrap_err();
+        assert!(matches!(err, DomainError::Validation { ref field, .. } if field == "max_events"));
+    }
+}
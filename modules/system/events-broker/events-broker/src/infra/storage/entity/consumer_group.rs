@@ -0,0 +1,26 @@
+use modkit_db_macros::Scopable;
+use sea_orm::entity::prelude::*;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Position of a consumer group in a topic: the offset of the next event
+/// the group will receive. Groups belong to the tenant that uses them, so
+/// equal group names of different tenants never share a position.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Scopable)]
+#[sea_orm(table_name = "events_broker_consumer_groups")]
+#[secure(tenant_col = "tenant_id", no_resource, no_owner, no_type)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub tenant_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub group_name: String,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub topic: String,
+    pub position: i64,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,27 @@
+use modkit_db_macros::Scopable;
+use sea_orm::entity::prelude::*;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// A published event; `seq` is its offset in the topic. Reads are scoped by
+/// the publishing tenant.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Scopable)]
+#[sea_orm(table_name = "events_broker_events")]
+#[secure(tenant_col = "tenant_id", no_resource, no_owner, no_type)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub topic: String,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub seq: i64,
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub dedupe_key: Option<String>,
+    /// JSON-encoded payload.
+    pub payload: String,
+    pub published_at: OffsetDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
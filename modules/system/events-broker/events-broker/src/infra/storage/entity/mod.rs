@@ -0,0 +1,3 @@
+pub mod consumer_group;
+pub mod event;
+pub mod topic;
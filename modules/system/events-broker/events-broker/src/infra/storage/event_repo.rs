@@ -0,0 +1,335 @@
+use modkit_db::secure::{
+    SecureEntityExt, SecureInsertExt, SecureOnConflict, SecureUpdateExt, secure_insert,
+};
+use modkit_db::{DBProvider, DbError};
+use modkit_security::AccessScope;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, Order, Set};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::entity::{consumer_group, event, topic};
+use super::is_unique_violation;
+
+/// Event to append.
+pub struct NewEventRow {
+    pub id: Uuid,
+    pub topic: String,
+    pub tenant_id: Uuid,
+    pub dedupe_key: Option<String>,
+    pub payload: String,
+}
+
+/// Topics, their events and consumer-group positions in the module database.
+///
+/// Topics and their offsets are shared by all tenants. Event reads run under
+/// the caller's access scope, and consumer groups and dedupe keys belong to
+/// one tenant.
+pub struct EventRepo {
+    db: DBProvider<DbError>,
+}
+
+impl EventRepo {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>) -> Self {
+        Self { db }
+    }
+
+    /// Append an event and assign it the next offset of its topic.
+    ///
+    /// The topic row is created on first publish. Incrementing
+    /// `last_offset` locks it until commit, so concurrent publishers take
+    /// turns and a rolled-back publish leaves no gap.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if a query fails; a unique violation means the
+    /// dedupe key is already taken on the topic.
+    pub async fn append(&self, row: NewEventRow) -> Result<event::Model, DbError> {
+        self.db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let all = AccessScope::allow_all();
+                    let now = OffsetDateTime::now_utc();
+
+                    let bump = SecureOnConflict::<topic::Entity>::columns([topic::Column::Topic])
+                        .value(
+                        topic::Column::LastOffset,
+                        Expr::col((topic::Entity, topic::Column::LastOffset)).add(1),
+                    )?;
+                    topic::Entity::insert(topic::ActiveModel {
+                        topic: Set(row.topic.clone()),
+                        last_offset: Set(1),
+                        created_at: Set(now),
+                    })
+                    .secure()
+                    .scope_unchecked(&all)?
+                    .on_conflict(bump)
+                    .exec(tx)
+                    .await?;
+
+                    let offset = topic::Entity::find()
+                        .secure()
+                        .scope_with(&all)
+                        .filter(Condition::all().add(topic::Column::Topic.eq(row.topic.as_str())))
+                        .one(tx)
+                        .await?
+                        .map(|t| t.last_offset)
+                        .ok_or_else(|| {
+                            DbError::Other(anyhow::anyhow!("topic '{}' vanished", row.topic))
+                        })?;
+
+                    let am = event::ActiveModel {
+                        topic: Set(row.topic),
+                        seq: Set(offset),
+                        id: Set(row.id),
+                        tenant_id: Set(row.tenant_id),
+                        dedupe_key: Set(row.dedupe_key),
+                        payload: Set(row.payload),
+                        published_at: Set(now),
+                    };
+                    Ok(secure_insert::<event::Entity>(am, &all, tx).await?)
+                })
+            })
+            .await
+    }
+
+    /// The event `tenant_id` published on `topic` with `dedupe_key`, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn find_by_dedupe_key(
+        &self,
+        tenant_id: Uuid,
+        topic: &str,
+        dedupe_key: &str,
+    ) -> Result<Option<event::Model>, DbError> {
+        let conn = self.db.conn()?;
+        Ok(event::Entity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .filter(
+                Condition::all()
+                    .add(event::Column::Topic.eq(topic))
+                    .add(event::Column::DedupeKey.eq(dedupe_key)),
+            )
+            .one(&conn)
+            .await?)
+    }
+
+    /// Offset of the last event of `topic`; 0 if nothing was published.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn last_offset(&self, topic: &str) -> Result<i64, DbError> {
+        let conn = self.db.conn()?;
+        let row = topic::Entity::find()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .filter(Condition::all().add(topic::Column::Topic.eq(topic)))
+            .one(&conn)
+            .await?;
+        Ok(row.map_or(0, |t| t.last_offset))
+    }
+
+    /// Offset of the first event of `topic` published at or after `at`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn first_offset_since(
+        &self,
+        topic: &str,
+        at: OffsetDateTime,
+    ) -> Result<Option<i64>, DbError> {
+        let conn = self.db.conn()?;
+        let row = event::Entity::find()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .filter(
+                Condition::all()
+                    .add(event::Column::Topic.eq(topic))
+                    .add(event::Column::PublishedAt.gte(at)),
+            )
+            .order_by(event::Column::Seq, Order::Asc)
+            .limit(1)
+            .one(&conn)
+            .await?;
+        Ok(row.map(|e| e.seq))
+    }
+
+    /// Up to `limit` events of `topic` visible under `scope` from offset
+    /// `from`, in offset order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn events_from(
+        &self,
+        scope: &AccessScope,
+        topic: &str,
+        from: i64,
+        limit: u64,
+    ) -> Result<Vec<event::Model>, DbError> {
+        let conn = self.db.conn()?;
+        Ok(event::Entity::find()
+            .secure()
+            .scope_with(scope)
+            .filter(
+                Condition::all()
+                    .add(event::Column::Topic.eq(topic))
+                    .add(event::Column::Seq.gte(from)),
+            )
+            .order_by(event::Column::Seq, Order::Asc)
+            .limit(limit)
+            .all(&conn)
+            .await?)
+    }
+
+    /// Position of the tenant's `group` in `topic`, if the group has read it
+    /// before.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn group_position(
+        &self,
+        tenant_id: Uuid,
+        group: &str,
+        topic: &str,
+    ) -> Result<Option<i64>, DbError> {
+        let conn = self.db.conn()?;
+        let row = consumer_group::Entity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .filter(group_key(group, topic))
+            .one(&conn)
+            .await?;
+        Ok(row.map(|g| g.position))
+    }
+
+    /// Store `position` as the group's position unless the group already
+    /// has one; returns the stored position.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if a query fails.
+    pub async fn init_group_position(
+        &self,
+        tenant_id: Uuid,
+        group: &str,
+        topic: &str,
+        position: i64,
+    ) -> Result<i64, DbError> {
+        let conn = self.db.conn()?;
+        let am = consumer_group::ActiveModel {
+            tenant_id: Set(tenant_id),
+            group_name: Set(group.to_owned()),
+            topic: Set(topic.to_owned()),
+            position: Set(position),
+            updated_at: Set(OffsetDateTime::now_utc()),
+        };
+        match secure_insert::<consumer_group::Entity>(am, &AccessScope::allow_all(), &conn).await {
+            Ok(row) => Ok(row.position),
+            Err(e) => {
+                let e = DbError::from(e);
+                if !is_unique_violation(&e) {
+                    return Err(e);
+                }
+                // Another member of the group got there first.
+                self.group_position(tenant_id, group, topic)
+                    .await?
+                    .ok_or_else(|| DbError::Other(anyhow::anyhow!("group '{group}' vanished")))
+            }
+        }
+    }
+
+    /// Move the group's position forward to `position`; positions are never
+    /// moved back, so a late commit cannot undo a newer one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if a query fails.
+    pub async fn advance_group(
+        &self,
+        tenant_id: Uuid,
+        group: &str,
+        topic: &str,
+        position: i64,
+    ) -> Result<(), DbError> {
+        let conn = self.db.conn()?;
+        let advance = || {
+            consumer_group::Entity::update_many()
+                .secure()
+                .scope_with(&AccessScope::for_tenant(tenant_id))
+                .filter(group_key(group, topic).add(consumer_group::Column::Position.lt(position)))
+                .col_expr(consumer_group::Column::Position, Expr::value(position))
+                .col_expr(
+                    consumer_group::Column::UpdatedAt,
+                    Expr::value(OffsetDateTime::now_utc()),
+                )
+        };
+        let updated = advance().exec(&conn).await?;
+        if updated.rows_affected == 0
+            && self
+                .group_position(tenant_id, group, topic)
+                .await?
+                .is_none()
+        {
+            // Committing without a prior fetch: the commit defines the start.
+            let stored = self
+                .init_group_position(tenant_id, group, topic, position)
+                .await?;
+            if stored < position {
+                advance().exec(&conn).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the group's position, backwards or forwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn set_group_position(
+        &self,
+        tenant_id: Uuid,
+        group: &str,
+        topic: &str,
+        position: i64,
+    ) -> Result<(), DbError> {
+        let conn = self.db.conn()?;
+        let all = AccessScope::allow_all();
+        let on_conflict = SecureOnConflict::<consumer_group::Entity>::columns([
+            consumer_group::Column::TenantId,
+            consumer_group::Column::GroupName,
+            consumer_group::Column::Topic,
+        ])
+        .update_columns([
+            consumer_group::Column::Position,
+            consumer_group::Column::UpdatedAt,
+        ])?;
+        consumer_group::Entity::insert(consumer_group::ActiveModel {
+            tenant_id: Set(tenant_id),
+            group_name: Set(group.to_owned()),
+            topic: Set(topic.to_owned()),
+            position: Set(position),
+            updated_at: Set(OffsetDateTime::now_utc()),
+        })
+        .secure()
+        .scope_unchecked(&all)?
+        .on_conflict(on_conflict)
+        .exec(&conn)
+        .await?;
+        Ok(())
+    }
+}
+
+fn group_key(group: &str, topic: &str) -> Condition {
+    Condition::all()
+        .add(consumer_group::Column::GroupName.eq(group))
+        .add(consumer_group::Column::Topic.eq(topic))
+}
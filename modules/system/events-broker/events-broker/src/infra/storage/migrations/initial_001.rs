@@ -0,0 +1,162 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let statements = match backend {
+            sea_orm::DatabaseBackend::Postgres => POSTGRES_UP,
+            sea_orm::DatabaseBackend::MySql => MYSQL_UP,
+            sea_orm::DatabaseBackend::Sqlite => SQLITE_UP,
+        };
+
+        // One statement per call: MySQL rejects multi-statement strings.
+        for sql in statements {
+            conn.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        for table in [
+            "events_broker_consumer_groups",
+            "events_broker_events",
+            "events_broker_topics",
+        ] {
+            conn.execute_unprepared(&format!("DROP TABLE IF EXISTS {table}"))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+// NULL dedupe keys never collide in a unique index on any backend, so
+// `(topic, tenant_id, dedupe_key)` only constrains events published with a key.
+
+const POSTGRES_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS events_broker_topics (
+    topic        VARCHAR(255) PRIMARY KEY NOT NULL,
+    last_offset  BIGINT NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL
+)
+",
+    r"
+CREATE TABLE IF NOT EXISTS events_broker_events (
+    topic         VARCHAR(255) NOT NULL,
+    seq           BIGINT NOT NULL,
+    id            UUID NOT NULL,
+    tenant_id     UUID NOT NULL,
+    dedupe_key    VARCHAR(255),
+    payload       TEXT NOT NULL,
+    published_at  TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (topic, seq)
+)
+",
+    r"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_events_broker_events_id ON events_broker_events (id)
+",
+    r"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_events_broker_events_dedupe
+    ON events_broker_events (topic, tenant_id, dedupe_key)
+",
+    r"
+CREATE INDEX IF NOT EXISTS idx_events_broker_events_published
+    ON events_broker_events (topic, published_at)
+",
+    r"
+CREATE TABLE IF NOT EXISTS events_broker_consumer_groups (
+    tenant_id   UUID NOT NULL,
+    group_name  VARCHAR(255) NOT NULL,
+    topic       VARCHAR(255) NOT NULL,
+    position    BIGINT NOT NULL,
+    updated_at  TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (tenant_id, group_name, topic)
+)
+",
+];
+
+const MYSQL_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS events_broker_topics (
+    topic        VARCHAR(255) PRIMARY KEY NOT NULL,
+    last_offset  BIGINT NOT NULL,
+    created_at   TIMESTAMP(6) NOT NULL
+)
+",
+    r"
+CREATE TABLE IF NOT EXISTS events_broker_events (
+    topic         VARCHAR(255) NOT NULL,
+    seq           BIGINT NOT NULL,
+    id            VARCHAR(36) NOT NULL,
+    tenant_id     VARCHAR(36) NOT NULL,
+    dedupe_key    VARCHAR(255) NULL,
+    payload       LONGTEXT NOT NULL,
+    published_at  TIMESTAMP(6) NOT NULL,
+    PRIMARY KEY (topic, seq),
+    UNIQUE KEY uq_events_broker_events_id (id),
+    UNIQUE KEY uq_events_broker_events_dedupe (topic, tenant_id, dedupe_key),
+    KEY idx_events_broker_events_published (topic, published_at)
+)
+",
+    r"
+CREATE TABLE IF NOT EXISTS events_broker_consumer_groups (
+    tenant_id   VARCHAR(36) NOT NULL,
+    group_name  VARCHAR(255) NOT NULL,
+    topic       VARCHAR(255) NOT NULL,
+    position    BIGINT NOT NULL,
+    updated_at  TIMESTAMP(6) NOT NULL,
+    PRIMARY KEY (tenant_id, group_name, topic)
+)
+",
+];
+
+const SQLITE_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS events_broker_topics (
+    topic        TEXT PRIMARY KEY NOT NULL,
+    last_offset  INTEGER NOT NULL,
+    created_at   TEXT NOT NULL
+)
+",
+    r"
+CREATE TABLE IF NOT EXISTS events_broker_events (
+    topic         TEXT NOT NULL,
+    seq           INTEGER NOT NULL,
+    id            TEXT NOT NULL,
+    tenant_id     TEXT NOT NULL,
+    dedupe_key    TEXT,
+    payload       TEXT NOT NULL,
+    published_at  TEXT NOT NULL,
+    PRIMARY KEY (topic, seq)
+)
+",
+    r"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_events_broker_events_id ON events_broker_events (id)
+",
+    r"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_events_broker_events_dedupe
+    ON events_broker_events (topic, tenant_id, dedupe_key)
+",
+    r"
+CREATE INDEX IF NOT EXISTS idx_events_broker_events_published
+    ON events_broker_events (topic, published_at)
+",
+    r"
+CREATE TABLE IF NOT EXISTS events_broker_consumer_groups (
+    tenant_id   TEXT NOT NULL,
+    group_name  TEXT NOT NULL,
+    topic       TEXT NOT NULL,
+    position    INTEGER NOT NULL,
+    updated_at  TEXT NOT NULL,
+    PRIMARY KEY (tenant_id, group_name, topic)
+)
+",
+];
@@ -0,0 +1,52 @@
+//! SQL storage of topics, events and consumer-group positions on top of the
+//! `modkit-db` secure ORM.
+//!
+//! `events_broker_topics` holds the last offset of each topic,
+//! `events_broker_events` the events keyed by (topic, offset), and
+//! `events_broker_consumer_groups` the next offset each group will read.
+
+pub mod entity;
+mod event_repo;
+pub mod migrations;
+
+pub use event_repo::{EventRepo, NewEventRow};
+
+use modkit_db::DbError;
+use modkit_db::secure::ScopeError;
+
+/// `true` if `e` is a unique-index violation, i.e. the row already exists.
+#[must_use]
+pub fn is_unique_violation(e: &DbError) -> bool {
+    let db_err = match e {
+        DbError::Sea(db) => Some(db),
+        DbError::Other(other) => match other.downcast_ref::<ScopeError>() {
+            Some(ScopeError::Db(db)) => Some(db),
+            _ => None,
+        },
+        _ => None,
+    };
+    matches!(
+        db_err.and_then(sea_orm::DbErr::sql_err),
+        Some(sea_orm::SqlErr::UniqueConstraintViolation(_))
+    )
+}
+
+#[cfg(test)]
+pub(crate) async fn test_provider() -> modkit_db::DBProvider<DbError> {
+    use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::{ConnectOpts, connect_db};
+    use sea_orm_migration::MigratorTrait;
+
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db("sqlite::memory:", opts)
+        .await
+        .expect("connect in-memory database");
+    run_migrations_for_testing(&db, migrations::Migrator::migrations())
+        .await
+        .expect("run migrations");
+    modkit_db::DBProvider::new(db)
+}
@@ -0,0 +1,39 @@
+//! Events Broker Module
+//!
+//! Pub/sub between modules with durable delivery. Events are appended to
+//! topics (GTS type ids) and numbered by a contiguous per-topic offset;
+//! consumer groups keep their committed position in SQL, so delivery is
+//! at-least-once and survives restarts. Groups can seek back to an offset
+//! or a point in time, and any reader can replay a topic.
+//!
+//! In-process modules get `EventsBrokerClientV1` from `ClientHub`;
+//! out-of-process modules reach the same API over gRPC through
+//! `events_broker_sdk::wire_client`.
+//!
+//! ## Configuration
+//!
+//! ```yaml
+//! modules:
+//!   events-broker:
+//!     database:
+//!       server: "sqlite_users"
+//!       file: "events_broker.db"
+//!     config:
+//!       max_fetch_events: 500
+//!       max_wait: "20s"
+//!       max_payload_bytes: 1048576
+//! ```
+
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod module;
+pub use module::EventsBrokerModule;
+
+#[doc(hidden)]
+pub mod api;
+#[doc(hidden)]
+pub mod config;
+#[doc(hidden)]
+pub mod domain;
+#[doc(hidden)]
+pub mod infra;
@@ -0,0 +1,93 @@
+//! Events broker module.
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use authz_resolver_sdk::{AuthZResolverClient, PolicyEnforcer};
+use events_broker_sdk::{EventsBrokerClientV1, EventsBrokerServiceServer, SERVICE_NAME};
+use modkit::Module;
+use modkit::context::ModuleCtx;
+use modkit::contracts::{GrpcServiceCapability, RegisterGrpcServiceFn};
+use tracing::info;
+
+use crate::api::grpc::EventsBrokerServiceImpl;
+use crate::config::EventsBrokerConfig;
+use crate::domain::Service;
+
+/// Events broker module.
+///
+/// Registers the in-process `EventsBrokerClientV1` in `ClientHub` and
+/// exports the same API over gRPC for out-of-process modules.
+#[modkit::module(
+    name = "events-broker",
+    deps = ["authz-resolver"],
+    capabilities = [db, grpc]
+)]
+pub struct EventsBrokerModule {
+    service: OnceLock<Arc<Service>>,
+}
+
+impl Default for EventsBrokerModule {
+    fn default() -> Self {
+        Self {
+            service: OnceLock::new(),
+        }
+    }
+}
+
+impl modkit::contracts::DatabaseCapability for EventsBrokerModule {
+    fn migrations(&self) -> Vec<Box<dyn sea_orm_migration::MigrationTrait>> {
+        use sea_orm_migration::MigratorTrait;
+        crate::infra::storage::migrations::Migrator::migrations()
+    }
+}
+
+#[async_trait]
+impl Module for EventsBrokerModule {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        let cfg: EventsBrokerConfig = ctx.config()?;
+        info!(
+            max_fetch_events = cfg.max_fetch_events,
+            max_wait = ?cfg.max_wait,
+            max_payload_bytes = cfg.max_payload_bytes,
+            "Loaded events broker configuration"
+        );
+
+        let authz = ctx
+            .client_hub()
+            .get::<dyn AuthZResolverClient>()
+            .map_err(|e| anyhow::anyhow!("failed to get AuthZ resolver: {e}"))?;
+        let policy_enforcer = PolicyEnforcer::new(authz);
+
+        let service = Arc::new(Service::new(ctx.db_required()?, policy_enforcer, cfg));
+        self.service
+            .set(service.clone())
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
+        let api: Arc<dyn EventsBrokerClientV1> = service;
+        ctx.client_hub().register::<dyn EventsBrokerClientV1>(api);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl GrpcServiceCapability for EventsBrokerModule {
+    async fn get_grpc_services(
+        &self,
+        _ctx: &ModuleCtx,
+    ) -> anyhow::Result<Vec<RegisterGrpcServiceFn>> {
+        let service = self
+            .service
+            .get()
+            .ok_or_else(|| anyhow::anyhow!("Service not initialized"))?
+            .clone();
+        let svc = EventsBrokerServiceServer::new(EventsBrokerServiceImpl::new(service));
+
+        Ok(vec![RegisterGrpcServiceFn {
+            service_name: SERVICE_NAME,
+            register: Box::new(move |routes| {
+                routes.add_service(svc.clone());
+            }),
+        }])
+    }
+}
@@ -0,0 +1,39 @@
+//! Public API trait for the license resolver.
+
+use async_trait::async_trait;
+use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+use crate::error::LicenseResolverError;
+use crate::models::GlobalFeatures;
+
+/// Public API trait for the license resolver.
+///
+/// This trait is registered in `ClientHub` by the module and
+/// can be consumed by other modules (primarily the API gateway):
+///
+/// ```ignore
+/// let licenses = hub.get::<dyn LicenseResolverClient>()?;
+///
+/// let features = licenses.get_global_features(&ctx, tenant_id).await?;
+/// let missing = features.missing(&required);
+/// ```
+#[async_trait]
+pub trait LicenseResolverClient: Send + Sync {
+    /// Global features licensed to `tenant_id`.
+    ///
+    /// Global features are not scoped to a particular resource: they gate
+    /// whole endpoints or modules for the tenant. A tenant without a
+    /// license gets an empty feature set, not an error.
+    ///
+    /// # Errors
+    ///
+    /// - `NoPluginAvailable` if no license plugin is registered
+    /// - `ServiceUnavailable` if the plugin is not ready
+    /// - `Internal` for unexpected errors
+    async fn get_global_features(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<GlobalFeatures, LicenseResolverError>;
+}
@@ -0,0 +1,19 @@
+//! Error types for the license resolver module.
+
+use thiserror::Error;
+
+/// Errors that can occur when using the license resolver API.
+#[derive(Debug, Error)]
+pub enum LicenseResolverError {
+    /// No license plugin is available to handle the request.
+    #[error("no plugin available")]
+    NoPluginAvailable,
+
+    /// The plugin is not available yet.
+    #[error("service unavailable: {0}")]
+    ServiceUnavailable(String),
+
+    /// An internal error occurred.
+    #[error("internal error: {0}")]
+    Internal(String),
+}
@@ -0,0 +1,28 @@
+//! GTS schema definitions for license resolver plugins.
+//!
+//! This module defines the GTS type for license resolver plugin instances.
+//! Plugins register instances of this type with the types-registry to be
+//! discovered by the gateway.
+
+use gts_macros::struct_to_gts_schema;
+use modkit::gts::BaseModkitPluginV1;
+
+/// GTS type definition for license resolver plugin instances.
+///
+/// Each plugin registers an instance of this type with its vendor-specific
+/// instance ID. The gateway discovers plugins by querying types-registry
+/// for instances matching this schema.
+///
+/// # Instance ID Format
+///
+/// ```text
+/// gts.x.core.modkit.plugin.v1~<vendor>.<package>.license_resolver.plugin.v1~
+/// ```
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = BaseModkitPluginV1,
+    schema_id = "gts.x.core.modkit.plugin.v1~x.core.license_resolver.plugin.v1~",
+    description = "License Resolver plugin specification",
+    properties = ""
+)]
+pub struct LicenseResolverPluginSpecV1;
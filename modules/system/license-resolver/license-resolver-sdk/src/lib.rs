@@ -0,0 +1,39 @@
+//! License Resolver SDK
+//!
+//! This crate provides the public API for the `license-resolver` module:
+//!
+//! - [`LicenseResolverClient`] - Public API trait for consumers
+//! - [`LicenseResolverPluginClient`] - Plugin API trait for implementations
+//! - [`GlobalFeatures`] - Features licensed to a tenant
+//! - [`LicenseResolverError`] - Error types
+//! - [`LicenseResolverPluginSpecV1`] - GTS schema for plugin discovery
+//!
+//! ## Usage
+//!
+//! Consumers obtain the client from `ClientHub`:
+//!
+//! ```ignore
+//! use license_resolver_sdk::LicenseResolverClient;
+//!
+//! let licenses = hub.get::<dyn LicenseResolverClient>()?;
+//!
+//! let features = licenses
+//!     .get_global_features(&ctx, ctx.subject_tenant_id())
+//!     .await?;
+//! if !features.has("gts.x.core.lic.feat.v1~x.acme.reports.export.v1") {
+//!     // ...
+//! }
+//! ```
+
+pub mod api;
+pub mod error;
+pub mod gts;
+pub mod models;
+pub mod plugin_api;
+
+// Re-export main types at crate root
+pub use api::LicenseResolverClient;
+pub use error::LicenseResolverError;
+pub use gts::LicenseResolverPluginSpecV1;
+pub use models::{BASE_FEATURE, GlobalFeatures};
+pub use plugin_api::LicenseResolverPluginClient;
@@ -0,0 +1,68 @@
+//! Domain models for the license resolver module.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// The feature every licensed tenant has; gates the platform itself.
+pub const BASE_FEATURE: &str = "gts.x.core.lic.feat.v1~x.core.global.base.v1";
+
+/// Global features licensed to a tenant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlobalFeatures {
+    pub tenant_id: Uuid,
+    /// GTS ids of the licensed features.
+    pub features: BTreeSet<String>,
+    /// Number of licensed seats, if the license limits them.
+    pub seats: Option<u32>,
+    /// When the license stops granting the features, if it expires.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+impl GlobalFeatures {
+    /// A tenant without any licensed feature.
+    #[must_use]
+    pub fn none(tenant_id: Uuid) -> Self {
+        Self {
+            tenant_id,
+            features: BTreeSet::new(),
+            seats: None,
+            expires_at: None,
+        }
+    }
+
+    /// Whether `feature` is licensed.
+    #[must_use]
+    pub fn has(&self, feature: &str) -> bool {
+        self.features.contains(feature)
+    }
+
+    /// The features of `required` that are not licensed, in order.
+    #[must_use]
+    pub fn missing<'a>(&self, required: &'a [String]) -> Vec<&'a str> {
+        required
+            .iter()
+            .map(String::as_str)
+            .filter(|f| !self.has(f))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_lists_unlicensed_features() {
+        let mut features = GlobalFeatures::none(Uuid::nil());
+        features.features.insert(BASE_FEATURE.to_owned());
+
+        let required = vec![BASE_FEATURE.to_owned(), "x.reports".to_owned()];
+        assert_eq!(features.missing(&required), vec!["x.reports"]);
+        assert!(features.missing(&required[..1]).is_empty());
+    }
+}
@@ -0,0 +1,31 @@
+//! Plugin API trait for license resolver implementations.
+//!
+//! Plugins implement this trait to provide license information.
+//! The gateway discovers plugins via GTS types-registry and delegates
+//! API calls to the selected plugin.
+
+use async_trait::async_trait;
+use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+use crate::error::LicenseResolverError;
+use crate::models::GlobalFeatures;
+
+/// Plugin API trait for license resolver implementations.
+///
+/// Each plugin registers this trait with a scoped `ClientHub` entry
+/// using its GTS instance ID as the scope.
+#[async_trait]
+pub trait LicenseResolverPluginClient: Send + Sync {
+    /// Global features licensed to `tenant_id`.
+    ///
+    /// # Errors
+    ///
+    /// - `ServiceUnavailable` if the license source cannot be reached
+    /// - `Internal` for unexpected errors
+    async fn get_global_features(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<GlobalFeatures, LicenseResolverError>;
+}
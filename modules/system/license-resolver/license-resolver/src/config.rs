@@ -0,0 +1,36 @@
+//! Configuration for the license resolver.
+
+use std::time::Duration;
+
+use modkit::cache::CacheConfig;
+use serde::Deserialize;
+
+/// Configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LicenseResolverConfig {
+    /// Vendor selector used to pick a plugin implementation.
+    ///
+    /// The resolver queries types-registry for plugin instances matching
+    /// this vendor and selects the one with lowest priority.
+    pub vendor: String,
+
+    /// Caching of licensed features, keyed by tenant (enabled by default).
+    ///
+    /// The API gateway checks the features of every licensed request, so
+    /// license changes take effect after up to the TTL.
+    pub cache: CacheConfig,
+}
+
+impl Default for LicenseResolverConfig {
+    fn default() -> Self {
+        Self {
+            vendor: "hyperspot".to_owned(),
+            cache: CacheConfig {
+                enabled: true,
+                ttl: Duration::from_secs(60),
+                ..CacheConfig::default()
+            },
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Local (in-process) client for the license resolver.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use license_resolver_sdk::{GlobalFeatures, LicenseResolverClient, LicenseResolverError};
+use modkit_macros::domain_model;
+use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+use super::{DomainError, Service};
+
+/// Local client wrapping the service.
+///
+/// Registered in `ClientHub` by the module during `init()`.
+#[domain_model]
+pub struct LicenseResolverLocalClient {
+    svc: Arc<Service>,
+}
+
+impl LicenseResolverLocalClient {
+    #[must_use]
+    pub fn new(svc: Arc<Service>) -> Self {
+        Self { svc }
+    }
+}
+
+fn log_and_convert(op: &str, e: DomainError) -> LicenseResolverError {
+    tracing::error!(operation = op, error = ?e, "license_resolver call failed");
+    e.into()
+}
+
+#[async_trait]
+impl LicenseResolverClient for LicenseResolverLocalClient {
+    async fn get_global_features(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<GlobalFeatures, LicenseResolverError> {
+        self.svc
+            .get_global_features(ctx, tenant_id)
+            .await
+            .map_err(|e| log_and_convert("get_global_features", e))
+    }
+}
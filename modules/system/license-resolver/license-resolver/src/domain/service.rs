@@ -0,0 +1,175 @@
+//! Domain service for the license resolver.
+//!
+//! Plugin discovery is lazy: resolved on first API call after
+//! types-registry is ready.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use license_resolver_sdk::{
+    GlobalFeatures, LicenseResolverPluginClient, LicenseResolverPluginSpecV1,
+};
+use modkit::cache::{Cache, CacheConfig};
+use modkit::client_hub::{ClientHub, ClientScope};
+use modkit::plugins::{GtsPluginSelector, choose_plugin_instance};
+use modkit::telemetry::ThrottledLog;
+use modkit_macros::domain_model;
+use modkit_security::SecurityContext;
+use time::OffsetDateTime;
+use tracing::info;
+use types_registry_sdk::{ListQuery, TypesRegistryClient};
+use uuid::Uuid;
+
+use super::error::DomainError;
+
+/// Throttle interval for unavailable plugin warnings.
+const UNAVAILABLE_LOG_THROTTLE: Duration = Duration::from_secs(10);
+
+/// License resolver service.
+///
+/// Discovers plugins via types-registry and delegates license lookups.
+///
+/// With `cache.enabled`, features are cached per tenant regardless of the
+/// caller: a tenant's license is the same for every subject asking. An
+/// entry never outlives the license it was read from.
+#[domain_model]
+pub struct Service {
+    hub: Arc<ClientHub>,
+    vendor: String,
+    selector: GtsPluginSelector,
+    unavailable_log_throttle: ThrottledLog,
+    cache: Option<Cache<Uuid, GlobalFeatures>>,
+}
+
+impl Service {
+    /// Creates a new service with lazy plugin resolution.
+    #[must_use]
+    pub fn new(hub: Arc<ClientHub>, vendor: String, cache: &CacheConfig) -> Self {
+        Self {
+            hub,
+            vendor,
+            selector: GtsPluginSelector::new(),
+            unavailable_log_throttle: ThrottledLog::new(UNAVAILABLE_LOG_THROTTLE),
+            cache: Cache::from_config("license-resolver.get_global_features", cache),
+        }
+    }
+
+    /// Drop all cached features, e.g. after installing a new license.
+    pub fn invalidate_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+    }
+
+    /// Lazily resolves and returns the plugin client.
+    async fn get_plugin(&self) -> Result<Arc<dyn LicenseResolverPluginClient>, DomainError> {
+        let instance_id = self.selector.get_or_init(|| self.resolve_plugin()).await?;
+        let scope = ClientScope::gts_id(instance_id.as_ref());
+
+        if let Some(client) = self
+            .hub
+            .try_get_scoped::<dyn LicenseResolverPluginClient>(&scope)
+        {
+            Ok(client)
+        } else {
+            if self.unavailable_log_throttle.should_log() {
+                tracing::warn!(
+                    plugin_gts_id = %instance_id,
+                    vendor = %self.vendor,
+                    "Plugin client not registered yet"
+                );
+            }
+            Err(DomainError::PluginUnavailable {
+                gts_id: instance_id.to_string(),
+                reason: "client not registered yet".into(),
+            })
+        }
+    }
+
+    /// Resolves the plugin instance from types-registry.
+    #[tracing::instrument(skip_all, fields(vendor = %self.vendor))]
+    async fn resolve_plugin(&self) -> Result<String, DomainError> {
+        info!("Resolving license_resolver plugin");
+
+        let registry = self
+            .hub
+            .get::<dyn TypesRegistryClient>()
+            .map_err(|e| DomainError::TypesRegistryUnavailable(e.to_string()))?;
+
+        let plugin_type_id = LicenseResolverPluginSpecV1::gts_schema_id().clone();
+
+        let instances = registry
+            .list(
+                ListQuery::new()
+                    .with_pattern(format!("{plugin_type_id}*"))
+                    .with_is_type(false),
+            )
+            .await?;
+
+        let gts_id = choose_plugin_instance::<LicenseResolverPluginSpecV1>(
+            &self.vendor,
+            instances.iter().map(|e| (e.gts_id.as_str(), &e.content)),
+        )?;
+        info!(plugin_gts_id = %gts_id, "Selected license_resolver plugin instance");
+
+        Ok(gts_id)
+    }
+
+    /// Global features licensed to `tenant_id`, via the selected plugin.
+    ///
+    /// # Errors
+    ///
+    /// Plugin resolution and plugin errors.
+    #[tracing::instrument(skip_all, fields(tenant_id = %tenant_id))]
+    pub async fn get_global_features(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<GlobalFeatures, DomainError> {
+        let load = || async {
+            let plugin = self.get_plugin().await?;
+            plugin
+                .get_global_features(ctx, tenant_id)
+                .await
+                .map_err(DomainError::from)
+        };
+        match &self.cache {
+            Some(cache) => {
+                cache
+                    .get_or_try_insert_with_ttl(tenant_id, || async {
+                        let features = load().await?;
+                        let lifetime = time_to_expiry(&features, OffsetDateTime::now_utc());
+                        Ok((features, lifetime))
+                    })
+                    .await
+            }
+            None => load().await,
+        }
+    }
+}
+
+/// How long `features` stay valid after `now`; zero once expired.
+fn time_to_expiry(features: &GlobalFeatures, now: OffsetDateTime) -> Duration {
+    features.expires_at.map_or(Duration::MAX, |expires_at| {
+        Duration::try_from(expires_at - now).unwrap_or(Duration::ZERO)
+    })
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_lifetime_ends_at_license_expiry() {
+        let now = OffsetDateTime::now_utc();
+        let mut features = GlobalFeatures::none(Uuid::from_u128(1));
+        assert_eq!(time_to_expiry(&features, now), Duration::MAX);
+
+        features.expires_at = Some(now + Duration::from_secs(90));
+        assert_eq!(time_to_expiry(&features, now), Duration::from_secs(90));
+
+        features.expires_at = Some(now - Duration::from_secs(1));
+        assert_eq!(time_to_expiry(&features, now), Duration::ZERO);
+    }
+}
@@ -0,0 +1,12 @@
+//! License Resolver Module
+//!
+//! This module discovers license resolver plugins via types-registry
+//! and routes license lookups to the selected plugin based on vendor configuration.
+//!
+//! Provides the `LicenseResolverClient` trait registered
+//! in `ClientHub` for consumption by other modules.
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod config;
+pub mod domain;
+pub mod module;
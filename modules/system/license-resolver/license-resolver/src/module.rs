@@ -0,0 +1,87 @@
+//! License resolver module.
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use license_resolver_sdk::{LicenseResolverClient, LicenseResolverPluginSpecV1};
+use modkit::Module;
+use modkit::context::ModuleCtx;
+use modkit::contracts::SystemCapability;
+use tracing::info;
+use types_registry_sdk::{RegisterResult, TypesRegistryClient};
+
+use crate::config::LicenseResolverConfig;
+use crate::domain::{LicenseResolverLocalClient, Service};
+
+/// License Resolver module.
+///
+/// This module:
+/// 1. Registers the plugin schema in types-registry
+/// 2. Discovers plugin instances via types-registry
+/// 3. Routes requests to the selected plugin based on vendor configuration
+///
+/// Plugin discovery is lazy: happens on first API call after types-registry
+/// is ready.
+#[modkit::module(
+    name = "license-resolver",
+    deps = ["types-registry"],
+    capabilities = [system]
+)]
+pub(crate) struct LicenseResolver {
+    service: OnceLock<Arc<Service>>,
+}
+
+impl Default for LicenseResolver {
+    fn default() -> Self {
+        Self {
+            service: OnceLock::new(),
+        }
+    }
+}
+
+// Marked as `system` so that init() runs in the system-module phase.
+// This ensures the LicenseResolver client is available in ClientHub before
+// the API gateway resolves it.
+impl SystemCapability for LicenseResolver {}
+
+#[async_trait]
+impl Module for LicenseResolver {
+    #[tracing::instrument(skip_all, fields(vendor))]
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        let cfg: LicenseResolverConfig = ctx.config()?;
+        tracing::Span::current().record("vendor", cfg.vendor.as_str());
+        info!(vendor = %cfg.vendor);
+
+        // Register plugin schema in types-registry
+        let registry = ctx.client_hub().get::<dyn TypesRegistryClient>()?;
+        let schema_str = LicenseResolverPluginSpecV1::gts_schema_with_refs_as_string();
+        let mut schema_json: serde_json::Value = serde_json::from_str(&schema_str)?;
+        // Same gts-macros workaround as the other resolvers: derived schemas omit
+        // the base's top-level "additionalProperties": false.
+        if let Some(obj) = schema_json.as_object_mut() {
+            obj.insert(
+                "additionalProperties".to_owned(),
+                serde_json::Value::Bool(false),
+            );
+        }
+        let results = registry.register(vec![schema_json]).await?;
+        RegisterResult::ensure_all_ok(&results)?;
+        info!(
+            schema_id = %LicenseResolverPluginSpecV1::gts_schema_id(),
+            "Registered plugin schema in types-registry"
+        );
+
+        // Create service
+        let hub = ctx.client_hub();
+        let svc = Arc::new(Service::new(hub, cfg.vendor, &cfg.cache));
+        self.service
+            .set(svc.clone())
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
+        // Register client in ClientHub
+        let api: Arc<dyn LicenseResolverClient> = Arc::new(LicenseResolverLocalClient::new(svc));
+        ctx.client_hub().register::<dyn LicenseResolverClient>(api);
+
+        Ok(())
+    }
+}
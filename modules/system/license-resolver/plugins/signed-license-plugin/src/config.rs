@@ -0,0 +1,36 @@
+//! Configuration for the signed license resolver plugin.
+
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+/// Plugin configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SignedLicensePluginConfig {
+    /// Vendor name for GTS instance registration.
+    pub vendor: String,
+
+    /// Plugin priority (lower = higher priority).
+    pub priority: i16,
+
+    /// Path of the signed license file, read at startup.
+    pub license_path: PathBuf,
+
+    /// Ed25519 public keys of the license issuer (base64, 32 bytes each).
+    ///
+    /// A license signed with any of them is accepted, which allows rotating
+    /// the signing key without reissuing every license at once.
+    pub public_keys: Vec<String>,
+}
+
+impl Default for SignedLicensePluginConfig {
+    fn default() -> Self {
+        Self {
+            vendor: "hyperspot".to_owned(),
+            priority: 50,
+            license_path: PathBuf::from("license.json"),
+            public_keys: Vec::new(),
+        }
+    }
+}
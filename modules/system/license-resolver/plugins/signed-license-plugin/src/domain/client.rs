@@ -0,0 +1,21 @@
+//! Client implementation for the signed license resolver plugin.
+//!
+//! Implements `LicenseResolverPluginClient` using the domain service.
+
+use async_trait::async_trait;
+use license_resolver_sdk::{GlobalFeatures, LicenseResolverError, LicenseResolverPluginClient};
+use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+use super::service::Service;
+
+#[async_trait]
+impl LicenseResolverPluginClient for Service {
+    async fn get_global_features(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<GlobalFeatures, LicenseResolverError> {
+        Ok(self.global_features(tenant_id))
+    }
+}
@@ -0,0 +1,304 @@
+//! Signed license files.
+//!
+//! A license file is a JSON envelope holding the license document, base64
+//! encoded, and an Ed25519 signature of the document bytes. Signing the
+//! encoded bytes rather than the parsed JSON avoids any canonicalization:
+//! the verifier checks exactly what the issuer signed.
+//!
+//! ```json
+//! {
+//!   "license_id": "LIC-2026-0042",
+//!   "licensee": "Contoso Ltd",
+//!   "issued_at": "2026-10-01T00:00:00Z",
+//!   "expires_at": "2027-10-01T00:00:00Z",
+//!   "features": ["gts.x.core.lic.feat.v1~x.core.global.base.v1"],
+//!   "seats": 100,
+//!   "tenants": [
+//!     {
+//!       "tenant_id": "00000000-df51-5b42-9538-d2b56b7ee953",
+//!       "features": ["gts.x.core.lic.feat.v1~x.acme.reports.export.v1"],
+//!       "seats": 25
+//!     }
+//!   ]
+//! }
+//! ```
+
+use std::collections::BTreeSet;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ed25519_dalek::{Signature, VerifyingKey};
+use license_resolver_sdk::GlobalFeatures;
+use modkit_macros::domain_model;
+use serde::Deserialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Errors of loading a license file.
+#[domain_model]
+#[derive(Debug, thiserror::Error)]
+pub enum LicenseError {
+    #[error("malformed license: {0}")]
+    Malformed(String),
+
+    #[error("invalid public key: {0}")]
+    InvalidKey(String),
+
+    #[error("license signature does not match any trusted key")]
+    InvalidSignature,
+}
+
+/// The on-disk envelope.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SignedLicense {
+    payload: String,
+    signature: String,
+}
+
+/// A verified license document.
+#[domain_model]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct License {
+    #[serde(rename = "license_id")]
+    pub id: String,
+    pub licensee: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub issued_at: OffsetDateTime,
+    /// Start of the validity period; defaults to `issued_at`.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub not_before: Option<OffsetDateTime>,
+    /// End of the validity period (exclusive).
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+    /// Features of every licensed tenant.
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// Seats of every licensed tenant, unless its entry sets its own.
+    #[serde(default)]
+    pub seats: Option<u32>,
+    /// Licensed tenants; an empty list licenses every tenant.
+    #[serde(default)]
+    pub tenants: Vec<TenantGrant>,
+}
+
+/// Grants of a single tenant, on top of the license-wide features.
+#[domain_model]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TenantGrant {
+    pub tenant_id: Uuid,
+    #[serde(default)]
+    pub features: Vec<String>,
+    #[serde(default)]
+    pub seats: Option<u32>,
+}
+
+/// Parse base64-encoded Ed25519 public keys.
+///
+/// # Errors
+///
+/// `InvalidKey` if a key is not 32 base64-encoded bytes of a valid point.
+pub fn parse_public_keys(keys: &[String]) -> Result<Vec<VerifyingKey>, LicenseError> {
+    keys.iter()
+        .map(|key| {
+            let bytes = STANDARD
+                .decode(key.trim())
+                .map_err(|e| LicenseError::InvalidKey(e.to_string()))?;
+            let bytes: [u8; 32] = bytes
+                .try_into()
+                .map_err(|_| LicenseError::InvalidKey("expected 32 bytes".to_owned()))?;
+            VerifyingKey::from_bytes(&bytes).map_err(|e| LicenseError::InvalidKey(e.to_string()))
+        })
+        .collect()
+}
+
+impl License {
+    /// Verify the license file `bytes` against `keys` and parse its document.
+    ///
+    /// # Errors
+    ///
+    /// - `Malformed` if the envelope or the document cannot be parsed
+    /// - `InvalidSignature` if no key verifies the signature
+    pub fn verify(bytes: &[u8], keys: &[VerifyingKey]) -> Result<Self, LicenseError> {
+        let signed: SignedLicense =
+            serde_json::from_slice(bytes).map_err(|e| LicenseError::Malformed(e.to_string()))?;
+        let payload = STANDARD
+            .decode(signed.payload.trim())
+            .map_err(|e| LicenseError::Malformed(format!("payload: {e}")))?;
+        let signature = STANDARD
+            .decode(signed.signature.trim())
+            .map_err(|e| LicenseError::Malformed(format!("signature: {e}")))?;
+        let signature =
+            Signature::from_slice(&signature).map_err(|_| LicenseError::InvalidSignature)?;
+
+        if !keys
+            .iter()
+            .any(|key| key.verify_strict(&payload, &signature).is_ok())
+        {
+            return Err(LicenseError::InvalidSignature);
+        }
+
+        serde_json::from_slice(&payload).map_err(|e| LicenseError::Malformed(e.to_string()))
+    }
+
+    /// Whether the license grants anything at `now`.
+    #[must_use]
+    pub fn is_valid_at(&self, now: OffsetDateTime) -> bool {
+        self.not_before.unwrap_or(self.issued_at) <= now && now < self.expires_at
+    }
+
+    /// Features licensed to `tenant_id` at `now`.
+    ///
+    /// Outside the validity period, and for tenants the license does not
+    /// cover, nothing is licensed.
+    #[must_use]
+    pub fn global_features(&self, tenant_id: Uuid, now: OffsetDateTime) -> GlobalFeatures {
+        if !self.is_valid_at(now) {
+            return GlobalFeatures::none(tenant_id);
+        }
+        let grant = if self.tenants.is_empty() {
+            None
+        } else {
+            match self.tenants.iter().find(|t| t.tenant_id == tenant_id) {
+                Some(grant) => Some(grant),
+                None => return GlobalFeatures::none(tenant_id),
+            }
+        };
+
+        let mut features: BTreeSet<String> = self.features.iter().cloned().collect();
+        if let Some(grant) = grant {
+            features.extend(grant.features.iter().cloned());
+        }
+        GlobalFeatures {
+            tenant_id,
+            features,
+            seats: grant.and_then(|g| g.seats).or(self.seats),
+            expires_at: Some(self.expires_at),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use ed25519_dalek::{Signer, SigningKey};
+    use license_resolver_sdk::BASE_FEATURE;
+    use serde_json::json;
+    use time::format_description::well_known::Rfc3339;
+
+    use super::*;
+
+    const REPORTS: &str = "gts.x.core.lic.feat.v1~x.acme.reports.export.v1";
+
+    fn at(timestamp: &str) -> OffsetDateTime {
+        OffsetDateTime::parse(timestamp, &Rfc3339).unwrap()
+    }
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn license_file(key: &SigningKey, document: &serde_json::Value) -> Vec<u8> {
+        let payload = serde_json::to_vec(document).unwrap();
+        let signature = key.sign(&payload);
+        serde_json::to_vec(&json!({
+            "payload": STANDARD.encode(&payload),
+            "signature": STANDARD.encode(signature.to_bytes()),
+        }))
+        .unwrap()
+    }
+
+    fn document() -> serde_json::Value {
+        json!({
+            "license_id": "LIC-1",
+            "licensee": "Contoso Ltd",
+            "issued_at": "2026-01-01T00:00:00Z",
+            "expires_at": "2027-01-01T00:00:00Z",
+            "features": [BASE_FEATURE],
+            "seats": 100,
+            "tenants": [
+                { "tenant_id": Uuid::from_u128(1), "features": [REPORTS], "seats": 25 },
+                { "tenant_id": Uuid::from_u128(2) },
+            ],
+        })
+    }
+
+    #[test]
+    fn verified_license_grants_features_per_tenant() {
+        let key = signing_key(7);
+        let license = License::verify(
+            &license_file(&key, &document()),
+            &[signing_key(9).verifying_key(), key.verifying_key()],
+        )
+        .unwrap();
+        let now = at("2026-06-01T00:00:00Z");
+
+        let first = license.global_features(Uuid::from_u128(1), now);
+        assert!(first.has(BASE_FEATURE) && first.has(REPORTS));
+        assert_eq!(first.seats, Some(25));
+        assert_eq!(first.expires_at, Some(at("2027-01-01T00:00:00Z")));
+
+        let second = license.global_features(Uuid::from_u128(2), now);
+        assert!(second.has(BASE_FEATURE) && !second.has(REPORTS));
+        assert_eq!(second.seats, Some(100));
+
+        let unlisted = license.global_features(Uuid::from_u128(3), now);
+        assert!(unlisted.features.is_empty());
+    }
+
+    #[test]
+    fn nothing_is_granted_outside_the_validity_period() {
+        let key = signing_key(7);
+        let license =
+            License::verify(&license_file(&key, &document()), &[key.verifying_key()]).unwrap();
+        let tenant = Uuid::from_u128(1);
+
+        let expired = license.global_features(tenant, at("2027-01-01T00:00:00Z"));
+        assert!(expired.features.is_empty());
+        let early = license.global_features(tenant, at("2025-12-31T23:59:00Z"));
+        assert!(early.features.is_empty());
+    }
+
+    #[test]
+    fn rejects_untrusted_or_tampered_licenses() {
+        let key = signing_key(7);
+        let file = license_file(&key, &document());
+
+        assert!(matches!(
+            License::verify(&file, &[signing_key(9).verifying_key()]),
+            Err(LicenseError::InvalidSignature)
+        ));
+
+        // Re-encode a modified document under the original signature.
+        let mut envelope: serde_json::Value = serde_json::from_slice(&file).unwrap();
+        let mut tampered = document();
+        tampered["expires_at"] = json!("2099-01-01T00:00:00Z");
+        envelope["payload"] = json!(STANDARD.encode(serde_json::to_vec(&tampered).unwrap()));
+        assert!(matches!(
+            License::verify(
+                &serde_json::to_vec(&envelope).unwrap(),
+                &[key.verifying_key()]
+            ),
+            Err(LicenseError::InvalidSignature)
+        ));
+
+        assert!(matches!(
+            License::verify(b"{}", &[key.verifying_key()]),
+            Err(LicenseError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn parses_base64_public_keys() {
+        let key = signing_key(7).verifying_key();
+        let parsed = parse_public_keys(&[STANDARD.encode(key.as_bytes())]).unwrap();
+        assert_eq!(parsed, vec![key]);
+
+        assert!(matches!(
+            parse_public_keys(&["c2hvcnQ=".to_owned()]),
+            Err(LicenseError::InvalidKey(_))
+        ));
+    }
+}
@@ -0,0 +1,8 @@
+//! Domain layer for the signed license resolver plugin.
+
+mod client;
+pub mod license;
+pub mod service;
+
+pub use license::{License, LicenseError};
+pub use service::Service;
@@ -0,0 +1,48 @@
+//! Service implementation for the signed license resolver plugin.
+
+use std::time::Duration;
+
+use license_resolver_sdk::GlobalFeatures;
+use modkit::telemetry::ThrottledLog;
+use modkit_macros::domain_model;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::license::License;
+
+/// Throttle interval for invalid license warnings.
+const INVALID_LOG_THROTTLE: Duration = Duration::from_secs(60);
+
+/// Signed license resolver service.
+///
+/// Serves the license verified at startup; validity is checked on every
+/// lookup, so an expiring license stops granting features without a restart.
+#[domain_model]
+pub struct Service {
+    license: License,
+    invalid_log_throttle: ThrottledLog,
+}
+
+impl Service {
+    #[must_use]
+    pub fn new(license: License) -> Self {
+        Self {
+            license,
+            invalid_log_throttle: ThrottledLog::new(INVALID_LOG_THROTTLE),
+        }
+    }
+
+    /// Features licensed to `tenant_id` now.
+    #[must_use]
+    pub fn global_features(&self, tenant_id: Uuid) -> GlobalFeatures {
+        let now = OffsetDateTime::now_utc();
+        if !self.license.is_valid_at(now) && self.invalid_log_throttle.should_log() {
+            tracing::warn!(
+                license_id = %self.license.id,
+                expires_at = %self.license.expires_at,
+                "License is not valid now; no features are granted"
+            );
+        }
+        self.license.global_features(tenant_id, now)
+    }
+}
@@ -0,0 +1,37 @@
+//! Signed License Resolver Plugin
+//!
+//! This plugin serves licensed features from a license file issued and signed
+//! by the vendor. The signature is verified offline against the configured
+//! Ed25519 public keys, so air-gapped installations can be licensed.
+//!
+//! ## License file
+//!
+//! ```json
+//! {
+//!   "payload": "<base64 of the license document>",
+//!   "signature": "<base64 Ed25519 signature of the payload bytes>"
+//! }
+//! ```
+//!
+//! The license document lists the features, the seat count and the validity
+//! period, either for every tenant or per tenant; see [`domain::license`].
+//!
+//! ## Configuration
+//!
+//! ```yaml
+//! modules:
+//!   signed-license-plugin:
+//!     config:
+//!       vendor: "hyperspot"
+//!       priority: 50
+//!       license_path: "/etc/hyperspot/license.json"
+//!       public_keys:
+//!         - "Vc0tyEPsA8nbyv2u3gWVTqKWQ40Lk9Ojpp0kr/cAMcY="
+//! ```
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod config;
+pub mod domain;
+pub mod module;
+
+pub use module::SignedLicensePlugin;
@@ -0,0 +1,106 @@
+//! Signed license resolver plugin module.
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use license_resolver_sdk::{LicenseResolverPluginClient, LicenseResolverPluginSpecV1};
+use modkit::Module;
+use modkit::client_hub::ClientScope;
+use modkit::context::ModuleCtx;
+use modkit::gts::BaseModkitPluginV1;
+use time::OffsetDateTime;
+use tracing::info;
+use types_registry_sdk::{RegisterResult, TypesRegistryClient};
+
+use crate::config::SignedLicensePluginConfig;
+use crate::domain::license::parse_public_keys;
+use crate::domain::{License, Service};
+
+/// Signed license resolver plugin module.
+///
+/// Verifies the license file at startup and fails to start if it is
+/// missing, malformed or not signed by a trusted key. An expired license
+/// is loaded but grants nothing.
+#[modkit::module(
+    name = "signed-license-plugin",
+    deps = ["types-registry"]
+)]
+pub struct SignedLicensePlugin {
+    service: OnceLock<Arc<Service>>,
+}
+
+impl Default for SignedLicensePlugin {
+    fn default() -> Self {
+        Self {
+            service: OnceLock::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Module for SignedLicensePlugin {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        // Load configuration
+        let cfg: SignedLicensePluginConfig = ctx.config()?;
+        if cfg.public_keys.is_empty() {
+            anyhow::bail!("signed-license-plugin: `public_keys` must not be empty");
+        }
+        let keys = parse_public_keys(&cfg.public_keys)?;
+
+        // Verify the license
+        let bytes = std::fs::read(&cfg.license_path).map_err(|e| {
+            anyhow::anyhow!(
+                "cannot read license file '{}': {e}",
+                cfg.license_path.display()
+            )
+        })?;
+        let license = License::verify(&bytes, &keys)?;
+        info!(
+            license_id = %license.id,
+            licensee = %license.licensee,
+            expires_at = %license.expires_at,
+            tenant_count = license.tenants.len(),
+            "Verified license"
+        );
+        if !license.is_valid_at(OffsetDateTime::now_utc()) {
+            tracing::warn!(
+                license_id = %license.id,
+                "License is outside its validity period; no features are granted"
+            );
+        }
+
+        // Generate plugin instance ID
+        let instance_id = LicenseResolverPluginSpecV1::gts_make_instance_id(
+            "hyperspot.builtin.signed_license_resolver.plugin.v1",
+        );
+
+        // Register plugin instance in types-registry
+        let registry = ctx.client_hub().get::<dyn TypesRegistryClient>()?;
+        let instance = BaseModkitPluginV1::<LicenseResolverPluginSpecV1> {
+            id: instance_id.clone(),
+            vendor: cfg.vendor.clone(),
+            priority: cfg.priority,
+            properties: LicenseResolverPluginSpecV1,
+        };
+        let instance_json = serde_json::to_value(&instance)?;
+
+        let results = registry.register(vec![instance_json]).await?;
+        RegisterResult::ensure_all_ok(&results)?;
+
+        let service = Arc::new(Service::new(license));
+        self.service
+            .set(service.clone())
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
+        // Register scoped client in ClientHub
+        let api: Arc<dyn LicenseResolverPluginClient> = service;
+        ctx.client_hub()
+            .register_scoped::<dyn LicenseResolverPluginClient>(
+                ClientScope::gts_id(&instance_id),
+                api,
+            );
+
+        info!(instance_id = %instance_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+//! Configuration for the static license resolver plugin.
+
+use license_resolver_sdk::BASE_FEATURE;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Plugin configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StaticLicensePluginConfig {
+    /// Vendor name for GTS instance registration.
+    pub vendor: String,
+
+    /// Plugin priority (lower = higher priority).
+    pub priority: i16,
+
+    /// Features of tenants not listed in `tenants`.
+    pub default_features: Vec<String>,
+
+    /// Per-tenant licenses.
+    pub tenants: Vec<TenantLicenseConfig>,
+}
+
+impl Default for StaticLicensePluginConfig {
+    fn default() -> Self {
+        Self {
+            vendor: "hyperspot".to_owned(),
+            priority: 100,
+            default_features: vec![BASE_FEATURE.to_owned()],
+            tenants: Vec::new(),
+        }
+    }
+}
+
+/// License of a single tenant.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TenantLicenseConfig {
+    /// Tenant ID.
+    pub tenant_id: Uuid,
+
+    /// Licensed features; replaces `default_features` for this tenant.
+    #[serde(default)]
+    pub features: Vec<String>,
+
+    /// Number of licensed seats, if limited.
+    #[serde(default)]
+    pub seats: Option<u32>,
+}
@@ -0,0 +1,87 @@
+//! Service implementation for the static license resolver plugin.
+
+use std::collections::HashMap;
+
+use license_resolver_sdk::GlobalFeatures;
+use modkit_macros::domain_model;
+use uuid::Uuid;
+
+use crate::config::StaticLicensePluginConfig;
+
+/// Static license resolver service.
+#[domain_model]
+pub struct Service {
+    default_features: Vec<String>,
+    tenants: HashMap<Uuid, GlobalFeatures>,
+}
+
+impl Service {
+    /// Create a service from plugin configuration.
+    #[must_use]
+    pub fn from_config(cfg: &StaticLicensePluginConfig) -> Self {
+        let tenants = cfg
+            .tenants
+            .iter()
+            .map(|t| {
+                let features = GlobalFeatures {
+                    tenant_id: t.tenant_id,
+                    features: t.features.iter().cloned().collect(),
+                    seats: t.seats,
+                    expires_at: None,
+                };
+                (t.tenant_id, features)
+            })
+            .collect();
+
+        Self {
+            default_features: cfg.default_features.clone(),
+            tenants,
+        }
+    }
+
+    /// Features licensed to `tenant_id`.
+    #[must_use]
+    pub fn global_features(&self, tenant_id: Uuid) -> GlobalFeatures {
+        self.tenants.get(&tenant_id).cloned().unwrap_or_else(|| {
+            let mut features = GlobalFeatures::none(tenant_id);
+            features.features = self.default_features.iter().cloned().collect();
+            features
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use license_resolver_sdk::BASE_FEATURE;
+
+    use super::*;
+    use crate::config::TenantLicenseConfig;
+
+    const REPORTS: &str = "gts.x.core.lic.feat.v1~x.acme.reports.export.v1";
+
+    #[test]
+    fn listed_tenants_replace_default_features() {
+        let licensed = Uuid::from_u128(1);
+        let cfg = StaticLicensePluginConfig {
+            tenants: vec![TenantLicenseConfig {
+                tenant_id: licensed,
+                features: vec![REPORTS.to_owned()],
+                seats: Some(25),
+            }],
+            ..StaticLicensePluginConfig::default()
+        };
+        let service = Service::from_config(&cfg);
+
+        let features = service.global_features(licensed);
+        assert!(features.has(REPORTS));
+        assert!(!features.has(BASE_FEATURE));
+        assert_eq!(features.seats, Some(25));
+
+        let other = service.global_features(Uuid::from_u128(2));
+        assert_eq!(other.tenant_id, Uuid::from_u128(2));
+        assert!(other.has(BASE_FEATURE));
+        assert!(!other.has(REPORTS));
+        assert_eq!(other.seats, None);
+    }
+}
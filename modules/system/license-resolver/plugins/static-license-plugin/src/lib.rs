@@ -0,0 +1,32 @@
+//! Static License Resolver Plugin
+//!
+//! This plugin serves licensed features from configuration, for development,
+//! single-tenant installations and deployments without a license manager.
+//!
+//! Tenants listed under `tenants` get exactly their configured features;
+//! every other tenant gets `default_features`.
+//!
+//! ## Configuration
+//!
+//! ```yaml
+//! modules:
+//!   static-license-plugin:
+//!     config:
+//!       vendor: "hyperspot"
+//!       priority: 100
+//!       default_features:
+//!         - "gts.x.core.lic.feat.v1~x.core.global.base.v1"
+//!       tenants:
+//!         - tenant_id: "00000000-df51-5b42-9538-d2b56b7ee953"
+//!           features:
+//!             - "gts.x.core.lic.feat.v1~x.core.global.base.v1"
+//!             - "gts.x.core.lic.feat.v1~x.acme.reports.export.v1"
+//!           seats: 25
+//! ```
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod config;
+pub mod domain;
+pub mod module;
+
+pub use module::StaticLicensePlugin;
@@ -1,7 +1,9 @@
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 
 use bytes::Bytes;
 use futures_core::Stream;
+use http::HeaderMap;
 
 /// Boxed error type for body stream errors.
 pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
@@ -19,11 +21,12 @@ pub type BodyStream = Pin<Box<dyn Stream<Item = Result<Bytes, BoxError>> + Send>
 ///
 /// # Protocol mapping
 ///
-/// | Protocol  | Request Body          | Response Body          |
-/// |-----------|-----------------------|------------------------|
-/// | HTTP      | `Body::Bytes`/`Empty` | `Body::Bytes`          |
-/// | SSE       | `Body::Bytes`/`Empty` | `Body::Stream`         |
-/// | WebSocket | `Body::Stream`        | `Body::Stream`         |
+/// | Protocol  | Request Body           | Response Body                 |
+/// |-----------|------------------------|-------------------------------|
+/// | HTTP      | `Body::Bytes`/`Empty`  | `Body::Bytes`                 |
+/// | SSE       | `Body::Bytes`/`Empty`  | `Body::Stream`                |
+/// | WebSocket | `Body::Stream`         | `Body::Stream`                |
+/// | gRPC      | `Body::Bytes`/`Stream` | `Body::Stream` + [`Trailers`] |
 pub enum Body {
     /// No body.
     Empty,
@@ -147,6 +150,47 @@ impl From<BodyStream> for Body {
     }
 }
 
+/// Trailing headers of a streamed response.
+///
+/// gRPC reports its outcome (`grpc-status`, `grpc-message`) in HTTP/2
+/// trailers, which a [`Body`] stream cannot carry. Proxy responses from gRPC
+/// upstreams therefore hold a `Trailers` handle in their extensions; it is
+/// filled in once the body stream has been read to the end.
+///
+/// ```ignore
+/// let trailers = resp.extensions().get::<Trailers>().cloned();
+/// let body = resp.into_body().into_bytes().await?;
+/// let status = trailers.and_then(|t| t.get()).and_then(|t| t.get("grpc-status").cloned());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Trailers(Arc<Mutex<Option<HeaderMap>>>);
+
+impl Trailers {
+    /// Create an empty handle.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the trailers received at the end of the body stream.
+    pub fn set(&self, trailers: HeaderMap) {
+        *self
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(trailers);
+    }
+
+    /// The trailers, or `None` while the body has not been read to the end
+    /// (or the upstream sent none).
+    #[must_use]
+    pub fn get(&self) -> Option<HeaderMap> {
+        self.0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -251,4 +295,16 @@ mod tests {
         let body = Body::Bytes(Bytes::from("data"));
         assert!(body.try_into_stream().is_err());
     }
+
+    #[test]
+    fn trailers_are_shared_between_clones() {
+        let trailers = Trailers::new();
+        let reader = trailers.clone();
+        assert!(reader.get().is_none());
+
+        let mut map = HeaderMap::new();
+        map.insert("grpc-status", http::HeaderValue::from_static("0"));
+        trailers.set(map);
+        assert_eq!(reader.get().unwrap()["grpc-status"], "0");
+    }
 }
@@ -32,6 +32,30 @@ pub enum ServiceGatewayError {
         retry_after_secs: Option<u64>,
     },
 
+    /// The rate-limit wait queue for this key is full.
+    #[error("{detail}")]
+    QueueFull {
+        detail: String,
+        instance: String,
+        retry_after_secs: Option<u64>,
+    },
+
+    /// The request waited in the rate-limit queue for longer than allowed.
+    #[error("{detail}")]
+    QueueTimeout {
+        detail: String,
+        instance: String,
+        retry_after_secs: Option<u64>,
+    },
+
+    /// The upstream's circuit breaker is open; the request was not forwarded.
+    #[error("{detail}")]
+    CircuitBreakerOpen {
+        detail: String,
+        instance: String,
+        retry_after_secs: Option<u64>,
+    },
+
     #[error("{detail}")]
     SecretNotFound { detail: String, instance: String },
 
@@ -9,17 +9,18 @@ pub mod ws;
 pub mod models;
 
 pub use models::{
-    AuthConfig, BurstConfig, CreateRouteRequest, CreateRouteRequestBuilder, CreateUpstreamRequest,
-    CreateUpstreamRequestBuilder, Endpoint, GrpcMatch, HeadersConfig, HttpMatch, HttpMethod,
-    ListQuery, MatchRules, PassthroughMode, PathSuffixMode, PluginsConfig, RateLimitAlgorithm,
-    RateLimitConfig, RateLimitScope, RateLimitStrategy, RequestHeaderRules, ResponseHeaderRules,
-    Route, Scheme, Server, SharingMode, SustainedRate, UpdateRouteRequest,
-    UpdateRouteRequestBuilder, UpdateUpstreamRequest, UpdateUpstreamRequestBuilder, Upstream,
-    Window,
+    AuthConfig, BurstConfig, CacheConfig, CircuitBreakerConfig, CircuitBreakerScope,
+    CreateRouteRequest, CreateRouteRequestBuilder, CreateUpstreamRequest,
+    CreateUpstreamRequestBuilder, Endpoint, FailureConditions, FailureRateConfig, GrpcMatch,
+    HeadersConfig, HttpMatch, HttpMethod, ListQuery, MatchRules, PassthroughMode, PathSuffixMode,
+    PluginsConfig, QueueConfig, RateLimitAlgorithm, RateLimitConfig, RateLimitScope,
+    RateLimitStrategy, RequestHeaderRules, ResponseHeaderRules, Route, Scheme, Server, SharingMode,
+    SustainedRate, UpdateRouteRequest, UpdateRouteRequestBuilder, UpdateUpstreamRequest,
+    UpdateUpstreamRequestBuilder, Upstream, Window,
 };
 
 pub use api::ServiceGatewayClientV1;
-pub use body::Body;
+pub use body::{Body, Trailers};
 pub use codec::Json;
 pub use error::StreamingError;
 pub use modkit_security::SecurityContext;
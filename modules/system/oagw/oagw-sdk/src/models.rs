@@ -137,6 +137,8 @@ pub struct RateLimitConfig {
     pub burst: Option<BurstConfig>,
     pub scope: RateLimitScope,
     pub strategy: RateLimitStrategy,
+    /// Wait-queue bounds for [`RateLimitStrategy::Queue`]. Defaults apply when `None`.
+    pub queue: Option<QueueConfig>,
     pub cost: u32,
 }
 
@@ -171,6 +173,15 @@ pub struct BurstConfig {
     pub capacity: u32,
 }
 
+/// Bounded wait queue used by the `queue` rate-limit strategy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueConfig {
+    /// Maximum number of requests waiting per rate-limit key.
+    pub max_depth: u32,
+    /// Maximum time a request may wait for capacity, in milliseconds.
+    pub max_wait_ms: u64,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum RateLimitScope {
     Global,
@@ -189,6 +200,121 @@ pub enum RateLimitStrategy {
     Degrade,
 }
 
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+/// Per-upstream circuit breaker. While the circuit is open, requests fail
+/// fast with [`ServiceGatewayError::CircuitBreakerOpen`](crate::error::ServiceGatewayError::CircuitBreakerOpen).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    pub enabled: bool,
+    /// Consecutive failures that open the circuit.
+    pub failure_threshold: u32,
+    /// Optional failure-rate trip, evaluated alongside `failure_threshold`.
+    pub failure_rate: Option<FailureRateConfig>,
+    /// Consecutive successful probes that close a half-open circuit.
+    pub success_threshold: u32,
+    /// Seconds the circuit stays open before probing the upstream again.
+    pub timeout_seconds: u32,
+    /// Probe requests allowed in flight while half-open.
+    pub half_open_max_requests: u32,
+    pub failure_conditions: FailureConditions,
+    pub scope: CircuitBreakerScope,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_threshold: 5,
+            failure_rate: None,
+            success_threshold: 3,
+            timeout_seconds: 30,
+            half_open_max_requests: 3,
+            failure_conditions: FailureConditions::default(),
+            scope: CircuitBreakerScope::default(),
+        }
+    }
+}
+
+/// Failure-rate trip: open when `threshold_percent` of the calls in the last
+/// `window_seconds` failed, once at least `minimum_requests` calls were seen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureRateConfig {
+    pub threshold_percent: u32,
+    pub minimum_requests: u32,
+    pub window_seconds: u32,
+}
+
+impl Default for FailureRateConfig {
+    fn default() -> Self {
+        Self {
+            threshold_percent: 50,
+            minimum_requests: 10,
+            window_seconds: 60,
+        }
+    }
+}
+
+/// Which upstream call outcomes count as circuit breaker failures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureConditions {
+    /// Upstream response status codes counted as failures.
+    pub status_codes: Vec<u16>,
+    pub timeout: bool,
+    pub connection_error: bool,
+}
+
+impl Default for FailureConditions {
+    fn default() -> Self {
+        Self {
+            status_codes: vec![500, 502, 503, 504],
+            timeout: true,
+            connection_error: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CircuitBreakerScope {
+    /// One circuit for the whole upstream.
+    #[default]
+    Global,
+    /// One circuit per upstream endpoint.
+    PerEndpoint,
+}
+
+// ---------------------------------------------------------------------------
+// CacheConfig
+// ---------------------------------------------------------------------------
+
+/// Per-route response cache. GET and HEAD responses are cached; POST only
+/// when `cache_post` is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    /// Maximum freshness; upstream `Cache-Control: max-age` may shorten it.
+    pub ttl_seconds: u32,
+    pub cache_post: bool,
+    /// Responses larger than this are not cached.
+    pub max_entry_bytes: u64,
+    /// Extra request headers that select a cache variant.
+    pub vary_headers: Vec<String>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_seconds: 300,
+            cache_post: false,
+            max_entry_bytes: 1024 * 1024,
+            vary_headers: Vec::new(),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // PluginsConfig
 // ---------------------------------------------------------------------------
@@ -199,6 +325,8 @@ pub struct PluginsConfig {
     pub sharing: SharingMode,
     /// Plugin references: GTS identifiers (builtin) or UUIDs (custom).
     pub items: Vec<String>,
+    /// Per-plugin configuration keyed by plugin reference.
+    pub config: HashMap<String, HashMap<String, String>>,
 }
 
 // ---------------------------------------------------------------------------
@@ -235,7 +363,7 @@ pub struct HttpMatch {
     pub path_suffix_mode: PathSuffixMode,
 }
 
-/// gRPC-protocol match rules for a route (future use).
+/// gRPC-protocol match rules for a route: an exact service and method.
 #[derive(Debug, Clone, PartialEq)]
 pub struct GrpcMatch {
     pub service: String,
@@ -262,6 +390,7 @@ pub struct Route {
     pub match_rules: MatchRules,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub cache: Option<CacheConfig>,
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
@@ -281,6 +410,7 @@ pub struct Upstream {
     pub headers: Option<HeadersConfig>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub tags: Vec<String>,
 }
 
@@ -317,6 +447,7 @@ pub struct CreateUpstreamRequest {
     headers: Option<HeadersConfig>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
     tags: Vec<String>,
     enabled: bool,
 }
@@ -332,6 +463,7 @@ impl CreateUpstreamRequest {
             headers: None,
             plugins: None,
             rate_limit: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         }
@@ -358,6 +490,9 @@ impl CreateUpstreamRequest {
     pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
         self.rate_limit.as_ref()
     }
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreakerConfig> {
+        self.circuit_breaker.as_ref()
+    }
     pub fn tags(&self) -> &[String] {
         &self.tags
     }
@@ -374,6 +509,7 @@ pub struct CreateUpstreamRequestBuilder {
     headers: Option<HeadersConfig>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
     tags: Vec<String>,
     enabled: bool,
 }
@@ -399,6 +535,10 @@ impl CreateUpstreamRequestBuilder {
         self.rate_limit = Some(rate_limit);
         self
     }
+    pub fn circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = tags;
         self
@@ -416,6 +556,7 @@ impl CreateUpstreamRequestBuilder {
             headers: self.headers,
             plugins: self.plugins,
             rate_limit: self.rate_limit,
+            circuit_breaker: self.circuit_breaker,
             tags: self.tags,
             enabled: self.enabled,
         }
@@ -433,6 +574,7 @@ pub struct UpdateUpstreamRequest {
     headers: Option<HeadersConfig>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
     tags: Option<Vec<String>>,
     enabled: Option<bool>,
 }
@@ -464,6 +606,9 @@ impl UpdateUpstreamRequest {
     pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
         self.rate_limit.as_ref()
     }
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreakerConfig> {
+        self.circuit_breaker.as_ref()
+    }
     pub fn tags(&self) -> Option<&[String]> {
         self.tags.as_deref()
     }
@@ -481,6 +626,7 @@ pub struct UpdateUpstreamRequestBuilder {
     headers: Option<HeadersConfig>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
     tags: Option<Vec<String>>,
     enabled: Option<bool>,
 }
@@ -514,6 +660,10 @@ impl UpdateUpstreamRequestBuilder {
         self.rate_limit = Some(rate_limit);
         self
     }
+    pub fn circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = Some(tags);
         self
@@ -531,6 +681,7 @@ impl UpdateUpstreamRequestBuilder {
             headers: self.headers,
             plugins: self.plugins,
             rate_limit: self.rate_limit,
+            circuit_breaker: self.circuit_breaker,
             tags: self.tags,
             enabled: self.enabled,
         }
@@ -548,6 +699,7 @@ pub struct CreateRouteRequest {
     match_rules: MatchRules,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    cache: Option<CacheConfig>,
     tags: Vec<String>,
     priority: i32,
     enabled: bool,
@@ -561,6 +713,7 @@ impl CreateRouteRequest {
             match_rules,
             plugins: None,
             rate_limit: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -579,6 +732,9 @@ impl CreateRouteRequest {
     pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
         self.rate_limit.as_ref()
     }
+    pub fn cache(&self) -> Option<&CacheConfig> {
+        self.cache.as_ref()
+    }
     pub fn tags(&self) -> &[String] {
         &self.tags
     }
@@ -595,6 +751,7 @@ pub struct CreateRouteRequestBuilder {
     match_rules: MatchRules,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    cache: Option<CacheConfig>,
     tags: Vec<String>,
     priority: i32,
     enabled: bool,
@@ -609,6 +766,10 @@ impl CreateRouteRequestBuilder {
         self.rate_limit = Some(rate_limit);
         self
     }
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = tags;
         self
@@ -627,6 +788,7 @@ impl CreateRouteRequestBuilder {
             match_rules: self.match_rules,
             plugins: self.plugins,
             rate_limit: self.rate_limit,
+            cache: self.cache,
             tags: self.tags,
             priority: self.priority,
             enabled: self.enabled,
@@ -641,6 +803,7 @@ pub struct UpdateRouteRequest {
     match_rules: Option<MatchRules>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    cache: Option<CacheConfig>,
     tags: Option<Vec<String>>,
     priority: Option<i32>,
     enabled: Option<bool>,
@@ -661,6 +824,9 @@ impl UpdateRouteRequest {
     pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
         self.rate_limit.as_ref()
     }
+    pub fn cache(&self) -> Option<&CacheConfig> {
+        self.cache.as_ref()
+    }
     pub fn tags(&self) -> Option<&[String]> {
         self.tags.as_deref()
     }
@@ -677,6 +843,7 @@ pub struct UpdateRouteRequestBuilder {
     match_rules: Option<MatchRules>,
     plugins: Option<PluginsConfig>,
     rate_limit: Option<RateLimitConfig>,
+    cache: Option<CacheConfig>,
     tags: Option<Vec<String>>,
     priority: Option<i32>,
     enabled: Option<bool>,
@@ -695,6 +862,10 @@ impl UpdateRouteRequestBuilder {
         self.rate_limit = Some(rate_limit);
         self
     }
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = Some(tags);
         self
@@ -712,6 +883,7 @@ impl UpdateRouteRequestBuilder {
             match_rules: self.match_rules,
             plugins: self.plugins,
             rate_limit: self.rate_limit,
+            cache: self.cache,
             tags: self.tags,
             priority: self.priority,
             enabled: self.enabled,
@@ -790,6 +962,7 @@ mod tests {
             },
             plugins: None,
             rate_limit: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -132,6 +132,8 @@ pub struct RateLimitConfig {
     pub scope: RateLimitScope,
     #[serde(default)]
     pub strategy: RateLimitStrategy,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue: Option<QueueConfig>,
     #[serde(default = "default_cost")]
     pub cost: u32,
 }
@@ -140,6 +142,22 @@ fn default_cost() -> u32 {
     1
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct QueueConfig {
+    #[serde(default = "default_queue_max_depth")]
+    pub max_depth: u32,
+    #[serde(default = "default_queue_max_wait_ms")]
+    pub max_wait_ms: u64,
+}
+
+fn default_queue_max_depth() -> u32 {
+    domain::QueueConfig::default().max_depth
+}
+
+fn default_queue_max_wait_ms() -> u64 {
+    domain::QueueConfig::default().max_wait_ms
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, utoipa::ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum RateLimitAlgorithm {
@@ -190,6 +208,89 @@ pub enum RateLimitStrategy {
     Degrade,
 }
 
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+/// Omitted fields take the domain defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(default)]
+pub struct CircuitBreakerConfig {
+    pub enabled: bool,
+    pub failure_threshold: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_rate: Option<FailureRateConfig>,
+    pub success_threshold: u32,
+    pub timeout_seconds: u32,
+    pub half_open_max_requests: u32,
+    pub failure_conditions: FailureConditions,
+    pub scope: CircuitBreakerScope,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        domain::CircuitBreakerConfig::default().into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(default)]
+pub struct FailureRateConfig {
+    pub threshold_percent: u32,
+    pub minimum_requests: u32,
+    pub window_seconds: u32,
+}
+
+impl Default for FailureRateConfig {
+    fn default() -> Self {
+        domain::FailureRateConfig::default().into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(default)]
+pub struct FailureConditions {
+    pub status_codes: Vec<u16>,
+    pub timeout: bool,
+    pub connection_error: bool,
+}
+
+impl Default for FailureConditions {
+    fn default() -> Self {
+        domain::FailureConditions::default().into()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitBreakerScope {
+    #[default]
+    Global,
+    PerEndpoint,
+}
+
+// ---------------------------------------------------------------------------
+// CacheConfig
+// ---------------------------------------------------------------------------
+
+/// Omitted fields take the domain defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(default)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    pub ttl_seconds: u32,
+    pub cache_post: bool,
+    pub max_entry_bytes: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub vary_headers: Vec<String>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        domain::CacheConfig::default().into()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // PluginsConfig
 // ---------------------------------------------------------------------------
@@ -200,6 +301,9 @@ pub struct PluginsConfig {
     pub sharing: SharingMode,
     #[serde(default)]
     pub items: Vec<String>,
+    /// Per-plugin configuration keyed by plugin reference.
+    #[serde(default)]
+    pub config: HashMap<String, HashMap<String, String>>,
 }
 
 // ---------------------------------------------------------------------------
@@ -267,6 +371,8 @@ pub struct CreateUpstreamRequest {
     pub plugins: Option<PluginsConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     #[serde(default)]
     pub tags: Vec<String>,
     #[serde(default = "default_true")]
@@ -290,6 +396,8 @@ pub struct UpdateUpstreamRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enabled: Option<bool>,
@@ -308,6 +416,8 @@ pub struct CreateRouteRequest {
     pub plugins: Option<PluginsConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheConfig>,
     #[serde(default)]
     pub tags: Vec<String>,
     #[serde(default)]
@@ -325,6 +435,8 @@ pub struct UpdateRouteRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub priority: Option<i32>,
@@ -352,6 +464,8 @@ pub struct UpstreamResponse {
     pub plugins: Option<PluginsConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
 }
@@ -367,6 +481,8 @@ pub struct RouteResponse {
     pub plugins: Option<PluginsConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheConfig>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
     pub priority: i32,
@@ -505,6 +621,15 @@ impl From<BurstConfig> for domain::BurstConfig {
     }
 }
 
+impl From<QueueConfig> for domain::QueueConfig {
+    fn from(v: QueueConfig) -> Self {
+        Self {
+            max_depth: v.max_depth,
+            max_wait_ms: v.max_wait_ms,
+        }
+    }
+}
+
 impl From<RateLimitScope> for domain::RateLimitScope {
     fn from(v: RateLimitScope) -> Self {
         match v {
@@ -536,16 +661,74 @@ impl From<RateLimitConfig> for domain::RateLimitConfig {
             burst: v.burst.map(Into::into),
             scope: v.scope.into(),
             strategy: v.strategy.into(),
+            queue: v.queue.map(Into::into),
             cost: v.cost,
         }
     }
 }
 
+impl From<CircuitBreakerScope> for domain::CircuitBreakerScope {
+    fn from(v: CircuitBreakerScope) -> Self {
+        match v {
+            CircuitBreakerScope::Global => Self::Global,
+            CircuitBreakerScope::PerEndpoint => Self::PerEndpoint,
+        }
+    }
+}
+
+impl From<FailureRateConfig> for domain::FailureRateConfig {
+    fn from(v: FailureRateConfig) -> Self {
+        Self {
+            threshold_percent: v.threshold_percent,
+            minimum_requests: v.minimum_requests,
+            window_seconds: v.window_seconds,
+        }
+    }
+}
+
+impl From<FailureConditions> for domain::FailureConditions {
+    fn from(v: FailureConditions) -> Self {
+        Self {
+            status_codes: v.status_codes,
+            timeout: v.timeout,
+            connection_error: v.connection_error,
+        }
+    }
+}
+
+impl From<CircuitBreakerConfig> for domain::CircuitBreakerConfig {
+    fn from(v: CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            failure_rate: v.failure_rate.map(Into::into),
+            success_threshold: v.success_threshold,
+            timeout_seconds: v.timeout_seconds,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: v.failure_conditions.into(),
+            scope: v.scope.into(),
+        }
+    }
+}
+
+impl From<CacheConfig> for domain::CacheConfig {
+    fn from(v: CacheConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            ttl_seconds: v.ttl_seconds,
+            cache_post: v.cache_post,
+            max_entry_bytes: v.max_entry_bytes,
+            vary_headers: v.vary_headers,
+        }
+    }
+}
+
 impl From<PluginsConfig> for domain::PluginsConfig {
     fn from(v: PluginsConfig) -> Self {
         Self {
             sharing: v.sharing.into(),
             items: v.items,
+            config: v.config,
         }
     }
 }
@@ -732,6 +915,15 @@ impl From<domain::BurstConfig> for BurstConfig {
     }
 }
 
+impl From<domain::QueueConfig> for QueueConfig {
+    fn from(v: domain::QueueConfig) -> Self {
+        Self {
+            max_depth: v.max_depth,
+            max_wait_ms: v.max_wait_ms,
+        }
+    }
+}
+
 impl From<domain::RateLimitScope> for RateLimitScope {
     fn from(v: domain::RateLimitScope) -> Self {
         match v {
@@ -763,16 +955,74 @@ impl From<domain::RateLimitConfig> for RateLimitConfig {
             burst: v.burst.map(Into::into),
             scope: v.scope.into(),
             strategy: v.strategy.into(),
+            queue: v.queue.map(Into::into),
             cost: v.cost,
         }
     }
 }
 
+impl From<domain::CircuitBreakerScope> for CircuitBreakerScope {
+    fn from(v: domain::CircuitBreakerScope) -> Self {
+        match v {
+            domain::CircuitBreakerScope::Global => Self::Global,
+            domain::CircuitBreakerScope::PerEndpoint => Self::PerEndpoint,
+        }
+    }
+}
+
+impl From<domain::FailureRateConfig> for FailureRateConfig {
+    fn from(v: domain::FailureRateConfig) -> Self {
+        Self {
+            threshold_percent: v.threshold_percent,
+            minimum_requests: v.minimum_requests,
+            window_seconds: v.window_seconds,
+        }
+    }
+}
+
+impl From<domain::FailureConditions> for FailureConditions {
+    fn from(v: domain::FailureConditions) -> Self {
+        Self {
+            status_codes: v.status_codes,
+            timeout: v.timeout,
+            connection_error: v.connection_error,
+        }
+    }
+}
+
+impl From<domain::CircuitBreakerConfig> for CircuitBreakerConfig {
+    fn from(v: domain::CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            failure_rate: v.failure_rate.map(Into::into),
+            success_threshold: v.success_threshold,
+            timeout_seconds: v.timeout_seconds,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: v.failure_conditions.into(),
+            scope: v.scope.into(),
+        }
+    }
+}
+
+impl From<domain::CacheConfig> for CacheConfig {
+    fn from(v: domain::CacheConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            ttl_seconds: v.ttl_seconds,
+            cache_post: v.cache_post,
+            max_entry_bytes: v.max_entry_bytes,
+            vary_headers: v.vary_headers,
+        }
+    }
+}
+
 impl From<domain::PluginsConfig> for PluginsConfig {
     fn from(v: domain::PluginsConfig) -> Self {
         Self {
             sharing: v.sharing.into(),
             items: v.items,
+            config: v.config,
         }
     }
 }
@@ -841,6 +1091,7 @@ impl From<CreateUpstreamRequest> for domain::CreateUpstreamRequest {
             headers: r.headers.map(Into::into),
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
+            circuit_breaker: r.circuit_breaker.map(Into::into),
             tags: r.tags,
             enabled: r.enabled,
         }
@@ -857,6 +1108,7 @@ impl From<UpdateUpstreamRequest> for domain::UpdateUpstreamRequest {
             headers: r.headers.map(Into::into),
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
+            circuit_breaker: r.circuit_breaker.map(Into::into),
             tags: r.tags,
             enabled: r.enabled,
         }
@@ -870,6 +1122,7 @@ impl From<CreateRouteRequest> for domain::CreateRouteRequest {
             match_rules: r.match_rules.into(),
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
+            cache: r.cache.map(Into::into),
             tags: r.tags,
             priority: r.priority,
             enabled: r.enabled,
@@ -883,6 +1136,7 @@ impl From<UpdateRouteRequest> for domain::UpdateRouteRequest {
             match_rules: r.match_rules.map(Into::into),
             plugins: r.plugins.map(Into::into),
             rate_limit: r.rate_limit.map(Into::into),
+            cache: r.cache.map(Into::into),
             tags: r.tags,
             priority: r.priority,
             enabled: r.enabled,
@@ -23,6 +23,10 @@ pub(crate) const ERR_PAYLOAD_TOO_LARGE: &str =
     "gts.x.core.errors.err.v1~x.oagw.payload.too_large.v1";
 pub(crate) const ERR_RATE_LIMIT_EXCEEDED: &str =
     "gts.x.core.errors.err.v1~x.oagw.rate_limit.exceeded.v1";
+pub(crate) const ERR_QUEUE_FULL: &str = "gts.x.core.errors.err.v1~x.oagw.queue.full.v1";
+pub(crate) const ERR_QUEUE_TIMEOUT: &str = "gts.x.core.errors.err.v1~x.oagw.queue.timeout.v1";
+pub(crate) const ERR_CIRCUIT_BREAKER_OPEN: &str =
+    "gts.x.core.errors.err.v1~x.oagw.circuit_breaker.open.v1";
 pub(crate) const ERR_SECRET_NOT_FOUND: &str = "gts.x.core.errors.err.v1~x.oagw.secret.not_found.v1";
 pub(crate) const ERR_DOWNSTREAM: &str = "gts.x.core.errors.err.v1~x.oagw.downstream.error.v1";
 pub(crate) const ERR_PROTOCOL: &str = "gts.x.core.errors.err.v1~x.oagw.protocol.error.v1";
@@ -50,6 +54,9 @@ fn gts_type(err: &DomainError) -> &str {
         DomainError::NotFound { .. } => ERR_NOT_FOUND,
         DomainError::PayloadTooLarge { .. } => ERR_PAYLOAD_TOO_LARGE,
         DomainError::RateLimitExceeded { .. } => ERR_RATE_LIMIT_EXCEEDED,
+        DomainError::QueueFull { .. } => ERR_QUEUE_FULL,
+        DomainError::QueueTimeout { .. } => ERR_QUEUE_TIMEOUT,
+        DomainError::CircuitBreakerOpen { .. } => ERR_CIRCUIT_BREAKER_OPEN,
         DomainError::SecretNotFound { .. } => ERR_SECRET_NOT_FOUND,
         DomainError::DownstreamError { .. } | DomainError::Internal { .. } => ERR_DOWNSTREAM,
         DomainError::ProtocolError { .. } => ERR_PROTOCOL,
@@ -77,7 +84,10 @@ fn http_status_code(err: &DomainError) -> StatusCode {
         DomainError::DownstreamError { .. } | DomainError::ProtocolError { .. } => {
             StatusCode::BAD_GATEWAY
         }
-        DomainError::UpstreamDisabled { .. } => StatusCode::SERVICE_UNAVAILABLE,
+        DomainError::UpstreamDisabled { .. }
+        | DomainError::QueueFull { .. }
+        | DomainError::QueueTimeout { .. }
+        | DomainError::CircuitBreakerOpen { .. } => StatusCode::SERVICE_UNAVAILABLE,
         DomainError::ConnectionTimeout { .. } | DomainError::RequestTimeout { .. } => {
             StatusCode::GATEWAY_TIMEOUT
         }
@@ -96,6 +106,9 @@ fn error_title(err: &DomainError) -> &str {
         DomainError::NotFound { .. } => "Not Found",
         DomainError::PayloadTooLarge { .. } => "Payload Too Large",
         DomainError::RateLimitExceeded { .. } => "Rate Limit Exceeded",
+        DomainError::QueueFull { .. } => "Queue Full",
+        DomainError::QueueTimeout { .. } => "Queue Timeout",
+        DomainError::CircuitBreakerOpen { .. } => "Circuit Breaker Open",
         DomainError::SecretNotFound { .. } => "Secret Not Found",
         DomainError::DownstreamError { .. } | DomainError::Internal { .. } => "Downstream Error",
         DomainError::ProtocolError { .. } => "Protocol Error",
@@ -115,6 +128,9 @@ fn error_instance(err: &DomainError) -> &str {
         | DomainError::AuthenticationFailed { instance, .. }
         | DomainError::PayloadTooLarge { instance, .. }
         | DomainError::RateLimitExceeded { instance, .. }
+        | DomainError::QueueFull { instance, .. }
+        | DomainError::QueueTimeout { instance, .. }
+        | DomainError::CircuitBreakerOpen { instance, .. }
         | DomainError::SecretNotFound { instance, .. }
         | DomainError::DownstreamError { instance, .. }
         | DomainError::ProtocolError { instance, .. }
@@ -167,6 +183,18 @@ pub fn error_response(err: DomainError) -> Response {
         DomainError::RateLimitExceeded {
             retry_after_secs: Some(secs),
             ..
+        }
+        | DomainError::QueueFull {
+            retry_after_secs: Some(secs),
+            ..
+        }
+        | DomainError::QueueTimeout {
+            retry_after_secs: Some(secs),
+            ..
+        }
+        | DomainError::CircuitBreakerOpen {
+            retry_after_secs: Some(secs),
+            ..
         } => Some(*secs),
         _ => None,
     };
@@ -229,6 +257,52 @@ mod tests {
         assert_eq!(p.type_url, ERR_RATE_LIMIT_EXCEEDED);
     }
 
+    #[test]
+    fn queue_errors_produce_503_with_retry_after() {
+        let err = DomainError::QueueTimeout {
+            detail: "request waited 5000ms in rate-limit queue".into(),
+            instance: "/oagw/v1/proxy/api.openai.com/v1/chat/completions".into(),
+            retry_after_secs: Some(2),
+        };
+        let resp = error_response(err);
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers().get("retry-after").unwrap(), "2");
+
+        let p: Problem = DomainError::QueueFull {
+            detail: "queue full".into(),
+            instance: "/test".into(),
+            retry_after_secs: Some(1),
+        }
+        .into();
+        assert_eq!(p.status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(p.type_url, ERR_QUEUE_FULL);
+    }
+
+    #[test]
+    fn circuit_breaker_open_produces_gateway_503_with_retry_after() {
+        let err = DomainError::CircuitBreakerOpen {
+            detail: "circuit breaker is open for this upstream".into(),
+            instance: "/oagw/v1/proxy/api.openai.com/v1/chat/completions".into(),
+            retry_after_secs: Some(15),
+        };
+        let resp = error_response(err);
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers().get("retry-after").unwrap(), "15");
+        assert_eq!(
+            resp.headers().get("x-oagw-error-source").unwrap(),
+            "gateway"
+        );
+
+        let p: Problem = DomainError::CircuitBreakerOpen {
+            detail: "open".into(),
+            instance: "/test".into(),
+            retry_after_secs: None,
+        }
+        .into();
+        assert_eq!(p.type_url, ERR_CIRCUIT_BREAKER_OPEN);
+        assert_eq!(p.title, "Circuit Breaker Open");
+    }
+
     #[test]
     fn not_found_produces_404() {
         let err = DomainError::NotFound {
@@ -277,6 +351,21 @@ mod tests {
                 instance: "/test".into(),
                 retry_after_secs: None,
             },
+            DomainError::QueueFull {
+                detail: "test".into(),
+                instance: "/test".into(),
+                retry_after_secs: None,
+            },
+            DomainError::QueueTimeout {
+                detail: "test".into(),
+                instance: "/test".into(),
+                retry_after_secs: None,
+            },
+            DomainError::CircuitBreakerOpen {
+                detail: "test".into(),
+                instance: "/test".into(),
+                retry_after_secs: None,
+            },
             DomainError::SecretNotFound {
                 detail: "test".into(),
                 instance: "/test".into(),
@@ -2,8 +2,12 @@ use crate::domain::error::DomainError;
 use axum::body::Body;
 use axum::extract::{Extension, Request};
 use axum::response::Response;
+use futures_util::StreamExt;
+use http_body_util::StreamBody;
+use hyper::body::Frame;
 use modkit_security::SecurityContext;
 use oagw_sdk::api::ErrorSource;
+use oagw_sdk::body::Trailers;
 
 use crate::api::rest::error::error_response;
 use crate::module::AppState;
@@ -105,6 +109,7 @@ pub async fn proxy_handler(
         .get::<ErrorSource>()
         .copied()
         .unwrap_or(ErrorSource::Gateway);
+    let trailers = resp_parts.extensions.get::<Trailers>().cloned();
 
     // Build axum response.
     // Response headers are already sanitized by the DP service layer.
@@ -117,8 +122,16 @@ pub async fn proxy_handler(
     // Add error source header.
     builder = builder.header("x-oagw-error-source", error_source.as_str());
 
-    // Stream the response body.
-    let body = Body::from_stream(sdk_body.into_stream());
+    // Stream the response body, ending with the upstream's trailers (gRPC).
+    let body = match trailers {
+        Some(trailers) => {
+            let data = sdk_body.into_stream().map(|chunk| chunk.map(Frame::data));
+            let tail = futures_util::stream::once(async move { trailers.get() })
+                .filter_map(|t| async move { t.map(|t| Ok(Frame::trailers(t))) });
+            Body::new(StreamBody::new(data.chain(tail)))
+        }
+        None => Body::from_stream(sdk_body.into_stream()),
+    };
 
     builder.body(body).map_err(|e| {
         error_response(DomainError::DownstreamError {
@@ -20,6 +20,7 @@ fn to_response(r: Route) -> RouteResponse {
         match_rules: r.match_rules.into(),
         plugins: r.plugins.map(Into::into),
         rate_limit: r.rate_limit.map(Into::into),
+        cache: r.cache.map(Into::into),
         tags: r.tags,
         priority: r.priority,
         enabled: r.enabled,
@@ -100,6 +101,5 @@ pub async fn delete_route(
         .delete_route(&ctx, uuid)
         .await
         .map_err(|e| domain_error_to_problem(e, &instance))?;
-    state.dp.remove_rate_limit_key(&format!("route:{uuid}"));
     Ok(StatusCode::NO_CONTENT)
 }
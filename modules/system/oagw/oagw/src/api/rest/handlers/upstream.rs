@@ -24,6 +24,7 @@ fn to_response(u: Upstream) -> UpstreamResponse {
         headers: u.headers.map(Into::into),
         plugins: u.plugins.map(Into::into),
         rate_limit: u.rate_limit.map(Into::into),
+        circuit_breaker: u.circuit_breaker.map(Into::into),
         tags: u.tags,
     }
 }
@@ -38,8 +39,6 @@ pub async fn create_upstream(
         .create_upstream(&ctx, req.into())
         .await
         .map_err(|e| domain_error_to_problem(e, "/oagw/v1/upstreams"))?;
-    // Defensive no-op: new IDs have no cache entry, but keeps CRUD handlers uniform.
-    state.backend_selector.invalidate(upstream.id);
     Ok((StatusCode::CREATED, Json(to_response(upstream))))
 }
 
@@ -86,7 +85,6 @@ pub async fn update_upstream(
         .update_upstream(&ctx, uuid, req.into())
         .await
         .map_err(|e| domain_error_to_problem(e, &instance))?;
-    state.backend_selector.invalidate(upstream.id);
     Ok(Json(to_response(upstream)))
 }
 
@@ -102,7 +100,5 @@ pub async fn delete_upstream(
         .delete_upstream(&ctx, uuid)
         .await
         .map_err(|e| domain_error_to_problem(e, &instance))?;
-    state.backend_selector.invalidate(uuid);
-    state.dp.remove_rate_limit_key(&format!("upstream:{uuid}"));
     Ok(StatusCode::NO_CONTENT)
 }
@@ -1,6 +1,8 @@
+use std::sync::Arc;
+
 use axum::Router;
-use modkit::api::OpenApiRegistry;
 use modkit::api::operation_builder::LicenseFeature;
+use modkit::api::{OpenApiRegistry, OperationAuditor};
 
 use crate::module::AppState;
 
@@ -19,13 +21,16 @@ impl AsRef<str> for License {
 impl LicenseFeature for License {}
 
 /// Register all OAGW REST routes with OpenAPI metadata.
+///
+/// Upstream and route changes are reported to `auditor`, if any.
 pub fn register_routes(
     mut router: Router,
     openapi: &dyn OpenApiRegistry,
     state: AppState,
+    auditor: Option<&Arc<dyn OperationAuditor>>,
 ) -> Router {
-    router = upstream::register(router, openapi);
-    router = route::register(router, openapi);
+    router = upstream::register(router, openapi, auditor);
+    router = route::register(router, openapi, auditor);
     router = proxy::register(router);
     router.layer(axum::Extension(state))
 }
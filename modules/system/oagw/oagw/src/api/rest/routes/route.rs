@@ -1,12 +1,18 @@
+use std::sync::Arc;
+
 use axum::Router;
-use modkit::api::OpenApiRegistry;
 use modkit::api::operation_builder::OperationBuilder;
+use modkit::api::{OpenApiRegistry, OperationAuditor};
 
 use super::super::dto;
 use super::super::handlers;
 use super::License;
 
-pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Router {
+pub(super) fn register(
+    mut router: Router,
+    openapi: &dyn OpenApiRegistry,
+    auditor: Option<&Arc<dyn OperationAuditor>>,
+) -> Router {
     // POST /oagw/v1/routes — Create route
     router = OperationBuilder::post("/oagw/v1/routes")
         .operation_id("oagw.create_route")
@@ -17,6 +23,7 @@ pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Rou
         .require_license_features::<License>([])
         .json_request::<dto::CreateRouteRequest>(openapi, "Route configuration")
         .handler(handlers::route::create_route)
+        .audited(auditor.cloned())
         .json_response_with_schema::<dto::RouteResponse>(
             openapi,
             http::StatusCode::CREATED,
@@ -54,6 +61,7 @@ pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Rou
         .require_license_features::<License>([])
         .json_request::<dto::UpdateRouteRequest>(openapi, "Route update data")
         .handler(handlers::route::update_route)
+        .audited(auditor.cloned())
         .json_response_with_schema::<dto::RouteResponse>(
             openapi,
             http::StatusCode::OK,
@@ -72,6 +80,7 @@ pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Rou
         .authenticated()
         .require_license_features::<License>([])
         .handler(handlers::route::delete_route)
+        .audited(auditor.cloned())
         .json_response(http::StatusCode::NO_CONTENT, "Route deleted")
         .standard_errors(openapi)
         .register(router, openapi);
@@ -1,12 +1,18 @@
+use std::sync::Arc;
+
 use axum::Router;
-use modkit::api::OpenApiRegistry;
 use modkit::api::operation_builder::OperationBuilder;
+use modkit::api::{OpenApiRegistry, OperationAuditor};
 
 use super::super::dto;
 use super::super::handlers;
 use super::License;
 
-pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Router {
+pub(super) fn register(
+    mut router: Router,
+    openapi: &dyn OpenApiRegistry,
+    auditor: Option<&Arc<dyn OperationAuditor>>,
+) -> Router {
     // POST /oagw/v1/upstreams — Create upstream
     router = OperationBuilder::post("/oagw/v1/upstreams")
         .operation_id("oagw.create_upstream")
@@ -17,6 +23,7 @@ pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Rou
         .require_license_features::<License>([])
         .json_request::<dto::CreateUpstreamRequest>(openapi, "Upstream configuration")
         .handler(handlers::upstream::create_upstream)
+        .audited(auditor.cloned())
         .json_response_with_schema::<dto::UpstreamResponse>(
             openapi,
             http::StatusCode::CREATED,
@@ -78,6 +85,7 @@ pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Rou
         .require_license_features::<License>([])
         .json_request::<dto::UpdateUpstreamRequest>(openapi, "Upstream update data")
         .handler(handlers::upstream::update_upstream)
+        .audited(auditor.cloned())
         .json_response_with_schema::<dto::UpstreamResponse>(
             openapi,
             http::StatusCode::OK,
@@ -96,6 +104,7 @@ pub(super) fn register(mut router: Router, openapi: &dyn OpenApiRegistry) -> Rou
         .authenticated()
         .require_license_features::<License>([])
         .handler(handlers::upstream::delete_upstream)
+        .audited(auditor.cloned())
         .json_response(http::StatusCode::NO_CONTENT, "Upstream deleted")
         .standard_errors(openapi)
         .register(router, openapi);
@@ -12,6 +12,23 @@ pub struct OagwConfig {
     pub max_body_size_bytes: usize,
     #[serde(default)]
     pub allow_http_upstream: bool,
+    /// Total size bound of the response cache shared by cache-enabled routes.
+    #[serde(default = "default_response_cache_max_bytes")]
+    pub response_cache_max_bytes: usize,
+    /// Where upstreams and routes are kept.
+    #[serde(default)]
+    pub storage: StorageBackend,
+}
+
+/// Backend for the upstream and route repositories.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackend {
+    /// Process-local maps: state is lost on restart and not shared between replicas.
+    #[default]
+    Memory,
+    /// The module database (requires a `database` section for `oagw`).
+    Database,
 }
 
 impl Default for OagwConfig {
@@ -20,6 +37,8 @@ impl Default for OagwConfig {
             proxy_timeout_secs: default_proxy_timeout_secs(),
             max_body_size_bytes: default_max_body_size_bytes(),
             allow_http_upstream: false,
+            response_cache_max_bytes: default_response_cache_max_bytes(),
+            storage: StorageBackend::default(),
         }
     }
 }
@@ -32,6 +51,10 @@ fn default_max_body_size_bytes() -> usize {
     10 * 1024 * 1024 // 10 MB
 }
 
+fn default_response_cache_max_bytes() -> usize {
+    64 * 1024 * 1024 // 64 MB
+}
+
 /// Read-only runtime configuration exposed to handlers via `AppState`.
 ///
 /// Derived from [`OagwConfig`] at init time.
@@ -54,6 +77,8 @@ impl fmt::Debug for OagwConfig {
             .field("proxy_timeout_secs", &self.proxy_timeout_secs)
             .field("max_body_size_bytes", &self.max_body_size_bytes)
             .field("allow_http_upstream", &self.allow_http_upstream)
+            .field("response_cache_max_bytes", &self.response_cache_max_bytes)
+            .field("storage", &self.storage)
             .finish()
     }
 }
@@ -69,4 +94,13 @@ mod tests {
         assert!(debug_output.contains("proxy_timeout_secs"));
         assert!(debug_output.contains("max_body_size_bytes"));
     }
+
+    #[test]
+    fn storage_defaults_to_memory() {
+        let config: OagwConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.storage, StorageBackend::Memory);
+
+        let config: OagwConfig = serde_json::from_str(r#"{"storage": "database"}"#).unwrap();
+        assert_eq!(config.storage, StorageBackend::Database);
+    }
 }
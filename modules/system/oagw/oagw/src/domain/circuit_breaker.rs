@@ -0,0 +1,577 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use modkit_macros::domain_model;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::domain::model::{
+    CircuitBreakerConfig, CircuitBreakerScope, Endpoint, FailureConditions,
+};
+
+/// Per-upstream (or per-endpoint) circuit breakers (ADR 0005).
+///
+/// State is kept in memory on each node; circuits are created lazily on the
+/// first request and start closed.
+#[domain_model]
+pub struct CircuitBreaker {
+    circuits: DashMap<String, Arc<Mutex<Circuit>>>,
+}
+
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl CircuitState {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+/// A state change caused by admitting or recording a call.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    pub from: CircuitState,
+    pub to: CircuitState,
+}
+
+/// Outcome of an upstream call, classified by [`FailureConditions`].
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallOutcome {
+    /// The upstream answered with this status code.
+    Response(u16),
+    Timeout,
+    ConnectionError,
+}
+
+impl FailureConditions {
+    #[must_use]
+    pub fn is_failure(&self, outcome: CallOutcome) -> bool {
+        match outcome {
+            CallOutcome::Response(status) => self.status_codes.contains(&status),
+            CallOutcome::Timeout => self.timeout,
+            CallOutcome::ConnectionError => self.connection_error,
+        }
+    }
+}
+
+/// Admission to call the upstream.
+///
+/// Pass it to [`CircuitBreaker::record`] once the outcome is known. Dropping
+/// it unrecorded (the call was cancelled, or failed for a reason unrelated to
+/// the upstream) frees its half-open probe slot without counting the call.
+#[must_use]
+pub struct CircuitPermit {
+    circuit: Arc<Mutex<Circuit>>,
+    epoch: u64,
+    probe: bool,
+    transition: Option<Transition>,
+}
+
+impl CircuitPermit {
+    /// State change caused by this admission (open → half-open), if any.
+    #[must_use]
+    pub fn transition(&self) -> Option<Transition> {
+        self.transition
+    }
+
+    /// Whether this is a half-open probe.
+    #[must_use]
+    pub fn is_probe(&self) -> bool {
+        self.probe
+    }
+}
+
+impl Drop for CircuitPermit {
+    fn drop(&mut self) {
+        if self.probe {
+            let mut circuit = lock(&self.circuit);
+            if circuit.epoch == self.epoch {
+                circuit.probes_in_flight = circuit.probes_in_flight.saturating_sub(1);
+            }
+        }
+    }
+}
+
+#[domain_model]
+struct Circuit {
+    state: CircuitState,
+    /// Bumped on every transition so that calls admitted in an earlier state
+    /// do not count towards the current one.
+    epoch: u64,
+    /// Time base for the failure-rate window buckets.
+    created_at: Instant,
+    opened_at: Instant,
+    consecutive_failures: u32,
+    window: OutcomeWindow,
+    probes_in_flight: u32,
+    probe_successes: u32,
+}
+
+impl Circuit {
+    fn new(now: Instant) -> Self {
+        Self {
+            state: CircuitState::Closed,
+            epoch: 0,
+            created_at: now,
+            opened_at: now,
+            consecutive_failures: 0,
+            window: OutcomeWindow::default(),
+            probes_in_flight: 0,
+            probe_successes: 0,
+        }
+    }
+
+    fn transition(&mut self, to: CircuitState, now: Instant) -> Transition {
+        let from = self.state;
+        self.state = to;
+        self.epoch += 1;
+        self.consecutive_failures = 0;
+        self.window.buckets.clear();
+        self.probes_in_flight = 0;
+        self.probe_successes = 0;
+        if to == CircuitState::Open {
+            self.opened_at = now;
+        }
+        Transition { from, to }
+    }
+
+    fn record_closed(&mut self, config: &CircuitBreakerConfig, failed: bool, now: Instant) -> bool {
+        if failed {
+            self.consecutive_failures += 1;
+        } else {
+            self.consecutive_failures = 0;
+        }
+        if self.consecutive_failures >= config.failure_threshold {
+            return true;
+        }
+
+        let Some(rate) = &config.failure_rate else {
+            return false;
+        };
+        let second = now.saturating_duration_since(self.created_at).as_secs();
+        self.window
+            .record(second, u64::from(rate.window_seconds), failed);
+        let (calls, failures) = self.window.totals();
+        calls >= u64::from(rate.minimum_requests)
+            && failures * 100 >= calls * u64::from(rate.threshold_percent)
+    }
+}
+
+/// Per-second call counts for the failure-rate trip.
+#[domain_model]
+#[derive(Default)]
+struct OutcomeWindow {
+    /// `(second, calls, failures)`, oldest first.
+    buckets: VecDeque<(u64, u64, u64)>,
+}
+
+impl OutcomeWindow {
+    fn record(&mut self, second: u64, window_secs: u64, failed: bool) {
+        while self
+            .buckets
+            .front()
+            .is_some_and(|(s, _, _)| s + window_secs <= second)
+        {
+            self.buckets.pop_front();
+        }
+        match self.buckets.back_mut() {
+            Some((s, calls, failures)) if *s == second => {
+                *calls += 1;
+                *failures += u64::from(failed);
+            }
+            _ => self.buckets.push_back((second, 1, u64::from(failed))),
+        }
+    }
+
+    fn totals(&self) -> (u64, u64) {
+        self.buckets
+            .iter()
+            .fold((0, 0), |(c, f), (_, calls, failures)| {
+                (c + calls, f + failures)
+            })
+    }
+}
+
+fn lock(circuit: &Mutex<Circuit>) -> MutexGuard<'_, Circuit> {
+    circuit.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn retry_after_secs(wait: Duration) -> u64 {
+    wait.as_secs_f64().ceil().max(1.0) as u64
+}
+
+impl CircuitBreaker {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            circuits: DashMap::new(),
+        }
+    }
+
+    /// Circuit key for a call to `endpoint` of `upstream_id`.
+    #[must_use]
+    pub fn key(upstream_id: Uuid, endpoint: &Endpoint, scope: CircuitBreakerScope) -> String {
+        match scope {
+            CircuitBreakerScope::Global => format!("circuit:{upstream_id}"),
+            CircuitBreakerScope::PerEndpoint => {
+                format!("circuit:{upstream_id}:{}:{}", endpoint.host, endpoint.port)
+            }
+        }
+    }
+
+    /// Drop every circuit of an upstream (e.g. after it was updated or deleted).
+    pub fn remove_upstream(&self, upstream_id: Uuid) {
+        let global = format!("circuit:{upstream_id}");
+        let per_endpoint = format!("{global}:");
+        self.circuits
+            .retain(|k, _| k != &global && !k.starts_with(&per_endpoint));
+    }
+
+    /// Admit a call unless the circuit is open, moving an open circuit to
+    /// half-open once `timeout_seconds` have passed.
+    ///
+    /// # Errors
+    /// `CircuitBreakerOpen` while open, or while half-open with all probe
+    /// slots in use.
+    pub fn try_acquire(
+        &self,
+        key: &str,
+        config: &CircuitBreakerConfig,
+        instance_uri: &str,
+    ) -> Result<CircuitPermit, DomainError> {
+        self.try_acquire_at(key, config, instance_uri, Instant::now())
+    }
+
+    fn try_acquire_at(
+        &self,
+        key: &str,
+        config: &CircuitBreakerConfig,
+        instance_uri: &str,
+        now: Instant,
+    ) -> Result<CircuitPermit, DomainError> {
+        let circuit = self
+            .circuits
+            .entry(key.to_string())
+            .or_insert_with(|| Arc::new(Mutex::new(Circuit::new(now))))
+            .clone();
+        let mut state = lock(&circuit);
+
+        let mut transition = None;
+        if state.state == CircuitState::Open {
+            let open_for = Duration::from_secs(config.timeout_seconds.into());
+            let elapsed = now.saturating_duration_since(state.opened_at);
+            if elapsed < open_for {
+                return Err(DomainError::CircuitBreakerOpen {
+                    detail: "circuit breaker is open for this upstream".into(),
+                    instance: instance_uri.to_string(),
+                    retry_after_secs: Some(retry_after_secs(open_for - elapsed)),
+                });
+            }
+            transition = Some(state.transition(CircuitState::HalfOpen, now));
+        }
+
+        let probe = state.state == CircuitState::HalfOpen;
+        if probe {
+            if state.probes_in_flight >= config.half_open_max_requests {
+                return Err(DomainError::CircuitBreakerOpen {
+                    detail: "circuit breaker is half-open and all probe requests are in flight"
+                        .into(),
+                    instance: instance_uri.to_string(),
+                    retry_after_secs: Some(1),
+                });
+            }
+            state.probes_in_flight += 1;
+        }
+
+        let epoch = state.epoch;
+        drop(state);
+        Ok(CircuitPermit {
+            circuit,
+            epoch,
+            probe,
+            transition,
+        })
+    }
+
+    /// Record the outcome of an admitted call. Returns the resulting state
+    /// change, if any.
+    pub fn record(
+        &self,
+        permit: CircuitPermit,
+        config: &CircuitBreakerConfig,
+        failed: bool,
+    ) -> Option<Transition> {
+        Self::record_at(permit, config, failed, Instant::now())
+    }
+
+    fn record_at(
+        mut permit: CircuitPermit,
+        config: &CircuitBreakerConfig,
+        failed: bool,
+        now: Instant,
+    ) -> Option<Transition> {
+        let mut circuit = lock(&permit.circuit);
+        if circuit.epoch != permit.epoch {
+            return None;
+        }
+        match circuit.state {
+            CircuitState::Closed => circuit
+                .record_closed(config, failed, now)
+                .then(|| circuit.transition(CircuitState::Open, now)),
+            CircuitState::HalfOpen => {
+                permit.probe = false;
+                circuit.probes_in_flight = circuit.probes_in_flight.saturating_sub(1);
+                if failed {
+                    return Some(circuit.transition(CircuitState::Open, now));
+                }
+                circuit.probe_successes += 1;
+                (circuit.probe_successes >= config.success_threshold)
+                    .then(|| circuit.transition(CircuitState::Closed, now))
+            }
+            CircuitState::Open => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::model::{FailureRateConfig, Scheme};
+
+    use super::*;
+
+    fn config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_threshold: 3,
+            success_threshold: 2,
+            timeout_seconds: 10,
+            half_open_max_requests: 1,
+            ..CircuitBreakerConfig::default()
+        }
+    }
+
+    fn call(
+        cb: &CircuitBreaker,
+        config: &CircuitBreakerConfig,
+        failed: bool,
+        now: Instant,
+    ) -> Option<Transition> {
+        let permit = cb.try_acquire_at("k", config, "/test", now).unwrap();
+        CircuitBreaker::record_at(permit, config, failed, now)
+    }
+
+    fn opened(from: CircuitState) -> Option<Transition> {
+        Some(Transition {
+            from,
+            to: CircuitState::Open,
+        })
+    }
+
+    #[test]
+    fn consecutive_failures_open_the_circuit() {
+        let cb = CircuitBreaker::new();
+        let config = config();
+        let now = Instant::now();
+
+        assert_eq!(call(&cb, &config, true, now), None);
+        assert_eq!(call(&cb, &config, false, now), None);
+        assert_eq!(call(&cb, &config, true, now), None);
+        assert_eq!(call(&cb, &config, true, now), None);
+        assert_eq!(call(&cb, &config, true, now), opened(CircuitState::Closed));
+
+        let err = cb.try_acquire_at("k", &config, "/test", now).err().unwrap();
+        match err {
+            DomainError::CircuitBreakerOpen {
+                retry_after_secs, ..
+            } => assert_eq!(retry_after_secs, Some(10)),
+            other => panic!("expected CircuitBreakerOpen, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn failure_rate_opens_the_circuit() {
+        let cb = CircuitBreaker::new();
+        let config = CircuitBreakerConfig {
+            failure_threshold: 100,
+            failure_rate: Some(FailureRateConfig {
+                threshold_percent: 50,
+                minimum_requests: 4,
+                window_seconds: 10,
+            }),
+            ..config()
+        };
+        let now = Instant::now();
+
+        // Alternating outcomes never reach 100 consecutive failures, but hit 50%.
+        assert_eq!(call(&cb, &config, true, now), None);
+        assert_eq!(call(&cb, &config, false, now), None);
+        assert_eq!(call(&cb, &config, false, now), None);
+        assert_eq!(call(&cb, &config, true, now), opened(CircuitState::Closed));
+    }
+
+    #[test]
+    fn failure_rate_forgets_calls_outside_the_window() {
+        let cb = CircuitBreaker::new();
+        let config = CircuitBreakerConfig {
+            failure_threshold: 100,
+            failure_rate: Some(FailureRateConfig {
+                threshold_percent: 50,
+                minimum_requests: 3,
+                window_seconds: 10,
+            }),
+            ..config()
+        };
+        let start = Instant::now();
+
+        assert_eq!(call(&cb, &config, true, start), None);
+        assert_eq!(call(&cb, &config, true, start), None);
+        let later = start + Duration::from_secs(11);
+        assert_eq!(call(&cb, &config, false, later), None);
+        assert_eq!(call(&cb, &config, false, later), None);
+        assert_eq!(call(&cb, &config, true, later), None);
+    }
+
+    #[test]
+    fn half_open_probes_close_the_circuit() {
+        let cb = CircuitBreaker::new();
+        let config = config();
+        let start = Instant::now();
+        for _ in 0..3 {
+            call(&cb, &config, true, start);
+        }
+
+        let after_timeout = start + Duration::from_secs(10);
+        let probe = cb
+            .try_acquire_at("k", &config, "/test", after_timeout)
+            .unwrap();
+        assert_eq!(
+            probe.transition(),
+            Some(Transition {
+                from: CircuitState::Open,
+                to: CircuitState::HalfOpen,
+            })
+        );
+        // Only one probe may be in flight.
+        assert!(
+            cb.try_acquire_at("k", &config, "/test", after_timeout)
+                .is_err()
+        );
+        assert_eq!(
+            CircuitBreaker::record_at(probe, &config, false, after_timeout),
+            None
+        );
+        assert_eq!(
+            call(&cb, &config, false, after_timeout),
+            Some(Transition {
+                from: CircuitState::HalfOpen,
+                to: CircuitState::Closed,
+            })
+        );
+    }
+
+    #[test]
+    fn half_open_failure_reopens_the_circuit() {
+        let cb = CircuitBreaker::new();
+        let config = config();
+        let start = Instant::now();
+        for _ in 0..3 {
+            call(&cb, &config, true, start);
+        }
+
+        let after_timeout = start + Duration::from_secs(10);
+        assert_eq!(
+            call(&cb, &config, true, after_timeout),
+            opened(CircuitState::HalfOpen)
+        );
+        assert!(
+            cb.try_acquire_at("k", &config, "/test", after_timeout)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn dropped_probe_frees_its_slot() {
+        let cb = CircuitBreaker::new();
+        let config = config();
+        let start = Instant::now();
+        for _ in 0..3 {
+            call(&cb, &config, true, start);
+        }
+
+        let after_timeout = start + Duration::from_secs(10);
+        drop(
+            cb.try_acquire_at("k", &config, "/test", after_timeout)
+                .unwrap(),
+        );
+        assert!(
+            cb.try_acquire_at("k", &config, "/test", after_timeout)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn calls_admitted_before_opening_are_ignored() {
+        let cb = CircuitBreaker::new();
+        let config = config();
+        let now = Instant::now();
+
+        let in_flight = cb.try_acquire_at("k", &config, "/test", now).unwrap();
+        for _ in 0..3 {
+            call(&cb, &config, true, now);
+        }
+        assert_eq!(
+            CircuitBreaker::record_at(in_flight, &config, true, now),
+            None
+        );
+    }
+
+    #[test]
+    fn keys_follow_scope_and_are_removed_per_upstream() {
+        let cb = CircuitBreaker::new();
+        let config = config();
+        let upstream_id = Uuid::new_v4();
+        let endpoint = Endpoint {
+            scheme: Scheme::Https,
+            host: "api.example.com".into(),
+            port: 443,
+        };
+        let global = CircuitBreaker::key(upstream_id, &endpoint, CircuitBreakerScope::Global);
+        let per_endpoint =
+            CircuitBreaker::key(upstream_id, &endpoint, CircuitBreakerScope::PerEndpoint);
+        assert_eq!(per_endpoint, format!("{global}:api.example.com:443"));
+
+        let other = CircuitBreaker::key(Uuid::new_v4(), &endpoint, CircuitBreakerScope::Global);
+        for key in [&global, &per_endpoint, &other] {
+            drop(cb.try_acquire(key, &config, "/test").unwrap());
+        }
+        cb.remove_upstream(upstream_id);
+        assert_eq!(cb.circuits.len(), 1);
+        assert!(cb.circuits.contains_key(&other));
+    }
+
+    #[test]
+    fn failure_conditions_classify_outcomes() {
+        let conditions = FailureConditions {
+            status_codes: vec![503],
+            timeout: true,
+            connection_error: false,
+        };
+        assert!(conditions.is_failure(CallOutcome::Response(503)));
+        assert!(!conditions.is_failure(CallOutcome::Response(500)));
+        assert!(conditions.is_failure(CallOutcome::Timeout));
+        assert!(!conditions.is_failure(CallOutcome::ConnectionError));
+    }
+}
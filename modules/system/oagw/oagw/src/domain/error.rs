@@ -44,6 +44,28 @@ pub enum DomainError {
         retry_after_secs: Option<u64>,
     },
 
+    #[error("{detail}")]
+    QueueFull {
+        detail: String,
+        instance: String,
+        retry_after_secs: Option<u64>,
+    },
+
+    #[error("{detail}")]
+    QueueTimeout {
+        detail: String,
+        instance: String,
+        retry_after_secs: Option<u64>,
+    },
+
+    /// The upstream's circuit breaker is open; no upstream call was made.
+    #[error("{detail}")]
+    CircuitBreakerOpen {
+        detail: String,
+        instance: String,
+        retry_after_secs: Option<u64>,
+    },
+
     #[error("{detail}")]
     SecretNotFound { detail: String, instance: String },
 
@@ -1,3 +1,4 @@
+pub(crate) mod circuit_breaker;
 pub(crate) mod error;
 pub(crate) mod gts_helpers;
 pub(crate) mod model;
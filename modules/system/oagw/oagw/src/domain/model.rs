@@ -119,6 +119,7 @@ pub struct RateLimitConfig {
     pub burst: Option<BurstConfig>,
     pub scope: RateLimitScope,
     pub strategy: RateLimitStrategy,
+    pub queue: Option<QueueConfig>,
     pub cost: u32,
 }
 
@@ -173,6 +174,144 @@ pub enum RateLimitStrategy {
     Degrade,
 }
 
+/// Wait-queue bounds for [`RateLimitStrategy::Queue`].
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueConfig {
+    pub max_depth: u32,
+    pub max_wait_ms: u64,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 100,
+            max_wait_ms: 5_000,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CircuitBreakerConfig
+// ---------------------------------------------------------------------------
+
+/// Per-upstream circuit breaker (ADR 0005). Only the `fail_fast` fallback is
+/// supported: while the circuit is open requests are rejected with 503.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    pub enabled: bool,
+    /// Consecutive failures that open a closed circuit.
+    pub failure_threshold: u32,
+    /// Optional failure-rate trip, evaluated alongside `failure_threshold`.
+    pub failure_rate: Option<FailureRateConfig>,
+    /// Consecutive probe successes that close a half-open circuit.
+    pub success_threshold: u32,
+    /// Seconds the circuit stays open before probing.
+    pub timeout_seconds: u32,
+    /// Probe requests allowed in flight while half-open.
+    pub half_open_max_requests: u32,
+    pub failure_conditions: FailureConditions,
+    pub scope: CircuitBreakerScope,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_threshold: 5,
+            failure_rate: None,
+            success_threshold: 3,
+            timeout_seconds: 30,
+            half_open_max_requests: 3,
+            failure_conditions: FailureConditions::default(),
+            scope: CircuitBreakerScope::default(),
+        }
+    }
+}
+
+/// Opens the circuit when at least `threshold_percent` of the calls in the
+/// last `window_seconds` failed, once `minimum_requests` calls were seen.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureRateConfig {
+    pub threshold_percent: u32,
+    pub minimum_requests: u32,
+    pub window_seconds: u32,
+}
+
+impl Default for FailureRateConfig {
+    fn default() -> Self {
+        Self {
+            threshold_percent: 50,
+            minimum_requests: 10,
+            window_seconds: 60,
+        }
+    }
+}
+
+/// Which upstream call outcomes count as failures.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureConditions {
+    /// Upstream response codes counted as failures.
+    pub status_codes: Vec<u16>,
+    pub timeout: bool,
+    pub connection_error: bool,
+}
+
+impl Default for FailureConditions {
+    fn default() -> Self {
+        Self {
+            status_codes: vec![500, 502, 503, 504],
+            timeout: true,
+            connection_error: true,
+        }
+    }
+}
+
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CircuitBreakerScope {
+    /// One circuit for the whole upstream.
+    #[default]
+    Global,
+    /// One circuit per endpoint of the upstream.
+    PerEndpoint,
+}
+
+// ---------------------------------------------------------------------------
+// CacheConfig
+// ---------------------------------------------------------------------------
+
+/// Opt-in per-route response cache. GET and HEAD responses are cached;
+/// POST only when `cache_post` is set (the body becomes part of the key).
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    /// Upper bound on freshness; upstream `max-age`/`s-maxage` may shorten it.
+    pub ttl_seconds: u32,
+    pub cache_post: bool,
+    /// Responses larger than this are passed through uncached.
+    pub max_entry_bytes: u64,
+    /// Request headers that select a cache variant in addition to the
+    /// upstream `Vary` header.
+    pub vary_headers: Vec<String>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_seconds: 300,
+            cache_post: false,
+            max_entry_bytes: 1024 * 1024,
+            vary_headers: Vec::new(),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // PluginsConfig
 // ---------------------------------------------------------------------------
@@ -182,6 +321,8 @@ pub enum RateLimitStrategy {
 pub struct PluginsConfig {
     pub sharing: SharingMode,
     pub items: Vec<String>,
+    /// Per-plugin configuration keyed by plugin reference.
+    pub config: HashMap<String, HashMap<String, String>>,
 }
 
 // ---------------------------------------------------------------------------
@@ -242,6 +383,7 @@ pub struct Route {
     pub match_rules: MatchRules,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub cache: Option<CacheConfig>,
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
@@ -260,6 +402,7 @@ pub struct Upstream {
     pub headers: Option<HeadersConfig>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub tags: Vec<String>,
 }
 
@@ -294,6 +437,7 @@ pub struct CreateUpstreamRequest {
     pub headers: Option<HeadersConfig>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub tags: Vec<String>,
     pub enabled: bool,
 }
@@ -308,6 +452,7 @@ pub struct UpdateUpstreamRequest {
     pub headers: Option<HeadersConfig>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub tags: Option<Vec<String>>,
     pub enabled: Option<bool>,
 }
@@ -319,6 +464,7 @@ pub struct CreateRouteRequest {
     pub match_rules: MatchRules,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub cache: Option<CacheConfig>,
     pub tags: Vec<String>,
     pub priority: i32,
     pub enabled: bool,
@@ -330,6 +476,7 @@ pub struct UpdateRouteRequest {
     pub match_rules: Option<MatchRules>,
     pub plugins: Option<PluginsConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub cache: Option<CacheConfig>,
     pub tags: Option<Vec<String>>,
     pub priority: Option<i32>,
     pub enabled: Option<bool>,
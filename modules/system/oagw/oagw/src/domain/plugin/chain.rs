@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use modkit_macros::domain_model;
+
+use crate::domain::model::{PluginsConfig, SharingMode};
+
+/// One level of plugin configuration contributing to the effective chain.
+#[domain_model]
+pub struct PluginLayer<'a> {
+    /// True when the layer belongs to an ancestor tenant's upstream.
+    pub inherited: bool,
+    pub plugins: &'a PluginsConfig,
+}
+
+/// A plugin reference with the configuration it runs with.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginBinding {
+    pub plugin_ref: String,
+    pub config: HashMap<String, String>,
+}
+
+/// Compose the effective guard/transform chain from layers ordered root
+/// ancestor first, then the caller's upstream, then the route.
+///
+/// - Inherited `private` layers are skipped; `inherit` and `enforce` layers
+///   are prepended to the descendant's plugins.
+/// - A plugin referenced by several layers runs once, at its first position.
+///   Later layers that configure it override the configuration unless an
+///   ancestor `enforce`d it.
+pub(crate) fn build_plugin_chain(layers: &[PluginLayer<'_>]) -> Vec<PluginBinding> {
+    let mut chain: Vec<PluginBinding> = Vec::new();
+    let mut enforced: Vec<bool> = Vec::new();
+
+    for layer in layers {
+        let sharing = layer.plugins.sharing;
+        if layer.inherited && sharing == SharingMode::Private {
+            continue;
+        }
+        let locks = layer.inherited && sharing == SharingMode::Enforce;
+
+        for plugin_ref in &layer.plugins.items {
+            let config = layer.plugins.config.get(plugin_ref);
+            match chain.iter().position(|b| &b.plugin_ref == plugin_ref) {
+                Some(pos) => {
+                    if !enforced[pos] {
+                        if let Some(config) = config {
+                            chain[pos].config = config.clone();
+                        }
+                        enforced[pos] = locks;
+                    }
+                }
+                None => {
+                    chain.push(PluginBinding {
+                        plugin_ref: plugin_ref.clone(),
+                        config: config.cloned().unwrap_or_default(),
+                    });
+                    enforced.push(locks);
+                }
+            }
+        }
+    }
+
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugins(sharing: SharingMode, items: &[(&str, &str)]) -> PluginsConfig {
+        PluginsConfig {
+            sharing,
+            items: items.iter().map(|(r, _)| (*r).to_string()).collect(),
+            config: items
+                .iter()
+                .filter(|(_, v)| !v.is_empty())
+                .map(|(r, v)| {
+                    (
+                        (*r).to_string(),
+                        HashMap::from([("value".to_string(), (*v).to_string())]),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn refs(chain: &[PluginBinding]) -> Vec<&str> {
+        chain.iter().map(|b| b.plugin_ref.as_str()).collect()
+    }
+
+    fn value(chain: &[PluginBinding], plugin_ref: &str) -> Option<String> {
+        chain
+            .iter()
+            .find(|b| b.plugin_ref == plugin_ref)
+            .and_then(|b| b.config.get("value").cloned())
+    }
+
+    #[test]
+    fn upstream_plugins_run_before_route_plugins() {
+        let upstream = plugins(SharingMode::Private, &[("u1", ""), ("u2", "")]);
+        let route = plugins(SharingMode::Private, &[("r1", ""), ("r2", "")]);
+        let chain = build_plugin_chain(&[
+            PluginLayer {
+                inherited: false,
+                plugins: &upstream,
+            },
+            PluginLayer {
+                inherited: false,
+                plugins: &route,
+            },
+        ]);
+        assert_eq!(refs(&chain), vec!["u1", "u2", "r1", "r2"]);
+    }
+
+    #[test]
+    fn private_ancestor_layer_is_not_inherited() {
+        let ancestor = plugins(SharingMode::Private, &[("a1", "")]);
+        let own = plugins(SharingMode::Private, &[("u1", "")]);
+        let chain = build_plugin_chain(&[
+            PluginLayer {
+                inherited: true,
+                plugins: &ancestor,
+            },
+            PluginLayer {
+                inherited: false,
+                plugins: &own,
+            },
+        ]);
+        assert_eq!(refs(&chain), vec!["u1"]);
+    }
+
+    #[test]
+    fn inherit_and_enforce_layers_are_prepended() {
+        let root = plugins(SharingMode::Enforce, &[("a1", "")]);
+        let parent = plugins(SharingMode::Inherit, &[("a2", "")]);
+        let own = plugins(SharingMode::Private, &[("u1", "")]);
+        let chain = build_plugin_chain(&[
+            PluginLayer {
+                inherited: true,
+                plugins: &root,
+            },
+            PluginLayer {
+                inherited: true,
+                plugins: &parent,
+            },
+            PluginLayer {
+                inherited: false,
+                plugins: &own,
+            },
+        ]);
+        assert_eq!(refs(&chain), vec!["a1", "a2", "u1"]);
+    }
+
+    #[test]
+    fn duplicate_runs_once_and_inherited_config_is_overridable() {
+        let ancestor = plugins(SharingMode::Inherit, &[("p", "ancestor"), ("a", "")]);
+        let own = plugins(SharingMode::Private, &[("u", ""), ("p", "own")]);
+        let chain = build_plugin_chain(&[
+            PluginLayer {
+                inherited: true,
+                plugins: &ancestor,
+            },
+            PluginLayer {
+                inherited: false,
+                plugins: &own,
+            },
+        ]);
+        assert_eq!(refs(&chain), vec!["p", "a", "u"]);
+        assert_eq!(value(&chain, "p").as_deref(), Some("own"));
+    }
+
+    #[test]
+    fn unconfigured_duplicate_keeps_inherited_config() {
+        let ancestor = plugins(SharingMode::Inherit, &[("p", "ancestor")]);
+        let own = plugins(SharingMode::Private, &[("p", "")]);
+        let chain = build_plugin_chain(&[
+            PluginLayer {
+                inherited: true,
+                plugins: &ancestor,
+            },
+            PluginLayer {
+                inherited: false,
+                plugins: &own,
+            },
+        ]);
+        assert_eq!(value(&chain, "p").as_deref(), Some("ancestor"));
+    }
+
+    #[test]
+    fn enforced_config_cannot_be_overridden() {
+        let ancestor = plugins(SharingMode::Enforce, &[("p", "ancestor")]);
+        let own = plugins(SharingMode::Private, &[("p", "own")]);
+        let route = plugins(SharingMode::Private, &[("p", "route")]);
+        let chain = build_plugin_chain(&[
+            PluginLayer {
+                inherited: true,
+                plugins: &ancestor,
+            },
+            PluginLayer {
+                inherited: false,
+                plugins: &own,
+            },
+            PluginLayer {
+                inherited: false,
+                plugins: &route,
+            },
+        ]);
+        assert_eq!(refs(&chain), vec!["p"]);
+        assert_eq!(value(&chain, "p").as_deref(), Some("ancestor"));
+    }
+}
@@ -1,21 +1,26 @@
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use modkit_macros::domain_model;
 use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+mod chain;
+
+pub(crate) use chain::{PluginLayer, build_plugin_chain};
 
 // ---------------------------------------------------------------------------
 // Plugin errors
 // ---------------------------------------------------------------------------
 
-/// Errors returned by auth plugins.
+/// Errors returned by auth, guard and transform plugins.
 #[domain_model]
 #[derive(Debug, thiserror::Error)]
 pub enum PluginError {
     #[error("secret not found: {0}")]
     SecretNotFound(String),
     #[error("authentication failed: {0}")]
-    #[allow(dead_code)] // Part of plugin trait API; no current plugin constructs this.
     AuthFailed(String),
     #[error("request rejected: {0}")]
     #[allow(dead_code)] // Part of plugin trait API; no current plugin constructs this.
@@ -31,6 +36,8 @@ pub enum PluginError {
 /// Request context passed to an auth plugin for header injection.
 #[domain_model]
 pub struct AuthContext {
+    /// Upstream the request is proxied to; scopes any cached credentials.
+    pub upstream_id: Uuid,
     /// Outbound request headers (modified in-place by the plugin).
     pub headers: HashMap<String, String>,
     /// Plugin-specific configuration key/value pairs.
@@ -47,4 +54,134 @@ pub struct AuthContext {
 pub trait AuthPlugin: Send + Sync {
     /// Apply authentication to the outbound request context.
     async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError>;
+
+    /// Called when the upstream answered `401 Unauthorized` to a request this
+    /// plugin authenticated. Plugins that cache credentials drop them here so
+    /// the next request fetches fresh ones.
+    async fn on_unauthorized(&self, _ctx: &AuthContext) {}
+}
+
+// ---------------------------------------------------------------------------
+// Guard plugin
+// ---------------------------------------------------------------------------
+
+/// Outcome of a guard check.
+#[domain_model]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardDecision {
+    /// Continue with the next plugin in the chain.
+    Allow,
+    /// Reject the request with the given HTTP status.
+    Reject { status: u16, detail: String },
+    /// Answer the request locally without calling the upstream
+    /// (e.g. a CORS preflight).
+    Respond {
+        status: u16,
+        headers: HashMap<String, String>,
+    },
+}
+
+/// Request context passed to guard and transform plugins before the upstream call.
+#[domain_model]
+pub struct RequestContext {
+    /// Upstream the request is proxied to.
+    pub upstream_id: Uuid,
+    /// Route that matched the request.
+    pub route_id: Uuid,
+    /// HTTP method of the inbound request (uppercase).
+    pub method: String,
+    /// Request path relative to the upstream alias.
+    pub path: String,
+    /// Headers as received from the client (read-only, lowercase names).
+    pub inbound_headers: HashMap<String, String>,
+    /// Outbound request headers (modified in-place by plugins).
+    pub headers: HashMap<String, String>,
+    /// Configuration of the plugin being executed.
+    pub config: HashMap<String, String>,
+    /// Security context of the calling subject.
+    pub security_context: SecurityContext,
+    /// When the gateway accepted the request.
+    pub started_at: Instant,
+    /// Time allowed for the upstream call; guards may only shorten it.
+    pub timeout: Duration,
+}
+
+/// Response context passed to plugins after the upstream answered.
+#[domain_model]
+pub struct ResponseContext {
+    /// Upstream the request was proxied to.
+    pub upstream_id: Uuid,
+    /// HTTP method of the inbound request (uppercase).
+    pub method: String,
+    /// Request path relative to the upstream alias.
+    pub path: String,
+    /// Headers as received from the client (read-only, lowercase names).
+    pub inbound_headers: HashMap<String, String>,
+    /// Headers sent to the upstream after request plugins ran (read-only).
+    pub request_headers: HashMap<String, String>,
+    /// Upstream response status code.
+    pub status: u16,
+    /// Headers returned by the upstream (read-only, lowercase names).
+    pub upstream_headers: HashMap<String, String>,
+    /// Headers to set on the response returned to the client.
+    pub headers: HashMap<String, String>,
+    /// Configuration of the plugin being executed.
+    pub config: HashMap<String, String>,
+    /// Time elapsed since the gateway accepted the request.
+    pub elapsed: Duration,
+}
+
+/// Error context passed to transform plugins when the proxy call failed.
+#[domain_model]
+pub struct ErrorContext {
+    /// Upstream the request was proxied to.
+    pub upstream_id: Uuid,
+    /// HTTP method of the inbound request (uppercase).
+    pub method: String,
+    /// Request path relative to the upstream alias.
+    pub path: String,
+    /// Human-readable error description.
+    pub detail: String,
+    /// Configuration of the plugin being executed.
+    pub config: HashMap<String, String>,
+    /// Time elapsed since the gateway accepted the request.
+    pub elapsed: Duration,
+}
+
+/// Trait for guard plugins: validation and policy enforcement that may reject
+/// a request before it reaches the upstream.
+#[async_trait]
+pub trait GuardPlugin: Send + Sync {
+    /// Inspect the request and decide whether it may proceed.
+    async fn guard_request(&self, ctx: &mut RequestContext) -> Result<GuardDecision, PluginError>;
+
+    /// Adjust the response of a request this guard allowed.
+    async fn guard_response(&self, _ctx: &mut ResponseContext) -> Result<(), PluginError> {
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Transform plugin
+// ---------------------------------------------------------------------------
+
+/// Trait for transform plugins: request/response mutation and observation.
+///
+/// All phases default to no-ops so plugins implement only the ones they need.
+#[async_trait]
+pub trait TransformPlugin: Send + Sync {
+    /// Modify the outbound request before the upstream call.
+    async fn transform_request(&self, _ctx: &mut RequestContext) -> Result<(), PluginError> {
+        Ok(())
+    }
+
+    /// Modify the response returned to the client.
+    async fn transform_response(&self, _ctx: &mut ResponseContext) -> Result<(), PluginError> {
+        Ok(())
+    }
+
+    /// Observe a failed proxy call.
+    async fn transform_error(&self, _ctx: &ErrorContext) -> Result<(), PluginError> {
+        Ok(())
+    }
 }
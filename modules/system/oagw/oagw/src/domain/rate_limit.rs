@@ -1,14 +1,107 @@
-use std::collections::HashSet;
-use std::time::Instant;
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
 
 use crate::domain::error::DomainError;
-use crate::domain::model::{RateLimitConfig, Window};
+use crate::domain::model::{RateLimitAlgorithm, RateLimitConfig, RateLimitStrategy, Window};
 use dashmap::DashMap;
 use modkit_macros::domain_model;
 
 #[domain_model]
 pub struct RateLimiter {
-    buckets: DashMap<String, TokenBucket>,
+    buckets: DashMap<String, Limiter>,
+    queues: DashMap<String, Arc<WaitQueue>>,
+}
+
+/// Outcome of [`RateLimiter::acquire`] for a request that may proceed.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitDecision {
+    /// Capacity was available immediately.
+    Allowed,
+    /// The request waited in the key's queue before capacity freed up.
+    Queued { waited: Duration },
+    /// The limit was exceeded but the strategy is `degrade`: the request
+    /// proceeds and the caller should tag it as degraded.
+    Degraded,
+}
+
+/// Per-key FIFO wait queue for the `queue` strategy.
+///
+/// Waiters line up on `gate` (tokio's mutex grants the lock in FIFO order),
+/// so only the head of the queue polls the limiter. `depth` counts every
+/// request that holds a slot, including the head.
+#[domain_model]
+#[derive(Default)]
+struct WaitQueue {
+    gate: tokio::sync::Mutex<()>,
+    depth: AtomicU32,
+}
+
+impl WaitQueue {
+    /// Reserve a slot unless `max_depth` requests are already waiting.
+    fn reserve(&self, max_depth: u32) -> Option<QueueSlot<'_>> {
+        self.depth
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |d| {
+                (d < max_depth).then_some(d + 1)
+            })
+            .ok()
+            .map(|_| QueueSlot(self))
+    }
+}
+
+/// Releases the reserved queue slot on drop (admission, timeout or cancel).
+struct QueueSlot<'a>(&'a WaitQueue);
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.0.depth.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Per-key limiter state, selected by `RateLimitConfig.algorithm`.
+#[domain_model]
+enum Limiter {
+    TokenBucket(TokenBucket),
+    SlidingWindow(SlidingWindowLog),
+}
+
+impl Limiter {
+    fn new(config: &RateLimitConfig) -> Self {
+        match config.algorithm {
+            RateLimitAlgorithm::TokenBucket => Self::TokenBucket(TokenBucket::new(config)),
+            RateLimitAlgorithm::SlidingWindow => Self::SlidingWindow(SlidingWindowLog::new(config)),
+        }
+    }
+
+    fn algorithm(&self) -> RateLimitAlgorithm {
+        match self {
+            Self::TokenBucket(_) => RateLimitAlgorithm::TokenBucket,
+            Self::SlidingWindow(_) => RateLimitAlgorithm::SlidingWindow,
+        }
+    }
+
+    /// Try to consume `cost`; on failure returns how long until it would fit.
+    fn try_consume(&mut self, cost: u32, now: Instant) -> Result<(), Duration> {
+        match self {
+            Self::TokenBucket(bucket) => {
+                let cost = f64::from(cost);
+                if bucket.try_consume(cost) {
+                    Ok(())
+                } else {
+                    Err(bucket.retry_after(cost))
+                }
+            }
+            Self::SlidingWindow(log) => {
+                if log.try_consume(cost, now) {
+                    Ok(())
+                } else {
+                    Err(log.retry_after(cost, now))
+                }
+            }
+        }
+    }
 }
 
 #[domain_model]
@@ -52,15 +145,81 @@ impl TokenBucket {
         }
     }
 
-    fn retry_after_secs(&self, cost: f64) -> u64 {
+    fn retry_after(&self, cost: f64) -> Duration {
         if self.refill_rate <= 0.0 {
-            return 60;
+            return Duration::from_secs(60);
         }
         let needed = cost - self.tokens;
         if needed <= 0.0 {
-            return 0;
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(needed / self.refill_rate)
+    }
+}
+
+/// Sliding-window log: remembers the timestamp and cost of every admitted
+/// request within the trailing window, so the limit holds for *any* window
+/// of `sustained.window` length — not just aligned ones. Burst capacity does
+/// not apply; the window admits at most `sustained.rate` cost units.
+///
+/// Memory is O(requests admitted per window) per key.
+#[domain_model]
+struct SlidingWindowLog {
+    limit: u64,
+    window: Duration,
+    /// Admitted requests in arrival order: (admitted at, cost).
+    entries: VecDeque<(Instant, u32)>,
+    /// Sum of costs currently in `entries`.
+    used: u64,
+}
+
+impl SlidingWindowLog {
+    fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            limit: u64::from(config.sustained.rate),
+            window: Duration::from_secs_f64(window_to_secs(&config.sustained.window)),
+            entries: VecDeque::new(),
+            used: 0,
+        }
+    }
+
+    /// Drop entries that have fallen out of the trailing window ending at `now`.
+    fn evict(&mut self, now: Instant) {
+        while let Some(&(at, cost)) = self.entries.front() {
+            if now.duration_since(at) < self.window {
+                break;
+            }
+            self.entries.pop_front();
+            self.used -= u64::from(cost);
+        }
+    }
+
+    fn try_consume(&mut self, cost: u32, now: Instant) -> bool {
+        self.evict(now);
+        if self.used + u64::from(cost) <= self.limit {
+            self.entries.push_back((now, cost));
+            self.used += u64::from(cost);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Time until enough of the oldest entries expire to admit `cost`.
+    fn retry_after(&self, cost: u32, now: Instant) -> Duration {
+        let cost = u64::from(cost);
+        if cost > self.limit {
+            // Can never fit; advise waiting a full window.
+            return self.window;
         }
-        (needed / self.refill_rate).ceil() as u64
+        let mut remaining = self.used;
+        for &(at, entry_cost) in &self.entries {
+            remaining -= u64::from(entry_cost);
+            if remaining + cost <= self.limit {
+                return (at + self.window).saturating_duration_since(now);
+            }
+        }
+        Duration::ZERO
     }
 }
 
@@ -73,11 +232,17 @@ fn window_to_secs(window: &Window) -> f64 {
     }
 }
 
+/// Whole seconds for a Retry-After header: rounded up, never zero.
+fn retry_after_secs(wait: Duration) -> u64 {
+    wait.as_secs_f64().ceil().max(1.0) as u64
+}
+
 impl RateLimiter {
     #[must_use]
     pub fn new() -> Self {
         Self {
             buckets: DashMap::new(),
+            queues: DashMap::new(),
         }
     }
 
@@ -85,6 +250,7 @@ impl RateLimiter {
     #[allow(dead_code)]
     pub fn purge_keys(&self, active_keys: &HashSet<String>) {
         self.buckets.retain(|k, _| active_keys.contains(k));
+        self.queues.retain(|k, _| active_keys.contains(k));
     }
 
     /// Remove a single rate-limit bucket by key.
@@ -93,6 +259,96 @@ impl RateLimiter {
     /// does not linger in memory.
     pub fn remove_key(&self, key: &str) {
         self.buckets.remove(key);
+        self.queues.remove(key);
+    }
+
+    /// Admit a request according to `config.strategy`.
+    ///
+    /// - `reject`: same as [`Self::try_consume`].
+    /// - `degrade`: never fails on the limit; returns
+    ///   [`RateLimitDecision::Degraded`] when it would have rejected.
+    /// - `queue`: waits in the key's FIFO queue until capacity frees up,
+    ///   bounded by `config.queue` (max depth and max wait).
+    ///
+    /// # Errors
+    /// `RateLimitExceeded` (reject), `QueueFull` or `QueueTimeout` (queue).
+    pub async fn acquire(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+        instance_uri: &str,
+    ) -> Result<RateLimitDecision, DomainError> {
+        match config.strategy {
+            RateLimitStrategy::Reject => self
+                .try_consume(key, config, instance_uri)
+                .map(|()| RateLimitDecision::Allowed),
+            RateLimitStrategy::Degrade => match self.consume_at(key, config, Instant::now()) {
+                Ok(()) => Ok(RateLimitDecision::Allowed),
+                Err(_) => Ok(RateLimitDecision::Degraded),
+            },
+            RateLimitStrategy::Queue => self.enqueue(key, config, instance_uri).await,
+        }
+    }
+
+    async fn enqueue(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+        instance_uri: &str,
+    ) -> Result<RateLimitDecision, DomainError> {
+        let queue_config = config.queue.clone().unwrap_or_default();
+        let max_wait = Duration::from_millis(queue_config.max_wait_ms);
+        let queue = Arc::clone(&self.queues.entry(key.to_string()).or_default());
+
+        // Fast path: nobody is waiting, so taking capacity directly is fair.
+        if queue.depth.load(Ordering::Acquire) == 0
+            && self.consume_at(key, config, Instant::now()).is_ok()
+        {
+            return Ok(RateLimitDecision::Allowed);
+        }
+
+        let Some(_slot) = queue.reserve(queue_config.max_depth) else {
+            return Err(DomainError::QueueFull {
+                detail: format!(
+                    "rate limit queue for key {key} is full ({} waiting)",
+                    queue_config.max_depth
+                ),
+                instance: instance_uri.to_string(),
+                retry_after_secs: Some(retry_after_secs(max_wait)),
+            });
+        };
+
+        let started = Instant::now();
+        let deadline = started + max_wait;
+        let wait_turn = async {
+            let _head = queue.gate.lock().await;
+            loop {
+                let now = Instant::now();
+                match self.consume_at(key, config, now) {
+                    Ok(()) => return Ok(()),
+                    // Capacity won't free up in time; fail now instead of
+                    // sleeping until the deadline.
+                    Err(wait) if now + wait > deadline => return Err(wait),
+                    Err(wait) => tokio::time::sleep(wait).await,
+                }
+            }
+        };
+
+        let timeout = |wait: Duration| DomainError::QueueTimeout {
+            detail: format!(
+                "request queued for {}ms on key {key}, no capacity available",
+                started.elapsed().as_millis()
+            ),
+            instance: instance_uri.to_string(),
+            retry_after_secs: Some(retry_after_secs(wait)),
+        };
+        match tokio::time::timeout_at(deadline.into(), wait_turn).await {
+            Ok(Ok(())) => Ok(RateLimitDecision::Queued {
+                waited: started.elapsed(),
+            }),
+            Ok(Err(wait)) => Err(timeout(wait)),
+            Err(_) => Err(timeout(max_wait)),
+        }
     }
 
     /// Try to consume tokens for the given key.
@@ -105,29 +361,51 @@ impl RateLimiter {
         config: &RateLimitConfig,
         instance_uri: &str,
     ) -> Result<(), DomainError> {
-        let cost = config.cost as f64;
-        let mut bucket = self
-            .buckets
-            .entry(key.to_string())
-            .or_insert_with(|| TokenBucket::new(config));
+        self.try_consume_at(key, config, instance_uri, Instant::now())
+    }
 
-        if bucket.try_consume(cost) {
-            Ok(())
-        } else {
-            let retry_after = bucket.retry_after_secs(cost);
-            Err(DomainError::RateLimitExceeded {
+    fn try_consume_at(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+        instance_uri: &str,
+        now: Instant,
+    ) -> Result<(), DomainError> {
+        self.consume_at(key, config, now)
+            .map_err(|wait| DomainError::RateLimitExceeded {
                 detail: format!("rate limit exceeded for key: {key}"),
                 instance: instance_uri.to_string(),
-                retry_after_secs: Some(retry_after),
+                retry_after_secs: Some(retry_after_secs(wait)),
             })
+    }
+
+    /// Consume `config.cost` for `key`; on failure returns the time until it would fit.
+    fn consume_at(
+        &self,
+        key: &str,
+        config: &RateLimitConfig,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let mut limiter = self
+            .buckets
+            .entry(key.to_string())
+            .or_insert_with(|| Limiter::new(config));
+
+        // The algorithm may change when the upstream/route is updated;
+        // start fresh rather than interpreting foreign state.
+        if limiter.algorithm() != config.algorithm {
+            *limiter = Limiter::new(config);
         }
+
+        limiter.try_consume(config.cost, now)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::domain::model::{
-        BurstConfig, RateLimitAlgorithm, RateLimitScope, RateLimitStrategy, SustainedRate,
+        BurstConfig, QueueConfig, RateLimitAlgorithm, RateLimitScope, RateLimitStrategy,
+        SustainedRate,
     };
 
     use super::*;
@@ -140,10 +418,18 @@ mod tests {
             burst: burst_capacity.map(|c| BurstConfig { capacity: c }),
             scope: RateLimitScope::Tenant,
             strategy: RateLimitStrategy::Reject,
+            queue: None,
             cost: 1,
         }
     }
 
+    fn make_sliding_config(rate: u32, window: Window) -> RateLimitConfig {
+        RateLimitConfig {
+            algorithm: RateLimitAlgorithm::SlidingWindow,
+            ..make_config(rate, window, None)
+        }
+    }
+
     #[test]
     fn allows_within_capacity() {
         let limiter = RateLimiter::new();
@@ -251,4 +537,252 @@ mod tests {
 
         assert!(limiter.buckets.is_empty());
     }
+
+    // -----------------------------------------------------------------------
+    // Sliding window
+    // -----------------------------------------------------------------------
+
+    fn retry_after(err: DomainError) -> u64 {
+        match err {
+            DomainError::RateLimitExceeded {
+                retry_after_secs, ..
+            } => retry_after_secs.unwrap(),
+            other => panic!("expected RateLimitExceeded, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn sliding_window_denies_at_limit() {
+        let limiter = RateLimiter::new();
+        let config = make_sliding_config(3, Window::Minute);
+        let t0 = Instant::now();
+        for _ in 0..3 {
+            limiter.try_consume_at("k", &config, "/test", t0).unwrap();
+        }
+        let err = limiter
+            .try_consume_at("k", &config, "/test", t0)
+            .unwrap_err();
+        assert_eq!(retry_after(err), 60);
+    }
+
+    #[test]
+    fn sliding_window_ignores_burst_capacity() {
+        let limiter = RateLimiter::new();
+        let config = RateLimitConfig {
+            algorithm: RateLimitAlgorithm::SlidingWindow,
+            ..make_config(2, Window::Second, Some(10))
+        };
+        let t0 = Instant::now();
+        limiter.try_consume_at("k", &config, "/test", t0).unwrap();
+        limiter.try_consume_at("k", &config, "/test", t0).unwrap();
+        assert!(limiter.try_consume_at("k", &config, "/test", t0).is_err());
+    }
+
+    #[test]
+    fn sliding_window_has_no_boundary_burst() {
+        let limiter = RateLimiter::new();
+        let config = make_sliding_config(2, Window::Minute);
+        let t0 = Instant::now();
+        limiter.try_consume_at("k", &config, "/test", t0).unwrap();
+        let t1 = t0 + Duration::from_secs(59);
+        limiter.try_consume_at("k", &config, "/test", t1).unwrap();
+
+        // A fixed window would reset at t0+60s; the sliding window still
+        // counts the request admitted at t1.
+        let t2 = t0 + Duration::from_secs(60);
+        limiter.try_consume_at("k", &config, "/test", t2).unwrap();
+        let err = limiter
+            .try_consume_at("k", &config, "/test", t2)
+            .unwrap_err();
+        // Oldest remaining entry (t1) expires at t1+60s = 59s from t2.
+        assert_eq!(retry_after(err), 59);
+
+        let t3 = t1 + Duration::from_secs(60);
+        assert!(limiter.try_consume_at("k", &config, "/test", t3).is_ok());
+    }
+
+    #[test]
+    fn sliding_window_retry_after_accounts_for_cost() {
+        let limiter = RateLimiter::new();
+        let single = make_sliding_config(4, Window::Minute);
+        let t0 = Instant::now();
+        limiter.try_consume_at("k", &single, "/test", t0).unwrap();
+        let t1 = t0 + Duration::from_secs(10);
+        limiter.try_consume_at("k", &single, "/test", t1).unwrap();
+        let t2 = t0 + Duration::from_secs(20);
+        limiter.try_consume_at("k", &single, "/test", t2).unwrap();
+
+        // 3 of 4 used; a cost-3 request needs the two oldest entries gone.
+        let heavy = RateLimitConfig { cost: 3, ..single };
+        let now = t0 + Duration::from_secs(30);
+        let err = limiter
+            .try_consume_at("k", &heavy, "/test", now)
+            .unwrap_err();
+        assert_eq!(retry_after(err), 40);
+    }
+
+    #[test]
+    fn sliding_window_cost_above_limit_never_fits() {
+        let limiter = RateLimiter::new();
+        let config = RateLimitConfig {
+            cost: 5,
+            ..make_sliding_config(2, Window::Hour)
+        };
+        let err = limiter.try_consume("k", &config, "/test").unwrap_err();
+        assert_eq!(retry_after(err), 3600);
+    }
+
+    #[test]
+    fn algorithm_change_resets_state() {
+        let limiter = RateLimiter::new();
+        let bucket = make_config(1, Window::Minute, None);
+        limiter.try_consume("k", &bucket, "/test").unwrap();
+        assert!(limiter.try_consume("k", &bucket, "/test").is_err());
+
+        let sliding = make_sliding_config(1, Window::Minute);
+        assert!(limiter.try_consume("k", &sliding, "/test").is_ok());
+        assert!(matches!(
+            *limiter.buckets.get("k").unwrap(),
+            Limiter::SlidingWindow(_)
+        ));
+    }
+
+    #[test]
+    fn sliding_window_keys_are_housekept() {
+        let limiter = RateLimiter::new();
+        let config = make_sliding_config(10, Window::Second);
+        limiter.try_consume("a", &config, "/test").unwrap();
+        limiter.try_consume("b", &config, "/test").unwrap();
+
+        limiter.remove_key("a");
+        assert!(!limiter.buckets.contains_key("a"));
+
+        limiter.purge_keys(&HashSet::new());
+        assert!(limiter.buckets.is_empty());
+    }
+
+    fn make_queue_config(
+        rate: u32,
+        window: Window,
+        max_depth: u32,
+        max_wait_ms: u64,
+    ) -> RateLimitConfig {
+        RateLimitConfig {
+            strategy: RateLimitStrategy::Queue,
+            queue: Some(QueueConfig {
+                max_depth,
+                max_wait_ms,
+            }),
+            ..make_config(rate, window, None)
+        }
+    }
+
+    #[tokio::test]
+    async fn degrade_admits_over_limit() {
+        let limiter = RateLimiter::new();
+        let config = RateLimitConfig {
+            strategy: RateLimitStrategy::Degrade,
+            ..make_config(1, Window::Minute, None)
+        };
+        let first = limiter.acquire("k", &config, "/test").await.unwrap();
+        assert_eq!(first, RateLimitDecision::Allowed);
+        let second = limiter.acquire("k", &config, "/test").await.unwrap();
+        assert_eq!(second, RateLimitDecision::Degraded);
+    }
+
+    #[tokio::test]
+    async fn reject_strategy_still_rejects() {
+        let limiter = RateLimiter::new();
+        let config = make_config(1, Window::Minute, None);
+        limiter.acquire("k", &config, "/test").await.unwrap();
+        let err = limiter.acquire("k", &config, "/test").await.unwrap_err();
+        assert!(matches!(err, DomainError::RateLimitExceeded { .. }));
+    }
+
+    #[tokio::test]
+    async fn queue_admits_after_capacity_frees() {
+        let limiter = RateLimiter::new();
+        // 10/s without burst: one token every 100ms.
+        let config = make_queue_config(10, Window::Second, 10, 1_000);
+        for _ in 0..10 {
+            limiter.acquire("k", &config, "/test").await.unwrap();
+        }
+        match limiter.acquire("k", &config, "/test").await.unwrap() {
+            RateLimitDecision::Queued { waited } => assert!(waited >= Duration::from_millis(50)),
+            other => panic!("expected Queued, got {other:?}"),
+        }
+        assert_eq!(
+            limiter
+                .queues
+                .get("k")
+                .unwrap()
+                .depth
+                .load(Ordering::Acquire),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn queue_full_rejects_new_waiters() {
+        let limiter = Arc::new(RateLimiter::new());
+        let config = make_queue_config(10, Window::Second, 1, 1_000);
+        for _ in 0..10 {
+            limiter.acquire("k", &config, "/test").await.unwrap();
+        }
+
+        let waiter = {
+            let limiter = Arc::clone(&limiter);
+            let config = config.clone();
+            tokio::spawn(async move { limiter.acquire("k", &config, "/test").await })
+        };
+        while limiter
+            .queues
+            .get("k")
+            .is_none_or(|q| q.depth.load(Ordering::Acquire) == 0)
+        {
+            tokio::task::yield_now().await;
+        }
+
+        let err = limiter.acquire("k", &config, "/test").await.unwrap_err();
+        assert!(matches!(err, DomainError::QueueFull { .. }));
+        assert!(matches!(
+            waiter.await.unwrap(),
+            Ok(RateLimitDecision::Queued { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn queue_times_out_when_capacity_cannot_free_in_time() {
+        let limiter = RateLimiter::new();
+        let config = make_queue_config(1, Window::Minute, 10, 50);
+        limiter.acquire("k", &config, "/test").await.unwrap();
+
+        let started = Instant::now();
+        let err = limiter.acquire("k", &config, "/test").await.unwrap_err();
+        // Next token is ~60s away, far beyond max_wait: fail without sleeping.
+        assert!(started.elapsed() < Duration::from_secs(1));
+        match err {
+            DomainError::QueueTimeout {
+                retry_after_secs, ..
+            } => assert_eq!(retry_after_secs, Some(60)),
+            other => panic!("expected QueueTimeout, got {other:?}"),
+        }
+        assert_eq!(
+            limiter
+                .queues
+                .get("k")
+                .unwrap()
+                .depth
+                .load(Ordering::Acquire),
+            0
+        );
+    }
+
+    #[test]
+    fn remove_key_drops_queue() {
+        let limiter = RateLimiter::new();
+        limiter.queues.insert("k".into(), Arc::default());
+        limiter.remove_key("k");
+        assert!(limiter.queues.is_empty());
+    }
 }
@@ -16,7 +16,6 @@ pub enum RepositoryError {
     #[error("conflict: {0}")]
     Conflict(String),
     #[error("internal: {0}")]
-    #[allow(dead_code)]
     Internal(String),
 }
 
@@ -78,6 +77,16 @@ pub trait RouteRepository: Send + Sync {
         path: &str,
     ) -> Result<Route, RepositoryError>;
 
+    /// Find the gRPC route for a fully-qualified service and method.
+    /// Match criteria: enabled=true, exact service and method, highest priority.
+    async fn find_grpc_matching(
+        &self,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+        service: &str,
+        method: &str,
+    ) -> Result<Route, RepositoryError>;
+
     /// Update an existing route.
     async fn update(&self, route: Route) -> Result<Route, RepositoryError>;
 
@@ -245,6 +245,33 @@ fn domain_err_to_sdk(err: DomainError) -> ServiceGatewayError {
             instance,
             retry_after_secs,
         },
+        DomainError::QueueFull {
+            detail,
+            instance,
+            retry_after_secs,
+        } => ServiceGatewayError::QueueFull {
+            detail,
+            instance,
+            retry_after_secs,
+        },
+        DomainError::QueueTimeout {
+            detail,
+            instance,
+            retry_after_secs,
+        } => ServiceGatewayError::QueueTimeout {
+            detail,
+            instance,
+            retry_after_secs,
+        },
+        DomainError::CircuitBreakerOpen {
+            detail,
+            instance,
+            retry_after_secs,
+        } => ServiceGatewayError::CircuitBreakerOpen {
+            detail,
+            instance,
+            retry_after_secs,
+        },
         DomainError::SecretNotFound { detail, instance } => {
             ServiceGatewayError::SecretNotFound { detail, instance }
         }
@@ -279,6 +306,10 @@ fn sdk_create_upstream_to_domain(
         headers: req.headers().cloned().map(headers_config_to_domain),
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
+        circuit_breaker: req
+            .circuit_breaker()
+            .cloned()
+            .map(circuit_breaker_config_to_domain),
         tags: req.tags().to_vec(),
         enabled: req.enabled(),
     }
@@ -295,6 +326,10 @@ fn sdk_update_upstream_to_domain(
         headers: req.headers().cloned().map(headers_config_to_domain),
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
+        circuit_breaker: req
+            .circuit_breaker()
+            .cloned()
+            .map(circuit_breaker_config_to_domain),
         tags: req.tags().map(|s| s.to_vec()),
         enabled: req.enabled(),
     }
@@ -306,6 +341,7 @@ fn sdk_create_route_to_domain(req: oagw_sdk::CreateRouteRequest) -> model::Creat
         match_rules: match_rules_to_domain(req.match_rules().clone()),
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
+        cache: req.cache().cloned().map(cache_config_to_domain),
         tags: req.tags().to_vec(),
         priority: req.priority(),
         enabled: req.enabled(),
@@ -317,6 +353,7 @@ fn sdk_update_route_to_domain(req: oagw_sdk::UpdateRouteRequest) -> model::Updat
         match_rules: req.match_rules().cloned().map(match_rules_to_domain),
         plugins: req.plugins().cloned().map(plugins_config_to_domain),
         rate_limit: req.rate_limit().cloned().map(rate_limit_config_to_domain),
+        cache: req.cache().cloned().map(cache_config_to_domain),
         tags: req.tags().map(|s| s.to_vec()),
         priority: req.priority(),
         enabled: req.enabled(),
@@ -435,14 +472,55 @@ fn rate_limit_config_to_domain(v: oagw_sdk::RateLimitConfig) -> model::RateLimit
             oagw_sdk::RateLimitStrategy::Queue => model::RateLimitStrategy::Queue,
             oagw_sdk::RateLimitStrategy::Degrade => model::RateLimitStrategy::Degrade,
         },
+        queue: v.queue.map(|q| model::QueueConfig {
+            max_depth: q.max_depth,
+            max_wait_ms: q.max_wait_ms,
+        }),
         cost: v.cost,
     }
 }
 
+fn circuit_breaker_config_to_domain(
+    v: oagw_sdk::CircuitBreakerConfig,
+) -> model::CircuitBreakerConfig {
+    model::CircuitBreakerConfig {
+        enabled: v.enabled,
+        failure_threshold: v.failure_threshold,
+        failure_rate: v.failure_rate.map(|r| model::FailureRateConfig {
+            threshold_percent: r.threshold_percent,
+            minimum_requests: r.minimum_requests,
+            window_seconds: r.window_seconds,
+        }),
+        success_threshold: v.success_threshold,
+        timeout_seconds: v.timeout_seconds,
+        half_open_max_requests: v.half_open_max_requests,
+        failure_conditions: model::FailureConditions {
+            status_codes: v.failure_conditions.status_codes,
+            timeout: v.failure_conditions.timeout,
+            connection_error: v.failure_conditions.connection_error,
+        },
+        scope: match v.scope {
+            oagw_sdk::CircuitBreakerScope::Global => model::CircuitBreakerScope::Global,
+            oagw_sdk::CircuitBreakerScope::PerEndpoint => model::CircuitBreakerScope::PerEndpoint,
+        },
+    }
+}
+
+fn cache_config_to_domain(v: oagw_sdk::CacheConfig) -> model::CacheConfig {
+    model::CacheConfig {
+        enabled: v.enabled,
+        ttl_seconds: v.ttl_seconds,
+        cache_post: v.cache_post,
+        max_entry_bytes: v.max_entry_bytes,
+        vary_headers: v.vary_headers,
+    }
+}
+
 fn plugins_config_to_domain(v: oagw_sdk::PluginsConfig) -> model::PluginsConfig {
     model::PluginsConfig {
         sharing: sharing_mode_to_domain(v.sharing),
         items: v.items,
+        config: v.config,
     }
 }
 
@@ -549,8 +627,10 @@ fn upstream_to_sdk(u: model::Upstream) -> oagw_sdk::Upstream {
         plugins: u.plugins.map(|p| oagw_sdk::PluginsConfig {
             sharing: sharing_mode_to_sdk(p.sharing),
             items: p.items,
+            config: p.config,
         }),
         rate_limit: u.rate_limit.map(rate_limit_config_to_sdk),
+        circuit_breaker: u.circuit_breaker.map(circuit_breaker_config_to_sdk),
         tags: u.tags,
     }
 }
@@ -588,8 +668,10 @@ fn route_to_sdk(r: model::Route) -> oagw_sdk::Route {
         plugins: r.plugins.map(|p| oagw_sdk::PluginsConfig {
             sharing: sharing_mode_to_sdk(p.sharing),
             items: p.items,
+            config: p.config,
         }),
         rate_limit: r.rate_limit.map(rate_limit_config_to_sdk),
+        cache: r.cache.map(cache_config_to_sdk),
         tags: r.tags,
         priority: r.priority,
         enabled: r.enabled,
@@ -627,10 +709,48 @@ fn rate_limit_config_to_sdk(v: model::RateLimitConfig) -> oagw_sdk::RateLimitCon
             model::RateLimitStrategy::Queue => oagw_sdk::RateLimitStrategy::Queue,
             model::RateLimitStrategy::Degrade => oagw_sdk::RateLimitStrategy::Degrade,
         },
+        queue: v.queue.map(|q| oagw_sdk::QueueConfig {
+            max_depth: q.max_depth,
+            max_wait_ms: q.max_wait_ms,
+        }),
         cost: v.cost,
     }
 }
 
+fn circuit_breaker_config_to_sdk(v: model::CircuitBreakerConfig) -> oagw_sdk::CircuitBreakerConfig {
+    oagw_sdk::CircuitBreakerConfig {
+        enabled: v.enabled,
+        failure_threshold: v.failure_threshold,
+        failure_rate: v.failure_rate.map(|r| oagw_sdk::FailureRateConfig {
+            threshold_percent: r.threshold_percent,
+            minimum_requests: r.minimum_requests,
+            window_seconds: r.window_seconds,
+        }),
+        success_threshold: v.success_threshold,
+        timeout_seconds: v.timeout_seconds,
+        half_open_max_requests: v.half_open_max_requests,
+        failure_conditions: oagw_sdk::FailureConditions {
+            status_codes: v.failure_conditions.status_codes,
+            timeout: v.failure_conditions.timeout,
+            connection_error: v.failure_conditions.connection_error,
+        },
+        scope: match v.scope {
+            model::CircuitBreakerScope::Global => oagw_sdk::CircuitBreakerScope::Global,
+            model::CircuitBreakerScope::PerEndpoint => oagw_sdk::CircuitBreakerScope::PerEndpoint,
+        },
+    }
+}
+
+fn cache_config_to_sdk(v: model::CacheConfig) -> oagw_sdk::CacheConfig {
+    oagw_sdk::CacheConfig {
+        enabled: v.enabled,
+        ttl_seconds: v.ttl_seconds,
+        cache_post: v.cache_post,
+        max_entry_bytes: v.max_entry_bytes,
+        vary_headers: v.vary_headers,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -691,6 +811,7 @@ mod tests {
             headers: None,
             plugins: None,
             rate_limit: None,
+            circuit_breaker: None,
             tags: vec![],
         };
 
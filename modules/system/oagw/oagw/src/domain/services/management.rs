@@ -1,34 +1,82 @@
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock, Weak};
+use std::time::Duration;
 
-use super::ControlPlaneService;
+use super::{ConfigChangeListener, ControlPlaneService};
 use std::net::IpAddr;
 
 use crate::domain::error::DomainError;
+use crate::domain::gts_helpers::{GUARD_PLUGIN_SCHEMA, TRANSFORM_PLUGIN_SCHEMA};
 use crate::domain::model::{
-    CreateRouteRequest, CreateUpstreamRequest, Endpoint, ListQuery, Route, UpdateRouteRequest,
-    UpdateUpstreamRequest, Upstream,
+    CacheConfig, CircuitBreakerConfig, CreateRouteRequest, CreateUpstreamRequest, Endpoint,
+    ListQuery, PluginsConfig, Route, UpdateRouteRequest, UpdateUpstreamRequest, Upstream,
 };
-use crate::domain::repo::{RouteRepository, UpstreamRepository};
+use crate::domain::repo::{RepositoryError, RouteRepository, UpstreamRepository};
 
 use async_trait::async_trait;
+use modkit::cache::Cache;
 use modkit_macros::domain_model;
 use modkit_security::SecurityContext;
+use tenant_resolver_sdk::{GetAncestorsOptions, TenantResolverClient};
 use uuid::Uuid;
 
-/// Control Plane service implementation backed by in-memory repositories.
+/// Control Plane service implementation backed by the upstream and route repositories.
 #[domain_model]
 pub(crate) struct ControlPlaneServiceImpl {
     upstreams: Arc<dyn UpstreamRepository>,
     routes: Arc<dyn RouteRepository>,
+    /// Resolves the tenant hierarchy for inherited plugin chains. Without it
+    /// only the caller's own configuration applies.
+    tenant_resolver: Option<Arc<dyn TenantResolverClient>>,
+    /// Composed ancestor chains per `(tenant, alias)`. Upstream CRUD drops
+    /// the entries of the affected aliases; tenant moves are picked up once
+    /// the entries expire.
+    ancestors: Cache<(Uuid, String), Vec<Upstream>>,
+    /// Runtime state told about every configuration change.
+    listener: OnceLock<Weak<dyn ConfigChangeListener>>,
 }
 
+/// How long a composed ancestor chain is reused.
+const ANCESTOR_CACHE_TTL: Duration = Duration::from_secs(30);
+/// Maximum number of cached ancestor chains.
+const ANCESTOR_CACHE_MAX_ENTRIES: usize = 10_000;
+
 impl ControlPlaneServiceImpl {
     #[must_use]
     pub(crate) fn new(
         upstreams: Arc<dyn UpstreamRepository>,
         routes: Arc<dyn RouteRepository>,
     ) -> Self {
-        Self { upstreams, routes }
+        Self {
+            upstreams,
+            routes,
+            tenant_resolver: None,
+            ancestors: Cache::new(
+                "oagw.ancestor_upstreams",
+                ANCESTOR_CACHE_TTL,
+                ANCESTOR_CACHE_MAX_ENTRIES,
+            ),
+            listener: OnceLock::new(),
+        }
+    }
+
+    fn notify(&self, event: impl FnOnce(&dyn ConfigChangeListener)) {
+        if let Some(listener) = self.listener.get().and_then(Weak::upgrade) {
+            event(listener.as_ref());
+        }
+    }
+
+    /// Drop the cached ancestor chains that may include an upstream with
+    /// one of `aliases`.
+    fn invalidate_ancestors(&self, aliases: &[&str]) {
+        self.ancestors
+            .invalidate_if(|(_, alias), _| aliases.contains(&alias.as_str()));
+    }
+
+    /// Enable ancestor lookups through the tenant hierarchy.
+    #[must_use]
+    pub(crate) fn with_tenant_resolver(mut self, resolver: Arc<dyn TenantResolverClient>) -> Self {
+        self.tenant_resolver = Some(resolver);
+        self
     }
 }
 
@@ -124,6 +172,116 @@ fn validate_alias(alias: &str) -> Result<(), DomainError> {
     Ok(())
 }
 
+/// Validate a plugin chain: only guard and transform plugins may be listed,
+/// and per-plugin configuration must refer to a listed plugin.
+fn validate_plugins(plugins: Option<&PluginsConfig>) -> Result<(), DomainError> {
+    let Some(plugins) = plugins else {
+        return Ok(());
+    };
+    for plugin_ref in &plugins.items {
+        if !plugin_ref.starts_with(GUARD_PLUGIN_SCHEMA)
+            && !plugin_ref.starts_with(TRANSFORM_PLUGIN_SCHEMA)
+        {
+            return Err(DomainError::validation(format!(
+                "plugin '{plugin_ref}' is not a guard or transform plugin"
+            )));
+        }
+    }
+    if let Some(key) = plugins
+        .config
+        .keys()
+        .find(|key| !plugins.items.contains(key))
+    {
+        return Err(DomainError::validation(format!(
+            "plugin config references '{key}', which is not listed in plugin items"
+        )));
+    }
+    Ok(())
+}
+
+/// Longest failure-rate window the in-memory breaker keeps outcomes for.
+const MAX_FAILURE_RATE_WINDOW_SECS: u32 = 3600;
+
+/// Validate circuit breaker thresholds: counts and durations must be positive,
+/// the failure rate a percentage, and failure status codes valid HTTP codes.
+fn validate_circuit_breaker(cb: Option<&CircuitBreakerConfig>) -> Result<(), DomainError> {
+    let Some(cb) = cb else {
+        return Ok(());
+    };
+    for (field, value) in [
+        ("failure_threshold", cb.failure_threshold),
+        ("success_threshold", cb.success_threshold),
+        ("timeout_seconds", cb.timeout_seconds),
+        ("half_open_max_requests", cb.half_open_max_requests),
+    ] {
+        if value == 0 {
+            return Err(DomainError::validation(format!(
+                "circuit_breaker.{field} must be at least 1"
+            )));
+        }
+    }
+    if let Some(rate) = &cb.failure_rate {
+        if !(1..=100).contains(&rate.threshold_percent) {
+            return Err(DomainError::validation(
+                "circuit_breaker.failure_rate.threshold_percent must be between 1 and 100",
+            ));
+        }
+        if rate.minimum_requests == 0 {
+            return Err(DomainError::validation(
+                "circuit_breaker.failure_rate.minimum_requests must be at least 1",
+            ));
+        }
+        if !(1..=MAX_FAILURE_RATE_WINDOW_SECS).contains(&rate.window_seconds) {
+            return Err(DomainError::validation(format!(
+                "circuit_breaker.failure_rate.window_seconds must be between 1 and {MAX_FAILURE_RATE_WINDOW_SECS}"
+            )));
+        }
+    }
+    if let Some(code) = cb
+        .failure_conditions
+        .status_codes
+        .iter()
+        .find(|code| !(100..=599).contains(*code))
+    {
+        return Err(DomainError::validation(format!(
+            "circuit_breaker.failure_conditions.status_codes contains invalid status {code}"
+        )));
+    }
+    Ok(())
+}
+
+/// Upper bound on `cache.ttl_seconds` (one day).
+const MAX_CACHE_TTL_SECS: u32 = 86_400;
+
+fn validate_cache(cache: Option<&CacheConfig>) -> Result<(), DomainError> {
+    let Some(cache) = cache else {
+        return Ok(());
+    };
+    if !(1..=MAX_CACHE_TTL_SECS).contains(&cache.ttl_seconds) {
+        return Err(DomainError::validation(format!(
+            "cache.ttl_seconds must be between 1 and {MAX_CACHE_TTL_SECS}"
+        )));
+    }
+    if cache.max_entry_bytes == 0 {
+        return Err(DomainError::validation(
+            "cache.max_entry_bytes must be at least 1",
+        ));
+    }
+    // RFC 9110 token characters.
+    let is_token = |name: &str| {
+        !name.is_empty()
+            && name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+    };
+    if let Some(name) = cache.vary_headers.iter().find(|name| !is_token(name)) {
+        return Err(DomainError::validation(format!(
+            "cache.vary_headers contains invalid header name '{name}'"
+        )));
+    }
+    Ok(())
+}
+
 /// Strip surrounding `[` and `]` from a host string so that bracketed IPv6
 /// literals (e.g. `[2001:db8::1]`) can be parsed by `Ipv6Addr` / `IpAddr`.
 fn strip_brackets(host: &str) -> &str {
@@ -153,6 +311,8 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         req: CreateUpstreamRequest,
     ) -> Result<Upstream, DomainError> {
         validate_endpoints(&req.server.endpoints)?;
+        validate_plugins(req.plugins.as_ref())?;
+        validate_circuit_breaker(req.circuit_breaker.as_ref())?;
 
         let tenant_id = ctx.subject_tenant_id();
         let id = Uuid::new_v4();
@@ -168,6 +328,7 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             headers: req.headers.clone(),
             plugins: req.plugins.clone(),
             rate_limit: req.rate_limit.clone(),
+            circuit_breaker: req.circuit_breaker.clone(),
             tags: req.tags.clone(),
         };
 
@@ -180,10 +341,14 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
 
         let upstream = Upstream { alias, ..upstream };
 
-        self.upstreams
+        let created = self
+            .upstreams
             .create(upstream)
             .await
-            .map_err(DomainError::from)
+            .map_err(DomainError::from)?;
+        self.invalidate_ancestors(&[&created.alias]);
+        self.notify(|l| l.upstream_changed(created.id, false));
+        Ok(created)
     }
 
     async fn get_upstream(&self, ctx: &SecurityContext, id: Uuid) -> Result<Upstream, DomainError> {
@@ -218,6 +383,9 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             .get_by_id(tenant_id, id)
             .await
             .map_err(|_| DomainError::not_found("upstream", id))?;
+        let previous_alias = existing.alias.clone();
+        // New endpoints or thresholds start from fresh, closed circuits.
+        let reset_circuits = req.server.is_some() || req.circuit_breaker.is_some();
 
         // Apply partial update.
         if let Some(server) = req.server {
@@ -238,11 +406,16 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             existing.headers = Some(headers);
         }
         if let Some(plugins) = req.plugins {
+            validate_plugins(Some(&plugins))?;
             existing.plugins = Some(plugins);
         }
         if let Some(rate_limit) = req.rate_limit {
             existing.rate_limit = Some(rate_limit);
         }
+        if let Some(circuit_breaker) = req.circuit_breaker {
+            validate_circuit_breaker(Some(&circuit_breaker))?;
+            existing.circuit_breaker = Some(circuit_breaker);
+        }
         if let Some(tags) = req.tags {
             existing.tags = tags;
         }
@@ -250,20 +423,32 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             existing.enabled = enabled;
         }
 
-        self.upstreams
+        let updated = self
+            .upstreams
             .update(existing)
             .await
-            .map_err(DomainError::from)
+            .map_err(DomainError::from)?;
+        self.invalidate_ancestors(&[&previous_alias, &updated.alias]);
+        self.notify(|l| l.upstream_changed(updated.id, reset_circuits));
+        Ok(updated)
     }
 
     async fn delete_upstream(&self, ctx: &SecurityContext, id: Uuid) -> Result<(), DomainError> {
         let tenant_id = ctx.subject_tenant_id();
+        let existing = self
+            .upstreams
+            .get_by_id(tenant_id, id)
+            .await
+            .map_err(|_| DomainError::not_found("upstream", id))?;
         // Cascade delete routes.
         let _ = self.routes.delete_by_upstream(tenant_id, id).await;
         self.upstreams
             .delete(tenant_id, id)
             .await
-            .map_err(|_| DomainError::not_found("upstream", id))
+            .map_err(|_| DomainError::not_found("upstream", id))?;
+        self.invalidate_ancestors(&[&existing.alias]);
+        self.notify(|l| l.upstream_deleted(id));
+        Ok(())
     }
 
     // -- Route CRUD --
@@ -273,6 +458,8 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
         ctx: &SecurityContext,
         req: CreateRouteRequest,
     ) -> Result<Route, DomainError> {
+        validate_plugins(req.plugins.as_ref())?;
+        validate_cache(req.cache.as_ref())?;
         let tenant_id = ctx.subject_tenant_id();
         // Validate that the upstream exists and belongs to this tenant.
         self.upstreams
@@ -292,12 +479,16 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             match_rules: req.match_rules,
             plugins: req.plugins,
             rate_limit: req.rate_limit,
+            cache: req.cache,
             tags: req.tags,
             priority: req.priority,
             enabled: req.enabled,
         };
 
-        self.routes.create(route).await.map_err(DomainError::from)
+        let created = self.routes.create(route).await.map_err(DomainError::from)?;
+        // A new route may shadow paths whose responses are cached under another.
+        self.notify(|l| l.route_changed(created.upstream_id));
+        Ok(created)
     }
 
     async fn get_route(&self, ctx: &SecurityContext, id: Uuid) -> Result<Route, DomainError> {
@@ -338,11 +529,16 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             existing.match_rules = match_rules;
         }
         if let Some(plugins) = req.plugins {
+            validate_plugins(Some(&plugins))?;
             existing.plugins = Some(plugins);
         }
         if let Some(rate_limit) = req.rate_limit {
             existing.rate_limit = Some(rate_limit);
         }
+        if let Some(cache) = req.cache {
+            validate_cache(Some(&cache))?;
+            existing.cache = Some(cache);
+        }
         if let Some(tags) = req.tags {
             existing.tags = tags;
         }
@@ -353,18 +549,28 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             existing.enabled = enabled;
         }
 
-        self.routes
+        let updated = self
+            .routes
             .update(existing)
             .await
-            .map_err(DomainError::from)
+            .map_err(DomainError::from)?;
+        self.notify(|l| l.route_changed(updated.upstream_id));
+        Ok(updated)
     }
 
     async fn delete_route(&self, ctx: &SecurityContext, id: Uuid) -> Result<(), DomainError> {
         let tenant_id = ctx.subject_tenant_id();
+        let existing = self
+            .routes
+            .get_by_id(tenant_id, id)
+            .await
+            .map_err(|_| DomainError::not_found("route", id))?;
         self.routes
             .delete(tenant_id, id)
             .await
-            .map_err(|_| DomainError::not_found("route", id))
+            .map_err(|_| DomainError::not_found("route", id))?;
+        self.notify(|l| l.route_deleted(id, existing.upstream_id));
+        Ok(())
     }
 
     // -- Resolution --
@@ -401,10 +607,63 @@ impl ControlPlaneService for ControlPlaneServiceImpl {
             .await
             .map_err(|_| DomainError::not_found("route", Uuid::nil()))
     }
+
+    async fn resolve_grpc_route(
+        &self,
+        ctx: &SecurityContext,
+        upstream_id: Uuid,
+        service: &str,
+        method: &str,
+    ) -> Result<Route, DomainError> {
+        let tenant_id = ctx.subject_tenant_id();
+        self.routes
+            .find_grpc_matching(tenant_id, upstream_id, service, method)
+            .await
+            .map_err(|_| DomainError::not_found("route", Uuid::nil()))
+    }
+
+    async fn resolve_ancestor_upstreams(
+        &self,
+        ctx: &SecurityContext,
+        alias: &str,
+    ) -> Result<Vec<Upstream>, DomainError> {
+        let Some(ref tenant_resolver) = self.tenant_resolver else {
+            return Ok(Vec::new());
+        };
+        let tenant_id = ctx.subject_tenant_id();
+        self.ancestors
+            .get_or_try_insert_with((tenant_id, alias.to_owned()), || async {
+                let hierarchy = tenant_resolver
+                    .get_ancestors(ctx, tenant_id, &GetAncestorsOptions::default())
+                    .await
+                    .map_err(|e| {
+                        DomainError::internal(format!("failed to resolve tenant ancestors: {e}"))
+                    })?;
+
+                // Ancestors come ordered from the direct parent up; walk from the root.
+                let mut upstreams = Vec::new();
+                for ancestor in hierarchy.ancestors.iter().rev() {
+                    match self.upstreams.get_by_alias(ancestor.id, alias).await {
+                        Ok(upstream) => upstreams.push(upstream),
+                        Err(RepositoryError::NotFound { .. }) => {}
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+                Ok(upstreams)
+            })
+            .await
+    }
+
+    fn set_change_listener(&self, listener: Weak<dyn ConfigChangeListener>) {
+        if self.listener.set(listener).is_err() {
+            tracing::warn!("OAGW config change listener already set; ignoring");
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::sync::Arc;
 
     use crate::domain::model::{
@@ -444,6 +703,7 @@ mod tests {
             headers: None,
             plugins: None,
             rate_limit: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         }
@@ -463,6 +723,7 @@ mod tests {
             },
             plugins: None,
             rate_limit: None,
+            cache: None,
             tags: vec![],
             priority: 0,
             enabled: true,
@@ -541,6 +802,7 @@ mod tests {
             headers: None,
             plugins: None,
             rate_limit: None,
+            circuit_breaker: None,
             tags: vec![],
             enabled: true,
         };
@@ -919,4 +1181,329 @@ mod tests {
         // Route should be gone.
         assert!(svc.get_route(&ctx, r.id).await.is_err());
     }
+
+    #[tokio::test]
+    async fn plugins_must_be_guards_or_transforms_with_listed_config() {
+        use crate::domain::gts_helpers::{
+            APIKEY_AUTH_PLUGIN_ID, CORS_GUARD_PLUGIN_ID, REQUEST_ID_TRANSFORM_PLUGIN_ID,
+        };
+
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let plugins = |items: &[&str], config_key: Option<&str>| PluginsConfig {
+            sharing: Default::default(),
+            items: items.iter().map(ToString::to_string).collect(),
+            config: config_key
+                .map(|k| (k.to_string(), HashMap::new()))
+                .into_iter()
+                .collect(),
+        };
+
+        for (alias, bad) in [
+            ("auth-item", plugins(&[APIKEY_AUTH_PLUGIN_ID], None)),
+            (
+                "stray-config",
+                plugins(
+                    &[REQUEST_ID_TRANSFORM_PLUGIN_ID],
+                    Some(CORS_GUARD_PLUGIN_ID),
+                ),
+            ),
+        ] {
+            let mut req = make_create_upstream(Some(alias));
+            req.plugins = Some(bad);
+            let err = svc.create_upstream(&ctx, req).await.unwrap_err();
+            assert!(matches!(err, DomainError::Validation { .. }), "{alias}");
+        }
+
+        let mut req = make_create_upstream(Some("valid"));
+        req.plugins = Some(plugins(
+            &[CORS_GUARD_PLUGIN_ID, REQUEST_ID_TRANSFORM_PLUGIN_ID],
+            Some(CORS_GUARD_PLUGIN_ID),
+        ));
+        svc.create_upstream(&ctx, req).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_thresholds_are_validated() {
+        use crate::domain::model::{CircuitBreakerConfig, FailureRateConfig};
+
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let invalid = [
+            CircuitBreakerConfig {
+                failure_threshold: 0,
+                ..Default::default()
+            },
+            CircuitBreakerConfig {
+                half_open_max_requests: 0,
+                ..Default::default()
+            },
+            CircuitBreakerConfig {
+                failure_rate: Some(FailureRateConfig {
+                    threshold_percent: 101,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            CircuitBreakerConfig {
+                failure_conditions: crate::domain::model::FailureConditions {
+                    status_codes: vec![500, 42],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ];
+        for (i, cb) in invalid.into_iter().enumerate() {
+            let mut req = make_create_upstream(Some(&format!("cb-{i}")));
+            req.circuit_breaker = Some(cb);
+            let err = svc.create_upstream(&ctx, req).await.unwrap_err();
+            assert!(matches!(err, DomainError::Validation { .. }), "case {i}");
+        }
+
+        let mut req = make_create_upstream(Some("cb-valid"));
+        req.circuit_breaker = Some(CircuitBreakerConfig::default());
+        let u = svc.create_upstream(&ctx, req).await.unwrap();
+
+        let update = UpdateUpstreamRequest {
+            circuit_breaker: Some(CircuitBreakerConfig {
+                timeout_seconds: 0,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let err = svc.update_upstream(&ctx, u.id, update).await.unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }));
+    }
+
+    #[tokio::test]
+    async fn route_cache_config_is_validated() {
+        use crate::domain::model::CacheConfig;
+
+        let svc = make_service();
+        let ctx = test_ctx(Uuid::new_v4());
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("cache")))
+            .await
+            .unwrap();
+
+        let invalid = [
+            CacheConfig {
+                ttl_seconds: 0,
+                ..Default::default()
+            },
+            CacheConfig {
+                ttl_seconds: MAX_CACHE_TTL_SECS + 1,
+                ..Default::default()
+            },
+            CacheConfig {
+                max_entry_bytes: 0,
+                ..Default::default()
+            },
+            CacheConfig {
+                vary_headers: vec!["x model".into()],
+                ..Default::default()
+            },
+        ];
+        for (i, cache) in invalid.into_iter().enumerate() {
+            let mut req = make_create_route(u.id);
+            req.cache = Some(cache);
+            let err = svc.create_route(&ctx, req).await.unwrap_err();
+            assert!(matches!(err, DomainError::Validation { .. }), "case {i}");
+        }
+
+        let mut req = make_create_route(u.id);
+        req.cache = Some(CacheConfig::default());
+        let r = svc.create_route(&ctx, req).await.unwrap();
+
+        let update = UpdateRouteRequest {
+            cache: Some(CacheConfig {
+                vary_headers: vec![String::new()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let err = svc.update_route(&ctx, r.id, update).await.unwrap_err();
+        assert!(matches!(err, DomainError::Validation { .. }));
+    }
+
+    #[tokio::test]
+    async fn ancestor_upstreams_resolve_root_first() {
+        use crate::domain::test_support::MockTenantResolverClient;
+
+        let root = Uuid::new_v4();
+        let parent = Uuid::new_v4();
+        let child = Uuid::new_v4();
+        let svc = make_service().with_tenant_resolver(Arc::new(
+            MockTenantResolverClient::with_parents(vec![(child, parent), (parent, root)]),
+        ));
+
+        let root_up = svc
+            .create_upstream(&test_ctx(root), make_create_upstream(Some("shared")))
+            .await
+            .unwrap();
+        let parent_up = svc
+            .create_upstream(&test_ctx(parent), make_create_upstream(Some("shared")))
+            .await
+            .unwrap();
+        svc.create_upstream(&test_ctx(parent), make_create_upstream(Some("other")))
+            .await
+            .unwrap();
+
+        let ancestors = svc
+            .resolve_ancestor_upstreams(&test_ctx(child), "shared")
+            .await
+            .unwrap();
+        let ids: Vec<Uuid> = ancestors.iter().map(|u| u.id).collect();
+        assert_eq!(ids, vec![root_up.id, parent_up.id]);
+
+        assert!(
+            make_service()
+                .resolve_ancestor_upstreams(&test_ctx(child), "shared")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn config_changes_notify_the_listener() {
+        #[derive(Default)]
+        struct Recorder(std::sync::Mutex<Vec<String>>);
+
+        impl Recorder {
+            fn push(&self, event: String) {
+                self.0.lock().unwrap().push(event);
+            }
+        }
+
+        impl ConfigChangeListener for Recorder {
+            fn upstream_changed(&self, upstream_id: Uuid, reset_circuits: bool) {
+                self.push(format!("upstream_changed {upstream_id} {reset_circuits}"));
+            }
+            fn upstream_deleted(&self, upstream_id: Uuid) {
+                self.push(format!("upstream_deleted {upstream_id}"));
+            }
+            fn route_changed(&self, upstream_id: Uuid) {
+                self.push(format!("route_changed {upstream_id}"));
+            }
+            fn route_deleted(&self, route_id: Uuid, upstream_id: Uuid) {
+                self.push(format!("route_deleted {route_id} {upstream_id}"));
+            }
+        }
+
+        let svc = make_service();
+        let recorder = Arc::new(Recorder::default());
+        svc.set_change_listener(Arc::downgrade(&recorder) as _);
+        let ctx = test_ctx(Uuid::new_v4());
+
+        let u = svc
+            .create_upstream(&ctx, make_create_upstream(Some("notify")))
+            .await
+            .unwrap();
+        let tags_only = UpdateUpstreamRequest {
+            tags: Some(vec!["t".into()]),
+            ..Default::default()
+        };
+        svc.update_upstream(&ctx, u.id, tags_only).await.unwrap();
+        let new_server = UpdateUpstreamRequest {
+            server: Some(u.server.clone()),
+            ..Default::default()
+        };
+        svc.update_upstream(&ctx, u.id, new_server).await.unwrap();
+        let r = svc
+            .create_route(&ctx, make_create_route(u.id))
+            .await
+            .unwrap();
+        svc.update_route(&ctx, r.id, UpdateRouteRequest::default())
+            .await
+            .unwrap();
+        svc.delete_route(&ctx, r.id).await.unwrap();
+        svc.delete_upstream(&ctx, u.id).await.unwrap();
+
+        // Failed changes notify nothing.
+        svc.delete_upstream(&ctx, u.id).await.unwrap_err();
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                format!("upstream_changed {} false", u.id),
+                format!("upstream_changed {} false", u.id),
+                format!("upstream_changed {} true", u.id),
+                format!("route_changed {}", u.id),
+                format!("route_changed {}", u.id),
+                format!("route_deleted {} {}", r.id, u.id),
+                format!("upstream_deleted {}", u.id),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn ancestor_chain_is_cached_until_upstream_crud() {
+        use crate::domain::test_support::MockTenantResolverClient;
+
+        let parent = Uuid::new_v4();
+        let child = Uuid::new_v4();
+        let resolver = Arc::new(MockTenantResolverClient::with_parents(vec![(
+            child, parent,
+        )]));
+        let svc = make_service().with_tenant_resolver(Arc::clone(&resolver) as _);
+        let ctx = test_ctx(child);
+
+        assert!(
+            svc.resolve_ancestor_upstreams(&ctx, "shared")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        svc.resolve_ancestor_upstreams(&ctx, "shared")
+            .await
+            .unwrap();
+        assert_eq!(resolver.ancestor_calls(), 1);
+
+        // Creating an upstream under the alias drops the cached chain.
+        let up = svc
+            .create_upstream(&test_ctx(parent), make_create_upstream(Some("shared")))
+            .await
+            .unwrap();
+        let chain = svc
+            .resolve_ancestor_upstreams(&ctx, "shared")
+            .await
+            .unwrap();
+        assert_eq!(chain.len(), 1);
+        assert_eq!(resolver.ancestor_calls(), 2);
+
+        // Renaming it drops the chains of both the old and the new alias.
+        svc.resolve_ancestor_upstreams(&ctx, "renamed")
+            .await
+            .unwrap();
+        let rename = UpdateUpstreamRequest {
+            alias: Some("renamed".into()),
+            ..Default::default()
+        };
+        svc.update_upstream(&test_ctx(parent), up.id, rename)
+            .await
+            .unwrap();
+        assert!(
+            svc.resolve_ancestor_upstreams(&ctx, "shared")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            svc.resolve_ancestor_upstreams(&ctx, "renamed")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        svc.delete_upstream(&test_ctx(parent), up.id).await.unwrap();
+        assert!(
+            svc.resolve_ancestor_upstreams(&ctx, "renamed")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
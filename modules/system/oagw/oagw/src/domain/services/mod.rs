@@ -4,6 +4,8 @@ pub(crate) mod management;
 pub(crate) use client::ServiceGatewayClientV1Facade;
 pub(crate) use management::ControlPlaneServiceImpl;
 
+use std::sync::Weak;
+
 use async_trait::async_trait;
 use modkit_security::SecurityContext;
 use oagw_sdk::Body;
@@ -84,6 +86,47 @@ pub(crate) trait ControlPlaneService: Send + Sync {
         method: &str,
         path: &str,
     ) -> Result<Route, DomainError>;
+
+    /// Resolve the gRPC route for `service` (fully qualified) and `method`.
+    async fn resolve_grpc_route(
+        &self,
+        ctx: &SecurityContext,
+        upstream_id: Uuid,
+        service: &str,
+        method: &str,
+    ) -> Result<Route, DomainError>;
+
+    /// Upstreams with `alias` owned by ancestors of the caller's tenant,
+    /// ordered from the root down. Used to compose inherited plugin chains.
+    async fn resolve_ancestor_upstreams(
+        &self,
+        ctx: &SecurityContext,
+        alias: &str,
+    ) -> Result<Vec<Upstream>, DomainError>;
+
+    // -- Change notification --
+
+    /// Register the listener told about every upstream and route change.
+    /// Held weakly: the data plane owns the control plane, not vice versa.
+    fn set_change_listener(&self, listener: Weak<dyn ConfigChangeListener>);
+}
+
+/// Runtime state derived from upstream and route configuration (endpoint
+/// selection, circuits, rate-limit buckets, cached responses). The control
+/// plane notifies it after every successful change, whichever API made it.
+pub(crate) trait ConfigChangeListener: Send + Sync {
+    /// An upstream was created or updated. `reset_circuits` is set when its
+    /// endpoints or circuit breaker thresholds changed.
+    fn upstream_changed(&self, upstream_id: Uuid, reset_circuits: bool);
+
+    /// An upstream and its routes were deleted.
+    fn upstream_deleted(&self, upstream_id: Uuid);
+
+    /// A route of `upstream_id` was created or updated.
+    fn route_changed(&self, upstream_id: Uuid);
+
+    /// A route of `upstream_id` was deleted.
+    fn route_deleted(&self, route_id: Uuid, upstream_id: Uuid);
 }
 
 /// Internal Data Plane service trait — proxy orchestration and plugin execution.
@@ -94,9 +137,6 @@ pub(crate) trait DataPlaneService: Send + Sync {
         ctx: SecurityContext,
         req: http::Request<Body>,
     ) -> Result<http::Response<Body>, DomainError>;
-
-    /// Remove a rate-limit bucket by key (e.g. `"upstream:{id}"` or `"route:{id}"`).
-    fn remove_rate_limit_key(&self, key: &str);
 }
 
 /// Endpoint selection abstraction for multi-endpoint load balancing.
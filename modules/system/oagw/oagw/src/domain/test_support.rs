@@ -1,6 +1,7 @@
 //! Test utilities for CP and DP integration tests.
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
@@ -10,11 +11,17 @@ use authz_resolver_sdk::{
     EvaluationResponseContext, PolicyEnforcer,
 };
 use credstore_sdk::{
-    CredStoreClientV1, CredStoreError, GetSecretResponse, SecretRef, SecretValue, SharingMode,
+    CredStoreClientV1, CredStoreError, GetSecretResponse, RotateSecretResponse, SecretInfo,
+    SecretRef, SecretValue, SecretVersion, SharingMode,
 };
 use modkit::client_hub::ClientHub;
 use modkit_security::SecurityContext;
 use oagw_sdk::api::ServiceGatewayClientV1;
+use tenant_resolver_sdk::{
+    GetAncestorsOptions, GetAncestorsResponse, GetDescendantsOptions, GetDescendantsResponse,
+    GetTenantsOptions, IsAncestorOptions, TenantId, TenantInfo, TenantRef, TenantResolverClient,
+    TenantResolverError, TenantStatus,
+};
 use uuid::Uuid;
 
 use crate::domain::services::{
@@ -120,6 +127,7 @@ impl AuthZResolverClient for CapturingAuthZResolverClient {
 /// the bare secret name (without `cred://` prefix).
 pub struct MockCredStoreClient {
     store: HashMap<String, Vec<u8>>,
+    gets: AtomicUsize,
 }
 
 impl MockCredStoreClient {
@@ -135,15 +143,24 @@ impl MockCredStoreClient {
                 (key, v.into_bytes())
             })
             .collect();
-        Self { store }
+        Self {
+            store,
+            gets: AtomicUsize::new(0),
+        }
     }
 
     /// Create an empty mock (all lookups return `Ok(None)`).
     pub fn empty() -> Self {
         Self {
             store: HashMap::new(),
+            gets: AtomicUsize::new(0),
         }
     }
+
+    /// Number of secret lookups served so far.
+    pub fn get_calls(&self) -> usize {
+        self.gets.load(Ordering::Relaxed)
+    }
 }
 
 #[async_trait]
@@ -153,13 +170,62 @@ impl CredStoreClientV1 for MockCredStoreClient {
         _ctx: &SecurityContext,
         key: &SecretRef,
     ) -> Result<Option<GetSecretResponse>, CredStoreError> {
+        self.gets.fetch_add(1, Ordering::Relaxed);
         Ok(self.store.get(key.as_ref()).map(|v| GetSecretResponse {
             value: SecretValue::new(v.clone()),
             owner_tenant_id: Uuid::nil(),
             sharing: SharingMode::default(),
             is_inherited: false,
+            version: 1,
         }))
     }
+
+    async fn get_version(
+        &self,
+        ctx: &SecurityContext,
+        key: &SecretRef,
+        version: SecretVersion,
+    ) -> Result<Option<GetSecretResponse>, CredStoreError> {
+        if version == 1 {
+            self.get(ctx, key).await
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn put(
+        &self,
+        _ctx: &SecurityContext,
+        _key: &SecretRef,
+        _value: SecretValue,
+        _sharing: SharingMode,
+    ) -> Result<SecretVersion, CredStoreError> {
+        Err(CredStoreError::Internal(
+            "mock credstore is read-only".into(),
+        ))
+    }
+
+    async fn rotate(
+        &self,
+        _ctx: &SecurityContext,
+        _key: &SecretRef,
+        _value: SecretValue,
+        _grace_period: Duration,
+    ) -> Result<RotateSecretResponse, CredStoreError> {
+        Err(CredStoreError::Internal(
+            "mock credstore is read-only".into(),
+        ))
+    }
+
+    async fn delete(&self, _ctx: &SecurityContext, _key: &SecretRef) -> Result<(), CredStoreError> {
+        Err(CredStoreError::Internal(
+            "mock credstore is read-only".into(),
+        ))
+    }
+
+    async fn list(&self, _ctx: &SecurityContext) -> Result<Vec<SecretInfo>, CredStoreError> {
+        Ok(Vec::new())
+    }
 }
 
 /// Mock `CredStoreClientV1` that always returns `CredStoreError::Internal`.
@@ -177,17 +243,212 @@ impl CredStoreClientV1 for FailingCredStoreClient {
     ) -> Result<Option<GetSecretResponse>, CredStoreError> {
         Err(CredStoreError::Internal("backend failure".into()))
     }
+
+    async fn get_version(
+        &self,
+        _ctx: &SecurityContext,
+        _key: &SecretRef,
+        _version: SecretVersion,
+    ) -> Result<Option<GetSecretResponse>, CredStoreError> {
+        Err(CredStoreError::Internal("backend failure".into()))
+    }
+
+    async fn put(
+        &self,
+        _ctx: &SecurityContext,
+        _key: &SecretRef,
+        _value: SecretValue,
+        _sharing: SharingMode,
+    ) -> Result<SecretVersion, CredStoreError> {
+        Err(CredStoreError::Internal("backend failure".into()))
+    }
+
+    async fn rotate(
+        &self,
+        _ctx: &SecurityContext,
+        _key: &SecretRef,
+        _value: SecretValue,
+        _grace_period: Duration,
+    ) -> Result<RotateSecretResponse, CredStoreError> {
+        Err(CredStoreError::Internal("backend failure".into()))
+    }
+
+    async fn delete(&self, _ctx: &SecurityContext, _key: &SecretRef) -> Result<(), CredStoreError> {
+        Err(CredStoreError::Internal("backend failure".into()))
+    }
+
+    async fn list(&self, _ctx: &SecurityContext) -> Result<Vec<SecretInfo>, CredStoreError> {
+        Err(CredStoreError::Internal("backend failure".into()))
+    }
+}
+
+/// Mock `TenantResolverClient` backed by a child → parent map. Only
+/// `get_ancestors` is supported; other lookups report `Internal`.
+pub struct MockTenantResolverClient {
+    parents: HashMap<Uuid, Uuid>,
+    ancestor_calls: AtomicUsize,
+}
+
+impl MockTenantResolverClient {
+    /// Create a mock from `(child, parent)` pairs.
+    pub fn with_parents(pairs: Vec<(Uuid, Uuid)>) -> Self {
+        Self {
+            parents: pairs.into_iter().collect(),
+            ancestor_calls: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of `get_ancestors` calls served so far.
+    #[cfg(test)]
+    pub fn ancestor_calls(&self) -> usize {
+        self.ancestor_calls.load(Ordering::Relaxed)
+    }
+
+    fn tenant_ref(&self, id: Uuid) -> TenantRef {
+        TenantRef {
+            id,
+            status: TenantStatus::Active,
+            tenant_type: None,
+            parent_id: self.parents.get(&id).copied(),
+            self_managed: false,
+        }
+    }
+}
+
+#[async_trait]
+impl TenantResolverClient for MockTenantResolverClient {
+    async fn get_tenant(
+        &self,
+        _ctx: &SecurityContext,
+        _id: TenantId,
+    ) -> Result<TenantInfo, TenantResolverError> {
+        Err(TenantResolverError::Internal(
+            "not supported by mock".into(),
+        ))
+    }
+
+    async fn get_tenants(
+        &self,
+        _ctx: &SecurityContext,
+        _ids: &[TenantId],
+        _options: &GetTenantsOptions,
+    ) -> Result<Vec<TenantInfo>, TenantResolverError> {
+        Err(TenantResolverError::Internal(
+            "not supported by mock".into(),
+        ))
+    }
+
+    async fn get_ancestors(
+        &self,
+        _ctx: &SecurityContext,
+        id: TenantId,
+        _options: &GetAncestorsOptions,
+    ) -> Result<GetAncestorsResponse, TenantResolverError> {
+        self.ancestor_calls.fetch_add(1, Ordering::Relaxed);
+        let mut ancestors = Vec::new();
+        let mut current = id;
+        while let Some(parent) = self.parents.get(&current) {
+            ancestors.push(self.tenant_ref(*parent));
+            current = *parent;
+        }
+        Ok(GetAncestorsResponse {
+            tenant: self.tenant_ref(id),
+            ancestors,
+        })
+    }
+
+    async fn get_descendants(
+        &self,
+        _ctx: &SecurityContext,
+        _id: TenantId,
+        _options: &GetDescendantsOptions,
+    ) -> Result<GetDescendantsResponse, TenantResolverError> {
+        Err(TenantResolverError::Internal(
+            "not supported by mock".into(),
+        ))
+    }
+
+    async fn is_ancestor(
+        &self,
+        _ctx: &SecurityContext,
+        _ancestor_id: TenantId,
+        _descendant_id: TenantId,
+        _options: &IsAncestorOptions,
+    ) -> Result<bool, TenantResolverError> {
+        Err(TenantResolverError::Internal(
+            "not supported by mock".into(),
+        ))
+    }
+}
+
+/// Build a guard/transform [`RequestContext`] for plugin unit tests.
+#[cfg(test)]
+pub(crate) fn test_request_context(
+    method: &str,
+    inbound_headers: &[(&str, &str)],
+    config: &[(&str, &str)],
+) -> crate::domain::plugin::RequestContext {
+    crate::domain::plugin::RequestContext {
+        upstream_id: Uuid::new_v4(),
+        route_id: Uuid::new_v4(),
+        method: method.to_string(),
+        path: "/v1/items".to_string(),
+        inbound_headers: string_map(inbound_headers),
+        headers: HashMap::new(),
+        config: string_map(config),
+        security_context: SecurityContext::builder()
+            .subject_tenant_id(Uuid::new_v4())
+            .subject_id(Uuid::new_v4())
+            .build()
+            .expect("test security context"),
+        started_at: std::time::Instant::now(),
+        timeout: Duration::from_secs(30),
+    }
+}
+
+/// Build a guard/transform [`ResponseContext`] for plugin unit tests.
+#[cfg(test)]
+pub(crate) fn test_response_context(
+    inbound_headers: &[(&str, &str)],
+    request_headers: &[(&str, &str)],
+    config: &[(&str, &str)],
+) -> crate::domain::plugin::ResponseContext {
+    crate::domain::plugin::ResponseContext {
+        upstream_id: Uuid::new_v4(),
+        method: "GET".to_string(),
+        path: "/v1/items".to_string(),
+        inbound_headers: string_map(inbound_headers),
+        request_headers: string_map(request_headers),
+        status: 200,
+        upstream_headers: HashMap::new(),
+        headers: HashMap::new(),
+        config: string_map(config),
+        elapsed: Duration::from_millis(5),
+    }
+}
+
+#[cfg(test)]
+fn string_map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+        .collect()
 }
 
 /// Re-export for tests that need a `CredStoreClientV1` mock.
 pub use MockCredStoreClient as TestCredStoreClient;
 
 /// Re-export plugin ID constants for test configurations.
-pub use crate::domain::gts_helpers::APIKEY_AUTH_PLUGIN_ID;
+pub use crate::domain::gts_helpers::{
+    APIKEY_AUTH_PLUGIN_ID, CORS_GUARD_PLUGIN_ID, LOGGING_TRANSFORM_PLUGIN_ID,
+    METRICS_TRANSFORM_PLUGIN_ID, OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID, REQUEST_ID_TRANSFORM_PLUGIN_ID,
+    TIMEOUT_GUARD_PLUGIN_ID,
+};
 
 /// Builder for a fully-wired Control Plane test environment.
 pub struct TestCpBuilder {
     credentials: Vec<(String, String)>,
+    tenant_parents: Vec<(Uuid, Uuid)>,
 }
 
 impl TestCpBuilder {
@@ -195,9 +456,18 @@ impl TestCpBuilder {
     pub fn new() -> Self {
         Self {
             credentials: Vec::new(),
+            tenant_parents: Vec::new(),
         }
     }
 
+    /// Declare a tenant hierarchy as `(child, parent)` pairs so inherited
+    /// plugin chains can be resolved.
+    #[must_use]
+    pub fn with_tenant_hierarchy(mut self, parents: Vec<(Uuid, Uuid)>) -> Self {
+        self.tenant_parents = parents;
+        self
+    }
+
     /// Pre-load credentials into the mock credstore client.
     #[must_use]
     pub fn with_credentials(mut self, creds: Vec<(String, String)>) -> Self {
@@ -210,8 +480,11 @@ impl TestCpBuilder {
     pub(crate) fn build_and_register(self, hub: &ClientHub) -> Arc<dyn ControlPlaneService> {
         let upstream_repo = Arc::new(InMemoryUpstreamRepo::new());
         let route_repo = Arc::new(InMemoryRouteRepo::new());
-        let cp: Arc<dyn ControlPlaneService> =
-            Arc::new(ControlPlaneServiceImpl::new(upstream_repo, route_repo));
+        let cp: Arc<dyn ControlPlaneService> = Arc::new(
+            ControlPlaneServiceImpl::new(upstream_repo, route_repo).with_tenant_resolver(Arc::new(
+                MockTenantResolverClient::with_parents(self.tenant_parents),
+            )),
+        );
 
         let credstore: Arc<dyn CredStoreClientV1> =
             Arc::new(MockCredStoreClient::with_secrets(self.credentials));
@@ -234,7 +507,6 @@ impl Default for TestCpBuilder {
 pub struct TestDpBuilder {
     request_timeout: Option<Duration>,
     authz_client: Option<Arc<dyn AuthZResolverClient>>,
-    backend_selector: Option<Arc<dyn EndpointSelector>>,
     max_body_size: Option<usize>,
     skip_upstream_tls_verify: bool,
 }
@@ -245,7 +517,6 @@ impl TestDpBuilder {
         Self {
             request_timeout: None,
             authz_client: None,
-            backend_selector: None,
             max_body_size: None,
             skip_upstream_tls_verify: false,
         }
@@ -279,14 +550,6 @@ impl TestDpBuilder {
         self
     }
 
-    /// Inject a shared `EndpointSelector` so callers can hold the same
-    /// instance that the DP service uses (e.g. for `invalidate()` calls).
-    #[must_use]
-    pub(crate) fn with_backend_selector(mut self, selector: Arc<dyn EndpointSelector>) -> Self {
-        self.backend_selector = Some(selector);
-        self
-    }
-
     /// Fetch `CredStoreClientV1` from the hub, create a DP service with
     /// the given CP, and return the trait object.
     pub(crate) fn build_and_register(
@@ -315,13 +578,23 @@ impl TestDpBuilder {
         ));
 
         let backend_selector: Arc<dyn EndpointSelector> =
-            self.backend_selector.unwrap_or_else(|| {
-                Arc::new(crate::infra::proxy::pingora_proxy::PingoraEndpointSelector::new())
-            });
+            Arc::new(crate::infra::proxy::pingora_proxy::PingoraEndpointSelector::new());
 
-        let mut svc =
-            DataPlaneServiceImpl::new(cp, credstore, policy_enforcer, backend_selector, proxy)
-                .with_allow_http_upstream(true);
+        let grpc_transport = crate::infra::proxy::grpc::GrpcTransport::new(
+            Duration::from_secs(10),
+            self.skip_upstream_tls_verify,
+        )
+        .expect("gRPC transport TLS config");
+
+        let mut svc = DataPlaneServiceImpl::new(
+            cp.clone(),
+            credstore,
+            policy_enforcer,
+            backend_selector,
+            proxy,
+        )
+        .with_grpc_transport(grpc_transport)
+        .with_allow_http_upstream(true);
         if let Some(timeout) = self.request_timeout {
             svc = svc.with_request_timeout(timeout);
         }
@@ -329,7 +602,9 @@ impl TestDpBuilder {
             svc = svc.with_max_body_size(size);
         }
 
-        Arc::new(svc)
+        let svc = Arc::new(svc);
+        cp.set_change_listener(Arc::downgrade(&svc) as _);
+        svc
     }
 }
 
@@ -356,12 +631,8 @@ pub fn build_test_app_state(
     cp_builder: TestCpBuilder,
     dp_builder: TestDpBuilder,
 ) -> TestAppState {
-    let backend_selector: Arc<dyn EndpointSelector> =
-        Arc::new(crate::infra::proxy::pingora_proxy::PingoraEndpointSelector::new());
     let cp = cp_builder.build_and_register(hub);
-    let dp = dp_builder
-        .with_backend_selector(backend_selector.clone())
-        .build_and_register(hub, cp.clone());
+    let dp = dp_builder.build_and_register(hub, cp.clone());
     let facade: Arc<dyn ServiceGatewayClientV1> =
         Arc::new(ServiceGatewayClientV1Facade::new(cp.clone(), dp.clone()));
     hub.register::<dyn ServiceGatewayClientV1>(facade.clone());
@@ -369,7 +640,6 @@ pub fn build_test_app_state(
         state: crate::module::AppState {
             cp,
             dp,
-            backend_selector,
             config: crate::config::RuntimeConfig {
                 max_body_size_bytes: 100 * 1024 * 1024, // 100 MB default for tests
             },
@@ -5,9 +5,13 @@
 
 use async_trait::async_trait;
 use modkit_macros::domain_model;
+use modkit_security::SecurityContext;
 
 use super::error::DomainError;
-use super::model::{CreateRouteRequest, CreateUpstreamRequest};
+use super::model::{
+    CreateRouteRequest, CreateUpstreamRequest, Endpoint, ListQuery, UpdateUpstreamRequest, Upstream,
+};
+use super::services::ControlPlaneService;
 use uuid::Uuid;
 
 /// An upstream definition read from the types-registry.
@@ -39,3 +43,143 @@ pub trait TypeProvisioningService: Send + Sync {
     /// List all route instances registered in the types-registry.
     async fn list_routes(&self) -> Result<Vec<ProvisionedRoute>, DomainError>;
 }
+
+/// How [`provision_upstream`] materialized an upstream.
+#[domain_model]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpstreamProvisioning {
+    Created,
+    /// An upstream with the same alias existed and was updated.
+    Updated,
+    /// An upstream with the same alias already matched the definition.
+    Unchanged,
+}
+
+/// Create the provisioned upstream `req` in the tenant of `ctx`.
+///
+/// Persistent storage keeps upstreams across restarts, so an upstream with
+/// the same alias may already exist; it is brought in line with `req`,
+/// keeping its id and therefore its routes.
+///
+/// # Errors
+///
+/// Returns the create, list or update error of `cp`.
+pub(crate) async fn provision_upstream(
+    cp: &dyn ControlPlaneService,
+    ctx: &SecurityContext,
+    req: &CreateUpstreamRequest,
+) -> Result<(Upstream, UpstreamProvisioning), DomainError> {
+    let detail = match cp.create_upstream(ctx, req.clone()).await {
+        Ok(created) => return Ok((created, UpstreamProvisioning::Created)),
+        Err(DomainError::Conflict { detail }) => detail,
+        Err(e) => return Err(e),
+    };
+
+    // Same default as `create_upstream`: the first endpoint.
+    let alias = req
+        .alias
+        .clone()
+        .or_else(|| {
+            req.server
+                .endpoints
+                .first()
+                .map(Endpoint::alias_contribution)
+        })
+        .unwrap_or_default();
+    let all = ListQuery {
+        top: u32::MAX,
+        skip: 0,
+    };
+    let existing = cp
+        .list_upstreams(ctx, &all)
+        .await?
+        .into_iter()
+        .find(|u| u.alias == alias)
+        .ok_or(DomainError::Conflict { detail })?;
+
+    if matches_request(&existing, req) {
+        return Ok((existing, UpstreamProvisioning::Unchanged));
+    }
+    let update = UpdateUpstreamRequest {
+        server: Some(req.server.clone()),
+        protocol: Some(req.protocol.clone()),
+        alias: None,
+        auth: req.auth.clone(),
+        headers: req.headers.clone(),
+        plugins: req.plugins.clone(),
+        rate_limit: req.rate_limit.clone(),
+        circuit_breaker: req.circuit_breaker.clone(),
+        tags: Some(req.tags.clone()),
+        enabled: Some(req.enabled),
+    };
+    let updated = cp.update_upstream(ctx, existing.id, update).await?;
+    Ok((updated, UpstreamProvisioning::Updated))
+}
+
+fn matches_request(upstream: &Upstream, req: &CreateUpstreamRequest) -> bool {
+    upstream.server == req.server
+        && upstream.protocol == req.protocol
+        && upstream.auth == req.auth
+        && upstream.headers == req.headers
+        && upstream.plugins == req.plugins
+        && upstream.rate_limit == req.rate_limit
+        && upstream.circuit_breaker == req.circuit_breaker
+        && upstream.tags == req.tags
+        && upstream.enabled == req.enabled
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::domain::model::{Scheme, Server};
+    use crate::domain::services::ControlPlaneServiceImpl;
+    use crate::infra::storage::{InMemoryRouteRepo, InMemoryUpstreamRepo};
+
+    fn request(port: u16) -> CreateUpstreamRequest {
+        CreateUpstreamRequest {
+            server: Server {
+                endpoints: vec![Endpoint {
+                    scheme: Scheme::Https,
+                    host: "api.openai.com".into(),
+                    port,
+                }],
+            },
+            protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
+            alias: Some("openai".into()),
+            auth: None,
+            headers: None,
+            plugins: None,
+            rate_limit: None,
+            circuit_breaker: None,
+            tags: vec![],
+            enabled: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn existing_upstream_is_updated_in_place() {
+        let cp = ControlPlaneServiceImpl::new(
+            Arc::new(InMemoryUpstreamRepo::new()),
+            Arc::new(InMemoryRouteRepo::new()),
+        );
+        let ctx = SecurityContext::builder()
+            .subject_id(Uuid::new_v4())
+            .subject_tenant_id(Uuid::new_v4())
+            .build()
+            .unwrap();
+
+        let (created, outcome) = provision_upstream(&cp, &ctx, &request(443)).await.unwrap();
+        assert_eq!(outcome, UpstreamProvisioning::Created);
+
+        let (same, outcome) = provision_upstream(&cp, &ctx, &request(443)).await.unwrap();
+        assert_eq!(outcome, UpstreamProvisioning::Unchanged);
+        assert_eq!(same.id, created.id);
+
+        let (updated, outcome) = provision_upstream(&cp, &ctx, &request(8443)).await.unwrap();
+        assert_eq!(outcome, UpstreamProvisioning::Updated);
+        assert_eq!(updated.id, created.id);
+        assert_eq!(updated.server.endpoints[0].port, 8443);
+    }
+}
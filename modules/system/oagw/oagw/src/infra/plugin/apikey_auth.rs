@@ -1,9 +1,10 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use credstore_sdk::{CredStoreClientV1, SecretRef};
+use credstore_sdk::CredStoreClientV1;
 use serde::Deserialize;
 
+use super::secret::{parse_config, resolve_secret};
 use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
 
 /// Configuration for the API key auth plugin.
@@ -33,31 +34,11 @@ impl ApiKeyAuthPlugin {
 #[async_trait]
 impl AuthPlugin for ApiKeyAuthPlugin {
     async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
-        let config: ApiKeyConfig = serde_json::from_value(
-            serde_json::to_value(&ctx.config)
-                .map_err(|e| PluginError::Internal(format!("invalid apikey auth config: {e}")))?,
-        )
-        .map_err(|e| PluginError::Internal(format!("invalid apikey auth config: {e}")))?;
-
-        let raw_ref = config
-            .secret_ref
-            .strip_prefix("cred://")
-            .unwrap_or(&config.secret_ref);
-        let key = SecretRef::new(raw_ref)
-            .map_err(|e| PluginError::Internal(format!("invalid secret ref '{raw_ref}': {e}")))?;
-
-        let response = self
-            .credstore
-            .get(&ctx.security_context, &key)
-            .await
-            .map_err(|e| PluginError::Internal(format!("credstore error: {e}")))?
-            .ok_or_else(|| PluginError::SecretNotFound(config.secret_ref.clone()))?;
-
-        let secret_str = std::str::from_utf8(response.value.as_bytes())
-            .map_err(|_| PluginError::Internal("secret value is not valid UTF-8".into()))?
-            .to_string();
-
-        let value = format!("{}{}", config.prefix, secret_str);
+        let config: ApiKeyConfig = parse_config(&ctx.config, "apikey")?;
+        let secret =
+            resolve_secret(&*self.credstore, &ctx.security_context, &config.secret_ref).await?;
+
+        let value = format!("{}{}", config.prefix, secret);
         ctx.headers.insert(config.header.to_lowercase(), value);
 
         Ok(())
@@ -98,6 +79,7 @@ mod tests {
 
     fn make_auth_ctx(config: HashMap<String, String>) -> AuthContext {
         AuthContext {
+            upstream_id: Uuid::new_v4(),
             headers: HashMap::new(),
             config,
             security_context: test_security_context(),
@@ -199,8 +181,53 @@ mod tests {
                     owner_tenant_id: Uuid::nil(),
                     sharing: SharingMode::default(),
                     is_inherited: false,
+                    version: 1,
                 }))
             }
+
+            async fn get_version(
+                &self,
+                _ctx: &SecurityContext,
+                _key: &SecretRef,
+                _version: credstore_sdk::SecretVersion,
+            ) -> Result<Option<GetSecretResponse>, CredStoreError> {
+                unimplemented!()
+            }
+
+            async fn put(
+                &self,
+                _ctx: &SecurityContext,
+                _key: &SecretRef,
+                _value: SecretValue,
+                _sharing: SharingMode,
+            ) -> Result<credstore_sdk::SecretVersion, CredStoreError> {
+                unimplemented!()
+            }
+
+            async fn rotate(
+                &self,
+                _ctx: &SecurityContext,
+                _key: &SecretRef,
+                _value: SecretValue,
+                _grace_period: std::time::Duration,
+            ) -> Result<credstore_sdk::RotateSecretResponse, CredStoreError> {
+                unimplemented!()
+            }
+
+            async fn delete(
+                &self,
+                _ctx: &SecurityContext,
+                _key: &SecretRef,
+            ) -> Result<(), CredStoreError> {
+                unimplemented!()
+            }
+
+            async fn list(
+                &self,
+                _ctx: &SecurityContext,
+            ) -> Result<Vec<credstore_sdk::SecretInfo>, CredStoreError> {
+                unimplemented!()
+            }
         }
 
         let plugin = ApiKeyAuthPlugin::new(Arc::new(Utf8ErrorCredStore));
@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use credstore_sdk::CredStoreClientV1;
+use serde::Deserialize;
+
+use super::secret::{parse_config, resolve_secret};
+use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
+
+/// Configuration for the HTTP Basic auth plugin.
+#[derive(Debug, Deserialize)]
+struct BasicConfig {
+    /// Secret reference holding the username.
+    username_ref: String,
+    /// Secret reference holding the password.
+    password_ref: String,
+}
+
+/// Auth plugin that injects `Authorization: Basic base64(username:password)`.
+pub struct BasicAuthPlugin {
+    credstore: Arc<dyn CredStoreClientV1>,
+}
+
+impl BasicAuthPlugin {
+    #[must_use]
+    pub fn new(credstore: Arc<dyn CredStoreClientV1>) -> Self {
+        Self { credstore }
+    }
+}
+
+#[async_trait]
+impl AuthPlugin for BasicAuthPlugin {
+    async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+        let config: BasicConfig = parse_config(&ctx.config, "basic")?;
+        let username = resolve_secret(
+            &*self.credstore,
+            &ctx.security_context,
+            &config.username_ref,
+        )
+        .await?;
+        let password = resolve_secret(
+            &*self.credstore,
+            &ctx.security_context,
+            &config.password_ref,
+        )
+        .await?;
+
+        // RFC 7617: the user-id must not contain a colon.
+        if username.contains(':') {
+            return Err(PluginError::Internal(
+                "basic auth username must not contain ':'".into(),
+            ));
+        }
+
+        let encoded = STANDARD.encode(format!("{username}:{password}"));
+        ctx.headers
+            .insert("authorization".into(), format!("Basic {encoded}"));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use modkit_security::SecurityContext;
+    use uuid::Uuid;
+
+    use crate::domain::test_support::MockCredStoreClient;
+
+    use super::*;
+
+    fn make_config() -> HashMap<String, String> {
+        HashMap::from([
+            ("username_ref".into(), "cred://legacy-user".into()),
+            ("password_ref".into(), "cred://legacy-pass".into()),
+        ])
+    }
+
+    fn make_auth_ctx(config: HashMap<String, String>) -> AuthContext {
+        AuthContext {
+            upstream_id: Uuid::new_v4(),
+            headers: HashMap::new(),
+            config,
+            security_context: SecurityContext::builder()
+                .subject_tenant_id(Uuid::new_v4())
+                .subject_id(Uuid::new_v4())
+                .build()
+                .expect("test security context"),
+        }
+    }
+
+    #[tokio::test]
+    async fn injects_basic_credentials() {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![
+            ("legacy-user".into(), "Aladdin".into()),
+            ("legacy-pass".into(), "open sesame".into()),
+        ]));
+        let plugin = BasicAuthPlugin::new(credstore);
+        let mut ctx = make_auth_ctx(make_config());
+
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(
+            ctx.headers.get("authorization").unwrap(),
+            "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_password_returns_secret_not_found() {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![(
+            "legacy-user".into(),
+            "Aladdin".into(),
+        )]));
+        let plugin = BasicAuthPlugin::new(credstore);
+        let mut ctx = make_auth_ctx(make_config());
+
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::SecretNotFound(ref s) if s == "cred://legacy-pass"));
+    }
+
+    #[tokio::test]
+    async fn username_with_colon_is_rejected() {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![
+            ("legacy-user".into(), "a:b".into()),
+            ("legacy-pass".into(), "pw".into()),
+        ]));
+        let plugin = BasicAuthPlugin::new(credstore);
+        let mut ctx = make_auth_ctx(make_config());
+
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::Internal(_)));
+        assert!(!ctx.headers.contains_key("authorization"));
+    }
+}
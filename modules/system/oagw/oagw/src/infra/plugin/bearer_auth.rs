@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use credstore_sdk::CredStoreClientV1;
+use serde::Deserialize;
+
+use super::secret::{parse_config, resolve_secret};
+use crate::domain::plugin::{AuthContext, AuthPlugin, PluginError};
+
+/// Configuration for the bearer token auth plugin.
+#[derive(Debug, Deserialize)]
+struct BearerConfig {
+    /// Secret reference holding the static token (e.g. "cred://partner-token").
+    secret_ref: String,
+}
+
+/// Auth plugin that injects a stored token as `Authorization: Bearer <token>`.
+pub struct BearerAuthPlugin {
+    credstore: Arc<dyn CredStoreClientV1>,
+}
+
+impl BearerAuthPlugin {
+    #[must_use]
+    pub fn new(credstore: Arc<dyn CredStoreClientV1>) -> Self {
+        Self { credstore }
+    }
+}
+
+#[async_trait]
+impl AuthPlugin for BearerAuthPlugin {
+    async fn authenticate(&self, ctx: &mut AuthContext) -> Result<(), PluginError> {
+        let config: BearerConfig = parse_config(&ctx.config, "bearer")?;
+        let token =
+            resolve_secret(&*self.credstore, &ctx.security_context, &config.secret_ref).await?;
+
+        ctx.headers
+            .insert("authorization".into(), format!("Bearer {token}"));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use modkit_security::SecurityContext;
+    use uuid::Uuid;
+
+    use crate::domain::test_support::MockCredStoreClient;
+
+    use super::*;
+
+    fn make_auth_ctx(config: HashMap<String, String>) -> AuthContext {
+        AuthContext {
+            upstream_id: Uuid::new_v4(),
+            headers: HashMap::from([("authorization".into(), "Bearer inbound".into())]),
+            config,
+            security_context: SecurityContext::builder()
+                .subject_tenant_id(Uuid::new_v4())
+                .subject_id(Uuid::new_v4())
+                .build()
+                .expect("test security context"),
+        }
+    }
+
+    #[tokio::test]
+    async fn replaces_authorization_with_stored_token() {
+        let credstore = Arc::new(MockCredStoreClient::with_secrets(vec![(
+            "partner-token".into(),
+            "tok-123".into(),
+        )]));
+        let plugin = BearerAuthPlugin::new(credstore);
+        let mut ctx = make_auth_ctx(HashMap::from([(
+            "secret_ref".into(),
+            "cred://partner-token".into(),
+        )]));
+
+        plugin.authenticate(&mut ctx).await.unwrap();
+        assert_eq!(ctx.headers.get("authorization").unwrap(), "Bearer tok-123");
+    }
+
+    #[tokio::test]
+    async fn missing_secret_ref_is_internal_error() {
+        let plugin = BearerAuthPlugin::new(Arc::new(MockCredStoreClient::empty()));
+        let mut ctx = make_auth_ctx(HashMap::new());
+
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::Internal(_)));
+    }
+
+    #[tokio::test]
+    async fn secret_not_found_returns_error() {
+        let plugin = BearerAuthPlugin::new(Arc::new(MockCredStoreClient::empty()));
+        let mut ctx = make_auth_ctx(HashMap::from([(
+            "secret_ref".into(),
+            "cred://missing".into(),
+        )]));
+
+        let err = plugin.authenticate(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::SecretNotFound(_)));
+    }
+}
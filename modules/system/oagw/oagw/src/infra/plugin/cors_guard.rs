@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::domain::plugin::{
+    GuardDecision, GuardPlugin, PluginError, RequestContext, ResponseContext,
+};
+
+/// Upper bound for `max_age` (browsers cap preflight caching at 24h anyway).
+const MAX_PREFLIGHT_AGE_SECS: u64 = 86_400;
+
+/// Parsed CORS configuration. List values are comma-separated in the plugin
+/// config, e.g. `allowed_origins: "https://app.example.com,https://admin.example.com"`.
+struct CorsConfig {
+    allowed_origins: Vec<String>,
+    allowed_methods: Vec<String>,
+    allowed_headers: Vec<String>,
+    expose_headers: Vec<String>,
+    max_age: u64,
+    allow_credentials: bool,
+}
+
+impl CorsConfig {
+    fn parse(config: &HashMap<String, String>) -> Result<Self, PluginError> {
+        let list = |key: &str, default: &str| -> Vec<String> {
+            config
+                .get(key)
+                .map_or(default, String::as_str)
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+        let invalid =
+            |detail: String| PluginError::Internal(format!("invalid cors config: {detail}"));
+
+        let allowed_origins = list("allowed_origins", "");
+        if allowed_origins.is_empty() {
+            return Err(invalid("allowed_origins must not be empty".into()));
+        }
+        let max_age = match config.get("max_age") {
+            Some(v) => v
+                .parse::<u64>()
+                .map_err(|e| invalid(format!("max_age: {e}")))?
+                .min(MAX_PREFLIGHT_AGE_SECS),
+            None => MAX_PREFLIGHT_AGE_SECS,
+        };
+        let allow_credentials = match config.get("allow_credentials").map(String::as_str) {
+            None | Some("false") => false,
+            Some("true") => true,
+            Some(other) => {
+                return Err(invalid(format!(
+                    "allow_credentials: '{other}' is not a boolean"
+                )));
+            }
+        };
+        if allow_credentials && allowed_origins.iter().any(|o| o == "*") {
+            return Err(invalid(
+                "allow_credentials requires explicit origins, not '*'".into(),
+            ));
+        }
+
+        Ok(Self {
+            allowed_origins,
+            allowed_methods: list("allowed_methods", "GET,POST")
+                .into_iter()
+                .map(|m| m.to_ascii_uppercase())
+                .collect(),
+            allowed_headers: list("allowed_headers", "content-type,authorization")
+                .into_iter()
+                .map(|h| h.to_ascii_lowercase())
+                .collect(),
+            expose_headers: list("expose_headers", ""),
+            max_age,
+            allow_credentials,
+        })
+    }
+
+    fn allows_origin(&self, origin: &str) -> bool {
+        self.allowed_origins.iter().any(|o| o == "*" || o == origin)
+    }
+
+    /// Headers common to preflight and actual responses.
+    fn origin_headers(&self, origin: &str) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        if self.allowed_origins.iter().any(|o| o == "*") {
+            headers.insert("access-control-allow-origin".into(), "*".into());
+        } else {
+            headers.insert("access-control-allow-origin".into(), origin.to_string());
+            headers.insert("vary".into(), "Origin".into());
+        }
+        if self.allow_credentials {
+            headers.insert("access-control-allow-credentials".into(), "true".into());
+        }
+        headers
+    }
+}
+
+/// Guard plugin implementing CORS for browser clients.
+///
+/// Preflight requests (`OPTIONS` with `Access-Control-Request-Method`) are
+/// answered locally and never reach the upstream. Requests from origins that
+/// are not allowed are rejected with 403; requests without `Origin` pass
+/// through untouched.
+pub struct CorsGuardPlugin;
+
+#[async_trait]
+impl GuardPlugin for CorsGuardPlugin {
+    async fn guard_request(&self, ctx: &mut RequestContext) -> Result<GuardDecision, PluginError> {
+        let config = CorsConfig::parse(&ctx.config)?;
+        let Some(origin) = ctx.inbound_headers.get("origin") else {
+            return Ok(GuardDecision::Allow);
+        };
+        if !config.allows_origin(origin) {
+            return Ok(GuardDecision::Reject {
+                status: 403,
+                detail: format!("CORS origin '{origin}' is not allowed"),
+            });
+        }
+
+        let Some(requested_method) = ctx
+            .inbound_headers
+            .get("access-control-request-method")
+            .filter(|_| ctx.method == "OPTIONS")
+        else {
+            return Ok(GuardDecision::Allow);
+        };
+
+        if !config
+            .allowed_methods
+            .iter()
+            .any(|m| m.eq_ignore_ascii_case(requested_method))
+        {
+            return Ok(GuardDecision::Reject {
+                status: 403,
+                detail: format!("CORS method '{requested_method}' is not allowed"),
+            });
+        }
+        if let Some(requested_headers) = ctx.inbound_headers.get("access-control-request-headers")
+            && let Some(header) = requested_headers
+                .split(',')
+                .map(|h| h.trim().to_ascii_lowercase())
+                .find(|h| !h.is_empty() && !config.allowed_headers.contains(h))
+        {
+            return Ok(GuardDecision::Reject {
+                status: 403,
+                detail: format!("CORS header '{header}' is not allowed"),
+            });
+        }
+
+        let mut headers = config.origin_headers(origin);
+        headers.insert(
+            "access-control-allow-methods".into(),
+            config.allowed_methods.join(", "),
+        );
+        if !config.allowed_headers.is_empty() {
+            headers.insert(
+                "access-control-allow-headers".into(),
+                config.allowed_headers.join(", "),
+            );
+        }
+        headers.insert("access-control-max-age".into(), config.max_age.to_string());
+        Ok(GuardDecision::Respond {
+            status: 204,
+            headers,
+        })
+    }
+
+    async fn guard_response(&self, ctx: &mut ResponseContext) -> Result<(), PluginError> {
+        let config = CorsConfig::parse(&ctx.config)?;
+        let Some(origin) = ctx.inbound_headers.get("origin") else {
+            return Ok(());
+        };
+        if !config.allows_origin(origin) {
+            return Ok(());
+        }
+
+        let mut headers = config.origin_headers(origin);
+        if let Some(vary) = headers.get_mut("vary")
+            && let Some(upstream_vary) = ctx.upstream_headers.get("vary")
+            && !upstream_vary
+                .split(',')
+                .any(|v| v.trim().eq_ignore_ascii_case("origin"))
+        {
+            *vary = format!("{upstream_vary}, Origin");
+        }
+        if !config.expose_headers.is_empty() {
+            headers.insert(
+                "access-control-expose-headers".into(),
+                config.expose_headers.join(", "),
+            );
+        }
+        ctx.headers.extend(headers);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::test_support::{test_request_context, test_response_context};
+
+    use super::*;
+
+    const CONFIG: &[(&str, &str)] = &[
+        ("allowed_origins", "https://app.example.com"),
+        ("allowed_methods", "GET,POST"),
+        ("allowed_headers", "Content-Type,Authorization"),
+        ("expose_headers", "X-Request-ID"),
+        ("max_age", "600"),
+        ("allow_credentials", "true"),
+    ];
+
+    #[tokio::test]
+    async fn request_without_origin_is_allowed() {
+        let mut ctx = test_request_context("GET", &[], CONFIG);
+        let decision = CorsGuardPlugin.guard_request(&mut ctx).await.unwrap();
+        assert_eq!(decision, GuardDecision::Allow);
+    }
+
+    #[tokio::test]
+    async fn disallowed_origin_is_rejected() {
+        let mut ctx =
+            test_request_context("GET", &[("origin", "https://evil.example.com")], CONFIG);
+        let decision = CorsGuardPlugin.guard_request(&mut ctx).await.unwrap();
+        assert!(matches!(
+            decision,
+            GuardDecision::Reject { status: 403, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn preflight_is_answered_locally() {
+        let mut ctx = test_request_context(
+            "OPTIONS",
+            &[
+                ("origin", "https://app.example.com"),
+                ("access-control-request-method", "POST"),
+                ("access-control-request-headers", "content-type"),
+            ],
+            CONFIG,
+        );
+        let GuardDecision::Respond { status, headers } =
+            CorsGuardPlugin.guard_request(&mut ctx).await.unwrap()
+        else {
+            panic!("expected preflight response");
+        };
+        assert_eq!(status, 204);
+        assert_eq!(
+            headers["access-control-allow-origin"],
+            "https://app.example.com"
+        );
+        assert_eq!(headers["access-control-allow-methods"], "GET, POST");
+        assert_eq!(
+            headers["access-control-allow-headers"],
+            "content-type, authorization"
+        );
+        assert_eq!(headers["access-control-max-age"], "600");
+        assert_eq!(headers["access-control-allow-credentials"], "true");
+        assert_eq!(headers["vary"], "Origin");
+    }
+
+    #[tokio::test]
+    async fn preflight_with_disallowed_method_or_header_is_rejected() {
+        for extra in [
+            vec![("access-control-request-method", "DELETE")],
+            vec![
+                ("access-control-request-method", "GET"),
+                ("access-control-request-headers", "x-secret"),
+            ],
+        ] {
+            let mut headers = vec![("origin", "https://app.example.com")];
+            headers.extend(extra);
+            let mut ctx = test_request_context("OPTIONS", &headers, CONFIG);
+            let decision = CorsGuardPlugin.guard_request(&mut ctx).await.unwrap();
+            assert!(matches!(
+                decision,
+                GuardDecision::Reject { status: 403, .. }
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn plain_options_without_request_method_passes_through() {
+        let mut ctx =
+            test_request_context("OPTIONS", &[("origin", "https://app.example.com")], CONFIG);
+        let decision = CorsGuardPlugin.guard_request(&mut ctx).await.unwrap();
+        assert_eq!(decision, GuardDecision::Allow);
+    }
+
+    #[tokio::test]
+    async fn actual_response_gets_cors_headers() {
+        let mut ctx = test_response_context(&[("origin", "https://app.example.com")], &[], CONFIG);
+        ctx.upstream_headers
+            .insert("vary".into(), "Accept-Encoding".into());
+
+        CorsGuardPlugin.guard_response(&mut ctx).await.unwrap();
+        assert_eq!(
+            ctx.headers["access-control-allow-origin"],
+            "https://app.example.com"
+        );
+        assert_eq!(ctx.headers["access-control-expose-headers"], "X-Request-ID");
+        assert_eq!(ctx.headers["access-control-allow-credentials"], "true");
+        assert_eq!(ctx.headers["vary"], "Accept-Encoding, Origin");
+    }
+
+    #[tokio::test]
+    async fn wildcard_origin_answers_with_star() {
+        let config = [("allowed_origins", "*")];
+        let mut ctx = test_response_context(&[("origin", "https://any.example.com")], &[], &config);
+
+        CorsGuardPlugin.guard_response(&mut ctx).await.unwrap();
+        assert_eq!(ctx.headers["access-control-allow-origin"], "*");
+        assert!(!ctx.headers.contains_key("vary"));
+    }
+
+    #[tokio::test]
+    async fn credentials_with_wildcard_is_config_error() {
+        let config = [("allowed_origins", "*"), ("allow_credentials", "true")];
+        let mut ctx = test_request_context("GET", &[("origin", "https://a.example.com")], &config);
+        let err = CorsGuardPlugin.guard_request(&mut ctx).await.unwrap_err();
+        assert!(matches!(err, PluginError::Internal(_)));
+    }
+}
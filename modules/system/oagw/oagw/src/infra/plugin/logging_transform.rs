@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+
+use crate::domain::plugin::{
+    ErrorContext, PluginError, RequestContext, ResponseContext, TransformPlugin,
+};
+
+/// Transform plugin that logs each proxied request and its outcome.
+///
+/// Only the tenant, method, path, status and latency are logged; headers and bodies
+/// are never written since they may carry credentials.
+pub struct LoggingTransformPlugin;
+
+#[async_trait]
+impl TransformPlugin for LoggingTransformPlugin {
+    async fn transform_request(&self, ctx: &mut RequestContext) -> Result<(), PluginError> {
+        tracing::info!(
+            upstream_id = %ctx.upstream_id,
+            route_id = %ctx.route_id,
+            tenant_id = %ctx.security_context.subject_tenant_id(),
+            method = %ctx.method,
+            path = %ctx.path,
+            "proxy request"
+        );
+        Ok(())
+    }
+
+    async fn transform_response(&self, ctx: &mut ResponseContext) -> Result<(), PluginError> {
+        tracing::info!(
+            upstream_id = %ctx.upstream_id,
+            method = %ctx.method,
+            path = %ctx.path,
+            status = ctx.status,
+            elapsed_ms = ctx.elapsed.as_millis(),
+            "proxy response"
+        );
+        Ok(())
+    }
+
+    async fn transform_error(&self, ctx: &ErrorContext) -> Result<(), PluginError> {
+        tracing::warn!(
+            upstream_id = %ctx.upstream_id,
+            method = %ctx.method,
+            path = %ctx.path,
+            elapsed_ms = ctx.elapsed.as_millis(),
+            detail = %ctx.detail,
+            "proxy error"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::test_support::{test_request_context, test_response_context};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn logging_leaves_request_and_response_unchanged() {
+        let mut req = test_request_context("GET", &[("authorization", "Bearer secret")], &[]);
+        LoggingTransformPlugin
+            .transform_request(&mut req)
+            .await
+            .unwrap();
+        assert!(req.headers.is_empty());
+
+        let mut resp = test_response_context(&[], &[], &[]);
+        LoggingTransformPlugin
+            .transform_response(&mut resp)
+            .await
+            .unwrap();
+        assert!(resp.headers.is_empty());
+    }
+}
@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use opentelemetry::KeyValue;
+use opentelemetry::global;
+use opentelemetry::metrics::{Counter, Histogram};
+
+use crate::domain::plugin::{ErrorContext, PluginError, ResponseContext, TransformPlugin};
+
+/// Transform plugin that records per-upstream request metrics.
+///
+/// Instruments come from the global OpenTelemetry meter provider, exported in
+/// Prometheus format by the host.
+pub struct MetricsTransformPlugin {
+    /// `oagw_upstream_requests_total{upstream_id, method, status}`; `status` is
+    /// the response code, or `error` when no upstream response was received.
+    requests: Counter<u64>,
+    /// `oagw_upstream_request_duration_seconds{upstream_id, method}`
+    duration: Histogram<f64>,
+}
+
+impl MetricsTransformPlugin {
+    #[must_use]
+    pub fn new() -> Self {
+        let meter = global::meter("oagw");
+        Self {
+            requests: meter
+                .u64_counter("oagw_upstream_requests_total")
+                .with_description("Proxied requests, by upstream, method and status")
+                .build(),
+            duration: meter
+                .f64_histogram("oagw_upstream_request_duration_seconds")
+                .with_description("End-to-end latency of proxied requests")
+                .with_unit("s")
+                .build(),
+        }
+    }
+
+    fn record(&self, upstream_id: String, method: &str, status: String, elapsed_secs: f64) {
+        let upstream = KeyValue::new("upstream_id", upstream_id);
+        let method = KeyValue::new("method", method.to_string());
+        self.duration
+            .record(elapsed_secs, &[upstream.clone(), method.clone()]);
+        self.requests
+            .add(1, &[upstream, method, KeyValue::new("status", status)]);
+    }
+}
+
+impl Default for MetricsTransformPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TransformPlugin for MetricsTransformPlugin {
+    async fn transform_response(&self, ctx: &mut ResponseContext) -> Result<(), PluginError> {
+        self.record(
+            ctx.upstream_id.to_string(),
+            &ctx.method,
+            ctx.status.to_string(),
+            ctx.elapsed.as_secs_f64(),
+        );
+        Ok(())
+    }
+
+    async fn transform_error(&self, ctx: &ErrorContext) -> Result<(), PluginError> {
+        self.record(
+            ctx.upstream_id.to_string(),
+            &ctx.method,
+            "error".to_string(),
+            ctx.elapsed.as_secs_f64(),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::test_support::test_response_context;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn records_without_meter_provider() {
+        let plugin = MetricsTransformPlugin::new();
+        let mut ctx = test_response_context(&[], &[], &[]);
+        plugin.transform_response(&mut ctx).await.unwrap();
+        assert!(ctx.headers.is_empty());
+    }
+}
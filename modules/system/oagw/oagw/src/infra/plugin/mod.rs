@@ -1,5 +1,14 @@
 pub(crate) mod apikey_auth;
+pub(crate) mod basic_auth;
+pub(crate) mod bearer_auth;
+pub(crate) mod cors_guard;
+pub(crate) mod logging_transform;
+pub(crate) mod metrics_transform;
 pub(crate) mod noop_auth;
+pub(crate) mod oauth2_client_cred_auth;
 pub(crate) mod registry;
+pub(crate) mod request_id_transform;
+mod secret;
+pub(crate) mod timeout_guard;
 
-pub(crate) use registry::AuthPluginRegistry;
+pub(crate) use registry::{AuthPluginRegistry, GuardPluginRegistry, TransformPluginRegistry};
@@ -27,6 +27,7 @@ mod tests {
         headers.insert("x-existing".to_string(), "value".to_string());
 
         let mut ctx = AuthContext {
+            upstream_id: Uuid::nil(),
             headers: headers.clone(),
             config: HashMap::new(),
             security_context: SecurityContext::builder()
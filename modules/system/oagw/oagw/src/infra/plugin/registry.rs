@@ -3,11 +3,24 @@ use std::sync::Arc;
 
 use credstore_sdk::CredStoreClientV1;
 
-use crate::domain::plugin::{AuthPlugin, PluginError};
+use crate::domain::plugin::{AuthPlugin, GuardPlugin, PluginError, TransformPlugin};
 
 use super::apikey_auth::ApiKeyAuthPlugin;
+use super::basic_auth::BasicAuthPlugin;
+use super::bearer_auth::BearerAuthPlugin;
+use super::cors_guard::CorsGuardPlugin;
+use super::logging_transform::LoggingTransformPlugin;
+use super::metrics_transform::MetricsTransformPlugin;
 use super::noop_auth::NoopAuthPlugin;
-use crate::domain::gts_helpers::{APIKEY_AUTH_PLUGIN_ID, NOOP_AUTH_PLUGIN_ID};
+use super::oauth2_client_cred_auth::OAuth2ClientCredAuthPlugin;
+use super::request_id_transform::RequestIdTransformPlugin;
+use super::timeout_guard::TimeoutGuardPlugin;
+use crate::domain::gts_helpers::{
+    APIKEY_AUTH_PLUGIN_ID, BASIC_AUTH_PLUGIN_ID, BEARER_AUTH_PLUGIN_ID, CORS_GUARD_PLUGIN_ID,
+    LOGGING_TRANSFORM_PLUGIN_ID, METRICS_TRANSFORM_PLUGIN_ID, NOOP_AUTH_PLUGIN_ID,
+    OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID, OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID,
+    REQUEST_ID_TRANSFORM_PLUGIN_ID, TIMEOUT_GUARD_PLUGIN_ID,
+};
 
 /// Registry that resolves auth plugin GTS identifiers to plugin implementations.
 pub struct AuthPluginRegistry {
@@ -15,13 +28,30 @@ pub struct AuthPluginRegistry {
 }
 
 impl AuthPluginRegistry {
-    /// Create a registry with the built-in plugins (apikey, noop).
+    /// Create a registry with the built-in plugins (apikey, basic, bearer,
+    /// oauth2 client credentials, noop).
     #[must_use]
     pub fn with_builtins(credstore: Arc<dyn CredStoreClientV1>) -> Self {
         let mut plugins: HashMap<String, Arc<dyn AuthPlugin>> = HashMap::new();
         plugins.insert(
             APIKEY_AUTH_PLUGIN_ID.to_string(),
-            Arc::new(ApiKeyAuthPlugin::new(credstore)),
+            Arc::new(ApiKeyAuthPlugin::new(credstore.clone())),
+        );
+        plugins.insert(
+            BASIC_AUTH_PLUGIN_ID.to_string(),
+            Arc::new(BasicAuthPlugin::new(credstore.clone())),
+        );
+        plugins.insert(
+            BEARER_AUTH_PLUGIN_ID.to_string(),
+            Arc::new(BearerAuthPlugin::new(credstore.clone())),
+        );
+        plugins.insert(
+            OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID.to_string(),
+            Arc::new(OAuth2ClientCredAuthPlugin::form(credstore.clone())),
+        );
+        plugins.insert(
+            OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID.to_string(),
+            Arc::new(OAuth2ClientCredAuthPlugin::basic(credstore)),
         );
         plugins.insert(NOOP_AUTH_PLUGIN_ID.to_string(), Arc::new(NoopAuthPlugin));
         Self { plugins }
@@ -39,6 +69,73 @@ impl AuthPluginRegistry {
     }
 }
 
+/// Registry that resolves guard plugin GTS identifiers to plugin implementations.
+pub struct GuardPluginRegistry {
+    plugins: HashMap<String, Arc<dyn GuardPlugin>>,
+}
+
+impl GuardPluginRegistry {
+    /// Create a registry with the built-in plugins (timeout, cors).
+    #[must_use]
+    pub fn with_builtins() -> Self {
+        let mut plugins: HashMap<String, Arc<dyn GuardPlugin>> = HashMap::new();
+        plugins.insert(
+            TIMEOUT_GUARD_PLUGIN_ID.to_string(),
+            Arc::new(TimeoutGuardPlugin),
+        );
+        plugins.insert(CORS_GUARD_PLUGIN_ID.to_string(), Arc::new(CorsGuardPlugin));
+        Self { plugins }
+    }
+
+    /// Resolve a plugin by its GTS identifier.
+    ///
+    /// # Errors
+    /// Returns `PluginError::Internal` if the plugin is not registered.
+    pub fn resolve(&self, plugin_id: &str) -> Result<Arc<dyn GuardPlugin>, PluginError> {
+        self.plugins
+            .get(plugin_id)
+            .cloned()
+            .ok_or_else(|| PluginError::Internal(format!("unknown guard plugin: {plugin_id}")))
+    }
+}
+
+/// Registry that resolves transform plugin GTS identifiers to plugin implementations.
+pub struct TransformPluginRegistry {
+    plugins: HashMap<String, Arc<dyn TransformPlugin>>,
+}
+
+impl TransformPluginRegistry {
+    /// Create a registry with the built-in plugins (logging, metrics, request id).
+    #[must_use]
+    pub fn with_builtins() -> Self {
+        let mut plugins: HashMap<String, Arc<dyn TransformPlugin>> = HashMap::new();
+        plugins.insert(
+            LOGGING_TRANSFORM_PLUGIN_ID.to_string(),
+            Arc::new(LoggingTransformPlugin),
+        );
+        plugins.insert(
+            METRICS_TRANSFORM_PLUGIN_ID.to_string(),
+            Arc::new(MetricsTransformPlugin::new()),
+        );
+        plugins.insert(
+            REQUEST_ID_TRANSFORM_PLUGIN_ID.to_string(),
+            Arc::new(RequestIdTransformPlugin),
+        );
+        Self { plugins }
+    }
+
+    /// Resolve a plugin by its GTS identifier.
+    ///
+    /// # Errors
+    /// Returns `PluginError::Internal` if the plugin is not registered.
+    pub fn resolve(&self, plugin_id: &str) -> Result<Arc<dyn TransformPlugin>, PluginError> {
+        self.plugins
+            .get(plugin_id)
+            .cloned()
+            .ok_or_else(|| PluginError::Internal(format!("unknown transform plugin: {plugin_id}")))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -57,6 +154,19 @@ mod tests {
         assert!(registry.resolve(APIKEY_AUTH_PLUGIN_ID).is_ok());
     }
 
+    #[test]
+    fn resolves_credential_plugins() {
+        let registry = make_registry();
+        for id in [
+            BASIC_AUTH_PLUGIN_ID,
+            BEARER_AUTH_PLUGIN_ID,
+            OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID,
+            OAUTH2_CLIENT_CRED_BASIC_AUTH_PLUGIN_ID,
+        ] {
+            assert!(registry.resolve(id).is_ok(), "{id} not registered");
+        }
+    }
+
     #[test]
     fn resolves_noop_plugin() {
         let registry = make_registry();
@@ -69,4 +179,21 @@ mod tests {
         let err = registry.resolve("gts.x.core.oagw.auth_plugin.v1~x.core.oagw.unknown.v1");
         assert!(err.is_err());
     }
+
+    #[test]
+    fn resolves_guard_and_transform_plugins() {
+        let guards = GuardPluginRegistry::with_builtins();
+        for id in [TIMEOUT_GUARD_PLUGIN_ID, CORS_GUARD_PLUGIN_ID] {
+            assert!(guards.resolve(id).is_ok(), "{id} not registered");
+        }
+        let transforms = TransformPluginRegistry::with_builtins();
+        for id in [
+            LOGGING_TRANSFORM_PLUGIN_ID,
+            METRICS_TRANSFORM_PLUGIN_ID,
+            REQUEST_ID_TRANSFORM_PLUGIN_ID,
+        ] {
+            assert!(transforms.resolve(id).is_ok(), "{id} not registered");
+        }
+        assert!(guards.resolve(LOGGING_TRANSFORM_PLUGIN_ID).is_err());
+    }
 }
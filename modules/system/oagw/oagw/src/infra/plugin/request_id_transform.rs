@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::plugin::{PluginError, RequestContext, ResponseContext, TransformPlugin};
+
+const REQUEST_ID_HEADER: &str = "x-request-id";
+/// Longer client-supplied IDs are replaced rather than forwarded.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Transform plugin that propagates `X-Request-ID`.
+///
+/// A well-formed ID supplied by the client is kept; otherwise a UUID v4 is
+/// generated. The ID is sent to the upstream and echoed on the response.
+pub struct RequestIdTransformPlugin;
+
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN && id.bytes().all(|b| b.is_ascii_graphic())
+}
+
+#[async_trait]
+impl TransformPlugin for RequestIdTransformPlugin {
+    async fn transform_request(&self, ctx: &mut RequestContext) -> Result<(), PluginError> {
+        let id = ctx
+            .inbound_headers
+            .get(REQUEST_ID_HEADER)
+            .filter(|id| is_valid_request_id(id))
+            .cloned()
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        ctx.headers.insert(REQUEST_ID_HEADER.into(), id);
+        Ok(())
+    }
+
+    async fn transform_response(&self, ctx: &mut ResponseContext) -> Result<(), PluginError> {
+        if let Some(id) = ctx.request_headers.get(REQUEST_ID_HEADER) {
+            ctx.headers.insert(REQUEST_ID_HEADER.into(), id.clone());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::test_support::{test_request_context, test_response_context};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn keeps_client_request_id() {
+        let mut ctx = test_request_context("GET", &[("x-request-id", "abc-123")], &[]);
+        RequestIdTransformPlugin
+            .transform_request(&mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(ctx.headers["x-request-id"], "abc-123");
+    }
+
+    #[tokio::test]
+    async fn generates_id_when_missing_or_malformed() {
+        for inbound in [vec![], vec![("x-request-id", "has space")]] {
+            let mut ctx = test_request_context("GET", &inbound, &[]);
+            RequestIdTransformPlugin
+                .transform_request(&mut ctx)
+                .await
+                .unwrap();
+            assert!(Uuid::parse_str(&ctx.headers["x-request-id"]).is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn echoes_request_id_on_response() {
+        let mut ctx = test_response_context(&[], &[("x-request-id", "abc-123")], &[]);
+        RequestIdTransformPlugin
+            .transform_response(&mut ctx)
+            .await
+            .unwrap();
+        assert_eq!(ctx.headers["x-request-id"], "abc-123");
+    }
+}
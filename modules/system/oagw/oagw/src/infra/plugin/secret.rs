@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use credstore_sdk::{CredStoreClientV1, SecretRef};
+use modkit_security::SecurityContext;
+use serde::de::DeserializeOwned;
+
+use crate::domain::plugin::PluginError;
+
+/// Deserialize a plugin's key/value configuration into its typed form.
+pub(super) fn parse_config<T: DeserializeOwned>(
+    config: &HashMap<String, String>,
+    plugin: &str,
+) -> Result<T, PluginError> {
+    let value = serde_json::to_value(config)
+        .map_err(|e| PluginError::Internal(format!("invalid {plugin} auth config: {e}")))?;
+    serde_json::from_value(value)
+        .map_err(|e| PluginError::Internal(format!("invalid {plugin} auth config: {e}")))
+}
+
+/// Resolve a secret reference (`cred://name` or bare `name`) to its UTF-8 value.
+pub(super) async fn resolve_secret(
+    credstore: &dyn CredStoreClientV1,
+    ctx: &SecurityContext,
+    secret_ref: &str,
+) -> Result<String, PluginError> {
+    let raw_ref = secret_ref.strip_prefix("cred://").unwrap_or(secret_ref);
+    let key = SecretRef::new(raw_ref)
+        .map_err(|e| PluginError::Internal(format!("invalid secret ref '{raw_ref}': {e}")))?;
+
+    let response = credstore
+        .get(ctx, &key)
+        .await
+        .map_err(|e| PluginError::Internal(format!("credstore error: {e}")))?
+        .ok_or_else(|| PluginError::SecretNotFound(secret_ref.to_string()))?;
+
+    std::str::from_utf8(response.value.as_bytes())
+        .map(str::to_string)
+        .map_err(|_| PluginError::Internal("secret value is not valid UTF-8".into()))
+}
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::domain::plugin::{GuardDecision, GuardPlugin, PluginError, RequestContext};
+
+/// Guard plugin that enforces an end-to-end time budget (`timeout_ms`).
+///
+/// The budget starts when the gateway accepts the request. Time already spent
+/// (e.g. fetching credentials) is deducted, and the remainder caps the
+/// upstream call. A request whose budget is exhausted is rejected with 504.
+pub struct TimeoutGuardPlugin;
+
+#[async_trait]
+impl GuardPlugin for TimeoutGuardPlugin {
+    async fn guard_request(&self, ctx: &mut RequestContext) -> Result<GuardDecision, PluginError> {
+        let timeout_ms: u64 = ctx
+            .config
+            .get("timeout_ms")
+            .ok_or_else(|| {
+                PluginError::Internal("invalid timeout guard config: missing timeout_ms".into())
+            })?
+            .parse()
+            .map_err(|e| {
+                PluginError::Internal(format!("invalid timeout guard config: timeout_ms: {e}"))
+            })?;
+        let budget = Duration::from_millis(timeout_ms);
+
+        let remaining = budget.saturating_sub(ctx.started_at.elapsed());
+        if remaining.is_zero() {
+            return Ok(GuardDecision::Reject {
+                status: 504,
+                detail: format!(
+                    "request exceeded its {timeout_ms}ms timeout before reaching the upstream"
+                ),
+            });
+        }
+
+        ctx.timeout = ctx.timeout.min(remaining);
+        Ok(GuardDecision::Allow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use crate::domain::test_support::test_request_context;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn shortens_upstream_timeout_to_budget() {
+        let mut ctx = test_request_context("GET", &[], &[("timeout_ms", "500")]);
+
+        let decision = TimeoutGuardPlugin.guard_request(&mut ctx).await.unwrap();
+        assert_eq!(decision, GuardDecision::Allow);
+        assert!(ctx.timeout <= Duration::from_millis(500));
+        assert!(ctx.timeout > Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn never_extends_gateway_timeout() {
+        let mut ctx = test_request_context("GET", &[], &[("timeout_ms", "120000")]);
+        ctx.timeout = Duration::from_secs(5);
+
+        TimeoutGuardPlugin.guard_request(&mut ctx).await.unwrap();
+        assert_eq!(ctx.timeout, Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn exhausted_budget_is_rejected_with_504() {
+        let mut ctx = test_request_context("GET", &[], &[("timeout_ms", "10")]);
+        ctx.started_at = Instant::now() - Duration::from_millis(50);
+
+        let decision = TimeoutGuardPlugin.guard_request(&mut ctx).await.unwrap();
+        assert!(matches!(
+            decision,
+            GuardDecision::Reject { status: 504, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn invalid_config_is_internal_error() {
+        for config in [vec![], vec![("timeout_ms", "soon")]] {
+            let mut ctx = test_request_context("GET", &[], &config);
+            let err = TimeoutGuardPlugin
+                .guard_request(&mut ctx)
+                .await
+                .unwrap_err();
+            assert!(matches!(err, PluginError::Internal(_)));
+        }
+    }
+}
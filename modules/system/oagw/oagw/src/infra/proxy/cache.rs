@@ -0,0 +1,820 @@
+//! Per-route response cache for idempotent upstream calls.
+//!
+//! Entries are keyed by tenant, upstream and the normalized request (method,
+//! path, sorted query and, for POST, a hash of the body). A request carrying
+//! credentials (`Authorization`, `Proxy-Authorization` or `Cookie`) is also
+//! keyed by its subject and a hash of those credentials, so a response that
+//! an upstream produced for one caller is never served to another. Under one
+//! key the
+//! request headers named by the upstream `Vary` header and the route's
+//! `vary_headers` select the variant. Freshness is the route TTL, shortened
+//! by `s-maxage`/`max-age`; expired entries that carry an `ETag` are
+//! revalidated with `If-None-Match` instead of being fetched again.
+//!
+//! The cache is bounded by the total size of the stored responses and evicts
+//! the least recently used entries first.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use futures_util::StreamExt;
+use http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode, header};
+use oagw_sdk::api::ErrorSource;
+use oagw_sdk::body::Body;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::domain::model::CacheConfig;
+
+/// Set on responses from cache-enabled routes: `hit`, `revalidated` or `miss`.
+pub(crate) const H_CACHE_STATUS: &str = "x-oagw-cache";
+
+/// Default bound on the total size of cached responses: 64 MiB.
+pub(crate) const DEFAULT_CAPACITY_BYTES: usize = 64 * 1024 * 1024;
+
+/// How the request's own `Cache-Control` lets the cache take part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RequestPolicy {
+    /// Serve from the cache and store the response.
+    Use,
+    /// `no-cache` or `max-age=0`: skip the lookup but store the response.
+    Refresh,
+    /// `no-store`: leave the cache alone.
+    Bypass,
+}
+
+/// A stored upstream response.
+#[derive(Debug, Clone)]
+pub(crate) struct CachedResponse {
+    pub(crate) status: StatusCode,
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: Bytes,
+}
+
+impl CachedResponse {
+    pub(crate) fn etag(&self) -> Option<&HeaderValue> {
+        self.headers.get(header::ETAG)
+    }
+
+    /// Merge the headers of a `304 Not Modified` into the stored ones.
+    pub(crate) fn refresh_headers(&mut self, not_modified: &HeaderMap) {
+        for name in not_modified.keys() {
+            if name == header::CONTENT_LENGTH || name == header::TRANSFER_ENCODING {
+                continue;
+            }
+            self.headers.remove(name);
+            for value in not_modified.get_all(name) {
+                self.headers.append(name.clone(), value.clone());
+            }
+        }
+    }
+
+    /// Build the response served to the client, answering its
+    /// `If-None-Match` with a 304 when the `ETag` matches.
+    pub(crate) fn into_response(
+        self,
+        age: Duration,
+        req_headers: &HeaderMap,
+        cache_status: &'static str,
+    ) -> http::Response<Body> {
+        let not_modified = self
+            .etag()
+            .is_some_and(|etag| etag_matches(req_headers, etag));
+        let mut resp = if not_modified {
+            let mut resp = http::Response::new(Body::Empty);
+            *resp.status_mut() = StatusCode::NOT_MODIFIED;
+            for name in [header::ETAG, header::CACHE_CONTROL, header::VARY] {
+                for value in self.headers.get_all(&name) {
+                    resp.headers_mut().append(name.clone(), value.clone());
+                }
+            }
+            resp
+        } else {
+            let mut resp = http::Response::new(Body::from(self.body));
+            *resp.status_mut() = self.status;
+            *resp.headers_mut() = self.headers;
+            resp
+        };
+        resp.headers_mut()
+            .insert(header::AGE, HeaderValue::from(age.as_secs()));
+        resp.headers_mut().insert(
+            HeaderName::from_static(H_CACHE_STATUS),
+            HeaderValue::from_static(cache_status),
+        );
+        resp.extensions_mut().insert(ErrorSource::Upstream);
+        resp
+    }
+}
+
+/// Result of a cache lookup.
+pub(crate) enum Lookup {
+    /// A fresh entry, ready to serve.
+    Fresh(http::Response<Body>),
+    /// An expired entry that can be revalidated with its `ETag`.
+    Stale(CachedResponse),
+    Miss,
+}
+
+struct Entry {
+    response: CachedResponse,
+    stored_at: Instant,
+    ttl: Duration,
+    size: usize,
+    last_used: u64,
+}
+
+/// Values of the `vary` request headers selecting one variant of a key.
+type Variant = Vec<Option<HeaderValue>>;
+
+/// All variants stored under one key.
+struct Slot {
+    upstream_id: Uuid,
+    /// Request headers the variants differ by.
+    vary: Vec<HeaderName>,
+    variants: HashMap<Variant, Entry>,
+}
+
+#[derive(Default)]
+struct Inner {
+    slots: HashMap<String, Slot>,
+    /// Every entry by its `last_used` tick, least recently used first.
+    lru: BTreeMap<u64, (String, Variant)>,
+    used_bytes: usize,
+    /// Logical clock for least-recently-used eviction.
+    tick: u64,
+}
+
+impl Inner {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn remove_variant(&mut self, key: &str, variant: &[Option<HeaderValue>]) {
+        let Some(slot) = self.slots.get_mut(key) else {
+            return;
+        };
+        if let Some(entry) = slot.variants.remove(variant) {
+            self.used_bytes -= entry.size;
+            self.lru.remove(&entry.last_used);
+        }
+        if slot.variants.is_empty() {
+            self.slots.remove(key);
+        }
+    }
+
+    /// Forget the bookkeeping of a slot that was taken out of `slots`.
+    fn release_slot(&mut self, slot: &Slot) {
+        for entry in slot.variants.values() {
+            self.used_bytes -= entry.size;
+            self.lru.remove(&entry.last_used);
+        }
+    }
+
+    fn evict_to(&mut self, capacity: usize) {
+        while self.used_bytes > capacity {
+            let Some((_, (key, variant))) = self.lru.pop_first() else {
+                return;
+            };
+            self.remove_variant(&key, &variant);
+        }
+    }
+}
+
+/// In-memory response cache shared by all cache-enabled routes.
+pub(crate) struct ResponseCache {
+    capacity_bytes: usize,
+    inner: Mutex<Inner>,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(capacity_bytes: usize) -> Self {
+        Self {
+            capacity_bytes,
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn lookup(&self, key: &str, req_headers: &HeaderMap) -> Lookup {
+        let mut inner = self.lock();
+        let tick = inner.next_tick();
+        let Inner { slots, lru, .. } = &mut *inner;
+        let Some(slot) = slots.get_mut(key) else {
+            return Lookup::Miss;
+        };
+        let variant = variant_key(&slot.vary, req_headers);
+        let Some(entry) = slot.variants.get_mut(&variant) else {
+            return Lookup::Miss;
+        };
+        if let Some(position) = lru.remove(&entry.last_used) {
+            lru.insert(tick, position);
+        }
+        entry.last_used = tick;
+        let age = entry.stored_at.elapsed();
+        if age < entry.ttl {
+            return Lookup::Fresh(
+                entry
+                    .response
+                    .clone()
+                    .into_response(age, req_headers, "hit"),
+            );
+        }
+        if entry.response.etag().is_some() {
+            return Lookup::Stale(entry.response.clone());
+        }
+        inner.remove_variant(key, &variant);
+        Lookup::Miss
+    }
+
+    /// Store a response under `key`. `vary` names the request headers that
+    /// select this variant; storing with a different set replaces all
+    /// variants of the key.
+    pub(crate) fn store(
+        &self,
+        key: String,
+        upstream_id: Uuid,
+        vary: Vec<HeaderName>,
+        req_headers: &HeaderMap,
+        response: CachedResponse,
+        ttl: Duration,
+    ) {
+        let size = key.len() + response.body.len() + headers_size(&response.headers);
+        if size > self.capacity_bytes {
+            return;
+        }
+        let variant = variant_key(&vary, req_headers);
+
+        let mut inner = self.lock();
+        let tick = inner.next_tick();
+        let entry = Entry {
+            response,
+            stored_at: Instant::now(),
+            ttl,
+            size,
+            last_used: tick,
+        };
+        if inner
+            .slots
+            .get(&key)
+            .is_some_and(|slot| slot.upstream_id != upstream_id || slot.vary != vary)
+            && let Some(old) = inner.slots.remove(&key)
+        {
+            inner.release_slot(&old);
+        }
+        inner.remove_variant(&key, &variant);
+        inner.lru.insert(tick, (key.clone(), variant.clone()));
+        inner
+            .slots
+            .entry(key)
+            .or_insert_with(|| Slot {
+                upstream_id,
+                vary,
+                variants: HashMap::new(),
+            })
+            .variants
+            .insert(variant, entry);
+        inner.used_bytes += size;
+        inner.evict_to(self.capacity_bytes);
+    }
+
+    /// Drop every entry cached for an upstream (called on upstream and route CRUD).
+    pub(crate) fn invalidate_upstream(&self, upstream_id: Uuid) {
+        let mut inner = self.lock();
+        let keys: Vec<String> = inner
+            .slots
+            .iter()
+            .filter(|(_, slot)| slot.upstream_id == upstream_id)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in keys {
+            if let Some(slot) = inner.slots.remove(&key) {
+                inner.release_slot(&slot);
+            }
+        }
+    }
+}
+
+/// Request headers that carry the caller's credentials.
+const CREDENTIAL_HEADERS: [HeaderName; 3] = [
+    header::AUTHORIZATION,
+    header::PROXY_AUTHORIZATION,
+    header::COOKIE,
+];
+
+/// Key part separating the entries of callers with different credentials;
+/// `None` when the request carries none.
+pub(crate) fn credentials_key(subject_id: Uuid, req_headers: &HeaderMap) -> Option<String> {
+    let mut hasher = Sha256::new();
+    let mut any = false;
+    for name in &CREDENTIAL_HEADERS {
+        for value in req_headers.get_all(name) {
+            any = true;
+            hasher.update(name.as_str());
+            hasher.update(b":");
+            hasher.update(value.as_bytes());
+            hasher.update(b"\n");
+        }
+    }
+    any.then(|| format!("{subject_id}:{:x}", hasher.finalize()))
+}
+
+/// Build the primary cache key. The body only takes part for POST, the
+/// [`credentials_key`] when the request carries credentials.
+pub(crate) fn cache_key(
+    tenant_id: Uuid,
+    upstream_id: Uuid,
+    credentials: Option<&str>,
+    method: &Method,
+    path: &str,
+    query: &[(String, String)],
+    body: &[u8],
+) -> String {
+    let mut sorted: Vec<_> = query.iter().collect();
+    sorted.sort();
+    let query = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(sorted)
+        .finish();
+    let mut key = format!("{tenant_id}:{upstream_id}:{method} {path}?{query}");
+    if let Some(credentials) = credentials {
+        key.push_str(&format!(":as {credentials}"));
+    }
+    if *method == Method::POST {
+        key.push_str(&format!(":{:x}", Sha256::digest(body)));
+    }
+    key
+}
+
+/// Whether `method` is cacheable under `config`.
+pub(crate) fn is_cacheable_method(config: &CacheConfig, method: &Method) -> bool {
+    *method == Method::GET
+        || *method == Method::HEAD
+        || (config.cache_post && *method == Method::POST)
+}
+
+pub(crate) fn request_policy(req_headers: &HeaderMap) -> RequestPolicy {
+    let directives = cache_control(req_headers);
+    if directives.iter().any(|(name, _)| name == "no-store") {
+        return RequestPolicy::Bypass;
+    }
+    let pragma_no_cache = req_headers
+        .get_all(header::PRAGMA)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .any(|v| v.to_ascii_lowercase().contains("no-cache"));
+    let refresh = pragma_no_cache
+        || directives.iter().any(|(name, value)| {
+            name == "no-cache" || (name == "max-age" && value.as_deref() == Some("0"))
+        });
+    if refresh {
+        RequestPolicy::Refresh
+    } else {
+        RequestPolicy::Use
+    }
+}
+
+/// Freshness lifetime of an upstream response, or `None` if it must not be
+/// stored: only complete `200` responses without `no-store`, `no-cache`,
+/// `private`, `Vary: *` or cookies are cached, and never event streams.
+pub(crate) fn storable_ttl(
+    config: &CacheConfig,
+    status: StatusCode,
+    headers: &HeaderMap,
+) -> Option<Duration> {
+    if status != StatusCode::OK || headers.contains_key(header::SET_COOKIE) {
+        return None;
+    }
+    if headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.trim_start().starts_with("text/event-stream"))
+    {
+        return None;
+    }
+    if header_tokens(headers, &header::VARY).any(|name| name == "*") {
+        return None;
+    }
+    if headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .is_some_and(|len| len > config.max_entry_bytes)
+    {
+        return None;
+    }
+
+    let directives = cache_control(headers);
+    if directives
+        .iter()
+        .any(|(name, _)| matches!(name.as_str(), "no-store" | "no-cache" | "private"))
+    {
+        return None;
+    }
+    let seconds = |directive: &str| {
+        directives
+            .iter()
+            .find(|(name, _)| name == directive)
+            .and_then(|(_, value)| value.as_deref()?.parse::<u64>().ok())
+    };
+    let mut ttl = u64::from(config.ttl_seconds);
+    if let Some(max_age) = seconds("s-maxage").or_else(|| seconds("max-age")) {
+        let age = headers
+            .get(header::AGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(0);
+        ttl = ttl.min(max_age.saturating_sub(age));
+    }
+    (ttl > 0).then(|| Duration::from_secs(ttl))
+}
+
+/// Request headers a response varies by: the upstream `Vary` header plus the
+/// route's `vary_headers`, lowercased, sorted and deduplicated.
+pub(crate) fn vary_headers(config: &CacheConfig, resp_headers: &HeaderMap) -> Vec<HeaderName> {
+    let mut names: Vec<HeaderName> = header_tokens(resp_headers, &header::VARY)
+        .chain(config.vary_headers.iter().map(|s| s.to_ascii_lowercase()))
+        .filter_map(|name| HeaderName::from_bytes(name.as_bytes()).ok())
+        .collect();
+    names.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    names.dedup();
+    names
+}
+
+/// Buffer a response body of at most `limit` bytes. A larger or failing body
+/// is handed back unchanged, with the chunks read so far replayed first.
+pub(crate) async fn read_bounded(body: Body, limit: u64) -> Result<Bytes, Body> {
+    let limit = usize::try_from(limit).unwrap_or(usize::MAX);
+    let mut stream = match body {
+        Body::Empty => return Ok(Bytes::new()),
+        Body::Bytes(b) if b.len() <= limit => return Ok(b),
+        Body::Bytes(b) => return Err(Body::Bytes(b)),
+        Body::Stream(s) => s,
+    };
+    let mut chunks = Vec::new();
+    let mut total = 0usize;
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(chunk) => {
+                total = total.saturating_add(chunk.len());
+                chunks.push(Ok(chunk));
+                if total > limit {
+                    return Err(replay(chunks, stream));
+                }
+            }
+            Err(e) => {
+                chunks.push(Err(e));
+                return Err(replay(chunks, stream));
+            }
+        }
+    }
+    let mut buf = Vec::with_capacity(total);
+    for chunk in chunks.into_iter().flatten() {
+        buf.extend_from_slice(&chunk);
+    }
+    Ok(Bytes::from(buf))
+}
+
+fn replay(
+    chunks: Vec<Result<Bytes, oagw_sdk::body::BoxError>>,
+    rest: oagw_sdk::body::BodyStream,
+) -> Body {
+    Body::Stream(Box::pin(futures_util::stream::iter(chunks).chain(rest)))
+}
+
+/// Lowercased `Cache-Control` directives with their unquoted values.
+fn cache_control(headers: &HeaderMap) -> Vec<(String, Option<String>)> {
+    header_tokens(headers, &header::CACHE_CONTROL)
+        .map(|directive| match directive.split_once('=') {
+            Some((name, value)) => (
+                name.trim().to_string(),
+                Some(value.trim().trim_matches('"').to_string()),
+            ),
+            None => (directive, None),
+        })
+        .collect()
+}
+
+/// Comma-separated, lowercased tokens across all values of a header.
+fn header_tokens<'a>(
+    headers: &'a HeaderMap,
+    name: &HeaderName,
+) -> impl Iterator<Item = String> + 'a {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|t| t.trim().to_ascii_lowercase())
+        .filter(|t| !t.is_empty())
+}
+
+fn etag_matches(req_headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    let Ok(etag) = etag.to_str() else {
+        return false;
+    };
+    let weak = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    req_headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|tag| tag.trim() == "*" || weak(tag) == weak(etag))
+}
+
+fn variant_key(vary: &[HeaderName], req_headers: &HeaderMap) -> Vec<Option<HeaderValue>> {
+    vary.iter()
+        .map(|name| req_headers.get(name).cloned())
+        .collect()
+}
+
+fn headers_size(headers: &HeaderMap) -> usize {
+    headers
+        .iter()
+        .map(|(name, value)| name.as_str().len() + value.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.append(*name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    fn response(body: &'static str, pairs: &[(&'static str, &'static str)]) -> CachedResponse {
+        CachedResponse {
+            status: StatusCode::OK,
+            headers: headers(pairs),
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn key_sorts_query_and_hashes_post_body() {
+        let (tenant, upstream) = (Uuid::nil(), Uuid::nil());
+        let q1 = vec![("b".into(), "2".into()), ("a".into(), "1".into())];
+        let q2 = vec![("a".into(), "1".into()), ("b".into(), "2".into())];
+        assert_eq!(
+            cache_key(tenant, upstream, None, &Method::GET, "/v1/models", &q1, b""),
+            cache_key(tenant, upstream, None, &Method::GET, "/v1/models", &q2, b""),
+        );
+
+        let post = |body: &[u8]| {
+            cache_key(
+                tenant,
+                upstream,
+                None,
+                &Method::POST,
+                "/v1/embed",
+                &[],
+                body,
+            )
+        };
+        assert_eq!(post(b"{\"input\":\"x\"}"), post(b"{\"input\":\"x\"}"));
+        assert_ne!(post(b"{\"input\":\"x\"}"), post(b"{\"input\":\"y\"}"));
+        assert_ne!(
+            cache_key(
+                Uuid::new_v4(),
+                upstream,
+                None,
+                &Method::GET,
+                "/v1/models",
+                &[],
+                b""
+            ),
+            cache_key(tenant, upstream, None, &Method::GET, "/v1/models", &[], b""),
+        );
+    }
+
+    #[test]
+    fn key_separates_callers_with_credentials() {
+        let (tenant, upstream) = (Uuid::nil(), Uuid::nil());
+        let (alice, bob) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        assert_eq!(credentials_key(alice, &HeaderMap::new()), None);
+        assert_eq!(
+            credentials_key(alice, &headers(&[("accept", "application/json")])),
+            None
+        );
+
+        let token = |t: &'static str| headers(&[("authorization", t)]);
+        let a = credentials_key(alice, &token("Bearer a")).unwrap();
+        assert_eq!(credentials_key(alice, &token("Bearer a")).unwrap(), a);
+        assert_ne!(credentials_key(alice, &token("Bearer b")).unwrap(), a);
+        assert_ne!(credentials_key(bob, &token("Bearer a")).unwrap(), a);
+        assert!(credentials_key(alice, &headers(&[("cookie", "session=1")])).is_some());
+
+        let key = |credentials: Option<&str>| {
+            cache_key(
+                tenant,
+                upstream,
+                credentials,
+                &Method::GET,
+                "/v1/me",
+                &[],
+                b"",
+            )
+        };
+        assert_ne!(key(Some(&a)), key(None));
+    }
+
+    #[test]
+    fn request_cache_control_selects_policy() {
+        assert_eq!(request_policy(&HeaderMap::new()), RequestPolicy::Use);
+        assert_eq!(
+            request_policy(&headers(&[("cache-control", "no-cache")])),
+            RequestPolicy::Refresh
+        );
+        assert_eq!(
+            request_policy(&headers(&[("cache-control", "max-age=0")])),
+            RequestPolicy::Refresh
+        );
+        assert_eq!(
+            request_policy(&headers(&[("cache-control", "No-Store, max-age=0")])),
+            RequestPolicy::Bypass
+        );
+    }
+
+    #[test]
+    fn storable_ttl_honors_response_directives() {
+        let cfg = CacheConfig::default();
+        let ttl = |pairs| storable_ttl(&cfg, StatusCode::OK, &headers(pairs));
+
+        assert_eq!(ttl(&[]), Some(Duration::from_secs(300)));
+        assert_eq!(
+            ttl(&[("cache-control", "public, max-age=60")]),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            ttl(&[("cache-control", "max-age=60, s-maxage=30"), ("age", "10")]),
+            Some(Duration::from_secs(20))
+        );
+        assert_eq!(
+            ttl(&[("cache-control", "max-age=3600")]),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(ttl(&[("cache-control", "max-age=0")]), None);
+        assert_eq!(ttl(&[("cache-control", "private")]), None);
+        assert_eq!(ttl(&[("cache-control", "no-store")]), None);
+        assert_eq!(ttl(&[("vary", "*")]), None);
+        assert_eq!(ttl(&[("set-cookie", "id=1")]), None);
+        assert_eq!(ttl(&[("content-type", "text/event-stream")]), None);
+        assert_eq!(ttl(&[("content-length", "2097152")]), None);
+        assert_eq!(
+            storable_ttl(&cfg, StatusCode::NOT_FOUND, &HeaderMap::new()),
+            None
+        );
+    }
+
+    #[test]
+    fn variants_are_selected_by_vary_headers() {
+        let cache = ResponseCache::new(DEFAULT_CAPACITY_BYTES);
+        let cfg = CacheConfig {
+            vary_headers: vec!["X-Model".into()],
+            ..CacheConfig::default()
+        };
+        let upstream = Uuid::new_v4();
+        let vary = vary_headers(&cfg, &headers(&[("vary", "Accept-Language")]));
+        assert_eq!(vary, vec!["accept-language", "x-model"]);
+
+        let en = headers(&[("accept-language", "en"), ("x-model", "a")]);
+        let de = headers(&[("accept-language", "de"), ("x-model", "a")]);
+        cache.store(
+            "k".into(),
+            upstream,
+            vary.clone(),
+            &en,
+            response("en", &[]),
+            TTL,
+        );
+        cache.store("k".into(), upstream, vary, &de, response("de", &[]), TTL);
+
+        let Lookup::Fresh(resp) = cache.lookup("k", &en) else {
+            panic!("expected a hit");
+        };
+        assert_eq!(resp.headers()[H_CACHE_STATUS], "hit");
+        assert!(matches!(resp.into_body(), Body::Bytes(b) if b == "en"));
+        assert!(matches!(
+            cache.lookup("k", &headers(&[("accept-language", "fr")])),
+            Lookup::Miss
+        ));
+    }
+
+    #[test]
+    fn expired_entries_revalidate_only_with_etag() {
+        let cache = ResponseCache::new(DEFAULT_CAPACITY_BYTES);
+        let upstream = Uuid::new_v4();
+        let req = HeaderMap::new();
+        cache.store(
+            "tagged".into(),
+            upstream,
+            vec![],
+            &req,
+            response("a", &[("etag", "\"v1\"")]),
+            Duration::ZERO,
+        );
+        cache.store(
+            "plain".into(),
+            upstream,
+            vec![],
+            &req,
+            response("b", &[]),
+            Duration::ZERO,
+        );
+
+        assert!(
+            matches!(cache.lookup("tagged", &req), Lookup::Stale(r) if r.etag().unwrap() == "\"v1\"")
+        );
+        assert!(matches!(cache.lookup("plain", &req), Lookup::Miss));
+        let inner = cache.lock();
+        assert!(!inner.slots.contains_key("plain"));
+        assert_eq!(inner.slots.len(), 1);
+    }
+
+    #[test]
+    fn fresh_hit_answers_matching_if_none_match_with_304() {
+        let cache = ResponseCache::new(DEFAULT_CAPACITY_BYTES);
+        cache.store(
+            "k".into(),
+            Uuid::new_v4(),
+            vec![],
+            &HeaderMap::new(),
+            response("body", &[("etag", "W/\"v1\"")]),
+            TTL,
+        );
+        let Lookup::Fresh(resp) = cache.lookup("k", &headers(&[("if-none-match", "\"v1\"")]))
+        else {
+            panic!("expected a hit");
+        };
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(resp.headers()[header::ETAG], "W/\"v1\"");
+        assert!(resp.body().is_empty());
+    }
+
+    #[test]
+    fn least_recently_used_entries_are_evicted_first() {
+        let entry_size = "k1".len() + 100;
+        let cache = ResponseCache::new(entry_size * 2);
+        let upstream = Uuid::new_v4();
+        let req = HeaderMap::new();
+        let body = || CachedResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::from(vec![0u8; 100]),
+        };
+        cache.store("k1".into(), upstream, vec![], &req, body(), TTL);
+        cache.store("k2".into(), upstream, vec![], &req, body(), TTL);
+        assert!(matches!(cache.lookup("k1", &req), Lookup::Fresh(_)));
+        cache.store("k3".into(), upstream, vec![], &req, body(), TTL);
+
+        assert!(matches!(cache.lookup("k1", &req), Lookup::Fresh(_)));
+        assert!(matches!(cache.lookup("k2", &req), Lookup::Miss));
+        assert!(matches!(cache.lookup("k3", &req), Lookup::Fresh(_)));
+        let inner = cache.lock();
+        assert_eq!(inner.used_bytes, entry_size * 2);
+        let order: Vec<&str> = inner.lru.values().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(order, ["k1", "k3"]);
+    }
+
+    #[test]
+    fn invalidate_upstream_drops_only_its_entries() {
+        let cache = ResponseCache::new(DEFAULT_CAPACITY_BYTES);
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let req = HeaderMap::new();
+        cache.store("a".into(), a, vec![], &req, response("a", &[]), TTL);
+        cache.store("b".into(), b, vec![], &req, response("b", &[]), TTL);
+
+        cache.invalidate_upstream(a);
+        assert!(matches!(cache.lookup("a", &req), Lookup::Miss));
+        assert!(matches!(cache.lookup("b", &req), Lookup::Fresh(_)));
+        assert_eq!(cache.lock().lru.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn read_bounded_replays_oversized_streams() {
+        let chunks = || {
+            let items: Vec<Result<Bytes, oagw_sdk::body::BoxError>> = vec![
+                Ok(Bytes::from_static(b"abc")),
+                Ok(Bytes::from_static(b"def")),
+            ];
+            Body::Stream(Box::pin(futures_util::stream::iter(items)))
+        };
+        assert_eq!(read_bounded(chunks(), 6).await.unwrap(), "abcdef");
+
+        let Err(body) = read_bounded(chunks(), 4).await else {
+            panic!("expected the body back");
+        };
+        assert_eq!(body.into_bytes().await.unwrap(), "abcdef");
+    }
+}
@@ -0,0 +1,431 @@
+//! Native gRPC transport (ADR 0014).
+//!
+//! gRPC needs HTTP/2 end to end and reports its outcome in trailers, neither
+//! of which survives the Pingora HTTP/1 bridge. gRPC calls therefore go
+//! straight to the upstream over a pooled HTTP/2 client (TLS, ALPN `h2`); the
+//! rest of the proxy pipeline — auth, header rules, guards, circuit breaker,
+//! rate limits — runs unchanged in front of it. Frames are forwarded as-is,
+//! without parsing Protobuf.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use http::{HeaderMap, HeaderName, HeaderValue};
+use http_body_util::{BodyExt, StreamBody};
+use hyper::body::{Frame, Incoming};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::client::legacy::Client;
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::rt::{TokioExecutor, TokioTimer};
+use oagw_sdk::api::ErrorSource;
+use oagw_sdk::body::{Body, BoxError, Trailers};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::WebPkiSupportedAlgorithms;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, SignatureScheme};
+
+use crate::domain::error::DomainError;
+use crate::domain::model::Endpoint;
+
+use super::headers;
+
+const CONTENT_TYPE_PREFIX: &str = "application/grpc";
+const H_GRPC_STATUS: &str = "grpc-status";
+const H_GRPC_MESSAGE: &str = "grpc-message";
+const H_GRPC_TIMEOUT: &str = "grpc-timeout";
+/// Call metadata the gRPC wire protocol needs, forwarded whatever the
+/// upstream's passthrough mode.
+const PROTOCOL_HEADERS: &[&str] = &[H_GRPC_TIMEOUT, "grpc-encoding", "grpc-accept-encoding"];
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30);
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+// gRPC status codes mapped onto gateway errors.
+const DEADLINE_EXCEEDED: u32 = 4;
+const PERMISSION_DENIED: u32 = 7;
+const RESOURCE_EXHAUSTED: u32 = 8;
+const UNAVAILABLE: u32 = 14;
+const UNAUTHENTICATED: u32 = 16;
+
+type FrameStream = Pin<Box<dyn Stream<Item = Result<Frame<Bytes>, BoxError>> + Send>>;
+type RequestBody = StreamBody<FrameStream>;
+
+/// Whether the request is a gRPC call (`content-type: application/grpc*`).
+pub(crate) fn is_grpc_request(headers: &HeaderMap) -> bool {
+    headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with(CONTENT_TYPE_PREFIX))
+}
+
+/// Split a gRPC request path `/{package.Service}/{Method}`.
+pub(crate) fn parse_path(path: &str) -> Option<(&str, &str)> {
+    let (service, method) = path.strip_prefix('/')?.split_once('/')?;
+    (!service.is_empty() && !method.is_empty() && !method.contains('/'))
+        .then_some((service, method))
+}
+
+/// Restore what the gRPC wire protocol requires after passthrough filtering
+/// and hop-by-hop stripping: `te: trailers` and the call metadata headers.
+/// HTTP/2 carries the authority in the request URI, so `host` is dropped.
+pub(crate) fn prepare_request_headers(outbound: &mut HeaderMap, inbound: &HeaderMap) {
+    for name in PROTOCOL_HEADERS {
+        if !outbound.contains_key(*name)
+            && let Some(v) = inbound.get(*name)
+        {
+            outbound.insert(HeaderName::from_static(name), v.clone());
+        }
+    }
+    outbound.insert(http::header::TE, HeaderValue::from_static("trailers"));
+    outbound.remove(http::header::HOST);
+}
+
+/// The deadline the caller set through `grpc-timeout`, if any.
+pub(crate) fn request_deadline(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(H_GRPC_TIMEOUT)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_timeout)
+}
+
+/// Parse a `grpc-timeout` value: up to 8 digits followed by a unit
+/// (`H`, `M`, `S`, `m`, `u`, `n`).
+fn parse_timeout(value: &str) -> Option<Duration> {
+    let unit = value.chars().last()?;
+    let digits = &value[..value.len() - unit.len_utf8()];
+    if digits.is_empty() || digits.len() > 8 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let amount: u64 = digits.parse().ok()?;
+    Some(match unit {
+        'H' => Duration::from_secs(amount * 3600),
+        'M' => Duration::from_secs(amount * 60),
+        'S' => Duration::from_secs(amount),
+        'm' => Duration::from_millis(amount),
+        'u' => Duration::from_micros(amount),
+        'n' => Duration::from_nanos(amount),
+        _ => return None,
+    })
+}
+
+/// Map a failed call reported in the response headers (a "Trailers-Only"
+/// response) onto the gateway's error model. Statuses outside the mapping are
+/// application outcomes and reach the caller untouched, as do statuses sent in
+/// trailers after a streamed body.
+pub(crate) fn status_error(headers: &HeaderMap, instance: &str) -> Option<DomainError> {
+    let code: u32 = headers
+        .get(H_GRPC_STATUS)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    let message = headers
+        .get(H_GRPC_MESSAGE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let detail = |name: &str| {
+        if message.is_empty() {
+            format!("upstream returned gRPC status {name}")
+        } else {
+            format!("upstream returned gRPC status {name}: {message}")
+        }
+    };
+    let instance = instance.to_string();
+    Some(match code {
+        UNAUTHENTICATED => DomainError::AuthenticationFailed {
+            detail: detail("UNAUTHENTICATED"),
+            instance,
+        },
+        // The upstream refused the call; the gateway's own policy allowed it.
+        PERMISSION_DENIED => DomainError::DownstreamError {
+            detail: detail("PERMISSION_DENIED"),
+            instance,
+        },
+        RESOURCE_EXHAUSTED => DomainError::RateLimitExceeded {
+            detail: detail("RESOURCE_EXHAUSTED"),
+            instance,
+            retry_after_secs: None,
+        },
+        UNAVAILABLE => DomainError::DownstreamError {
+            detail: detail("UNAVAILABLE"),
+            instance,
+        },
+        DEADLINE_EXCEEDED => DomainError::RequestTimeout {
+            detail: detail("DEADLINE_EXCEEDED"),
+            instance,
+        },
+        _ => return None,
+    })
+}
+
+/// Pooled HTTP/2 client for gRPC upstreams.
+pub struct GrpcTransport {
+    client: Client<HttpsConnector<HttpConnector>, RequestBody>,
+}
+
+impl GrpcTransport {
+    /// Build a transport that trusts the WebPKI roots. With
+    /// `skip_tls_verify` the upstream certificate is not checked at all —
+    /// **test use only**.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TLS configuration cannot be built.
+    pub fn new(connect_timeout: Duration, skip_tls_verify: bool) -> Result<Self, rustls::Error> {
+        let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+        let tls = if skip_tls_verify {
+            let config = ClientConfig::builder_with_provider(provider.clone())
+                .with_safe_default_protocol_versions()?
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(NoVerification(
+                    provider.signature_verification_algorithms,
+                )))
+                .with_no_client_auth();
+            HttpsConnectorBuilder::new().with_tls_config(config)
+        } else {
+            HttpsConnectorBuilder::new().with_provider_and_webpki_roots(provider)?
+        };
+
+        let mut http = HttpConnector::new();
+        http.enforce_http(false);
+        http.set_nodelay(true);
+        http.set_connect_timeout(Some(connect_timeout));
+        let connector = tls.https_only().enable_http2().wrap_connector(http);
+
+        let client = Client::builder(TokioExecutor::new())
+            .http2_only(true)
+            .timer(TokioTimer::new())
+            .pool_timer(TokioTimer::new())
+            .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+            .http2_keep_alive_interval(KEEP_ALIVE_INTERVAL)
+            .build(connector);
+        Ok(Self { client })
+    }
+
+    /// Send one call and return once the upstream has answered with its
+    /// response headers. The response body streams; its trailers land in the
+    /// [`Trailers`] extension once the body has been read to the end.
+    ///
+    /// A streamed request body longer than `max_body` bytes is cut off, which
+    /// resets the HTTP/2 stream.
+    pub(crate) async fn send(
+        &self,
+        endpoint: &Endpoint,
+        path: &str,
+        headers: HeaderMap,
+        body: Body,
+        max_body: usize,
+        instance_uri: &str,
+    ) -> Result<http::Response<Body>, DomainError> {
+        let uri = format!("https://{}:{}{path}", endpoint.host, endpoint.port);
+        let mut req = http::Request::post(&uri)
+            .version(http::Version::HTTP_2)
+            .body(request_body(body, max_body))
+            .map_err(|e| DomainError::ProtocolError {
+                detail: format!("invalid gRPC upstream request: {e}"),
+                instance: instance_uri.to_string(),
+            })?;
+        *req.headers_mut() = headers;
+
+        let resp = self
+            .client
+            .request(req)
+            .await
+            .map_err(|e| DomainError::DownstreamError {
+                detail: format!("gRPC upstream call to {uri} failed: {e}"),
+                instance: instance_uri.to_string(),
+            })?;
+        Ok(proxy_response(resp))
+    }
+}
+
+/// Forward the caller's body as HTTP/2 data frames, bounded by `max_body`.
+fn request_body(body: Body, max_body: usize) -> RequestBody {
+    let mut total: usize = 0;
+    let frames = body.into_stream().map(move |chunk| {
+        let chunk = chunk?;
+        total = total.saturating_add(chunk.len());
+        if total > max_body {
+            tracing::warn!(
+                total,
+                max_body,
+                "gRPC request body exceeded max size, aborting"
+            );
+            return Err(format!("request body exceeds maximum of {max_body} bytes").into());
+        }
+        Ok(Frame::data(chunk))
+    });
+    StreamBody::new(Box::pin(frames))
+}
+
+/// Turn the upstream response into a proxy response whose body yields the
+/// data frames and whose [`Trailers`] extension receives the trailer frame.
+fn proxy_response(resp: http::Response<Incoming>) -> http::Response<Body> {
+    let (mut parts, incoming) = resp.into_parts();
+    headers::sanitize_response_headers(&mut parts.headers);
+
+    let trailers = Trailers::new();
+    let sink = trailers.clone();
+    let data = futures_util::stream::unfold(Some(incoming), move |state| {
+        let sink = sink.clone();
+        async move {
+            let mut incoming = state?;
+            loop {
+                match incoming.frame().await? {
+                    Ok(frame) => match frame.into_data() {
+                        Ok(bytes) => return Some((Ok(bytes), Some(incoming))),
+                        Err(frame) => {
+                            if let Ok(t) = frame.into_trailers() {
+                                sink.set(t);
+                            }
+                        }
+                    },
+                    Err(e) => return Some((Err(BoxError::from(e)), None)),
+                }
+            }
+        }
+    });
+
+    parts.extensions = http::Extensions::new();
+    parts.extensions.insert(ErrorSource::Upstream);
+    parts.extensions.insert(trailers);
+    http::Response::from_parts(parts, Body::Stream(Box::pin(data)))
+}
+
+/// Accepts any upstream certificate. Used only when upstream TLS verification
+/// is disabled for tests.
+#[derive(Debug)]
+struct NoVerification(WebPkiSupportedAlgorithms);
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(message, cert, dss, &self.0)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(message, cert, dss, &self.0)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_grpc_content_types() {
+        let mut headers = HeaderMap::new();
+        assert!(!is_grpc_request(&headers));
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/grpc+proto"),
+        );
+        assert!(is_grpc_request(&headers));
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        assert!(!is_grpc_request(&headers));
+    }
+
+    #[test]
+    fn parses_service_and_method() {
+        assert_eq!(
+            parse_path("/helloworld.Greeter/SayHello"),
+            Some(("helloworld.Greeter", "SayHello"))
+        );
+        assert_eq!(parse_path("/helloworld.Greeter"), None);
+        assert_eq!(parse_path("/helloworld.Greeter/"), None);
+        assert_eq!(parse_path("//SayHello"), None);
+        assert_eq!(parse_path("/a/b/c"), None);
+    }
+
+    #[test]
+    fn parses_grpc_timeout() {
+        assert_eq!(parse_timeout("2S"), Some(Duration::from_secs(2)));
+        assert_eq!(parse_timeout("150m"), Some(Duration::from_millis(150)));
+        assert_eq!(parse_timeout("1H"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_timeout("5n"), Some(Duration::from_nanos(5)));
+        assert_eq!(parse_timeout("S"), None);
+        assert_eq!(parse_timeout("123456789S"), None);
+        assert_eq!(parse_timeout("10x"), None);
+        assert_eq!(parse_timeout("-1S"), None);
+    }
+
+    #[test]
+    fn restores_protocol_headers() {
+        let mut inbound = HeaderMap::new();
+        inbound.insert("grpc-timeout", HeaderValue::from_static("1S"));
+        inbound.insert("grpc-encoding", HeaderValue::from_static("gzip"));
+        let mut outbound = HeaderMap::new();
+        outbound.insert(http::header::HOST, HeaderValue::from_static("example.com"));
+
+        prepare_request_headers(&mut outbound, &inbound);
+        assert_eq!(outbound["te"], "trailers");
+        assert_eq!(outbound["grpc-timeout"], "1S");
+        assert_eq!(outbound["grpc-encoding"], "gzip");
+        assert!(!outbound.contains_key(http::header::HOST));
+    }
+
+    #[test]
+    fn maps_statuses_onto_gateway_errors() {
+        let error = |code: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(H_GRPC_STATUS, HeaderValue::from_static(code));
+            headers.insert(H_GRPC_MESSAGE, HeaderValue::from_static("nope"));
+            status_error(&headers, "/svc")
+        };
+        assert!(matches!(
+            error("16"),
+            Some(DomainError::AuthenticationFailed { ref detail, .. }) if detail.ends_with("UNAUTHENTICATED: nope")
+        ));
+        assert!(matches!(
+            error("7"),
+            Some(DomainError::DownstreamError { ref detail, ref instance })
+                if detail.ends_with("PERMISSION_DENIED: nope") && instance == "/svc"
+        ));
+        assert!(matches!(
+            error("8"),
+            Some(DomainError::RateLimitExceeded { .. })
+        ));
+        assert!(matches!(
+            error("14"),
+            Some(DomainError::DownstreamError { .. })
+        ));
+        assert!(matches!(
+            error("4"),
+            Some(DomainError::RequestTimeout { .. })
+        ));
+        // OK and application statuses pass through.
+        assert!(error("0").is_none());
+        assert!(error("5").is_none());
+        assert!(status_error(&HeaderMap::new(), "/svc").is_none());
+    }
+}
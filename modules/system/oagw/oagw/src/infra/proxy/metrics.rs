@@ -0,0 +1,232 @@
+//! Data-plane metrics (ADR 0012 backpressure and queueing, ADR 0005 circuit
+//! breaker, response cache).
+//!
+//! Instruments are created from the global OpenTelemetry meter provider that
+//! modkit installs at startup; without one they are no-ops.
+
+use opentelemetry::KeyValue;
+use opentelemetry::global;
+use opentelemetry::metrics::{Counter, Gauge, Histogram};
+
+use crate::domain::circuit_breaker::{CircuitPermit, CircuitState, Transition};
+use crate::domain::error::DomainError;
+use crate::domain::model::{CircuitBreakerScope, Endpoint, RateLimitStrategy, Upstream};
+use crate::domain::rate_limit::RateLimitDecision;
+
+pub(crate) struct ProxyMetrics {
+    /// `oagw_backpressure_total{host, strategy, reason}`
+    backpressure: Counter<u64>,
+    /// `oagw_queue_wait_duration_seconds{host}`
+    queue_wait: Histogram<f64>,
+    /// `oagw_queue_rejected_total{host, reason}`
+    queue_rejected: Counter<u64>,
+    /// `oagw_circuit_breaker_state{upstream_id, tenant_id, endpoint}`:
+    /// 0 = closed, 1 = half-open, 2 = open.
+    circuit_state: Gauge<u64>,
+    /// `oagw_circuit_breaker_state_changes_total{upstream_id, tenant_id, endpoint, from_state, to_state}`
+    circuit_state_changes: Counter<u64>,
+    /// `oagw_circuit_breaker_rejected_requests_total{upstream_id, tenant_id, endpoint}`
+    circuit_rejected: Counter<u64>,
+    /// `oagw_circuit_breaker_failures_total{upstream_id, tenant_id, endpoint}`
+    circuit_failures: Counter<u64>,
+    /// `oagw_circuit_breaker_half_open_successes_total{upstream_id, tenant_id, endpoint}`
+    circuit_probe_successes: Counter<u64>,
+    /// `oagw_circuit_breaker_half_open_failures_total{upstream_id, tenant_id, endpoint}`
+    circuit_probe_failures: Counter<u64>,
+    /// `oagw_cache_requests_total{host, outcome}`
+    cache_requests: Counter<u64>,
+}
+
+impl ProxyMetrics {
+    pub(crate) fn new() -> Self {
+        let meter = global::meter("oagw");
+        Self {
+            backpressure: meter
+                .u64_counter("oagw_backpressure_total")
+                .with_description("Requests that hit a limit, by backpressure strategy")
+                .build(),
+            queue_wait: meter
+                .f64_histogram("oagw_queue_wait_duration_seconds")
+                .with_description("Time requests spent queued for rate-limit capacity")
+                .with_unit("s")
+                .build(),
+            queue_rejected: meter
+                .u64_counter("oagw_queue_rejected_total")
+                .with_description("Queued requests rejected, by reason")
+                .build(),
+            circuit_state: meter
+                .u64_gauge("oagw_circuit_breaker_state")
+                .with_description("Circuit breaker state: 0 = closed, 1 = half-open, 2 = open")
+                .build(),
+            circuit_state_changes: meter
+                .u64_counter("oagw_circuit_breaker_state_changes_total")
+                .with_description("Circuit breaker state transitions")
+                .build(),
+            circuit_rejected: meter
+                .u64_counter("oagw_circuit_breaker_rejected_requests_total")
+                .with_description("Requests rejected by an open circuit breaker")
+                .build(),
+            circuit_failures: meter
+                .u64_counter("oagw_circuit_breaker_failures_total")
+                .with_description("Upstream calls counted as circuit breaker failures")
+                .build(),
+            circuit_probe_successes: meter
+                .u64_counter("oagw_circuit_breaker_half_open_successes_total")
+                .with_description("Successful half-open probe calls")
+                .build(),
+            circuit_probe_failures: meter
+                .u64_counter("oagw_circuit_breaker_half_open_failures_total")
+                .with_description("Failed half-open probe calls")
+                .build(),
+            cache_requests: meter
+                .u64_counter("oagw_cache_requests_total")
+                .with_description("Requests on cache-enabled routes, by hit, revalidated or miss")
+                .build(),
+        }
+    }
+
+    /// Record the outcome of a rate-limit admission for `host`.
+    ///
+    /// Requests admitted without touching the limit are not recorded.
+    pub(crate) fn record_rate_limit(
+        &self,
+        host: &str,
+        strategy: RateLimitStrategy,
+        outcome: &Result<RateLimitDecision, DomainError>,
+    ) {
+        let limited = match outcome {
+            Ok(RateLimitDecision::Allowed) => false,
+            Ok(RateLimitDecision::Queued { waited }) => {
+                self.queue_wait.record(
+                    waited.as_secs_f64(),
+                    &[KeyValue::new("host", host.to_string())],
+                );
+                true
+            }
+            Ok(RateLimitDecision::Degraded) | Err(DomainError::RateLimitExceeded { .. }) => true,
+            Err(DomainError::QueueFull { .. }) => {
+                self.record_queue_rejected(host, "queue_full");
+                true
+            }
+            Err(DomainError::QueueTimeout { .. }) => {
+                self.record_queue_rejected(host, "timeout");
+                true
+            }
+            Err(_) => false,
+        };
+        if limited {
+            self.backpressure.add(
+                1,
+                &[
+                    KeyValue::new("host", host.to_string()),
+                    KeyValue::new("strategy", strategy_label(strategy)),
+                    KeyValue::new("reason", "rate_limit"),
+                ],
+            );
+        }
+    }
+
+    /// Record a circuit breaker admission: a rejection, or the open →
+    /// half-open transition that admitted a probe.
+    pub(crate) fn record_circuit_admission(
+        &self,
+        labels: &[KeyValue],
+        outcome: &Result<CircuitPermit, DomainError>,
+    ) {
+        match outcome {
+            Ok(permit) => {
+                if let Some(transition) = permit.transition() {
+                    self.record_circuit_transition(labels, transition);
+                }
+            }
+            Err(_) => self.circuit_rejected.add(1, labels),
+        }
+    }
+
+    /// Record the outcome of a call admitted by the circuit breaker.
+    pub(crate) fn record_circuit_outcome(
+        &self,
+        labels: &[KeyValue],
+        probe: bool,
+        failed: bool,
+        transition: Option<Transition>,
+    ) {
+        if failed {
+            self.circuit_failures.add(1, labels);
+        }
+        if probe {
+            if failed {
+                self.circuit_probe_failures.add(1, labels);
+            } else {
+                self.circuit_probe_successes.add(1, labels);
+            }
+        }
+        if let Some(transition) = transition {
+            self.record_circuit_transition(labels, transition);
+        }
+    }
+
+    /// Record a request on a cache-enabled route for `host`.
+    pub(crate) fn record_cache(&self, host: &str, outcome: &'static str) {
+        self.cache_requests.add(
+            1,
+            &[
+                KeyValue::new("host", host.to_string()),
+                KeyValue::new("outcome", outcome),
+            ],
+        );
+    }
+
+    fn record_circuit_transition(&self, labels: &[KeyValue], transition: Transition) {
+        self.circuit_state
+            .record(state_value(transition.to), labels);
+        let mut change = labels.to_vec();
+        change.push(KeyValue::new("from_state", transition.from.as_str()));
+        change.push(KeyValue::new("to_state", transition.to.as_str()));
+        self.circuit_state_changes.add(1, &change);
+    }
+
+    fn record_queue_rejected(&self, host: &str, reason: &'static str) {
+        self.queue_rejected.add(
+            1,
+            &[
+                KeyValue::new("host", host.to_string()),
+                KeyValue::new("reason", reason),
+            ],
+        );
+    }
+}
+
+fn strategy_label(strategy: RateLimitStrategy) -> &'static str {
+    match strategy {
+        RateLimitStrategy::Reject => "reject",
+        RateLimitStrategy::Queue => "queue",
+        RateLimitStrategy::Degrade => "degrade",
+    }
+}
+
+fn state_value(state: CircuitState) -> u64 {
+    match state {
+        CircuitState::Closed => 0,
+        CircuitState::HalfOpen => 1,
+        CircuitState::Open => 2,
+    }
+}
+
+/// Labels identifying a circuit: `endpoint` is `host:port` for per-endpoint
+/// circuits and `*` when the circuit covers the whole upstream.
+pub(crate) fn circuit_labels(
+    upstream: &Upstream,
+    endpoint: &Endpoint,
+    scope: CircuitBreakerScope,
+) -> Vec<KeyValue> {
+    let endpoint = match scope {
+        CircuitBreakerScope::Global => "*".to_string(),
+        CircuitBreakerScope::PerEndpoint => format!("{}:{}", endpoint.host, endpoint.port),
+    };
+    vec![
+        KeyValue::new("upstream_id", upstream.id.to_string()),
+        KeyValue::new("tenant_id", upstream.tenant_id.to_string()),
+        KeyValue::new("endpoint", endpoint),
+    ]
+}
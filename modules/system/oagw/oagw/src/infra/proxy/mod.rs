@@ -1,6 +1,9 @@
 use authz_resolver_sdk::pep::ResourceType;
 
+pub(crate) mod cache;
+pub(crate) mod grpc;
 pub(crate) mod headers;
+pub(crate) mod metrics;
 pub(crate) mod pingora_proxy;
 pub(crate) mod request_builder;
 pub(crate) mod service;
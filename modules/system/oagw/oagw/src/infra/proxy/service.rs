@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use authz_resolver_sdk::PolicyEnforcer;
@@ -10,21 +10,35 @@ use credstore_sdk::CredStoreClientV1;
 use futures_util::StreamExt;
 use http::{HeaderMap, HeaderName, HeaderValue};
 use modkit_security::SecurityContext;
+use oagw_sdk::api::ErrorSource;
 use oagw_sdk::body::{Body, BodyStream};
 use pingora_core::apps::HttpServerApp;
 use pingora_proxy::HttpProxy;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::watch;
+use uuid::Uuid;
 
+use crate::domain::circuit_breaker::{CallOutcome, CircuitBreaker};
 use crate::domain::error::DomainError;
-use crate::domain::model::{Endpoint, PassthroughMode, PathSuffixMode, Scheme, Upstream};
-use crate::domain::plugin::AuthContext;
-use crate::domain::rate_limit::RateLimiter;
-use crate::domain::services::{ControlPlaneService, DataPlaneService, EndpointSelector};
-use crate::infra::plugin::AuthPluginRegistry;
+use crate::domain::gts_helpers::{GUARD_PLUGIN_SCHEMA, TRANSFORM_PLUGIN_SCHEMA};
+use crate::domain::model::{
+    CacheConfig, Endpoint, PassthroughMode, PathSuffixMode, Route, Scheme, Upstream,
+};
+use crate::domain::plugin::{
+    AuthContext, ErrorContext, GuardDecision, GuardPlugin, PluginError, PluginLayer,
+    RequestContext, ResponseContext, TransformPlugin, build_plugin_chain,
+};
+use crate::domain::rate_limit::{RateLimitDecision, RateLimiter};
+use crate::domain::services::{
+    ConfigChangeListener, ControlPlaneService, DataPlaneService, EndpointSelector,
+};
+use crate::infra::plugin::{AuthPluginRegistry, GuardPluginRegistry, TransformPluginRegistry};
 use crate::infra::proxy::{actions, resources};
 
+use super::cache::{self, CachedResponse, Lookup, RequestPolicy, ResponseCache};
+use super::grpc::{self, GrpcTransport};
 use super::headers;
+use super::metrics::{ProxyMetrics, circuit_labels};
 use super::pingora_proxy::{
     H_ENDPOINT_HOST, H_ENDPOINT_PORT, H_ENDPOINT_SCHEME, H_INSTANCE_URI, H_UPSTREAM_ID,
     PingoraProxy,
@@ -34,6 +48,24 @@ use super::{request_builder, session_bridge};
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 /// Default maximum request body size: 100 MB.
 const MAX_BODY_SIZE: usize = 100 * 1024 * 1024;
+/// Set on responses to requests admitted over the limit by `strategy: degrade`.
+const H_RATE_LIMIT_DEGRADED: &str = "x-oagw-rate-limit-degraded";
+
+/// Plugin configuration key/value pairs.
+type PluginConfig = HashMap<String, String>;
+
+/// Guard and transform plugins resolved for one request, each with its config.
+#[derive(Default)]
+struct PluginPipeline {
+    guards: Vec<(Arc<dyn GuardPlugin>, PluginConfig)>,
+    transforms: Vec<(Arc<dyn TransformPlugin>, PluginConfig)>,
+}
+
+impl PluginPipeline {
+    fn is_empty(&self) -> bool {
+        self.guards.is_empty() && self.transforms.is_empty()
+    }
+}
 
 /// Data Plane service implementation: proxy orchestration and plugin execution.
 pub struct DataPlaneServiceImpl {
@@ -44,7 +76,14 @@ pub struct DataPlaneServiceImpl {
     _shutdown_tx: watch::Sender<bool>,
     shutdown_rx: watch::Receiver<bool>,
     auth_registry: AuthPluginRegistry,
+    guard_registry: GuardPluginRegistry,
+    transform_registry: TransformPluginRegistry,
     rate_limiter: RateLimiter,
+    circuit_breaker: CircuitBreaker,
+    response_cache: ResponseCache,
+    /// HTTP/2 client for gRPC upstreams; gRPC calls are rejected without one.
+    grpc: Option<GrpcTransport>,
+    metrics: ProxyMetrics,
     request_timeout: Duration,
     /// Enforces authorization policy before proxying each request.
     policy_enforcer: PolicyEnforcer,
@@ -73,7 +112,13 @@ impl DataPlaneServiceImpl {
             _shutdown_tx: shutdown_tx,
             shutdown_rx,
             auth_registry,
+            guard_registry: GuardPluginRegistry::with_builtins(),
+            transform_registry: TransformPluginRegistry::with_builtins(),
             rate_limiter,
+            circuit_breaker: CircuitBreaker::new(),
+            response_cache: ResponseCache::new(cache::DEFAULT_CAPACITY_BYTES),
+            grpc: None,
+            metrics: ProxyMetrics::new(),
             request_timeout: REQUEST_TIMEOUT,
             policy_enforcer,
             allow_http_upstream: false,
@@ -95,6 +140,20 @@ impl DataPlaneServiceImpl {
         self
     }
 
+    /// Override the total size bound of the response cache.
+    #[must_use]
+    pub fn with_response_cache_capacity(mut self, bytes: usize) -> Self {
+        self.response_cache = ResponseCache::new(bytes);
+        self
+    }
+
+    /// Enable proxying of gRPC calls to upstreams with `grpc` endpoints.
+    #[must_use]
+    pub fn with_grpc_transport(mut self, transport: GrpcTransport) -> Self {
+        self.grpc = Some(transport);
+        self
+    }
+
     /// Allow HTTP (non-TLS) upstream connections.
     #[must_use]
     pub fn with_allow_http_upstream(mut self, allow: bool) -> Self {
@@ -102,87 +161,62 @@ impl DataPlaneServiceImpl {
         self
     }
 
-    /// Two-tier endpoint selection (D1):
-    /// 1. `X-OAGW-Target-Host` header → validate against endpoint list
-    /// 2. Round-robin via `BackendSelector` for multi-endpoint, direct for single
-    async fn select_endpoint(
+    /// Compose the guard/transform pipeline for a request: inherited ancestor
+    /// layers first, then the upstream's plugins, then the route's.
+    async fn build_pipeline(
         &self,
+        ctx: &SecurityContext,
         upstream: &Upstream,
-        req_headers: &http::HeaderMap,
-        instance_uri: &str,
-    ) -> Result<Endpoint, DomainError> {
-        let endpoints = &upstream.server.endpoints;
-
-        if endpoints.is_empty() {
-            return Err(DomainError::DownstreamError {
-                detail: "upstream has no endpoints".into(),
-                instance: instance_uri.to_string(),
+        route: &Route,
+    ) -> Result<PluginPipeline, DomainError> {
+        let ancestors = self
+            .cp
+            .resolve_ancestor_upstreams(ctx, &upstream.alias)
+            .await?;
+        let inherited = ancestors.iter().filter_map(|u| {
+            u.plugins.as_ref().map(|plugins| PluginLayer {
+                inherited: true,
+                plugins,
+            })
+        });
+        let own = [upstream.plugins.as_ref(), route.plugins.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(|plugins| PluginLayer {
+                inherited: false,
+                plugins,
             });
+        let layers: Vec<PluginLayer<'_>> = inherited.chain(own).collect();
+
+        let mut pipeline = PluginPipeline::default();
+        for binding in build_plugin_chain(&layers) {
+            let resolved = if binding.plugin_ref.starts_with(GUARD_PLUGIN_SCHEMA) {
+                self.guard_registry
+                    .resolve(&binding.plugin_ref)
+                    .map(|p| pipeline.guards.push((p, binding.config)))
+            } else if binding.plugin_ref.starts_with(TRANSFORM_PLUGIN_SCHEMA) {
+                self.transform_registry
+                    .resolve(&binding.plugin_ref)
+                    .map(|p| pipeline.transforms.push((p, binding.config)))
+            } else {
+                Err(PluginError::Internal(format!(
+                    "'{}' is not a guard or transform plugin",
+                    binding.plugin_ref
+                )))
+            };
+            resolved.map_err(|e| DomainError::internal(e.to_string()))?;
         }
-
-        // Tier 1: Explicit selection via X-OAGW-Target-Host header.
-        if let Some(target_host) = req_headers
-            .get("x-oagw-target-host")
-            .and_then(|v| v.to_str().ok())
-        {
-            // Validate format: allowlist of safe hostname/IP characters.
-            // Rejects null bytes, @, \, Unicode homoglyphs, and port/path syntax.
-            if target_host.is_empty()
-                || !target_host
-                    .bytes()
-                    .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_'))
-            {
-                return Err(DomainError::InvalidTargetHost {
-                    instance: instance_uri.to_string(),
-                });
-            }
-
-            // Find matching endpoint by host.
-            return endpoints
-                .iter()
-                .find(|ep| ep.host.eq_ignore_ascii_case(target_host))
-                .cloned()
-                .ok_or_else(|| {
-                    let valid_hosts: Vec<&str> =
-                        endpoints.iter().map(|ep| ep.host.as_str()).collect();
-                    tracing::warn!(
-                        target_host,
-                        ?valid_hosts,
-                        "X-OAGW-Target-Host does not match any configured endpoint"
-                    );
-                    DomainError::UnknownTargetHost {
-                        detail: format!(
-                            "X-OAGW-Target-Host '{}' does not match any configured endpoint",
-                            target_host
-                        ),
-                        instance: instance_uri.to_string(),
-                    }
-                });
-        }
-
-        // Tier 2: Automatic selection.
-        if endpoints.len() == 1 {
-            // Single-endpoint: use directly, no LB overhead.
-            return Ok(endpoints[0].clone());
-        }
-
-        // Multi-endpoint: round-robin via BackendSelector.
-        self.backend_selector
-            .select(upstream.id, endpoints)
-            .await
-            .ok_or_else(|| DomainError::DownstreamError {
-                detail: "all backends are unhealthy".into(),
-                instance: instance_uri.to_string(),
-            })
+        Ok(pipeline)
     }
-}
 
-#[async_trait]
-impl DataPlaneService for DataPlaneServiceImpl {
-    async fn proxy_request(
+    /// Proxy one request. Once the plugin pipeline is known it is stored in
+    /// `run` so the caller can run its response and error phases.
+    async fn forward(
         &self,
         ctx: SecurityContext,
         req: http::Request<Body>,
+        started_at: Instant,
+        run: &mut Option<PipelineRun>,
     ) -> Result<http::Response<Body>, DomainError> {
         let instance_uri = req.uri().to_string();
 
@@ -226,6 +260,7 @@ impl DataPlaneService for DataPlaneServiceImpl {
         let (parts, body) = req.into_parts();
         let method = parts.method;
         let req_headers = parts.headers;
+        let is_grpc = grpc::is_grpc_request(&req_headers);
 
         // Reject WebSocket upgrade requests — the current bridge is unidirectional
         // and cannot support the bidirectional tunnel that WebSocket requires.
@@ -265,11 +300,51 @@ impl DataPlaneService for DataPlaneServiceImpl {
         // 1. Resolve upstream by alias.
         let upstream = self.cp.resolve_upstream(&ctx, &alias).await?;
 
-        // 2. Resolve route.
-        let route = self
-            .cp
-            .resolve_route(&ctx, upstream.id, method.as_ref(), &path_suffix)
-            .await?;
+        // 2. Resolve route. gRPC calls match on `/{service}/{method}`. A CORS
+        //    preflight to a route that does not accept OPTIONS is matched
+        //    against the method it asks about; it must then be answered by a
+        //    guard plugin and is never forwarded.
+        let mut unanswered_preflight = None;
+        let route = if is_grpc {
+            let target = grpc::parse_path(&path_suffix).filter(|_| method == http::Method::POST);
+            let Some((service, grpc_method)) = target else {
+                return Err(DomainError::ProtocolError {
+                    detail: "gRPC calls must be POST requests to /{service}/{method}".into(),
+                    instance: instance_uri,
+                });
+            };
+            self.cp
+                .resolve_grpc_route(&ctx, upstream.id, service, grpc_method)
+                .await?
+        } else {
+            match self
+                .cp
+                .resolve_route(&ctx, upstream.id, method.as_ref(), &path_suffix)
+                .await
+            {
+                Ok(route) => route,
+                Err(e) => {
+                    let requested = req_headers
+                        .get("access-control-request-method")
+                        .and_then(|v| v.to_str().ok())
+                        .filter(|_| method == http::Method::OPTIONS);
+                    let Some(requested) = requested else {
+                        return Err(e);
+                    };
+                    match self
+                        .cp
+                        .resolve_route(&ctx, upstream.id, requested, &path_suffix)
+                        .await
+                    {
+                        Ok(route) => {
+                            unanswered_preflight = Some(e);
+                            route
+                        }
+                        Err(_) => return Err(e),
+                    }
+                }
+            }
+        };
 
         // 2b. Validate query parameters against route's allowlist.
         if let Some(ref http_match) = route.match_rules.http
@@ -305,6 +380,20 @@ impl DataPlaneService for DataPlaneServiceImpl {
             }
         }
 
+        // 2d. Resolve the guard/transform pipeline (ancestors, upstream, route).
+        let pipeline = self.build_pipeline(&ctx, &upstream, &route).await?;
+        if !pipeline.is_empty() {
+            *run = Some(PipelineRun {
+                pipeline,
+                upstream_id: upstream.id,
+                method: method.to_string(),
+                path: path_suffix.clone(),
+                instance_uri: instance_uri.clone(),
+                inbound_headers: header_strings(&req_headers),
+                request_headers: HashMap::new(),
+            });
+        }
+
         // 3. Prepare outbound headers (passthrough + strip).
         let mode = upstream
             .headers
@@ -320,7 +409,9 @@ impl DataPlaneService for DataPlaneServiceImpl {
         headers::strip_hop_by_hop(&mut outbound_headers);
         headers::strip_internal_headers(&mut outbound_headers);
 
-        // 4. Execute auth plugin.
+        // 4. Execute auth plugin. The plugin and its context are kept so a 401
+        //    from the upstream can be reported back to it.
+        let mut auth_state = None;
         if let Some(ref auth) = upstream.auth {
             let plugin = self.auth_registry.resolve(&auth.plugin_type).map_err(|e| {
                 DomainError::AuthenticationFailed {
@@ -328,16 +419,9 @@ impl DataPlaneService for DataPlaneServiceImpl {
                     instance: instance_uri.clone(),
                 }
             })?;
-            let auth_headers: HashMap<String, String> = outbound_headers
-                .iter()
-                .filter_map(|(k, v)| {
-                    v.to_str()
-                        .ok()
-                        .map(|s| (k.as_str().to_string(), s.to_string()))
-                })
-                .collect();
             let mut auth_ctx = AuthContext {
-                headers: auth_headers,
+                upstream_id: upstream.id,
+                headers: header_strings(&outbound_headers),
                 config: auth.config.clone().unwrap_or_default(),
                 security_context: ctx.clone(),
             };
@@ -366,14 +450,8 @@ impl DataPlaneService for DataPlaneServiceImpl {
                     }
                 })?;
             outbound_headers = HeaderMap::new();
-            for (k, v) in &auth_ctx.headers {
-                if let (Ok(name), Ok(val)) = (
-                    HeaderName::from_bytes(k.as_bytes()),
-                    HeaderValue::from_str(v),
-                ) {
-                    outbound_headers.insert(name, val);
-                }
-            }
+            insert_headers(&mut outbound_headers, &auth_ctx.headers);
+            auth_state = Some((plugin, auth_ctx));
         }
 
         // 5. Apply header rules + set Host.
@@ -383,12 +461,104 @@ impl DataPlaneService for DataPlaneServiceImpl {
             headers::apply_header_rules(&mut outbound_headers, rules);
         }
 
-        // 5a. Endpoint selection (D1 — two-tier).
+        // 5a. Run guard plugins, then request transforms. Guards may reject
+        //     the request, answer it locally, or shorten the upstream timeout.
+        let mut deadline = None;
+        if let Some(run) = run.as_mut() {
+            let mut req_ctx = RequestContext {
+                upstream_id: upstream.id,
+                route_id: route.id,
+                method: run.method.clone(),
+                path: run.path.clone(),
+                inbound_headers: run.inbound_headers.clone(),
+                headers: header_strings(&outbound_headers),
+                config: HashMap::new(),
+                security_context: ctx.clone(),
+                started_at,
+                timeout: self.request_timeout,
+            };
+            for (guard, config) in &run.pipeline.guards {
+                req_ctx.config.clone_from(config);
+                let decision = guard
+                    .guard_request(&mut req_ctx)
+                    .await
+                    .map_err(|e| plugin_error(e, &instance_uri))?;
+                match decision {
+                    GuardDecision::Allow => {}
+                    GuardDecision::Reject { status, detail } => {
+                        return Err(guard_rejection(status, detail, instance_uri));
+                    }
+                    GuardDecision::Respond { status, headers } => {
+                        return local_response(status, &headers, instance_uri);
+                    }
+                }
+            }
+            if let Some(e) = unanswered_preflight.take() {
+                return Err(e);
+            }
+            for (transform, config) in &run.pipeline.transforms {
+                req_ctx.config.clone_from(config);
+                transform
+                    .transform_request(&mut req_ctx)
+                    .await
+                    .map_err(|e| plugin_error(e, &instance_uri))?;
+            }
+            outbound_headers = HeaderMap::new();
+            insert_headers(&mut outbound_headers, &req_ctx.headers);
+            headers::strip_internal_headers(&mut outbound_headers);
+            run.request_headers = req_ctx.headers;
+            deadline = Some(Instant::now() + req_ctx.timeout);
+        }
+        if let Some(e) = unanswered_preflight {
+            return Err(e);
+        }
+
+        // 5b. Response cache. A fresh entry is served without touching the
+        //     upstream, its circuit or rate limits; a stale one is revalidated
+        //     with its ETag. Streamed request bodies and gRPC calls are never
+        //     cached.
+        let mut cache_call = None;
+        if let Some(config) = route.cache.as_ref().filter(|c| {
+            c.enabled && !is_grpc && body_stream.is_none() && cache::is_cacheable_method(c, &method)
+        }) {
+            let policy = cache::request_policy(&req_headers);
+            if policy != RequestPolicy::Bypass {
+                let credentials = cache::credentials_key(ctx.subject_id(), &req_headers);
+                let key = cache::cache_key(
+                    ctx.subject_tenant_id(),
+                    upstream.id,
+                    credentials.as_deref(),
+                    &method,
+                    &path_suffix,
+                    &query_params,
+                    &body_bytes,
+                );
+                let mut stale = None;
+                if policy == RequestPolicy::Use {
+                    match self.response_cache.lookup(&key, &req_headers) {
+                        Lookup::Fresh(resp) => {
+                            self.metrics.record_cache(&upstream.alias, "hit");
+                            return Ok(resp);
+                        }
+                        Lookup::Stale(cached) => {
+                            if let Some(etag) = cached.etag() {
+                                outbound_headers.insert(http::header::IF_NONE_MATCH, etag.clone());
+                            }
+                            stale = Some(cached);
+                        }
+                        Lookup::Miss => {}
+                    }
+                }
+                cache_call = Some((config, key, stale));
+            }
+        }
+
+        // 5c. Endpoint selection (D1 — two-tier).
         let endpoint = self
             .select_endpoint(&upstream, &req_headers, &instance_uri)
             .await?;
 
-        // 5b. Enforce HTTPS-only constraint (cpt-cf-oagw-constraint-https-only).
+        // 5d. Enforce HTTPS-only constraint (cpt-cf-oagw-constraint-https-only).
         if !self.allow_http_upstream && matches!(endpoint.scheme, Scheme::Http) {
             return Err(DomainError::Validation {
                 detail: "upstream endpoint uses HTTP; only HTTPS endpoints are permitted".into(),
@@ -396,202 +566,634 @@ impl DataPlaneService for DataPlaneServiceImpl {
             });
         }
 
-        headers::set_host_header(&mut outbound_headers, &endpoint.host, endpoint.port);
+        // 5d'. gRPC calls need a `grpc` endpoint and go through the native
+        //      HTTP/2 transport instead of the Pingora bridge.
+        let grpc_transport = if is_grpc {
+            if !matches!(endpoint.scheme, Scheme::Grpc) {
+                return Err(DomainError::ProtocolError {
+                    detail: "gRPC call to an upstream endpoint without the grpc scheme".into(),
+                    instance: instance_uri,
+                });
+            }
+            let transport = self
+                .grpc
+                .as_ref()
+                .ok_or_else(|| DomainError::ProtocolError {
+                    detail: "gRPC proxying is not enabled".into(),
+                    instance: instance_uri.clone(),
+                })?;
+            Some(transport)
+        } else {
+            None
+        };
 
-        // 6. Check rate limit (upstream then route).
-        if let Some(ref rl) = upstream.rate_limit {
-            let key = format!("upstream:{}", upstream.id);
-            self.rate_limiter.try_consume(&key, rl, &instance_uri)?;
-        }
-        if let Some(ref rl) = route.rate_limit {
-            let key = format!("route:{}", route.id);
-            self.rate_limiter.try_consume(&key, rl, &instance_uri)?;
-        }
+        headers::set_host_header(&mut outbound_headers, &endpoint.host, endpoint.port);
 
-        // 7. Build URL.
-        // path_suffix is the full path from the proxy URL; strip the route prefix
-        // so we get: endpoint + route_path + remaining_suffix.
-        let route_path = route
-            .match_rules
-            .http
-            .as_ref()
-            .map_or("/", |h| h.path.as_str());
-        let remaining_suffix = path_suffix.strip_prefix(route_path).unwrap_or("");
-        let url = request_builder::build_upstream_url(
-            &endpoint,
-            route_path,
-            remaining_suffix,
-            &query_params,
-        )?;
-
-        // 7b. Inject internal context headers for PingoraProxy (D9).
-        let scheme_str = match endpoint.scheme {
-            Scheme::Http => "http",
-            Scheme::Https => "https",
-            Scheme::Wss => "wss",
-            Scheme::Wt => "wt",
-            Scheme::Grpc => "grpc",
+        // 5e. Circuit breaker: fail fast while the circuit is open. Checked
+        //     before the rate limit so rejected calls don't consume tokens.
+        let circuit = match upstream.circuit_breaker.as_ref().filter(|cb| cb.enabled) {
+            Some(cb) => {
+                let key = CircuitBreaker::key(upstream.id, &endpoint, cb.scope);
+                let labels = circuit_labels(&upstream, &endpoint, cb.scope);
+                let permit = self.circuit_breaker.try_acquire(&key, cb, &instance_uri);
+                self.metrics.record_circuit_admission(&labels, &permit);
+                Some((permit?, cb, labels))
+            }
+            None => None,
         };
-        if let Ok(v) = HeaderValue::from_str(&upstream.id.to_string()) {
-            outbound_headers.insert(H_UPSTREAM_ID, v);
-        }
-        if let Ok(v) = HeaderValue::from_str(&endpoint.host) {
-            outbound_headers.insert(H_ENDPOINT_HOST, v);
-        }
-        if let Ok(v) = HeaderValue::from_str(&endpoint.port.to_string()) {
-            outbound_headers.insert(H_ENDPOINT_PORT, v);
-        }
-        outbound_headers.insert(H_ENDPOINT_SCHEME, HeaderValue::from_static(scheme_str));
-        if let Ok(v) = HeaderValue::from_str(&instance_uri) {
-            outbound_headers.insert(H_INSTANCE_URI, v);
+
+        // 6. Check rate limit (upstream then route). Depending on the strategy
+        //    this may wait in the key's queue or let the request through degraded.
+        let mut rate_limit_degraded = false;
+        let limits = [
+            (
+                format!("upstream:{}", upstream.id),
+                upstream.rate_limit.as_ref(),
+            ),
+            (format!("route:{}", route.id), route.rate_limit.as_ref()),
+        ];
+        for (key, rl) in limits {
+            let Some(rl) = rl else { continue };
+            let outcome = self.rate_limiter.acquire(&key, rl, &instance_uri).await;
+            self.metrics
+                .record_rate_limit(&upstream.alias, rl.strategy, &outcome);
+            rate_limit_degraded |= outcome? == RateLimitDecision::Degraded;
         }
 
-        // 8. Bridge request into Pingora via in-memory DuplexStream.
-        let (client_io, server_io) = tokio::io::duplex(65_536);
+        let result = if let Some(transport) = grpc_transport {
+            // 7-9 (gRPC). Call the upstream over HTTP/2. The caller's
+            //     `grpc-timeout` also bounds the wait for response headers.
+            grpc::prepare_request_headers(&mut outbound_headers, &req_headers);
+            let mut timeout = deadline.map_or(self.request_timeout, |d: Instant| {
+                d.saturating_duration_since(Instant::now())
+            });
+            if let Some(grpc_timeout) = grpc::request_deadline(&req_headers) {
+                timeout = timeout.min(grpc_timeout);
+            }
+            let body = match body_stream {
+                Some(stream) => Body::Stream(stream),
+                None => Body::from(body_bytes),
+            };
+            let call = transport.send(
+                &endpoint,
+                &path_suffix,
+                outbound_headers,
+                body,
+                max_body,
+                &instance_uri,
+            );
+            tokio::time::timeout(timeout, call)
+                .await
+                .map_err(|_| DomainError::RequestTimeout {
+                    detail: format!("gRPC call {path_suffix} timed out after {timeout:?}"),
+                    instance: instance_uri.clone(),
+                })
+                .flatten()
+                .and_then(
+                    |resp| match grpc::status_error(resp.headers(), &instance_uri) {
+                        Some(e) => Err(e),
+                        None => Ok(resp),
+                    },
+                )
+        } else {
+            // 7. Build URL.
+            // path_suffix is the full path from the proxy URL; strip the route prefix
+            // so we get: endpoint + route_path + remaining_suffix.
+            let route_path = route
+                .match_rules
+                .http
+                .as_ref()
+                .map_or("/", |h| h.path.as_str());
+            let remaining_suffix = path_suffix.strip_prefix(route_path).unwrap_or("");
+            let url = request_builder::build_upstream_url(
+                &endpoint,
+                route_path,
+                remaining_suffix,
+                &query_params,
+            )?;
+
+            // 7b. Inject internal context headers for PingoraProxy (D9).
+            let scheme_str = match endpoint.scheme {
+                Scheme::Http => "http",
+                Scheme::Https => "https",
+                Scheme::Wss => "wss",
+                Scheme::Wt => "wt",
+                Scheme::Grpc => "grpc",
+            };
+            if let Ok(v) = HeaderValue::from_str(&upstream.id.to_string()) {
+                outbound_headers.insert(H_UPSTREAM_ID, v);
+            }
+            if let Ok(v) = HeaderValue::from_str(&endpoint.host) {
+                outbound_headers.insert(H_ENDPOINT_HOST, v);
+            }
+            if let Ok(v) = HeaderValue::from_str(&endpoint.port.to_string()) {
+                outbound_headers.insert(H_ENDPOINT_PORT, v);
+            }
+            outbound_headers.insert(H_ENDPOINT_SCHEME, HeaderValue::from_static(scheme_str));
+            if let Ok(v) = HeaderValue::from_str(&instance_uri) {
+                outbound_headers.insert(H_INSTANCE_URI, v);
+            }
 
-        // Create Pingora H1 session from the server side of the DuplexStream.
-        // Pingora implements all IO traits for DuplexStream (in ext_io_impl).
-        let session = pingora_core::protocols::http::ServerSession::new_http1(Box::new(server_io));
+            // 8/9. Call the upstream.
+            async {
+                // 8. Bridge request into Pingora via in-memory DuplexStream.
+                let (client_io, server_io) = tokio::io::duplex(65_536);
 
-        // Spawn Pingora proxy processing in background.
-        let proxy = self.proxy.clone();
-        let shutdown = self.shutdown_rx.clone();
-        tokio::spawn(async move {
-            proxy.process_new_http(session, &shutdown).await;
-        });
+                // Create Pingora H1 session from the server side of the DuplexStream.
+                // Pingora implements all IO traits for DuplexStream (in ext_io_impl).
+                let session = pingora_core::protocols::http::ServerSession::new_http1(Box::new(server_io));
+
+                // Spawn Pingora proxy processing in background.
+                let proxy = self.proxy.clone();
+                let shutdown = self.shutdown_rx.clone();
+                tokio::spawn(async move {
+                    proxy.process_new_http(session, &shutdown).await;
+                });
 
-        // Write the request and read the response from the client side.
-        let timeout = self.request_timeout;
+                // Write the request and read the response from the client side. A
+                // guard-imposed deadline also covers time spent in rate-limit queues.
+                let timeout = deadline.map_or(self.request_timeout, |d: Instant| {
+                    d.saturating_duration_since(Instant::now())
+                });
 
-        if let Some(mut body_stream) = body_stream {
-            // Streaming path: write headers, then forward body chunks concurrently.
-            let (client_read, mut client_write) = tokio::io::split(client_io);
+                if let Some(mut body_stream) = body_stream {
+                    // Streaming path: write headers, then forward body chunks concurrently.
+                    let (client_read, mut client_write) = tokio::io::split(client_io);
 
-            let header_bytes =
-                session_bridge::serialize_request_wire(&method, &url, &outbound_headers, None);
-            client_write.write_all(&header_bytes).await.map_err(|e| {
-                DomainError::DownstreamError {
-                    detail: format!("failed to write to proxy bridge: {e}"),
-                    instance: instance_uri.clone(),
-                }
-            })?;
+                    let header_bytes =
+                        session_bridge::serialize_request_wire(&method, &url, &outbound_headers, None);
+                    client_write.write_all(&header_bytes).await.map_err(|e| {
+                        DomainError::DownstreamError {
+                            detail: format!("failed to write to proxy bridge: {e}"),
+                            instance: instance_uri.clone(),
+                        }
+                    })?;
 
-            // Spawn task to forward body stream chunks, then shutdown.
-            // Enforce max_body_size on the streaming path: signal 413 if exceeded.
-            let (limit_tx, limit_rx) = tokio::sync::oneshot::channel::<usize>();
-            let body_instance_uri = instance_uri.clone();
-            tokio::spawn(async move {
-                let mut total_bytes: usize = 0;
-                let mut exceeded = false;
-                while let Some(chunk) = body_stream.next().await {
-                    match chunk {
-                        Ok(bytes) => {
-                            total_bytes = total_bytes.saturating_add(bytes.len());
-                            if total_bytes > max_body {
-                                tracing::warn!(
-                                    total_bytes,
-                                    max_body,
-                                    "streaming body exceeded max size, aborting"
-                                );
-                                exceeded = true;
-                                break;
-                            }
-                            if let Err(e) = client_write.write_all(&bytes).await {
-                                tracing::debug!(error = %e, "body stream write error");
-                                break;
+                    // Spawn task to forward body stream chunks, then shutdown.
+                    // Enforce max_body_size on the streaming path: signal 413 if exceeded.
+                    let (limit_tx, limit_rx) = tokio::sync::oneshot::channel::<usize>();
+                    let body_instance_uri = instance_uri.clone();
+                    tokio::spawn(async move {
+                        let mut total_bytes: usize = 0;
+                        let mut exceeded = false;
+                        while let Some(chunk) = body_stream.next().await {
+                            match chunk {
+                                Ok(bytes) => {
+                                    total_bytes = total_bytes.saturating_add(bytes.len());
+                                    if total_bytes > max_body {
+                                        tracing::warn!(
+                                            total_bytes,
+                                            max_body,
+                                            "streaming body exceeded max size, aborting"
+                                        );
+                                        exceeded = true;
+                                        break;
+                                    }
+                                    if let Err(e) = client_write.write_all(&bytes).await {
+                                        tracing::debug!(error = %e, "body stream write error");
+                                        break;
+                                    }
+                                }
+                                Err(e) => {
+                                    tracing::debug!(error = %e, "body stream chunk error");
+                                    break;
+                                }
                             }
                         }
-                        Err(e) => {
-                            tracing::debug!(error = %e, "body stream chunk error");
-                            break;
+                        if exceeded {
+                            let _ = limit_tx.send(total_bytes);
+                        }
+                        let _ = client_write.shutdown().await;
+                    });
+
+                    // 9. Parse response from the read half, but short-circuit to 413
+                    //    if the body-forwarding task signals a limit breach.
+                    //
+                    // TODO(hardening): a fast upstream can respond before the body-forwarder
+                    // detects the limit breach, causing the client to see 200 instead of 413.
+                    // Fix: wrap the write half in a LimitedAsyncWrite that returns io::Error
+                    // at the byte limit, so Pingora aborts the exchange before responding.
+                    let resp_future =
+                        tokio::time::timeout(timeout, session_bridge::parse_response_stream(client_read));
+                    tokio::select! {
+                        biased;
+                        Ok(total) = limit_rx => {
+                            Err(DomainError::PayloadTooLarge {
+                                detail: format!(
+                                    "streaming request body of {total} bytes exceeds maximum of {max_body} bytes"
+                                ),
+                                instance: body_instance_uri,
+                            })
+                        }
+                        result = resp_future => {
+                            let (status, resp_headers, resp_body_stream) = result
+                                .map_err(|_| DomainError::RequestTimeout {
+                                    detail: format!("request to {url} timed out after {timeout:?}"),
+                                    instance: instance_uri.clone(),
+                                })?
+                                .map_err(|e| DomainError::DownstreamError {
+                                    detail: format!("proxy bridge error: {e}"),
+                                    instance: instance_uri.clone(),
+                                })?;
+                            build_proxy_response(status, resp_headers, resp_body_stream, instance_uri)
                         }
                     }
+                } else {
+                    // Buffered path: write full request, shutdown write side, then read response.
+                    let wire = session_bridge::serialize_request_wire(
+                        &method,
+                        &url,
+                        &outbound_headers,
+                        Some(&body_bytes),
+                    );
+                    let mut client_io = client_io;
+                    client_io
+                        .write_all(&wire)
+                        .await
+                        .map_err(|e| DomainError::DownstreamError {
+                            detail: format!("failed to write to proxy bridge: {e}"),
+                            instance: instance_uri.clone(),
+                        })?;
+                    // Do NOT shutdown the write side — Pingora uses Content-Length to
+                    // determine the request boundary, and an early write-close is
+                    // misinterpreted as "downstream dropped the connection".
+
+                    // 9. Parse response.
+                    let (status, resp_headers, resp_body_stream) =
+                        tokio::time::timeout(timeout, session_bridge::parse_response_stream(client_io))
+                            .await
+                            .map_err(|_| DomainError::RequestTimeout {
+                                detail: format!("request to {url} timed out after {timeout:?}"),
+                                instance: instance_uri.clone(),
+                            })?
+                            .map_err(|e| DomainError::DownstreamError {
+                                detail: format!("proxy bridge error: {e}"),
+                                instance: instance_uri.clone(),
+                            })?;
+
+                    build_proxy_response(status, resp_headers, resp_body_stream, instance_uri)
                 }
-                if exceeded {
-                    let _ = limit_tx.send(total_bytes);
+            }
+            .await
+        };
+
+        if let Some((permit, cb, labels)) = circuit
+            && let Some(outcome) = call_outcome(&result)
+        {
+            let probe = permit.is_probe();
+            let failed = cb.failure_conditions.is_failure(outcome);
+            let transition = self.circuit_breaker.record(permit, cb, failed);
+            self.metrics
+                .record_circuit_outcome(&labels, probe, failed, transition);
+        }
+        if is_grpc
+            && matches!(result, Err(DomainError::AuthenticationFailed { .. }))
+            && let Some((plugin, auth_ctx)) = &auth_state
+        {
+            plugin.on_unauthorized(auth_ctx).await;
+        }
+        let mut resp = result?;
+
+        if resp.status() == http::StatusCode::UNAUTHORIZED
+            && let Some((plugin, auth_ctx)) = &auth_state
+        {
+            plugin.on_unauthorized(auth_ctx).await;
+        }
+
+        if let Some((config, key, stale)) = cache_call {
+            resp = self
+                .complete_cached_call(config, key, &upstream, &req_headers, stale, resp)
+                .await;
+        }
+
+        if rate_limit_degraded {
+            resp.headers_mut().insert(
+                HeaderName::from_static(H_RATE_LIMIT_DEGRADED),
+                HeaderValue::from_static("true"),
+            );
+        }
+        Ok(resp)
+    }
+
+    /// Finish a call on a cache-enabled route: answer a `304` to our own
+    /// revalidation from the stored entry, otherwise store the response if
+    /// it is cacheable. Bodies larger than `max_entry_bytes` stream through.
+    async fn complete_cached_call(
+        &self,
+        config: &CacheConfig,
+        key: String,
+        upstream: &Upstream,
+        req_headers: &HeaderMap,
+        stale: Option<CachedResponse>,
+        resp: http::Response<Body>,
+    ) -> http::Response<Body> {
+        if let Some(mut cached) = stale
+            && resp.status() == http::StatusCode::NOT_MODIFIED
+        {
+            cached.refresh_headers(resp.headers());
+            if let Some(ttl) = cache::storable_ttl(config, cached.status, &cached.headers) {
+                let vary = cache::vary_headers(config, &cached.headers);
+                self.response_cache
+                    .store(key, upstream.id, vary, req_headers, cached.clone(), ttl);
+            }
+            self.metrics.record_cache(&upstream.alias, "revalidated");
+            return cached.into_response(Duration::ZERO, req_headers, "revalidated");
+        }
+
+        self.metrics.record_cache(&upstream.alias, "miss");
+        let (mut parts, mut body) = resp.into_parts();
+        if let Some(ttl) = cache::storable_ttl(config, parts.status, &parts.headers) {
+            body = match cache::read_bounded(body, config.max_entry_bytes).await {
+                Ok(bytes) => {
+                    let vary = cache::vary_headers(config, &parts.headers);
+                    let cached = CachedResponse {
+                        status: parts.status,
+                        headers: parts.headers.clone(),
+                        body: bytes.clone(),
+                    };
+                    self.response_cache
+                        .store(key, upstream.id, vary, req_headers, cached, ttl);
+                    Body::from(bytes)
                 }
-                let _ = client_write.shutdown().await;
+                Err(body) => body,
+            };
+        }
+        parts.headers.insert(
+            HeaderName::from_static(cache::H_CACHE_STATUS),
+            HeaderValue::from_static("miss"),
+        );
+        http::Response::from_parts(parts, body)
+    }
+
+    /// Two-tier endpoint selection (D1):
+    /// 1. `X-OAGW-Target-Host` header → validate against endpoint list
+    /// 2. Round-robin via `BackendSelector` for multi-endpoint, direct for single
+    async fn select_endpoint(
+        &self,
+        upstream: &Upstream,
+        req_headers: &http::HeaderMap,
+        instance_uri: &str,
+    ) -> Result<Endpoint, DomainError> {
+        let endpoints = &upstream.server.endpoints;
+
+        if endpoints.is_empty() {
+            return Err(DomainError::DownstreamError {
+                detail: "upstream has no endpoints".into(),
+                instance: instance_uri.to_string(),
             });
+        }
 
-            // 9. Parse response from the read half, but short-circuit to 413
-            //    if the body-forwarding task signals a limit breach.
-            //
-            // TODO(hardening): a fast upstream can respond before the body-forwarder
-            // detects the limit breach, causing the client to see 200 instead of 413.
-            // Fix: wrap the write half in a LimitedAsyncWrite that returns io::Error
-            // at the byte limit, so Pingora aborts the exchange before responding.
-            let resp_future =
-                tokio::time::timeout(timeout, session_bridge::parse_response_stream(client_read));
-            tokio::select! {
-                biased;
-                Ok(total) = limit_rx => {
-                    return Err(DomainError::PayloadTooLarge {
+        // Tier 1: Explicit selection via X-OAGW-Target-Host header.
+        if let Some(target_host) = req_headers
+            .get("x-oagw-target-host")
+            .and_then(|v| v.to_str().ok())
+        {
+            // Validate format: allowlist of safe hostname/IP characters.
+            // Rejects null bytes, @, \, Unicode homoglyphs, and port/path syntax.
+            if target_host.is_empty()
+                || !target_host
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_'))
+            {
+                return Err(DomainError::InvalidTargetHost {
+                    instance: instance_uri.to_string(),
+                });
+            }
+
+            // Find matching endpoint by host.
+            return endpoints
+                .iter()
+                .find(|ep| ep.host.eq_ignore_ascii_case(target_host))
+                .cloned()
+                .ok_or_else(|| {
+                    let valid_hosts: Vec<&str> =
+                        endpoints.iter().map(|ep| ep.host.as_str()).collect();
+                    tracing::warn!(
+                        target_host,
+                        ?valid_hosts,
+                        "X-OAGW-Target-Host does not match any configured endpoint"
+                    );
+                    DomainError::UnknownTargetHost {
                         detail: format!(
-                            "streaming request body of {total} bytes exceeds maximum of {max_body} bytes"
+                            "X-OAGW-Target-Host '{}' does not match any configured endpoint",
+                            target_host
                         ),
-                        instance: body_instance_uri,
-                    });
-                }
-                result = resp_future => {
-                    let (status, resp_headers, resp_body_stream) = result
-                        .map_err(|_| DomainError::RequestTimeout {
-                            detail: format!("request to {url} timed out after {timeout:?}"),
-                            instance: instance_uri.clone(),
-                        })?
-                        .map_err(|e| DomainError::DownstreamError {
-                            detail: format!("proxy bridge error: {e}"),
-                            instance: instance_uri.clone(),
-                        })?;
-                    Ok(build_proxy_response(status, resp_headers, resp_body_stream, instance_uri)?)
+                        instance: instance_uri.to_string(),
+                    }
+                });
+        }
+
+        // Tier 2: Automatic selection.
+        if endpoints.len() == 1 {
+            // Single-endpoint: use directly, no LB overhead.
+            return Ok(endpoints[0].clone());
+        }
+
+        // Multi-endpoint: round-robin via BackendSelector.
+        self.backend_selector
+            .select(upstream.id, endpoints)
+            .await
+            .ok_or_else(|| DomainError::DownstreamError {
+                detail: "all backends are unhealthy".into(),
+                instance: instance_uri.to_string(),
+            })
+    }
+}
+
+#[async_trait]
+impl DataPlaneService for DataPlaneServiceImpl {
+    async fn proxy_request(
+        &self,
+        ctx: SecurityContext,
+        req: http::Request<Body>,
+    ) -> Result<http::Response<Body>, DomainError> {
+        let started_at = Instant::now();
+        let mut run = None;
+        let result = self.forward(ctx, req, started_at, &mut run).await;
+        match run {
+            Some(run) => run.finish(result, started_at).await,
+            None => result,
+        }
+    }
+}
+
+impl ConfigChangeListener for DataPlaneServiceImpl {
+    fn upstream_changed(&self, upstream_id: Uuid, reset_circuits: bool) {
+        self.backend_selector.invalidate(upstream_id);
+        self.response_cache.invalidate_upstream(upstream_id);
+        if reset_circuits {
+            self.circuit_breaker.remove_upstream(upstream_id);
+        }
+    }
+
+    fn upstream_deleted(&self, upstream_id: Uuid) {
+        self.backend_selector.invalidate(upstream_id);
+        self.rate_limiter
+            .remove_key(&format!("upstream:{upstream_id}"));
+        self.circuit_breaker.remove_upstream(upstream_id);
+        self.response_cache.invalidate_upstream(upstream_id);
+    }
+
+    fn route_changed(&self, upstream_id: Uuid) {
+        self.response_cache.invalidate_upstream(upstream_id);
+    }
+
+    fn route_deleted(&self, route_id: Uuid, upstream_id: Uuid) {
+        self.rate_limiter.remove_key(&format!("route:{route_id}"));
+        self.response_cache.invalidate_upstream(upstream_id);
+    }
+}
+
+/// Plugin pipeline state carried from the request phase to the response and
+/// error phases.
+struct PipelineRun {
+    pipeline: PluginPipeline,
+    upstream_id: Uuid,
+    method: String,
+    path: String,
+    instance_uri: String,
+    inbound_headers: HashMap<String, String>,
+    /// Outbound request headers after request transforms ran.
+    request_headers: HashMap<String, String>,
+}
+
+impl PipelineRun {
+    /// Run response guards and transforms on success, or error transforms on
+    /// failure. Error transforms only observe; their failures are logged.
+    async fn finish(
+        self,
+        result: Result<http::Response<Body>, DomainError>,
+        started_at: Instant,
+    ) -> Result<http::Response<Body>, DomainError> {
+        let mut resp = match result {
+            Ok(resp) => resp,
+            Err(e) => {
+                let mut err_ctx = ErrorContext {
+                    upstream_id: self.upstream_id,
+                    method: self.method,
+                    path: self.path,
+                    detail: e.to_string(),
+                    config: HashMap::new(),
+                    elapsed: started_at.elapsed(),
+                };
+                for (transform, config) in &self.pipeline.transforms {
+                    err_ctx.config.clone_from(config);
+                    if let Err(plugin_err) = transform.transform_error(&err_ctx).await {
+                        tracing::warn!(error = %plugin_err, "transform plugin failed on error");
+                    }
                 }
+                return Err(e);
             }
-        } else {
-            // Buffered path: write full request, shutdown write side, then read response.
-            let wire = session_bridge::serialize_request_wire(
-                &method,
-                &url,
-                &outbound_headers,
-                Some(&body_bytes),
-            );
-            let mut client_io = client_io;
-            client_io
-                .write_all(&wire)
+        };
+
+        let mut resp_ctx = ResponseContext {
+            upstream_id: self.upstream_id,
+            method: self.method,
+            path: self.path,
+            inbound_headers: self.inbound_headers,
+            request_headers: self.request_headers,
+            status: resp.status().as_u16(),
+            upstream_headers: header_strings(resp.headers()),
+            headers: HashMap::new(),
+            config: HashMap::new(),
+            elapsed: started_at.elapsed(),
+        };
+        for (guard, config) in &self.pipeline.guards {
+            resp_ctx.config.clone_from(config);
+            guard
+                .guard_response(&mut resp_ctx)
                 .await
-                .map_err(|e| DomainError::DownstreamError {
-                    detail: format!("failed to write to proxy bridge: {e}"),
-                    instance: instance_uri.clone(),
-                })?;
-            // Do NOT shutdown the write side — Pingora uses Content-Length to
-            // determine the request boundary, and an early write-close is
-            // misinterpreted as "downstream dropped the connection".
+                .map_err(|e| plugin_error(e, &self.instance_uri))?;
+        }
+        for (transform, config) in &self.pipeline.transforms {
+            resp_ctx.config.clone_from(config);
+            transform
+                .transform_response(&mut resp_ctx)
+                .await
+                .map_err(|e| plugin_error(e, &self.instance_uri))?;
+        }
+        insert_headers(resp.headers_mut(), &resp_ctx.headers);
+        Ok(resp)
+    }
+}
 
-            // 9. Parse response.
-            let (status, resp_headers, resp_body_stream) =
-                tokio::time::timeout(timeout, session_bridge::parse_response_stream(client_io))
-                    .await
-                    .map_err(|_| DomainError::RequestTimeout {
-                        detail: format!("request to {url} timed out after {timeout:?}"),
-                        instance: instance_uri.clone(),
-                    })?
-                    .map_err(|e| DomainError::DownstreamError {
-                        detail: format!("proxy bridge error: {e}"),
-                        instance: instance_uri.clone(),
-                    })?;
+/// Flatten a header map into lowercase name → value pairs. Non-UTF-8 values
+/// are dropped; for repeated headers the last value wins.
+fn header_strings(headers: &HeaderMap) -> HashMap<String, String> {
+    headers
+        .iter()
+        .filter_map(|(k, v)| {
+            v.to_str()
+                .ok()
+                .map(|s| (k.as_str().to_string(), s.to_string()))
+        })
+        .collect()
+}
 
-            Ok(build_proxy_response(
-                status,
-                resp_headers,
-                resp_body_stream,
-                instance_uri,
-            )?)
+/// Insert plugin-produced headers, skipping names or values that are not
+/// valid HTTP.
+fn insert_headers(target: &mut HeaderMap, headers: &HashMap<String, String>) {
+    for (k, v) in headers {
+        if let (Ok(name), Ok(val)) = (
+            HeaderName::from_bytes(k.as_bytes()),
+            HeaderValue::from_str(v),
+        ) {
+            target.insert(name, val);
         }
     }
+}
+
+/// Map a guard or transform plugin failure to a domain error.
+fn plugin_error(e: PluginError, instance_uri: &str) -> DomainError {
+    match e {
+        PluginError::Rejected(detail) => DomainError::Validation {
+            detail,
+            instance: instance_uri.to_string(),
+        },
+        PluginError::SecretNotFound(detail) => DomainError::SecretNotFound {
+            detail,
+            instance: instance_uri.to_string(),
+        },
+        PluginError::AuthFailed(_) => DomainError::AuthenticationFailed {
+            detail: e.to_string(),
+            instance: instance_uri.to_string(),
+        },
+        PluginError::Internal(message) => DomainError::internal(message),
+    }
+}
 
-    fn remove_rate_limit_key(&self, key: &str) {
-        self.rate_limiter.remove_key(key);
+/// Map a guard rejection status to the closest domain error.
+fn guard_rejection(status: u16, detail: String, instance: String) -> DomainError {
+    match status {
+        403 => DomainError::Forbidden { detail },
+        408 | 504 => DomainError::RequestTimeout { detail, instance },
+        413 => DomainError::PayloadTooLarge { detail, instance },
+        429 => DomainError::RateLimitExceeded {
+            detail,
+            instance,
+            retry_after_secs: None,
+        },
+        _ => DomainError::Validation { detail, instance },
     }
 }
 
+/// Build a response answered by a guard without calling the upstream.
+fn local_response(
+    status: u16,
+    headers: &HashMap<String, String>,
+    instance_uri: String,
+) -> Result<http::Response<Body>, DomainError> {
+    let status = http::StatusCode::from_u16(status).map_err(|e| DomainError::DownstreamError {
+        detail: format!("guard plugin answered with invalid status: {e}"),
+        instance: instance_uri,
+    })?;
+    let mut resp = http::Response::new(Body::Empty);
+    *resp.status_mut() = status;
+    insert_headers(resp.headers_mut(), headers);
+    resp.extensions_mut().insert(ErrorSource::Gateway);
+    Ok(resp)
+}
+
 /// Build the final proxy response: extract error source, sanitize headers,
 /// assemble the `http::Response<Body>`.
 fn build_proxy_response(
@@ -615,6 +1217,26 @@ fn build_proxy_response(
     Ok(resp)
 }
 
+/// Classify an upstream call for the circuit breaker. Gateway-sourced proxy
+/// responses are Pingora's own timeout (504) and connection (502) errors.
+/// Failures not caused by the upstream yield `None` and are not recorded.
+fn call_outcome(result: &Result<http::Response<Body>, DomainError>) -> Option<CallOutcome> {
+    match result {
+        Ok(resp) => Some(match resp.extensions().get::<ErrorSource>() {
+            Some(ErrorSource::Gateway) if resp.status() == http::StatusCode::GATEWAY_TIMEOUT => {
+                CallOutcome::Timeout
+            }
+            Some(ErrorSource::Gateway) => CallOutcome::ConnectionError,
+            _ => CallOutcome::Response(resp.status().as_u16()),
+        }),
+        Err(DomainError::RequestTimeout { .. } | DomainError::ConnectionTimeout { .. }) => {
+            Some(CallOutcome::Timeout)
+        }
+        Err(DomainError::DownstreamError { .. }) => Some(CallOutcome::ConnectionError),
+        Err(_) => None,
+    }
+}
+
 /// Normalize a URL path: collapse consecutive slashes and resolve `.`/`..` segments.
 /// Segments that would escape above the root are discarded.
 fn normalize_path(path: &str) -> String {
@@ -693,6 +1315,7 @@ mod tests {
             headers: None,
             plugins: None,
             rate_limit: None,
+            circuit_breaker: None,
             tags: vec![],
         }
     }
@@ -730,7 +1353,10 @@ mod tests {
             AuthZResolverClient, AuthZResolverError, EvaluationRequest, EvaluationResponse,
             EvaluationResponseContext, PolicyEnforcer,
         };
-        use credstore_sdk::{CredStoreClientV1, CredStoreError, GetSecretResponse, SecretRef};
+        use credstore_sdk::{
+            CredStoreClientV1, CredStoreError, GetSecretResponse, SecretRef, SecretValue,
+            SharingMode,
+        };
         use modkit_security::SecurityContext;
 
         struct AllowAllAuthZ;
@@ -760,6 +1386,50 @@ mod tests {
             ) -> Result<Option<GetSecretResponse>, CredStoreError> {
                 Ok(None)
             }
+
+            async fn get_version(
+                &self,
+                _ctx: &SecurityContext,
+                _key: &SecretRef,
+                _version: credstore_sdk::SecretVersion,
+            ) -> Result<Option<GetSecretResponse>, CredStoreError> {
+                unimplemented!()
+            }
+
+            async fn put(
+                &self,
+                _ctx: &SecurityContext,
+                _key: &SecretRef,
+                _value: SecretValue,
+                _sharing: SharingMode,
+            ) -> Result<credstore_sdk::SecretVersion, CredStoreError> {
+                unimplemented!()
+            }
+
+            async fn rotate(
+                &self,
+                _ctx: &SecurityContext,
+                _key: &SecretRef,
+                _value: SecretValue,
+                _grace_period: std::time::Duration,
+            ) -> Result<credstore_sdk::RotateSecretResponse, CredStoreError> {
+                unimplemented!()
+            }
+
+            async fn delete(
+                &self,
+                _ctx: &SecurityContext,
+                _key: &SecretRef,
+            ) -> Result<(), CredStoreError> {
+                unimplemented!()
+            }
+
+            async fn list(
+                &self,
+                _ctx: &SecurityContext,
+            ) -> Result<Vec<credstore_sdk::SecretInfo>, CredStoreError> {
+                unimplemented!()
+            }
         }
 
         let credstore: Arc<dyn CredStoreClientV1> = Arc::new(NoopCredStore);
@@ -854,6 +1524,28 @@ mod tests {
             ) -> Result<Route, DomainError> {
                 unimplemented!()
             }
+            async fn resolve_grpc_route(
+                &self,
+                _: &SecurityContext,
+                _: Uuid,
+                _: &str,
+                _: &str,
+            ) -> Result<Route, DomainError> {
+                unimplemented!()
+            }
+            async fn resolve_ancestor_upstreams(
+                &self,
+                _: &SecurityContext,
+                _: &str,
+            ) -> Result<Vec<Upstream>, DomainError> {
+                unimplemented!()
+            }
+
+            fn set_change_listener(
+                &self,
+                _: std::sync::Weak<dyn crate::domain::services::ConfigChangeListener>,
+            ) {
+            }
         }
 
         let cp: Arc<dyn ControlPlaneService> = Arc::new(NoopCp);
@@ -870,6 +1562,53 @@ mod tests {
         DataPlaneServiceImpl::new(cp, credstore, policy_enforcer, selector, proxy)
     }
 
+    #[test]
+    fn call_outcome_classifies_gateway_and_upstream_failures() {
+        let response = |status: u16, source: ErrorSource| {
+            let mut resp = http::Response::builder()
+                .status(status)
+                .body(Body::Empty)
+                .unwrap();
+            resp.extensions_mut().insert(source);
+            Ok(resp)
+        };
+        let err = |e: fn(String, String) -> DomainError| Err(e(String::new(), String::new()));
+
+        assert_eq!(
+            call_outcome(&response(504, ErrorSource::Gateway)),
+            Some(CallOutcome::Timeout)
+        );
+        assert_eq!(
+            call_outcome(&response(502, ErrorSource::Gateway)),
+            Some(CallOutcome::ConnectionError)
+        );
+        assert_eq!(
+            call_outcome(&response(504, ErrorSource::Upstream)),
+            Some(CallOutcome::Response(504))
+        );
+        assert_eq!(
+            call_outcome(&err(|detail, instance| DomainError::RequestTimeout {
+                detail,
+                instance
+            })),
+            Some(CallOutcome::Timeout)
+        );
+        assert_eq!(
+            call_outcome(&err(|detail, instance| DomainError::DownstreamError {
+                detail,
+                instance
+            })),
+            Some(CallOutcome::ConnectionError)
+        );
+        assert_eq!(
+            call_outcome(&err(|detail, instance| DomainError::PayloadTooLarge {
+                detail,
+                instance
+            })),
+            None
+        );
+    }
+
     // P2 #12: Alias extraction happens on raw path, then suffix is normalized.
     // Path traversal in the alias segment must not influence which upstream is resolved.
     #[test]
@@ -0,0 +1,92 @@
+//! Route selection shared by the `RouteRepository` implementations.
+
+use crate::domain::model::{HttpMethod, Route};
+
+/// Pick the best HTTP route for `method` and `path` among `candidates`.
+///
+/// A candidate qualifies when it is enabled, has HTTP match rules listing the
+/// method, and its path is a prefix of `path`. The longest path prefix wins,
+/// then the highest priority; on a full tie the earliest candidate wins, so
+/// callers pass candidates in creation order.
+pub(crate) fn select_http_route(
+    candidates: impl IntoIterator<Item = Route>,
+    method: &str,
+    path: &str,
+) -> Option<Route> {
+    // Unknown methods never match.
+    let request_method = parse_method(method)?;
+
+    let mut best: Option<Route> = None;
+    let mut best_path_len = 0;
+    let mut best_priority = i32::MIN;
+
+    for route in candidates {
+        // Must be enabled.
+        if !route.enabled {
+            continue;
+        }
+        // Must have HTTP match rules.
+        let Some(http_match) = &route.match_rules.http else {
+            continue;
+        };
+        // Method must match.
+        if !http_match.methods.contains(&request_method) {
+            continue;
+        }
+        // Path must be a prefix match.
+        if !path.starts_with(&http_match.path) {
+            continue;
+        }
+
+        let path_len = http_match.path.len();
+        let priority = route.priority;
+
+        // Select by longest path prefix, then highest priority.
+        if path_len > best_path_len || (path_len == best_path_len && priority > best_priority) {
+            best_path_len = path_len;
+            best_priority = priority;
+            best = Some(route);
+        }
+    }
+
+    best
+}
+
+/// Pick the gRPC route for `service` and `method` among `candidates`.
+///
+/// A candidate qualifies when it is enabled and its gRPC match rules name
+/// exactly this service and method. The highest priority wins; on a tie the
+/// earliest candidate wins, as for HTTP routes.
+pub(crate) fn select_grpc_route(
+    candidates: impl IntoIterator<Item = Route>,
+    service: &str,
+    method: &str,
+) -> Option<Route> {
+    let mut best: Option<Route> = None;
+    for route in candidates {
+        if !route.enabled {
+            continue;
+        }
+        let Some(grpc_match) = &route.match_rules.grpc else {
+            continue;
+        };
+        if grpc_match.service != service || grpc_match.method != method {
+            continue;
+        }
+        if best.as_ref().is_none_or(|b| route.priority > b.priority) {
+            best = Some(route);
+        }
+    }
+    best
+}
+
+fn parse_method(s: &str) -> Option<HttpMethod> {
+    match s.to_uppercase().as_str() {
+        "GET" => Some(HttpMethod::Get),
+        "POST" => Some(HttpMethod::Post),
+        "PUT" => Some(HttpMethod::Put),
+        "DELETE" => Some(HttpMethod::Delete),
+        "PATCH" => Some(HttpMethod::Patch),
+        _ => None,
+    }
+}
@@ -1,5 +1,8 @@
+mod matching;
 pub(crate) mod route_repo;
+pub(crate) mod sql;
 pub(crate) mod upstream_repo;
 
 pub(crate) use route_repo::InMemoryRouteRepo;
+pub(crate) use sql::{SqlRouteRepo, SqlUpstreamRepo};
 pub(crate) use upstream_repo::InMemoryUpstreamRepo;
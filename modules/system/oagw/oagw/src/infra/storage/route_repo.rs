@@ -1,5 +1,6 @@
-use crate::domain::model::{HttpMethod, ListQuery, Route};
+use crate::domain::model::{ListQuery, Route};
 use crate::domain::repo::{RepositoryError, RouteRepository};
+use crate::infra::storage::matching::{select_grpc_route, select_http_route};
 use async_trait::async_trait;
 use dashmap::DashMap;
 use modkit_macros::domain_model;
@@ -100,54 +101,40 @@ impl RouteRepository for InMemoryRouteRepo {
             .map(|ids| ids.clone())
             .unwrap_or_default();
 
-        let request_method = parse_method(method);
+        let candidates = route_ids.iter().filter_map(|id| {
+            self.store
+                .get(id)
+                .filter(|r| r.tenant_id == tenant_id)
+                .map(|r| r.clone())
+        });
 
-        let mut best: Option<Route> = None;
-        let mut best_path_len = 0;
-        let mut best_priority = i32::MIN;
-
-        for id in &route_ids {
-            let Some(route_ref) = self.store.get(id) else {
-                continue;
-            };
-            let route = route_ref.value();
-
-            // Must match tenant.
-            if route.tenant_id != tenant_id {
-                continue;
-            }
-            // Must be enabled.
-            if !route.enabled {
-                continue;
-            }
-            // Must have HTTP match rules.
-            let Some(http_match) = &route.match_rules.http else {
-                continue;
-            };
-            // Method must match (unknown methods never match).
-            let Some(req_method) = &request_method else {
-                continue;
-            };
-            if !http_match.methods.contains(req_method) {
-                continue;
-            }
-            // Path must be a prefix match.
-            if !path.starts_with(&http_match.path) {
-                continue;
-            }
+        select_http_route(candidates, method, path).ok_or(RepositoryError::NotFound {
+            entity: "route",
+            id: Uuid::nil(),
+        })
+    }
 
-            let path_len = http_match.path.len();
-            let priority = route.priority;
+    async fn find_grpc_matching(
+        &self,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+        service: &str,
+        method: &str,
+    ) -> Result<Route, RepositoryError> {
+        let route_ids: Vec<Uuid> = self
+            .upstream_index
+            .get(&upstream_id)
+            .map(|ids| ids.clone())
+            .unwrap_or_default();
 
-            // Select by longest path prefix, then highest priority.
-            if path_len > best_path_len || (path_len == best_path_len && priority > best_priority) {
-                best_path_len = path_len;
-                best_priority = priority;
-                best = Some(route.clone());
-            }
-        }
+        let candidates = route_ids.iter().filter_map(|id| {
+            self.store
+                .get(id)
+                .filter(|r| r.tenant_id == tenant_id)
+                .map(|r| r.clone())
+        });
 
-        best.ok_or(RepositoryError::NotFound {
+        select_grpc_route(candidates, service, method).ok_or(RepositoryError::NotFound {
             entity: "route",
             id: Uuid::nil(),
         })
@@ -219,20 +206,9 @@ impl RouteRepository for InMemoryRouteRepo {
     }
 }
 
-fn parse_method(s: &str) -> Option<HttpMethod> {
-    match s.to_uppercase().as_str() {
-        "GET" => Some(HttpMethod::Get),
-        "POST" => Some(HttpMethod::Post),
-        "PUT" => Some(HttpMethod::Put),
-        "DELETE" => Some(HttpMethod::Delete),
-        "PATCH" => Some(HttpMethod::Patch),
-        _ => None,
-    }
-}
-
 #[cfg(test)]
 mod tests {
-    use crate::domain::model::{HttpMatch, MatchRules, PathSuffixMode};
+    use crate::domain::model::{GrpcMatch, HttpMatch, HttpMethod, MatchRules, PathSuffixMode};
 
     use super::*;
 
@@ -258,6 +234,7 @@ mod tests {
             },
             plugins: None,
             rate_limit: None,
+            cache: None,
             tags: vec![],
             priority,
             enabled: true,
@@ -343,6 +320,46 @@ mod tests {
         assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
     }
 
+    #[tokio::test]
+    async fn find_grpc_matching_exact_service_and_method() {
+        let repo = InMemoryRouteRepo::new();
+        let tenant = Uuid::new_v4();
+        let upstream = Uuid::new_v4();
+
+        let grpc_route = |method: &str, priority: i32| {
+            let mut r = make_route(tenant, upstream, vec![], "/", priority);
+            r.match_rules = MatchRules {
+                http: None,
+                grpc: Some(GrpcMatch {
+                    service: "helloworld.Greeter".into(),
+                    method: method.into(),
+                }),
+            };
+            r
+        };
+        let low = grpc_route("SayHello", 0);
+        let high = grpc_route("SayHello", 5);
+        repo.create(low).await.unwrap();
+        repo.create(high.clone()).await.unwrap();
+        repo.create(grpc_route("SayGoodbye", 0)).await.unwrap();
+
+        let matched = repo
+            .find_grpc_matching(tenant, upstream, "helloworld.Greeter", "SayHello")
+            .await
+            .unwrap();
+        assert_eq!(matched.id, high.id);
+
+        let result = repo
+            .find_grpc_matching(tenant, upstream, "helloworld.Greeter", "Missing")
+            .await;
+        assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
+        // gRPC routes never take part in HTTP matching.
+        let result = repo
+            .find_matching(tenant, upstream, "POST", "/helloworld.Greeter/SayHello")
+            .await;
+        assert!(matches!(result, Err(RepositoryError::NotFound { .. })));
+    }
+
     #[tokio::test]
     async fn find_matching_unknown_method_returns_not_found() {
         let repo = InMemoryRouteRepo::new();
@@ -0,0 +1,37 @@
+use modkit_db_macros::Scopable;
+use sea_orm::entity::prelude::*;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// `match_type`, `path_prefix` and the gRPC columns duplicate `match_rules`
+/// so route selection can filter on indexed columns.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Scopable)]
+#[sea_orm(table_name = "oagw_route")]
+#[secure(tenant_col = "tenant_id", resource_col = "id", no_owner, no_type)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub upstream_id: Uuid,
+    pub enabled: bool,
+    pub priority: i32,
+    pub match_type: String,
+    pub path_prefix: Option<String>,
+    pub grpc_service: Option<String>,
+    pub grpc_method: Option<String>,
+    pub match_rules: Json,
+    pub plugins: Option<Json>,
+    pub rate_limit: Option<Json>,
+    pub cache: Option<Json>,
+    pub tags: Json,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+pub const MATCH_TYPE_HTTP: &str = "http";
+pub const MATCH_TYPE_GRPC: &str = "grpc";
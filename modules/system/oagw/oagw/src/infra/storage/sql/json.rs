@@ -0,0 +1,604 @@
+//! Serde mirrors of the domain configuration stored in JSON columns.
+//!
+//! Domain models carry no serde derives; these types pin the persisted shape
+//! so that renaming a domain field does not silently change stored data.
+//! New optional fields must be `#[serde(default)]` to keep old rows readable.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::model as domain;
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum SharingMode {
+    Private,
+    Inherit,
+    Enforce,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum Scheme {
+    Http,
+    Https,
+    Wss,
+    Wt,
+    Grpc,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct Endpoint {
+    scheme: Scheme,
+    host: String,
+    port: u16,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct Server {
+    endpoints: Vec<Endpoint>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct AuthConfig {
+    plugin_type: String,
+    sharing: SharingMode,
+    #[serde(default)]
+    config: Option<HashMap<String, String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum PassthroughMode {
+    None,
+    Allowlist,
+    All,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct RequestHeaderRules {
+    #[serde(default)]
+    set: HashMap<String, String>,
+    #[serde(default)]
+    add: HashMap<String, String>,
+    #[serde(default)]
+    remove: Vec<String>,
+    passthrough: PassthroughMode,
+    #[serde(default)]
+    passthrough_allowlist: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct ResponseHeaderRules {
+    #[serde(default)]
+    set: HashMap<String, String>,
+    #[serde(default)]
+    add: HashMap<String, String>,
+    #[serde(default)]
+    remove: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct HeadersConfig {
+    #[serde(default)]
+    request: Option<RequestHeaderRules>,
+    #[serde(default)]
+    response: Option<ResponseHeaderRules>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct PluginsConfig {
+    sharing: SharingMode,
+    #[serde(default)]
+    items: Vec<String>,
+    #[serde(default)]
+    config: HashMap<String, HashMap<String, String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum RateLimitAlgorithm {
+    TokenBucket,
+    SlidingWindow,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum Window {
+    Second,
+    Minute,
+    Hour,
+    Day,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum RateLimitScope {
+    Global,
+    Tenant,
+    User,
+    Ip,
+    Route,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum RateLimitStrategy {
+    Reject,
+    Queue,
+    Degrade,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct SustainedRate {
+    rate: u32,
+    window: Window,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct BurstConfig {
+    capacity: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct QueueConfig {
+    max_depth: u32,
+    max_wait_ms: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct RateLimitConfig {
+    sharing: SharingMode,
+    algorithm: RateLimitAlgorithm,
+    sustained: SustainedRate,
+    #[serde(default)]
+    burst: Option<BurstConfig>,
+    scope: RateLimitScope,
+    strategy: RateLimitStrategy,
+    #[serde(default)]
+    queue: Option<QueueConfig>,
+    cost: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct FailureRateConfig {
+    threshold_percent: u32,
+    minimum_requests: u32,
+    window_seconds: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct FailureConditions {
+    status_codes: Vec<u16>,
+    timeout: bool,
+    connection_error: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum CircuitBreakerScope {
+    Global,
+    PerEndpoint,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct CircuitBreakerConfig {
+    enabled: bool,
+    failure_threshold: u32,
+    #[serde(default)]
+    failure_rate: Option<FailureRateConfig>,
+    success_threshold: u32,
+    timeout_seconds: u32,
+    half_open_max_requests: u32,
+    failure_conditions: FailureConditions,
+    scope: CircuitBreakerScope,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct CacheConfig {
+    enabled: bool,
+    ttl_seconds: u32,
+    cache_post: bool,
+    max_entry_bytes: u64,
+    #[serde(default)]
+    vary_headers: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "UPPERCASE")]
+pub(super) enum HttpMethod {
+    Get,
+    Post,
+    Put,
+    Delete,
+    Patch,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum PathSuffixMode {
+    Disabled,
+    Append,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct HttpMatch {
+    methods: Vec<HttpMethod>,
+    path: String,
+    #[serde(default)]
+    query_allowlist: Vec<String>,
+    path_suffix_mode: PathSuffixMode,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct GrpcMatch {
+    service: String,
+    method: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct MatchRules {
+    #[serde(default)]
+    http: Option<HttpMatch>,
+    #[serde(default)]
+    grpc: Option<GrpcMatch>,
+}
+
+// ---------------------------------------------------------------------------
+// JSON (de)serialization helpers
+// ---------------------------------------------------------------------------
+
+/// Serialize a domain value through its storage mirror `S`.
+pub(super) fn to_json<S, D>(value: D) -> serde_json::Value
+where
+    S: Serialize + From<D>,
+{
+    // Mirrors hold only strings, numbers, maps and sequences with string
+    // keys, so serialization cannot fail.
+    serde_json::to_value(S::from(value)).unwrap_or_default()
+}
+
+/// Deserialize a stored JSON column through its storage mirror `S`.
+pub(super) fn from_json<S, D>(value: serde_json::Value) -> Result<D, serde_json::Error>
+where
+    S: for<'de> Deserialize<'de> + Into<D>,
+{
+    serde_json::from_value::<S>(value).map(Into::into)
+}
+
+// ---------------------------------------------------------------------------
+// Conversions
+// ---------------------------------------------------------------------------
+
+macro_rules! enum_conversions {
+    ($name:ident { $($variant:ident),+ $(,)? }) => {
+        impl From<domain::$name> for $name {
+            fn from(v: domain::$name) -> Self {
+                match v {
+                    $(domain::$name::$variant => Self::$variant,)+
+                }
+            }
+        }
+
+        impl From<$name> for domain::$name {
+            fn from(v: $name) -> Self {
+                match v {
+                    $($name::$variant => Self::$variant,)+
+                }
+            }
+        }
+    };
+}
+
+enum_conversions!(SharingMode {
+    Private,
+    Inherit,
+    Enforce
+});
+enum_conversions!(Scheme {
+    Http,
+    Https,
+    Wss,
+    Wt,
+    Grpc
+});
+enum_conversions!(PassthroughMode {
+    None,
+    Allowlist,
+    All
+});
+enum_conversions!(RateLimitAlgorithm {
+    TokenBucket,
+    SlidingWindow
+});
+enum_conversions!(Window {
+    Second,
+    Minute,
+    Hour,
+    Day
+});
+enum_conversions!(RateLimitScope {
+    Global,
+    Tenant,
+    User,
+    Ip,
+    Route
+});
+enum_conversions!(RateLimitStrategy {
+    Reject,
+    Queue,
+    Degrade
+});
+enum_conversions!(CircuitBreakerScope {
+    Global,
+    PerEndpoint
+});
+enum_conversions!(HttpMethod {
+    Get,
+    Post,
+    Put,
+    Delete,
+    Patch
+});
+enum_conversions!(PathSuffixMode { Disabled, Append });
+
+impl From<domain::Server> for Server {
+    fn from(v: domain::Server) -> Self {
+        Self {
+            endpoints: v
+                .endpoints
+                .into_iter()
+                .map(|e| Endpoint {
+                    scheme: e.scheme.into(),
+                    host: e.host,
+                    port: e.port,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<Server> for domain::Server {
+    fn from(v: Server) -> Self {
+        Self {
+            endpoints: v
+                .endpoints
+                .into_iter()
+                .map(|e| domain::Endpoint {
+                    scheme: e.scheme.into(),
+                    host: e.host,
+                    port: e.port,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<domain::AuthConfig> for AuthConfig {
+    fn from(v: domain::AuthConfig) -> Self {
+        Self {
+            plugin_type: v.plugin_type,
+            sharing: v.sharing.into(),
+            config: v.config,
+        }
+    }
+}
+
+impl From<AuthConfig> for domain::AuthConfig {
+    fn from(v: AuthConfig) -> Self {
+        Self {
+            plugin_type: v.plugin_type,
+            sharing: v.sharing.into(),
+            config: v.config,
+        }
+    }
+}
+
+impl From<domain::HeadersConfig> for HeadersConfig {
+    fn from(v: domain::HeadersConfig) -> Self {
+        Self {
+            request: v.request.map(|r| RequestHeaderRules {
+                set: r.set,
+                add: r.add,
+                remove: r.remove,
+                passthrough: r.passthrough.into(),
+                passthrough_allowlist: r.passthrough_allowlist,
+            }),
+            response: v.response.map(|r| ResponseHeaderRules {
+                set: r.set,
+                add: r.add,
+                remove: r.remove,
+            }),
+        }
+    }
+}
+
+impl From<HeadersConfig> for domain::HeadersConfig {
+    fn from(v: HeadersConfig) -> Self {
+        Self {
+            request: v.request.map(|r| domain::RequestHeaderRules {
+                set: r.set,
+                add: r.add,
+                remove: r.remove,
+                passthrough: r.passthrough.into(),
+                passthrough_allowlist: r.passthrough_allowlist,
+            }),
+            response: v.response.map(|r| domain::ResponseHeaderRules {
+                set: r.set,
+                add: r.add,
+                remove: r.remove,
+            }),
+        }
+    }
+}
+
+impl From<domain::PluginsConfig> for PluginsConfig {
+    fn from(v: domain::PluginsConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            items: v.items,
+            config: v.config,
+        }
+    }
+}
+
+impl From<PluginsConfig> for domain::PluginsConfig {
+    fn from(v: PluginsConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            items: v.items,
+            config: v.config,
+        }
+    }
+}
+
+impl From<domain::RateLimitConfig> for RateLimitConfig {
+    fn from(v: domain::RateLimitConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            algorithm: v.algorithm.into(),
+            sustained: SustainedRate {
+                rate: v.sustained.rate,
+                window: v.sustained.window.into(),
+            },
+            burst: v.burst.map(|b| BurstConfig {
+                capacity: b.capacity,
+            }),
+            scope: v.scope.into(),
+            strategy: v.strategy.into(),
+            queue: v.queue.map(|q| QueueConfig {
+                max_depth: q.max_depth,
+                max_wait_ms: q.max_wait_ms,
+            }),
+            cost: v.cost,
+        }
+    }
+}
+
+impl From<RateLimitConfig> for domain::RateLimitConfig {
+    fn from(v: RateLimitConfig) -> Self {
+        Self {
+            sharing: v.sharing.into(),
+            algorithm: v.algorithm.into(),
+            sustained: domain::SustainedRate {
+                rate: v.sustained.rate,
+                window: v.sustained.window.into(),
+            },
+            burst: v.burst.map(|b| domain::BurstConfig {
+                capacity: b.capacity,
+            }),
+            scope: v.scope.into(),
+            strategy: v.strategy.into(),
+            queue: v.queue.map(|q| domain::QueueConfig {
+                max_depth: q.max_depth,
+                max_wait_ms: q.max_wait_ms,
+            }),
+            cost: v.cost,
+        }
+    }
+}
+
+impl From<domain::CircuitBreakerConfig> for CircuitBreakerConfig {
+    fn from(v: domain::CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            failure_rate: v.failure_rate.map(|r| FailureRateConfig {
+                threshold_percent: r.threshold_percent,
+                minimum_requests: r.minimum_requests,
+                window_seconds: r.window_seconds,
+            }),
+            success_threshold: v.success_threshold,
+            timeout_seconds: v.timeout_seconds,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: FailureConditions {
+                status_codes: v.failure_conditions.status_codes,
+                timeout: v.failure_conditions.timeout,
+                connection_error: v.failure_conditions.connection_error,
+            },
+            scope: v.scope.into(),
+        }
+    }
+}
+
+impl From<CircuitBreakerConfig> for domain::CircuitBreakerConfig {
+    fn from(v: CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            failure_rate: v.failure_rate.map(|r| domain::FailureRateConfig {
+                threshold_percent: r.threshold_percent,
+                minimum_requests: r.minimum_requests,
+                window_seconds: r.window_seconds,
+            }),
+            success_threshold: v.success_threshold,
+            timeout_seconds: v.timeout_seconds,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: domain::FailureConditions {
+                status_codes: v.failure_conditions.status_codes,
+                timeout: v.failure_conditions.timeout,
+                connection_error: v.failure_conditions.connection_error,
+            },
+            scope: v.scope.into(),
+        }
+    }
+}
+
+impl From<domain::CacheConfig> for CacheConfig {
+    fn from(v: domain::CacheConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            ttl_seconds: v.ttl_seconds,
+            cache_post: v.cache_post,
+            max_entry_bytes: v.max_entry_bytes,
+            vary_headers: v.vary_headers,
+        }
+    }
+}
+
+impl From<CacheConfig> for domain::CacheConfig {
+    fn from(v: CacheConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            ttl_seconds: v.ttl_seconds,
+            cache_post: v.cache_post,
+            max_entry_bytes: v.max_entry_bytes,
+            vary_headers: v.vary_headers,
+        }
+    }
+}
+
+impl From<domain::MatchRules> for MatchRules {
+    fn from(v: domain::MatchRules) -> Self {
+        Self {
+            http: v.http.map(|h| HttpMatch {
+                methods: h.methods.into_iter().map(Into::into).collect(),
+                path: h.path,
+                query_allowlist: h.query_allowlist,
+                path_suffix_mode: h.path_suffix_mode.into(),
+            }),
+            grpc: v.grpc.map(|g| GrpcMatch {
+                service: g.service,
+                method: g.method,
+            }),
+        }
+    }
+}
+
+impl From<MatchRules> for domain::MatchRules {
+    fn from(v: MatchRules) -> Self {
+        Self {
+            http: v.http.map(|h| domain::HttpMatch {
+                methods: h.methods.into_iter().map(Into::into).collect(),
+                path: h.path,
+                query_allowlist: h.query_allowlist,
+                path_suffix_mode: h.path_suffix_mode.into(),
+            }),
+            grpc: v.grpc.map(|g| domain::GrpcMatch {
+                service: g.service,
+                method: g.method,
+            }),
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let statements = match backend {
+            sea_orm::DatabaseBackend::Postgres => POSTGRES_UP,
+            sea_orm::DatabaseBackend::MySql => MYSQL_UP,
+            sea_orm::DatabaseBackend::Sqlite => SQLITE_UP,
+        };
+
+        // One statement per call: MySQL rejects multi-statement strings.
+        for sql in statements {
+            conn.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        for sql in DOWN {
+            conn.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+}
+
+const DOWN: &[&str] = &[
+    "DROP TABLE IF EXISTS oagw_route",
+    "DROP TABLE IF EXISTS oagw_upstream",
+];
+
+const POSTGRES_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS oagw_upstream (
+    id          UUID PRIMARY KEY NOT NULL,
+    tenant_id   UUID NOT NULL,
+    alias       VARCHAR(255) NOT NULL,
+    protocol    VARCHAR(255) NOT NULL,
+    enabled     BOOLEAN NOT NULL DEFAULT TRUE,
+    server      JSONB NOT NULL,
+    auth        JSONB,
+    headers     JSONB,
+    plugins     JSONB,
+    rate_limit  JSONB,
+    tags        JSONB NOT NULL DEFAULT '[]',
+    created_at  TIMESTAMPTZ NOT NULL,
+    updated_at  TIMESTAMPTZ NOT NULL
+)
+",
+    r"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_oagw_upstream_tenant_alias
+    ON oagw_upstream (tenant_id, alias)
+",
+    r"
+CREATE TABLE IF NOT EXISTS oagw_route (
+    id           UUID PRIMARY KEY NOT NULL,
+    tenant_id    UUID NOT NULL,
+    upstream_id  UUID NOT NULL REFERENCES oagw_upstream(id) ON DELETE CASCADE,
+    enabled      BOOLEAN NOT NULL DEFAULT TRUE,
+    priority     INT NOT NULL DEFAULT 0,
+    match_type   VARCHAR(16) NOT NULL,
+    path_prefix  TEXT,
+    grpc_service VARCHAR(255),
+    grpc_method  VARCHAR(255),
+    match_rules  JSONB NOT NULL,
+    plugins      JSONB,
+    rate_limit   JSONB,
+    tags         JSONB NOT NULL DEFAULT '[]',
+    created_at   TIMESTAMPTZ NOT NULL,
+    updated_at   TIMESTAMPTZ NOT NULL
+)
+",
+    r"
+CREATE INDEX IF NOT EXISTS idx_oagw_route_selection
+    ON oagw_route (upstream_id, enabled, match_type, priority)
+",
+];
+
+const MYSQL_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS oagw_upstream (
+    id          VARCHAR(36) PRIMARY KEY NOT NULL,
+    tenant_id   VARCHAR(36) NOT NULL,
+    alias       VARCHAR(255) NOT NULL,
+    protocol    VARCHAR(255) NOT NULL,
+    enabled     BOOLEAN NOT NULL DEFAULT TRUE,
+    server      JSON NOT NULL,
+    auth        JSON,
+    headers     JSON,
+    plugins     JSON,
+    rate_limit  JSON,
+    tags        JSON NOT NULL,
+    created_at  TIMESTAMP(6) NOT NULL,
+    updated_at  TIMESTAMP(6) NOT NULL,
+    UNIQUE KEY uq_oagw_upstream_tenant_alias (tenant_id, alias)
+)
+",
+    r"
+CREATE TABLE IF NOT EXISTS oagw_route (
+    id           VARCHAR(36) PRIMARY KEY NOT NULL,
+    tenant_id    VARCHAR(36) NOT NULL,
+    upstream_id  VARCHAR(36) NOT NULL,
+    enabled      BOOLEAN NOT NULL DEFAULT TRUE,
+    priority     INT NOT NULL DEFAULT 0,
+    match_type   VARCHAR(16) NOT NULL,
+    path_prefix  TEXT,
+    grpc_service VARCHAR(255),
+    grpc_method  VARCHAR(255),
+    match_rules  JSON NOT NULL,
+    plugins      JSON,
+    rate_limit   JSON,
+    tags         JSON NOT NULL,
+    created_at   TIMESTAMP(6) NOT NULL,
+    updated_at   TIMESTAMP(6) NOT NULL,
+    KEY idx_oagw_route_selection (upstream_id, enabled, match_type, priority),
+    CONSTRAINT fk_oagw_route_upstream FOREIGN KEY (upstream_id)
+        REFERENCES oagw_upstream(id) ON DELETE CASCADE
+)
+",
+];
+
+const SQLITE_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS oagw_upstream (
+    id          TEXT PRIMARY KEY NOT NULL,
+    tenant_id   TEXT NOT NULL,
+    alias       TEXT NOT NULL,
+    protocol    TEXT NOT NULL,
+    enabled     INTEGER NOT NULL DEFAULT 1,
+    server      TEXT NOT NULL,
+    auth        TEXT,
+    headers     TEXT,
+    plugins     TEXT,
+    rate_limit  TEXT,
+    tags        TEXT NOT NULL DEFAULT '[]',
+    created_at  TEXT NOT NULL,
+    updated_at  TEXT NOT NULL
+)
+",
+    r"
+CREATE UNIQUE INDEX IF NOT EXISTS uq_oagw_upstream_tenant_alias
+    ON oagw_upstream (tenant_id, alias)
+",
+    r"
+CREATE TABLE IF NOT EXISTS oagw_route (
+    id           TEXT PRIMARY KEY NOT NULL,
+    tenant_id    TEXT NOT NULL,
+    upstream_id  TEXT NOT NULL REFERENCES oagw_upstream(id) ON DELETE CASCADE,
+    enabled      INTEGER NOT NULL DEFAULT 1,
+    priority     INTEGER NOT NULL DEFAULT 0,
+    match_type   TEXT NOT NULL,
+    path_prefix  TEXT,
+    grpc_service TEXT,
+    grpc_method  TEXT,
+    match_rules  TEXT NOT NULL,
+    plugins      TEXT,
+    rate_limit   TEXT,
+    tags         TEXT NOT NULL DEFAULT '[]',
+    created_at   TEXT NOT NULL,
+    updated_at   TEXT NOT NULL
+)
+",
+    r"
+CREATE INDEX IF NOT EXISTS idx_oagw_route_selection
+    ON oagw_route (upstream_id, enabled, match_type, priority)
+",
+];
@@ -0,0 +1,27 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+/// Adds the per-route response cache configuration column.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let sql = match manager.get_database_backend() {
+            sea_orm::DatabaseBackend::Postgres => "ALTER TABLE oagw_route ADD COLUMN cache JSONB",
+            sea_orm::DatabaseBackend::MySql => "ALTER TABLE oagw_route ADD COLUMN cache JSON",
+            sea_orm::DatabaseBackend::Sqlite => "ALTER TABLE oagw_route ADD COLUMN cache TEXT",
+        };
+        manager.get_connection().execute_unprepared(sql).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE oagw_route DROP COLUMN cache")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+use sea_orm_migration::prelude::*;
+
+pub mod initial_001;
+pub mod m20261018_000002_circuit_breaker;
+pub mod m20261018_000003_route_cache;
+
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(initial_001::Migration),
+            Box::new(m20261018_000002_circuit_breaker::Migration),
+            Box::new(m20261018_000003_route_cache::Migration),
+        ]
+    }
+}
@@ -0,0 +1,60 @@
+//! SQL-backed repositories on top of the `modkit-db` secure ORM.
+//!
+//! Every query is scoped to the caller's tenant through [`AccessScope`], so a
+//! repository method can never read or modify another tenant's rows even if
+//! handed a foreign id.
+
+pub(crate) mod entity;
+mod json;
+pub(crate) mod migrations;
+mod route_repo;
+mod upstream_repo;
+
+pub(crate) use route_repo::SqlRouteRepo;
+pub(crate) use upstream_repo::SqlUpstreamRepo;
+
+use modkit_db::DbError;
+use modkit_db::secure::ScopeError;
+
+use crate::domain::repo::RepositoryError;
+
+fn db_err(e: DbError) -> RepositoryError {
+    RepositoryError::Internal(format!("database error: {e}"))
+}
+
+/// Map secure-ORM errors; unique-index violations surface as `Conflict`.
+fn scope_err(e: ScopeError, conflict: impl FnOnce() -> String) -> RepositoryError {
+    match e {
+        ScopeError::Db(db) => match db.sql_err() {
+            Some(sea_orm::SqlErr::UniqueConstraintViolation(_)) => {
+                RepositoryError::Conflict(conflict())
+            }
+            _ => RepositoryError::Internal(format!("database error: {db}")),
+        },
+        other => RepositoryError::Internal(other.to_string()),
+    }
+}
+
+fn json_err(column: &str, e: &serde_json::Error) -> RepositoryError {
+    RepositoryError::Internal(format!("invalid stored {column}: {e}"))
+}
+
+#[cfg(test)]
+pub(super) async fn test_provider() -> modkit_db::DBProvider<DbError> {
+    use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::{ConnectOpts, connect_db};
+    use sea_orm_migration::MigratorTrait;
+
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db("sqlite::memory:", opts)
+        .await
+        .expect("connect in-memory database");
+    run_migrations_for_testing(&db, migrations::Migrator::migrations())
+        .await
+        .expect("run migrations");
+    modkit_db::DBProvider::new(db)
+}
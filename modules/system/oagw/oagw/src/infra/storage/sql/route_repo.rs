@@ -0,0 +1,540 @@
+use async_trait::async_trait;
+use modkit_db::secure::{
+    DBRunner, ScopeError, SecureDeleteExt, SecureEntityExt, secure_insert, secure_update_with_scope,
+};
+use modkit_db::{DBProvider, DbError};
+use modkit_security::AccessScope;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveValue, Condition, EntityTrait, Order, Set};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::entity::route::{ActiveModel, Column, Entity, MATCH_TYPE_GRPC, MATCH_TYPE_HTTP, Model};
+use super::json::{self, from_json, to_json};
+use super::{db_err, json_err, scope_err};
+use crate::domain::model::{ListQuery, Route};
+use crate::domain::repo::{RepositoryError, RouteRepository};
+use crate::infra::storage::matching::{select_grpc_route, select_http_route};
+
+/// Route repository persisted in the module database.
+pub(crate) struct SqlRouteRepo {
+    db: DBProvider<DbError>,
+}
+
+impl SqlRouteRepo {
+    #[must_use]
+    pub(crate) fn new(db: DBProvider<DbError>) -> Self {
+        Self { db }
+    }
+
+    async fn find_all(
+        &self,
+        conn: &impl DBRunner,
+        tenant_id: Uuid,
+        filter: Condition,
+        query: Option<&ListQuery>,
+    ) -> Result<Vec<Route>, RepositoryError> {
+        let mut select = Entity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .filter(filter);
+        select = match query {
+            Some(q) => select
+                .order_by(Column::Id, Order::Asc)
+                .offset(u64::from(q.skip))
+                .limit(u64::from(q.top)),
+            // Creation order, so that route selection breaks full ties the
+            // same way as the in-memory repository.
+            None => select
+                .order_by(Column::CreatedAt, Order::Asc)
+                .order_by(Column::Id, Order::Asc),
+        };
+        select
+            .all(conn)
+            .await
+            .map_err(|e| scope_err(e, String::new))?
+            .into_iter()
+            .map(to_domain)
+            .collect()
+    }
+}
+
+fn not_found(id: Uuid) -> RepositoryError {
+    RepositoryError::NotFound {
+        entity: "route",
+        id,
+    }
+}
+
+fn to_active_model(r: Route) -> ActiveModel {
+    let (match_type, path_prefix) = match &r.match_rules.http {
+        Some(http) => (MATCH_TYPE_HTTP, Some(http.path.clone())),
+        None => (MATCH_TYPE_GRPC, None),
+    };
+    let (grpc_service, grpc_method) = r
+        .match_rules
+        .grpc
+        .as_ref()
+        .map(|g| (g.service.clone(), g.method.clone()))
+        .unzip();
+
+    ActiveModel {
+        id: Set(r.id),
+        tenant_id: Set(r.tenant_id),
+        upstream_id: Set(r.upstream_id),
+        enabled: Set(r.enabled),
+        priority: Set(r.priority),
+        match_type: Set(match_type.to_owned()),
+        path_prefix: Set(path_prefix),
+        grpc_service: Set(grpc_service),
+        grpc_method: Set(grpc_method),
+        match_rules: Set(to_json::<json::MatchRules, _>(r.match_rules)),
+        plugins: Set(r.plugins.map(to_json::<json::PluginsConfig, _>)),
+        rate_limit: Set(r.rate_limit.map(to_json::<json::RateLimitConfig, _>)),
+        cache: Set(r.cache.map(to_json::<json::CacheConfig, _>)),
+        tags: Set(serde_json::Value::from(r.tags)),
+        created_at: ActiveValue::NotSet,
+        updated_at: ActiveValue::NotSet,
+    }
+}
+
+fn to_domain(m: Model) -> Result<Route, RepositoryError> {
+    Ok(Route {
+        id: m.id,
+        tenant_id: m.tenant_id,
+        upstream_id: m.upstream_id,
+        match_rules: from_json::<json::MatchRules, _>(m.match_rules)
+            .map_err(|e| json_err("match_rules", &e))?,
+        plugins: m
+            .plugins
+            .map(from_json::<json::PluginsConfig, _>)
+            .transpose()
+            .map_err(|e| json_err("plugins", &e))?,
+        rate_limit: m
+            .rate_limit
+            .map(from_json::<json::RateLimitConfig, _>)
+            .transpose()
+            .map_err(|e| json_err("rate_limit", &e))?,
+        cache: m
+            .cache
+            .map(from_json::<json::CacheConfig, _>)
+            .transpose()
+            .map_err(|e| json_err("cache", &e))?,
+        tags: serde_json::from_value(m.tags).map_err(|e| json_err("tags", &e))?,
+        priority: m.priority,
+        enabled: m.enabled,
+    })
+}
+
+#[async_trait]
+impl RouteRepository for SqlRouteRepo {
+    async fn create(&self, route: Route) -> Result<Route, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let scope = AccessScope::for_tenant(route.tenant_id);
+        let now = OffsetDateTime::now_utc();
+
+        let mut am = to_active_model(route.clone());
+        am.created_at = Set(now);
+        am.updated_at = Set(now);
+
+        secure_insert::<Entity>(am, &scope, &conn)
+            .await
+            .map_err(|e| scope_err(e, || format!("route '{}' already exists", route.id)))?;
+        Ok(route)
+    }
+
+    async fn get_by_id(&self, tenant_id: Uuid, id: Uuid) -> Result<Route, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        self.find_all(
+            &conn,
+            tenant_id,
+            Condition::all().add(Expr::col(Column::Id).eq(id)),
+            None,
+        )
+        .await?
+        .pop()
+        .ok_or_else(|| not_found(id))
+    }
+
+    async fn list_by_upstream(
+        &self,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+        query: &ListQuery,
+    ) -> Result<Vec<Route>, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        self.find_all(
+            &conn,
+            tenant_id,
+            Condition::all().add(Expr::col(Column::UpstreamId).eq(upstream_id)),
+            Some(query),
+        )
+        .await
+    }
+
+    async fn find_matching(
+        &self,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+        method: &str,
+        path: &str,
+    ) -> Result<Route, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        // Narrow to the upstream's enabled HTTP routes in SQL; prefix and
+        // method matching run in Rust so both repositories share one
+        // definition of "best match".
+        let candidates = self
+            .find_all(
+                &conn,
+                tenant_id,
+                Condition::all()
+                    .add(Expr::col(Column::UpstreamId).eq(upstream_id))
+                    .add(Expr::col(Column::Enabled).eq(true))
+                    .add(Expr::col(Column::MatchType).eq(MATCH_TYPE_HTTP)),
+                None,
+            )
+            .await?;
+
+        select_http_route(candidates, method, path).ok_or(not_found(Uuid::nil()))
+    }
+
+    async fn find_grpc_matching(
+        &self,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+        service: &str,
+        method: &str,
+    ) -> Result<Route, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let candidates = self
+            .find_all(
+                &conn,
+                tenant_id,
+                Condition::all()
+                    .add(Expr::col(Column::UpstreamId).eq(upstream_id))
+                    .add(Expr::col(Column::Enabled).eq(true))
+                    .add(Expr::col(Column::MatchType).eq(MATCH_TYPE_GRPC))
+                    .add(Expr::col(Column::GrpcService).eq(service))
+                    .add(Expr::col(Column::GrpcMethod).eq(method)),
+                None,
+            )
+            .await?;
+
+        select_grpc_route(candidates, service, method).ok_or(not_found(Uuid::nil()))
+    }
+
+    async fn update(&self, route: Route) -> Result<Route, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let scope = AccessScope::for_tenant(route.tenant_id);
+        let id = route.id;
+
+        let mut am = to_active_model(route.clone());
+        am.updated_at = Set(OffsetDateTime::now_utc());
+
+        secure_update_with_scope::<Entity>(am, &scope, id, &conn)
+            .await
+            .map_err(|e| match e {
+                // The row is missing or belongs to another tenant.
+                ScopeError::Denied(_) => not_found(id),
+                e => scope_err(e, String::new),
+            })?;
+        Ok(route)
+    }
+
+    async fn delete(&self, tenant_id: Uuid, id: Uuid) -> Result<(), RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let result = Entity::delete_many()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .filter(Condition::all().add(Expr::col(Column::Id).eq(id)))
+            .exec(&conn)
+            .await
+            .map_err(|e| scope_err(e, String::new))?;
+
+        if result.rows_affected == 0 {
+            return Err(not_found(id));
+        }
+        Ok(())
+    }
+
+    async fn delete_by_upstream(
+        &self,
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+    ) -> Result<u64, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let result = Entity::delete_many()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .filter(Condition::all().add(Expr::col(Column::UpstreamId).eq(upstream_id)))
+            .exec(&conn)
+            .await
+            .map_err(|e| scope_err(e, String::new))?;
+        Ok(result.rows_affected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use modkit_db::DBProvider;
+
+    use crate::domain::model::{
+        CacheConfig, Endpoint, GrpcMatch, HttpMatch, HttpMethod, MatchRules, PathSuffixMode,
+        Scheme, Server, Upstream,
+    };
+    use crate::domain::repo::UpstreamRepository;
+    use crate::infra::storage::SqlUpstreamRepo;
+
+    use super::super::test_provider;
+    use super::*;
+
+    async fn setup(tenant_id: Uuid) -> (SqlRouteRepo, Uuid) {
+        let db: DBProvider<DbError> = test_provider().await;
+        let upstream_id = create_upstream(&db, tenant_id, "svc").await;
+        (SqlRouteRepo::new(db), upstream_id)
+    }
+
+    async fn create_upstream(db: &DBProvider<DbError>, tenant_id: Uuid, alias: &str) -> Uuid {
+        let upstream = Upstream {
+            id: Uuid::new_v4(),
+            tenant_id,
+            alias: alias.into(),
+            server: Server {
+                endpoints: vec![Endpoint {
+                    scheme: Scheme::Https,
+                    host: "api.example.com".into(),
+                    port: 443,
+                }],
+            },
+            protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
+            enabled: true,
+            auth: None,
+            headers: None,
+            plugins: None,
+            rate_limit: None,
+            circuit_breaker: None,
+            tags: vec![],
+        };
+        SqlUpstreamRepo::new(db.clone())
+            .create(upstream)
+            .await
+            .unwrap()
+            .id
+    }
+
+    fn make_route(
+        tenant_id: Uuid,
+        upstream_id: Uuid,
+        methods: Vec<HttpMethod>,
+        path: &str,
+        priority: i32,
+    ) -> Route {
+        Route {
+            id: Uuid::new_v4(),
+            tenant_id,
+            upstream_id,
+            match_rules: MatchRules {
+                http: Some(HttpMatch {
+                    methods,
+                    path: path.into(),
+                    query_allowlist: vec!["version".into()],
+                    path_suffix_mode: PathSuffixMode::Append,
+                }),
+                grpc: None,
+            },
+            plugins: None,
+            rate_limit: None,
+            cache: None,
+            tags: vec!["chat".into()],
+            priority,
+            enabled: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn create_and_get_round_trip() {
+        let tenant = Uuid::new_v4();
+        let (repo, upstream) = setup(tenant).await;
+        let mut r = make_route(tenant, upstream, vec![HttpMethod::Post], "/v1/chat", 3);
+        r.cache = Some(CacheConfig {
+            cache_post: true,
+            vary_headers: vec!["accept-language".into()],
+            ..CacheConfig::default()
+        });
+
+        repo.create(r.clone()).await.unwrap();
+        assert_eq!(repo.get_by_id(tenant, r.id).await.unwrap(), r);
+    }
+
+    #[tokio::test]
+    async fn grpc_route_round_trip() {
+        let tenant = Uuid::new_v4();
+        let (repo, upstream) = setup(tenant).await;
+        let mut r = make_route(tenant, upstream, vec![], "/", 0);
+        r.match_rules = MatchRules {
+            http: None,
+            grpc: Some(GrpcMatch {
+                service: "helloworld.Greeter".into(),
+                method: "SayHello".into(),
+            }),
+        };
+
+        repo.create(r.clone()).await.unwrap();
+        assert_eq!(repo.get_by_id(tenant, r.id).await.unwrap(), r);
+
+        // gRPC routes never take part in HTTP matching.
+        assert!(matches!(
+            repo.find_matching(tenant, upstream, "POST", "/helloworld.Greeter/SayHello")
+                .await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        let found = repo
+            .find_grpc_matching(tenant, upstream, "helloworld.Greeter", "SayHello")
+            .await
+            .unwrap();
+        assert_eq!(found.id, r.id);
+        assert!(matches!(
+            repo.find_grpc_matching(tenant, upstream, "helloworld.Greeter", "SayGoodbye")
+                .await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn find_matching_longest_prefix_wins() {
+        let tenant = Uuid::new_v4();
+        let (repo, upstream) = setup(tenant).await;
+
+        let short = make_route(tenant, upstream, vec![HttpMethod::Post], "/v1", 0);
+        let long = make_route(tenant, upstream, vec![HttpMethod::Post], "/v1/chat", 0);
+        repo.create(short).await.unwrap();
+        repo.create(long.clone()).await.unwrap();
+
+        let matched = repo
+            .find_matching(tenant, upstream, "POST", "/v1/chat/completions")
+            .await
+            .unwrap();
+        assert_eq!(matched.id, long.id);
+    }
+
+    #[tokio::test]
+    async fn find_matching_priority_breaks_ties() {
+        let tenant = Uuid::new_v4();
+        let (repo, upstream) = setup(tenant).await;
+
+        let low = make_route(tenant, upstream, vec![HttpMethod::Get], "/v1", 1);
+        let high = make_route(tenant, upstream, vec![HttpMethod::Get], "/v1", 10);
+        repo.create(low).await.unwrap();
+        repo.create(high.clone()).await.unwrap();
+
+        let matched = repo
+            .find_matching(tenant, upstream, "GET", "/v1/models")
+            .await
+            .unwrap();
+        assert_eq!(matched.id, high.id);
+    }
+
+    #[tokio::test]
+    async fn find_matching_skips_disabled_and_wrong_method() {
+        let tenant = Uuid::new_v4();
+        let (repo, upstream) = setup(tenant).await;
+
+        let mut disabled = make_route(tenant, upstream, vec![HttpMethod::Get], "/v1/models", 0);
+        disabled.enabled = false;
+        repo.create(disabled).await.unwrap();
+        repo.create(make_route(
+            tenant,
+            upstream,
+            vec![HttpMethod::Post],
+            "/v1",
+            0,
+        ))
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            repo.find_matching(tenant, upstream, "GET", "/v1/models")
+                .await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn tenant_isolation() {
+        let owner = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let (repo, upstream) = setup(owner).await;
+        let r = make_route(owner, upstream, vec![HttpMethod::Get], "/v1", 0);
+        repo.create(r.clone()).await.unwrap();
+
+        assert!(matches!(
+            repo.get_by_id(other, r.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        assert!(matches!(
+            repo.find_matching(other, upstream, "GET", "/v1").await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        assert!(matches!(
+            repo.delete(other, r.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        assert_eq!(repo.delete_by_upstream(other, upstream).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn update_and_list_by_upstream() {
+        let tenant = Uuid::new_v4();
+        let (repo, upstream) = setup(tenant).await;
+        let mut r = make_route(tenant, upstream, vec![HttpMethod::Get], "/v1", 0);
+        repo.create(r.clone()).await.unwrap();
+        for i in 0..3 {
+            repo.create(make_route(
+                tenant,
+                upstream,
+                vec![HttpMethod::Get],
+                &format!("/v{i}"),
+                0,
+            ))
+            .await
+            .unwrap();
+        }
+
+        r.priority = 7;
+        r.enabled = false;
+        repo.update(r.clone()).await.unwrap();
+        assert_eq!(repo.get_by_id(tenant, r.id).await.unwrap(), r);
+
+        let page = repo
+            .list_by_upstream(tenant, upstream, &ListQuery { top: 2, skip: 0 })
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 2);
+        let all = repo
+            .list_by_upstream(tenant, upstream, &ListQuery { top: 10, skip: 0 })
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn delete_by_upstream_removes_only_its_routes() {
+        let tenant = Uuid::new_v4();
+        let db = test_provider().await;
+        let u1 = create_upstream(&db, tenant, "one").await;
+        let u2 = create_upstream(&db, tenant, "two").await;
+        let repo = SqlRouteRepo::new(db);
+
+        repo.create(make_route(tenant, u1, vec![HttpMethod::Get], "/a", 0))
+            .await
+            .unwrap();
+        repo.create(make_route(tenant, u1, vec![HttpMethod::Get], "/b", 0))
+            .await
+            .unwrap();
+        let keep = make_route(tenant, u2, vec![HttpMethod::Get], "/c", 0);
+        repo.create(keep.clone()).await.unwrap();
+
+        assert_eq!(repo.delete_by_upstream(tenant, u1).await.unwrap(), 2);
+        assert!(repo.get_by_id(tenant, keep.id).await.is_ok());
+    }
+}
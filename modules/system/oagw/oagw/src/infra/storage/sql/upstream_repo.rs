@@ -0,0 +1,388 @@
+use async_trait::async_trait;
+use modkit_db::secure::{
+    DBRunner, ScopeError, SecureDeleteExt, SecureEntityExt, secure_insert, secure_update_with_scope,
+};
+use modkit_db::{DBProvider, DbError};
+use modkit_security::AccessScope;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveValue, Condition, EntityTrait, Order, Set};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::entity::upstream::{ActiveModel, Column, Entity, Model};
+use super::json::{self, from_json, to_json};
+use super::{db_err, json_err, scope_err};
+use crate::domain::model::{ListQuery, Upstream};
+use crate::domain::repo::{RepositoryError, UpstreamRepository};
+
+/// Upstream repository persisted in the module database.
+pub(crate) struct SqlUpstreamRepo {
+    db: DBProvider<DbError>,
+}
+
+impl SqlUpstreamRepo {
+    #[must_use]
+    pub(crate) fn new(db: DBProvider<DbError>) -> Self {
+        Self { db }
+    }
+
+    async fn find_one(
+        &self,
+        conn: &impl DBRunner,
+        tenant_id: Uuid,
+        filter: Condition,
+    ) -> Result<Option<Upstream>, RepositoryError> {
+        Entity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .filter(filter)
+            .one(conn)
+            .await
+            .map_err(|e| scope_err(e, String::new))?
+            .map(to_domain)
+            .transpose()
+    }
+}
+
+fn alias_conflict(alias: &str) -> String {
+    format!("alias '{alias}' already exists for tenant")
+}
+
+fn not_found(id: Uuid) -> RepositoryError {
+    RepositoryError::NotFound {
+        entity: "upstream",
+        id,
+    }
+}
+
+fn to_active_model(u: Upstream) -> ActiveModel {
+    ActiveModel {
+        id: Set(u.id),
+        tenant_id: Set(u.tenant_id),
+        alias: Set(u.alias),
+        protocol: Set(u.protocol),
+        enabled: Set(u.enabled),
+        server: Set(to_json::<json::Server, _>(u.server)),
+        auth: Set(u.auth.map(to_json::<json::AuthConfig, _>)),
+        headers: Set(u.headers.map(to_json::<json::HeadersConfig, _>)),
+        plugins: Set(u.plugins.map(to_json::<json::PluginsConfig, _>)),
+        rate_limit: Set(u.rate_limit.map(to_json::<json::RateLimitConfig, _>)),
+        circuit_breaker: Set(u
+            .circuit_breaker
+            .map(to_json::<json::CircuitBreakerConfig, _>)),
+        tags: Set(serde_json::Value::from(u.tags)),
+        created_at: ActiveValue::NotSet,
+        updated_at: ActiveValue::NotSet,
+    }
+}
+
+fn to_domain(m: Model) -> Result<Upstream, RepositoryError> {
+    Ok(Upstream {
+        id: m.id,
+        tenant_id: m.tenant_id,
+        alias: m.alias,
+        server: from_json::<json::Server, _>(m.server).map_err(|e| json_err("server", &e))?,
+        protocol: m.protocol,
+        enabled: m.enabled,
+        auth: m
+            .auth
+            .map(from_json::<json::AuthConfig, _>)
+            .transpose()
+            .map_err(|e| json_err("auth", &e))?,
+        headers: m
+            .headers
+            .map(from_json::<json::HeadersConfig, _>)
+            .transpose()
+            .map_err(|e| json_err("headers", &e))?,
+        plugins: m
+            .plugins
+            .map(from_json::<json::PluginsConfig, _>)
+            .transpose()
+            .map_err(|e| json_err("plugins", &e))?,
+        rate_limit: m
+            .rate_limit
+            .map(from_json::<json::RateLimitConfig, _>)
+            .transpose()
+            .map_err(|e| json_err("rate_limit", &e))?,
+        circuit_breaker: m
+            .circuit_breaker
+            .map(from_json::<json::CircuitBreakerConfig, _>)
+            .transpose()
+            .map_err(|e| json_err("circuit_breaker", &e))?,
+        tags: serde_json::from_value(m.tags).map_err(|e| json_err("tags", &e))?,
+    })
+}
+
+#[async_trait]
+impl UpstreamRepository for SqlUpstreamRepo {
+    async fn create(&self, upstream: Upstream) -> Result<Upstream, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let scope = AccessScope::for_tenant(upstream.tenant_id);
+        let now = OffsetDateTime::now_utc();
+
+        let mut am = to_active_model(upstream.clone());
+        am.created_at = Set(now);
+        am.updated_at = Set(now);
+
+        // Alias uniqueness is enforced by the (tenant_id, alias) unique index.
+        secure_insert::<Entity>(am, &scope, &conn)
+            .await
+            .map_err(|e| scope_err(e, || alias_conflict(&upstream.alias)))?;
+        Ok(upstream)
+    }
+
+    async fn get_by_id(&self, tenant_id: Uuid, id: Uuid) -> Result<Upstream, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        self.find_one(
+            &conn,
+            tenant_id,
+            Condition::all().add(Expr::col(Column::Id).eq(id)),
+        )
+        .await?
+        .ok_or_else(|| not_found(id))
+    }
+
+    async fn get_by_alias(
+        &self,
+        tenant_id: Uuid,
+        alias: &str,
+    ) -> Result<Upstream, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        self.find_one(
+            &conn,
+            tenant_id,
+            Condition::all().add(Expr::col(Column::Alias).eq(alias)),
+        )
+        .await?
+        .ok_or_else(|| not_found(Uuid::nil()))
+    }
+
+    async fn list(
+        &self,
+        tenant_id: Uuid,
+        query: &ListQuery,
+    ) -> Result<Vec<Upstream>, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        Entity::find()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .order_by(Column::Id, Order::Asc)
+            .offset(u64::from(query.skip))
+            .limit(u64::from(query.top))
+            .all(&conn)
+            .await
+            .map_err(|e| scope_err(e, String::new))?
+            .into_iter()
+            .map(to_domain)
+            .collect()
+    }
+
+    async fn update(&self, upstream: Upstream) -> Result<Upstream, RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let scope = AccessScope::for_tenant(upstream.tenant_id);
+        let id = upstream.id;
+
+        let mut am = to_active_model(upstream.clone());
+        am.updated_at = Set(OffsetDateTime::now_utc());
+
+        secure_update_with_scope::<Entity>(am, &scope, id, &conn)
+            .await
+            .map_err(|e| match e {
+                // The row is missing or belongs to another tenant.
+                ScopeError::Denied(_) => not_found(id),
+                e => scope_err(e, || alias_conflict(&upstream.alias)),
+            })?;
+        Ok(upstream)
+    }
+
+    async fn delete(&self, tenant_id: Uuid, id: Uuid) -> Result<(), RepositoryError> {
+        let conn = self.db.conn().map_err(db_err)?;
+        let result = Entity::delete_many()
+            .secure()
+            .scope_with(&AccessScope::for_tenant(tenant_id))
+            .filter(Condition::all().add(Expr::col(Column::Id).eq(id)))
+            .exec(&conn)
+            .await
+            .map_err(|e| scope_err(e, String::new))?;
+
+        if result.rows_affected == 0 {
+            return Err(not_found(id));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::domain::model::{
+        AuthConfig, CircuitBreakerConfig, CircuitBreakerScope, Endpoint, FailureRateConfig,
+        HeadersConfig, QueueConfig, RateLimitAlgorithm, RateLimitConfig, RateLimitScope,
+        RateLimitStrategy, RequestHeaderRules, Scheme, Server, SharingMode, SustainedRate, Window,
+    };
+
+    use super::super::test_provider;
+    use super::*;
+
+    fn make_upstream(tenant_id: Uuid, alias: &str) -> Upstream {
+        Upstream {
+            id: Uuid::new_v4(),
+            tenant_id,
+            alias: alias.into(),
+            server: Server {
+                endpoints: vec![Endpoint {
+                    scheme: Scheme::Https,
+                    host: "api.openai.com".into(),
+                    port: 443,
+                }],
+            },
+            protocol: "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1".into(),
+            enabled: true,
+            auth: None,
+            headers: None,
+            plugins: None,
+            rate_limit: None,
+            circuit_breaker: None,
+            tags: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn create_and_get_round_trip_with_configs() {
+        let repo = SqlUpstreamRepo::new(test_provider().await);
+        let tenant = Uuid::new_v4();
+        let mut u = make_upstream(tenant, "openai");
+        u.auth = Some(AuthConfig {
+            plugin_type: "gts.x.core.oagw.auth_plugin.v1~x.core.oagw.apikey.v1".into(),
+            sharing: SharingMode::Inherit,
+            config: Some(HashMap::from([("header".into(), "authorization".into())])),
+        });
+        u.headers = Some(HeadersConfig {
+            request: Some(RequestHeaderRules {
+                set: HashMap::from([("x-env".into(), "prod".into())]),
+                ..Default::default()
+            }),
+            response: None,
+        });
+        u.rate_limit = Some(RateLimitConfig {
+            sharing: SharingMode::Enforce,
+            algorithm: RateLimitAlgorithm::SlidingWindow,
+            sustained: SustainedRate {
+                rate: 100,
+                window: Window::Minute,
+            },
+            burst: None,
+            scope: RateLimitScope::Tenant,
+            strategy: RateLimitStrategy::Queue,
+            queue: Some(QueueConfig::default()),
+            cost: 1,
+        });
+        u.circuit_breaker = Some(CircuitBreakerConfig {
+            failure_rate: Some(FailureRateConfig::default()),
+            scope: CircuitBreakerScope::PerEndpoint,
+            ..Default::default()
+        });
+        u.tags = vec!["llm".into()];
+
+        repo.create(u.clone()).await.unwrap();
+
+        assert_eq!(repo.get_by_id(tenant, u.id).await.unwrap(), u);
+        assert_eq!(repo.get_by_alias(tenant, "openai").await.unwrap(), u);
+    }
+
+    #[tokio::test]
+    async fn alias_uniqueness_same_tenant() {
+        let repo = SqlUpstreamRepo::new(test_provider().await);
+        let tenant = Uuid::new_v4();
+
+        repo.create(make_upstream(tenant, "openai")).await.unwrap();
+        let err = repo.create(make_upstream(tenant, "openai")).await;
+        assert!(matches!(err, Err(RepositoryError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn alias_allowed_in_different_tenants() {
+        let repo = SqlUpstreamRepo::new(test_provider().await);
+
+        repo.create(make_upstream(Uuid::new_v4(), "openai"))
+            .await
+            .unwrap();
+        repo.create(make_upstream(Uuid::new_v4(), "openai"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn tenant_isolation() {
+        let repo = SqlUpstreamRepo::new(test_provider().await);
+        let owner = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let u = make_upstream(owner, "openai");
+        repo.create(u.clone()).await.unwrap();
+
+        assert!(matches!(
+            repo.get_by_id(other, u.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        assert!(matches!(
+            repo.update(Upstream {
+                tenant_id: other,
+                ..u.clone()
+            })
+            .await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        assert!(matches!(
+            repo.delete(other, u.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+        assert!(repo.get_by_id(owner, u.id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn update_and_delete() {
+        let repo = SqlUpstreamRepo::new(test_provider().await);
+        let tenant = Uuid::new_v4();
+        let mut u = make_upstream(tenant, "openai");
+        repo.create(u.clone()).await.unwrap();
+
+        u.alias = "openai-v2".into();
+        u.enabled = false;
+        repo.update(u.clone()).await.unwrap();
+        assert_eq!(repo.get_by_id(tenant, u.id).await.unwrap(), u);
+
+        repo.delete(tenant, u.id).await.unwrap();
+        assert!(matches!(
+            repo.get_by_id(tenant, u.id).await,
+            Err(RepositoryError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn list_with_pagination() {
+        let repo = SqlUpstreamRepo::new(test_provider().await);
+        let tenant = Uuid::new_v4();
+        for i in 0..5 {
+            repo.create(make_upstream(tenant, &format!("svc{i}")))
+                .await
+                .unwrap();
+        }
+        repo.create(make_upstream(Uuid::new_v4(), "foreign"))
+            .await
+            .unwrap();
+
+        let page = repo
+            .list(tenant, &ListQuery { top: 2, skip: 0 })
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 2);
+
+        let all = repo
+            .list(tenant, &ListQuery { top: 10, skip: 0 })
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 5);
+        assert!(all.iter().all(|u| u.tenant_id == tenant));
+    }
+}
@@ -178,6 +178,7 @@ mod tests {
             headers: None,
             plugins: None,
             rate_limit: None,
+            circuit_breaker: None,
             tags: vec![],
         }
     }
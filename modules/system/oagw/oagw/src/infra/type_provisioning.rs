@@ -38,6 +38,14 @@ fn default_cost() -> u32 {
     1
 }
 
+fn default_queue_max_depth() -> u32 {
+    domain::QueueConfig::default().max_depth
+}
+
+fn default_queue_max_wait_ms() -> u64 {
+    domain::QueueConfig::default().max_wait_ms
+}
+
 #[derive(Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 enum Scheme {
@@ -129,6 +137,8 @@ struct PluginsConfig {
     sharing: SharingMode,
     #[serde(default)]
     items: Vec<String>,
+    #[serde(default)]
+    config: HashMap<String, HashMap<String, String>>,
 }
 
 #[derive(Deserialize, Default)]
@@ -161,6 +171,14 @@ struct BurstConfig {
     capacity: u32,
 }
 
+#[derive(Deserialize)]
+struct QueueConfig {
+    #[serde(default = "default_queue_max_depth")]
+    max_depth: u32,
+    #[serde(default = "default_queue_max_wait_ms")]
+    max_wait_ms: u64,
+}
+
 #[derive(Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 enum RateLimitScope {
@@ -194,10 +212,110 @@ struct RateLimitConfig {
     scope: RateLimitScope,
     #[serde(default)]
     strategy: RateLimitStrategy,
+    #[serde(default)]
+    queue: Option<QueueConfig>,
     #[serde(default = "default_cost")]
     cost: u32,
 }
 
+#[derive(Deserialize)]
+#[serde(default)]
+struct FailureRateConfig {
+    threshold_percent: u32,
+    minimum_requests: u32,
+    window_seconds: u32,
+}
+
+impl Default for FailureRateConfig {
+    fn default() -> Self {
+        let d = domain::FailureRateConfig::default();
+        Self {
+            threshold_percent: d.threshold_percent,
+            minimum_requests: d.minimum_requests,
+            window_seconds: d.window_seconds,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct FailureConditions {
+    status_codes: Vec<u16>,
+    timeout: bool,
+    connection_error: bool,
+}
+
+impl Default for FailureConditions {
+    fn default() -> Self {
+        let d = domain::FailureConditions::default();
+        Self {
+            status_codes: d.status_codes,
+            timeout: d.timeout,
+            connection_error: d.connection_error,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum CircuitBreakerScope {
+    #[default]
+    Global,
+    PerEndpoint,
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct CircuitBreakerConfig {
+    enabled: bool,
+    failure_threshold: u32,
+    failure_rate: Option<FailureRateConfig>,
+    success_threshold: u32,
+    timeout_seconds: u32,
+    half_open_max_requests: u32,
+    failure_conditions: FailureConditions,
+    scope: CircuitBreakerScope,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        let d = domain::CircuitBreakerConfig::default();
+        Self {
+            enabled: d.enabled,
+            failure_threshold: d.failure_threshold,
+            failure_rate: None,
+            success_threshold: d.success_threshold,
+            timeout_seconds: d.timeout_seconds,
+            half_open_max_requests: d.half_open_max_requests,
+            failure_conditions: FailureConditions::default(),
+            scope: CircuitBreakerScope::default(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct CacheConfig {
+    enabled: bool,
+    ttl_seconds: u32,
+    cache_post: bool,
+    max_entry_bytes: u64,
+    vary_headers: Vec<String>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        let d = domain::CacheConfig::default();
+        Self {
+            enabled: d.enabled,
+            ttl_seconds: d.ttl_seconds,
+            cache_post: d.cache_post,
+            max_entry_bytes: d.max_entry_bytes,
+            vary_headers: d.vary_headers,
+        }
+    }
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 enum HttpMethod {
@@ -257,6 +375,8 @@ struct UpstreamPayload {
     #[serde(default)]
     rate_limit: Option<RateLimitConfig>,
     #[serde(default)]
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    #[serde(default)]
     tags: Vec<String>,
     #[serde(default = "default_true")]
     enabled: bool,
@@ -274,6 +394,8 @@ struct RoutePayload {
     #[serde(default)]
     rate_limit: Option<RateLimitConfig>,
     #[serde(default)]
+    cache: Option<CacheConfig>,
+    #[serde(default)]
     tags: Vec<String>,
     #[serde(default)]
     priority: i32,
@@ -413,6 +535,15 @@ impl From<BurstConfig> for domain::BurstConfig {
     }
 }
 
+impl From<QueueConfig> for domain::QueueConfig {
+    fn from(v: QueueConfig) -> Self {
+        Self {
+            max_depth: v.max_depth,
+            max_wait_ms: v.max_wait_ms,
+        }
+    }
+}
+
 impl From<RateLimitScope> for domain::RateLimitScope {
     fn from(v: RateLimitScope) -> Self {
         match v {
@@ -444,16 +575,56 @@ impl From<RateLimitConfig> for domain::RateLimitConfig {
             burst: v.burst.map(Into::into),
             scope: v.scope.into(),
             strategy: v.strategy.into(),
+            queue: v.queue.map(Into::into),
             cost: v.cost,
         }
     }
 }
 
+impl From<CircuitBreakerConfig> for domain::CircuitBreakerConfig {
+    fn from(v: CircuitBreakerConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            failure_threshold: v.failure_threshold,
+            failure_rate: v.failure_rate.map(|r| domain::FailureRateConfig {
+                threshold_percent: r.threshold_percent,
+                minimum_requests: r.minimum_requests,
+                window_seconds: r.window_seconds,
+            }),
+            success_threshold: v.success_threshold,
+            timeout_seconds: v.timeout_seconds,
+            half_open_max_requests: v.half_open_max_requests,
+            failure_conditions: domain::FailureConditions {
+                status_codes: v.failure_conditions.status_codes,
+                timeout: v.failure_conditions.timeout,
+                connection_error: v.failure_conditions.connection_error,
+            },
+            scope: match v.scope {
+                CircuitBreakerScope::Global => domain::CircuitBreakerScope::Global,
+                CircuitBreakerScope::PerEndpoint => domain::CircuitBreakerScope::PerEndpoint,
+            },
+        }
+    }
+}
+
+impl From<CacheConfig> for domain::CacheConfig {
+    fn from(v: CacheConfig) -> Self {
+        Self {
+            enabled: v.enabled,
+            ttl_seconds: v.ttl_seconds,
+            cache_post: v.cache_post,
+            max_entry_bytes: v.max_entry_bytes,
+            vary_headers: v.vary_headers,
+        }
+    }
+}
+
 impl From<PluginsConfig> for domain::PluginsConfig {
     fn from(v: PluginsConfig) -> Self {
         Self {
             sharing: v.sharing.into(),
             items: v.items,
+            config: v.config,
         }
     }
 }
@@ -520,6 +691,7 @@ impl From<UpstreamPayload> for ProvisionedUpstream {
                 headers: p.headers.map(Into::into),
                 plugins: p.plugins.map(Into::into),
                 rate_limit: p.rate_limit.map(Into::into),
+                circuit_breaker: p.circuit_breaker.map(Into::into),
                 tags: p.tags,
                 enabled: p.enabled,
             },
@@ -536,6 +708,7 @@ impl From<RoutePayload> for ProvisionedRoute {
                 match_rules: p.match_rules.into(),
                 plugins: p.plugins.map(Into::into),
                 rate_limit: p.rate_limit.map(Into::into),
+                cache: p.cache.map(Into::into),
                 tags: p.tags,
                 priority: p.priority,
                 enabled: p.enabled,
@@ -1,9 +1,9 @@
 use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
-use crate::config::OagwConfig;
+use crate::config::{OagwConfig, StorageBackend};
 use crate::domain::type_catalog::oagw_gts_entities;
-use crate::domain::type_provisioning::TypeProvisioningService;
+use crate::domain::type_provisioning::{TypeProvisioningService, provision_upstream};
 use crate::infra::type_provisioning::TypeProvisioningServiceImpl;
 use async_trait::async_trait;
 use authz_resolver_sdk::{AuthZResolverClient, PolicyEnforcer};
@@ -13,31 +13,35 @@ use modkit::contracts::SystemCapability;
 use modkit::{Module, ModuleCtx, RestApiCapability};
 use modkit_security::SecurityContext;
 use oagw_sdk::api::ServiceGatewayClientV1;
+use tenant_resolver_sdk::TenantResolverClient;
 use tracing::info;
 use types_registry_sdk::{RegisterResult, RegisterSummary, TypesRegistryClient};
 
 use crate::api::rest::routes;
+use crate::domain::model::ListQuery;
+use crate::domain::repo::{RouteRepository, UpstreamRepository};
 use crate::domain::services::{
     ControlPlaneService, ControlPlaneServiceImpl, DataPlaneService, EndpointSelector,
     ServiceGatewayClientV1Facade,
 };
 use crate::infra::proxy::DataPlaneServiceImpl;
-use crate::infra::storage::{InMemoryRouteRepo, InMemoryUpstreamRepo};
+use crate::infra::storage::{
+    InMemoryRouteRepo, InMemoryUpstreamRepo, SqlRouteRepo, SqlUpstreamRepo,
+};
 
 /// Shared application state injected into all handlers.
 #[derive(Clone)]
 pub struct AppState {
     pub(crate) cp: Arc<dyn ControlPlaneService>,
     pub(crate) dp: Arc<dyn DataPlaneService>,
-    pub(crate) backend_selector: Arc<dyn EndpointSelector>,
     pub(crate) config: crate::config::RuntimeConfig,
 }
 
 /// Outbound API Gateway module: wires repos, services, and routes.
 #[modkit::module(
     name = "oagw",
-    deps = ["types-registry", "authz-resolver", "credstore"],
-    capabilities = [system, rest]
+    deps = ["types-registry", "authz-resolver", "credstore", "tenant-resolver"],
+    capabilities = [system, rest, db]
 )]
 pub struct OutboundApiGatewayModule {
     state: arc_swap::ArcSwapOption<AppState>,
@@ -55,17 +59,44 @@ impl Default for OutboundApiGatewayModule {
     }
 }
 
+impl modkit::contracts::DatabaseCapability for OutboundApiGatewayModule {
+    fn migrations(&self) -> Vec<Box<dyn sea_orm_migration::MigrationTrait>> {
+        use sea_orm_migration::MigratorTrait;
+        info!("Providing OAGW database migrations");
+        crate::infra::storage::sql::migrations::Migrator::migrations()
+    }
+}
+
 #[async_trait]
 impl Module for OutboundApiGatewayModule {
     async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
         let cfg: OagwConfig = ctx.config()?;
-        info!("OAGW config: proxy_timeout_secs={}", cfg.proxy_timeout_secs);
+        info!(
+            "OAGW config: proxy_timeout_secs={}, storage={:?}",
+            cfg.proxy_timeout_secs, cfg.storage
+        );
 
         // -- Control Plane init --
-        let upstream_repo = Arc::new(InMemoryUpstreamRepo::new());
-        let route_repo = Arc::new(InMemoryRouteRepo::new());
-        let cp: Arc<dyn ControlPlaneService> =
-            Arc::new(ControlPlaneServiceImpl::new(upstream_repo, route_repo));
+        let (upstream_repo, route_repo): (Arc<dyn UpstreamRepository>, Arc<dyn RouteRepository>) =
+            match cfg.storage {
+                StorageBackend::Memory => (
+                    Arc::new(InMemoryUpstreamRepo::new()),
+                    Arc::new(InMemoryRouteRepo::new()),
+                ),
+                StorageBackend::Database => {
+                    let db = ctx.db_required()?;
+                    (
+                        Arc::new(SqlUpstreamRepo::new(db.clone())),
+                        Arc::new(SqlRouteRepo::new(db)),
+                    )
+                }
+            };
+        // -- Tenant hierarchy for inherited plugin chains --
+        let tenant_resolver = ctx.client_hub().get::<dyn TenantResolverClient>()?;
+        let cp: Arc<dyn ControlPlaneService> = Arc::new(
+            ControlPlaneServiceImpl::new(upstream_repo, route_repo)
+                .with_tenant_resolver(tenant_resolver),
+        );
 
         let credstore = ctx.client_hub().get::<dyn CredStoreClientV1>()?;
 
@@ -88,19 +119,24 @@ impl Module for OutboundApiGatewayModule {
         ));
         let backend_selector: Arc<dyn EndpointSelector> =
             Arc::new(crate::infra::proxy::pingora_proxy::PingoraEndpointSelector::new());
+        let grpc_transport = crate::infra::proxy::grpc::GrpcTransport::new(connect_timeout, false)?;
 
-        let dp: Arc<dyn DataPlaneService> = Arc::new(
+        let dp = Arc::new(
             DataPlaneServiceImpl::new(
                 cp.clone(),
                 credstore,
                 policy_enforcer,
-                backend_selector.clone(),
+                backend_selector,
                 proxy,
             )
             .with_request_timeout(Duration::from_secs(cfg.proxy_timeout_secs))
             .with_max_body_size(cfg.max_body_size_bytes)
+            .with_response_cache_capacity(cfg.response_cache_max_bytes)
+            .with_grpc_transport(grpc_transport)
             .with_allow_http_upstream(cfg.allow_http_upstream),
         );
+        cp.set_change_listener(Arc::downgrade(&dp) as _);
+        let dp: Arc<dyn DataPlaneService> = dp;
 
         // -- Facade (for external SDK consumers) --
         let oagw: Arc<dyn ServiceGatewayClientV1> =
@@ -143,7 +179,6 @@ impl Module for OutboundApiGatewayModule {
         let app_state = AppState {
             cp,
             dp,
-            backend_selector,
             config: (&cfg).into(),
         };
 
@@ -164,7 +199,7 @@ impl SystemCapability for OutboundApiGatewayModule {
         let provisioning: Arc<dyn TypeProvisioningService> =
             Arc::new(TypeProvisioningServiceImpl::new(registry));
 
-        // -- Materialize provisioned upstreams and routes into in-memory repos --
+        // -- Materialize provisioned upstreams and routes into the repos --
         let app_state = self
             .state
             .load()
@@ -178,17 +213,16 @@ impl SystemCapability for OutboundApiGatewayModule {
             let ctx = SecurityContext::builder()
                 .subject_tenant_id(u.tenant_id)
                 .build()?;
-            let created = app_state
-                .cp
-                .create_upstream(&ctx, u.request.clone())
+            let (upstream, outcome) = provision_upstream(app_state.cp.as_ref(), &ctx, &u.request)
                 .await
                 .map_err(|e| {
                     anyhow::anyhow!("Failed to provision upstream (tenant={}): {e}", u.tenant_id)
                 })?;
             info!(
-                id = %created.id,
+                id = %upstream.id,
                 tenant_id = %u.tenant_id,
-                alias = %created.alias,
+                alias = %upstream.alias,
+                ?outcome,
                 "Provisioned upstream from types-registry"
             );
         }
@@ -198,6 +232,31 @@ impl SystemCapability for OutboundApiGatewayModule {
             let ctx = SecurityContext::builder()
                 .subject_tenant_id(r.tenant_id)
                 .build()?;
+            let existing = app_state
+                .cp
+                .list_routes(
+                    &ctx,
+                    r.request.upstream_id,
+                    &ListQuery {
+                        top: u32::MAX,
+                        skip: 0,
+                    },
+                )
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!("Failed to list routes (tenant={}): {e}", r.tenant_id)
+                })?;
+            if existing
+                .iter()
+                .any(|route| route.match_rules == r.request.match_rules)
+            {
+                info!(
+                    tenant_id = %r.tenant_id,
+                    upstream_id = %r.request.upstream_id,
+                    "Provisioned route already exists"
+                );
+                continue;
+            }
             let created = app_state
                 .cp
                 .create_route(&ctx, r.request.clone())
@@ -229,7 +288,7 @@ impl SystemCapability for OutboundApiGatewayModule {
 impl RestApiCapability for OutboundApiGatewayModule {
     fn register_rest(
         &self,
-        _ctx: &ModuleCtx,
+        ctx: &ModuleCtx,
         router: axum::Router,
         openapi: &dyn OpenApiRegistry,
     ) -> anyhow::Result<axum::Router> {
@@ -243,7 +302,8 @@ impl RestApiCapability for OutboundApiGatewayModule {
             .as_ref()
             .clone();
 
-        let router = routes::register_routes(router, openapi, state);
+        let auditor = audit_sdk::operation_auditor(&ctx.client_hub());
+        let router = routes::register_routes(router, openapi, state, auditor.as_ref());
         Ok(router)
     }
 }
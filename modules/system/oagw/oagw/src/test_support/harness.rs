@@ -57,6 +57,7 @@ pub struct AppHarnessBuilder {
     authz_client: Option<Arc<dyn AuthZResolverClient>>,
     max_body_size: Option<usize>,
     skip_upstream_tls_verify: bool,
+    tenant_parents: Vec<(Uuid, Uuid)>,
 }
 
 impl AppHarnessBuilder {
@@ -88,10 +89,17 @@ impl AppHarnessBuilder {
         self
     }
 
+    /// Declare a tenant hierarchy as `(child, parent)` pairs (useful for
+    /// plugin inheritance tests).
+    pub fn with_tenant_hierarchy(mut self, parents: Vec<(Uuid, Uuid)>) -> Self {
+        self.tenant_parents = parents;
+        self
+    }
+
     pub async fn build(self) -> AppHarness {
         let hub = ClientHub::new();
 
-        let mut cp_builder = TestCpBuilder::new();
+        let mut cp_builder = TestCpBuilder::new().with_tenant_hierarchy(self.tenant_parents);
         if !self.credentials.is_empty() {
             cp_builder = cp_builder.with_credentials(self.credentials);
         }
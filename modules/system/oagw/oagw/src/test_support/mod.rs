@@ -15,6 +15,9 @@ pub use response::TestResponse;
 
 pub use crate::domain::gts_helpers::{format_route_gts, format_upstream_gts, parse_resource_gts};
 pub use crate::domain::test_support::{
-    APIKEY_AUTH_PLUGIN_ID, CapturingAuthZResolverClient, DenyingAuthZResolverClient, TestAppState,
-    TestCpBuilder, TestCredStoreClient, TestDpBuilder, build_test_app_state, build_test_gateway,
+    APIKEY_AUTH_PLUGIN_ID, CORS_GUARD_PLUGIN_ID, CapturingAuthZResolverClient,
+    DenyingAuthZResolverClient, LOGGING_TRANSFORM_PLUGIN_ID, METRICS_TRANSFORM_PLUGIN_ID,
+    OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID, REQUEST_ID_TRANSFORM_PLUGIN_ID, TIMEOUT_GUARD_PLUGIN_ID,
+    TestAppState, TestCpBuilder, TestCredStoreClient, TestDpBuilder, build_test_app_state,
+    build_test_gateway,
 };
@@ -0,0 +1,416 @@
+//! E2E tests for native gRPC proxying.
+//!
+//! Spins up a local TLS server that speaks HTTP/2 only and answers like a
+//! gRPC service (data frames followed by `grpc-status` trailers), configures
+//! OAGW with a `grpc` endpoint and gRPC routes, and calls it through the SDK
+//! facade so the response trailers can be inspected.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use http::{HeaderMap, HeaderValue, Method, StatusCode};
+use http_body_util::StreamBody;
+use hyper::body::{Frame, Incoming};
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use oagw::test_support::{APIKEY_AUTH_PLUGIN_ID, AppHarness};
+use oagw_sdk::error::ServiceGatewayError;
+use oagw_sdk::{
+    AuthConfig, Body, BurstConfig, CreateRouteRequest, CreateUpstreamRequest, Endpoint, GrpcMatch,
+    HeadersConfig, MatchRules, RateLimitAlgorithm, RateLimitConfig, RateLimitScope,
+    RateLimitStrategy, RequestHeaderRules, Scheme, Server, SharingMode, SustainedRate, Trailers,
+    Window,
+};
+use rcgen::generate_simple_self_signed;
+use rustls::ServerConfig;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+use tokio_rustls::TlsAcceptor;
+
+const SERVICE: &str = "helloworld.Greeter";
+
+// ---------------------------------------------------------------------------
+// gRPC-over-H2 TLS mock upstream
+// ---------------------------------------------------------------------------
+
+/// Recorded request from the gRPC mock.
+#[derive(Debug, Clone)]
+struct GrpcRecordedRequest {
+    path: String,
+    version: hyper::Version,
+    headers: HeaderMap,
+}
+
+struct GrpcMockState {
+    recorded: Mutex<Vec<GrpcRecordedRequest>>,
+}
+
+type MockFrame = Result<Frame<Bytes>, hyper::Error>;
+type MockBody = StreamBody<futures_util::stream::Iter<std::vec::IntoIter<MockFrame>>>;
+
+fn grpc_trailers(status: &'static str) -> HeaderMap {
+    let mut trailers = HeaderMap::new();
+    trailers.insert("grpc-status", HeaderValue::from_static(status));
+    trailers
+}
+
+/// Frame a message the way gRPC does: uncompressed flag + 4-byte length.
+fn grpc_message(payload: &[u8]) -> Bytes {
+    let mut framed = vec![0u8];
+    framed.extend_from_slice(&u32::try_from(payload.len()).unwrap().to_be_bytes());
+    framed.extend_from_slice(payload);
+    Bytes::from(framed)
+}
+
+/// Answer one call. `SayHello` echoes the request, `StreamGreetings` sends
+/// three messages, `Secret` fails with a Trailers-Only UNAUTHENTICATED and
+/// `Missing` with a Trailers-Only NOT_FOUND.
+fn mock_response(path: &str, request: Bytes) -> Response<MockBody> {
+    let method = path.rsplit('/').next().unwrap_or_default();
+    let trailers_only = match method {
+        "SayHello" | "StreamGreetings" => None,
+        "Secret" => Some(("16", "bad token")),
+        _ => Some(("5", "no such greeting")),
+    };
+    let frames: Vec<MockFrame> = match method {
+        "SayHello" => vec![
+            Ok(Frame::data(request)),
+            Ok(Frame::trailers(grpc_trailers("0"))),
+        ],
+        "StreamGreetings" => ["one", "two", "three"]
+            .iter()
+            .map(|m| Ok(Frame::data(grpc_message(m.as_bytes()))))
+            .chain([Ok(Frame::trailers(grpc_trailers("0")))])
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let mut builder = Response::builder()
+        .status(200)
+        .header("content-type", "application/grpc");
+    if let Some((code, message)) = trailers_only {
+        builder = builder
+            .header("grpc-status", code)
+            .header("grpc-message", message);
+    }
+    builder
+        .body(StreamBody::new(futures_util::stream::iter(frames)))
+        .unwrap()
+}
+
+/// Start a TLS server on a random port that only accepts HTTP/2 via ALPN.
+async fn start_grpc_mock() -> (SocketAddr, Arc<GrpcMockState>, tokio::task::JoinHandle<()>) {
+    let cert = generate_simple_self_signed(vec!["localhost".into(), "127.0.0.1".into()])
+        .expect("cert generation");
+    let cert_der = CertificateDer::from(cert.cert.der().to_vec());
+    let key_der = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(
+        cert.key_pair.serialize_der().to_vec(),
+    ));
+    let mut tls_config = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(vec![cert_der], key_der)
+        .expect("TLS config");
+    tls_config.alpn_protocols = vec![b"h2".to_vec()];
+    let tls_acceptor = TlsAcceptor::from(Arc::new(tls_config));
+
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind gRPC mock");
+    let addr = listener.local_addr().expect("local addr");
+
+    let state = Arc::new(GrpcMockState {
+        recorded: Mutex::new(Vec::new()),
+    });
+
+    let state_clone = state.clone();
+    let handle = tokio::spawn(async move {
+        loop {
+            let Ok((tcp_stream, _)) = listener.accept().await else {
+                continue;
+            };
+            let tls_acceptor = tls_acceptor.clone();
+            let state = state_clone.clone();
+
+            tokio::spawn(async move {
+                let Ok(tls_stream) = tls_acceptor.accept(tcp_stream).await else {
+                    return;
+                };
+                let service = service_fn(move |req: Request<Incoming>| {
+                    let state = state.clone();
+                    async move {
+                        let path = req.uri().path().to_string();
+                        state.recorded.lock().await.push(GrpcRecordedRequest {
+                            path: path.clone(),
+                            version: req.version(),
+                            headers: req.headers().clone(),
+                        });
+                        let body = http_body_util::BodyExt::collect(req.into_body())
+                            .await
+                            .map(|b| b.to_bytes())
+                            .unwrap_or_default();
+                        Ok::<_, hyper::Error>(mock_response(&path, body))
+                    }
+                });
+
+                if let Err(e) = hyper_util::server::conn::auto::Builder::new(TokioExecutor::new())
+                    .http2_only()
+                    .serve_connection(TokioIo::new(tls_stream), service)
+                    .await
+                {
+                    eprintln!("gRPC mock connection error: {e}");
+                }
+            });
+        }
+    });
+
+    (addr, state, handle)
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Harness with a `grpc` upstream (API key auth + a header rule) pointing at
+/// the mock, and one gRPC route per method.
+async fn setup(
+    mock_addr: SocketAddr,
+    alias: &str,
+    methods: &[&str],
+    rate_limit: Option<RateLimitConfig>,
+) -> AppHarness {
+    let h = AppHarness::builder()
+        .with_credentials(vec![("cred://grpc-key".into(), "grpc-secret".into())])
+        .with_skip_upstream_tls_verify(true)
+        .build()
+        .await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Grpc,
+                        host: "127.0.0.1".into(),
+                        port: mock_addr.port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.grpc.v1",
+            )
+            .alias(alias)
+            .auth(AuthConfig {
+                plugin_type: APIKEY_AUTH_PLUGIN_ID.into(),
+                sharing: SharingMode::Private,
+                config: Some(
+                    [
+                        ("header".into(), "authorization".into()),
+                        ("prefix".into(), "Bearer ".into()),
+                        ("secret_ref".into(), "cred://grpc-key".into()),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            })
+            .headers(HeadersConfig {
+                request: Some(RequestHeaderRules {
+                    set: [("x-client".into(), "oagw".into())].into_iter().collect(),
+                    ..Default::default()
+                }),
+                response: None,
+            })
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    for method in methods {
+        let mut route = CreateRouteRequest::builder(
+            upstream.id,
+            MatchRules {
+                http: None,
+                grpc: Some(GrpcMatch {
+                    service: SERVICE.into(),
+                    method: (*method).into(),
+                }),
+            },
+        );
+        if let Some(rl) = rate_limit.clone() {
+            route = route.rate_limit(rl);
+        }
+        h.facade()
+            .create_route(ctx.clone(), route.build())
+            .await
+            .unwrap();
+    }
+    h
+}
+
+fn grpc_request(alias: &str, method: &str, body: Body) -> http::Request<Body> {
+    http::Request::builder()
+        .method(Method::POST)
+        .uri(format!("/{alias}/{SERVICE}/{method}"))
+        .header("content-type", "application/grpc")
+        .header("te", "trailers")
+        .header("grpc-timeout", "5S")
+        .body(body)
+        .unwrap()
+}
+
+// ---------------------------------------------------------------------------
+// E2E tests
+// ---------------------------------------------------------------------------
+
+/// Unary call: auth and header rules apply, the upstream sees HTTP/2 with
+/// `te: trailers`, and the response trailers reach the caller.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn e2e_grpc_unary_round_trip() {
+    let (mock_addr, mock_state, _handle) = start_grpc_mock().await;
+    let h = setup(mock_addr, "grpc-unary", &["SayHello"], None).await;
+
+    let message = grpc_message(b"\x0a\x05world");
+    let resp = h
+        .facade()
+        .proxy_request(
+            h.security_context().clone(),
+            grpc_request("grpc-unary", "SayHello", Body::from(message.clone())),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "application/grpc");
+
+    let trailers = resp.extensions().get::<Trailers>().cloned().unwrap();
+    assert!(trailers.get().is_none(), "trailers arrive after the body");
+    let body = resp.into_body().into_bytes().await.unwrap();
+    assert_eq!(body, message);
+    assert_eq!(trailers.get().unwrap()["grpc-status"], "0");
+
+    let recorded = mock_state.recorded.lock().await;
+    assert_eq!(recorded.len(), 1);
+    let call = &recorded[0];
+    assert_eq!(call.path, format!("/{SERVICE}/SayHello"));
+    assert_eq!(call.version, hyper::Version::HTTP_2);
+    assert_eq!(call.headers["authorization"], "Bearer grpc-secret");
+    assert_eq!(call.headers["x-client"], "oagw");
+    assert_eq!(call.headers["te"], "trailers");
+    assert_eq!(call.headers["grpc-timeout"], "5S");
+}
+
+/// Server streaming: every message is relayed, then the trailers.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn e2e_grpc_server_streaming() {
+    let (mock_addr, _mock_state, _handle) = start_grpc_mock().await;
+    let h = setup(mock_addr, "grpc-stream", &["StreamGreetings"], None).await;
+
+    let resp = h
+        .facade()
+        .proxy_request(
+            h.security_context().clone(),
+            grpc_request(
+                "grpc-stream",
+                "StreamGreetings",
+                Body::from(grpc_message(b"")),
+            ),
+        )
+        .await
+        .unwrap();
+    let trailers = resp.extensions().get::<Trailers>().cloned().unwrap();
+    let body = resp.into_body().into_bytes().await.unwrap();
+
+    let expected: Vec<u8> = ["one", "two", "three"]
+        .iter()
+        .flat_map(|m| grpc_message(m.as_bytes()).to_vec())
+        .collect();
+    assert_eq!(body.as_ref(), expected.as_slice());
+    assert_eq!(trailers.get().unwrap()["grpc-status"], "0");
+}
+
+/// Trailers-Only failures: mapped statuses become gateway errors, others
+/// pass through untouched. Unrouted methods are rejected by the gateway.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn e2e_grpc_status_mapping() {
+    let (mock_addr, mock_state, _handle) = start_grpc_mock().await;
+    let h = setup(mock_addr, "grpc-status", &["Secret", "Missing"], None).await;
+    let ctx = h.security_context().clone();
+
+    let err = h
+        .facade()
+        .proxy_request(
+            ctx.clone(),
+            grpc_request("grpc-status", "Secret", Body::Empty),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, ServiceGatewayError::AuthenticationFailed { ref detail, .. } if detail.contains("bad token")),
+        "got: {err:?}"
+    );
+
+    let resp = h
+        .facade()
+        .proxy_request(
+            ctx.clone(),
+            grpc_request("grpc-status", "Missing", Body::Empty),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["grpc-status"], "5");
+
+    let err = h
+        .facade()
+        .proxy_request(ctx, grpc_request("grpc-status", "SayHello", Body::Empty))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, ServiceGatewayError::NotFound { .. }),
+        "got: {err:?}"
+    );
+    assert_eq!(mock_state.recorded.lock().await.len(), 2);
+}
+
+/// Route rate limits apply to gRPC calls like to HTTP requests.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn e2e_grpc_rate_limited() {
+    let (mock_addr, mock_state, _handle) = start_grpc_mock().await;
+    let rate_limit = RateLimitConfig {
+        sharing: SharingMode::Private,
+        algorithm: RateLimitAlgorithm::TokenBucket,
+        sustained: SustainedRate {
+            rate: 1,
+            window: Window::Minute,
+        },
+        burst: Some(BurstConfig { capacity: 1 }),
+        scope: RateLimitScope::Tenant,
+        strategy: RateLimitStrategy::Reject,
+        queue: None,
+        cost: 1,
+    };
+    let h = setup(mock_addr, "grpc-limited", &["SayHello"], Some(rate_limit)).await;
+    let ctx = h.security_context().clone();
+
+    let call = || {
+        h.facade().proxy_request(
+            ctx.clone(),
+            grpc_request("grpc-limited", "SayHello", Body::from(grpc_message(b""))),
+        )
+    };
+    call()
+        .await
+        .unwrap()
+        .into_body()
+        .into_bytes()
+        .await
+        .unwrap();
+    let err = call().await.unwrap_err();
+    assert!(
+        matches!(err, ServiceGatewayError::RateLimitExceeded { .. }),
+        "got: {err:?}"
+    );
+    assert_eq!(mock_state.recorded.lock().await.len(), 1);
+}
@@ -1,16 +1,19 @@
 use http::{Method, StatusCode};
+use modkit_security::SecurityContext;
 use oagw::test_support::{
-    APIKEY_AUTH_PLUGIN_ID, AppHarness, MockBody, MockGuard, MockResponse, MockUpstream,
-    parse_resource_gts,
+    APIKEY_AUTH_PLUGIN_ID, AppHarness, CORS_GUARD_PLUGIN_ID, LOGGING_TRANSFORM_PLUGIN_ID, MockBody,
+    MockGuard, MockResponse, MockUpstream, OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID,
+    REQUEST_ID_TRANSFORM_PLUGIN_ID, parse_resource_gts,
 };
 use oagw_sdk::Body;
 use oagw_sdk::api::ErrorSource;
 use oagw_sdk::{
     BurstConfig, CreateRouteRequest, CreateUpstreamRequest, Endpoint, HttpMatch, HttpMethod,
-    MatchRules, PathSuffixMode, RateLimitAlgorithm, RateLimitConfig, RateLimitScope,
+    MatchRules, PathSuffixMode, PluginsConfig, RateLimitAlgorithm, RateLimitConfig, RateLimitScope,
     RateLimitStrategy, Scheme, Server, SharingMode, SustainedRate, Window,
 };
 use serde_json::json;
+use uuid::Uuid;
 
 async fn setup_openai_mock() -> AppHarness {
     let h = AppHarness::builder()
@@ -403,6 +406,7 @@ async fn proxy_rate_limit_exceeded_returns_429() {
                 burst: Some(BurstConfig { capacity: 1 }),
                 scope: RateLimitScope::Tenant,
                 strategy: RateLimitStrategy::Reject,
+                queue: None,
                 cost: 1,
             })
             .build(),
@@ -454,6 +458,96 @@ async fn proxy_rate_limit_exceeded_returns_429() {
     }
 }
 
+// Rate limit with strategy=degrade lets the request through, tagged.
+#[tokio::test]
+async fn proxy_rate_limit_degrade_tags_response() {
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("rate-degraded")
+            .rate_limit(RateLimitConfig {
+                sharing: SharingMode::Private,
+                algorithm: RateLimitAlgorithm::TokenBucket,
+                sustained: SustainedRate {
+                    rate: 1,
+                    window: Window::Minute,
+                },
+                burst: Some(BurstConfig { capacity: 1 }),
+                scope: RateLimitScope::Tenant,
+                strategy: RateLimitStrategy::Degrade,
+                queue: None,
+                cost: 1,
+            })
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: "/v1/models".into(),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Append,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    let send = || {
+        let req = http::Request::builder()
+            .method(Method::GET)
+            .uri("/rate-degraded/v1/models")
+            .body(Body::Empty)
+            .unwrap();
+        h.facade().proxy_request(ctx.clone(), req)
+    };
+
+    // Within the limit: no tag.
+    let response = send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response
+            .headers()
+            .get("x-oagw-rate-limit-degraded")
+            .is_none()
+    );
+
+    // Over the limit: still proxied, but tagged as degraded.
+    let response = send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response
+            .headers()
+            .get("x-oagw-rate-limit-degraded")
+            .unwrap(),
+        "true"
+    );
+}
+
 // 6.16: Upstream timeout — proxy to gated mock that never responds, assert 504.
 // Uses multi_thread runtime so the timer driver runs on a dedicated thread,
 // preventing stalls when other test binaries compete for CPU.
@@ -1429,6 +1523,134 @@ async fn proxy_with_mock_guard_custom_response() {
     assert!(recorded[0].uri.contains("/custom/endpoint"));
 }
 
+// OAuth2 client credentials: the token is fetched once, injected as a bearer
+// token, and re-fetched after the upstream answers 401.
+#[tokio::test]
+async fn proxy_oauth2_client_credentials_refreshes_after_401() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/oauth/token",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"access_token": "at-1", "expires_in": 3600})),
+        },
+    );
+    guard.mock(
+        "GET",
+        "/v1/resource",
+        MockResponse {
+            status: 401,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"error": "invalid_token"})),
+        },
+    );
+
+    let h = AppHarness::builder()
+        .with_credentials(vec![
+            ("cred://vendor-client-id".into(), "client-1".into()),
+            ("cred://vendor-client-secret".into(), "s3cret".into()),
+        ])
+        .build()
+        .await;
+    let ctx = h.security_context().clone();
+
+    let upstream = h
+        .facade()
+        .create_upstream(
+            ctx.clone(),
+            CreateUpstreamRequest::builder(
+                Server {
+                    endpoints: vec![Endpoint {
+                        scheme: Scheme::Http,
+                        host: "127.0.0.1".into(),
+                        port: h.mock_port(),
+                    }],
+                },
+                "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            )
+            .alias("oauth2-test")
+            .auth(oagw_sdk::AuthConfig {
+                plugin_type: OAUTH2_CLIENT_CRED_AUTH_PLUGIN_ID.into(),
+                sharing: SharingMode::Private,
+                config: Some(
+                    [
+                        (
+                            "token_url".into(),
+                            format!(
+                                "http://127.0.0.1:{}{}",
+                                h.mock_port(),
+                                guard.path("/oauth/token")
+                            ),
+                        ),
+                        ("client_id_ref".into(), "cred://vendor-client-id".into()),
+                        (
+                            "client_secret_ref".into(),
+                            "cred://vendor-client-secret".into(),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+            })
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Get],
+                        path: guard.path("/v1/resource"),
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    for _ in 0..2 {
+        let req = http::Request::builder()
+            .method(Method::GET)
+            .uri(format!("/oauth2-test{}", guard.path("/v1/resource")))
+            .body(Body::Empty)
+            .unwrap();
+        let response = h.facade().proxy_request(ctx.clone(), req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let recorded = guard.recorded_requests().await;
+    let token_requests = recorded
+        .iter()
+        .filter(|r| r.uri.contains("/oauth/token"))
+        .count();
+    let resource_requests: Vec<_> = recorded
+        .iter()
+        .filter(|r| r.uri.contains("/v1/resource"))
+        .collect();
+    // One token fetch per request: the 401 dropped the cached token.
+    assert_eq!(token_requests, 2);
+    assert_eq!(resource_requests.len(), 2);
+    for r in resource_requests {
+        let auth = r
+            .headers
+            .iter()
+            .find(|(k, _)| k == "authorization")
+            .map(|(_, v)| v.as_str());
+        assert_eq!(auth, Some("Bearer at-1"));
+    }
+}
+
 // P0 #3: WebSocket upgrade requests are rejected with ProtocolError.
 #[tokio::test]
 async fn proxy_websocket_upgrade_rejected() {
@@ -1693,3 +1915,499 @@ async fn proxy_streaming_body_exceeding_limit_returns_413() {
         ),
     }
 }
+
+async fn create_plugin_upstream(
+    h: &AppHarness,
+    ctx: &SecurityContext,
+    alias: &str,
+    path: String,
+    plugins: Option<PluginsConfig>,
+) {
+    let mut builder = CreateUpstreamRequest::builder(
+        Server {
+            endpoints: vec![Endpoint {
+                scheme: Scheme::Http,
+                host: "127.0.0.1".into(),
+                port: h.mock_port(),
+            }],
+        },
+        "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+    )
+    .alias(alias);
+    if let Some(plugins) = plugins {
+        builder = builder.plugins(plugins);
+    }
+    let upstream = h
+        .facade()
+        .create_upstream(ctx.clone(), builder.build())
+        .await
+        .unwrap();
+
+    h.facade()
+        .create_route(
+            ctx.clone(),
+            CreateRouteRequest::builder(
+                upstream.id,
+                MatchRules {
+                    http: Some(HttpMatch {
+                        methods: vec![HttpMethod::Post],
+                        path,
+                        query_allowlist: vec![],
+                        path_suffix_mode: PathSuffixMode::Disabled,
+                    }),
+                    grpc: None,
+                },
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+}
+
+/// Key/value configuration of a single plugin.
+type PluginSettings<'a> = &'a [(&'a str, &'a str)];
+
+fn plugins(
+    sharing: SharingMode,
+    items: &[&str],
+    config: &[(&str, PluginSettings<'_>)],
+) -> PluginsConfig {
+    PluginsConfig {
+        sharing,
+        items: items.iter().map(ToString::to_string).collect(),
+        config: config
+            .iter()
+            .map(|(plugin, kv)| {
+                let kv = kv
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect();
+                (plugin.to_string(), kv)
+            })
+            .collect(),
+    }
+}
+
+// Transform plugins: X-Request-ID is forwarded to the upstream and echoed.
+#[tokio::test]
+async fn proxy_request_id_transform_propagates_id() {
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+    create_plugin_upstream(
+        &h,
+        &ctx,
+        "request-id-test",
+        "/echo".into(),
+        Some(plugins(
+            SharingMode::Private,
+            &[REQUEST_ID_TRANSFORM_PLUGIN_ID, LOGGING_TRANSFORM_PLUGIN_ID],
+            &[],
+        )),
+    )
+    .await;
+
+    let req = http::Request::builder()
+        .method(Method::POST)
+        .uri("/request-id-test/echo")
+        .header("x-request-id", "req-42")
+        .body(Body::from("{}"))
+        .unwrap();
+    let response = h.facade().proxy_request(ctx.clone(), req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-request-id"], "req-42");
+
+    let body_bytes = response.into_body().into_bytes().await.unwrap();
+    let body_json: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(body_json["headers"]["x-request-id"], "req-42");
+}
+
+// Guard plugins: CORS preflight is answered by the gateway, disallowed origins
+// are rejected, and allowed requests get CORS response headers.
+#[tokio::test]
+async fn proxy_cors_guard_handles_preflight_and_origins() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/cors",
+        MockResponse {
+            status: 200,
+            headers: vec![],
+            body: MockBody::Json(json!({"ok": true})),
+        },
+    );
+
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+    create_plugin_upstream(
+        &h,
+        &ctx,
+        "cors-test",
+        guard.path("/cors"),
+        Some(plugins(
+            SharingMode::Private,
+            &[CORS_GUARD_PLUGIN_ID],
+            &[(
+                CORS_GUARD_PLUGIN_ID,
+                &[("allowed_origins", "https://app.example.com")],
+            )],
+        )),
+    )
+    .await;
+    let uri = format!("/cors-test{}", guard.path("/cors"));
+
+    // Preflight for a POST-only route never reaches the upstream.
+    let req = http::Request::builder()
+        .method(Method::OPTIONS)
+        .uri(&uri)
+        .header("origin", "https://app.example.com")
+        .header("access-control-request-method", "POST")
+        .body(Body::Empty)
+        .unwrap();
+    let response = h.facade().proxy_request(ctx.clone(), req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+    assert_eq!(
+        response.extensions().get::<ErrorSource>(),
+        Some(&ErrorSource::Gateway)
+    );
+    assert!(guard.recorded_requests().await.is_empty());
+
+    let req = http::Request::builder()
+        .method(Method::POST)
+        .uri(&uri)
+        .header("origin", "https://evil.example.com")
+        .body(Body::from("{}"))
+        .unwrap();
+    match h.facade().proxy_request(ctx.clone(), req).await {
+        Err(err) => assert!(matches!(
+            err,
+            oagw_sdk::error::ServiceGatewayError::Forbidden { .. }
+        )),
+        Ok(_) => panic!("expected disallowed origin to be rejected"),
+    }
+    assert!(guard.recorded_requests().await.is_empty());
+
+    let req = http::Request::builder()
+        .method(Method::POST)
+        .uri(&uri)
+        .header("origin", "https://app.example.com")
+        .body(Body::from("{}"))
+        .unwrap();
+    let response = h.facade().proxy_request(ctx.clone(), req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+    assert_eq!(guard.recorded_requests().await.len(), 1);
+}
+
+// Plugin inheritance: an ancestor's `enforce` chain applies to a descendant's
+// upstream with the same alias, while `private` chains do not.
+#[tokio::test]
+async fn proxy_inherits_enforced_plugins_from_parent_tenant() {
+    let parent = Uuid::new_v4();
+    let child = Uuid::new_v4();
+    let h = AppHarness::builder()
+        .with_tenant_hierarchy(vec![(child, parent)])
+        .build()
+        .await;
+    let tenant_ctx = |tenant| {
+        SecurityContext::builder()
+            .subject_tenant_id(tenant)
+            .subject_id(Uuid::new_v4())
+            .build()
+            .unwrap()
+    };
+    let parent_ctx = tenant_ctx(parent);
+    let child_ctx = tenant_ctx(child);
+
+    for (alias, sharing) in [
+        ("inherit-enforced", SharingMode::Enforce),
+        ("inherit-private", SharingMode::Private),
+    ] {
+        create_plugin_upstream(
+            &h,
+            &parent_ctx,
+            alias,
+            "/echo".into(),
+            Some(plugins(sharing, &[REQUEST_ID_TRANSFORM_PLUGIN_ID], &[])),
+        )
+        .await;
+        create_plugin_upstream(&h, &child_ctx, alias, "/echo".into(), None).await;
+    }
+
+    for (alias, expect_request_id) in [("inherit-enforced", true), ("inherit-private", false)] {
+        let req = http::Request::builder()
+            .method(Method::POST)
+            .uri(format!("/{alias}/echo"))
+            .body(Body::from("{}"))
+            .unwrap();
+        let response = h
+            .facade()
+            .proxy_request(child_ctx.clone(), req)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().contains_key("x-request-id"),
+            expect_request_id,
+            "{alias}"
+        );
+    }
+}
+
+// Circuit breaker: consecutive upstream failures open the circuit, further
+// requests fail fast without reaching the upstream, and updating the circuit
+// breaker config starts a fresh, closed circuit.
+#[tokio::test]
+async fn proxy_circuit_breaker_opens_after_failures() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "POST",
+        "/flaky",
+        MockResponse {
+            status: 500,
+            headers: vec![],
+            body: MockBody::Json(json!({"error": "boom"})),
+        },
+    );
+
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": "cb-test",
+            "circuit_breaker": {"failure_threshold": 2, "timeout_seconds": 60}
+        }))
+        .expect_status(201)
+        .await;
+    let body = resp.json();
+    assert_eq!(body["circuit_breaker"]["failure_threshold"], 2);
+    assert_eq!(body["circuit_breaker"]["success_threshold"], 3);
+    assert_eq!(body["circuit_breaker"]["scope"], "global");
+    let upstream_id = body["id"].as_str().unwrap().to_string();
+    let (_, upstream_uuid) = parse_resource_gts(&upstream_id).unwrap();
+
+    h.api_v1()
+        .post_route()
+        .with_body(json!({
+            "upstream_id": upstream_uuid,
+            "match": {"http": {"methods": ["POST"], "path": guard.path("/flaky")}}
+        }))
+        .expect_status(201)
+        .await;
+
+    let uri = format!("/cb-test{}", guard.path("/flaky"));
+    let send = || {
+        let req = http::Request::builder()
+            .method(Method::POST)
+            .uri(&uri)
+            .body(Body::from("{}"))
+            .unwrap();
+        h.facade().proxy_request(ctx.clone(), req)
+    };
+
+    for _ in 0..2 {
+        let response = send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            response.extensions().get::<ErrorSource>(),
+            Some(&ErrorSource::Upstream)
+        );
+    }
+
+    match send().await {
+        Err(oagw_sdk::error::ServiceGatewayError::CircuitBreakerOpen {
+            retry_after_secs, ..
+        }) => assert!(retry_after_secs.is_some_and(|s| s > 0 && s <= 60)),
+        other => panic!("expected CircuitBreakerOpen, got {other:?}"),
+    }
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+
+    h.api_v1()
+        .patch_upstream(&upstream_id)
+        .with_body(json!({"circuit_breaker": {"failure_threshold": 5}}))
+        .expect_status(200)
+        .await;
+    let response = send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(guard.recorded_requests().await.len(), 3);
+}
+
+#[tokio::test]
+async fn proxy_route_cache_serves_repeated_gets() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/models",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"data": ["gpt-4o"]})),
+        },
+    );
+
+    let h = AppHarness::builder().build().await;
+    let ctx = h.security_context().clone();
+
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": "cache-test"
+        }))
+        .expect_status(201)
+        .await;
+    let (_, upstream_uuid) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+
+    let resp = h
+        .api_v1()
+        .post_route()
+        .with_body(json!({
+            "upstream_id": upstream_uuid,
+            "match": {"http": {"methods": ["GET"], "path": guard.path("/models")}},
+            "cache": {"ttl_seconds": 60}
+        }))
+        .expect_status(201)
+        .await;
+    let body = resp.json();
+    assert_eq!(body["cache"]["ttl_seconds"], 60);
+    assert_eq!(body["cache"]["cache_post"], false);
+    let route_id = body["id"].as_str().unwrap().to_string();
+
+    let uri = format!("/cache-test{}", guard.path("/models"));
+    let send = |cache_control: Option<&'static str>| {
+        let mut req = http::Request::builder().method(Method::GET).uri(&uri);
+        if let Some(cc) = cache_control {
+            req = req.header("cache-control", cc);
+        }
+        h.facade()
+            .proxy_request(ctx.clone(), req.body(Body::Empty).unwrap())
+    };
+    let cache_status = |resp: &http::Response<Body>| {
+        resp.headers()
+            .get("x-oagw-cache")
+            .map(|v| v.to_str().unwrap().to_string())
+    };
+
+    let first = send(None).await.unwrap();
+    assert_eq!(first.status(), StatusCode::OK);
+    assert_eq!(cache_status(&first).as_deref(), Some("miss"));
+    let first_body = first.into_body().into_bytes().await.unwrap();
+
+    let second = send(None).await.unwrap();
+    assert_eq!(second.status(), StatusCode::OK);
+    assert_eq!(cache_status(&second).as_deref(), Some("hit"));
+    assert_eq!(
+        second.extensions().get::<ErrorSource>(),
+        Some(&ErrorSource::Upstream)
+    );
+    assert_eq!(second.into_body().into_bytes().await.unwrap(), first_body);
+    assert_eq!(guard.recorded_requests().await.len(), 1);
+
+    // `no-store` goes to the upstream and leaves the entry alone.
+    let bypass = send(Some("no-store")).await.unwrap();
+    assert_eq!(cache_status(&bypass), None);
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+
+    // Route updates invalidate the upstream's entries.
+    h.api_v1()
+        .patch_route(&route_id)
+        .with_body(json!({"cache": {"ttl_seconds": 120}}))
+        .expect_status(200)
+        .await;
+    let after_update = send(None).await.unwrap();
+    assert_eq!(cache_status(&after_update).as_deref(), Some("miss"));
+    assert_eq!(guard.recorded_requests().await.len(), 3);
+}
+
+#[tokio::test]
+async fn proxy_route_cache_is_not_shared_between_callers_with_credentials() {
+    let mut guard = MockGuard::new();
+    guard.mock(
+        "GET",
+        "/me",
+        MockResponse {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: MockBody::Json(json!({"user": "whoever called first"})),
+        },
+    );
+
+    let h = AppHarness::builder().build().await;
+    let alice = h.security_context().clone();
+    let bob = SecurityContext::builder()
+        .subject_tenant_id(alice.subject_tenant_id())
+        .subject_id(Uuid::new_v4())
+        .build()
+        .unwrap();
+
+    let resp = h
+        .api_v1()
+        .post_upstream()
+        .with_body(json!({
+            "server": {
+                "endpoints": [{"host": "127.0.0.1", "port": h.mock_port(), "scheme": "http"}]
+            },
+            "protocol": "gts.x.core.oagw.protocol.v1~x.core.oagw.http.v1",
+            "alias": "cache-auth"
+        }))
+        .expect_status(201)
+        .await;
+    let (_, upstream_uuid) = parse_resource_gts(resp.json()["id"].as_str().unwrap()).unwrap();
+    h.api_v1()
+        .post_route()
+        .with_body(json!({
+            "upstream_id": upstream_uuid,
+            "match": {"http": {"methods": ["GET"], "path": guard.path("/me")}},
+            "cache": {"ttl_seconds": 60}
+        }))
+        .expect_status(201)
+        .await;
+
+    let uri = format!("/cache-auth{}", guard.path("/me"));
+    let send = |ctx: &SecurityContext, token: &'static str| {
+        let req = http::Request::builder()
+            .method(Method::GET)
+            .uri(&uri)
+            .header("authorization", token)
+            .body(Body::Empty)
+            .unwrap();
+        h.facade().proxy_request(ctx.clone(), req)
+    };
+    let cache_status = |resp: &http::Response<Body>| {
+        resp.headers()
+            .get("x-oagw-cache")
+            .map(|v| v.to_str().unwrap().to_string())
+    };
+
+    let first = send(&alice, "Bearer alice").await.unwrap();
+    assert_eq!(cache_status(&first).as_deref(), Some("miss"));
+
+    // Another subject with its own token goes to the upstream.
+    let other = send(&bob, "Bearer bob").await.unwrap();
+    assert_eq!(cache_status(&other).as_deref(), Some("miss"));
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+
+    // Each caller still reuses its own entry.
+    let again = send(&alice, "Bearer alice").await.unwrap();
+    assert_eq!(cache_status(&again).as_deref(), Some("hit"));
+    let again = send(&bob, "Bearer bob").await.unwrap();
+    assert_eq!(cache_status(&again).as_deref(), Some("hit"));
+    assert_eq!(guard.recorded_requests().await.len(), 2);
+}
@@ -0,0 +1,100 @@
+//! REST DTOs for tenant management.
+
+use tenant_resolver_sdk::{TenantInfo, TenantStatus};
+use uuid::Uuid;
+
+use crate::domain::NewTenant;
+
+/// Tenant lifecycle status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[modkit_macros::api_dto(response)]
+pub enum TenantStatusDto {
+    Active,
+    Suspended,
+    Deleted,
+}
+
+impl From<TenantStatus> for TenantStatusDto {
+    fn from(status: TenantStatus) -> Self {
+        match status {
+            TenantStatus::Active => Self::Active,
+            TenantStatus::Suspended => Self::Suspended,
+            TenantStatus::Deleted => Self::Deleted,
+        }
+    }
+}
+
+/// A tenant.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(response)]
+pub struct TenantDto {
+    #[schema(value_type = String)]
+    pub id: Uuid,
+    pub name: String,
+    pub status: TenantStatusDto,
+    /// Tenant type classification.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub tenant_type: Option<String>,
+    /// Parent tenant; absent for root tenants.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub parent_id: Option<Uuid>,
+    /// Whether the tenant is self-managed (a barrier for its ancestors).
+    pub self_managed: bool,
+}
+
+impl From<TenantInfo> for TenantDto {
+    fn from(info: TenantInfo) -> Self {
+        Self {
+            id: info.id,
+            name: info.name,
+            status: info.status.into(),
+            tenant_type: info.tenant_type,
+            parent_id: info.parent_id,
+            self_managed: info.self_managed,
+        }
+    }
+}
+
+/// Request to create a tenant.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request)]
+pub struct CreateTenantRequest {
+    /// Tenant ID; generated when omitted.
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub id: Option<Uuid>,
+    /// Tenant name (at most 255 characters).
+    pub name: String,
+    /// Tenant type classification.
+    #[serde(rename = "type", default)]
+    pub tenant_type: Option<String>,
+    /// Parent tenant; omit to create a root tenant.
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub parent_id: Option<Uuid>,
+    /// Whether the tenant is self-managed (a barrier for its ancestors).
+    #[serde(default)]
+    pub self_managed: bool,
+}
+
+impl From<CreateTenantRequest> for NewTenant {
+    fn from(req: CreateTenantRequest) -> Self {
+        Self {
+            id: req.id,
+            name: req.name,
+            tenant_type: req.tenant_type,
+            parent_id: req.parent_id,
+            self_managed: req.self_managed,
+        }
+    }
+}
+
+/// Request to move a tenant.
+#[derive(Debug, Clone)]
+#[modkit_macros::api_dto(request)]
+pub struct MoveTenantRequest {
+    /// New parent tenant.
+    #[schema(value_type = String)]
+    pub parent_id: Uuid,
+}
@@ -0,0 +1,91 @@
+//! REST error mapping for tenant management.
+
+use modkit::api::prelude::StatusCode;
+use modkit::api::problem::Problem;
+
+use crate::domain::DomainError;
+
+impl From<DomainError> for Problem {
+    fn from(e: DomainError) -> Self {
+        let trace_id = tracing::Span::current()
+            .id()
+            .map(|id| id.into_u64().to_string());
+
+        let (status, code, title, detail) = match &e {
+            DomainError::NotFound(id) => (
+                StatusCode::NOT_FOUND,
+                "TENANT_NOT_FOUND",
+                "Tenant not found",
+                format!("Tenant {id} does not exist or is not accessible"),
+            ),
+            DomainError::Validation(msg) => (
+                StatusCode::BAD_REQUEST,
+                "TENANT_INVALID_REQUEST",
+                "Invalid request",
+                msg.clone(),
+            ),
+            DomainError::Conflict(msg) => (
+                StatusCode::CONFLICT,
+                "TENANT_CONFLICT",
+                "Conflict",
+                msg.clone(),
+            ),
+            DomainError::Forbidden(msg) => (
+                StatusCode::FORBIDDEN,
+                "TENANT_ACCESS_DENIED",
+                "Access denied",
+                msg.clone(),
+            ),
+            DomainError::Internal(_) | DomainError::Database(_) => {
+                tracing::error!(error = ?e, "Internal error in tenant management");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "TENANT_INTERNAL",
+                    "Internal Server Error",
+                    "An internal error occurred".to_owned(),
+                )
+            }
+        };
+
+        let mut problem = Problem::new(status, title, detail)
+            .with_type(format!("https://errors.hyperspot.com/{code}"))
+            .with_code(code);
+
+        if let Some(id) = trace_id {
+            problem = problem.with_trace_id(id);
+        }
+
+        problem
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn domain_errors_map_to_status_codes() {
+        let cases = [
+            (DomainError::NotFound(Uuid::nil()), StatusCode::NOT_FOUND),
+            (DomainError::validation("bad"), StatusCode::BAD_REQUEST),
+            (DomainError::conflict("taken"), StatusCode::CONFLICT),
+            (
+                DomainError::Forbidden("no".to_owned()),
+                StatusCode::FORBIDDEN,
+            ),
+        ];
+        for (error, status) in cases {
+            let problem: Problem = error.into();
+            assert_eq!(problem.status, status);
+        }
+    }
+
+    #[test]
+    fn internal_maps_to_500_without_leaking_detail() {
+        let problem: Problem = DomainError::Internal("db password wrong".to_owned()).into();
+        assert_eq!(problem.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(problem.detail, "An internal error occurred");
+    }
+}
@@ -0,0 +1,98 @@
+//! REST handlers for tenant management.
+
+use std::sync::Arc;
+
+use axum::extract::{Extension, Path};
+use modkit::api::prelude::*;
+use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+use super::dto::{CreateTenantRequest, MoveTenantRequest, TenantDto};
+use crate::domain::Service;
+
+/// POST /tenant-resolver/v1/tenants
+///
+/// Create a tenant.
+///
+/// # Errors
+///
+/// Returns `Problem` with 404 if the parent is not accessible, 409 if it is
+/// deleted or the ID is taken, 400 for invalid input, or 403 if denied.
+pub async fn create_tenant(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    Json(req): Json<CreateTenantRequest>,
+) -> ApiResult<(StatusCode, Json<TenantDto>)> {
+    let tenant = svc.create_tenant(&ctx, req.into()).await?;
+    Ok((StatusCode::CREATED, Json(tenant.into())))
+}
+
+/// POST /tenant-resolver/v1/tenants/{id}/move
+///
+/// Move a tenant, with its subtree, below another parent.
+///
+/// # Errors
+///
+/// Returns `Problem` with 404 if either tenant is not accessible, 400 if the
+/// move would create a cycle, 409 if either tenant is deleted, or 403 if
+/// denied.
+pub async fn move_tenant(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<MoveTenantRequest>,
+) -> ApiResult<Json<TenantDto>> {
+    let tenant = svc.move_tenant(&ctx, id, req.parent_id).await?;
+    Ok(Json(tenant.into()))
+}
+
+/// POST /tenant-resolver/v1/tenants/{id}/suspend
+///
+/// Suspend a tenant.
+///
+/// # Errors
+///
+/// Returns `Problem` with 404 if the tenant is not accessible, 409 if it is
+/// deleted, or 403 if denied.
+pub async fn suspend_tenant(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<TenantDto>> {
+    let tenant = svc.suspend_tenant(&ctx, id).await?;
+    Ok(Json(tenant.into()))
+}
+
+/// POST /tenant-resolver/v1/tenants/{id}/resume
+///
+/// Reactivate a suspended tenant.
+///
+/// # Errors
+///
+/// Returns `Problem` with 404 if the tenant is not accessible, 409 if it is
+/// deleted, or 403 if denied.
+pub async fn resume_tenant(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<TenantDto>> {
+    let tenant = svc.resume_tenant(&ctx, id).await?;
+    Ok(Json(tenant.into()))
+}
+
+/// DELETE /tenant-resolver/v1/tenants/{id}
+///
+/// Mark a tenant deleted.
+///
+/// # Errors
+///
+/// Returns `Problem` with 404 if the tenant is not accessible, 409 if it has
+/// children that are not deleted, or 403 if denied.
+pub async fn delete_tenant(
+    Extension(ctx): Extension<SecurityContext>,
+    Extension(svc): Extension<Arc<Service>>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    svc.delete_tenant(&ctx, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
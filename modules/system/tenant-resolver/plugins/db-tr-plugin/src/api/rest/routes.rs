@@ -0,0 +1,111 @@
+//! REST route registration for tenant management.
+
+use std::sync::Arc;
+
+use axum::{Extension, Router};
+use modkit::api::OpenApiRegistry;
+use modkit::api::operation_builder::{LicenseFeature, OperationBuilder};
+use modkit::api::prelude::StatusCode;
+
+use super::dto::{CreateTenantRequest, MoveTenantRequest, TenantDto};
+use super::handlers;
+use crate::domain::Service;
+
+const TAG: &str = "Tenants";
+
+const ID_PARAM: &str = "Tenant ID";
+
+struct License;
+
+impl AsRef<str> for License {
+    fn as_ref(&self) -> &'static str {
+        "gts.x.core.lic.feat.v1~x.core.global.base.v1"
+    }
+}
+
+impl LicenseFeature for License {}
+
+/// Registers the tenant management routes.
+#[allow(clippy::needless_pass_by_value)]
+pub fn register_routes(
+    mut router: Router,
+    openapi: &dyn OpenApiRegistry,
+    service: Arc<Service>,
+) -> Router {
+    // POST /tenant-resolver/v1/tenants - Create a tenant
+    router = OperationBuilder::post("/tenant-resolver/v1/tenants")
+        .operation_id("tenant_resolver.create_tenant")
+        .summary("Create a tenant")
+        .description(
+            "Create an active tenant below `parent_id`. Omitting `parent_id` creates a root tenant, which requires an unconstrained grant.",
+        )
+        .tag(TAG)
+        .authenticated()
+        .require_license_features::<License>([])
+        .json_request::<CreateTenantRequest>(openapi, "Tenant to create")
+        .handler(handlers::create_tenant)
+        .json_response_with_schema::<TenantDto>(openapi, StatusCode::CREATED, "Tenant created")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // POST /tenant-resolver/v1/tenants/{id}/move - Re-parent a tenant
+    router = OperationBuilder::post("/tenant-resolver/v1/tenants/{id}/move")
+        .operation_id("tenant_resolver.move_tenant")
+        .summary("Move a tenant")
+        .description("Move a tenant, with its whole subtree, below another parent.")
+        .tag(TAG)
+        .authenticated()
+        .require_license_features::<License>([])
+        .path_param("id", ID_PARAM)
+        .json_request::<MoveTenantRequest>(openapi, "New parent")
+        .handler(handlers::move_tenant)
+        .json_response_with_schema::<TenantDto>(openapi, StatusCode::OK, "Tenant moved")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // POST /tenant-resolver/v1/tenants/{id}/suspend - Suspend a tenant
+    router = OperationBuilder::post("/tenant-resolver/v1/tenants/{id}/suspend")
+        .operation_id("tenant_resolver.suspend_tenant")
+        .summary("Suspend a tenant")
+        .description("Suspend an active tenant. Its descendants keep their own status.")
+        .tag(TAG)
+        .authenticated()
+        .require_license_features::<License>([])
+        .path_param("id", ID_PARAM)
+        .handler(handlers::suspend_tenant)
+        .json_response_with_schema::<TenantDto>(openapi, StatusCode::OK, "Tenant suspended")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // POST /tenant-resolver/v1/tenants/{id}/resume - Reactivate a tenant
+    router = OperationBuilder::post("/tenant-resolver/v1/tenants/{id}/resume")
+        .operation_id("tenant_resolver.resume_tenant")
+        .summary("Resume a tenant")
+        .description("Reactivate a suspended tenant.")
+        .tag(TAG)
+        .authenticated()
+        .require_license_features::<License>([])
+        .path_param("id", ID_PARAM)
+        .handler(handlers::resume_tenant)
+        .json_response_with_schema::<TenantDto>(openapi, StatusCode::OK, "Tenant resumed")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    // DELETE /tenant-resolver/v1/tenants/{id} - Soft-delete a tenant
+    router = OperationBuilder::delete("/tenant-resolver/v1/tenants/{id}")
+        .operation_id("tenant_resolver.delete_tenant")
+        .summary("Delete a tenant")
+        .description(
+            "Mark a tenant deleted. The tenant must not have children that are not deleted.",
+        )
+        .tag(TAG)
+        .authenticated()
+        .require_license_features::<License>([])
+        .path_param("id", ID_PARAM)
+        .handler(handlers::delete_tenant)
+        .json_response(StatusCode::NO_CONTENT, "Tenant deleted")
+        .standard_errors(openapi)
+        .register(router, openapi);
+
+    router.layer(Extension(service))
+}
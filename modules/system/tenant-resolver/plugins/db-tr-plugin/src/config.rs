@@ -0,0 +1,46 @@
+//! Configuration for the database tenant resolver plugin.
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Plugin configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DbTrPluginConfig {
+    /// Vendor name for GTS instance registration.
+    pub vendor: String,
+
+    /// Plugin priority (lower = higher priority).
+    pub priority: i16,
+
+    /// Root tenants created on start when missing.
+    ///
+    /// Existing tenants are left untouched, so renaming a root here has no
+    /// effect once it has been created.
+    pub root_tenants: Vec<RootTenantConfig>,
+}
+
+impl Default for DbTrPluginConfig {
+    fn default() -> Self {
+        Self {
+            vendor: "hyperspot".to_owned(),
+            priority: 100,
+            root_tenants: Vec::new(),
+        }
+    }
+}
+
+/// Configuration for a root tenant.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RootTenantConfig {
+    /// Tenant ID.
+    pub id: Uuid,
+
+    /// Tenant name.
+    pub name: String,
+
+    /// Tenant type classification.
+    #[serde(rename = "type", default)]
+    pub tenant_type: Option<String>,
+}
@@ -0,0 +1,320 @@
+//! Client implementation for the database tenant resolver plugin.
+//!
+//! Implements `TenantResolverPluginClient` using the domain service.
+
+use async_trait::async_trait;
+use modkit_security::SecurityContext;
+use tenant_resolver_sdk::{
+    GetAncestorsOptions, GetAncestorsResponse, GetDescendantsOptions, GetDescendantsResponse,
+    GetTenantsOptions, IsAncestorOptions, TenantId, TenantInfo, TenantResolverError,
+    TenantResolverPluginClient,
+};
+
+use super::service::Service;
+
+#[async_trait]
+impl TenantResolverPluginClient for Service {
+    async fn get_tenant(
+        &self,
+        _ctx: &SecurityContext,
+        id: TenantId,
+    ) -> Result<TenantInfo, TenantResolverError> {
+        Ok(self.get(id).await?)
+    }
+
+    async fn get_tenants(
+        &self,
+        _ctx: &SecurityContext,
+        ids: &[TenantId],
+        options: &GetTenantsOptions,
+    ) -> Result<Vec<TenantInfo>, TenantResolverError> {
+        Ok(self.get_many(ids, &options.status).await?)
+    }
+
+    async fn get_ancestors(
+        &self,
+        _ctx: &SecurityContext,
+        id: TenantId,
+        options: &GetAncestorsOptions,
+    ) -> Result<GetAncestorsResponse, TenantResolverError> {
+        let tenant = self.get(id).await?;
+        let ancestors = self.ancestors(&tenant, options.barrier_mode).await?;
+        Ok(GetAncestorsResponse {
+            tenant: tenant.into(),
+            ancestors,
+        })
+    }
+
+    async fn get_descendants(
+        &self,
+        _ctx: &SecurityContext,
+        id: TenantId,
+        options: &GetDescendantsOptions,
+    ) -> Result<GetDescendantsResponse, TenantResolverError> {
+        // The status filter does NOT apply to the starting tenant
+        let tenant = self.get(id).await?;
+        let descendants = self
+            .descendants(
+                &tenant,
+                &options.status,
+                options.barrier_mode,
+                options.max_depth,
+            )
+            .await?;
+        Ok(GetDescendantsResponse {
+            tenant: tenant.into(),
+            descendants,
+        })
+    }
+
+    async fn is_ancestor(
+        &self,
+        _ctx: &SecurityContext,
+        ancestor_id: TenantId,
+        descendant_id: TenantId,
+        options: &IsAncestorOptions,
+    ) -> Result<bool, TenantResolverError> {
+        Ok(self
+            .is_ancestor_of(ancestor_id, descendant_id, options.barrier_mode)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::domain::test_support::{allow_all, create, ctx, id, service};
+    use tenant_resolver_sdk::{BarrierMode, TenantRef, TenantStatus};
+
+    // 1
+    // ├── 2
+    // │   ├── 4 (self-managed)
+    // │   │   └── 5
+    // │   └── 6
+    // └── 3
+    async fn hierarchy() -> Service {
+        let svc = service(allow_all()).await;
+        create(&svc, 1, None, false).await;
+        create(&svc, 2, Some(1), false).await;
+        create(&svc, 3, Some(1), false).await;
+        create(&svc, 4, Some(2), true).await;
+        create(&svc, 5, Some(4), false).await;
+        create(&svc, 6, Some(2), false).await;
+        svc
+    }
+
+    fn ids(refs: &[TenantRef]) -> Vec<TenantId> {
+        refs.iter().map(|t| t.id).collect()
+    }
+
+    #[tokio::test]
+    async fn get_tenant_existing_and_missing() {
+        let svc = hierarchy().await;
+
+        let info = svc.get_tenant(&ctx(), id(4)).await.unwrap();
+        assert_eq!(info.name, "t4");
+        assert_eq!(info.parent_id, Some(id(2)));
+        assert!(info.self_managed);
+
+        let err = svc.get_tenant(&ctx(), id(99)).await.unwrap_err();
+        assert!(matches!(err, TenantResolverError::TenantNotFound { .. }));
+    }
+
+    #[tokio::test]
+    async fn get_tenants_dedups_skips_missing_and_filters() {
+        let svc = hierarchy().await;
+        svc.suspend_tenant(&ctx(), id(3)).await.unwrap();
+
+        let all = svc
+            .get_tenants(
+                &ctx(),
+                &[id(3), id(99), id(1), id(3)],
+                &GetTenantsOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            all.iter().map(|t| t.id).collect::<Vec<_>>(),
+            vec![id(3), id(1)]
+        );
+
+        let active = svc
+            .get_tenants(
+                &ctx(),
+                &[id(3), id(1)],
+                &GetTenantsOptions {
+                    status: vec![TenantStatus::Active],
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(active.iter().map(|t| t.id).collect::<Vec<_>>(), vec![id(1)]);
+    }
+
+    #[tokio::test]
+    async fn get_ancestors_respects_barriers() {
+        let svc = hierarchy().await;
+
+        let respect = svc
+            .get_ancestors(&ctx(), id(5), &GetAncestorsOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(respect.tenant.id, id(5));
+        assert_eq!(ids(&respect.ancestors), vec![id(4)]);
+
+        let ignore = svc
+            .get_ancestors(
+                &ctx(),
+                id(5),
+                &GetAncestorsOptions {
+                    barrier_mode: BarrierMode::Ignore,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(ids(&ignore.ancestors), vec![id(4), id(2), id(1)]);
+
+        // A self-managed tenant cannot see its own parent chain.
+        let barrier = svc
+            .get_ancestors(&ctx(), id(4), &GetAncestorsOptions::default())
+            .await
+            .unwrap();
+        assert!(barrier.ancestors.is_empty());
+
+        let root = svc
+            .get_ancestors(&ctx(), id(1), &GetAncestorsOptions::default())
+            .await
+            .unwrap();
+        assert!(root.ancestors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_descendants_pre_order_with_barriers() {
+        let svc = hierarchy().await;
+
+        let respect = svc
+            .get_descendants(&ctx(), id(1), &GetDescendantsOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(respect.tenant.id, id(1));
+        assert_eq!(ids(&respect.descendants), vec![id(2), id(6), id(3)]);
+
+        let ignore = svc
+            .get_descendants(
+                &ctx(),
+                id(1),
+                &GetDescendantsOptions {
+                    barrier_mode: BarrierMode::Ignore,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            ids(&ignore.descendants),
+            vec![id(2), id(4), id(5), id(6), id(3)]
+        );
+
+        // Starting at a self-managed tenant shows its own subtree.
+        let own = svc
+            .get_descendants(&ctx(), id(4), &GetDescendantsOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(ids(&own.descendants), vec![id(5)]);
+    }
+
+    #[tokio::test]
+    async fn get_descendants_max_depth_and_status() {
+        let svc = hierarchy().await;
+        svc.suspend_tenant(&ctx(), id(2)).await.unwrap();
+
+        let shallow = svc
+            .get_descendants(
+                &ctx(),
+                id(1),
+                &GetDescendantsOptions {
+                    max_depth: Some(1),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(ids(&shallow.descendants), vec![id(2), id(3)]);
+
+        // A filtered-out tenant hides its subtree; the start tenant is
+        // never filtered.
+        let active = GetDescendantsOptions {
+            status: vec![TenantStatus::Active],
+            ..Default::default()
+        };
+        let from_root = svc.get_descendants(&ctx(), id(1), &active).await.unwrap();
+        assert_eq!(ids(&from_root.descendants), vec![id(3)]);
+        let from_suspended = svc.get_descendants(&ctx(), id(2), &active).await.unwrap();
+        assert_eq!(from_suspended.tenant.id, id(2));
+        assert_eq!(ids(&from_suspended.descendants), vec![id(6)]);
+    }
+
+    #[tokio::test]
+    async fn is_ancestor_semantics() {
+        let svc = hierarchy().await;
+        let respect = IsAncestorOptions::default();
+        let ignore = IsAncestorOptions {
+            barrier_mode: BarrierMode::Ignore,
+        };
+
+        assert!(
+            svc.is_ancestor(&ctx(), id(1), id(6), &respect)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !svc.is_ancestor(&ctx(), id(6), id(1), &respect)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !svc.is_ancestor(&ctx(), id(1), id(1), &respect)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !svc.is_ancestor(&ctx(), id(3), id(6), &respect)
+                .await
+                .unwrap()
+        );
+
+        // The barrier hides 4 and everything below it from 2 and 1.
+        assert!(
+            !svc.is_ancestor(&ctx(), id(2), id(4), &respect)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !svc.is_ancestor(&ctx(), id(1), id(5), &respect)
+                .await
+                .unwrap()
+        );
+        assert!(
+            svc.is_ancestor(&ctx(), id(4), id(5), &respect)
+                .await
+                .unwrap()
+        );
+        assert!(
+            svc.is_ancestor(&ctx(), id(1), id(5), &ignore)
+                .await
+                .unwrap()
+        );
+
+        let err = svc
+            .is_ancestor(&ctx(), id(99), id(1), &respect)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TenantResolverError::TenantNotFound { .. }));
+        let err = svc
+            .is_ancestor(&ctx(), id(1), id(99), &respect)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TenantResolverError::TenantNotFound { .. }));
+    }
+}
@@ -0,0 +1,56 @@
+use modkit_db::DbError;
+use modkit_macros::domain_model;
+use tenant_resolver_sdk::{TenantId, TenantResolverError};
+
+#[domain_model]
+#[derive(Debug, thiserror::Error)]
+pub enum DomainError {
+    #[error("Tenant {0} not found")]
+    NotFound(TenantId),
+
+    #[error("Validation error: {0}")]
+    Validation(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Access forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Internal error: {0}")]
+    Internal(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] DbError),
+}
+
+impl DomainError {
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::Validation(message.into())
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::Conflict(message.into())
+    }
+}
+
+impl From<authz_resolver_sdk::EnforcerError> for DomainError {
+    fn from(e: authz_resolver_sdk::EnforcerError) -> Self {
+        tracing::error!(error = %e, "AuthZ scope resolution failed");
+        match e {
+            authz_resolver_sdk::EnforcerError::Denied { .. }
+            | authz_resolver_sdk::EnforcerError::CompileFailed(_) => Self::Forbidden(e.to_string()),
+            authz_resolver_sdk::EnforcerError::EvaluationFailed(_) => Self::Internal(e.to_string()),
+        }
+    }
+}
+
+impl From<DomainError> for TenantResolverError {
+    fn from(e: DomainError) -> Self {
+        match e {
+            DomainError::NotFound(tenant_id) => Self::TenantNotFound { tenant_id },
+            DomainError::Forbidden(_) => Self::Unauthorized,
+            other => Self::Internal(other.to_string()),
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! Domain layer for the database tenant resolver plugin.
+
+mod client;
+pub mod error;
+pub mod service;
+
+pub use error::DomainError;
+pub use service::{NewTenant, Service};
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+pub(crate) mod test_support;
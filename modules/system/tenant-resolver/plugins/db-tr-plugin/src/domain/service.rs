@@ -0,0 +1,721 @@
+//! Domain service for the database tenant resolver plugin.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use authz_resolver_sdk::PolicyEnforcer;
+use authz_resolver_sdk::pep::{AccessRequest, ResourceType};
+use modkit::client_hub::ClientHub;
+use modkit_db::{DBProvider, DbError};
+use modkit_macros::domain_model;
+use modkit_security::{AccessScope, SecurityContext, pep_properties};
+use tenant_resolver_sdk::{
+    BarrierMode, TenantHierarchyListener, TenantId, TenantInfo, TenantRef, TenantStatus,
+    matches_status,
+};
+use tracing::info;
+use uuid::Uuid;
+
+use super::error::DomainError;
+use crate::config::RootTenantConfig;
+use crate::infra::storage::entity::tenant;
+use crate::infra::storage::{
+    MoveOutcome, NewTenantRow, TenantRepo, is_unique_violation, status_str,
+};
+
+/// Authorization resource type for tenant management.
+///
+/// A tenant is its own owner tenant, so a grant on `owner_tenant_id` (or an
+/// `in_tenant_subtree` constraint, evaluated against the local closure
+/// table) selects the tenants the caller may manage.
+pub(crate) const TENANT_RESOURCE: ResourceType = ResourceType {
+    name: "tenant_resolver.tenant",
+    supported_properties: &[pep_properties::OWNER_TENANT_ID, pep_properties::RESOURCE_ID],
+};
+
+pub(crate) mod actions {
+    pub const CREATE: &str = "create";
+    pub const MOVE: &str = "move";
+    pub const SUSPEND: &str = "suspend";
+    pub const RESUME: &str = "resume";
+    pub const DELETE: &str = "delete";
+}
+
+const MAX_NAME_LEN: usize = 255;
+
+/// Tenant to create.
+#[domain_model]
+pub struct NewTenant {
+    /// Tenant ID; generated when `None`.
+    pub id: Option<TenantId>,
+    pub name: String,
+    pub tenant_type: Option<String>,
+    /// Parent tenant; `None` creates a root tenant.
+    pub parent_id: Option<TenantId>,
+    pub self_managed: bool,
+}
+
+/// Database tenant resolver service.
+///
+/// Serves the plugin API from the `tenants` and `tenant_closure` tables and
+/// implements tenant management. Management operations are authorized with
+/// the policy enforcer: the target tenant (and the parent, for create and
+/// move) must be visible under the scope granted for the action.
+///
+/// Committed changes are reported to the [`TenantHierarchyListener`]
+/// registered in `ClientHub` (the tenant resolver gateway), if any.
+#[domain_model]
+pub struct Service {
+    repo: TenantRepo,
+    policy_enforcer: PolicyEnforcer,
+    hub: Option<Arc<ClientHub>>,
+}
+
+impl Service {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>, policy_enforcer: PolicyEnforcer) -> Self {
+        Self {
+            repo: TenantRepo::new(db),
+            policy_enforcer,
+            hub: None,
+        }
+    }
+
+    /// Look up the hierarchy listener in `hub` on every change. It is
+    /// resolved lazily because the gateway may initialize after the plugin.
+    #[must_use]
+    pub fn with_client_hub(mut self, hub: Arc<ClientHub>) -> Self {
+        self.hub = Some(hub);
+        self
+    }
+
+    fn notify_changed(&self, ids: &[TenantId]) {
+        if let Some(listener) = self
+            .hub
+            .as_ref()
+            .and_then(|hub| hub.get::<dyn TenantHierarchyListener>().ok())
+        {
+            listener.tenants_changed(ids);
+        }
+    }
+
+    /// Create the configured root tenants that do not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError` if a root tenant is invalid or storage fails.
+    pub async fn ensure_roots(&self, roots: &[RootTenantConfig]) -> Result<(), DomainError> {
+        for root in roots {
+            if self.find(root.id).await?.is_some() {
+                continue;
+            }
+            validate_name(&root.name)?;
+            self.repo
+                .insert(NewTenantRow {
+                    id: root.id,
+                    name: root.name.clone(),
+                    tenant_type: root.tenant_type.clone(),
+                    parent_id: None,
+                    self_managed: false,
+                })
+                .await?;
+            info!(tenant_id = %root.id, "Created root tenant");
+        }
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // Management
+    // ------------------------------------------------------------------
+
+    /// Create a tenant below `new.parent_id`.
+    ///
+    /// Root tenants can only be created with an unconstrained grant.
+    ///
+    /// # Errors
+    ///
+    /// - `NotFound` if the parent does not exist or is not accessible
+    /// - `Validation` for an invalid name or type
+    /// - `Conflict` if the parent is deleted or the ID is taken
+    /// - `Forbidden` if the PDP denies the request
+    pub async fn create_tenant(
+        &self,
+        ctx: &SecurityContext,
+        new: NewTenant,
+    ) -> Result<TenantInfo, DomainError> {
+        validate_name(&new.name)?;
+        if new
+            .tenant_type
+            .as_ref()
+            .is_some_and(|t| t.len() > MAX_NAME_LEN)
+        {
+            return Err(DomainError::validation(format!(
+                "type must be at most {MAX_NAME_LEN} characters"
+            )));
+        }
+
+        if let Some(parent_id) = new.parent_id {
+            let parent = self.authorized(ctx, actions::CREATE, parent_id).await?;
+            ensure_not_deleted(&parent)?;
+        } else {
+            let scope = self
+                .policy_enforcer
+                .access_scope_with(
+                    ctx,
+                    &TENANT_RESOURCE,
+                    actions::CREATE,
+                    None,
+                    &AccessRequest::new().require_constraints(false),
+                )
+                .await?;
+            if !scope.is_unconstrained() {
+                return Err(DomainError::Forbidden(
+                    "creating a root tenant requires an unconstrained grant".to_owned(),
+                ));
+            }
+        }
+
+        let id = new.id.unwrap_or_else(Uuid::new_v4);
+        let model = self
+            .repo
+            .insert(NewTenantRow {
+                id,
+                name: new.name,
+                tenant_type: new.tenant_type,
+                parent_id: new.parent_id,
+                self_managed: new.self_managed,
+            })
+            .await
+            .map_err(|e| {
+                if is_unique_violation(&e) {
+                    DomainError::conflict(format!("tenant {id} already exists"))
+                } else {
+                    e.into()
+                }
+            })?;
+        info!(tenant_id = %id, parent_id = ?new.parent_id, "Created tenant");
+        self.notify_changed(&[id]);
+        Ok(TenantInfo::try_from(model)?)
+    }
+
+    /// Move a tenant, with its subtree, below `new_parent_id`.
+    ///
+    /// # Errors
+    ///
+    /// - `NotFound` if either tenant does not exist or is not accessible
+    /// - `Validation` if the new parent is the tenant or one of its descendants
+    /// - `Conflict` if either tenant is deleted
+    /// - `Forbidden` if the PDP denies the request
+    pub async fn move_tenant(
+        &self,
+        ctx: &SecurityContext,
+        id: TenantId,
+        new_parent_id: TenantId,
+    ) -> Result<TenantInfo, DomainError> {
+        if id == new_parent_id {
+            return Err(DomainError::validation("a tenant cannot be its own parent"));
+        }
+        let scope = self.access_scope(ctx, actions::MOVE, id).await?;
+        let parent_scope = self.access_scope(ctx, actions::MOVE, new_parent_id).await?;
+        let tenant = self.find_in(&scope, id).await?;
+        let parent = self.find_in(&parent_scope, new_parent_id).await?;
+        ensure_not_deleted(&tenant)?;
+        ensure_not_deleted(&parent)?;
+
+        if tenant.parent_id == Some(new_parent_id) {
+            return Ok(TenantInfo::try_from(tenant)?);
+        }
+        // Checked again on the locked rows, in the transaction of the move.
+        let moved = match self
+            .repo
+            .move_subtree(id, scope, new_parent_id, parent_scope)
+            .await?
+        {
+            MoveOutcome::Moved(moved) => moved,
+            MoveOutcome::NotFound(id) => return Err(DomainError::NotFound(id)),
+            MoveOutcome::Deleted(id) => {
+                return Err(DomainError::conflict(format!("tenant {id} is deleted")));
+            }
+            MoveOutcome::Cycle => {
+                return Err(DomainError::validation(
+                    "a tenant cannot be moved below its own descendant",
+                ));
+            }
+        };
+        info!(tenant_id = %id, parent_id = %new_parent_id, "Moved tenant");
+        self.notify_changed(&[id]);
+        Ok(TenantInfo::try_from(moved)?)
+    }
+
+    /// Suspend an active tenant. Suspending a suspended tenant is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// - `NotFound` if the tenant does not exist or is not accessible
+    /// - `Conflict` if the tenant is deleted
+    /// - `Forbidden` if the PDP denies the request
+    pub async fn suspend_tenant(
+        &self,
+        ctx: &SecurityContext,
+        id: TenantId,
+    ) -> Result<TenantInfo, DomainError> {
+        self.change_status(ctx, actions::SUSPEND, id, TenantStatus::Suspended)
+            .await
+    }
+
+    /// Reactivate a suspended tenant. Resuming an active tenant is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// - `NotFound` if the tenant does not exist or is not accessible
+    /// - `Conflict` if the tenant is deleted
+    /// - `Forbidden` if the PDP denies the request
+    pub async fn resume_tenant(
+        &self,
+        ctx: &SecurityContext,
+        id: TenantId,
+    ) -> Result<TenantInfo, DomainError> {
+        self.change_status(ctx, actions::RESUME, id, TenantStatus::Active)
+            .await
+    }
+
+    /// Mark a tenant deleted. The row is kept (soft delete); deleting a
+    /// deleted tenant is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// - `NotFound` if the tenant does not exist or is not accessible
+    /// - `Conflict` if the tenant still has children that are not deleted
+    /// - `Forbidden` if the PDP denies the request
+    pub async fn delete_tenant(
+        &self,
+        ctx: &SecurityContext,
+        id: TenantId,
+    ) -> Result<(), DomainError> {
+        let tenant = self.authorized(ctx, actions::DELETE, id).await?;
+        if is_deleted(&tenant) {
+            return Ok(());
+        }
+        if self.repo.has_live_children(id).await? {
+            return Err(DomainError::conflict(
+                "tenant has children that are not deleted",
+            ));
+        }
+        self.repo.set_status(id, TenantStatus::Deleted).await?;
+        info!(tenant_id = %id, "Deleted tenant");
+        self.notify_changed(&[id]);
+        Ok(())
+    }
+
+    async fn change_status(
+        &self,
+        ctx: &SecurityContext,
+        action: &str,
+        id: TenantId,
+        status: TenantStatus,
+    ) -> Result<TenantInfo, DomainError> {
+        let tenant = self.authorized(ctx, action, id).await?;
+        ensure_not_deleted(&tenant)?;
+        let mut info = TenantInfo::try_from(tenant)?;
+        if info.status != status {
+            self.repo.set_status(id, status).await?;
+            info!(tenant_id = %id, ?status, "Changed tenant status");
+            self.notify_changed(&[id]);
+            info.status = status;
+        }
+        Ok(info)
+    }
+
+    /// The tenant `id`, if the caller may perform `action` on it.
+    ///
+    /// Tenants outside the granted scope are reported as not found.
+    async fn authorized(
+        &self,
+        ctx: &SecurityContext,
+        action: &str,
+        id: TenantId,
+    ) -> Result<tenant::Model, DomainError> {
+        let scope = self.access_scope(ctx, action, id).await?;
+        self.find_in(&scope, id).await
+    }
+
+    /// The PDP scope of `action` on tenant `id`.
+    async fn access_scope(
+        &self,
+        ctx: &SecurityContext,
+        action: &str,
+        id: TenantId,
+    ) -> Result<AccessScope, DomainError> {
+        Ok(self
+            .policy_enforcer
+            .access_scope_with(
+                ctx,
+                &TENANT_RESOURCE,
+                action,
+                Some(id),
+                &AccessRequest::new()
+                    .require_constraints(false)
+                    .resource_property(pep_properties::OWNER_TENANT_ID, id),
+            )
+            .await?)
+    }
+
+    async fn find_in(
+        &self,
+        scope: &AccessScope,
+        id: TenantId,
+    ) -> Result<tenant::Model, DomainError> {
+        self.repo
+            .find(scope, id)
+            .await?
+            .ok_or(DomainError::NotFound(id))
+    }
+
+    // ------------------------------------------------------------------
+    // Hierarchy queries (plugin API)
+    // ------------------------------------------------------------------
+
+    pub(super) async fn find(&self, id: TenantId) -> Result<Option<TenantInfo>, DomainError> {
+        self.repo
+            .find(&AccessScope::allow_all(), id)
+            .await?
+            .map(TenantInfo::try_from)
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    pub(super) async fn get(&self, id: TenantId) -> Result<TenantInfo, DomainError> {
+        self.find(id).await?.ok_or(DomainError::NotFound(id))
+    }
+
+    /// Tenants among `ids` matching `statuses`, in request order, without
+    /// duplicates; missing IDs are skipped.
+    pub(super) async fn get_many(
+        &self,
+        ids: &[TenantId],
+        statuses: &[TenantStatus],
+    ) -> Result<Vec<TenantInfo>, DomainError> {
+        let mut seen = HashSet::new();
+        let unique: Vec<TenantId> = ids.iter().copied().filter(|id| seen.insert(*id)).collect();
+        let mut by_id = HashMap::new();
+        for model in self.repo.find_many(&unique).await? {
+            by_id.insert(model.id, TenantInfo::try_from(model)?);
+        }
+        Ok(unique
+            .iter()
+            .filter_map(|id| by_id.remove(id))
+            .filter(|tenant| matches_status(tenant, statuses))
+            .collect())
+    }
+
+    /// Ancestors of `tenant`, from its parent to the root.
+    ///
+    /// With `BarrierMode::Respect` the walk includes the first self-managed
+    /// ancestor and stops there; a self-managed `tenant` has no visible
+    /// ancestors.
+    pub(super) async fn ancestors(
+        &self,
+        tenant: &TenantInfo,
+        barrier_mode: BarrierMode,
+    ) -> Result<Vec<TenantRef>, DomainError> {
+        let mut by_id = HashMap::new();
+        for model in self
+            .repo
+            .ancestors(tenant.id, barrier_mode == BarrierMode::Respect)
+            .await?
+        {
+            by_id.insert(model.id, TenantRef::try_from(model)?);
+        }
+
+        let mut ancestors = Vec::with_capacity(by_id.len());
+        let mut next = tenant.parent_id;
+        while let Some(parent) = next.and_then(|id| by_id.remove(&id)) {
+            next = parent.parent_id;
+            ancestors.push(parent);
+        }
+        Ok(ancestors)
+    }
+
+    /// Descendants of `tenant` in pre-order, children ordered by name.
+    ///
+    /// A child failing the status filter is skipped with its subtree, as is
+    /// a self-managed child under `BarrierMode::Respect`.
+    pub(super) async fn descendants(
+        &self,
+        tenant: &TenantInfo,
+        statuses: &[TenantStatus],
+        barrier_mode: BarrierMode,
+        max_depth: Option<u32>,
+    ) -> Result<Vec<TenantRef>, DomainError> {
+        // Rows come ordered by name, so each child list is too.
+        let mut children: HashMap<TenantId, Vec<TenantRef>> = HashMap::new();
+        for model in self
+            .repo
+            .descendants(tenant.id, barrier_mode == BarrierMode::Respect)
+            .await?
+        {
+            let child = TenantRef::try_from(model)?;
+            if let Some(parent_id) = child.parent_id {
+                children.entry(parent_id).or_default().push(child);
+            }
+        }
+
+        let mut result = Vec::new();
+        let mut stack: Vec<(TenantRef, u32)> = children
+            .remove(&tenant.id)
+            .unwrap_or_default()
+            .into_iter()
+            .rev()
+            .map(|child| (child, 1))
+            .collect();
+        while let Some((node, depth)) = stack.pop() {
+            if max_depth.is_some_and(|max| depth > max) || !matches_status(&node, statuses) {
+                continue;
+            }
+            if let Some(grandchildren) = children.remove(&node.id) {
+                stack.extend(grandchildren.into_iter().rev().map(|c| (c, depth + 1)));
+            }
+            result.push(node);
+        }
+        Ok(result)
+    }
+
+    /// Whether `ancestor_id` is a strict ancestor of `descendant_id`.
+    ///
+    /// With `BarrierMode::Respect` a self-managed tenant on the path (the
+    /// descendant included, the ancestor excluded) hides the relation.
+    pub(super) async fn is_ancestor_of(
+        &self,
+        ancestor_id: TenantId,
+        descendant_id: TenantId,
+        barrier_mode: BarrierMode,
+    ) -> Result<bool, DomainError> {
+        self.get(ancestor_id).await?;
+        if ancestor_id == descendant_id {
+            return Ok(false);
+        }
+        self.get(descendant_id).await?;
+        Ok(self
+            .repo
+            .closure(ancestor_id, descendant_id)
+            .await?
+            .is_some_and(|row| barrier_mode == BarrierMode::Ignore || row.barrier == 0))
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), DomainError> {
+    if name.trim().is_empty() {
+        return Err(DomainError::validation("name must not be empty"));
+    }
+    if name.len() > MAX_NAME_LEN {
+        return Err(DomainError::validation(format!(
+            "name must be at most {MAX_NAME_LEN} characters"
+        )));
+    }
+    Ok(())
+}
+
+fn is_deleted(tenant: &tenant::Model) -> bool {
+    tenant.status == status_str(TenantStatus::Deleted)
+}
+
+fn ensure_not_deleted(tenant: &tenant::Model) -> Result<(), DomainError> {
+    if is_deleted(tenant) {
+        return Err(DomainError::conflict(format!(
+            "tenant {} is deleted",
+            tenant.id
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::domain::test_support::{allow_all, create, ctx, deny_all, id, service, subtree_of};
+
+    fn new_tenant(n: u128, parent: Option<u128>) -> NewTenant {
+        NewTenant {
+            id: Some(id(n)),
+            name: format!("t{n}"),
+            tenant_type: None,
+            parent_id: parent.map(id),
+            self_managed: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn create_root_and_child() {
+        let svc = service(allow_all()).await;
+        let root = svc
+            .create_tenant(&ctx(), new_tenant(1, None))
+            .await
+            .unwrap();
+        assert_eq!(root.status, TenantStatus::Active);
+        assert_eq!(root.parent_id, None);
+
+        let child = svc
+            .create_tenant(&ctx(), new_tenant(2, Some(1)))
+            .await
+            .unwrap();
+        assert_eq!(child.parent_id, Some(id(1)));
+    }
+
+    #[tokio::test]
+    async fn create_rejects_invalid_and_duplicate() {
+        let svc = service(allow_all()).await;
+        create(&svc, 1, None, false).await;
+
+        let mut blank = new_tenant(2, Some(1));
+        blank.name = "  ".to_owned();
+        let err = svc.create_tenant(&ctx(), blank).await.unwrap_err();
+        assert!(matches!(err, DomainError::Validation(_)));
+
+        let err = svc
+            .create_tenant(&ctx(), new_tenant(1, None))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Conflict(_)));
+
+        let err = svc
+            .create_tenant(&ctx(), new_tenant(3, Some(99)))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn denied_requests_are_forbidden() {
+        let svc = service(deny_all()).await;
+        let err = svc
+            .create_tenant(&ctx(), new_tenant(1, None))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Forbidden(_)));
+    }
+
+    #[tokio::test]
+    async fn subtree_grant_limits_management() {
+        let db = crate::infra::storage::test_provider().await;
+        let admin = Service::new(db.clone(), allow_all());
+        create(&admin, 1, None, false).await;
+        create(&admin, 2, Some(1), false).await;
+        create(&admin, 3, Some(1), false).await;
+
+        let scoped = Service::new(db, subtree_of(id(2)));
+        scoped
+            .create_tenant(&ctx(), new_tenant(4, Some(2)))
+            .await
+            .unwrap();
+        scoped.suspend_tenant(&ctx(), id(4)).await.unwrap();
+
+        // Tenants outside the subtree are invisible.
+        let err = scoped.suspend_tenant(&ctx(), id(3)).await.unwrap_err();
+        assert!(matches!(err, DomainError::NotFound(_)));
+        let err = scoped.move_tenant(&ctx(), id(4), id(3)).await.unwrap_err();
+        assert!(matches!(err, DomainError::NotFound(_)));
+
+        // Root creation needs an unconstrained grant.
+        let err = scoped
+            .create_tenant(&ctx(), new_tenant(5, None))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Forbidden(_)));
+    }
+
+    #[tokio::test]
+    async fn move_rejects_cycles_and_updates_hierarchy() {
+        let svc = service(allow_all()).await;
+        create(&svc, 1, None, false).await;
+        create(&svc, 2, Some(1), false).await;
+        create(&svc, 3, Some(2), false).await;
+        create(&svc, 4, Some(1), false).await;
+
+        let err = svc.move_tenant(&ctx(), id(2), id(3)).await.unwrap_err();
+        assert!(matches!(err, DomainError::Validation(_)));
+        let err = svc.move_tenant(&ctx(), id(2), id(2)).await.unwrap_err();
+        assert!(matches!(err, DomainError::Validation(_)));
+
+        let moved = svc.move_tenant(&ctx(), id(2), id(4)).await.unwrap();
+        assert_eq!(moved.parent_id, Some(id(4)));
+        assert!(
+            svc.is_ancestor_of(id(4), id(3), BarrierMode::Respect)
+                .await
+                .unwrap()
+        );
+        let tenant = svc.get(id(3)).await.unwrap();
+        let ancestors = svc.ancestors(&tenant, BarrierMode::Respect).await.unwrap();
+        assert_eq!(
+            ancestors.iter().map(|t| t.id).collect::<Vec<_>>(),
+            vec![id(2), id(4), id(1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn status_changes_and_delete() {
+        let svc = service(allow_all()).await;
+        create(&svc, 1, None, false).await;
+        create(&svc, 2, Some(1), false).await;
+
+        let suspended = svc.suspend_tenant(&ctx(), id(2)).await.unwrap();
+        assert_eq!(suspended.status, TenantStatus::Suspended);
+        let resumed = svc.resume_tenant(&ctx(), id(2)).await.unwrap();
+        assert_eq!(resumed.status, TenantStatus::Active);
+
+        let err = svc.delete_tenant(&ctx(), id(1)).await.unwrap_err();
+        assert!(matches!(err, DomainError::Conflict(_)));
+
+        svc.delete_tenant(&ctx(), id(2)).await.unwrap();
+        svc.delete_tenant(&ctx(), id(2)).await.unwrap();
+        assert_eq!(svc.get(id(2)).await.unwrap().status, TenantStatus::Deleted);
+        let err = svc.resume_tenant(&ctx(), id(2)).await.unwrap_err();
+        assert!(matches!(err, DomainError::Conflict(_)));
+
+        // Deleted children no longer block deleting the parent.
+        svc.delete_tenant(&ctx(), id(1)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn committed_changes_notify_the_hierarchy_listener() {
+        #[derive(Default)]
+        struct Recorder(std::sync::Mutex<Vec<TenantId>>);
+
+        impl TenantHierarchyListener for Recorder {
+            fn tenants_changed(&self, ids: &[TenantId]) {
+                self.0.lock().unwrap().extend_from_slice(ids);
+            }
+        }
+
+        let hub = Arc::new(ClientHub::new());
+        let recorder = Arc::new(Recorder::default());
+        hub.register::<dyn TenantHierarchyListener>(recorder.clone());
+        let svc = service(allow_all()).await.with_client_hub(hub);
+        create(&svc, 1, None, false).await;
+        create(&svc, 2, Some(1), false).await;
+        create(&svc, 3, Some(1), false).await;
+        recorder.0.lock().unwrap().clear();
+
+        svc.move_tenant(&ctx(), id(3), id(2)).await.unwrap();
+        svc.suspend_tenant(&ctx(), id(3)).await.unwrap();
+        // No-op and rejected changes are not reported.
+        svc.suspend_tenant(&ctx(), id(3)).await.unwrap();
+        svc.move_tenant(&ctx(), id(2), id(3)).await.unwrap_err();
+        svc.delete_tenant(&ctx(), id(3)).await.unwrap();
+
+        assert_eq!(*recorder.0.lock().unwrap(), vec![id(3), id(3), id(3)]);
+    }
+
+    #[tokio::test]
+    async fn ensure_roots_is_idempotent() {
+        let svc = service(allow_all()).await;
+        let roots = vec![RootTenantConfig {
+            id: id(1),
+            name: "Root".to_owned(),
+            tenant_type: None,
+        }];
+        svc.ensure_roots(&roots).await.unwrap();
+        svc.ensure_roots(&roots).await.unwrap();
+        assert_eq!(svc.get(id(1)).await.unwrap().name, "Root");
+    }
+}
@@ -0,0 +1,102 @@
+//! Shared test infrastructure for domain-layer unit tests.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use authz_resolver_sdk::constraints::{Constraint, InTenantSubtreePredicate, Predicate};
+use authz_resolver_sdk::models::{
+    EvaluationRequest, EvaluationResponse, EvaluationResponseContext,
+};
+use authz_resolver_sdk::{AuthZResolverClient, AuthZResolverError, Capability, PolicyEnforcer};
+use modkit_security::{SecurityContext, pep_properties};
+use tenant_resolver_sdk::TenantId;
+use uuid::Uuid;
+
+use super::{NewTenant, Service};
+use crate::infra::storage::test_provider;
+
+/// Mock `AuthZ` resolver returning a fixed decision and constraints.
+struct MockAuthZResolver {
+    decision: bool,
+    constraints: Vec<Constraint>,
+}
+
+#[async_trait]
+impl AuthZResolverClient for MockAuthZResolver {
+    async fn evaluate(
+        &self,
+        _request: EvaluationRequest,
+    ) -> Result<EvaluationResponse, AuthZResolverError> {
+        Ok(EvaluationResponse {
+            decision: self.decision,
+            context: EvaluationResponseContext {
+                constraints: self.constraints.clone(),
+                deny_reason: None,
+            },
+        })
+    }
+}
+
+fn enforcer(decision: bool, constraints: Vec<Constraint>) -> PolicyEnforcer {
+    PolicyEnforcer::new(Arc::new(MockAuthZResolver {
+        decision,
+        constraints,
+    }))
+    .with_capabilities(vec![Capability::TenantHierarchy])
+}
+
+/// Grants every action without constraints.
+pub fn allow_all() -> PolicyEnforcer {
+    enforcer(true, Vec::new())
+}
+
+/// Denies every action.
+pub fn deny_all() -> PolicyEnforcer {
+    enforcer(false, Vec::new())
+}
+
+/// Grants every action on the subtree of `root`.
+pub fn subtree_of(root: TenantId) -> PolicyEnforcer {
+    enforcer(
+        true,
+        vec![Constraint {
+            predicates: vec![Predicate::InTenantSubtree(InTenantSubtreePredicate::new(
+                pep_properties::OWNER_TENANT_ID,
+                root,
+            ))],
+        }],
+    )
+}
+
+/// Service over a fresh in-memory database.
+pub async fn service(enforcer: PolicyEnforcer) -> Service {
+    Service::new(test_provider().await, enforcer)
+}
+
+pub fn ctx() -> SecurityContext {
+    SecurityContext::builder()
+        .subject_id(Uuid::from_u128(0xA))
+        .subject_tenant_id(Uuid::from_u128(0x1))
+        .build()
+        .unwrap()
+}
+
+pub fn id(n: u128) -> TenantId {
+    Uuid::from_u128(n)
+}
+
+/// Create tenant `n` below `parent` (a root when `None`).
+pub async fn create(svc: &Service, n: u128, parent: Option<u128>, self_managed: bool) {
+    svc.create_tenant(
+        &ctx(),
+        NewTenant {
+            id: Some(id(n)),
+            name: format!("t{n}"),
+            tenant_type: None,
+            parent_id: parent.map(id),
+            self_managed,
+        },
+    )
+    .await
+    .unwrap();
+}
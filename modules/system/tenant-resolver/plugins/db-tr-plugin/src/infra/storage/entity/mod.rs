@@ -0,0 +1,2 @@
+pub mod tenant;
+pub mod tenant_closure;
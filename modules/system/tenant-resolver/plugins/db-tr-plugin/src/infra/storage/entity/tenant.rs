@@ -0,0 +1,27 @@
+use modkit_db_macros::Scopable;
+use sea_orm::entity::prelude::*;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// A tenant; its owner tenant is the tenant itself, so `AuthZ` constraints
+/// on `owner_tenant_id` select the tenants a caller may manage.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Scopable)]
+#[sea_orm(table_name = "tenants")]
+#[secure(tenant_col = "id", resource_col = "id", no_owner, no_type)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub name: String,
+    /// `TenantStatus` in `snake_case`.
+    pub status: String,
+    pub tenant_type: Option<String>,
+    pub parent_id: Option<Uuid>,
+    pub self_managed: bool,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
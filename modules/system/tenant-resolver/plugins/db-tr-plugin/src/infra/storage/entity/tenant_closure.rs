@@ -0,0 +1,25 @@
+use modkit_db_macros::Scopable;
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+/// Row of the `tenant_closure` projection (see
+/// `modkit_db::secure::projections`).
+///
+/// `barrier` is 1 when a self-managed tenant lies on the path from the
+/// ancestor (exclusive) to the descendant (inclusive).
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Scopable)]
+#[sea_orm(table_name = "tenant_closure")]
+#[secure(unrestricted)]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub ancestor_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub descendant_id: Uuid,
+    pub barrier: i32,
+    pub descendant_status: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,104 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The runner applies migrations in name order, which puts this one
+        // before the projection migration; create `tenant_closure` first so
+        // its index below has a table to attach to.
+        modkit_db::secure::projections::migration()
+            .up(manager)
+            .await?;
+
+        let backend = manager.get_database_backend();
+        let conn = manager.get_connection();
+
+        let statements = match backend {
+            sea_orm::DatabaseBackend::Postgres => POSTGRES_UP,
+            sea_orm::DatabaseBackend::MySql => MYSQL_UP,
+            sea_orm::DatabaseBackend::Sqlite => SQLITE_UP,
+        };
+
+        // One statement per call: MySQL rejects multi-statement strings.
+        for sql in statements {
+            conn.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS tenants")
+            .await?;
+        Ok(())
+    }
+}
+
+const POSTGRES_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS tenants (
+    id            UUID PRIMARY KEY NOT NULL,
+    name          VARCHAR(255) NOT NULL,
+    status        VARCHAR(32) NOT NULL,
+    tenant_type   VARCHAR(255),
+    parent_id     UUID,
+    self_managed  BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at    TIMESTAMPTZ NOT NULL,
+    updated_at    TIMESTAMPTZ NOT NULL
+)
+",
+    r"
+CREATE INDEX IF NOT EXISTS idx_tenants_parent_id ON tenants (parent_id)
+",
+    r"
+CREATE INDEX IF NOT EXISTS idx_tenant_closure_descendant
+    ON tenant_closure (descendant_id, ancestor_id)
+",
+];
+
+const MYSQL_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS tenants (
+    id            VARCHAR(36) PRIMARY KEY NOT NULL,
+    name          VARCHAR(255) NOT NULL,
+    status        VARCHAR(32) NOT NULL,
+    tenant_type   VARCHAR(255) NULL,
+    parent_id     VARCHAR(36) NULL,
+    self_managed  BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at    TIMESTAMP(6) NOT NULL,
+    updated_at    TIMESTAMP(6) NOT NULL,
+    KEY idx_tenants_parent_id (parent_id)
+)
+",
+    r"
+CREATE INDEX idx_tenant_closure_descendant
+    ON tenant_closure (descendant_id, ancestor_id)
+",
+];
+
+const SQLITE_UP: &[&str] = &[
+    r"
+CREATE TABLE IF NOT EXISTS tenants (
+    id            TEXT PRIMARY KEY NOT NULL,
+    name          TEXT NOT NULL,
+    status        TEXT NOT NULL,
+    tenant_type   TEXT,
+    parent_id     TEXT,
+    self_managed  INTEGER NOT NULL DEFAULT 0,
+    created_at    TEXT NOT NULL,
+    updated_at    TEXT NOT NULL
+)
+",
+    r"
+CREATE INDEX IF NOT EXISTS idx_tenants_parent_id ON tenants (parent_id)
+",
+    r"
+CREATE INDEX IF NOT EXISTS idx_tenant_closure_descendant
+    ON tenant_closure (descendant_id, ancestor_id)
+",
+];
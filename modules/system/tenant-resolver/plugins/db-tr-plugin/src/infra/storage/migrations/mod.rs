@@ -0,0 +1,17 @@
+use sea_orm_migration::prelude::*;
+
+pub mod initial_001;
+
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        // `tenant_closure` is the shared projection table, so resources stored
+        // in this database can use `in_tenant_subtree` constraints directly.
+        vec![
+            modkit_db::secure::projections::migration(),
+            Box::new(initial_001::Migration),
+        ]
+    }
+}
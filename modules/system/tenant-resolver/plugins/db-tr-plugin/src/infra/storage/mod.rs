@@ -0,0 +1,96 @@
+//! SQL storage of the tenant hierarchy on top of the `modkit-db` secure ORM.
+//!
+//! `tenants` holds one row per tenant; `tenant_closure` holds one row per
+//! (ancestor, descendant) pair, self-rows included, with the barrier flag and
+//! the descendant status denormalized for hierarchy queries.
+
+pub mod entity;
+pub mod migrations;
+mod tenant_repo;
+
+pub use tenant_repo::{MoveOutcome, NewTenantRow, TenantRepo};
+
+use modkit_db::DbError;
+use modkit_db::secure::ScopeError;
+use tenant_resolver_sdk::{TenantInfo, TenantRef, TenantStatus};
+
+/// Stored form of a status; matches its `snake_case` serialization.
+#[must_use]
+pub fn status_str(status: TenantStatus) -> &'static str {
+    match status {
+        TenantStatus::Active => "active",
+        TenantStatus::Suspended => "suspended",
+        TenantStatus::Deleted => "deleted",
+    }
+}
+
+fn parse_status(value: &str) -> Result<TenantStatus, DbError> {
+    match value {
+        "active" => Ok(TenantStatus::Active),
+        "suspended" => Ok(TenantStatus::Suspended),
+        "deleted" => Ok(TenantStatus::Deleted),
+        other => Err(DbError::Other(anyhow::anyhow!(
+            "unknown tenant status '{other}'"
+        ))),
+    }
+}
+
+impl TryFrom<entity::tenant::Model> for TenantInfo {
+    type Error = DbError;
+
+    fn try_from(model: entity::tenant::Model) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: model.id,
+            name: model.name,
+            status: parse_status(&model.status)?,
+            tenant_type: model.tenant_type,
+            parent_id: model.parent_id,
+            self_managed: model.self_managed,
+        })
+    }
+}
+
+impl TryFrom<entity::tenant::Model> for TenantRef {
+    type Error = DbError;
+
+    fn try_from(model: entity::tenant::Model) -> Result<Self, Self::Error> {
+        TenantInfo::try_from(model).map(Into::into)
+    }
+}
+
+/// `true` if `e` is a unique-index violation, i.e. the row already exists.
+#[must_use]
+pub fn is_unique_violation(e: &DbError) -> bool {
+    let db_err = match e {
+        DbError::Sea(db) => Some(db),
+        DbError::Other(other) => match other.downcast_ref::<ScopeError>() {
+            Some(ScopeError::Db(db)) => Some(db),
+            _ => None,
+        },
+        _ => None,
+    };
+    matches!(
+        db_err.and_then(sea_orm::DbErr::sql_err),
+        Some(sea_orm::SqlErr::UniqueConstraintViolation(_))
+    )
+}
+
+#[cfg(test)]
+pub(crate) async fn test_provider() -> modkit_db::DBProvider<DbError> {
+    use modkit_db::migration_runner::run_migrations_for_testing;
+    use modkit_db::{ConnectOpts, connect_db};
+    use sea_orm_migration::MigratorTrait;
+
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        min_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db("sqlite::memory:", opts)
+        .await
+        .expect("connect in-memory database");
+    run_migrations_for_testing(&db, migrations::Migrator::migrations())
+        .await
+        .expect("run migrations");
+    modkit_db::DBProvider::new(db)
+}
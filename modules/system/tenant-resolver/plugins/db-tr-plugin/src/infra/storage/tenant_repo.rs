@@ -0,0 +1,583 @@
+use modkit_db::secure::{
+    DBRunner, SecureDeleteExt, SecureEntityExt, SecureUpdateExt, secure_insert,
+};
+use modkit_db::{DBProvider, DbError};
+use modkit_security::AccessScope;
+use sea_orm::sea_query::{Expr, Query, SelectStatement};
+use sea_orm::{ColumnTrait, Condition, EntityTrait, Order, Set};
+use tenant_resolver_sdk::{TenantId, TenantStatus};
+use time::OffsetDateTime;
+
+use super::entity::{tenant, tenant_closure};
+use super::status_str;
+
+/// Tenant to insert.
+pub struct NewTenantRow {
+    pub id: TenantId,
+    pub name: String,
+    pub tenant_type: Option<String>,
+    pub parent_id: Option<TenantId>,
+    pub self_managed: bool,
+}
+
+/// Result of [`TenantRepo::move_subtree`].
+pub enum MoveOutcome {
+    /// The tenant, now below its new parent.
+    Moved(tenant::Model),
+    /// The tenant or the new parent does not exist within its scope.
+    NotFound(TenantId),
+    /// The tenant or the new parent is deleted.
+    Deleted(TenantId),
+    /// The new parent is inside the subtree.
+    Cycle,
+}
+
+/// Tenants and their closure table in the module database.
+///
+/// Reads of the hierarchy go through `tenant_closure`, so none of them walk
+/// the tree row by row. Every write keeps both tables consistent inside one
+/// transaction.
+pub struct TenantRepo {
+    db: DBProvider<DbError>,
+}
+
+impl TenantRepo {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>) -> Self {
+        Self { db }
+    }
+
+    /// The tenant `id`, if it exists within `scope`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn find(
+        &self,
+        scope: &AccessScope,
+        id: TenantId,
+    ) -> Result<Option<tenant::Model>, DbError> {
+        let conn = self.db.conn()?;
+        find_tenant(&conn, scope, id).await
+    }
+
+    /// The tenants among `ids` (unordered).
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn find_many(&self, ids: &[TenantId]) -> Result<Vec<tenant::Model>, DbError> {
+        let conn = self.db.conn()?;
+        Ok(tenant::Entity::find()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .filter(Condition::all().add(tenant::Column::Id.is_in(ids.iter().copied())))
+            .all(&conn)
+            .await?)
+    }
+
+    /// Strict ancestors of `id` (unordered). With `respect_barriers`, only
+    /// ancestors reachable without crossing a self-managed tenant.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn ancestors(
+        &self,
+        id: TenantId,
+        respect_barriers: bool,
+    ) -> Result<Vec<tenant::Model>, DbError> {
+        let mut query = Query::select();
+        query
+            .column(tenant_closure::Column::AncestorId)
+            .from(tenant_closure::Entity)
+            .and_where(tenant_closure::Column::DescendantId.eq(id))
+            .and_where(tenant_closure::Column::AncestorId.ne(id));
+        self.tenants_in(query, respect_barriers).await
+    }
+
+    /// Strict descendants of `id` (unordered). With `respect_barriers`,
+    /// self-managed tenants and their subtrees are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn descendants(
+        &self,
+        id: TenantId,
+        respect_barriers: bool,
+    ) -> Result<Vec<tenant::Model>, DbError> {
+        let mut query = Query::select();
+        query
+            .column(tenant_closure::Column::DescendantId)
+            .from(tenant_closure::Entity)
+            .and_where(tenant_closure::Column::AncestorId.eq(id))
+            .and_where(tenant_closure::Column::DescendantId.ne(id));
+        self.tenants_in(query, respect_barriers).await
+    }
+
+    async fn tenants_in(
+        &self,
+        mut ids: SelectStatement,
+        respect_barriers: bool,
+    ) -> Result<Vec<tenant::Model>, DbError> {
+        if respect_barriers {
+            ids.and_where(tenant_closure::Column::Barrier.eq(0));
+        }
+        let conn = self.db.conn()?;
+        Ok(tenant::Entity::find()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .filter(Condition::all().add(Expr::col(tenant::Column::Id).in_subquery(ids)))
+            .order_by(tenant::Column::Name, Order::Asc)
+            .order_by(tenant::Column::Id, Order::Asc)
+            .all(&conn)
+            .await?)
+    }
+
+    /// The closure row linking `ancestor_id` to `descendant_id`, if the
+    /// first is an ancestor of (or equal to) the second.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn closure(
+        &self,
+        ancestor_id: TenantId,
+        descendant_id: TenantId,
+    ) -> Result<Option<tenant_closure::Model>, DbError> {
+        let conn = self.db.conn()?;
+        Ok(tenant_closure::Entity::find()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .filter(
+                Condition::all()
+                    .add(tenant_closure::Column::AncestorId.eq(ancestor_id))
+                    .add(tenant_closure::Column::DescendantId.eq(descendant_id)),
+            )
+            .one(&conn)
+            .await?)
+    }
+
+    /// Whether `id` has a child that is not deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if the query fails.
+    pub async fn has_live_children(&self, id: TenantId) -> Result<bool, DbError> {
+        let conn = self.db.conn()?;
+        let child = tenant::Entity::find()
+            .secure()
+            .scope_with(&AccessScope::allow_all())
+            .filter(
+                Condition::all()
+                    .add(tenant::Column::ParentId.eq(id))
+                    .add(tenant::Column::Status.ne(status_str(TenantStatus::Deleted))),
+            )
+            .one(&conn)
+            .await?;
+        Ok(child.is_some())
+    }
+
+    /// Insert an active tenant below its parent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if a query fails; a unique violation means the
+    /// tenant id is taken.
+    pub async fn insert(&self, row: NewTenantRow) -> Result<tenant::Model, DbError> {
+        self.db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let all = AccessScope::allow_all();
+                    let now = OffsetDateTime::now_utc();
+                    let status = status_str(TenantStatus::Active);
+                    let am = tenant::ActiveModel {
+                        id: Set(row.id),
+                        name: Set(row.name),
+                        status: Set(status.to_owned()),
+                        tenant_type: Set(row.tenant_type),
+                        parent_id: Set(row.parent_id),
+                        self_managed: Set(row.self_managed),
+                        created_at: Set(now),
+                        updated_at: Set(now),
+                    };
+                    let model = secure_insert::<tenant::Entity>(am, &all, tx).await?;
+
+                    let own = tenant_closure::Model {
+                        ancestor_id: row.id,
+                        descendant_id: row.id,
+                        barrier: 0,
+                        descendant_status: status.to_owned(),
+                    };
+                    if let Some(parent_id) = row.parent_id {
+                        link_subtree(tx, parent_id, row.self_managed, std::slice::from_ref(&own))
+                            .await?;
+                    }
+                    insert_closure(tx, own).await?;
+                    Ok(model)
+                })
+            })
+            .await
+    }
+
+    /// Re-parent `id` (with its subtree) below `new_parent_id`.
+    ///
+    /// Changes nothing unless both tenants exist within their scopes
+    /// (`scope` for `id`, `parent_scope` for `new_parent_id`), neither is
+    /// deleted and `new_parent_id` is outside the subtree. The checks run in
+    /// the same transaction as the move, with the tenant and every ancestor
+    /// of the new parent locked, so a concurrent move, delete or scope change
+    /// cannot slip in between.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if a query fails.
+    pub async fn move_subtree(
+        &self,
+        id: TenantId,
+        scope: AccessScope,
+        new_parent_id: TenantId,
+        parent_scope: AccessScope,
+    ) -> Result<MoveOutcome, DbError> {
+        self.db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let all = AccessScope::allow_all();
+                    let path: Vec<TenantId> = closure_rows(
+                        tx,
+                        Condition::all()
+                            .add(tenant_closure::Column::DescendantId.eq(new_parent_id)),
+                    )
+                    .await?
+                    .into_iter()
+                    .map(|row| row.ancestor_id)
+                    .collect();
+                    // Locked in id order so concurrent moves cannot deadlock.
+                    tenant::Entity::find()
+                        .secure()
+                        .scope_with(&all)
+                        .filter(
+                            Condition::all()
+                                .add(tenant::Column::Id.is_in(path.iter().copied().chain([id]))),
+                        )
+                        .order_by(tenant::Column::Id, Order::Asc)
+                        .lock_exclusive()
+                        .all(tx)
+                        .await?;
+
+                    let in_subtree = closure_rows(
+                        tx,
+                        Condition::all()
+                            .add(tenant_closure::Column::AncestorId.eq(id))
+                            .add(tenant_closure::Column::DescendantId.eq(new_parent_id)),
+                    )
+                    .await?;
+                    let Some(tenant) = find_tenant(tx, &scope, id).await? else {
+                        return Ok(MoveOutcome::NotFound(id));
+                    };
+                    let Some(parent) = find_tenant(tx, &parent_scope, new_parent_id).await? else {
+                        return Ok(MoveOutcome::NotFound(new_parent_id));
+                    };
+                    let deleted = status_str(TenantStatus::Deleted);
+                    if let Some(row) = [&tenant, &parent].into_iter().find(|t| t.status == deleted)
+                    {
+                        return Ok(MoveOutcome::Deleted(row.id));
+                    }
+                    if !in_subtree.is_empty() {
+                        return Ok(MoveOutcome::Cycle);
+                    }
+
+                    let subtree = closure_rows(
+                        tx,
+                        Condition::all().add(tenant_closure::Column::AncestorId.eq(id)),
+                    )
+                    .await?;
+                    let old_ancestors: Vec<TenantId> = closure_rows(
+                        tx,
+                        Condition::all()
+                            .add(tenant_closure::Column::DescendantId.eq(id))
+                            .add(tenant_closure::Column::AncestorId.ne(id)),
+                    )
+                    .await?
+                    .into_iter()
+                    .map(|row| row.ancestor_id)
+                    .collect();
+
+                    if !old_ancestors.is_empty() {
+                        tenant_closure::Entity::delete_many()
+                            .secure()
+                            .scope_with(&all)
+                            .filter(
+                                Condition::all()
+                                    .add(tenant_closure::Column::AncestorId.is_in(old_ancestors))
+                                    .add(
+                                        tenant_closure::Column::DescendantId
+                                            .is_in(subtree.iter().map(|row| row.descendant_id)),
+                                    ),
+                            )
+                            .exec(tx)
+                            .await?;
+                    }
+                    link_subtree(tx, new_parent_id, tenant.self_managed, &subtree).await?;
+
+                    let now = OffsetDateTime::now_utc();
+                    tenant::Entity::update_many()
+                        .secure()
+                        .col_expr(tenant::Column::ParentId, Expr::value(new_parent_id))
+                        .col_expr(tenant::Column::UpdatedAt, Expr::value(now))
+                        .filter(Condition::all().add(tenant::Column::Id.eq(id)))
+                        .scope_with(&all)
+                        .exec(tx)
+                        .await?;
+                    Ok(MoveOutcome::Moved(tenant::Model {
+                        parent_id: Some(new_parent_id),
+                        updated_at: now,
+                        ..tenant
+                    }))
+                })
+            })
+            .await
+    }
+
+    /// Set the status of `id`, in the tenant row and in the closure table.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DbError` if a query fails.
+    pub async fn set_status(&self, id: TenantId, status: TenantStatus) -> Result<(), DbError> {
+        self.db
+            .transaction(move |tx| {
+                Box::pin(async move {
+                    let all = AccessScope::allow_all();
+                    let status = status_str(status);
+                    tenant::Entity::update_many()
+                        .secure()
+                        .col_expr(tenant::Column::Status, Expr::value(status))
+                        .col_expr(
+                            tenant::Column::UpdatedAt,
+                            Expr::value(OffsetDateTime::now_utc()),
+                        )
+                        .filter(Condition::all().add(tenant::Column::Id.eq(id)))
+                        .scope_with(&all)
+                        .exec(tx)
+                        .await?;
+                    tenant_closure::Entity::update_many()
+                        .secure()
+                        .col_expr(
+                            tenant_closure::Column::DescendantStatus,
+                            Expr::value(status),
+                        )
+                        .filter(Condition::all().add(tenant_closure::Column::DescendantId.eq(id)))
+                        .scope_with(&all)
+                        .exec(tx)
+                        .await?;
+                    Ok(())
+                })
+            })
+            .await
+    }
+}
+
+async fn find_tenant(
+    runner: &impl DBRunner,
+    scope: &AccessScope,
+    id: TenantId,
+) -> Result<Option<tenant::Model>, DbError> {
+    Ok(tenant::Entity::find()
+        .secure()
+        .scope_with(scope)
+        .filter(Condition::all().add(tenant::Column::Id.eq(id)))
+        .one(runner)
+        .await?)
+}
+
+async fn closure_rows(
+    runner: &impl DBRunner,
+    condition: Condition,
+) -> Result<Vec<tenant_closure::Model>, DbError> {
+    Ok(tenant_closure::Entity::find()
+        .secure()
+        .scope_with(&AccessScope::allow_all())
+        .filter(condition)
+        .all(runner)
+        .await?)
+}
+
+async fn insert_closure(runner: &impl DBRunner, row: tenant_closure::Model) -> Result<(), DbError> {
+    let am = tenant_closure::ActiveModel {
+        ancestor_id: Set(row.ancestor_id),
+        descendant_id: Set(row.descendant_id),
+        barrier: Set(row.barrier),
+        descendant_status: Set(row.descendant_status),
+    };
+    secure_insert::<tenant_closure::Entity>(am, &AccessScope::allow_all(), runner).await?;
+    Ok(())
+}
+
+/// Link a subtree below `parent_id`: every ancestor of the parent (the
+/// parent included) gets a row for every subtree member.
+///
+/// `subtree` holds the rows of the subtree root to each member; the root is
+/// self-managed if `root_self_managed`.
+async fn link_subtree(
+    runner: &impl DBRunner,
+    parent_id: TenantId,
+    root_self_managed: bool,
+    subtree: &[tenant_closure::Model],
+) -> Result<(), DbError> {
+    let ancestors = closure_rows(
+        runner,
+        Condition::all().add(tenant_closure::Column::DescendantId.eq(parent_id)),
+    )
+    .await?;
+    for ancestor in &ancestors {
+        for member in subtree {
+            // Path ancestor -> member = (ancestor, parent] + (parent, root] + (root, member]
+            let barrier = ancestor.barrier | i32::from(root_self_managed) | member.barrier;
+            insert_closure(
+                runner,
+                tenant_closure::Model {
+                    ancestor_id: ancestor.ancestor_id,
+                    descendant_id: member.descendant_id,
+                    barrier,
+                    descendant_status: member.descendant_status.clone(),
+                },
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::infra::storage::test_provider;
+    use uuid::Uuid;
+
+    fn id(n: u128) -> TenantId {
+        Uuid::from_u128(n)
+    }
+
+    async fn insert(repo: &TenantRepo, n: u128, parent: Option<u128>, self_managed: bool) {
+        repo.insert(NewTenantRow {
+            id: id(n),
+            name: format!("t{n}"),
+            tenant_type: None,
+            parent_id: parent.map(id),
+            self_managed,
+        })
+        .await
+        .unwrap();
+    }
+
+    async fn barrier(repo: &TenantRepo, a: u128, d: u128) -> Option<i32> {
+        repo.closure(id(a), id(d))
+            .await
+            .unwrap()
+            .map(|row| row.barrier)
+    }
+
+    #[tokio::test]
+    async fn insert_maintains_closure_and_barriers() {
+        let repo = TenantRepo::new(test_provider().await);
+        insert(&repo, 1, None, false).await;
+        insert(&repo, 2, Some(1), true).await;
+        insert(&repo, 3, Some(2), false).await;
+
+        assert_eq!(barrier(&repo, 1, 1).await, Some(0));
+        assert_eq!(barrier(&repo, 2, 2).await, Some(0));
+        assert_eq!(barrier(&repo, 1, 2).await, Some(1));
+        assert_eq!(barrier(&repo, 2, 3).await, Some(0));
+        assert_eq!(barrier(&repo, 1, 3).await, Some(1));
+        assert_eq!(barrier(&repo, 3, 1).await, None);
+    }
+
+    #[tokio::test]
+    async fn move_subtree_relinks_closure() {
+        let repo = TenantRepo::new(test_provider().await);
+        insert(&repo, 1, None, false).await;
+        insert(&repo, 2, Some(1), false).await;
+        insert(&repo, 3, Some(2), false).await;
+        insert(&repo, 4, Some(3), false).await;
+        insert(&repo, 5, Some(1), true).await;
+
+        let all = AccessScope::allow_all;
+        let MoveOutcome::Moved(moved) =
+            repo.move_subtree(id(3), all(), id(5), all()).await.unwrap()
+        else {
+            panic!("subtree not moved");
+        };
+        assert_eq!(moved.parent_id, Some(id(5)));
+
+        // Links to the old parent are gone; the subtree is intact.
+        assert_eq!(barrier(&repo, 2, 3).await, None);
+        assert_eq!(barrier(&repo, 2, 4).await, None);
+        assert_eq!(barrier(&repo, 3, 4).await, Some(0));
+        // The new parent sees the subtree; the root only across the barrier.
+        assert_eq!(barrier(&repo, 5, 3).await, Some(0));
+        assert_eq!(barrier(&repo, 5, 4).await, Some(0));
+        assert_eq!(barrier(&repo, 1, 3).await, Some(1));
+        assert_eq!(barrier(&repo, 1, 4).await, Some(1));
+    }
+
+    #[tokio::test]
+    async fn move_subtree_refuses_to_create_a_cycle() {
+        let repo = TenantRepo::new(test_provider().await);
+        insert(&repo, 1, None, false).await;
+        insert(&repo, 2, Some(1), false).await;
+        insert(&repo, 3, Some(2), false).await;
+
+        let all = AccessScope::allow_all;
+        assert!(matches!(
+            repo.move_subtree(id(2), all(), id(3), all()).await.unwrap(),
+            MoveOutcome::Cycle
+        ));
+        assert_eq!(barrier(&repo, 2, 3).await, Some(0));
+        assert_eq!(barrier(&repo, 3, 2).await, None);
+    }
+
+    #[tokio::test]
+    async fn move_subtree_rechecks_scopes_and_status_under_lock() {
+        let repo = TenantRepo::new(test_provider().await);
+        insert(&repo, 1, None, false).await;
+        insert(&repo, 2, Some(1), false).await;
+        insert(&repo, 3, Some(1), false).await;
+        let all = AccessScope::allow_all;
+
+        // Authorized before the move, out of scope by the time rows are locked.
+        assert!(matches!(
+            repo.move_subtree(id(3), all(), id(2), AccessScope::deny_all())
+                .await
+                .unwrap(),
+            MoveOutcome::NotFound(parent) if parent == id(2)
+        ));
+        // Deleted between the service's check and the move.
+        repo.set_status(id(2), TenantStatus::Deleted).await.unwrap();
+        assert!(matches!(
+            repo.move_subtree(id(3), all(), id(2), all()).await.unwrap(),
+            MoveOutcome::Deleted(parent) if parent == id(2)
+        ));
+        assert_eq!(barrier(&repo, 2, 3).await, None);
+        assert_eq!(barrier(&repo, 1, 3).await, Some(0));
+    }
+
+    #[tokio::test]
+    async fn set_status_updates_closure_rows() {
+        let repo = TenantRepo::new(test_provider().await);
+        insert(&repo, 1, None, false).await;
+        insert(&repo, 2, Some(1), false).await;
+
+        repo.set_status(id(2), TenantStatus::Suspended)
+            .await
+            .unwrap();
+        let row = repo.closure(id(1), id(2)).await.unwrap().unwrap();
+        assert_eq!(row.descendant_status, "suspended");
+        assert!(repo.has_live_children(id(1)).await.unwrap());
+
+        repo.set_status(id(2), TenantStatus::Deleted).await.unwrap();
+        assert!(!repo.has_live_children(id(1)).await.unwrap());
+    }
+}
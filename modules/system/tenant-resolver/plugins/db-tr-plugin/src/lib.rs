@@ -0,0 +1,32 @@
+//! Database Tenant Resolver Plugin
+//!
+//! This plugin persists tenants in the module database and serves the
+//! hierarchy from a closure table, so ancestor, descendant and ancestry
+//! queries never walk the tree row by row. It also exposes a REST API to
+//! create, move, suspend, resume and delete tenants.
+//!
+//! ## Configuration
+//!
+//! ```yaml
+//! modules:
+//!   db-tr-plugin:
+//!     database:
+//!       server: "sqlite_users"
+//!       file: "tenants.db"
+//!     config:
+//!       vendor: "hyperspot"
+//!       priority: 50
+//!       root_tenants:
+//!         - id: "550e8400-e29b-41d4-a716-446655440001"
+//!           name: "Root Tenant"
+//! ```
+
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod api;
+pub mod config;
+pub mod domain;
+pub mod infra;
+pub mod module;
+
+pub use module::DbTrPlugin;
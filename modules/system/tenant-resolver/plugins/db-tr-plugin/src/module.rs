@@ -0,0 +1,126 @@
+//! Database tenant resolver plugin module.
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use authz_resolver_sdk::{AuthZResolverClient, Capability, PolicyEnforcer};
+use axum::Router;
+use modkit::Module;
+use modkit::api::OpenApiRegistry;
+use modkit::client_hub::ClientScope;
+use modkit::context::ModuleCtx;
+use modkit::gts::BaseModkitPluginV1;
+use tenant_resolver_sdk::{TenantResolverPluginClient, TenantResolverPluginSpecV1};
+use tracing::info;
+use types_registry_sdk::{RegisterResult, TypesRegistryClient};
+
+use crate::api::rest::routes;
+use crate::config::DbTrPluginConfig;
+use crate::domain::Service;
+
+/// Database tenant resolver plugin module.
+///
+/// Serves the tenant hierarchy from the module database and exposes the
+/// tenant management REST API. Registration follows the plugin pattern of
+/// the static plugin (instance in types-registry, scoped client in
+/// `ClientHub`).
+#[modkit::module(
+    name = "db-tr-plugin",
+    deps = ["types-registry", "authz-resolver"],
+    capabilities = [db, rest]
+)]
+pub struct DbTrPlugin {
+    service: OnceLock<Arc<Service>>,
+}
+
+impl Default for DbTrPlugin {
+    fn default() -> Self {
+        Self {
+            service: OnceLock::new(),
+        }
+    }
+}
+
+impl modkit::contracts::DatabaseCapability for DbTrPlugin {
+    fn migrations(&self) -> Vec<Box<dyn sea_orm_migration::MigrationTrait>> {
+        use sea_orm_migration::MigratorTrait;
+        crate::infra::storage::migrations::Migrator::migrations()
+    }
+}
+
+#[async_trait]
+impl Module for DbTrPlugin {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        // Load configuration
+        let cfg: DbTrPluginConfig = ctx.config()?;
+        info!(
+            vendor = %cfg.vendor,
+            priority = cfg.priority,
+            root_tenants = cfg.root_tenants.len(),
+            "Loaded plugin configuration"
+        );
+
+        // The plugin owns `tenant_closure`, so subtree constraints can be
+        // evaluated locally.
+        let authz = ctx
+            .client_hub()
+            .get::<dyn AuthZResolverClient>()
+            .map_err(|e| anyhow::anyhow!("failed to get AuthZ resolver: {e}"))?;
+        let policy_enforcer =
+            PolicyEnforcer::new(authz).with_capabilities(vec![Capability::TenantHierarchy]);
+
+        let service = Arc::new(
+            Service::new(ctx.db_required()?, policy_enforcer).with_client_hub(ctx.client_hub()),
+        );
+        service.ensure_roots(&cfg.root_tenants).await?;
+
+        // Generate plugin instance ID
+        let instance_id = TenantResolverPluginSpecV1::gts_make_instance_id(
+            "hyperspot.builtin.db_tenant_resolver.plugin.v1",
+        );
+
+        // Register plugin instance in types-registry
+        let registry = ctx.client_hub().get::<dyn TypesRegistryClient>()?;
+        let instance = BaseModkitPluginV1::<TenantResolverPluginSpecV1> {
+            id: instance_id.clone(),
+            vendor: cfg.vendor.clone(),
+            priority: cfg.priority,
+            properties: TenantResolverPluginSpecV1,
+        };
+        let instance_json = serde_json::to_value(&instance)?;
+
+        let results = registry.register(vec![instance_json]).await?;
+        RegisterResult::ensure_all_ok(&results)?;
+
+        self.service
+            .set(service.clone())
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
+        // Register scoped client in ClientHub
+        let api: Arc<dyn TenantResolverPluginClient> = service;
+        ctx.client_hub()
+            .register_scoped::<dyn TenantResolverPluginClient>(
+                ClientScope::gts_id(&instance_id),
+                api,
+            );
+
+        info!(instance_id = %instance_id);
+        Ok(())
+    }
+}
+
+impl modkit::contracts::RestApiCapability for DbTrPlugin {
+    fn register_rest(
+        &self,
+        _ctx: &ModuleCtx,
+        router: Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> anyhow::Result<Router> {
+        let service = self
+            .service
+            .get()
+            .ok_or_else(|| anyhow::anyhow!("Service not initialized"))?
+            .clone();
+        Ok(routes::register_routes(router, openapi, service))
+    }
+}
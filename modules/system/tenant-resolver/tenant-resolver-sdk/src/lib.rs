@@ -4,6 +4,7 @@
 //!
 //! - [`TenantResolverClient`] - Public API trait for consumers
 //! - [`TenantResolverPluginClient`] - Plugin API trait for implementations
+//! - [`TenantHierarchyListener`] - Hierarchy change notifications from plugins
 //! - [`TenantInfo`], [`TenantStatus`] - Domain models
 //! - [`TenantResolverError`] - Error types
 //! - [`TenantResolverPluginSpecV1`] - GTS schema for plugin discovery
@@ -52,4 +53,4 @@ pub use models::{
     GetDescendantsResponse, GetTenantsOptions, HasStatus, IsAncestorOptions, TenantId, TenantInfo,
     TenantRef, TenantStatus, matches_status,
 };
-pub use plugin_api::TenantResolverPluginClient;
+pub use plugin_api::{TenantHierarchyListener, TenantResolverPluginClient};
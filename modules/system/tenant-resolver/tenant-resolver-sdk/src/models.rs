@@ -134,7 +134,7 @@ impl HasStatus for TenantRef {
 /// // Ignore barriers (traverse through self-managed tenants)
 /// let mode = BarrierMode::Ignore;
 /// ```
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BarrierMode {
     /// Respect all barriers - stop traversal at barrier boundaries (default).
     #[default]
@@ -158,7 +158,7 @@ pub enum BarrierMode {
 ///     barrier_mode: BarrierMode::Ignore,
 /// };
 /// ```
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct GetAncestorsOptions {
     /// How to handle barriers during traversal.
     pub barrier_mode: BarrierMode,
@@ -179,7 +179,7 @@ pub struct GetAncestorsOptions {
 ///     status: vec![TenantStatus::Active],
 /// };
 /// ```
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct GetTenantsOptions {
     /// Filter by tenant status. Empty means all statuses are included.
     #[serde(default)]
@@ -203,7 +203,7 @@ pub struct GetTenantsOptions {
 ///     max_depth: Some(2),
 /// };
 /// ```
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct GetDescendantsOptions {
     /// Filter descendants by status. Empty means all statuses are included.
     /// Does NOT apply to the starting tenant.
@@ -230,7 +230,7 @@ pub struct GetDescendantsOptions {
 ///     barrier_mode: BarrierMode::Ignore,
 /// };
 /// ```
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct IsAncestorOptions {
     /// How to handle barriers during traversal.
     pub barrier_mode: BarrierMode,
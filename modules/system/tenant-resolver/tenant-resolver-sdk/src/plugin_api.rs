@@ -142,3 +142,13 @@ pub trait TenantResolverPluginClient: Send + Sync {
         options: &IsAncestorOptions,
     ) -> Result<bool, TenantResolverError>;
 }
+
+/// Hierarchy change notifications from plugins to the gateway.
+///
+/// The gateway registers this trait in `ClientHub`. A plugin that changes
+/// tenants (create, move, status change, delete) calls it once the change is
+/// committed, so cached lookups are not served until they expire.
+pub trait TenantHierarchyListener: Send + Sync {
+    /// The position or status of `ids` in the hierarchy changed.
+    fn tenants_changed(&self, ids: &[TenantId]);
+}
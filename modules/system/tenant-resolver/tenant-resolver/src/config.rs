@@ -1,5 +1,6 @@
 //! Configuration for the tenant resolver module.
 
+use modkit::cache::CacheConfig;
 use serde::Deserialize;
 
 /// Module configuration.
@@ -11,12 +12,18 @@ pub struct TenantResolverConfig {
     /// The module queries types-registry for plugin instances matching
     /// this vendor and selects the one with lowest priority.
     pub vendor: String,
+
+    /// Caching of plugin responses, per caller (disabled by default).
+    ///
+    /// Cached hierarchy data may lag behind tenant changes by up to the TTL.
+    pub cache: CacheConfig,
 }
 
 impl Default for TenantResolverConfig {
     fn default() -> Self {
         Self {
             vendor: "hyperspot".to_owned(),
+            cache: CacheConfig::default(),
         }
     }
 }
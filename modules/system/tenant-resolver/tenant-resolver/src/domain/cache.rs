@@ -0,0 +1,45 @@
+//! Per-caller cache of plugin responses.
+//!
+//! Every key embeds the caller's [`SubjectKey`], so a response computed for
+//! one subject or tenant is never served to another. Errors are not cached.
+
+use modkit::cache::{Cache, CacheConfig, SubjectKey};
+use tenant_resolver_sdk::{
+    GetAncestorsOptions, GetAncestorsResponse, GetDescendantsOptions, GetDescendantsResponse,
+    GetTenantsOptions, IsAncestorOptions, TenantId, TenantInfo,
+};
+
+type TenantsKey = (SubjectKey, Vec<TenantId>, GetTenantsOptions);
+type IsAncestorKey = (SubjectKey, TenantId, TenantId, IsAncestorOptions);
+
+/// One cache per plugin call.
+pub struct ResolverCache {
+    pub tenant: Cache<(SubjectKey, TenantId), TenantInfo>,
+    pub tenants: Cache<TenantsKey, Vec<TenantInfo>>,
+    pub ancestors: Cache<(SubjectKey, TenantId, GetAncestorsOptions), GetAncestorsResponse>,
+    pub descendants: Cache<(SubjectKey, TenantId, GetDescendantsOptions), GetDescendantsResponse>,
+    pub is_ancestor: Cache<IsAncestorKey, bool>,
+}
+
+impl ResolverCache {
+    /// Build the caches described by `cfg`, or `None` if caching is disabled.
+    #[must_use]
+    pub fn from_config(cfg: &CacheConfig) -> Option<Self> {
+        Some(Self {
+            tenant: Cache::from_config("tenant-resolver.get_tenant", cfg)?,
+            tenants: Cache::from_config("tenant-resolver.get_tenants", cfg)?,
+            ancestors: Cache::from_config("tenant-resolver.get_ancestors", cfg)?,
+            descendants: Cache::from_config("tenant-resolver.get_descendants", cfg)?,
+            is_ancestor: Cache::from_config("tenant-resolver.is_ancestor", cfg)?,
+        })
+    }
+
+    /// Drop every cached response, e.g. after the tenant hierarchy changed.
+    pub fn clear(&self) {
+        self.tenant.clear();
+        self.tenants.clear();
+        self.ancestors.clear();
+        self.descendants.clear();
+        self.is_ancestor.clear();
+    }
+}
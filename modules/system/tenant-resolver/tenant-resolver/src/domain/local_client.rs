@@ -7,8 +7,8 @@ use modkit_macros::domain_model;
 use modkit_security::SecurityContext;
 use tenant_resolver_sdk::{
     GetAncestorsOptions, GetAncestorsResponse, GetDescendantsOptions, GetDescendantsResponse,
-    GetTenantsOptions, IsAncestorOptions, TenantId, TenantInfo, TenantResolverClient,
-    TenantResolverError,
+    GetTenantsOptions, IsAncestorOptions, TenantHierarchyListener, TenantId, TenantInfo,
+    TenantResolverClient, TenantResolverError,
 };
 
 use super::{DomainError, Service};
@@ -95,3 +95,13 @@ impl TenantResolverClient for TenantResolverLocalClient {
             .map_err(|e| log_and_convert("is_ancestor", e))
     }
 }
+
+impl TenantHierarchyListener for TenantResolverLocalClient {
+    fn tenants_changed(&self, ids: &[TenantId]) {
+        tracing::debug!(
+            tenants = ids.len(),
+            "Tenant hierarchy changed; clearing cache"
+        );
+        self.svc.invalidate_cache();
+    }
+}
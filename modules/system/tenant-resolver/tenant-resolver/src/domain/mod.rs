@@ -1,5 +1,6 @@
 //! Domain layer for the tenant resolver.
 
+pub mod cache;
 pub mod error;
 pub mod local_client;
 pub mod service;
@@ -3,9 +3,12 @@
 //! Plugin discovery is lazy: resolved on first API call after
 //! types-registry is ready.
 
+use std::future::Future;
+use std::hash::Hash;
 use std::sync::Arc;
 use std::time::Duration;
 
+use modkit::cache::{Cache, CacheConfig, SubjectKey};
 use modkit::client_hub::{ClientHub, ClientScope};
 use modkit::plugins::{GtsPluginSelector, choose_plugin_instance};
 use modkit::telemetry::ThrottledLog;
@@ -19,6 +22,7 @@ use tenant_resolver_sdk::{
 use tracing::info;
 use types_registry_sdk::{ListQuery, TypesRegistryClient};
 
+use super::cache::ResolverCache;
 use super::error::DomainError;
 
 /// Throttle interval for unavailable plugin warnings.
@@ -35,6 +39,11 @@ const UNAVAILABLE_LOG_THROTTLE: Duration = Duration::from_secs(10);
 /// which is responsible for deciding how (or whether) to enforce
 /// authorization. This is intentional — different plugins may have
 /// different access-control semantics.
+///
+/// # Caching
+///
+/// With `cache.enabled`, plugin responses are cached per caller (subject,
+/// subject tenant and token scopes) for the configured TTL.
 #[domain_model]
 pub struct Service {
     hub: Arc<ClientHub>,
@@ -43,17 +52,31 @@ pub struct Service {
     selector: GtsPluginSelector,
     /// Throttle for plugin unavailable warnings.
     unavailable_log_throttle: ThrottledLog,
+    /// Plugin response cache; `None` when disabled.
+    cache: Option<ResolverCache>,
 }
 
 impl Service {
     /// Creates a new service with lazy plugin resolution.
     #[must_use]
-    pub fn new(hub: Arc<ClientHub>, vendor: String) -> Self {
+    pub fn new(hub: Arc<ClientHub>, vendor: String, cache: &CacheConfig) -> Self {
         Self {
             hub,
             vendor,
             selector: GtsPluginSelector::new(),
             unavailable_log_throttle: ThrottledLog::new(UNAVAILABLE_LOG_THROTTLE),
+            cache: ResolverCache::from_config(cache),
+        }
+    }
+
+    /// Drop all cached plugin responses.
+    ///
+    /// Called when a plugin reports a hierarchy change: moves and status
+    /// changes affect the ancestors and descendants of every tenant in the
+    /// subtree, so entries are not invalidated selectively.
+    pub fn invalidate_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
         }
     }
 
@@ -126,8 +149,16 @@ impl Service {
         ctx: &SecurityContext,
         id: TenantId,
     ) -> Result<TenantInfo, DomainError> {
-        let plugin = self.get_plugin().await?;
-        plugin.get_tenant(ctx, id).await.map_err(DomainError::from)
+        let cache = self.cache.as_ref().map(|c| &c.tenant);
+        cached(
+            cache,
+            || (SubjectKey::from(ctx), id),
+            || async {
+                let plugin = self.get_plugin().await?;
+                plugin.get_tenant(ctx, id).await.map_err(DomainError::from)
+            },
+        )
+        .await
     }
 
     /// Get multiple tenants by IDs (batch).
@@ -144,11 +175,16 @@ impl Service {
         ids: &[TenantId],
         options: &GetTenantsOptions,
     ) -> Result<Vec<TenantInfo>, DomainError> {
-        let plugin = self.get_plugin().await?;
-        plugin
-            .get_tenants(ctx, ids, options)
-            .await
-            .map_err(DomainError::from)
+        let cache = self.cache.as_ref().map(|c| &c.tenants);
+        let key = || (SubjectKey::from(ctx), ids.to_vec(), options.clone());
+        cached(cache, key, || async {
+            let plugin = self.get_plugin().await?;
+            plugin
+                .get_tenants(ctx, ids, options)
+                .await
+                .map_err(DomainError::from)
+        })
+        .await
     }
 
     /// Get ancestor chain from tenant to root.
@@ -164,11 +200,16 @@ impl Service {
         id: TenantId,
         options: &GetAncestorsOptions,
     ) -> Result<GetAncestorsResponse, DomainError> {
-        let plugin = self.get_plugin().await?;
-        plugin
-            .get_ancestors(ctx, id, options)
-            .await
-            .map_err(DomainError::from)
+        let cache = self.cache.as_ref().map(|c| &c.ancestors);
+        let key = || (SubjectKey::from(ctx), id, options.clone());
+        cached(cache, key, || async {
+            let plugin = self.get_plugin().await?;
+            plugin
+                .get_ancestors(ctx, id, options)
+                .await
+                .map_err(DomainError::from)
+        })
+        .await
     }
 
     /// Get descendants subtree of the given tenant.
@@ -184,11 +225,16 @@ impl Service {
         id: TenantId,
         options: &GetDescendantsOptions,
     ) -> Result<GetDescendantsResponse, DomainError> {
-        let plugin = self.get_plugin().await?;
-        plugin
-            .get_descendants(ctx, id, options)
-            .await
-            .map_err(DomainError::from)
+        let cache = self.cache.as_ref().map(|c| &c.descendants);
+        let key = || (SubjectKey::from(ctx), id, options.clone());
+        cached(cache, key, || async {
+            let plugin = self.get_plugin().await?;
+            plugin
+                .get_descendants(ctx, id, options)
+                .await
+                .map_err(DomainError::from)
+        })
+        .await
     }
 
     /// Check if `ancestor_id` is an ancestor of `descendant_id`.
@@ -205,10 +251,39 @@ impl Service {
         descendant_id: TenantId,
         options: &IsAncestorOptions,
     ) -> Result<bool, DomainError> {
-        let plugin = self.get_plugin().await?;
-        plugin
-            .is_ancestor(ctx, ancestor_id, descendant_id, options)
-            .await
-            .map_err(DomainError::from)
+        let cache = self.cache.as_ref().map(|c| &c.is_ancestor);
+        let key = || {
+            (
+                SubjectKey::from(ctx),
+                ancestor_id,
+                descendant_id,
+                options.clone(),
+            )
+        };
+        cached(cache, key, || async {
+            let plugin = self.get_plugin().await?;
+            plugin
+                .is_ancestor(ctx, ancestor_id, descendant_id, options)
+                .await
+                .map_err(DomainError::from)
+        })
+        .await
+    }
+}
+
+/// Runs `load` through `cache` when caching is enabled.
+async fn cached<K, V, Fut>(
+    cache: Option<&Cache<K, V>>,
+    key: impl FnOnce() -> K,
+    load: impl FnOnce() -> Fut,
+) -> Result<V, DomainError>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    Fut: Future<Output = Result<V, DomainError>>,
+{
+    match cache {
+        Some(cache) => cache.get_or_try_insert_with(key(), load).await,
+        None => load().await,
     }
 }
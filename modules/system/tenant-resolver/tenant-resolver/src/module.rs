@@ -6,7 +6,9 @@ use async_trait::async_trait;
 use modkit::Module;
 use modkit::context::ModuleCtx;
 use modkit::contracts::SystemCapability;
-use tenant_resolver_sdk::{TenantResolverClient, TenantResolverPluginSpecV1};
+use tenant_resolver_sdk::{
+    TenantHierarchyListener, TenantResolverClient, TenantResolverPluginSpecV1,
+};
 use tracing::info;
 use types_registry_sdk::{RegisterResult, TypesRegistryClient};
 
@@ -75,14 +77,19 @@ impl Module for TenantResolver {
 
         // Create service
         let hub = ctx.client_hub();
-        let svc = Arc::new(Service::new(hub, cfg.vendor));
+        let svc = Arc::new(Service::new(hub, cfg.vendor, &cfg.cache));
         self.service
             .set(svc.clone())
             .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
 
-        // Register local client in ClientHub
-        let api: Arc<dyn TenantResolverClient> = Arc::new(TenantResolverLocalClient::new(svc));
+        // Register local client in ClientHub; plugins report hierarchy
+        // changes through the same client.
+        let client = Arc::new(TenantResolverLocalClient::new(svc));
+        let api: Arc<dyn TenantResolverClient> = client.clone();
         ctx.client_hub().register::<dyn TenantResolverClient>(api);
+        let listener: Arc<dyn TenantHierarchyListener> = client;
+        ctx.client_hub()
+            .register::<dyn TenantHierarchyListener>(listener);
 
         Ok(())
     }